@@ -0,0 +1,63 @@
+use serde::Deserialize;
+
+use crate::types::HumanDuration;
+
+/// Alert pipeline settings applied before sink dispatch.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertConfig {
+    /// Drop alerts whose `(rule_name, entity_type, entity_id)` was already
+    /// dispatched within this window. Unset disables suppression.
+    #[serde(default)]
+    pub suppress_ttl: Option<HumanDuration>,
+    /// Round alert scores to this many decimal places. Unset keeps the raw
+    /// `f64`.
+    #[serde(default)]
+    pub score_precision: Option<u32>,
+    /// Capacity of the bounded channel between rule tasks and the alert
+    /// dispatcher.
+    #[serde(default = "default_channel_capacity")]
+    pub channel_capacity: usize,
+    /// What a rule task does when the alert channel is full (sink stalled).
+    #[serde(default)]
+    pub on_full: AlertOverflowPolicy,
+    /// Also send alerts at or above a rule priority to a dedicated target.
+    #[serde(default)]
+    pub priority_route: Option<PriorityRoute>,
+}
+
+/// `[alert.priority_route]`: alerts whose rule `priority >= min` are
+/// dispatched a second time to the sinks routed for `target`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PriorityRoute {
+    pub min: i64,
+    pub target: String,
+}
+
+fn default_channel_capacity() -> usize {
+    64
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            suppress_ttl: None,
+            score_precision: None,
+            channel_capacity: default_channel_capacity(),
+            on_full: AlertOverflowPolicy::default(),
+            priority_route: None,
+        }
+    }
+}
+
+/// Backpressure policy for a full alert channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertOverflowPolicy {
+    /// Wait for capacity: the rule task stops consuming windows until the
+    /// sink catches up. No alert is lost.
+    #[default]
+    Block,
+    /// Drop the alert and count it in `wf_alert_channel_dropped_total`.
+    /// Rule tasks never stall on a slow sink.
+    Drop,
+}
@@ -4,11 +4,13 @@ use std::str::FromStr;
 
 use serde::Deserialize;
 
+use crate::alert::AlertConfig;
+use crate::interpolate;
 use crate::logging::LoggingConfig;
 use crate::metrics::MetricsConfig;
 use crate::runtime::RuntimeConfig;
 use crate::server::ServerConfig;
-use crate::validate;
+use crate::validate::{self, ConfigProblem};
 use crate::window::{WindowConfig, WindowDefaults, WindowOverride};
 
 // ---------------------------------------------------------------------------
@@ -31,6 +33,8 @@ struct FusionConfigRaw {
     logging: LoggingConfig,
     #[serde(default)]
     metrics: MetricsConfig,
+    #[serde(default)]
+    alert: AlertConfig,
     /// User-defined variables for WFL `$VAR` / `${VAR:default}` preprocessing.
     #[serde(default)]
     vars: HashMap<String, String>,
@@ -52,11 +56,19 @@ pub struct FusionConfig {
     pub work_root: Option<String>,
     pub logging: LoggingConfig,
     pub metrics: MetricsConfig,
+    pub alert: AlertConfig,
     /// User-defined variables for WFL `$VAR` / `${VAR:default}` preprocessing.
     pub vars: HashMap<String, String>,
 }
 
 impl FusionConfig {
+    /// Re-check the loaded config's cross-field invariants, returning every
+    /// problem found (empty when valid). Loading already fails on these;
+    /// call this again after modifying a config in code.
+    pub fn validate(&self) -> Vec<ConfigProblem> {
+        validate::check_config(self)
+    }
+
     /// Read and parse a `wfusion.toml` file.
     pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
         let content = std::fs::read_to_string(path.as_ref())
@@ -69,14 +81,23 @@ impl FromStr for FusionConfig {
     type Err = anyhow::Error;
 
     /// Parse a TOML string into a resolved, validated [`FusionConfig`].
+    ///
+    /// `${VAR}` / `${VAR:default}` references are first substituted from
+    /// the process environment.
     fn from_str(toml_str: &str) -> anyhow::Result<Self> {
-        let raw: FusionConfigRaw = toml::from_str(toml_str)?;
+        let toml_str = interpolate::interpolate_env(toml_str)?;
+        let raw: FusionConfigRaw = toml::from_str(&toml_str)?;
 
-        // Resolve window overrides against defaults.
+        // Resolve window overrides against defaults, collecting failures so
+        // they are reported together with the remaining validation problems.
         let mut windows = Vec::with_capacity(raw.window.len());
+        let mut problems = Vec::new();
         for (name, ovr) in raw.window {
-            let wc = ovr.resolve(name, &raw.window_defaults)?;
-            windows.push(wc);
+            let key = format!("window.{name}.mode");
+            match ovr.resolve(name, &raw.window_defaults) {
+                Ok(wc) => windows.push(wc),
+                Err(e) => problems.push(ConfigProblem::new(key, e.to_string())),
+            }
         }
         // Sort by name for deterministic ordering.
         windows.sort_by(|a, b| a.name.cmp(&b.name));
@@ -90,10 +111,13 @@ impl FromStr for FusionConfig {
             work_root: raw.work_root,
             logging: raw.logging,
             metrics: raw.metrics,
+            alert: raw.alert,
             vars: raw.vars,
         };
 
-        validate::validate(&config)?;
+        problems.sort_by(|a, b| a.key.cmp(&b.key));
+        problems.extend(validate::check_config(&config));
+        validate::ensure_no_problems(problems)?;
 
         Ok(config)
     }
@@ -106,6 +130,7 @@ impl FromStr for FusionConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::alert::{AlertOverflowPolicy, PriorityRoute};
     use crate::types::{ByteSize, DistMode, EvictPolicy, HumanDuration, LatePolicy};
     use std::time::Duration;
 
@@ -220,6 +245,94 @@ over_cap = "48h"
         assert!(toml.parse::<FusionConfig>().is_err());
     }
 
+    #[test]
+    fn load_with_env_interpolation_defaults() {
+        let toml = FULL_TOML
+            .replace(
+                "tcp://127.0.0.1:9800",
+                "tcp://${WF_TEST_UNSET_LISTEN_HOST:127.0.0.1}:9800",
+            )
+            .replace(
+                "executor_parallelism = 2",
+                "executor_parallelism = ${WF_TEST_UNSET_PARALLELISM:3}",
+            );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(cfg.server.listen, "tcp://127.0.0.1:9800");
+        assert_eq!(cfg.runtime.executor_parallelism, 3);
+    }
+
+    #[test]
+    fn reject_unset_env_reference() {
+        let toml = FULL_TOML.replace("tcp://127.0.0.1:9800", "${WF_TEST_UNSET_LISTEN}");
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(err.contains("server.listen"), "{err}");
+        assert!(err.contains("WF_TEST_UNSET_LISTEN"), "{err}");
+    }
+
+    #[test]
+    fn reports_all_problems_at_once() {
+        let toml = FULL_TOML
+            .replace("tcp://127.0.0.1:9800", "http://bad")
+            .replace("executor_parallelism = 2", "executor_parallelism = 0")
+            .replace(
+                "[window.auth_events]\nmode = \"local\"",
+                "[window.auth_events]\nmode = \"distributed\"",
+            );
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(err.contains("3 problems"), "{err}");
+        assert!(err.contains("\n  - server.listen: "), "{err}");
+        assert!(
+            err.contains("\n  - runtime.executor_parallelism: "),
+            "{err}"
+        );
+        assert!(err.contains("\n  - window.auth_events.mode: "), "{err}");
+    }
+
+    #[test]
+    fn reject_zero_timeouts_and_intervals() {
+        let toml = FULL_TOML
+            .replace("rule_exec_timeout = \"30s\"", "rule_exec_timeout = \"0s\"")
+            .replace("evict_interval = \"30s\"", "evict_interval = \"0s\"");
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(
+            err.contains("runtime.rule_exec_timeout: must be > 0"),
+            "{err}"
+        );
+        assert!(
+            err.contains("window_defaults.evict_interval: must be > 0"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn reject_default_window_bytes_above_total() {
+        let toml = FULL_TOML.replace("max_window_bytes = \"256MB\"", "max_window_bytes = \"4GB\"");
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(err.contains("window_defaults.max_window_bytes"), "{err}");
+    }
+
+    #[test]
+    fn reject_metrics_listen_shared_with_http_listen() {
+        let toml = FULL_TOML.replace(
+            "listen = \"tcp://127.0.0.1:9800\"",
+            "listen = \"tcp://127.0.0.1:9800\"\nhttp_listen = \"127.0.0.1:9901\"",
+        ) + "\n[metrics]\nenabled = true\nprometheus_listen = \"127.0.0.1:9901\"\n";
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(err.contains("metrics.prometheus_listen"), "{err}");
+        assert!(err.contains("server.http_listen"), "{err}");
+    }
+
+    #[test]
+    fn validate_rechecks_modified_config() {
+        let mut cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert!(cfg.validate().is_empty());
+        cfg.metrics.enabled = true;
+        cfg.metrics.prometheus_listen = String::new();
+        let problems = cfg.validate();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].key, "metrics.prometheus_listen");
+    }
+
     #[test]
     fn reject_zero_parallelism() {
         let toml = FULL_TOML.replace("executor_parallelism = 2", "executor_parallelism = 0");
@@ -345,6 +458,7 @@ MAX_COUNT_2 = "99"
 enabled = true
 report_interval = "5s"
 prometheus_listen = "127.0.0.1:19001"
+sample_matches = 8
 
 [metrics.topn]
 enabled = true
@@ -360,11 +474,155 @@ queue_capacity = 8192
             Duration::from_secs(5)
         );
         assert_eq!(cfg.metrics.prometheus_listen, "127.0.0.1:19001");
+        assert_eq!(cfg.metrics.sample_matches, 8);
         assert!(cfg.metrics.topn.enabled);
         assert_eq!(cfg.metrics.topn.max, 50);
         assert_eq!(cfg.metrics.topn.queue_capacity, 8192);
     }
 
+    #[test]
+    fn load_with_alert_suppression() {
+        let cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert!(cfg.alert.suppress_ttl.is_none());
+
+        let toml = format!("{FULL_TOML}\n[alert]\nsuppress_ttl = \"10m\"\n");
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(
+            cfg.alert.suppress_ttl.map(|ttl| ttl.as_duration()),
+            Some(Duration::from_secs(600))
+        );
+
+        let toml = format!("{FULL_TOML}\n[alert]\nsuppress_ttl = \"0s\"\n");
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(err.contains("alert.suppress_ttl: must be > 0"), "{err}");
+    }
+
+    #[test]
+    fn load_with_alert_backpressure() {
+        let cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert_eq!(cfg.alert.channel_capacity, 64);
+        assert_eq!(cfg.alert.on_full, AlertOverflowPolicy::Block);
+
+        let toml = format!("{FULL_TOML}\n[alert]\nchannel_capacity = 8\non_full = \"drop\"\n");
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(cfg.alert.channel_capacity, 8);
+        assert_eq!(cfg.alert.on_full, AlertOverflowPolicy::Drop);
+
+        let toml = format!("{FULL_TOML}\n[alert]\nchannel_capacity = 0\n");
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(err.contains("alert.channel_capacity: must be > 0"), "{err}");
+    }
+
+    #[test]
+    fn load_with_alert_priority_route() {
+        let cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert!(cfg.alert.priority_route.is_none());
+
+        let toml = format!(
+            "{FULL_TOML}\n[alert.priority_route]\nmin = 10\ntarget = \"critical_alerts\"\n"
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(
+            cfg.alert.priority_route,
+            Some(PriorityRoute {
+                min: 10,
+                target: "critical_alerts".into(),
+            })
+        );
+
+        let toml = format!("{FULL_TOML}\n[alert.priority_route]\nmin = 10\ntarget = \"\"\n");
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(
+            err.contains("alert.priority_route.target: must be non-empty"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn load_with_score_precision() {
+        let cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert!(cfg.alert.score_precision.is_none());
+
+        let toml = format!("{FULL_TOML}\n[alert]\nscore_precision = 2\n");
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(cfg.alert.score_precision, Some(2));
+
+        let toml = format!("{FULL_TOML}\n[alert]\nscore_precision = 16\n");
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(
+            err.contains("alert.score_precision: must be <= 15"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn load_with_max_out_of_orderness() {
+        let cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert!(cfg.runtime.max_out_of_orderness.as_duration().is_zero());
+
+        let toml = FULL_TOML.replace(
+            "rules   = \"rules/*.wfl\"",
+            "rules   = \"rules/*.wfl\"\nmax_out_of_orderness = \"10s\"",
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(
+            cfg.runtime.max_out_of_orderness.as_duration(),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn load_with_timeout_scan_interval() {
+        let cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert!(cfg.runtime.timeout_scan_interval.is_none());
+
+        let toml = FULL_TOML.replace(
+            "rules   = \"rules/*.wfl\"",
+            "rules   = \"rules/*.wfl\"\ntimeout_scan_interval = \"2s\"",
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(
+            cfg.runtime.timeout_scan_interval.unwrap().as_duration(),
+            Duration::from_secs(2)
+        );
+
+        let toml = FULL_TOML.replace(
+            "rules   = \"rules/*.wfl\"",
+            "rules   = \"rules/*.wfl\"\ntimeout_scan_interval = \"0s\"",
+        );
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(
+            err.contains("runtime.timeout_scan_interval: must be > 0"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn load_with_drain_timeout() {
+        let cfg: FusionConfig = FULL_TOML.parse().unwrap();
+        assert_eq!(
+            cfg.runtime.drain_timeout.as_duration(),
+            Duration::from_secs(30)
+        );
+
+        let toml = FULL_TOML.replace(
+            "rules   = \"rules/*.wfl\"",
+            "rules   = \"rules/*.wfl\"\ndrain_timeout = \"5s\"",
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(
+            cfg.runtime.drain_timeout.as_duration(),
+            Duration::from_secs(5)
+        );
+
+        let toml = FULL_TOML.replace(
+            "rules   = \"rules/*.wfl\"",
+            "rules   = \"rules/*.wfl\"\ndrain_timeout = \"0s\"",
+        );
+        let err = toml.parse::<FusionConfig>().unwrap_err().to_string();
+        assert!(err.contains("runtime.drain_timeout: must be > 0"), "{err}");
+    }
+
     #[test]
     fn reject_invalid_metrics_listen() {
         let toml = format!(
@@ -377,4 +635,99 @@ prometheus_listen = "not-a-socket"
         );
         assert!(toml.parse::<FusionConfig>().is_err());
     }
+
+    #[test]
+    fn load_with_kafka_source() {
+        let toml = format!(
+            r#"{}
+[server.kafka]
+brokers = ["127.0.0.1:9092"]
+group_id = "wfusion"
+format = "arrow"
+
+[server.kafka.topics]
+auth-events = "auth_events"
+"#,
+            FULL_TOML
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        let kafka = cfg.server.kafka.expect("kafka source");
+        assert_eq!(kafka.brokers, vec!["127.0.0.1:9092"]);
+        assert_eq!(kafka.group_id, "wfusion");
+        assert_eq!(kafka.format, crate::server::SourceFormat::Arrow);
+        assert_eq!(kafka.topics["auth-events"], "auth_events");
+    }
+
+    #[test]
+    fn reject_kafka_without_topics() {
+        let toml = format!(
+            r#"{}
+[server.kafka]
+brokers = ["127.0.0.1:9092"]
+group_id = "wfusion"
+topics = {{}}
+"#,
+            FULL_TOML
+        );
+        assert!(toml.parse::<FusionConfig>().is_err());
+    }
+
+    #[test]
+    fn load_with_tls() {
+        let toml = format!(
+            r#"{}
+[server.tls]
+cert = "certs/server.pem"
+key = "certs/server.key"
+"#,
+            FULL_TOML
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        let tls = cfg.server.tls.expect("tls config");
+        assert_eq!(tls.cert, "certs/server.pem");
+        assert_eq!(tls.key, "certs/server.key");
+
+        let toml = format!(
+            r#"{}
+[server.tls]
+cert = "certs/server.pem"
+key = ""
+"#,
+            FULL_TOML
+        );
+        assert!(toml.parse::<FusionConfig>().is_err());
+    }
+
+    #[test]
+    fn load_with_dead_letter() {
+        let toml = format!(
+            r#"{}
+[server.dead_letter]
+path = "data/dead_letter.ndjson"
+"#,
+            FULL_TOML
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        let dlq = cfg.server.dead_letter.expect("dead_letter config");
+        assert_eq!(dlq.path, "data/dead_letter.ndjson");
+
+        let toml = format!("{}\n[server.dead_letter]\npath = \" \"\n", FULL_TOML);
+        assert!(toml.parse::<FusionConfig>().is_err());
+    }
+
+    #[test]
+    fn load_with_http_listen() {
+        let toml = FULL_TOML.replace(
+            "listen = \"tcp://127.0.0.1:9800\"",
+            "listen = \"tcp://127.0.0.1:9800\"\nhttp_listen = \"127.0.0.1:9810\"",
+        );
+        let cfg: FusionConfig = toml.parse().unwrap();
+        assert_eq!(cfg.server.http_listen.as_deref(), Some("127.0.0.1:9810"));
+
+        let toml = FULL_TOML.replace(
+            "listen = \"tcp://127.0.0.1:9800\"",
+            "listen = \"tcp://127.0.0.1:9800\"\nhttp_listen = \"not-a-socket\"",
+        );
+        assert!(toml.parse::<FusionConfig>().is_err());
+    }
 }
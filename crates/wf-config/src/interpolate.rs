@@ -0,0 +1,270 @@
+//! `${VAR}` / `${VAR:default}` environment interpolation for `wfusion.toml`.
+//!
+//! Runs over the raw TOML text before it is parsed, using the same
+//! `${IDENT:default}` semantics as WFL variable preprocessing. References in
+//! `#` comments are left untouched, and `$${` produces a literal `${`.
+//! Values substituted inside `"..."` and `"""..."""` strings are escaped for
+//! TOML, control characters included. Quote state carries across lines, so
+//! multi-line strings are tracked like single-line ones.
+
+use std::fmt::Write as _;
+
+/// Substitute environment variables into raw `wfusion.toml` text.
+pub(crate) fn interpolate_env(source: &str) -> anyhow::Result<String> {
+    interpolate_with(source, |name| std::env::var(name).ok())
+}
+
+fn interpolate_with(
+    source: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> anyhow::Result<String> {
+    let mut out = String::with_capacity(source.len());
+    let mut table = String::new();
+    let mut key = String::new();
+    let mut quote = Quote::None;
+
+    for (idx, line) in source.split_inclusive('\n').enumerate() {
+        // Lines continuing a `"""` / `'''` string hold no headers or keys.
+        if quote == Quote::None {
+            track_key_path(line, &mut table, &mut key);
+        }
+        let key_path = match (table.is_empty(), key.is_empty()) {
+            (true, _) => key.clone(),
+            (false, true) => table.clone(),
+            (false, false) => format!("{table}.{key}"),
+        };
+        substitute_line(line, &lookup, &mut quote, &mut out)
+            .map_err(|msg| anyhow::anyhow!("wfusion.toml line {}: {key_path}: {msg}", idx + 1))?;
+        // Single-line strings never continue onto the next line.
+        if matches!(quote, Quote::Basic | Quote::Literal) {
+            quote = Quote::None;
+        }
+    }
+    Ok(out)
+}
+
+/// TOML string the scanner is currently inside.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    None,
+    /// `"..."`
+    Basic,
+    /// `'...'`
+    Literal,
+    /// `"""..."""`, may span lines.
+    MultiBasic,
+    /// `'''...'''`, may span lines.
+    MultiLiteral,
+}
+
+/// Update the current `[table]` and `key =` from one line of TOML.
+fn track_key_path(line: &str, table: &mut String, key: &mut String) {
+    let trimmed = line.trim_start();
+    if let Some(header) = trimmed.strip_prefix('[') {
+        let header = header.trim_start_matches('[');
+        if let Some(end) = header.find(']') {
+            *table = header[..end].trim().to_string();
+            key.clear();
+        }
+    } else if let Some((k, _)) = trimmed.split_once('=') {
+        let k = k.trim();
+        if !k.is_empty()
+            && k.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        {
+            *key = k.to_string();
+        }
+    }
+}
+
+fn substitute_line(
+    line: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+    quote: &mut Quote,
+    out: &mut String,
+) -> Result<(), String> {
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        // Multi-character delimiters first, copied verbatim.
+        let delimiter = match (*quote, c) {
+            (Quote::None, '"') if rest.starts_with("\"\"\"") => Some((3, Quote::MultiBasic)),
+            (Quote::None, '\'') if rest.starts_with("'''") => Some((3, Quote::MultiLiteral)),
+            (Quote::MultiBasic, '"') if rest.starts_with("\"\"\"") => Some((3, Quote::None)),
+            (Quote::MultiLiteral, '\'') if rest.starts_with("'''") => Some((3, Quote::None)),
+            (Quote::None, '"') => Some((1, Quote::Basic)),
+            (Quote::None, '\'') => Some((1, Quote::Literal)),
+            (Quote::Basic, '"') | (Quote::Literal, '\'') => Some((1, Quote::None)),
+            _ => None,
+        };
+        if let Some((len, next)) = delimiter {
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            *quote = next;
+            continue;
+        }
+
+        let escaped = matches!(*quote, Quote::Basic | Quote::MultiBasic);
+        match c {
+            '#' if *quote == Quote::None => {
+                out.push_str(rest);
+                return Ok(());
+            }
+            '\\' if escaped => {
+                // Copy the escape and the escaped char verbatim.
+                let n = rest.chars().take(2).map(char::len_utf8).sum();
+                out.push_str(&rest[..n]);
+                rest = &rest[n..];
+                continue;
+            }
+            '$' if rest.starts_with("$${") => {
+                out.push_str("${");
+                rest = &rest[3..];
+                continue;
+            }
+            '$' if rest.starts_with("${") => {
+                let end = rest.find('}').ok_or_else(|| {
+                    format!("unterminated variable reference '{}'", rest.trim_end())
+                })?;
+                let value = resolve(&rest[2..end], lookup)?;
+                if escaped {
+                    push_escaped(&value, out);
+                } else {
+                    out.push_str(&value);
+                }
+                rest = &rest[end + 1..];
+                continue;
+            }
+            _ => {}
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Ok(())
+}
+
+/// Append `value` escaped for a TOML basic string: quotes, backslashes and
+/// every control character.
+fn push_escaped(value: &str, out: &mut String) {
+    for v in value.chars() {
+        match v {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            _ if v.is_control() => {
+                let _ = write!(out, "\\u{:04X}", v as u32);
+            }
+            _ => out.push(v),
+        }
+    }
+}
+
+fn resolve(reference: &str, lookup: &impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let (name, default) = match reference.split_once(':') {
+        Some((name, default)) => (name, Some(default)),
+        None => (reference, None),
+    };
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!(
+            "invalid variable name '{name}' in '${{{reference}}}'"
+        ));
+    }
+    match (lookup(name), default) {
+        (Some(value), _) => Ok(value),
+        (None, Some(default)) => Ok(default.to_string()),
+        (None, None) => Err(format!(
+            "environment variable '{name}' is not set and has no default"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn run(source: &str, env: &[(&str, &str)]) -> anyhow::Result<String> {
+        let env: HashMap<String, String> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        interpolate_with(source, |name| env.get(name).cloned())
+    }
+
+    #[test]
+    fn substitutes_set_variables() {
+        let src = "[server]\nlisten = \"tcp://${HOST}:${PORT}\"\n";
+        let out = run(src, &[("HOST", "0.0.0.0"), ("PORT", "9800")]).unwrap();
+        assert_eq!(out, "[server]\nlisten = \"tcp://0.0.0.0:9800\"\n");
+    }
+
+    #[test]
+    fn falls_back_to_default() {
+        let src = "sinks = \"${SINK_DIR:sinks}\"\nparallelism = ${PAR:4}\n";
+        let out = run(src, &[("PAR", "8")]).unwrap();
+        assert_eq!(out, "sinks = \"sinks\"\nparallelism = 8\n");
+    }
+
+    #[test]
+    fn missing_variable_reports_key_path() {
+        let src = "sinks = \"sinks\"\n\n[server]\nlisten = \"${WF_LISTEN}\"\n";
+        let err = run(src, &[]).unwrap_err().to_string();
+        assert!(err.contains("line 4"), "{err}");
+        assert!(err.contains("server.listen"), "{err}");
+        assert!(err.contains("'WF_LISTEN' is not set"), "{err}");
+    }
+
+    #[test]
+    fn comments_and_escapes_pass_through() {
+        let src = "# uses ${UNSET}\nkey = \"$${literal}\" # ${ALSO_UNSET}\n";
+        let out = run(src, &[]).unwrap();
+        assert_eq!(
+            out,
+            "# uses ${UNSET}\nkey = \"${literal}\" # ${ALSO_UNSET}\n"
+        );
+    }
+
+    #[test]
+    fn escapes_values_inside_basic_strings() {
+        let src = "password = \"${PW}\"\n";
+        let out = run(src, &[("PW", r#"a"b\c"#)]).unwrap();
+        assert_eq!(out, "password = \"a\\\"b\\\\c\"\n");
+    }
+
+    #[test]
+    fn escapes_control_characters_inside_basic_strings() {
+        let src = "banner = \"${BANNER}\"\n";
+        let out = run(src, &[("BANNER", "a\nb\tc\u{1}")]).unwrap();
+        assert_eq!(out, "banner = \"a\\nb\\tc\\u0001\"\n");
+        let parsed: toml::Value = toml::from_str(&out).unwrap();
+        assert_eq!(parsed["banner"].as_str(), Some("a\nb\tc\u{1}"));
+    }
+
+    #[test]
+    fn tracks_multi_line_strings() {
+        let src =
+            "note = \"\"\"\nsee # ${A}\n\"\"\"\nraw = '''\n# ${B}\n'''\nafter = \"${C}\" # ${D}\n";
+        let out = run(src, &[("A", "x\"y"), ("B", "b"), ("C", "c")]).unwrap();
+        assert_eq!(
+            out,
+            "note = \"\"\"\nsee # x\\\"y\n\"\"\"\nraw = '''\n# b\n'''\nafter = \"c\" # ${D}\n"
+        );
+    }
+
+    #[test]
+    fn unterminated_reference_is_an_error() {
+        let err = run("[runtime]\nrules = \"${RULES\"\n", &[]).unwrap_err();
+        assert!(err.to_string().contains("runtime.rules"), "{err}");
+        assert!(err.to_string().contains("unterminated"), "{err}");
+    }
+}
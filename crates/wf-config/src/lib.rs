@@ -1,20 +1,27 @@
+pub mod alert;
 pub mod fusion;
+mod interpolate;
 pub mod logging;
 pub mod metrics;
 pub mod project;
 pub mod runtime;
 pub mod server;
 pub mod sink;
+pub mod template;
 pub mod types;
 pub mod validate;
 pub mod window;
 
+pub use alert::{AlertConfig, AlertOverflowPolicy, PriorityRoute};
 pub use fusion::FusionConfig;
 pub use logging::{LogFormat, LoggingConfig};
 pub use metrics::{MetricsConfig, MetricsTopNConfig};
-pub use project::{load_schemas, load_wfl, parse_vars};
+pub use project::{
+    STDIN_PATH, is_stdin, load_schemas, load_wfl, parse_vars, read_source, resolve_pattern,
+    resolve_patterns,
+};
 pub use runtime::{RuntimeConfig, resolve_glob};
-pub use server::ServerConfig;
+pub use server::{DeadLetterConfig, KafkaSourceConfig, ServerConfig, SourceFormat, TlsConfig};
 pub use types::{ByteSize, DistMode, EvictPolicy, HumanDuration, LatePolicy};
-pub use validate::validate_over_vs_over_cap;
+pub use validate::{ConfigProblem, check_deployment, validate_over_vs_over_cap};
 pub use window::WindowConfig;
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Logging configuration. All fields have defaults so the entire `[logging]`
 /// section may be omitted from `wfusion.toml`.
@@ -12,6 +12,10 @@ pub struct LoggingConfig {
     pub level: String,
     /// Per-module level overrides, e.g. `{ "wf_runtime::receiver" = "debug" }`.
     pub modules: HashMap<String, String>,
+    /// Per-domain level overrides keyed by the `domain` field of `wf_log!`
+    /// events, e.g. `{ pipe = "debug" }`. Takes precedence over `level` and
+    /// `modules` for events tagged with that domain.
+    pub domains: HashMap<String, String>,
     /// Optional file path for log output. Relative paths are resolved against
     /// the config file's parent directory.
     pub file: Option<PathBuf>,
@@ -24,6 +28,7 @@ impl Default for LoggingConfig {
         Self {
             level: "info".to_string(),
             modules: HashMap::new(),
+            domains: HashMap::new(),
             file: None,
             format: LogFormat::Plain,
         }
@@ -31,7 +36,7 @@ impl Default for LoggingConfig {
 }
 
 /// Log output format.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     Plain,
@@ -13,6 +13,10 @@ pub struct MetricsConfig {
     pub report_interval: HumanDuration,
     #[serde(default = "default_prometheus_listen")]
     pub prometheus_listen: String,
+    /// Keep the last N matched contexts per rule for `/debug/matches`;
+    /// `0` disables sampling.
+    #[serde(default)]
+    pub sample_matches: usize,
     #[serde(default)]
     pub topn: MetricsTopNConfig,
 }
@@ -60,6 +64,7 @@ impl Default for MetricsConfig {
             enabled: false,
             report_interval: default_report_interval(),
             prometheus_listen: default_prometheus_listen(),
+            sample_matches: 0,
             topn: MetricsTopNConfig::default(),
         }
     }
@@ -1,40 +1,77 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
 use crate::runtime::resolve_glob;
 
+/// Path argument that stands for standard input.
+pub const STDIN_PATH: &str = "-";
+
+/// Whether `path` is the [`STDIN_PATH`] placeholder.
+pub fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == STDIN_PATH
+}
+
+/// Read the file at `path`, or all of standard input when `path` is `-`.
+pub fn read_source(path: &Path) -> Result<String> {
+    if is_stdin(path) {
+        let mut source = String::new();
+        std::io::stdin()
+            .read_to_string(&mut source)
+            .context("reading standard input")?;
+        return Ok(source);
+    }
+    std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+}
+
 /// Load and preprocess a .wfl file with variable substitutions.
+/// `-` reads the source from standard input.
 /// Variables are resolved in order: `vars` (from `--var`) first, then
 /// environment variables. An error is returned only if a variable is
 /// found in neither source and has no `${VAR:default}` fallback.
 pub fn load_wfl(path: &Path, vars: &HashMap<String, String>) -> Result<String> {
-    let source =
-        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let source = read_source(path)?;
     let preprocessed = wf_lang::preprocess_vars_with_env(&source, vars)?;
     Ok(preprocessed)
 }
 
 /// Load all .wfs schema files matching a glob pattern.
+///
+/// A file matched by more than one pattern is loaded once.
 pub fn load_schemas(patterns: &[String], base_dir: &Path) -> Result<Vec<wf_lang::WindowSchema>> {
     let mut schemas = Vec::new();
+    for path in resolve_patterns(patterns, base_dir)? {
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading schema {}", path.display()))?;
+        let mut parsed = wf_lang::parse_wfs(&source)
+            .map_err(|e| anyhow::anyhow!("parsing {}: {e}", path.display()))?;
+        schemas.append(&mut parsed);
+    }
+    Ok(schemas)
+}
+
+/// Resolve each pattern with [`resolve_pattern`] and concatenate the results,
+/// dropping paths that an earlier pattern already produced.
+pub fn resolve_patterns<S: AsRef<str>>(patterns: &[S], base_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut seen = HashSet::new();
+    let mut resolved = Vec::new();
     for pattern in patterns {
-        let paths = resolve_schema_glob(pattern, base_dir)?;
-        for path in paths {
-            let source = std::fs::read_to_string(&path)
-                .with_context(|| format!("reading schema {}", path.display()))?;
-            let mut parsed = wf_lang::parse_wfs(&source)
-                .map_err(|e| anyhow::anyhow!("parsing {}: {e}", path.display()))?;
-            schemas.append(&mut parsed);
+        for path in resolve_pattern(pattern.as_ref(), base_dir)? {
+            let key = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if seen.insert(key) {
+                resolved.push(path);
+            }
         }
     }
-    Ok(schemas)
+    Ok(resolved)
 }
 
-/// Resolve a glob pattern for schema files. If the pattern contains glob
-/// characters, use glob expansion; otherwise treat as a literal path.
-fn resolve_schema_glob(pattern: &str, base_dir: &Path) -> Result<Vec<PathBuf>> {
+/// Resolve a file pattern relative to `base_dir`. If the pattern contains
+/// glob characters, use glob expansion (an error if nothing matches);
+/// otherwise treat it as a literal path that must exist.
+pub fn resolve_pattern(pattern: &str, base_dir: &Path) -> Result<Vec<PathBuf>> {
     if pattern.contains('*') || pattern.contains('?') || pattern.contains('[') {
         resolve_glob(pattern, base_dir)
     } else {
@@ -42,7 +79,7 @@ fn resolve_schema_glob(pattern: &str, base_dir: &Path) -> Result<Vec<PathBuf>> {
         if path.exists() {
             Ok(vec![path])
         } else {
-            anyhow::bail!("schema file not found: {}", path.display());
+            anyhow::bail!("file not found: {}", path.display());
         }
     }
 }
@@ -58,3 +95,63 @@ pub fn parse_vars(var_args: &[String]) -> Result<HashMap<String, String>> {
     }
     Ok(vars)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA_A: &str = r#"
+window a_events {
+    stream = "a"
+    time = event_time
+    over = 5m
+    fields {
+        sip: ip
+        event_time: time
+    }
+}
+"#;
+
+    const SCHEMA_B: &str = r#"
+window b_events {
+    stream = "b"
+    time = event_time
+    over = 5m
+    fields {
+        dip: ip
+        event_time: time
+    }
+}
+"#;
+
+    fn schema_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wf-config-{tag}-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("schemas")).unwrap();
+        std::fs::write(dir.join("schemas/a.wfs"), SCHEMA_A).unwrap();
+        std::fs::write(dir.join("schemas/b.wfs"), SCHEMA_B).unwrap();
+        std::fs::write(dir.join("schemas/notes.txt"), "ignored").unwrap();
+        dir
+    }
+
+    #[test]
+    fn load_schemas_expands_glob_and_dedups() {
+        let dir = schema_dir("glob");
+        let patterns = vec!["schemas/*.wfs".to_string(), "schemas/a.wfs".to_string()];
+        let schemas = load_schemas(&patterns, &dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<_> = schemas.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a_events", "b_events"]);
+    }
+
+    #[test]
+    fn resolve_pattern_rejects_empty_glob() {
+        let dir = schema_dir("empty-glob");
+        let err = resolve_pattern("schemas/*.wfl", &dir).unwrap_err();
+        let missing = resolve_pattern("schemas/c.wfs", &dir).unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(err.to_string().contains("matched no files"), "{err}");
+        assert!(missing.to_string().contains("file not found"), "{missing}");
+    }
+}
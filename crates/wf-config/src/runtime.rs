@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,40 @@ pub struct RuntimeConfig {
     pub schemas: String,
     /// Glob pattern for WFL rule (.wfl) files, relative to config dir.
     pub rules: String,
+    /// How far each rule's match watermark trails the newest event time, so
+    /// slightly out-of-order events are still folded in before expiry.
+    #[serde(default = "default_max_out_of_orderness")]
+    pub max_out_of_orderness: HumanDuration,
+    /// How often every rule task scans for timed-out instances. Unset derives
+    /// the interval per rule from its match window.
+    #[serde(default)]
+    pub timeout_scan_interval: Option<HumanDuration>,
+    /// Upper bound on graceful shutdown. Task groups still running when it
+    /// elapses are aborted.
+    #[serde(default = "default_drain_timeout")]
+    pub drain_timeout: HumanDuration,
+}
+
+fn default_max_out_of_orderness() -> HumanDuration {
+    Duration::ZERO.into()
+}
+
+fn default_drain_timeout() -> HumanDuration {
+    Duration::from_secs(30).into()
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            executor_parallelism: 2,
+            rule_exec_timeout: "30s".parse().expect("hardcoded duration must parse"),
+            schemas: "schemas/*.wfs".to_string(),
+            rules: "rules/*.wfl".to_string(),
+            max_out_of_orderness: default_max_out_of_orderness(),
+            timeout_scan_interval: None,
+            drain_timeout: default_drain_timeout(),
+        }
+    }
 }
 
 /// Expand a glob `pattern` relative to `base_dir` and return matched paths
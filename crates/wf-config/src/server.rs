@@ -1,7 +1,80 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServerConfig {
     /// Listen address, e.g. `"tcp://127.0.0.1:9800"`.
     pub listen: String,
+    /// Optional HTTP JSON push listener (`host:port`), serving
+    /// `POST /ingest/{window}`.
+    #[serde(default)]
+    pub http_listen: Option<String>,
+    /// Optional Kafka source, consumed alongside the TCP listener.
+    #[serde(default)]
+    pub kafka: Option<KafkaSourceConfig>,
+    /// Optional TLS termination for the TCP listener.
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// Optional dead-letter file for batches no window accepts.
+    #[serde(default)]
+    pub dead_letter: Option<DeadLetterConfig>,
+}
+
+/// `[server.tls]` — serve the TCP Arrow IPC listener over TLS.
+///
+/// Paths are PEM files, relative to the config file directory.
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            listen: "tcp://127.0.0.1:9800".to_string(),
+            http_listen: None,
+            kafka: None,
+            tls: None,
+            dead_letter: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TlsConfig {
+    /// Certificate chain (leaf first).
+    pub cert: String,
+    /// Private key (PKCS#8, PKCS#1 or SEC1).
+    pub key: String,
+}
+
+/// `[server.dead_letter]` — keep batches the router could not deliver
+/// (schema mismatch, no subscribing window) instead of only logging them.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeadLetterConfig {
+    /// NDJSON file, relative to the config file directory. Appended to,
+    /// never truncated.
+    pub path: String,
+}
+
+/// `[server.kafka]` — consume events from Kafka topics into windows.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KafkaSourceConfig {
+    /// Bootstrap brokers, e.g. `["127.0.0.1:9092"]`.
+    pub brokers: Vec<String>,
+    /// Consumer group id.
+    pub group_id: String,
+    /// Payload encoding of every message.
+    #[serde(default)]
+    pub format: SourceFormat,
+    /// Topic → window name mapping.
+    pub topics: BTreeMap<String, String>,
+}
+
+/// Payload encoding accepted by non-TCP sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceFormat {
+    /// Newline-delimited JSON objects, or a JSON array of objects, per
+    /// message.
+    #[default]
+    Json,
+    /// One Arrow IPC frame per message (same encoding as the TCP receiver).
+    Arrow,
 }
@@ -116,6 +116,7 @@ pub fn build_flex_group(
         )?;
         sinks.push(spec);
     }
+    let serializers = route_group.sinks.iter().map(|rs| rs.serializer).collect();
 
     let merged_tags = merge_tags(&defaults.tags, group_tags, None);
 
@@ -126,6 +127,7 @@ pub fn build_flex_group(
         tags: merged_tags,
         expect: route_group.expect.clone(),
         sinks,
+        serializers,
     })
 }
 
@@ -150,11 +152,13 @@ pub fn build_fixed_group(
         )?;
         sinks.push(spec);
     }
+    let serializers = route_group.sinks.iter().map(|rs| rs.serializer).collect();
 
     Ok(FixedGroup {
         name: route_group.name.clone(),
         expect: route_group.expect.clone(),
         sinks,
+        serializers,
         parallel,
     })
 }
@@ -214,6 +218,7 @@ fn resolve_route_sink(
 
 #[cfg(test)]
 mod tests {
+    use super::super::types::AlertSerializer;
     use super::*;
 
     fn sample_connector() -> ConnectorDef {
@@ -293,6 +298,7 @@ mod tests {
                     m.insert("path".into(), serde_json::json!("alerts/sec.jsonl"));
                     m
                 },
+                serializer: AlertSerializer::Cef,
                 tags: None,
                 expect: None,
             }],
@@ -312,6 +318,7 @@ mod tests {
             group.sinks[0].params["path"],
             serde_json::json!("alerts/sec.jsonl")
         );
+        assert_eq!(group.serializers, vec![AlertSerializer::Cef]);
     }
 
     #[test]
@@ -327,6 +334,7 @@ mod tests {
                 connect: "missing".into(),
                 name: None,
                 params: ParamMap::new(),
+                serializer: AlertSerializer::default(),
                 tags: None,
                 expect: None,
             }],
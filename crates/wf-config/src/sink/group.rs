@@ -1,7 +1,7 @@
 use wp_connector_api::SinkSpec as ResolvedSinkSpec;
 
 use super::expect::GroupExpectSpec;
-use super::types::WildArray;
+use super::types::{AlertSerializer, WildArray};
 
 // ---------------------------------------------------------------------------
 // FlexGroup — resolved business routing group
@@ -24,6 +24,8 @@ pub struct FlexGroup {
     pub expect: Option<GroupExpectSpec>,
     /// Resolved sink specifications (ready for factory building).
     pub sinks: Vec<ResolvedSinkSpec>,
+    /// Alert serializer per sink, index-aligned with `sinks`.
+    pub serializers: Vec<AlertSerializer>,
 }
 
 // ---------------------------------------------------------------------------
@@ -39,6 +41,8 @@ pub struct FixedGroup {
     pub expect: Option<GroupExpectSpec>,
     /// Resolved sink specifications.
     pub sinks: Vec<ResolvedSinkSpec>,
+    /// Alert serializer per sink, index-aligned with `sinks`.
+    pub serializers: Vec<AlertSerializer>,
     /// Max parallel writers.
     pub parallel: usize,
 }
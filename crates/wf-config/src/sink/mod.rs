@@ -15,5 +15,5 @@ pub use expect::{GroupExpectSpec, SinkExpectOverride};
 pub use group::{FixedGroup, FlexGroup};
 pub use io::{SinkConfigBundle, load_sink_config};
 pub use route::{RouteFile, RouteGroup, RouteSink};
-pub use types::{ParamMap, StringOrArray, WildArray};
+pub use types::{AlertSerializer, ParamMap, StringOrArray, WildArray};
 pub use validate::validate_sink_coverage;
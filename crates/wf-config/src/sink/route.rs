@@ -1,7 +1,7 @@
 use serde::Deserialize;
 
 use super::expect::{GroupExpectSpec, SinkExpectOverride};
-use super::types::{ParamMap, StringOrArray};
+use super::types::{AlertSerializer, ParamMap, StringOrArray};
 
 // ---------------------------------------------------------------------------
 // RouteFile — top-level TOML structure for business/infra route files
@@ -63,6 +63,9 @@ pub struct RouteSink {
     /// Parameter overrides (merged with connector defaults, subject to allow_override).
     #[serde(default)]
     pub params: ParamMap,
+    /// Alert wire format for this sink; defaults to JSON.
+    #[serde(default)]
+    pub serializer: AlertSerializer,
     /// Tags to attach to alerts for this specific sink.
     pub tags: Option<Vec<String>>,
     /// Per-sink expect overrides.
@@ -46,6 +46,29 @@ impl<'de> Deserialize<'de> for StringOrArray {
     }
 }
 
+// ---------------------------------------------------------------------------
+// AlertSerializer — wire format a sink receives alerts in
+// ---------------------------------------------------------------------------
+
+/// Wire format used to render alerts for a sink.
+///
+/// ```toml
+/// [[sink_group.sinks]]
+/// connect = "siem_syslog"
+/// serializer = "cef"   # json (default) | cef | leef
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSerializer {
+    /// One compact JSON object per alert.
+    #[default]
+    Json,
+    /// ArcSight Common Event Format (`CEF:0|...`).
+    Cef,
+    /// IBM QRadar Log Event Extended Format (`LEEF:1.0|...`).
+    Leef,
+}
+
 // ---------------------------------------------------------------------------
 // WildArray — compiled wildcard pattern matcher
 // ---------------------------------------------------------------------------
@@ -139,6 +162,20 @@ mod tests {
         assert!(!wa.matches("anything"));
     }
 
+    #[test]
+    fn alert_serializer_from_toml() {
+        #[derive(Deserialize)]
+        struct W {
+            #[serde(default)]
+            v: AlertSerializer,
+        }
+        let w: W = toml::from_str(r#"v = "cef""#).unwrap();
+        assert_eq!(w.v, AlertSerializer::Cef);
+        let w: W = toml::from_str("").unwrap();
+        assert_eq!(w.v, AlertSerializer::Json);
+        assert!(toml::from_str::<W>(r#"v = "xml""#).is_err());
+    }
+
     #[test]
     fn string_or_array_single() {
         #[derive(Deserialize)]
@@ -52,6 +52,7 @@ mod tests {
                     name: "__default".into(),
                     expect: None,
                     sinks: vec![],
+                    serializers: vec![],
                     parallel: 1,
                 })
             } else {
@@ -69,6 +70,7 @@ mod tests {
             tags: vec![],
             expect: None,
             sinks: vec![],
+            serializers: vec![],
         }
     }
 
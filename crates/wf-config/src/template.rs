@@ -0,0 +1,344 @@
+//! Annotated default `wfusion.toml`, as written by `wfusion config init`.
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::logging::LoggingConfig;
+use crate::metrics::MetricsConfig;
+use crate::runtime::RuntimeConfig;
+use crate::server::ServerConfig;
+use crate::window::WindowDefaults;
+
+/// Render a fully commented `wfusion.toml` populated from the `Default` of
+/// each config section. The output parses back into an equivalent
+/// [`FusionConfig`](crate::FusionConfig).
+pub fn default_config_toml() -> String {
+    let server = ServerConfig::default();
+    let runtime = RuntimeConfig::default();
+    let window = WindowDefaults::default();
+    let logging = LoggingConfig::default();
+    let metrics = MetricsConfig::default();
+
+    let mut out = String::new();
+    line(
+        &mut out,
+        "# wfusion.toml — generated by `wfusion config init`.",
+    );
+    line(
+        &mut out,
+        "# Values are the built-in defaults; ${VAR} / ${VAR:default} read the environment.",
+    );
+    line(&mut out, "");
+    line(&mut out, "# ── Alert sinks ──");
+    field(
+        &mut out,
+        "sinks",
+        &"sinks",
+        "Path to the sinks/ directory (connector-based routing), relative to this file",
+    );
+    commented(
+        &mut out,
+        "work_root = \".\"",
+        "Optional root for sink file-path resolution",
+    );
+
+    header(&mut out, "Server", "server");
+    field(
+        &mut out,
+        "listen",
+        &server.listen,
+        "TCP listen address (must start with tcp://)",
+    );
+    commented(
+        &mut out,
+        "http_listen = \"127.0.0.1:9810\"",
+        "Optional HTTP JSON push endpoint (host:port)",
+    );
+    line(&mut out, "");
+    line(&mut out, "# Optional TLS termination for the TCP listener.");
+    line(&mut out, "# [server.tls]");
+    line(&mut out, "# cert = \"certs/server.pem\"");
+    line(&mut out, "# key = \"certs/server.key\"");
+    line(&mut out, "");
+    line(
+        &mut out,
+        "# Optional dead-letter file for batches no window accepts.",
+    );
+    line(&mut out, "# [server.dead_letter]");
+    line(&mut out, "# path = \"data/dead_letter.ndjson\"");
+    line(&mut out, "");
+    line(
+        &mut out,
+        "# Optional Kafka source, consumed alongside the TCP listener.",
+    );
+    line(&mut out, "# [server.kafka]");
+    line(&mut out, "# brokers = [\"127.0.0.1:9092\"]");
+    line(&mut out, "# group_id = \"wfusion\"");
+    commented(&mut out, "format = \"json\"", "json | arrow");
+    line(&mut out, "# [server.kafka.topics]");
+    commented(&mut out, "auth-events = \"auth_events\"", "topic -> window");
+
+    header(&mut out, "Runtime", "runtime");
+    field(
+        &mut out,
+        "executor_parallelism",
+        &runtime.executor_parallelism,
+        "Max rules executing concurrently",
+    );
+    field(
+        &mut out,
+        "rule_exec_timeout",
+        &runtime.rule_exec_timeout,
+        "Timeout for a single rule execution",
+    );
+    field(
+        &mut out,
+        "schemas",
+        &runtime.schemas,
+        "Window schema (.wfs) glob, relative to this file",
+    );
+    field(
+        &mut out,
+        "rules",
+        &runtime.rules,
+        "Rule (.wfl) glob, relative to this file",
+    );
+    field(
+        &mut out,
+        "max_out_of_orderness",
+        &runtime.max_out_of_orderness,
+        "Match watermark lag behind the newest event",
+    );
+
+    header(&mut out, "Window defaults", "window_defaults");
+    field(
+        &mut out,
+        "evict_interval",
+        &window.evict_interval,
+        "How often eviction runs",
+    );
+    field(
+        &mut out,
+        "max_window_bytes",
+        &window.max_window_bytes,
+        "Memory cap per window",
+    );
+    field(
+        &mut out,
+        "max_total_bytes",
+        &window.max_total_bytes,
+        "Memory cap across all windows",
+    );
+    field(
+        &mut out,
+        "evict_policy",
+        &window.evict_policy,
+        "time_first | lru",
+    );
+    field(&mut out, "watermark", &window.watermark, "Watermark delay");
+    field(
+        &mut out,
+        "allowed_lateness",
+        &window.allowed_lateness,
+        "Lateness tolerated past the watermark",
+    );
+    field(
+        &mut out,
+        "late_policy",
+        &window.late_policy,
+        "drop | revise | side_output",
+    );
+    field(
+        &mut out,
+        "compact_below",
+        &window.compact_below,
+        "Merge adjacent batches smaller than this (0B disables)",
+    );
+
+    line(&mut out, "");
+    line(
+        &mut out,
+        "# ── Per-window overrides (one per window declared in .wfs) ──",
+    );
+    line(&mut out, "# [window.auth_events]");
+    commented(
+        &mut out,
+        "mode = \"local\"",
+        "local | replicated | partitioned",
+    );
+    commented(
+        &mut out,
+        "partition_key = \"sip\"",
+        "Required when mode = \"partitioned\"",
+    );
+    commented(
+        &mut out,
+        "over_cap = \"30m\"",
+        "Upper bound for the schema's `over`",
+    );
+    commented(
+        &mut out,
+        "max_window_bytes = \"256MB\"",
+        "Any [window_defaults] key may be overridden",
+    );
+
+    header(&mut out, "Logging", "logging");
+    field(
+        &mut out,
+        "level",
+        &logging.level,
+        "Global level filter: trace | debug | info | warn | error",
+    );
+    field(&mut out, "format", &logging.format, "plain | json");
+    commented(
+        &mut out,
+        "file = \"logs/wfusion.log\"",
+        "Log to a file instead of stderr",
+    );
+    line(&mut out, "# [logging.modules]");
+    commented(
+        &mut out,
+        "\"wf_runtime::receiver\" = \"debug\"",
+        "Per-module level overrides",
+    );
+    line(&mut out, "# [logging.domains]");
+    commented(
+        &mut out,
+        "pipe = \"debug\"",
+        "Per-domain (sys | conn | pipe | res | conf) level overrides",
+    );
+
+    header(&mut out, "Runtime metrics", "metrics");
+    field(
+        &mut out,
+        "enabled",
+        &metrics.enabled,
+        "Collect and export runtime metrics",
+    );
+    field(
+        &mut out,
+        "report_interval",
+        &metrics.report_interval,
+        "Snapshot reporting interval",
+    );
+    field(
+        &mut out,
+        "prometheus_listen",
+        &metrics.prometheus_listen,
+        "/metrics endpoint (host:port)",
+    );
+    field(
+        &mut out,
+        "sample_matches",
+        &metrics.sample_matches,
+        "Matched contexts kept per rule for /debug/matches (0 = off)",
+    );
+    line(&mut out, "");
+    line(&mut out, "[metrics.topn]");
+    field(
+        &mut out,
+        "enabled",
+        &metrics.topn.enabled,
+        "Track top-N keys per rule",
+    );
+    field(&mut out, "max", &metrics.topn.max, "Keys kept per rule");
+    field(
+        &mut out,
+        "queue_capacity",
+        &metrics.topn.queue_capacity,
+        "Top-N update queue size",
+    );
+
+    header(&mut out, "Alert pipeline", "alert");
+    commented(
+        &mut out,
+        "suppress_ttl = \"10m\"",
+        "Drop repeats of the same rule + entity within this window",
+    );
+
+    header(
+        &mut out,
+        "Variables for .wfl $VAR / ${VAR:default} preprocessing",
+        "vars",
+    );
+    line(&mut out, "# FAIL_THRESHOLD = \"3\"");
+
+    out
+}
+
+fn header(out: &mut String, title: &str, table: &str) {
+    let _ = write!(out, "\n# ── {title} ──\n[{table}]\n");
+}
+
+/// A commented-out optional key, aligned like [`field`].
+fn commented(out: &mut String, assignment: &str, comment: &str) {
+    let assignment = format!("# {assignment}");
+    let _ = writeln!(out, "{assignment:<36} # {comment}");
+}
+
+fn line(out: &mut String, text: &str) {
+    out.push_str(text);
+    out.push('\n');
+}
+
+/// `key = value  # comment`, with `value` rendered as a TOML literal.
+fn field<T: Serialize>(out: &mut String, key: &str, value: &T, comment: &str) {
+    let value = toml::Value::try_from(value).expect("config defaults serialize to TOML");
+    let assignment = format!("{key} = {value}");
+    let _ = writeln!(out, "{assignment:<36} # {comment}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FusionConfig;
+
+    #[test]
+    fn default_template_round_trips() {
+        let text = default_config_toml();
+        let dir = std::env::temp_dir().join(format!("wf-config-init-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("wfusion.toml");
+        std::fs::write(&path, &text).unwrap();
+        let cfg = FusionConfig::load(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(cfg.server.listen, ServerConfig::default().listen);
+        assert_eq!(cfg.runtime.rules, RuntimeConfig::default().rules);
+        assert_eq!(
+            cfg.window_defaults.max_total_bytes,
+            WindowDefaults::default().max_total_bytes
+        );
+        assert_eq!(cfg.logging.level, "info");
+        assert!(!cfg.metrics.enabled);
+        assert_eq!(cfg.metrics.topn.max, MetricsConfig::default().topn.max);
+        assert!(cfg.windows.is_empty());
+        assert!(cfg.alert.suppress_ttl.is_none());
+        assert!(cfg.vars.is_empty());
+    }
+
+    #[test]
+    fn default_template_documents_every_section() {
+        let text = default_config_toml();
+        for table in [
+            "[server]",
+            "[runtime]",
+            "[window_defaults]",
+            "# [window.auth_events]",
+            "[logging]",
+            "[metrics]",
+            "[metrics.topn]",
+            "[alert]",
+            "[vars]",
+        ] {
+            assert!(text.contains(table), "missing {table}:\n{text}");
+        }
+        // Every active key carries an inline comment.
+        for l in text.lines() {
+            if l.contains(" = ") && !l.starts_with('#') {
+                assert!(l.contains(" # "), "uncommented key: {l}");
+            }
+        }
+    }
+}
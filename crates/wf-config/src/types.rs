@@ -95,7 +95,7 @@ impl<'de> Deserialize<'de> for HumanDuration {
 // ---------------------------------------------------------------------------
 
 /// A byte size parsed from a human-readable string like `"256MB"`, `"2GB"`, `"64KB"`, `"1024B"`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct ByteSize(usize);
 
 impl ByteSize {
@@ -1,81 +1,244 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::net::ToSocketAddrs;
 use std::time::Duration;
 
+use wf_lang::WindowSchema;
+use wf_lang::plan::RulePlan;
+
 use crate::fusion::FusionConfig;
 use crate::window::WindowConfig;
 
-/// Internal validation, called automatically during `FusionConfig::from_str` / `load`.
-pub(crate) fn validate(config: &FusionConfig) -> anyhow::Result<()> {
+/// One configuration problem, tagged with the offending key path
+/// (e.g. `server.listen`, `window.auth_events.over_cap`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub key: String,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    pub fn new(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// Turn a problem list into a single error listing all of them.
+pub fn ensure_no_problems(problems: Vec<ConfigProblem>) -> anyhow::Result<()> {
+    match problems.len() {
+        0 => Ok(()),
+        1 => anyhow::bail!("invalid configuration: {}", problems[0]),
+        n => {
+            let mut msg = format!("invalid configuration ({n} problems):");
+            for p in &problems {
+                msg.push_str(&format!("\n  - {p}"));
+            }
+            anyhow::bail!(msg)
+        }
+    }
+}
+
+/// Check the invariants of a loaded `wfusion.toml` that serde cannot
+/// express, returning every problem found. Called automatically during
+/// `FusionConfig::from_str` / `load`.
+pub fn check_config(config: &FusionConfig) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let mut problem = |key: &str, message: String| problems.push(ConfigProblem::new(key, message));
+
     // server.listen must start with tcp://
     if !config.server.listen.starts_with("tcp://") {
-        anyhow::bail!(
-            "server.listen must start with \"tcp://\", got {:?}",
-            config.server.listen,
+        problem(
+            "server.listen",
+            format!("must start with \"tcp://\", got {:?}", config.server.listen),
         );
     }
 
+    // server.http_listen must be host:port (no scheme)
+    if let Some(http_listen) = &config.server.http_listen
+        && let Some(msg) = socket_addr_problem(http_listen)
+    {
+        problem("server.http_listen", msg);
+    }
+
+    // server.kafka needs brokers, a group and at least one topic
+    if let Some(kafka) = &config.server.kafka {
+        if kafka.brokers.is_empty() {
+            problem("server.kafka.brokers", "must be non-empty".into());
+        }
+        if kafka.group_id.is_empty() {
+            problem("server.kafka.group_id", "must be non-empty".into());
+        }
+        if kafka.topics.is_empty() {
+            problem(
+                "server.kafka.topics",
+                "must map at least one topic to a window".into(),
+            );
+        }
+    }
+
+    // server.tls needs both a certificate and a key
+    if let Some(tls) = &config.server.tls {
+        if tls.cert.trim().is_empty() {
+            problem("server.tls.cert", "must be non-empty".into());
+        }
+        if tls.key.trim().is_empty() {
+            problem("server.tls.key", "must be non-empty".into());
+        }
+    }
+
+    if let Some(dead_letter) = &config.server.dead_letter
+        && dead_letter.path.trim().is_empty()
+    {
+        problem("server.dead_letter.path", "must be non-empty".into());
+    }
+
     // runtime.executor_parallelism > 0
     if config.runtime.executor_parallelism == 0 {
-        anyhow::bail!("runtime.executor_parallelism must be > 0");
+        problem("runtime.executor_parallelism", "must be > 0".into());
+    }
+    if config.runtime.rule_exec_timeout.as_duration().is_zero() {
+        problem("runtime.rule_exec_timeout", "must be > 0".into());
+    }
+    if let Some(interval) = &config.runtime.timeout_scan_interval
+        && interval.as_duration().is_zero()
+    {
+        problem(
+            "runtime.timeout_scan_interval",
+            "must be > 0 (omit it to derive from each rule's window)".into(),
+        );
+    }
+    if config.runtime.drain_timeout.as_duration().is_zero() {
+        problem("runtime.drain_timeout", "must be > 0".into());
     }
 
-    // Each window's max_window_bytes ≤ window_defaults.max_total_bytes
+    // Window memory budgets
     let max_total = config.window_defaults.max_total_bytes.as_bytes();
+    if config
+        .window_defaults
+        .evict_interval
+        .as_duration()
+        .is_zero()
+    {
+        problem("window_defaults.evict_interval", "must be > 0".into());
+    }
+    if config.window_defaults.max_window_bytes.as_bytes() > max_total {
+        problem(
+            "window_defaults.max_window_bytes",
+            format!(
+                "({}) exceeds window_defaults.max_total_bytes ({})",
+                config.window_defaults.max_window_bytes, config.window_defaults.max_total_bytes,
+            ),
+        );
+    }
+    // Each window's max_window_bytes ≤ window_defaults.max_total_bytes
     for w in &config.windows {
         if w.max_window_bytes.as_bytes() > max_total {
-            anyhow::bail!(
-                "window {:?}: max_window_bytes ({}) exceeds window_defaults.max_total_bytes ({})",
-                w.name,
-                w.max_window_bytes,
-                config.window_defaults.max_total_bytes,
+            problem(
+                &format!("window.{}.max_window_bytes", w.name),
+                format!(
+                    "({}) exceeds window_defaults.max_total_bytes ({})",
+                    w.max_window_bytes, config.window_defaults.max_total_bytes,
+                ),
             );
         }
     }
 
     // vars keys must be valid WFL identifiers: [A-Za-z_][A-Za-z0-9_]*
-    for key in config.vars.keys() {
+    let mut var_names: Vec<&String> = config.vars.keys().collect();
+    var_names.sort();
+    for key in var_names {
         if !is_valid_var_name(key) {
-            anyhow::bail!(
-                "vars: invalid variable name {:?} — must match [A-Za-z_][A-Za-z0-9_]*",
-                key,
+            problem(
+                &format!("vars.{key}"),
+                "invalid variable name — must match [A-Za-z_][A-Za-z0-9_]*".into(),
             );
         }
     }
 
     // sinks path must be non-empty
     if config.sinks.is_empty() {
-        anyhow::bail!("sinks must be a non-empty path to the sinks/ directory");
+        problem(
+            "sinks",
+            "must be a non-empty path to the sinks/ directory".into(),
+        );
+    }
+
+    // alert.suppress_ttl: omit the key to disable suppression
+    if let Some(ttl) = &config.alert.suppress_ttl
+        && ttl.as_duration().is_zero()
+    {
+        problem(
+            "alert.suppress_ttl",
+            "must be > 0 (omit it to disable suppression)".into(),
+        );
+    }
+
+    if let Some(precision) = config.alert.score_precision
+        && precision > 15
+    {
+        problem("alert.score_precision", "must be <= 15".into());
+    }
+    if config.alert.channel_capacity == 0 {
+        problem("alert.channel_capacity", "must be > 0".into());
+    }
+    if let Some(route) = &config.alert.priority_route
+        && route.target.trim().is_empty()
+    {
+        problem("alert.priority_route.target", "must be non-empty".into());
     }
 
     // metrics config sanity
     if config.metrics.report_interval.as_duration().is_zero() {
-        anyhow::bail!("metrics.report_interval must be > 0");
+        problem("metrics.report_interval", "must be > 0".into());
     }
     if config.metrics.topn.max == 0 {
-        anyhow::bail!("metrics.topn.max must be > 0");
+        problem("metrics.topn.max", "must be > 0".into());
     }
     if config.metrics.topn.queue_capacity == 0 {
-        anyhow::bail!("metrics.topn.queue_capacity must be > 0");
+        problem("metrics.topn.queue_capacity", "must be > 0".into());
     }
     if config.metrics.enabled {
-        if config.metrics.prometheus_listen.trim().is_empty() {
-            anyhow::bail!("metrics.prometheus_listen must be non-empty when metrics.enabled=true");
+        let listen = config.metrics.prometheus_listen.trim();
+        if listen.is_empty() {
+            problem(
+                "metrics.prometheus_listen",
+                "must be non-empty when metrics.enabled = true".into(),
+            );
+        } else {
+            // Must be host:port (no scheme).
+            if let Some(msg) = socket_addr_problem(listen) {
+                problem("metrics.prometheus_listen", msg);
+            }
+            if config.server.http_listen.as_deref().map(str::trim) == Some(listen) {
+                problem(
+                    "metrics.prometheus_listen",
+                    format!("{listen:?} is also used by server.http_listen"),
+                );
+            }
         }
-        // Must be host:port (no scheme).
-        if config
-            .metrics
-            .prometheus_listen
-            .to_socket_addrs()
-            .map_err(|e| anyhow::anyhow!("metrics.prometheus_listen invalid: {e}"))?
+    }
+
+    problems
+}
+
+/// `None` if `addr` is a resolvable `host:port`, else why not.
+fn socket_addr_problem(addr: &str) -> Option<String> {
+    match addr.to_socket_addrs() {
+        Err(e) => Some(format!("invalid: {e}")),
+        Ok(mut addrs) => addrs
             .next()
             .is_none()
-        {
-            anyhow::bail!("metrics.prometheus_listen resolved to no socket address");
-        }
+            .then(|| "resolved to no socket address".to_string()),
     }
-
-    Ok(())
 }
 
 /// A valid variable name starts with ASCII letter or underscore, followed by
@@ -100,18 +263,88 @@ pub fn validate_over_vs_over_cap(
     windows: &[WindowConfig],
     window_overs: &HashMap<String, Duration>,
 ) -> anyhow::Result<()> {
+    ensure_no_problems(check_over_vs_over_cap(windows, window_overs))
+}
+
+fn check_over_vs_over_cap(
+    windows: &[WindowConfig],
+    window_overs: &HashMap<String, Duration>,
+) -> Vec<ConfigProblem> {
+    // Sorted for a stable report order.
+    let window_overs: BTreeMap<_, _> = window_overs.iter().collect();
+    let mut problems = Vec::new();
     for (name, over) in window_overs {
-        let wc = windows.iter().find(|w| w.name == *name).ok_or_else(|| {
-            anyhow::anyhow!(
-                "window {name:?} found in .wfs schema but not in wfusion.toml [window.{name}]"
-            )
-        })?;
+        let Some(wc) = windows.iter().find(|w| w.name == *name) else {
+            problems.push(ConfigProblem::new(
+                format!("window.{name}"),
+                format!(
+                    "window {name:?} found in .wfs schema but not in wfusion.toml [window.{name}]"
+                ),
+            ));
+            continue;
+        };
         let cap: Duration = wc.over_cap.into();
         if *over > cap {
-            anyhow::bail!("window {name:?}: over ({over:?}) exceeds over_cap ({cap:?})",);
+            problems.push(ConfigProblem::new(
+                format!("window.{name}.over_cap"),
+                format!("window {name:?}: over ({over:?}) exceeds over_cap ({cap:?})"),
+            ));
+        }
+    }
+    problems
+}
+
+/// Cross-file validation of a whole deployment: config windows, the loaded
+/// `.wfs` schemas (from all files) and the compiled rules.
+///
+/// Reports duplicate window names across `.wfs` files, `over` vs
+/// `over_cap` violations, schema windows missing from `wfusion.toml`, and
+/// rules that bind an `over = 0` (static) window as an event source even
+/// though every rule evaluates over a time window.
+pub fn check_deployment(
+    windows: &[WindowConfig],
+    schemas: &[WindowSchema],
+    plans: &[RulePlan],
+) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+
+    let mut seen = HashMap::new();
+    for ws in schemas {
+        let count = seen.entry(ws.name.as_str()).or_insert(0usize);
+        *count += 1;
+        if *count == 2 {
+            problems.push(ConfigProblem::new(
+                "runtime.schemas",
+                format!(
+                    "window `{}` is declared in more than one .wfs file",
+                    ws.name
+                ),
+            ));
+        }
+    }
+
+    let window_overs: HashMap<String, Duration> = schemas
+        .iter()
+        .map(|ws| (ws.name.clone(), ws.over))
+        .collect();
+    problems.extend(check_over_vs_over_cap(windows, &window_overs));
+
+    for plan in plans {
+        for bind in &plan.binds {
+            if window_overs.get(&bind.window) == Some(&Duration::ZERO) {
+                problems.push(ConfigProblem::new(
+                    format!("window.{}", bind.window),
+                    format!(
+                        "rule `{}` binds `{}` as event source `{}`, but the window has over = 0 \
+                         (static set); give it a time field and over > 0, or use it via join",
+                        plan.name, bind.window, bind.alias
+                    ),
+                ));
+            }
         }
     }
-    Ok(())
+
+    problems
 }
 
 // ---------------------------------------------------------------------------
@@ -133,6 +366,7 @@ mod tests {
             watermark: HumanDuration::from(Duration::from_secs(5)),
             allowed_lateness: HumanDuration::from(Duration::from_secs(0)),
             late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
         }
     }
 
@@ -160,4 +394,104 @@ mod tests {
         overs.insert("unknown_window".into(), Duration::from_secs(300));
         assert!(validate_over_vs_over_cap(&windows, &overs).is_err());
     }
+
+    fn deployment(wfs: &[&str], wfl: &str) -> (Vec<WindowSchema>, Vec<RulePlan>) {
+        let schemas: Vec<WindowSchema> = wfs
+            .iter()
+            .flat_map(|src| wf_lang::parse_wfs(src).unwrap())
+            .collect();
+        let plans = wf_lang::compile_wfl(&wf_lang::parse_wfl(wfl).unwrap(), &schemas).unwrap();
+        (schemas, plans)
+    }
+
+    const AUTH_WFS: &str = r#"
+window auth_events {
+    stream = "auth"
+    time = event_time
+    over = 5m
+    fields {
+        sip: ip
+        event_time: time
+    }
+}
+"#;
+
+    const BLOCKLIST_WFS: &str = r#"
+window ip_blocklist {
+    over = 0
+    fields {
+        sip: ip
+    }
+}
+"#;
+
+    const RULE_WFL: &str = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield ip_blocklist (sip = e.sip)
+}
+"#;
+
+    #[test]
+    fn deployment_accepts_valid_setup() {
+        let (schemas, plans) = deployment(&[AUTH_WFS, BLOCKLIST_WFS], RULE_WFL);
+        let windows = vec![
+            sample_window("auth_events", 1800),
+            sample_window("ip_blocklist", 0),
+        ];
+        assert_eq!(check_deployment(&windows, &schemas, &plans), vec![]);
+    }
+
+    #[test]
+    fn deployment_reports_duplicate_windows_across_files() {
+        let (mut schemas, plans) = deployment(&[AUTH_WFS, BLOCKLIST_WFS], RULE_WFL);
+        schemas.extend(wf_lang::parse_wfs(BLOCKLIST_WFS).unwrap());
+        let windows = vec![
+            sample_window("auth_events", 1800),
+            sample_window("ip_blocklist", 0),
+        ];
+        let problems = check_deployment(&windows, &schemas, &plans);
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert_eq!(problems[0].key, "runtime.schemas");
+        assert!(problems[0].message.contains("ip_blocklist"));
+    }
+
+    #[test]
+    fn deployment_reports_static_window_bound_as_event_source() {
+        let wfl = r#"
+rule r {
+    events { b : ip_blocklist }
+    match<sip:5m> { on event { b | count >= 1; } } -> score(50.0)
+    entity(ip, b.sip)
+    yield ip_blocklist (sip = b.sip)
+}
+"#;
+        let (schemas, plans) = deployment(&[AUTH_WFS, BLOCKLIST_WFS], wfl);
+        let windows = vec![
+            sample_window("auth_events", 1800),
+            sample_window("ip_blocklist", 0),
+        ];
+        let problems = check_deployment(&windows, &schemas, &plans);
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert_eq!(problems[0].key, "window.ip_blocklist");
+        assert!(problems[0].message.contains("over = 0"));
+    }
+
+    #[test]
+    fn deployment_lists_every_problem() {
+        let (schemas, plans) = deployment(&[AUTH_WFS, BLOCKLIST_WFS], RULE_WFL);
+        // auth_events over (5m) > over_cap (1m); ip_blocklist has no [window.*].
+        let windows = vec![sample_window("auth_events", 60)];
+        let problems = check_deployment(&windows, &schemas, &plans);
+        let keys: Vec<&str> = problems.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(keys, ["window.auth_events.over_cap", "window.ip_blocklist"]);
+
+        let err = ensure_no_problems(problems).unwrap_err().to_string();
+        assert!(
+            err.starts_with("invalid configuration (2 problems):"),
+            "{err}"
+        );
+    }
 }
@@ -15,6 +15,25 @@ pub struct WindowDefaults {
     pub watermark: HumanDuration,
     pub allowed_lateness: HumanDuration,
     pub late_policy: LatePolicy,
+    /// Adjacent batches smaller than this are merged into one; `0B` disables
+    /// compaction.
+    #[serde(default)]
+    pub compact_below: ByteSize,
+}
+
+impl Default for WindowDefaults {
+    fn default() -> Self {
+        Self {
+            evict_interval: "30s".parse().expect("hardcoded duration must parse"),
+            max_window_bytes: "256MB".parse().expect("hardcoded size must parse"),
+            max_total_bytes: "2GB".parse().expect("hardcoded size must parse"),
+            evict_policy: EvictPolicy::TimeFirst,
+            watermark: "5s".parse().expect("hardcoded duration must parse"),
+            allowed_lateness: "0s".parse().expect("hardcoded duration must parse"),
+            late_policy: LatePolicy::Drop,
+            compact_below: ByteSize::default(),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -31,6 +50,7 @@ pub struct WindowOverride {
     pub watermark: Option<HumanDuration>,
     pub allowed_lateness: Option<HumanDuration>,
     pub late_policy: Option<LatePolicy>,
+    pub compact_below: Option<ByteSize>,
 }
 
 // ---------------------------------------------------------------------------
@@ -47,6 +67,7 @@ pub struct WindowConfig {
     pub watermark: HumanDuration,
     pub allowed_lateness: HumanDuration,
     pub late_policy: LatePolicy,
+    pub compact_below: ByteSize,
 }
 
 impl WindowOverride {
@@ -63,6 +84,7 @@ impl WindowOverride {
             watermark: self.watermark.unwrap_or(defaults.watermark),
             allowed_lateness: self.allowed_lateness.unwrap_or(defaults.allowed_lateness),
             late_policy: self.late_policy.unwrap_or(defaults.late_policy),
+            compact_below: self.compact_below.unwrap_or(defaults.compact_below),
         })
     }
 }
@@ -97,6 +119,7 @@ mod tests {
             watermark: "5s".parse().unwrap(),
             allowed_lateness: "0s".parse().unwrap(),
             late_policy: LatePolicy::Drop,
+            compact_below: "0B".parse().unwrap(),
         }
     }
 
@@ -142,6 +165,7 @@ mod tests {
             watermark: None,
             allowed_lateness: None,
             late_policy: None,
+            compact_below: None,
         };
         let defaults = sample_defaults();
         let wc = ovr.resolve("test".into(), &defaults).unwrap();
@@ -151,6 +175,7 @@ mod tests {
         assert_eq!(wc.watermark, defaults.watermark);
         assert_eq!(wc.allowed_lateness, defaults.allowed_lateness);
         assert_eq!(wc.late_policy, defaults.late_policy);
+        assert_eq!(wc.compact_below, defaults.compact_below);
     }
 
     #[test]
@@ -164,6 +189,7 @@ mod tests {
             watermark: Some("10s".parse().unwrap()),
             allowed_lateness: Some("30s".parse().unwrap()),
             late_policy: Some(LatePolicy::Revise),
+            compact_below: Some("64KB".parse().unwrap()),
         };
         let defaults = sample_defaults();
         let wc = ovr.resolve("test".into(), &defaults).unwrap();
@@ -173,5 +199,6 @@ mod tests {
         assert_eq!(wc.watermark, "10s".parse::<HumanDuration>().unwrap());
         assert_eq!(wc.allowed_lateness, "30s".parse::<HumanDuration>().unwrap());
         assert_eq!(wc.late_policy, LatePolicy::Revise);
+        assert_eq!(wc.compact_below, "64KB".parse::<ByteSize>().unwrap());
     }
 }
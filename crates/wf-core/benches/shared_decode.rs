@@ -0,0 +1,103 @@
+//! Arrow → Event decoding cost for several rules reading one window:
+//! every rule decoding the batch itself vs. sharing one decode through
+//! `Window::read_shared_since`.
+//!
+//! ```sh
+//! cargo bench -p wf-core --bench shared_decode
+//! ```
+
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::array::{Int64Array, StringArray, TimestampNanosecondArray};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use wf_config::{DistMode, EvictPolicy, LatePolicy, WindowConfig};
+use wf_core::rule::batch_to_events;
+use wf_core::window::{Window, WindowParams};
+
+const ROWS: usize = 4096;
+
+fn batch() -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        Field::new("sip", DataType::Utf8, false),
+        Field::new("action", DataType::Utf8, false),
+        Field::new("bytes", DataType::Int64, false),
+    ]));
+    let ts: Vec<i64> = (0..ROWS as i64).map(|i| 1_000_000_000 + i).collect();
+    let sip: Vec<String> = (0..ROWS)
+        .map(|i| format!("10.0.{}.{}", i / 256, i % 256))
+        .collect();
+    let action: Vec<&str> = (0..ROWS)
+        .map(|i| if i % 3 == 0 { "failed" } else { "ok" })
+        .collect();
+    let bytes: Vec<i64> = (0..ROWS as i64).map(|i| i * 17).collect();
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(TimestampNanosecondArray::from(ts)),
+            Arc::new(StringArray::from(sip)),
+            Arc::new(StringArray::from(action)),
+            Arc::new(Int64Array::from(bytes)),
+        ],
+    )
+    .unwrap()
+}
+
+fn window(batch: &RecordBatch) -> Window {
+    let config = WindowConfig {
+        name: "bench".into(),
+        mode: DistMode::Local,
+        max_window_bytes: usize::MAX.into(),
+        over_cap: Duration::from_secs(3600).into(),
+        evict_policy: EvictPolicy::TimeFirst,
+        watermark: Duration::from_secs(0).into(),
+        allowed_lateness: Duration::from_secs(3600).into(),
+        late_policy: LatePolicy::Drop,
+        compact_below: 0.into(),
+    };
+    let mut win = Window::new(
+        WindowParams {
+            name: "bench".into(),
+            schema: batch.schema(),
+            time_col_index: Some(0),
+            over: Duration::from_secs(3600),
+        },
+        config,
+    );
+    win.append(batch.clone()).unwrap();
+    win
+}
+
+fn decode(c: &mut Criterion) {
+    let batch = batch();
+    let win = window(&batch);
+    let mut group = c.benchmark_group("decode_per_window_batch");
+    for rules in [1usize, 4, 16] {
+        group.bench_with_input(BenchmarkId::new("per_rule", rules), &rules, |b, &rules| {
+            b.iter(|| {
+                for _ in 0..rules {
+                    let (batches, _, _) = win.read_since(0);
+                    black_box(batch_to_events(&batches[0]));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("shared", rules), &rules, |b, &rules| {
+            b.iter(|| {
+                // Rules hold their events while they advance, so the reads
+                // overlap as they do in concurrently woken rule tasks.
+                let held: Vec<_> = (0..rules)
+                    .map(|_| win.read_shared_since(0).0[0].events())
+                    .collect();
+                black_box(held);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -0,0 +1,236 @@
+use wf_config::sink::AlertSerializer;
+
+use super::types::OutputRecord;
+
+const VENDOR: &str = "WarpFusion";
+const PRODUCT: &str = "wp-reactor";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Render `record` in a non-JSON SIEM format.
+///
+/// Returns `None` for [`AlertSerializer::Json`]; the alert task serializes
+/// JSON once per alert and shares it across every JSON sink.
+pub fn render_alert(record: &OutputRecord, serializer: AlertSerializer) -> Option<String> {
+    match serializer {
+        AlertSerializer::Json => None,
+        AlertSerializer::Cef => Some(to_cef(record)),
+        AlertSerializer::Leef => Some(to_leef(record)),
+    }
+}
+
+/// Render as an ArcSight CEF line.
+///
+/// Header: `CEF:0|WarpFusion|wp-reactor|<version>|<rule_name>|<summary>|<severity>|`,
+/// where severity is `score` scaled from `[0, 100]` to `[0, 10]`. System
+/// fields follow as extensions; `ip` entities additionally populate `src`.
+pub fn to_cef(record: &OutputRecord) -> String {
+    let name = if record.summary.is_empty() {
+        &record.rule_name
+    } else {
+        &record.summary
+    };
+    let mut line = format!(
+        "CEF:0|{VENDOR}|{PRODUCT}|{VERSION}|{}|{}|{}|",
+        cef_header(&record.rule_name),
+        cef_header(name),
+        severity(record.score),
+    );
+
+    let mut ext = vec![
+        ("rt", (record.event_time_nanos / 1_000_000).to_string()),
+        ("externalId", record.wfx_id.clone()),
+        ("cat", record.origin.as_str().to_string()),
+        ("cs1Label", "entityType".to_string()),
+        ("cs1", record.entity_type.clone()),
+        ("cs2Label", "entityId".to_string()),
+        ("cs2", record.entity_id.clone()),
+        ("cn1Label", "score".to_string()),
+        ("cn1", record.score.to_string()),
+    ];
+    if record.priority != 0 {
+        ext.push(("cn2Label", "priority".to_string()));
+        ext.push(("cn2", record.priority.to_string()));
+    }
+    if record.entity_type == "ip" {
+        ext.push(("src", record.entity_id.clone()));
+    }
+
+    let ext: Vec<String> = ext
+        .into_iter()
+        .map(|(k, v)| format!("{k}={}", cef_extension(&v)))
+        .collect();
+    line.push_str(&ext.join(" "));
+    line
+}
+
+/// Render as a QRadar LEEF 1.0 line (tab-delimited attributes).
+///
+/// Header: `LEEF:1.0|WarpFusion|wp-reactor|<version>|<rule_name>|`.
+pub fn to_leef(record: &OutputRecord) -> String {
+    let mut line = format!(
+        "LEEF:1.0|{VENDOR}|{PRODUCT}|{VERSION}|{}|",
+        leef_header(&record.rule_name),
+    );
+
+    let mut attrs = vec![
+        ("devTime", record.fired_at.clone()),
+        ("devTimeFormat", "yyyy-MM-dd'T'HH:mm:ss.SSSX".to_string()),
+        ("sev", severity(record.score).max(1).to_string()),
+        ("cat", record.origin.as_str().to_string()),
+        ("wfxId", record.wfx_id.clone()),
+        ("entityType", record.entity_type.clone()),
+        ("entityId", record.entity_id.clone()),
+        ("score", record.score.to_string()),
+        ("summary", record.summary.clone()),
+    ];
+    if record.priority != 0 {
+        attrs.push(("priority", record.priority.to_string()));
+    }
+    if record.entity_type == "ip" {
+        attrs.push(("src", record.entity_id.clone()));
+    }
+
+    let attrs: Vec<String> = attrs
+        .into_iter()
+        .map(|(k, v)| format!("{k}={}", leef_attribute(&v)))
+        .collect();
+    line.push_str(&attrs.join("\t"));
+    line
+}
+
+/// Map a `[0, 100]` score onto the `[0, 10]` CEF/LEEF severity scale.
+fn severity(score: f64) -> u8 {
+    (score / 10.0).round().clamp(0.0, 10.0) as u8
+}
+
+/// CEF header fields escape `\` and `|`; line breaks would split the record.
+fn cef_header(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '|' => out.push_str("\\|"),
+            '\r' | '\n' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// CEF extension values escape `\` and `=`, and encode line breaks.
+fn cef_extension(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '=' => out.push_str("\\="),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// LEEF header fields use the same escaping as CEF headers.
+fn leef_header(s: &str) -> String {
+    cef_header(s)
+}
+
+/// LEEF attribute values must not contain the tab delimiter or line breaks.
+fn leef_attribute(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\t' | '\r' | '\n' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::AlertOrigin;
+
+    fn sample_alert() -> OutputRecord {
+        OutputRecord {
+            wfx_id: "9f2c1a7b3e4d5f60".into(),
+            rule_name: "brute_force".into(),
+            score: 85.0,
+            entity_type: "ip".into(),
+            entity_id: "10.0.0.1".into(),
+            origin: AlertOrigin::Event,
+            fired_at: "2024-01-01T00:00:00.000Z".into(),
+            matched_rows: vec![],
+            summary: "5 failed logins | key=a=b".into(),
+            priority: 0,
+            yield_target: "alerts".into(),
+            yield_fields: vec![],
+            event_time_nanos: 1_704_067_200_000_000_000,
+        }
+    }
+
+    #[test]
+    fn cef_line_is_well_formed() {
+        let line = to_cef(&sample_alert());
+        assert_eq!(
+            line,
+            format!(
+                "CEF:0|WarpFusion|wp-reactor|{VERSION}|brute_force|5 failed logins \\| key=a=b|9|\
+                 rt=1704067200000 externalId=9f2c1a7b3e4d5f60 cat=event \
+                 cs1Label=entityType cs1=ip cs2Label=entityId cs2=10.0.0.1 \
+                 cn1Label=score cn1=85 src=10.0.0.1"
+            )
+        );
+
+        // Seven unescaped `|` separate the eight header fields.
+        let unescaped = line
+            .char_indices()
+            .filter(|&(i, c)| c == '|' && !line[..i].ends_with('\\'))
+            .count();
+        assert_eq!(unescaped, 7);
+    }
+
+    #[test]
+    fn cef_escapes_extension_values() {
+        let mut alert = sample_alert();
+        alert.entity_type = "user".into();
+        alert.entity_id = "a=b\\c\nd".into();
+        alert.priority = 3;
+        let line = to_cef(&alert);
+        assert!(line.contains("cs2=a\\=b\\\\c\\nd"));
+        assert!(line.ends_with("cn2Label=priority cn2=3"));
+        assert!(!line.contains("src="));
+    }
+
+    #[test]
+    fn leef_line_is_tab_delimited() {
+        let line = to_leef(&sample_alert());
+        let fields: Vec<&str> = line.splitn(6, '|').collect();
+        assert_eq!(
+            fields[..5],
+            [
+                "LEEF:1.0",
+                "WarpFusion",
+                "wp-reactor",
+                VERSION,
+                "brute_force"
+            ]
+        );
+        let attrs: Vec<&str> = fields[5].split('\t').collect();
+        assert_eq!(attrs[0], "devTime=2024-01-01T00:00:00.000Z");
+        assert!(attrs.contains(&"sev=9"));
+        assert!(attrs.contains(&"summary=5 failed logins | key=a=b"));
+        assert!(attrs.contains(&"src=10.0.0.1"));
+    }
+
+    #[test]
+    fn json_is_not_rendered_here() {
+        assert!(render_alert(&sample_alert(), AlertSerializer::Json).is_none());
+        assert!(
+            render_alert(&sample_alert(), AlertSerializer::Cef)
+                .unwrap()
+                .starts_with("CEF:0|")
+        );
+    }
+}
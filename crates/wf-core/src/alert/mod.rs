@@ -1,3 +1,5 @@
+mod format;
 mod types;
 
+pub use format::{render_alert, to_cef, to_leef};
 pub use types::{AlertOrigin, OutputRecord};
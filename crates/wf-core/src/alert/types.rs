@@ -90,6 +90,9 @@ pub struct OutputRecord {
     pub matched_rows: Vec<RecordBatch>,
     /// Human-readable summary of the alert.
     pub summary: String,
+    /// Rule priority from `meta { priority = "N" }`; omitted when 0.
+    #[serde(skip_serializing_if = "is_default_priority")]
+    pub priority: i64,
     /// Yield target window name, used for sink routing.
     #[serde(skip)]
     pub yield_target: String,
@@ -100,3 +103,7 @@ pub struct OutputRecord {
     #[serde(skip)]
     pub event_time_nanos: i64,
 }
+
+fn is_default_priority(priority: &i64) -> bool {
+    *priority == 0
+}
@@ -10,7 +10,9 @@ use wf_lang::plan::RulePlan;
 
 use crate::alert::OutputRecord;
 use crate::rule::match_engine::eval_expr;
-use crate::rule::{CepStateMachine, CloseReason, Event, RuleExecutor, StepResult, Value};
+use crate::rule::{
+    CepStateMachine, CloseReason, Event, RuleExecutor, StepResult, Value, bind_filter_passes,
+};
 
 /// Result of running a single test block against a rule.
 pub struct TestResult {
@@ -89,13 +91,22 @@ fn execute_test_run(
                     alias
                 };
 
-                match sm.advance_at(use_alias, &event, current_nanos) {
-                    StepResult::Matched(ctx) => {
-                        if let Ok(alert) = executor.execute_match(&ctx) {
-                            alerts.push(alert);
+                // Events rejected by the bind filter never reach the machine,
+                // as in the engine.
+                let filter = plan
+                    .binds
+                    .iter()
+                    .find(|b| b.alias == *use_alias)
+                    .and_then(|b| b.filter.as_ref());
+                if filter.is_none_or(|f| bind_filter_passes(f, &event)) {
+                    match sm.advance_at(use_alias, &event, current_nanos) {
+                        StepResult::Matched(ctx) => {
+                            if let Ok(records) = executor.execute_match(&ctx) {
+                                alerts.extend(records);
+                            }
                         }
+                        StepResult::Advance | StepResult::Accumulate => {}
                     }
-                    StepResult::Advance | StepResult::Accumulate => {}
                 }
 
                 current_nanos += 1_000_000_000;
@@ -104,8 +115,8 @@ fn execute_test_run(
                 current_nanos += dur.as_nanos() as i64;
                 let expired = sm.scan_expired_at_with_conv(current_nanos, conv_plan);
                 for close in expired {
-                    if let Ok(Some(alert)) = executor.execute_close(&close) {
-                        alerts.push(alert);
+                    if let Ok(records) = executor.execute_close(&close) {
+                        alerts.extend(records);
                     }
                 }
             }
@@ -117,8 +128,8 @@ fn execute_test_run(
     match close_trigger {
         None | Some(CloseTrigger::Eos) => {
             for close in sm.close_all_with_conv(CloseReason::Eos, conv_plan) {
-                if let Ok(Some(alert)) = executor.execute_close(&close) {
-                    alerts.push(alert);
+                if let Ok(records) = executor.execute_close(&close) {
+                    alerts.extend(records);
                 }
             }
         }
@@ -126,22 +137,22 @@ fn execute_test_run(
             current_nanos += 86_400_000_000_000i64;
             let expired = sm.scan_expired_at_with_conv(current_nanos, conv_plan);
             for close in expired {
-                if let Ok(Some(alert)) = executor.execute_close(&close) {
-                    alerts.push(alert);
+                if let Ok(records) = executor.execute_close(&close) {
+                    alerts.extend(records);
                 }
             }
         }
         Some(CloseTrigger::Flush) => {
             for close in sm.close_all_with_conv(CloseReason::Flush, conv_plan) {
-                if let Ok(Some(alert)) = executor.execute_close(&close) {
-                    alerts.push(alert);
+                if let Ok(records) = executor.execute_close(&close) {
+                    alerts.extend(records);
                 }
             }
         }
         _ => {
             for close in sm.close_all_with_conv(CloseReason::Eos, conv_plan) {
-                if let Ok(Some(alert)) = executor.execute_close(&close) {
-                    alerts.push(alert);
+                if let Ok(records) = executor.execute_close(&close) {
+                    alerts.extend(records);
                 }
             }
         }
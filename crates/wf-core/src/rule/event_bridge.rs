@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use arrow::array::{
-    Array, BooleanArray, Float64Array, Int64Array, StringArray, TimestampNanosecondArray,
+    Array, BooleanArray, Float64Array, Int64Array, ListArray, StringArray, TimestampNanosecondArray,
 };
 use arrow::datatypes::{DataType, TimeUnit};
 use arrow::record_batch::RecordBatch;
@@ -15,11 +15,11 @@ use super::match_engine::{Event, Value};
 ///
 /// | Arrow Type           | → | CEP Value               |
 /// |----------------------|---|-------------------------|
-/// | Int64                | → | Value::Number(i as f64) |
+/// | Int64                | → | Value::Int(i)           |
 /// | Float64              | → | Value::Number(f)        |
 /// | Utf8                 | → | Value::Str(s)           |
 /// | Boolean              | → | Value::Bool(b)          |
-/// | Timestamp(Ns, _)     | → | Value::Number(ns as f64)|
+/// | Timestamp(Ns, _)     | → | Value::Int(ns)          |
 pub fn batch_to_events(batch: &RecordBatch) -> Vec<Event> {
     let num_rows = batch.num_rows();
     let schema = batch.schema();
@@ -84,7 +84,7 @@ fn extract_value(col: &dyn Array, row: usize) -> Option<Value> {
     match col.data_type() {
         DataType::Int64 => {
             let arr = col.as_any().downcast_ref::<Int64Array>()?;
-            Some(Value::Number(arr.value(row) as f64))
+            Some(Value::Int(arr.value(row)))
         }
         DataType::Float64 => {
             let arr = col.as_any().downcast_ref::<Float64Array>()?;
@@ -100,7 +100,18 @@ fn extract_value(col: &dyn Array, row: usize) -> Option<Value> {
         }
         DataType::Timestamp(TimeUnit::Nanosecond, _) => {
             let arr = col.as_any().downcast_ref::<TimestampNanosecondArray>()?;
-            Some(Value::Number(arr.value(row) as f64))
+            Some(Value::Int(arr.value(row)))
+        }
+        DataType::List(_) => {
+            let arr = col.as_any().downcast_ref::<ListArray>()?;
+            let items = arr.value(row);
+            // Null and unsupported elements are skipped.
+            Some(Value::Array(
+                (0..items.len())
+                    .filter(|&i| !items.is_null(i))
+                    .filter_map(|i| extract_value(items.as_ref(), i))
+                    .collect(),
+            ))
         }
         _ => None,
     }
@@ -141,11 +152,11 @@ mod tests {
         let events = batch_to_events(&batch);
         assert_eq!(events.len(), 2);
 
-        assert_eq!(events[0].fields["id"], Value::Number(42.0));
+        assert_eq!(events[0].fields["id"], Value::Int(42));
         assert_eq!(events[0].fields["name"], Value::Str("alice".to_string()));
         assert_eq!(events[0].fields["active"], Value::Bool(true));
 
-        assert_eq!(events[1].fields["id"], Value::Number(99.0));
+        assert_eq!(events[1].fields["id"], Value::Int(99));
         assert_eq!(events[1].fields["name"], Value::Str("bob".to_string()));
         assert_eq!(events[1].fields["active"], Value::Bool(false));
     }
@@ -157,7 +168,7 @@ mod tests {
             DataType::Timestamp(TimeUnit::Nanosecond, None),
             false,
         )]);
-        let nanos: i64 = 1_700_000_000_000_000_000;
+        let nanos: i64 = 1_700_000_000_123_456_789;
         let batch = RecordBatch::try_new(
             schema,
             vec![Arc::new(TimestampNanosecondArray::from(vec![nanos])) as ArrayRef],
@@ -166,7 +177,7 @@ mod tests {
 
         let events = batch_to_events(&batch);
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].fields["ts"], Value::Number(nanos as f64));
+        assert_eq!(events[0].fields["ts"], Value::Int(nanos));
     }
 
     #[test]
@@ -188,7 +199,7 @@ mod tests {
         assert_eq!(events.len(), 2);
 
         // Row 0: id=1, name is null (skipped)
-        assert_eq!(events[0].fields["id"], Value::Number(1.0));
+        assert_eq!(events[0].fields["id"], Value::Int(1));
         assert!(!events[0].fields.contains_key("name"));
 
         // Row 1: id is null (skipped), name="bob"
@@ -209,6 +220,31 @@ mod tests {
         assert!(events.is_empty());
     }
 
+    #[test]
+    fn test_batch_to_events_list() {
+        use arrow::array::{ListBuilder, StringBuilder};
+
+        let mut builder = ListBuilder::new(StringBuilder::new());
+        builder.values().append_value("admin");
+        builder.values().append_null();
+        builder.values().append_value("ops");
+        builder.append(true);
+        builder.append(true);
+        let tags = builder.finish();
+        let schema = make_schema(vec![Field::new("tags", tags.data_type().clone(), false)]);
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(tags) as ArrayRef]).unwrap();
+
+        let events = batch_to_events(&batch);
+        assert_eq!(
+            events[0].fields["tags"],
+            Value::Array(vec![
+                Value::Str("admin".to_string()),
+                Value::Str("ops".to_string()),
+            ])
+        );
+        assert_eq!(events[1].fields["tags"], Value::Array(vec![]));
+    }
+
     #[test]
     fn test_batch_to_events_float64() {
         let schema = make_schema(vec![Field::new("score", DataType::Float64, false)]);
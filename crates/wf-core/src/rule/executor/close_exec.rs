@@ -7,7 +7,7 @@ use crate::rule::match_engine::{CloseOutput, Event, StepData, WindowLookup};
 use super::RuleExecutor;
 use super::alert::{build_summary, build_wfx_id, format_nanos_utc};
 use super::context::{build_eval_context, execute_joins};
-use super::eval::{eval_entity_id, eval_score, eval_yield_expr};
+use super::eval::{eval_entity_id, eval_score, round_score};
 
 /// Check whether a close output qualifies to produce an alert.
 fn is_qualified(close: &CloseOutput) -> bool {
@@ -24,12 +24,13 @@ fn is_qualified(close: &CloseOutput) -> bool {
 }
 
 impl RuleExecutor {
-    /// Produce an [`OutputRecord`] from a close output (L1 — no joins).
+    /// Produce the [`OutputRecord`]s of a close output (L1 — no joins), one
+    /// per `yield` target.
     ///
-    /// Returns `Ok(None)` when the instance did not qualify for an alert.
-    pub fn execute_close(&self, close: &CloseOutput) -> CoreResult<Option<OutputRecord>> {
+    /// Returns an empty vec when the instance did not qualify for an alert.
+    pub fn execute_close(&self, close: &CloseOutput) -> CoreResult<Vec<OutputRecord>> {
         if !is_qualified(close) {
-            return Ok(None);
+            return Ok(vec![]);
         }
         let all_step_data = combine_step_data(close);
         let step_plans = combine_step_plans(self, close);
@@ -38,18 +39,20 @@ impl RuleExecutor {
             &close.scope_key,
             &all_step_data,
             &step_plans,
+            &self.plan.match_plan.captures,
+            &close.captures,
         );
         self.build_close_alert(close, &all_step_data, &ctx)
     }
 
-    /// Produce an [`OutputRecord`] from a close output with join support.
+    /// Produce the [`OutputRecord`]s of a close output with join support.
     pub fn execute_close_with_joins(
         &self,
         close: &CloseOutput,
         windows: &dyn WindowLookup,
-    ) -> CoreResult<Option<OutputRecord>> {
+    ) -> CoreResult<Vec<OutputRecord>> {
         if !is_qualified(close) {
-            return Ok(None);
+            return Ok(vec![]);
         }
         let all_step_data = combine_step_data(close);
         let step_plans = combine_step_plans(self, close);
@@ -58,19 +61,26 @@ impl RuleExecutor {
             &close.scope_key,
             &all_step_data,
             &step_plans,
+            &self.plan.match_plan.captures,
+            &close.captures,
         );
-        execute_joins(&self.plan.joins, &mut ctx, windows, close.last_event_nanos);
+        if !execute_joins(&self.plan.joins, &mut ctx, windows, close.last_event_nanos) {
+            return Ok(vec![]);
+        }
         self.build_close_alert(close, &all_step_data, &ctx)
     }
 
-    /// Internal: build the OutputRecord from an already-constructed eval context.
+    /// Internal: build the OutputRecords from an already-constructed eval context.
     fn build_close_alert(
         &self,
         close: &CloseOutput,
         all_step_data: &[StepData],
         ctx: &Event,
-    ) -> CoreResult<Option<OutputRecord>> {
-        let score = eval_score(&self.plan.score_plan.expr, ctx)?;
+    ) -> CoreResult<Vec<OutputRecord>> {
+        let score = round_score(
+            eval_score(&self.plan.score_plan.expr, ctx)?,
+            self.score_precision,
+        );
         let entity_id = eval_entity_id(&self.plan.entity_plan.entity_id_expr, ctx)?;
         let origin = AlertOrigin::Close {
             reason: close.close_reason,
@@ -90,18 +100,7 @@ impl RuleExecutor {
             all_step_data,
             &origin,
         );
-        let yield_fields = self
-            .plan
-            .yield_plan
-            .fields
-            .iter()
-            .filter_map(|field| {
-                let value = eval_yield_expr(&field.value, ctx)?;
-                Some((field.name.clone(), value))
-            })
-            .collect();
-
-        Ok(Some(OutputRecord {
+        let shared = OutputRecord {
             wfx_id,
             rule_name: self.plan.name.clone(),
             score,
@@ -111,10 +110,12 @@ impl RuleExecutor {
             fired_at,
             matched_rows: vec![],
             summary,
-            yield_target: self.plan.yield_plan.target.clone(),
-            yield_fields,
+            priority: self.plan.priority,
+            yield_target: String::new(),
+            yield_fields: vec![],
             event_time_nanos: close.last_event_nanos,
-        }))
+        };
+        Ok(self.fan_out(shared, ctx))
     }
 }
 
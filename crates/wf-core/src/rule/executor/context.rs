@@ -1,10 +1,10 @@
 use std::time::Duration;
 
-use wf_lang::ast::{FieldRef, JoinMode};
-use wf_lang::plan::{JoinCondPlan, JoinPlan, StepPlan};
+use wf_lang::ast::{AsofDirection, FieldRef, JoinMode};
+use wf_lang::plan::{CapturePlan, JoinCondPlan, JoinPlan, StepPlan};
 
 use crate::rule::match_engine::{
-    Event, StepData, Value, WindowLookup, field_ref_name, values_equal,
+    CapturedValue, Event, StepData, Value, WindowLookup, compare_values, field_ref_name,
 };
 
 /// Build a synthetic [`Event`] from match context for expression evaluation.
@@ -13,11 +13,15 @@ use crate::rule::match_engine::{
 /// - Adds step labels as fields → `label` → `Value::Number(measure_value)`
 /// - Labels that collide with key names are silently skipped (keys take priority)
 /// - Adds `_step_{i}_values` fields with collected values for L3 functions
+/// - Adds `_first_{alias}.{field}` / `_last_{alias}.{field}` for each
+///   captured value (`first()` / `last()`)
 pub(super) fn build_eval_context(
     keys: &[FieldRef],
     scope_key: &[Value],
     step_data: &[StepData],
     step_plans: &[&StepPlan],
+    capture_plans: &[CapturePlan],
+    captures: &[Option<CapturedValue>],
 ) -> Event {
     let mut fields = std::collections::HashMap::new();
 
@@ -47,23 +51,40 @@ pub(super) fn build_eval_context(
         }
     }
 
+    for (cp, cv) in capture_plans.iter().zip(captures) {
+        if let Some(cv) = cv {
+            fields.insert(capture_key("first", &cp.alias, &cp.field), cv.first.clone());
+            fields.insert(capture_key("last", &cp.alias, &cp.field), cv.last.clone());
+        }
+    }
+
     Event { fields }
 }
 
+/// Context field holding a captured `first` / `last` value.
+pub(super) fn capture_key(func: &str, alias: &str, field: &str) -> String {
+    format!("_{func}_{alias}.{field}")
+}
+
 /// Execute join plans, enriching the eval context with joined fields.
 ///
 /// For each join, dispatches on join mode:
 /// - `Snapshot`: snapshots all rows and finds the first condition-matching row.
-/// - `Asof`: gets timestamped rows, filters by time proximity, picks the latest match.
+/// - `Asof`: gets timestamped rows, filters by time proximity, and picks the
+///   nearest match on the configured side of the event time.
 ///
 /// Matched fields are added to the context both as `window.field` (qualified)
 /// and as plain `field` (if not already present).
+///
+/// Returns `false` when a non-`optional` asof join finds no row in an
+/// available right window: the match must then produce no output. Joins
+/// whose window is unavailable are skipped either way.
 pub(super) fn execute_joins(
     joins: &[JoinPlan],
     ctx: &mut Event,
     windows: &dyn WindowLookup,
     event_time_nanos: i64,
-) {
+) -> bool {
     for join in joins {
         let matched_row = match &join.mode {
             JoinMode::Snapshot => {
@@ -72,11 +93,18 @@ pub(super) fn execute_joins(
                 };
                 find_matching_row(&rows, &join.conds, ctx)
             }
-            JoinMode::Asof { within } => {
+            JoinMode::Asof { direction, within } => {
                 let Some(rows) = windows.snapshot_with_timestamps(&join.right_window) else {
                     continue;
                 };
-                find_asof_row(&rows, &join.conds, ctx, event_time_nanos, within.as_ref())
+                find_asof_row(
+                    &rows,
+                    &join.conds,
+                    ctx,
+                    event_time_nanos,
+                    *direction,
+                    within.as_ref(),
+                )
             }
             _ => {
                 // Unknown join mode — skip gracefully
@@ -85,6 +113,10 @@ pub(super) fn execute_joins(
         };
 
         let Some(row) = matched_row else {
+            // Joined fields stay unset, i.e. null for coalesce / isnull.
+            if matches!(join.mode, JoinMode::Asof { .. }) && !join.optional {
+                return false;
+            }
             continue;
         };
 
@@ -96,6 +128,7 @@ pub(super) fn execute_joins(
                 .or_insert_with(|| value.clone());
         }
     }
+    true
 }
 
 /// Find the first row matching all join conditions.
@@ -109,30 +142,56 @@ fn find_matching_row(
         .cloned()
 }
 
-/// Find the latest row that matches all conditions AND has timestamp <= event_time.
-/// If `within` is specified, also require timestamp >= event_time - within.
+/// Find the row nearest to `event_time` that matches all conditions.
+///
+/// - `Backward`: latest row with timestamp <= event_time; `within` bounds it
+///   to timestamp >= event_time - within.
+/// - `Forward`: earliest row with timestamp >= event_time; `within` bounds it
+///   to timestamp <= event_time + within.
+///
+/// Rows sharing the winning timestamp resolve to the one appended last
+/// (snapshot order), in both directions.
 fn find_asof_row(
     rows: &[(i64, std::collections::HashMap<String, Value>)],
     conds: &[JoinCondPlan],
     ctx: &Event,
     event_time_nanos: i64,
+    direction: AsofDirection,
     within: Option<&Duration>,
 ) -> Option<std::collections::HashMap<String, Value>> {
-    let min_ts = within
-        .map(|d| {
-            let nanos = i64::try_from(d.as_nanos()).unwrap_or(i64::MAX);
-            event_time_nanos.saturating_sub(nanos)
-        })
-        .unwrap_or(i64::MIN);
+    let within_nanos = within.map(|d| i64::try_from(d.as_nanos()).unwrap_or(i64::MAX));
+    let (min_ts, max_ts) = match direction {
+        AsofDirection::Backward => (
+            within_nanos
+                .map(|n| event_time_nanos.saturating_sub(n))
+                .unwrap_or(i64::MIN),
+            event_time_nanos,
+        ),
+        AsofDirection::Forward => (
+            event_time_nanos,
+            within_nanos
+                .map(|n| event_time_nanos.saturating_add(n))
+                .unwrap_or(i64::MAX),
+        ),
+    };
 
-    rows.iter()
-        .filter(|(ts, _)| *ts <= event_time_nanos && *ts >= min_ts)
-        .filter(|(_, row)| row_matches_conds(row, conds, ctx))
-        .max_by_key(|(ts, _)| *ts)
-        .map(|(_, row)| row.clone())
+    let candidates = rows
+        .iter()
+        .filter(|(ts, _)| *ts >= min_ts && *ts <= max_ts)
+        .filter(|(_, row)| row_matches_conds(row, conds, ctx));
+    // `max_by_key` keeps the last maximum; iterate in reverse for `min_by_key`
+    // so it also keeps the last-appended row among equal timestamps.
+    let best = match direction {
+        AsofDirection::Backward => candidates.max_by_key(|(ts, _)| *ts),
+        AsofDirection::Forward => candidates.rev().min_by_key(|(ts, _)| *ts),
+    };
+    best.map(|(_, row)| row.clone())
 }
 
 /// Check whether a row satisfies all join conditions against the current context.
+///
+/// Every condition is checked per row; there is no equality index, so
+/// ordering predicates cost the same as `==`.
 fn row_matches_conds(
     row: &std::collections::HashMap<String, Value>,
     conds: &[JoinCondPlan],
@@ -142,7 +201,7 @@ fn row_matches_conds(
         let left_name = field_ref_name(&cond.left);
         let right_name = field_ref_name(&cond.right);
         match (ctx.fields.get(left_name), row.get(right_name)) {
-            (Some(lv), Some(rv)) => values_equal(lv, rv),
+            (Some(lv), Some(rv)) => compare_values(cond.op, lv, rv),
             _ => false,
         }
     })
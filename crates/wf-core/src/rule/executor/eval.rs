@@ -3,9 +3,12 @@ use orion_error::prelude::*;
 
 use crate::error::{CoreReason, CoreResult};
 use crate::rule::match_engine::{
-    Event, Value, eval_expr, field_ref_name, value_to_string, values_equal,
+    Event, Value, array_contains, concat_values, eval_expr, field_ref_name, int_arithmetic,
+    value_to_string, values_equal,
 };
 
+use super::context::capture_key;
+
 /// Evaluate a yield/derive expression with L3 function support.
 ///
 /// L3 functions (collect_set, collect_list, first, last, stddev, percentile)
@@ -22,9 +25,16 @@ fn eval_expr_with_l3(expr: &wf_lang::ast::Expr, ctx: &Event) -> Option<Value> {
         Expr::Number(n) => Some(Value::Number(*n)),
         Expr::StringLit(s) => Some(Value::Str(s.clone())),
         Expr::Bool(b) => Some(Value::Bool(*b)),
+        // Time values are epoch nanoseconds, so durations compare in nanos too.
+        Expr::Duration(d) => Some(Value::Number(d.as_nanos() as f64)),
         Expr::Field(fr) => ctx.fields.get(field_ref_name(fr)).cloned(),
         Expr::Neg(inner) => match eval_expr_with_l3(inner, ctx)? {
             Value::Number(n) => Some(Value::Number(-n)),
+            Value::Int(i) => i.checked_neg().map(Value::Int),
+            _ => None,
+        },
+        Expr::Not(inner) => match eval_expr_with_l3(inner, ctx)? {
+            Value::Bool(b) => Some(Value::Bool(!b)),
             _ => None,
         },
         Expr::BinOp { op, left, right } => match op {
@@ -38,6 +48,14 @@ fn eval_expr_with_l3(expr: &wf_lang::ast::Expr, ctx: &Event) -> Option<Value> {
             BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod => {
                 let lv = eval_expr_with_l3(left, ctx)?;
                 let rv = eval_expr_with_l3(right, ctx)?;
+                if *op == BinOp::Add
+                    && let Some(joined) = concat_values(&lv, &rv)
+                {
+                    return Some(joined);
+                }
+                if let Some(exact) = int_arithmetic(*op, &lv, &rv) {
+                    return Some(exact);
+                }
                 let ln = coerce_to_f64(&lv)?;
                 let rn = coerce_to_f64(&rv)?;
                 let out = match op {
@@ -75,6 +93,18 @@ fn eval_expr_with_l3(expr: &wf_lang::ast::Expr, ctx: &Event) -> Option<Value> {
             });
             Some(Value::Bool(if *negated { !found } else { found }))
         }
+        Expr::Contains {
+            expr: target,
+            mode,
+            values,
+        } => {
+            let Value::Array(items) = eval_expr_with_l3(target, ctx)? else {
+                return None;
+            };
+            let needles: Vec<Option<Value>> =
+                values.iter().map(|v| eval_expr_with_l3(v, ctx)).collect();
+            Some(Value::Bool(array_contains(*mode, &items, &needles)))
+        }
         Expr::IfThenElse {
             cond,
             then_expr,
@@ -138,13 +168,19 @@ fn compare_values(op: wf_lang::ast::BinOp, lv: &Value, rv: &Value) -> bool {
         BinOp::Eq => values_equal(lv, rv),
         BinOp::Ne => !values_equal(lv, rv),
         BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge => match (lv, rv) {
-            (Value::Number(a), Value::Number(b)) => match op {
-                BinOp::Lt => a < b,
-                BinOp::Gt => a > b,
-                BinOp::Le => a <= b,
-                BinOp::Ge => a >= b,
-                _ => false,
-            },
+            (Value::Number(_) | Value::Int(_), Value::Number(_) | Value::Int(_)) => {
+                let ord = match (lv, rv) {
+                    (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+                    _ => coerce_to_f64(lv).partial_cmp(&coerce_to_f64(rv)),
+                };
+                ord.is_some_and(|ord| match op {
+                    BinOp::Lt => ord.is_lt(),
+                    BinOp::Gt => ord.is_gt(),
+                    BinOp::Le => ord.is_le(),
+                    BinOp::Ge => ord.is_ge(),
+                    _ => false,
+                })
+            }
             (Value::Str(a), Value::Str(b)) => match op {
                 BinOp::Lt => a < b,
                 BinOp::Gt => a > b,
@@ -166,10 +202,7 @@ fn compare_values(op: wf_lang::ast::BinOp, lv: &Value, rv: &Value) -> bool {
 }
 
 fn coerce_to_f64(v: &Value) -> Option<f64> {
-    match v {
-        Value::Number(n) => Some(*n),
-        _ => None,
-    }
+    v.as_f64()
 }
 
 fn is_l3_func(name: &str) -> bool {
@@ -184,10 +217,13 @@ fn contains_l3_func(expr: &wf_lang::ast::Expr) -> bool {
     match expr {
         Expr::FuncCall { name, args, .. } => is_l3_func(name) || args.iter().any(contains_l3_func),
         Expr::BinOp { left, right, .. } => contains_l3_func(left) || contains_l3_func(right),
-        Expr::Neg(inner) => contains_l3_func(inner),
+        Expr::Neg(inner) | Expr::Not(inner) => contains_l3_func(inner),
         Expr::InList { expr, list, .. } => {
             contains_l3_func(expr) || list.iter().any(contains_l3_func)
         }
+        Expr::Contains { expr, values, .. } => {
+            contains_l3_func(expr) || values.iter().any(contains_l3_func)
+        }
         Expr::IfThenElse {
             cond,
             then_expr,
@@ -255,6 +291,7 @@ fn eval_builtin_func_with_l3(
             };
             let start = match eval_expr_with_l3(&args[1], ctx)? {
                 Value::Number(n) => n.trunc() as i64,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let chars: Vec<char> = text.chars().collect();
@@ -276,6 +313,7 @@ fn eval_builtin_func_with_l3(
             if args.len() == 3 {
                 let length = match eval_expr_with_l3(&args[2], ctx)? {
                     Value::Number(n) => n.trunc() as i64,
+                    Value::Int(i) => i,
                     _ => return None,
                 };
                 if length <= 0 {
@@ -384,6 +422,7 @@ fn eval_builtin_func_with_l3(
             if args.len() == 2 {
                 let idx = match eval_expr_with_l3(&args[1], ctx)? {
                     Value::Number(n) => normalize_index(n.trunc() as i64, arr.len()),
+                    Value::Int(i) => normalize_index(i, arr.len()),
                     _ => return None,
                 }?;
                 return arr.get(idx).cloned();
@@ -393,10 +432,12 @@ fn eval_builtin_func_with_l3(
             }
             let start = match eval_expr_with_l3(&args[1], ctx)? {
                 Value::Number(n) => n.trunc() as i64,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let end = match eval_expr_with_l3(&args[2], ctx)? {
                 Value::Number(n) => n.trunc() as i64,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let len = arr.len() as i64;
@@ -474,6 +515,7 @@ fn eval_builtin_func_with_l3(
             }
             match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => Some(Value::Number(n.abs())),
+                Value::Int(i) => i.checked_abs().map(Value::Int),
                 _ => None,
             }
         }
@@ -483,11 +525,13 @@ fn eval_builtin_func_with_l3(
             }
             let value = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let precision = if args.len() == 2 {
                 match eval_expr_with_l3(&args[1], ctx)? {
                     Value::Number(n) => f64_to_i64_trunc(n)?,
+                    Value::Int(i) => i,
                     _ => return None,
                 }
             } else {
@@ -502,6 +546,7 @@ fn eval_builtin_func_with_l3(
             }
             match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => Some(Value::Number(n.ceil())),
+                Value::Int(i) => Some(Value::Int(i)),
                 _ => None,
             }
         }
@@ -511,6 +556,7 @@ fn eval_builtin_func_with_l3(
             }
             match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => Some(Value::Number(n.floor())),
+                Value::Int(i) => Some(Value::Int(i)),
                 _ => None,
             }
         }
@@ -520,6 +566,7 @@ fn eval_builtin_func_with_l3(
             }
             match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) if n >= 0.0 => Some(Value::Number(n.sqrt())),
+                Value::Int(i) if i >= 0 => Some(Value::Number((i as f64).sqrt())),
                 _ => None,
             }
         }
@@ -529,10 +576,12 @@ fn eval_builtin_func_with_l3(
             }
             let x = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let y = match eval_expr_with_l3(&args[1], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let out = x.powf(y);
@@ -548,6 +597,7 @@ fn eval_builtin_func_with_l3(
             }
             let x = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             if x <= 0.0 {
@@ -556,6 +606,7 @@ fn eval_builtin_func_with_l3(
             let out = if args.len() == 2 {
                 let base = match eval_expr_with_l3(&args[1], ctx)? {
                     Value::Number(n) => n,
+                    Value::Int(i) => i as f64,
                     _ => return None,
                 };
                 if base <= 0.0 || (base - 1.0).abs() < f64::EPSILON {
@@ -577,6 +628,7 @@ fn eval_builtin_func_with_l3(
             }
             let x = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let out = x.exp();
@@ -592,14 +644,17 @@ fn eval_builtin_func_with_l3(
             }
             let x = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let min = match eval_expr_with_l3(&args[1], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let max = match eval_expr_with_l3(&args[2], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             if min > max {
@@ -607,12 +662,30 @@ fn eval_builtin_func_with_l3(
             }
             Some(Value::Number(x.clamp(min, max)))
         }
+        "min" | "max" if args.len() == 2 => {
+            let a = match eval_expr_with_l3(&args[0], ctx)? {
+                Value::Number(n) => n,
+                Value::Int(i) => i as f64,
+                _ => return None,
+            };
+            let b = match eval_expr_with_l3(&args[1], ctx)? {
+                Value::Number(n) => n,
+                Value::Int(i) => i as f64,
+                _ => return None,
+            };
+            Some(Value::Number(if name == "min" {
+                a.min(b)
+            } else {
+                a.max(b)
+            }))
+        }
         "sign" => {
             if args.len() != 1 {
                 return None;
             }
             match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) if n.is_finite() => Some(Value::Number(n.signum())),
+                Value::Int(i) => Some(Value::Number(i.signum() as f64)),
                 _ => None,
             }
         }
@@ -622,6 +695,7 @@ fn eval_builtin_func_with_l3(
             }
             match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => Some(Value::Number(n.trunc())),
+                Value::Int(i) => Some(Value::Int(i)),
                 _ => None,
             }
         }
@@ -631,6 +705,7 @@ fn eval_builtin_func_with_l3(
             }
             match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => Some(Value::Bool(n.is_finite())),
+                Value::Int(_) => Some(Value::Bool(true)),
                 _ => None,
             }
         }
@@ -785,6 +860,7 @@ fn eval_builtin_func_with_l3(
             }
             let ts_nanos = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => f64_to_i64_trunc(n)?,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let fmt = match eval_expr_with_l3(&args[1], ctx)? {
@@ -807,7 +883,7 @@ fn eval_builtin_func_with_l3(
                 _ => return None,
             };
             let ts_nanos = parse_time_to_timestamp_nanos(&text, &fmt)?;
-            Some(Value::Number(ts_nanos as f64))
+            Some(Value::Int(ts_nanos))
         }
         "regex_match" => {
             if args.len() != 2 {
@@ -830,10 +906,12 @@ fn eval_builtin_func_with_l3(
             }
             let t1 = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let t2 = match eval_expr_with_l3(&args[1], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             Some(Value::Number((t1 - t2).abs() / 1_000_000_000.0))
@@ -844,10 +922,12 @@ fn eval_builtin_func_with_l3(
             }
             let t = match eval_expr_with_l3(&args[0], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let interval = match eval_expr_with_l3(&args[1], ctx)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let interval_nanos = interval * 1_000_000_000.0;
@@ -865,6 +945,13 @@ fn eval_l3_func(name: &str, args: &[wf_lang::ast::Expr], ctx: &Event) -> Option<
     if args.is_empty() {
         return None;
     }
+    // first/last: prefer the value the instance captured for `alias.field`
+    if matches!(name, "first" | "last")
+        && let [arg] = args
+        && let Some(captured) = captured_value(ctx, name, arg)
+    {
+        return Some(captured);
+    }
     let step_indices = resolve_step_indices(ctx, args.first());
     let values = flatten_step_values(ctx, &step_indices);
     match name {
@@ -902,13 +989,7 @@ fn eval_l3_func(name: &str, args: &[wf_lang::ast::Expr], ctx: &Event) -> Option<
             if args.len() != 1 {
                 return None;
             }
-            let nums: Vec<f64> = values
-                .iter()
-                .filter_map(|v| match v {
-                    Value::Number(n) => Some(*n),
-                    _ => None,
-                })
-                .collect();
+            let nums: Vec<f64> = values.iter().filter_map(Value::as_f64).collect();
             if nums.len() < 2 {
                 return Some(Value::Number(0.0));
             }
@@ -922,15 +1003,10 @@ fn eval_l3_func(name: &str, args: &[wf_lang::ast::Expr], ctx: &Event) -> Option<
             }
             let p = match eval_expr_with_l3(&args[1], ctx)? {
                 Value::Number(n) => n.clamp(0.0, 100.0) / 100.0,
+                Value::Int(i) => (i as f64).clamp(0.0, 100.0) / 100.0,
                 _ => return None,
             };
-            let mut nums: Vec<f64> = values
-                .iter()
-                .filter_map(|v| match v {
-                    Value::Number(n) => Some(*n),
-                    _ => None,
-                })
-                .collect();
+            let mut nums: Vec<f64> = values.iter().filter_map(Value::as_f64).collect();
             if nums.is_empty() {
                 return Some(Value::Number(0.0));
             }
@@ -998,6 +1074,17 @@ fn get_step_source(ctx: &Event, step_idx: usize) -> Option<&str> {
     }
 }
 
+fn captured_value(ctx: &Event, func: &str, arg: &wf_lang::ast::Expr) -> Option<Value> {
+    use wf_lang::ast::{Expr, FieldRef};
+    match arg {
+        Expr::Field(FieldRef::Qualified(alias, field))
+        | Expr::Field(FieldRef::Bracketed(alias, field)) => {
+            ctx.fields.get(&capture_key(func, alias, field)).cloned()
+        }
+        _ => None,
+    }
+}
+
 fn extract_source_alias(expr: &wf_lang::ast::Expr) -> Option<&str> {
     use wf_lang::ast::{Expr, FieldRef};
     match expr {
@@ -1019,10 +1106,10 @@ fn normalize_index(index: i64, len: usize) -> Option<usize> {
 }
 
 fn compare_sortable_values(a: &Value, b: &Value) -> std::cmp::Ordering {
+    if let Some(ord) = a.numeric_cmp(b) {
+        return ord;
+    }
     match (a, b) {
-        (Value::Number(x), Value::Number(y)) => {
-            x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal)
-        }
         (Value::Str(x), Value::Str(y)) => x.cmp(y),
         (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
         _ => value_to_string(a).cmp(&value_to_string(b)),
@@ -1086,6 +1173,7 @@ pub(super) fn eval_score(expr: &wf_lang::ast::Expr, ctx: &Event) -> CoreResult<f
     let val = eval_yield_expr(expr, ctx);
     let raw = match val {
         Some(Value::Number(n)) => n,
+        Some(Value::Int(i)) => i as f64,
         Some(other) => {
             return StructError::from(CoreReason::RuleExec)
                 .with_detail(format!(
@@ -1107,6 +1195,17 @@ fn clamp_score(v: f64) -> f64 {
     v.clamp(0.0, 100.0)
 }
 
+/// Round a score to `precision` decimal places (`None` leaves it untouched).
+pub(super) fn round_score(v: f64, precision: Option<u32>) -> f64 {
+    match precision {
+        Some(p) => {
+            let factor = 10f64.powi(p as i32);
+            (v * factor).round() / factor
+        }
+        None => v,
+    }
+}
+
 /// Evaluate the entity_id expression.
 ///
 pub(super) fn eval_entity_id(expr: &wf_lang::ast::Expr, ctx: &Event) -> CoreResult<String> {
@@ -1345,6 +1444,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_yield_string_concat() {
+        let mut fields = std::collections::HashMap::new();
+        fields.insert("sip".to_string(), Value::Str("10.0.0.1".to_string()));
+        fields.insert("dport".to_string(), Value::Number(443.0));
+        let ctx = Event { fields };
+        let expr = Expr::BinOp {
+            op: BinOp::Add,
+            left: Box::new(Expr::BinOp {
+                op: BinOp::Add,
+                left: Box::new(Expr::Field(FieldRef::Simple("sip".to_string()))),
+                right: Box::new(Expr::StringLit(":".to_string())),
+            }),
+            right: Box::new(Expr::Field(FieldRef::Simple("dport".to_string()))),
+        };
+        assert_eq!(
+            eval_yield_expr(&expr, &ctx),
+            Some(Value::Str("10.0.0.1:443".to_string()))
+        );
+    }
+
     #[test]
     fn test_qualified_alias_selects_matching_step() {
         let mut fields = std::collections::HashMap::new();
@@ -1771,10 +1891,7 @@ mod tests {
             eval_yield_expr(&strftime_expr, &ctx),
             Some(Value::Str("1970-01-01".to_string()))
         );
-        assert_eq!(
-            eval_yield_expr(&strptime_expr, &ctx),
-            Some(Value::Number(0.0))
-        );
+        assert_eq!(eval_yield_expr(&strptime_expr, &ctx), Some(Value::Int(0)));
         assert_eq!(eval_yield_expr(&sqrt_expr, &ctx), Some(Value::Number(4.0)));
         assert_eq!(eval_yield_expr(&pow_expr, &ctx), Some(Value::Number(256.0)));
         assert_eq!(eval_yield_expr(&log_expr, &ctx), Some(Value::Number(2.0)));
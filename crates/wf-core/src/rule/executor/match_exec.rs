@@ -5,49 +5,64 @@ use crate::rule::match_engine::{Event, MatchedContext, WindowLookup};
 use super::RuleExecutor;
 use super::alert::{build_summary, build_wfx_id, format_nanos_utc};
 use super::context::{build_eval_context, execute_joins};
-use super::eval::{eval_entity_id, eval_score, eval_yield_expr};
+use super::eval::{eval_entity_id, eval_score, round_score};
 
 impl RuleExecutor {
-    /// Produce an [`OutputRecord`] from an on-event match (L1 — no joins).
-    pub fn execute_match(&self, matched: &MatchedContext) -> CoreResult<OutputRecord> {
+    /// Produce the [`OutputRecord`]s of an on-event match (L1 — no joins),
+    /// one per `yield` target.
+    pub fn execute_match(&self, matched: &MatchedContext) -> CoreResult<Vec<OutputRecord>> {
         let step_plans: Vec<_> = self.plan.match_plan.event_steps.iter().collect();
         let ctx = build_eval_context(
             &self.plan.match_plan.keys,
             &matched.scope_key,
             &matched.step_data,
             &step_plans,
+            &self.plan.match_plan.captures,
+            &matched.captures,
         );
         self.build_match_alert(matched, &ctx)
     }
 
-    /// Produce an [`OutputRecord`] from an on-event match with join support.
+    /// Produce the [`OutputRecord`]s of an on-event match with join support.
     ///
     /// Executes joins before score/entity evaluation, enriching the eval
-    /// context with joined fields from external windows.
+    /// context with joined fields from external windows. Returns no records
+    /// when a required (non-`optional`) asof join finds no row.
     pub fn execute_match_with_joins(
         &self,
         matched: &MatchedContext,
         windows: &dyn WindowLookup,
-    ) -> CoreResult<OutputRecord> {
+    ) -> CoreResult<Vec<OutputRecord>> {
         let step_plans: Vec<_> = self.plan.match_plan.event_steps.iter().collect();
         let mut ctx = build_eval_context(
             &self.plan.match_plan.keys,
             &matched.scope_key,
             &matched.step_data,
             &step_plans,
+            &self.plan.match_plan.captures,
+            &matched.captures,
         );
-        execute_joins(
+        if !execute_joins(
             &self.plan.joins,
             &mut ctx,
             windows,
             matched.event_time_nanos,
-        );
+        ) {
+            return Ok(vec![]);
+        }
         self.build_match_alert(matched, &ctx)
     }
 
-    /// Internal: build the OutputRecord from an already-constructed eval context.
-    fn build_match_alert(&self, matched: &MatchedContext, ctx: &Event) -> CoreResult<OutputRecord> {
-        let score = eval_score(&self.plan.score_plan.expr, ctx)?;
+    /// Internal: build the OutputRecords from an already-constructed eval context.
+    fn build_match_alert(
+        &self,
+        matched: &MatchedContext,
+        ctx: &Event,
+    ) -> CoreResult<Vec<OutputRecord>> {
+        let score = round_score(
+            eval_score(&self.plan.score_plan.expr, ctx)?,
+            self.score_precision,
+        );
         let entity_id = eval_entity_id(&self.plan.entity_plan.entity_id_expr, ctx)?;
         let origin = AlertOrigin::Event;
         let fired_at = format_nanos_utc(matched.event_time_nanos);
@@ -65,18 +80,7 @@ impl RuleExecutor {
             &matched.step_data,
             &origin,
         );
-        let yield_fields = self
-            .plan
-            .yield_plan
-            .fields
-            .iter()
-            .filter_map(|field| {
-                let value = eval_yield_expr(&field.value, ctx)?;
-                Some((field.name.clone(), value))
-            })
-            .collect();
-
-        Ok(OutputRecord {
+        let shared = OutputRecord {
             wfx_id,
             rule_name: self.plan.name.clone(),
             score,
@@ -86,9 +90,11 @@ impl RuleExecutor {
             fired_at,
             matched_rows: vec![],
             summary,
-            yield_target: self.plan.yield_plan.target.clone(),
-            yield_fields,
+            priority: self.plan.priority,
+            yield_target: String::new(),
+            yield_fields: vec![],
             event_time_nanos: matched.event_time_nanos,
-        })
+        };
+        Ok(self.fan_out(shared, ctx))
     }
 }
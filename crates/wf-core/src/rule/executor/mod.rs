@@ -6,22 +6,59 @@ mod match_exec;
 
 use wf_lang::plan::RulePlan;
 
+use crate::alert::OutputRecord;
+use crate::rule::match_engine::Event;
+
+use eval::eval_yield_expr;
+
 /// Evaluates score/entity expressions from a [`RulePlan`] and produces
-/// [`OutputRecord`]s from CEP match/close outputs.
+/// [`OutputRecord`]s from CEP match/close outputs — one per `yield` target.
 ///
 /// L1 rules use `execute_match` / `execute_close` (no joins).
 /// L2 rules with joins use `execute_match_with_joins` / `execute_close_with_joins`
 /// which accept a [`WindowLookup`] for resolving join data.
 pub struct RuleExecutor {
     plan: RulePlan,
+    /// Decimal places alert scores are rounded to; `None` keeps them as is.
+    score_precision: Option<u32>,
 }
 
 impl RuleExecutor {
     pub fn new(plan: RulePlan) -> Self {
-        Self { plan }
+        Self {
+            plan,
+            score_precision: None,
+        }
+    }
+
+    /// Round every emitted score to `precision` decimal places.
+    pub fn with_score_precision(mut self, precision: Option<u32>) -> Self {
+        self.score_precision = precision;
+        self
     }
 
     pub fn plan(&self) -> &RulePlan {
         &self.plan
     }
+
+    /// Emit one record per `yield` target. Everything but the target and
+    /// its evaluated fields is taken from `shared`.
+    fn fan_out(&self, shared: OutputRecord, ctx: &Event) -> Vec<OutputRecord> {
+        self.plan
+            .yield_plans
+            .iter()
+            .map(|yp| OutputRecord {
+                yield_target: yp.target.clone(),
+                yield_fields: yp
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        let value = eval_yield_expr(&field.value, ctx)?;
+                        Some((field.name.clone(), value))
+                    })
+                    .collect(),
+                ..shared.clone()
+            })
+            .collect()
+    }
 }
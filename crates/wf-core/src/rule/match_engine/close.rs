@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use wf_lang::plan::{MatchPlan, StepPlan};
+use wf_lang::plan::{MatchPlan, StepPlan, WindowSpec};
 
 use super::eval::{eval_expr, eval_expr_ext};
 use super::state::{Instance, StepState};
@@ -66,10 +66,12 @@ pub(super) fn accumulate_close_steps(
 ///
 /// Creates a synthetic event with `close_reason` for guard evaluation.
 /// Reads already-accumulated measure state (no new accumulation).
+/// `span_nanos` is the instance's elapsed event-time span, used by `rate`.
 /// Returns `(close_ok, close_step_data)`.
 fn evaluate_close_steps(
     close_steps: &[StepPlan],
     close_step_states: &[StepState],
+    span_nanos: i64,
     reason: CloseReason,
 ) -> (bool, Vec<StepData>) {
     // Synthetic event for guard evaluation
@@ -89,7 +91,7 @@ fn evaluate_close_steps(
 
     for (step_idx, step_plan) in close_steps.iter().enumerate() {
         let step_state = &close_step_states[step_idx];
-        match evaluate_close_step(step_plan, step_state, &synthetic_event) {
+        match evaluate_close_step(step_plan, step_state, span_nanos, &synthetic_event) {
             Some((branch_idx, measure_value)) => {
                 let label = step_plan.branches[branch_idx].label.clone();
                 let collected_values = step_state.branch_states[branch_idx]
@@ -130,6 +132,7 @@ fn evaluate_close_steps(
 fn evaluate_close_step(
     step_plan: &StepPlan,
     step_state: &StepState,
+    span_nanos: i64,
     synthetic_event: &Event,
 ) -> Option<(usize, f64)> {
     for (branch_idx, branch) in step_plan.branches.iter().enumerate() {
@@ -142,8 +145,8 @@ fn evaluate_close_step(
 
         // Check accumulated threshold (no new accumulation)
         let bs = &step_state.branch_states[branch_idx];
-        if check_threshold(&branch.agg, bs) {
-            let measure_val = compute_measure(&branch.agg.measure, bs);
+        if check_threshold(&branch.agg, bs, span_nanos, synthetic_event) {
+            let measure_val = compute_measure(&branch.agg.measure, bs, span_nanos);
             return Some((branch_idx, measure_val));
         }
     }
@@ -151,6 +154,9 @@ fn evaluate_close_step(
 }
 
 /// Internal: evaluate close steps and build CloseOutput for a removed instance.
+///
+/// A session that closes with fewer than its `min_events` reports
+/// `close_ok = false`, so neither close mode emits for it.
 pub(super) fn evaluate_close(
     rule_name: &str,
     plan: &MatchPlan,
@@ -158,8 +164,18 @@ pub(super) fn evaluate_close(
     reason: CloseReason,
     watermark_nanos: i64,
 ) -> CloseOutput {
-    let (close_ok, close_step_data) =
-        evaluate_close_steps(&plan.close_steps, &instance.close_step_states, reason);
+    let (mut close_ok, close_step_data) = evaluate_close_steps(
+        &plan.close_steps,
+        &instance.close_step_states,
+        instance.last_event_nanos - instance.created_at,
+        reason,
+    );
+    if let WindowSpec::Session(spec) = plan.window_spec
+        && let Some(min) = spec.min_events
+        && instance.event_count < min
+    {
+        close_ok = false;
+    }
     CloseOutput {
         rule_name: rule_name.to_string(),
         scope_key: instance.scope_key,
@@ -172,5 +188,6 @@ pub(super) fn evaluate_close(
         close_step_data,
         watermark_nanos,
         last_event_nanos: instance.last_event_nanos,
+        captures: instance.captures,
     }
 }
@@ -127,16 +127,16 @@ fn compare_option_values(a: &Option<Value>, b: &Option<Value>) -> std::cmp::Orde
 
 /// Compare two values for sorting: numbers numerically, strings lexicographically.
 fn compare_values(a: &Value, b: &Value) -> std::cmp::Ordering {
+    if let Some(ord) = a.numeric_cmp(b) {
+        return ord;
+    }
     match (a, b) {
-        (Value::Number(x), Value::Number(y)) => {
-            x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal)
-        }
         (Value::Str(x), Value::Str(y)) => x.cmp(y),
         (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
         (Value::Array(x), Value::Array(y)) => x.len().cmp(&y.len()),
         // Mixed types: Number < Str < Bool < Array
-        (Value::Number(_), _) => std::cmp::Ordering::Less,
-        (_, Value::Number(_)) => std::cmp::Ordering::Greater,
+        (Value::Number(_) | Value::Int(_), _) => std::cmp::Ordering::Less,
+        (_, Value::Number(_) | Value::Int(_)) => std::cmp::Ordering::Greater,
         (Value::Str(_), Value::Bool(_) | Value::Array(_)) => std::cmp::Ordering::Less,
         (Value::Bool(_) | Value::Array(_), Value::Str(_)) => std::cmp::Ordering::Greater,
         (Value::Bool(_), Value::Array(_)) => std::cmp::Ordering::Less,
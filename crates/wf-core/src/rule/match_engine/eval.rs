@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
-use wf_lang::ast::{BinOp, CmpOp, Expr};
+use wf_lang::ast::{BinOp, CmpOp, ContainsMode, Expr};
 
 use super::key::{field_ref_name, value_to_string};
 use super::types::{Event, RollingStats, Value, WindowLookup};
@@ -13,12 +13,20 @@ use super::types::{Event, RollingStats, Value, WindowLookup};
 /// Evaluate an expression against an event, returning a [`Value`].
 ///
 /// Supports: literals, field refs, BinOp (And/Or/comparisons/arithmetic),
-/// Neg, InList, and basic FuncCall (contains, startswith, endswith, substr, replace, trim, lower, upper, len, mvcount, mvjoin, mvindex, mvappend, split, mvdedup, abs, round, ceil, floor, sqrt, pow, log, exp, clamp, sign, trunc, is_finite, ltrim, rtrim, concat, indexof, replace_plain, startswith_any, endswith_any, coalesce, isnull, isnotnull, mvsort, mvreverse, strftime, strptime, has, baseline).
+/// Neg, InList, Contains, and basic FuncCall (contains, startswith, endswith, substr, replace, trim, lower, upper, len, mvcount, mvjoin, mvindex, mvappend, split, mvdedup, abs, round, ceil, floor, sqrt, pow, log, exp, clamp, sign, trunc, is_finite, ltrim, rtrim, concat, indexof, replace_plain, startswith_any, endswith_any, coalesce, isnull, isnotnull, mvsort, mvreverse, strftime, strptime, has, baseline).
 pub(crate) fn eval_expr(expr: &Expr, event: &Event) -> Option<Value> {
     let mut empty = HashMap::new();
     eval_expr_ext(expr, event, None, &mut empty)
 }
 
+/// Evaluate a bind filter (`events { alias : window && expr }`) against an event.
+///
+/// Only an explicit `true` passes. Unlike close-step guards this is strict:
+/// a missing field or non-bool result drops the event.
+pub fn bind_filter_passes(filter: &Expr, event: &Event) -> bool {
+    matches!(eval_expr(filter, event), Some(Value::Bool(true)))
+}
+
 /// Extended expression evaluator with window lookup and baseline store access.
 ///
 /// All recursive calls go through this function (not `eval_expr`) to preserve
@@ -33,6 +41,8 @@ pub(crate) fn eval_expr_ext(
         Expr::Number(n) => Some(Value::Number(*n)),
         Expr::StringLit(s) => Some(Value::Str(s.clone())),
         Expr::Bool(b) => Some(Value::Bool(*b)),
+        // Time values are epoch nanoseconds, so durations compare in nanos too.
+        Expr::Duration(d) => Some(Value::Number(d.as_nanos() as f64)),
         Expr::Field(fr) => {
             let name = field_ref_name(fr);
             event.fields.get(name).cloned()
@@ -41,9 +51,14 @@ pub(crate) fn eval_expr_ext(
             let v = eval_expr_ext(inner, event, windows, baselines)?;
             match v {
                 Value::Number(n) => Some(Value::Number(-n)),
+                Value::Int(i) => i.checked_neg().map(Value::Int),
                 _ => None,
             }
         }
+        Expr::Not(inner) => match eval_expr_ext(inner, event, windows, baselines)? {
+            Value::Bool(b) => Some(Value::Bool(!b)),
+            _ => None,
+        },
         Expr::BinOp { op, left, right } => eval_binop(*op, left, right, event, windows, baselines),
         Expr::InList {
             expr: target,
@@ -60,16 +75,32 @@ pub(crate) fn eval_expr_ext(
             });
             Some(Value::Bool(if *negated { !found } else { found }))
         }
+        Expr::Contains {
+            expr: target,
+            mode,
+            values,
+        } => {
+            let Value::Array(items) = eval_expr_ext(target, event, windows, baselines)? else {
+                return None;
+            };
+            let needles: Vec<Option<Value>> = values
+                .iter()
+                .map(|v| eval_expr_ext(v, event, windows, baselines))
+                .collect();
+            Some(Value::Bool(array_contains(*mode, &items, &needles)))
+        }
         Expr::FuncCall {
             qualifier,
             name,
             args,
         } => {
-            // Handle window.has()
-            if let Some(window_name) = qualifier
-                && name == "has"
-            {
-                return eval_window_has(window_name, args, event, windows);
+            // Handle window.has() / window.count() / window.sum()
+            if let Some(window_name) = qualifier {
+                match name.as_str() {
+                    "has" => return eval_window_has(window_name, args, event, windows),
+                    "count" | "sum" => return eval_window_agg(window_name, name, args, windows),
+                    _ => {}
+                }
             }
             // Handle baseline()
             if name == "baseline" && (args.len() == 2 || args.len() == 3) {
@@ -118,6 +149,24 @@ fn eval_window_has(
     Some(Value::Bool(values.contains(&lookup_str)))
 }
 
+/// Evaluate `window.count()` / `window.sum("field")` against the target
+/// window's current contents.
+fn eval_window_agg(
+    window_name: &str,
+    name: &str,
+    args: &[Expr],
+    windows: Option<&dyn WindowLookup>,
+) -> Option<Value> {
+    let windows = windows?;
+    match (name, args) {
+        ("count", []) => Some(Value::Number(windows.row_count(window_name)? as f64)),
+        ("sum", [Expr::StringLit(field)]) => {
+            Some(Value::Number(windows.field_sum(window_name, field)?))
+        }
+        _ => None,
+    }
+}
+
 /// Evaluate `baseline(expr, duration_seconds [, method])`.
 ///
 /// Computes the z-score (number of standard deviations from the running mean)
@@ -131,6 +180,7 @@ fn eval_baseline(
 ) -> Option<Value> {
     let current_val = match eval_expr(&args[0], event)? {
         Value::Number(n) => n,
+        Value::Int(i) => i as f64,
         _ => return None,
     };
 
@@ -168,11 +218,19 @@ fn eval_binop(
         BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge => {
             let lv = eval_expr_ext(left, event, windows, baselines)?;
             let rv = eval_expr_ext(right, event, windows, baselines)?;
-            Some(Value::Bool(compare_values(op, &lv, &rv)))
+            Some(Value::Bool(compare_values(CmpOp::from_binop(op), &lv, &rv)))
         }
         BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod => {
             let lv = eval_expr_ext(left, event, windows, baselines)?;
             let rv = eval_expr_ext(right, event, windows, baselines)?;
+            if op == BinOp::Add
+                && let Some(joined) = concat_values(&lv, &rv)
+            {
+                return Some(joined);
+            }
+            if let Some(exact) = int_arithmetic(op, &lv, &rv) {
+                return Some(exact);
+            }
             let ln = coerce_to_f64(&lv)?;
             let rn = coerce_to_f64(&rv)?;
             eval_arithmetic(op, ln, rn)
@@ -220,6 +278,39 @@ fn eval_logic_or(
     }
 }
 
+/// String concatenation for `+`: applies when either operand is a string,
+/// rendering a numeric operand as text (`sip + ":" + dport` → `"10.0.0.1:22"`).
+/// Returns `None` when neither side is a string, so the caller falls back to
+/// arithmetic.
+pub(crate) fn concat_values(lv: &Value, rv: &Value) -> Option<Value> {
+    match (lv, rv) {
+        (Value::Str(_), Value::Str(_) | Value::Number(_) | Value::Int(_))
+        | (Value::Number(_) | Value::Int(_), Value::Str(_)) => Some(Value::Str(format!(
+            "{}{}",
+            value_to_string(lv),
+            value_to_string(rv)
+        ))),
+        _ => None,
+    }
+}
+
+/// Exact `+`, `-`, `*`, `%` on two [`Value::Int`]s, so differences of
+/// nanosecond timestamps do not round through `f64`. `None` (fall back to
+/// float arithmetic) for other operands, `/`, overflow or a zero divisor.
+pub(crate) fn int_arithmetic(op: BinOp, lv: &Value, rv: &Value) -> Option<Value> {
+    let (Value::Int(a), Value::Int(b)) = (lv, rv) else {
+        return None;
+    };
+    let result = match op {
+        BinOp::Add => a.checked_add(*b),
+        BinOp::Sub => a.checked_sub(*b),
+        BinOp::Mul => a.checked_mul(*b),
+        BinOp::Mod => a.checked_rem(*b),
+        _ => None,
+    };
+    result.map(Value::Int)
+}
+
 /// Arithmetic on two numeric values: +, -, *, /, %.
 fn eval_arithmetic(op: BinOp, lv: f64, rv: f64) -> Option<Value> {
     let result = match op {
@@ -246,13 +337,35 @@ fn eval_arithmetic(op: BinOp, lv: f64, rv: f64) -> Option<Value> {
 /// Equality check for InList membership.
 pub(crate) fn values_equal(a: &Value, b: &Value) -> bool {
     match (a, b) {
-        (Value::Number(x), Value::Number(y)) => (x - y).abs() < f64::EPSILON,
+        (Value::Int(x), Value::Int(y)) => x == y,
+        (Value::Number(_) | Value::Int(_), Value::Number(_) | Value::Int(_)) => a
+            .as_f64()
+            .zip(b.as_f64())
+            .is_some_and(|(x, y)| (x - y).abs() < f64::EPSILON),
         (Value::Str(x), Value::Str(y)) => x == y,
         (Value::Bool(x), Value::Bool(y)) => x == y,
         _ => false,
     }
 }
 
+/// Membership test for `arr contains [any|all] ...`. A needle that failed to
+/// evaluate never matches.
+pub(crate) fn array_contains(
+    mode: ContainsMode,
+    items: &[Value],
+    needles: &[Option<Value>],
+) -> bool {
+    let present = |needle: &Option<Value>| {
+        needle
+            .as_ref()
+            .is_some_and(|n| items.iter().any(|item| values_equal(item, n)))
+    };
+    match mode {
+        ContainsMode::Single | ContainsMode::Any => needles.iter().any(present),
+        ContainsMode::All => needles.iter().all(present),
+    }
+}
+
 /// Evaluate basic function calls in guard context.
 ///
 /// Supported functions:
@@ -296,7 +409,7 @@ pub(crate) fn values_equal(a: &Value, b: &Value) -> bool {
 /// - `mvsort(arr)` → Array
 /// - `mvreverse(arr)` → Array
 /// - `strftime(timestamp_nanos, format)` → Str
-/// - `strptime(text, format)` → Number (timestamp nanos)
+/// - `strptime(text, format)` → Int (timestamp nanos)
 fn eval_func_call(
     name: &str,
     args: &[Expr],
@@ -357,6 +470,7 @@ fn eval_func_call(
             };
             let start = match eval_expr_ext(&args[1], event, windows, baselines)? {
                 Value::Number(n) => n.trunc() as i64,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let chars: Vec<char> = text.chars().collect();
@@ -378,6 +492,7 @@ fn eval_func_call(
             if args.len() == 3 {
                 let length = match eval_expr_ext(&args[2], event, windows, baselines)? {
                     Value::Number(n) => n.trunc() as i64,
+                    Value::Int(i) => i,
                     _ => return None,
                 };
                 if length <= 0 {
@@ -486,6 +601,7 @@ fn eval_func_call(
             if args.len() == 2 {
                 let idx = match eval_expr_ext(&args[1], event, windows, baselines)? {
                     Value::Number(n) => normalize_index(n.trunc() as i64, arr.len()),
+                    Value::Int(i) => normalize_index(i, arr.len()),
                     _ => return None,
                 }?;
                 return arr.get(idx).cloned();
@@ -495,10 +611,12 @@ fn eval_func_call(
             }
             let start = match eval_expr_ext(&args[1], event, windows, baselines)? {
                 Value::Number(n) => n.trunc() as i64,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let end = match eval_expr_ext(&args[2], event, windows, baselines)? {
                 Value::Number(n) => n.trunc() as i64,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let len = arr.len() as i64;
@@ -576,6 +694,7 @@ fn eval_func_call(
             }
             match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => Some(Value::Number(n.abs())),
+                Value::Int(i) => i.checked_abs().map(Value::Int),
                 _ => None,
             }
         }
@@ -585,11 +704,13 @@ fn eval_func_call(
             }
             let value = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let precision = if args.len() == 2 {
                 match eval_expr_ext(&args[1], event, windows, baselines)? {
                     Value::Number(n) => f64_to_i64_trunc(n)?,
+                    Value::Int(i) => i,
                     _ => return None,
                 }
             } else {
@@ -604,6 +725,7 @@ fn eval_func_call(
             }
             match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => Some(Value::Number(n.ceil())),
+                Value::Int(i) => Some(Value::Int(i)),
                 _ => None,
             }
         }
@@ -613,6 +735,7 @@ fn eval_func_call(
             }
             match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => Some(Value::Number(n.floor())),
+                Value::Int(i) => Some(Value::Int(i)),
                 _ => None,
             }
         }
@@ -622,6 +745,7 @@ fn eval_func_call(
             }
             match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) if n >= 0.0 => Some(Value::Number(n.sqrt())),
+                Value::Int(i) if i >= 0 => Some(Value::Number((i as f64).sqrt())),
                 _ => None,
             }
         }
@@ -631,10 +755,12 @@ fn eval_func_call(
             }
             let x = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let y = match eval_expr_ext(&args[1], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let out = x.powf(y);
@@ -650,6 +776,7 @@ fn eval_func_call(
             }
             let x = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             if x <= 0.0 {
@@ -658,6 +785,7 @@ fn eval_func_call(
             let out = if args.len() == 2 {
                 let base = match eval_expr_ext(&args[1], event, windows, baselines)? {
                     Value::Number(n) => n,
+                    Value::Int(i) => i as f64,
                     _ => return None,
                 };
                 if base <= 0.0 || (base - 1.0).abs() < f64::EPSILON {
@@ -679,6 +807,7 @@ fn eval_func_call(
             }
             let x = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let out = x.exp();
@@ -694,14 +823,17 @@ fn eval_func_call(
             }
             let x = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let min = match eval_expr_ext(&args[1], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let max = match eval_expr_ext(&args[2], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             if min > max {
@@ -709,12 +841,30 @@ fn eval_func_call(
             }
             Some(Value::Number(x.clamp(min, max)))
         }
+        "min" | "max" if args.len() == 2 => {
+            let a = match eval_expr_ext(&args[0], event, windows, baselines)? {
+                Value::Number(n) => n,
+                Value::Int(i) => i as f64,
+                _ => return None,
+            };
+            let b = match eval_expr_ext(&args[1], event, windows, baselines)? {
+                Value::Number(n) => n,
+                Value::Int(i) => i as f64,
+                _ => return None,
+            };
+            Some(Value::Number(if name == "min" {
+                a.min(b)
+            } else {
+                a.max(b)
+            }))
+        }
         "sign" => {
             if args.len() != 1 {
                 return None;
             }
             match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) if n.is_finite() => Some(Value::Number(n.signum())),
+                Value::Int(i) => Some(Value::Number(i.signum() as f64)),
                 _ => None,
             }
         }
@@ -724,6 +874,7 @@ fn eval_func_call(
             }
             match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => Some(Value::Number(n.trunc())),
+                Value::Int(i) => Some(Value::Int(i)),
                 _ => None,
             }
         }
@@ -733,6 +884,7 @@ fn eval_func_call(
             }
             match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => Some(Value::Bool(n.is_finite())),
+                Value::Int(_) => Some(Value::Bool(true)),
                 _ => None,
             }
         }
@@ -891,6 +1043,7 @@ fn eval_func_call(
             }
             let ts_nanos = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => f64_to_i64_trunc(n)?,
+                Value::Int(i) => i,
                 _ => return None,
             };
             let fmt = match eval_expr_ext(&args[1], event, windows, baselines)? {
@@ -913,7 +1066,7 @@ fn eval_func_call(
                 _ => return None,
             };
             let ts_nanos = parse_time_to_timestamp_nanos(&text, &fmt)?;
-            Some(Value::Number(ts_nanos as f64))
+            Some(Value::Int(ts_nanos))
         }
         "regex_match" => {
             if args.len() != 2 {
@@ -936,10 +1089,12 @@ fn eval_func_call(
             }
             let t1 = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let t2 = match eval_expr_ext(&args[1], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             Some(Value::Number((t1 - t2).abs() / 1_000_000_000.0))
@@ -950,10 +1105,12 @@ fn eval_func_call(
             }
             let t = match eval_expr_ext(&args[0], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let interval = match eval_expr_ext(&args[1], event, windows, baselines)? {
                 Value::Number(n) => n,
+                Value::Int(i) => i as f64,
                 _ => return None,
             };
             let interval_nanos = interval * 1_000_000_000.0;
@@ -979,33 +1136,35 @@ fn eval_func_call(
     }
 }
 
-fn compare_values(op: BinOp, lv: &Value, rv: &Value) -> bool {
+/// Compare two values with `cmp`; mismatched types never compare true.
+pub(crate) fn compare_values(cmp: CmpOp, lv: &Value, rv: &Value) -> bool {
     match (lv, rv) {
-        (Value::Number(a), Value::Number(b)) => {
-            let cmp = CmpOp::from_binop(op);
-            compare_cmp(cmp, *a, *b)
-        }
-        (Value::Str(a), Value::Str(b)) => {
-            let ord = a.cmp(b);
-            match op {
-                BinOp::Eq => ord.is_eq(),
-                BinOp::Ne => !ord.is_eq(),
-                BinOp::Lt => ord.is_lt(),
-                BinOp::Gt => ord.is_gt(),
-                BinOp::Le => ord.is_le(),
-                BinOp::Ge => ord.is_ge(),
-                _ => false,
-            }
-        }
-        (Value::Bool(a), Value::Bool(b)) => match op {
-            BinOp::Eq => a == b,
-            BinOp::Ne => a != b,
+        (Value::Number(a), Value::Number(b)) => compare_cmp(cmp, *a, *b),
+        (Value::Int(a), Value::Int(b)) => compare_ord(cmp, a.cmp(b)),
+        (Value::Int(a), Value::Number(b)) => compare_cmp(cmp, *a as f64, *b),
+        (Value::Number(a), Value::Int(b)) => compare_cmp(cmp, *a, *b as f64),
+        (Value::Str(a), Value::Str(b)) => compare_ord(cmp, a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => match cmp {
+            CmpOp::Eq => a == b,
+            CmpOp::Ne => a != b,
             _ => false,
         },
         _ => false, // type mismatch
     }
 }
 
+fn compare_ord(cmp: CmpOp, ord: std::cmp::Ordering) -> bool {
+    match cmp {
+        CmpOp::Eq => ord.is_eq(),
+        CmpOp::Ne => !ord.is_eq(),
+        CmpOp::Lt => ord.is_lt(),
+        CmpOp::Gt => ord.is_gt(),
+        CmpOp::Le => ord.is_le(),
+        CmpOp::Ge => ord.is_ge(),
+        _ => false,
+    }
+}
+
 fn compare_cmp(cmp: CmpOp, lhs: f64, rhs: f64) -> bool {
     match cmp {
         CmpOp::Eq => (lhs - rhs).abs() < f64::EPSILON,
@@ -1092,10 +1251,7 @@ pub(super) fn try_eval_expr_to_value(expr: &Expr) -> Option<Value> {
 }
 
 fn coerce_to_f64(v: &Value) -> Option<f64> {
-    match v {
-        Value::Number(n) => Some(*n),
-        _ => None,
-    }
+    v.as_f64()
 }
 
 fn normalize_index(index: i64, len: usize) -> Option<usize> {
@@ -1109,10 +1265,10 @@ fn normalize_index(index: i64, len: usize) -> Option<usize> {
 }
 
 fn compare_sortable_values(a: &Value, b: &Value) -> std::cmp::Ordering {
+    if let Some(ord) = a.numeric_cmp(b) {
+        return ord;
+    }
     match (a, b) {
-        (Value::Number(x), Value::Number(y)) => {
-            x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal)
-        }
         (Value::Str(x), Value::Str(y)) => x.cmp(y),
         (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
         _ => value_to_string(a).cmp(&value_to_string(b)),
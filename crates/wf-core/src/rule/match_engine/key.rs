@@ -136,6 +136,7 @@ pub(super) fn make_scope_key_str(scope_key: &[Value]) -> String {
 pub(crate) fn value_to_string(v: &Value) -> String {
     match v {
         Value::Number(n) => n.to_string(),
+        Value::Int(i) => i.to_string(),
         Value::Str(s) => s.clone(),
         Value::Bool(b) => b.to_string(),
         Value::Array(_) => "[array]".to_string(),
@@ -8,11 +8,16 @@ mod types;
 
 // Re-export public types
 pub use types::{
-    CloseOutput, CloseReason, Event, MatchedContext, StepData, StepResult, Value, WindowLookup,
+    CapturedValue, CloseOutput, CloseReason, Event, MatchedContext, StepData, StepResult,
+    SuppressReason, Value, WindowLookup,
 };
 
+pub use eval::bind_filter_passes;
+
 // Re-export pub(crate) items
-pub(crate) use eval::{eval_expr, values_equal};
+pub(crate) use eval::{
+    array_contains, compare_values, concat_values, eval_expr, int_arithmetic, values_equal,
+};
 pub(crate) use key::{field_ref_name, value_to_string};
 
 #[cfg(test)]
@@ -46,13 +51,18 @@ pub struct CepStateMachine {
     plan: MatchPlan,
     instances: HashMap<InstanceKey, Instance>,
     time_field: Option<String>,
-    watermark_nanos: i64,
+    /// Max event time seen so far; the watermark trails it by
+    /// `plan.max_out_of_orderness`.
+    max_event_nanos: i64,
     limits: Option<LimitsPlan>,
     /// Set to true when `FailRule` limit is exceeded — all future events are
     /// rejected until the machine is reset.
     failed: bool,
     emit_count: u64,
     emit_window_start: i64,
+    /// Limit suppressions since the last [`take_suppressed`](Self::take_suppressed),
+    /// indexed by `SuppressReason as usize`.
+    suppressed: [u64; SuppressReason::ALL.len()],
 }
 
 impl CepStateMachine {
@@ -63,11 +73,12 @@ impl CepStateMachine {
             plan,
             instances: HashMap::new(),
             time_field,
-            watermark_nanos: 0,
+            max_event_nanos: 0,
             limits: None,
             failed: false,
             emit_count: 0,
             emit_window_start: 0,
+            suppressed: [0; SuppressReason::ALL.len()],
         }
     }
 
@@ -83,11 +94,12 @@ impl CepStateMachine {
             plan,
             instances: HashMap::new(),
             time_field,
-            watermark_nanos: 0,
+            max_event_nanos: 0,
             limits,
             failed: false,
             emit_count: 0,
             emit_window_start: 0,
+            suppressed: [0; SuppressReason::ALL.len()],
         }
     }
 
@@ -96,6 +108,35 @@ impl CepStateMachine {
         &self.rule_name
     }
 
+    /// Whether a `fail_rule` limit has tripped; a failed machine rejects all
+    /// events until [`reset`](Self::reset).
+    pub fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Drop all instances, clear the failed flag and restart the
+    /// `max_throttle` window, so the rule can resume after an overload.
+    ///
+    /// The watermark and undrained suppression counts are kept.
+    pub fn reset(&mut self) {
+        self.instances.clear();
+        self.failed = false;
+        self.emit_count = 0;
+        self.emit_window_start = 0;
+    }
+
+    /// Drain the limit suppression counts accumulated since the last call.
+    ///
+    /// Only reasons with a non-zero count are returned.
+    pub fn take_suppressed(&mut self) -> Vec<(SuppressReason, u64)> {
+        let counts = std::mem::take(&mut self.suppressed);
+        SuppressReason::ALL
+            .into_iter()
+            .zip(counts)
+            .filter(|(_, n)| *n > 0)
+            .collect()
+    }
+
     /// Feed one event (arriving on `alias`) into the state machine.
     ///
     /// Extracts event time from the configured `time_field`, falling back to 0.
@@ -119,10 +160,7 @@ impl CepStateMachine {
         self.time_field
             .as_ref()
             .and_then(|tf| event.fields.get(tf))
-            .and_then(|v| match v {
-                Value::Number(n) => Some(*n as i64),
-                _ => None,
-            })
+            .and_then(Value::as_i64)
             .unwrap_or(0)
     }
 
@@ -141,12 +179,13 @@ impl CepStateMachine {
     ) -> StepResult {
         // FailRule: once the rule has failed, reject all future events
         if self.failed {
+            self.suppressed[SuppressReason::RuleFailed as usize] += 1;
             return StepResult::Accumulate;
         }
 
         // Update watermark
-        if now_nanos > self.watermark_nanos {
-            self.watermark_nanos = now_nanos;
+        if now_nanos > self.max_event_nanos {
+            self.max_event_nanos = now_nanos;
         }
 
         // 1. Extract scope key from event
@@ -181,7 +220,10 @@ impl CepStateMachine {
             && self.instances.len() >= max_inst
         {
             match limits.on_exceed {
-                ExceedAction::Throttle => return StepResult::Accumulate,
+                ExceedAction::Throttle => {
+                    self.suppressed[SuppressReason::MaxInstances as usize] += 1;
+                    return StepResult::Accumulate;
+                }
                 ExceedAction::DropOldest => {
                     // Find and remove the oldest instance
                     if let Some(oldest_key) = self
@@ -195,6 +237,7 @@ impl CepStateMachine {
                 }
                 ExceedAction::FailRule => {
                     self.failed = true;
+                    self.suppressed[SuppressReason::MaxInstances as usize] += 1;
                     return StepResult::Accumulate;
                 }
             }
@@ -219,7 +262,10 @@ impl CepStateMachine {
                 + new_cost;
             if total >= max_bytes {
                 match limits.on_exceed {
-                    ExceedAction::Throttle => return StepResult::Accumulate,
+                    ExceedAction::Throttle => {
+                        self.suppressed[SuppressReason::MaxMemory as usize] += 1;
+                        return StepResult::Accumulate;
+                    }
                     ExceedAction::DropOldest => {
                         // Evict oldest instances in a loop until under limit or nothing left.
                         // If the current key is the oldest it gets evicted too — its
@@ -244,12 +290,14 @@ impl CepStateMachine {
                                 }
                             } else {
                                 // No instances to evict — cannot make room
+                                self.suppressed[SuppressReason::MaxMemory as usize] += 1;
                                 return StepResult::Accumulate;
                             }
                         }
                     }
                     ExceedAction::FailRule => {
                         self.failed = true;
+                        self.suppressed[SuppressReason::MaxMemory as usize] += 1;
                         return StepResult::Accumulate;
                     }
                 }
@@ -265,6 +313,8 @@ impl CepStateMachine {
         if now_nanos > instance.last_event_nanos {
             instance.last_event_nanos = now_nanos;
         }
+        instance.event_count += 1;
+        instance.record_captures(plan, alias, event);
 
         // 3. Accumulate close steps (if any) — happens on every event
         if !plan.close_steps.is_empty() {
@@ -295,6 +345,7 @@ impl CepStateMachine {
         let step_idx = instance.current_step;
         let step_plan = &plan.event_steps[step_idx];
         let step_state = &mut instance.step_states[step_idx];
+        let span_nanos = instance.last_event_nanos - instance.created_at;
 
         // 6. Evaluate step
         match evaluate_step(
@@ -302,6 +353,7 @@ impl CepStateMachine {
             event,
             step_plan,
             step_state,
+            span_nanos,
             windows,
             &mut instance.baselines,
         ) {
@@ -338,10 +390,12 @@ impl CepStateMachine {
                                         // Suppress the match — reset instance for future use
                                         let reset_at = fixed_created_at.unwrap_or(now_nanos);
                                         instance.reset(plan, reset_at);
+                                        self.suppressed[SuppressReason::MaxThrottle as usize] += 1;
                                         return StepResult::Accumulate;
                                     }
                                     ExceedAction::FailRule => {
                                         self.failed = true;
+                                        self.suppressed[SuppressReason::MaxThrottle as usize] += 1;
                                         return StepResult::Accumulate;
                                     }
                                 }
@@ -355,6 +409,7 @@ impl CepStateMachine {
                             scope_key,
                             step_data: instance.completed_steps.clone(),
                             event_time_nanos: now_nanos,
+                            captures: instance.captures.clone(),
                         };
                         instance.reset(plan, fixed_created_at.unwrap_or(now_nanos));
                         StepResult::Matched(ctx)
@@ -372,10 +427,12 @@ impl CepStateMachine {
                                 match limits.on_exceed {
                                     ExceedAction::Throttle | ExceedAction::DropOldest => {
                                         instance.event_emitted = true;
+                                        self.suppressed[SuppressReason::MaxThrottle as usize] += 1;
                                         return StepResult::Accumulate;
                                     }
                                     ExceedAction::FailRule => {
                                         self.failed = true;
+                                        self.suppressed[SuppressReason::MaxThrottle as usize] += 1;
                                         return StepResult::Accumulate;
                                     }
                                 }
@@ -388,6 +445,7 @@ impl CepStateMachine {
                             scope_key,
                             step_data: instance.completed_steps.clone(),
                             event_time_nanos: now_nanos,
+                            captures: instance.captures.clone(),
                         };
                         StepResult::Matched(ctx)
                     } else {
@@ -439,9 +497,9 @@ impl CepStateMachine {
             &self.plan,
             instance,
             reason,
-            self.watermark_nanos,
+            self.watermark_nanos(),
         );
-        self.rate_limit_close(&mut output, self.watermark_nanos);
+        self.rate_limit_close(&mut output, self.watermark_nanos());
         Some(output)
     }
 
@@ -449,7 +507,7 @@ impl CepStateMachine {
     ///
     /// Used by the scheduler on periodic ticks.
     pub fn scan_expired(&mut self) -> Vec<CloseOutput> {
-        self.scan_expired_at(self.watermark_nanos)
+        self.scan_expired_at(self.watermark_nanos())
     }
 
     /// Scan all instances for maxspan expiry using an explicit watermark.
@@ -460,30 +518,33 @@ impl CepStateMachine {
     /// watermark (the logical expiry time), rather than the detection-time
     /// watermark. This makes `fired_at` deterministic regardless of batch size
     /// or scan frequency.
+    ///
+    /// Session windows expire at `last_event + gap`, or at
+    /// `created_at + max_duration` when that hard cap comes first.
     pub fn scan_expired_at(&mut self, watermark_nanos: i64) -> Vec<CloseOutput> {
-        let maxspan_nanos = match self.plan.window_spec {
-            WindowSpec::Sliding(d) | WindowSpec::Fixed(d) | WindowSpec::Session(d) => {
-                d.as_nanos() as i64
-            }
+        let (maxspan_nanos, session_cap_nanos) = match self.plan.window_spec {
+            WindowSpec::Sliding(d) | WindowSpec::Fixed(d) => (d.as_nanos() as i64, None),
+            WindowSpec::Session(spec) => (
+                spec.gap.as_nanos() as i64,
+                spec.max_duration.map(|d| d.as_nanos() as i64),
+            ),
         };
         let is_session = matches!(self.plan.window_spec, WindowSpec::Session(_));
         let mut expired_keys: Vec<(InstanceKey, i64, i64)> = Vec::new();
         for (key, inst) in &self.instances {
-            // Session window: expire based on last_event_nanos (gap timeout)
+            // Session window: expire based on last_event_nanos (gap timeout),
+            // capped at created_at + max_duration
             // Sliding/Fixed window: expire based on created_at
-            let expiry_anchor = if is_session {
-                inst.last_event_nanos
+            let logical_expire_time = if is_session {
+                let gap_expiry = inst.last_event_nanos + maxspan_nanos;
+                match session_cap_nanos {
+                    Some(cap) => gap_expiry.min(inst.created_at + cap),
+                    None => gap_expiry,
+                }
             } else {
-                inst.created_at
+                inst.created_at + maxspan_nanos
             };
-            if watermark_nanos.saturating_sub(expiry_anchor) >= maxspan_nanos {
-                // For session: expire_time = last_event_nanos + gap
-                // For sliding/fixed: expire_time = created_at + duration
-                let logical_expire_time = if is_session {
-                    inst.last_event_nanos + maxspan_nanos
-                } else {
-                    inst.created_at + maxspan_nanos
-                };
+            if watermark_nanos >= logical_expire_time {
                 // Sort key: created_at for sliding/fixed, last_event_nanos for session
                 let sort_key = if is_session {
                     inst.last_event_nanos
@@ -554,7 +615,7 @@ impl CepStateMachine {
             .collect();
         keys.sort_by(|(k1, t1), (k2, t2)| t1.cmp(t2).then_with(|| k1.cmp(k2)));
         let mut results = Vec::with_capacity(keys.len());
-        let wm = self.watermark_nanos;
+        let wm = self.watermark_nanos();
         for (key, _) in keys {
             if let Some(instance) = self.instances.remove(&key) {
                 let mut output = evaluate_close(&self.rule_name, &self.plan, instance, reason, wm);
@@ -565,9 +626,11 @@ impl CepStateMachine {
         results
     }
 
-    /// Current watermark (nanoseconds since epoch).
+    /// Current watermark (nanoseconds since epoch): the max seen event time
+    /// minus the plan's `max_out_of_orderness`.
     pub fn watermark_nanos(&self) -> i64 {
-        self.watermark_nanos
+        let lag = self.plan.max_out_of_orderness.as_nanos() as i64;
+        self.max_event_nanos.saturating_sub(lag)
     }
 
     /// Apply max_throttle to a close output that would produce an alert.
@@ -602,6 +665,7 @@ impl CepStateMachine {
                         output.close_ok = false;
                     }
                 }
+                self.suppressed[SuppressReason::MaxThrottle as usize] += 1;
                 return;
             }
             self.emit_count += 1;
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use wf_lang::plan::MatchPlan;
 
-use super::types::{RollingStats, Value};
+use super::types::{CapturedValue, Event, RollingStats, Value};
 
 // ---------------------------------------------------------------------------
 // Internal — per-branch / per-step / per-instance state
@@ -58,6 +58,8 @@ pub(super) struct Instance {
     pub(super) scope_key: Vec<Value>,
     pub(super) created_at: i64,
     pub(super) last_event_nanos: i64,
+    /// Events routed to this instance (session `min_events`).
+    pub(super) event_count: u64,
     pub(super) current_step: usize,
     pub(super) event_ok: bool,
     pub(super) event_emitted: bool,
@@ -65,6 +67,8 @@ pub(super) struct Instance {
     pub(super) completed_steps: Vec<super::types::StepData>,
     pub(super) close_step_states: Vec<StepState>,
     pub(super) baselines: HashMap<String, RollingStats>,
+    /// First/last values, aligned with `MatchPlan::captures`.
+    pub(super) captures: Vec<Option<CapturedValue>>,
 }
 
 impl Instance {
@@ -87,6 +91,7 @@ impl Instance {
             scope_key,
             created_at,
             last_event_nanos: created_at,
+            event_count: 0,
             current_step: 0,
             event_ok: false,
             event_emitted: false,
@@ -94,6 +99,28 @@ impl Instance {
             completed_steps: Vec::new(),
             close_step_states,
             baselines: HashMap::new(),
+            captures: vec![None; plan.captures.len()],
+        }
+    }
+
+    /// Record the event's value for every capture on `alias`.
+    pub(super) fn record_captures(&mut self, plan: &MatchPlan, alias: &str, event: &Event) {
+        for (cp, slot) in plan.captures.iter().zip(self.captures.iter_mut()) {
+            if cp.alias != alias {
+                continue;
+            }
+            let Some(val) = event.fields.get(&cp.field) else {
+                continue;
+            };
+            match slot {
+                Some(cv) => cv.last = val.clone(),
+                None => {
+                    *slot = Some(CapturedValue {
+                        first: val.clone(),
+                        last: val.clone(),
+                    })
+                }
+            }
         }
     }
 
@@ -119,6 +146,11 @@ impl Instance {
         // baselines
         size += self.baselines.len() * 128;
 
+        // captures
+        for cv in self.captures.iter().flatten() {
+            size += val_estimated_bytes(&cv.first) + val_estimated_bytes(&cv.last);
+        }
+
         size
     }
 
@@ -148,6 +180,7 @@ impl Instance {
     pub(super) fn reset(&mut self, plan: &MatchPlan, created_at: i64) {
         self.created_at = created_at;
         self.last_event_nanos = created_at;
+        self.event_count = 0;
         self.current_step = 0;
         self.event_ok = false;
         self.event_emitted = false;
@@ -163,13 +196,14 @@ impl Instance {
             .map(|sp| StepState::new(sp.branches.len()))
             .collect();
         self.baselines.clear();
+        self.captures.iter_mut().for_each(|c| *c = None);
     }
 }
 
 fn val_estimated_bytes(v: &Value) -> usize {
     match v {
         Value::Str(s) => s.len() + 24,
-        Value::Number(_) | Value::Bool(_) => 8,
+        Value::Number(_) | Value::Int(_) | Value::Bool(_) => 8,
         Value::Array(arr) => 24 + arr.iter().map(val_estimated_bytes).sum::<usize>(),
     }
 }
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use wf_lang::ast::{CmpOp, FieldSelector, Measure, Transform};
 use wf_lang::plan::{AggPlan, StepPlan};
 
-use super::eval::{eval_expr_ext, try_eval_expr_to_f64, try_eval_expr_to_value};
+use super::eval::{eval_expr, eval_expr_ext, try_eval_expr_to_f64, try_eval_expr_to_value};
 use super::key::value_to_string;
 use super::state::{BranchState, StepState};
 use super::types::{Event, RollingStats, Value, WindowLookup};
@@ -14,11 +14,14 @@ use super::types::{Event, RollingStats, Value, WindowLookup};
 
 /// Evaluate all branches in a step. Returns the first branch that is
 /// satisfied: `Some((branch_index, measure_value))`.
+///
+/// `span_nanos` is the instance's elapsed event-time span, used by `rate`.
 pub(super) fn evaluate_step(
     alias: &str,
     event: &Event,
     step_plan: &StepPlan,
     step_state: &mut StepState,
+    span_nanos: i64,
     windows: Option<&dyn WindowLookup>,
     baselines: &mut HashMap<String, RollingStats>,
 ) -> Option<(usize, f64)> {
@@ -50,10 +53,10 @@ pub(super) fn evaluate_step(
         update_measure(&branch.agg.measure, &field_value, bs);
 
         // Check threshold
-        let satisfied = check_threshold(&branch.agg, bs);
+        let satisfied = check_threshold(&branch.agg, bs, span_nanos, event);
 
         if satisfied {
-            let measure_val = compute_measure(&branch.agg.measure, bs);
+            let measure_val = compute_measure(&branch.agg.measure, bs, span_nanos);
             return Some((branch_idx, measure_val));
         }
     }
@@ -112,7 +115,7 @@ pub(super) fn update_measure(measure: &Measure, field_value: &Option<Value>, bs:
     }
 
     match measure {
-        Measure::Count => {
+        Measure::Count | Measure::Rate(_) => {
             bs.count += 1;
         }
         Measure::Sum => {
@@ -163,9 +166,10 @@ fn update_extreme(
     }
 }
 
-pub(super) fn compute_measure(measure: &Measure, bs: &BranchState) -> f64 {
+pub(super) fn compute_measure(measure: &Measure, bs: &BranchState, span_nanos: i64) -> f64 {
     match measure {
         Measure::Count => bs.count as f64,
+        Measure::Rate(unit) => rate_per_unit(bs.count, span_nanos, unit.as_nanos() as f64),
         Measure::Sum => bs.sum,
         Measure::Avg => {
             if bs.avg_count == 0 {
@@ -180,16 +184,34 @@ pub(super) fn compute_measure(measure: &Measure, bs: &BranchState) -> f64 {
     }
 }
 
+/// Events per `unit_nanos` over `span_nanos`. The span is floored at one
+/// unit so that a burst right after the instance opens is not divided by
+/// a near-zero span.
+fn rate_per_unit(count: u64, span_nanos: i64, unit_nanos: f64) -> f64 {
+    if unit_nanos <= 0.0 {
+        return 0.0;
+    }
+    let span = (span_nanos.max(0) as f64).max(unit_nanos);
+    count as f64 * unit_nanos / span
+}
+
 /// Unified threshold check for a branch's aggregation plan.
 ///
 /// Strategy:
 /// 1. Try `try_eval_expr_to_f64` on the threshold expression.
 ///    - If it succeeds AND the numeric measure value is usable → f64 compare.
-/// 2. For min/max where the numeric path gives ±INF (non-numeric field)
-///    OR the threshold is non-constant → fall back to Value-based comparison.
-/// 3. If neither path resolves, the check returns `false` (not satisfied).
-pub(super) fn check_threshold(agg: &AggPlan, bs: &BranchState) -> bool {
-    let measure_f64 = compute_measure(&agg.measure, bs);
+/// 2. Otherwise resolve the threshold to a [`Value`]: constants directly,
+///    non-constant expressions (field refs, `if/then/else`) against `event`
+///    — the event that triggered this check, or the synthetic close event.
+///    min/max compare values; count/sum/avg need a numeric threshold.
+/// 3. If the threshold cannot be resolved, the check returns `false`.
+pub(super) fn check_threshold(
+    agg: &AggPlan,
+    bs: &BranchState,
+    span_nanos: i64,
+    event: &Event,
+) -> bool {
+    let measure_f64 = compute_measure(&agg.measure, bs, span_nanos);
 
     // Fast path: threshold is a constant numeric expression
     if let Some(threshold_f64) = try_eval_expr_to_f64(&agg.threshold) {
@@ -204,31 +226,25 @@ pub(super) fn check_threshold(agg: &AggPlan, bs: &BranchState) -> bool {
 
     // Value-based path: needed for min/max on non-numeric fields,
     // or when threshold expression is non-constant.
+    let Some(threshold_val) =
+        try_eval_expr_to_value(&agg.threshold).or_else(|| eval_expr(&agg.threshold, event))
+    else {
+        // Unresolvable (e.g. missing field): treat as unsatisfied rather
+        // than silently comparing against 0.0
+        return false;
+    };
     match agg.measure {
-        Measure::Min => {
-            if let (Some(val), Some(threshold_val)) =
-                (&bs.min_val, try_eval_expr_to_value(&agg.threshold))
-            {
-                compare_value_threshold(agg.cmp, val, &threshold_val)
-            } else {
-                false
-            }
-        }
-        Measure::Max => {
-            if let (Some(val), Some(threshold_val)) =
-                (&bs.max_val, try_eval_expr_to_value(&agg.threshold))
-            {
-                compare_value_threshold(agg.cmp, val, &threshold_val)
-            } else {
-                false
-            }
-        }
-        _ => {
-            // count/sum/avg with a non-constant threshold (e.g. field ref):
-            // cannot evaluate — treat as unsatisfied rather than silently
-            // comparing against 0.0
-            false
-        }
+        Measure::Min => bs
+            .min_val
+            .as_ref()
+            .is_some_and(|val| compare_value_threshold(agg.cmp, val, &threshold_val)),
+        Measure::Max => bs
+            .max_val
+            .as_ref()
+            .is_some_and(|val| compare_value_threshold(agg.cmp, val, &threshold_val)),
+        _ => threshold_val
+            .as_f64()
+            .is_some_and(|t| compare(agg.cmp, measure_f64, t)),
     }
 }
 
@@ -251,16 +267,16 @@ fn compare(cmp: CmpOp, lhs: f64, rhs: f64) -> bool {
 /// Ordering for Value (used by min/max on orderable fields).
 /// Number < Str < Bool < Array for cross-type (shouldn't happen in practice).
 fn value_ordering(a: &Value, b: &Value) -> std::cmp::Ordering {
+    if let Some(ord) = a.numeric_cmp(b) {
+        return ord;
+    }
     match (a, b) {
-        (Value::Number(x), Value::Number(y)) => {
-            x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal)
-        }
         (Value::Str(x), Value::Str(y)) => x.cmp(y),
         (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
         (Value::Array(x), Value::Array(y)) => x.len().cmp(&y.len()),
         // Cross-type: Number < Str < Bool < Array
-        (Value::Number(_), _) => std::cmp::Ordering::Less,
-        (_, Value::Number(_)) => std::cmp::Ordering::Greater,
+        (Value::Number(_) | Value::Int(_), _) => std::cmp::Ordering::Less,
+        (_, Value::Number(_) | Value::Int(_)) => std::cmp::Ordering::Greater,
         (Value::Str(_), Value::Bool(_) | Value::Array(_)) => std::cmp::Ordering::Less,
         (Value::Bool(_) | Value::Array(_), Value::Str(_)) => std::cmp::Ordering::Greater,
         (Value::Bool(_), Value::Array(_)) => std::cmp::Ordering::Less,
@@ -274,8 +290,10 @@ fn value_ordering(a: &Value, b: &Value) -> std::cmp::Ordering {
 fn compare_value_threshold(cmp: CmpOp, val: &Value, threshold: &Value) -> bool {
     let same_type = matches!(
         (val, threshold),
-        (Value::Number(_), Value::Number(_))
-            | (Value::Str(_), Value::Str(_))
+        (
+            Value::Number(_) | Value::Int(_),
+            Value::Number(_) | Value::Int(_)
+        ) | (Value::Str(_), Value::Str(_))
             | (Value::Bool(_), Value::Bool(_))
     );
     if !same_type {
@@ -294,8 +312,5 @@ fn compare_value_threshold(cmp: CmpOp, val: &Value, threshold: &Value) -> bool {
 }
 
 fn value_to_f64(v: &Value) -> Option<f64> {
-    match v {
-        Value::Number(n) => Some(*n),
-        _ => None,
-    }
+    v.as_f64()
 }
@@ -17,11 +17,48 @@ pub struct Event {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Number(f64),
+    /// Exact integer, e.g. from a `digit` or `time` column. Kept apart from
+    /// [`Value::Number`] so nanosecond timestamps beyond 2^53 survive intact.
+    Int(i64),
     Str(String),
     Bool(bool),
     Array(Vec<Value>),
 }
 
+impl Value {
+    /// Numeric view of the value; [`Value::Int`] is widened to `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Integer view of the value: [`Value::Int`] exactly, a finite
+    /// [`Value::Number`] truncated toward zero.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(i) => Some(*i),
+            Value::Number(n) if n.is_finite() => Some(n.trunc() as i64),
+            _ => None,
+        }
+    }
+
+    /// Order two numeric values, exactly when both are integers. `None` when
+    /// either side is not numeric; incomparable floats (NaN) order as equal.
+    pub fn numeric_cmp(&self, other: &Value) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+            _ => Some(
+                self.as_f64()?
+                    .partial_cmp(&other.as_f64()?)
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            ),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Public types — result of advance()
 // ---------------------------------------------------------------------------
@@ -37,6 +74,39 @@ pub enum StepResult {
     Matched(MatchedContext),
 }
 
+/// Why the state machine dropped an event or an alert because of `limits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuppressReason {
+    /// `max_instances` reached (event rejected or rule failed).
+    MaxInstances,
+    /// `max_memory_bytes` reached (event rejected or rule failed).
+    MaxMemory,
+    /// `max_throttle` reached — a match or close alert was not emitted.
+    MaxThrottle,
+    /// Event rejected because a previous `fail_rule` limit failed the rule.
+    RuleFailed,
+}
+
+impl SuppressReason {
+    /// All reasons, in metric label order.
+    pub const ALL: [SuppressReason; 4] = [
+        SuppressReason::MaxInstances,
+        SuppressReason::MaxMemory,
+        SuppressReason::MaxThrottle,
+        SuppressReason::RuleFailed,
+    ];
+
+    /// Stable label used in metrics.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SuppressReason::MaxInstances => "max_instances",
+            SuppressReason::MaxMemory => "max_memory",
+            SuppressReason::MaxThrottle => "max_throttle",
+            SuppressReason::RuleFailed => "rule_failed",
+        }
+    }
+}
+
 /// Context returned when a full match fires.
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchedContext {
@@ -44,6 +114,15 @@ pub struct MatchedContext {
     pub scope_key: Vec<Value>,
     pub step_data: Vec<StepData>,
     pub event_time_nanos: i64,
+    /// Captured values, aligned with `MatchPlan::captures`.
+    pub captures: Vec<Option<CapturedValue>>,
+}
+
+/// First and last value seen for one `MatchPlan::captures` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedValue {
+    pub first: Value,
+    pub last: Value,
 }
 
 /// Per-step snapshot captured when a step is satisfied.
@@ -98,6 +177,8 @@ pub struct CloseOutput {
     /// matching against right-table rows that appeared after the
     /// instance stopped receiving events.
     pub last_event_nanos: i64,
+    /// Captured values, aligned with `MatchPlan::captures`.
+    pub captures: Vec<Option<CapturedValue>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -121,6 +202,24 @@ pub trait WindowLookup: Send + Sync {
         let _ = window;
         None
     }
+
+    /// Number of rows currently held by a window (for `window.count()`).
+    fn row_count(&self, window: &str) -> Option<usize> {
+        self.snapshot(window).map(|rows| rows.len())
+    }
+
+    /// Sum of a numeric field over a window's rows (for `window.sum("f")`).
+    ///
+    /// Rows where the field is absent or not a number are skipped; an empty
+    /// window sums to `0.0`.
+    fn field_sum(&self, window: &str, field: &str) -> Option<f64> {
+        let rows = self.snapshot(window)?;
+        Some(
+            rows.iter()
+                .filter_map(|row| row.get(field).and_then(Value::as_f64))
+                .sum(),
+        )
+    }
 }
 
 // ---------------------------------------------------------------------------
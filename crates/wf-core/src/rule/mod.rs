@@ -9,6 +9,6 @@ mod tests;
 pub use event_bridge::{batch_to_events, batch_to_timestamped_rows};
 pub use executor::RuleExecutor;
 pub use match_engine::{
-    CepStateMachine, CloseOutput, CloseReason, Event, MatchedContext, StepData, StepResult, Value,
-    WindowLookup,
+    CapturedValue, CepStateMachine, CloseOutput, CloseReason, Event, MatchedContext, StepData,
+    StepResult, SuppressReason, Value, WindowLookup, bind_filter_passes,
 };
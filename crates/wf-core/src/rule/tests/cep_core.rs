@@ -195,6 +195,51 @@ fn distinct_transform() {
     ));
 }
 
+#[test]
+fn distinct_count_vs_plain_count_on_duplicates() {
+    // Same input, same threshold: `conn | count` counts every event,
+    // `conn.dport | distinct | count` only new ports.
+    let conn_branch = |field: Option<FieldSelector>, transforms: Vec<Transform>| BranchPlan {
+        label: None,
+        source: "conn".to_string(),
+        field,
+        guard: None,
+        agg: AggPlan {
+            transforms,
+            measure: Measure::Count,
+            cmp: CmpOp::Ge,
+            threshold: Expr::Number(2.0),
+        },
+    };
+    let plain = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![conn_branch(None, vec![])])],
+    );
+    let distinct = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![conn_branch(
+            Some(FieldSelector::Dot("dport".to_string())),
+            vec![Transform::Distinct],
+        )])],
+    );
+    let mut plain_sm = CepStateMachine::new("plain".to_string(), plain, None);
+    let mut distinct_sm = CepStateMachine::new("distinct".to_string(), distinct, None);
+
+    let mk = |port: f64| event(vec![("sip", str_val("10.0.0.1")), ("dport", num(port))]);
+    let ports = [22.0, 22.0, 22.0, 80.0];
+    let matched_at = |sm: &mut CepStateMachine| {
+        ports.iter().position(|&p| {
+            matches!(sm.advance("conn", &mk(p)), StepResult::Matched(ref ctx)
+                if ctx.step_data[0].measure_value == 2.0)
+        })
+    };
+
+    // The second event already reaches 2 for plain count; distinct needs
+    // the first new port (event 4).
+    assert_eq!(matched_at(&mut plain_sm), Some(1));
+    assert_eq!(matched_at(&mut distinct_sm), Some(3));
+}
+
 #[test]
 fn source_matching() {
     // events with wrong alias don't contribute to branch
@@ -266,6 +311,37 @@ fn sum_measure() {
     }
 }
 
+#[test]
+fn negative_sum_threshold() {
+    // sum(delta) <= -10 with a signed threshold
+    let agg = AggPlan {
+        transforms: vec![],
+        measure: Measure::Sum,
+        cmp: CmpOp::Le,
+        threshold: Expr::Neg(Box::new(Expr::Number(10.0))),
+    };
+    let plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![BranchPlan {
+            label: None,
+            source: "traffic".to_string(),
+            field: Some(FieldSelector::Dot("delta".to_string())),
+            guard: None,
+            agg,
+        }])],
+    );
+    let mut sm = CepStateMachine::new("rule9b".to_string(), plan, None);
+
+    let mk = |delta: f64| event(vec![("sip", str_val("10.0.0.1")), ("delta", num(delta))]);
+
+    assert_eq!(sm.advance("traffic", &mk(-4.0)), StepResult::Accumulate); // sum=-4
+    assert_eq!(sm.advance("traffic", &mk(-5.5)), StepResult::Accumulate); // sum=-9.5
+    assert!(matches!(
+        sm.advance("traffic", &mk(-0.5)), // sum=-10
+        StepResult::Matched(_)
+    ));
+}
+
 #[test]
 fn missing_key_skips() {
     // event without key field → Accumulate (skipped)
@@ -328,3 +404,37 @@ fn numeric_key_type_preserved_through_pipeline() {
         panic!("expected Matched");
     }
 }
+
+#[test]
+fn rate_measure_uses_instance_span() {
+    // rate >= 1.5 — events per second over the instance's span
+    let agg = AggPlan {
+        transforms: vec![],
+        measure: Measure::Rate(std::time::Duration::from_secs(1)),
+        cmp: CmpOp::Ge,
+        threshold: Expr::Number(1.5),
+    };
+    let plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![branch("req", agg)])],
+    );
+    let mut sm = CepStateMachine::new("rule_rate".to_string(), plan, None);
+    let e = event(vec![("sip", str_val("10.0.0.1"))]);
+    let secs = |s: i64| s * 1_000_000_000;
+
+    // One event every 2s: 5 events over 8s stays well below 1.5/s
+    for t in [0, 2, 4, 6, 8] {
+        assert_eq!(sm.advance_at("req", &e, secs(t)), StepResult::Accumulate);
+    }
+    // Burst at t=8s: 11 events over 8s is still below the rate
+    for _ in 0..6 {
+        assert_eq!(sm.advance_at("req", &e, secs(8)), StepResult::Accumulate);
+    }
+    // 12 events over 8s = 1.5/s
+    match sm.advance_at("req", &e, secs(8)) {
+        StepResult::Matched(ctx) => {
+            assert!((ctx.step_data[0].measure_value - 1.5).abs() < 1e-9);
+        }
+        other => panic!("expected Matched, got {other:?}"),
+    }
+}
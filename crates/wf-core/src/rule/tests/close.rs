@@ -1,4 +1,4 @@
-//! M15 close step / timeout tests (12–21).
+//! M15 close step / timeout tests (12–23).
 
 use std::time::Duration;
 
@@ -379,3 +379,83 @@ fn multiple_close_steps_all_must_pass() {
     assert!(out_c.event_ok);
     assert!(!out_c.close_ok);
 }
+
+/// `req count >= 3` with a timeout-guarded `resp count == 0` close step.
+fn late_tolerant_plan(max_out_of_orderness: Duration) -> wf_lang::plan::MatchPlan {
+    let mut plan = plan_with_close(
+        vec![simple_key("sip")],
+        vec![step(vec![branch("req", count_ge(3.0))])],
+        vec![step(vec![BranchPlan {
+            label: Some("no_resp".to_string()),
+            source: "resp".to_string(),
+            field: None,
+            guard: Some(close_reason_guard("timeout")),
+            agg: AggPlan {
+                transforms: vec![],
+                measure: Measure::Count,
+                cmp: CmpOp::Eq,
+                threshold: Expr::Number(0.0),
+            },
+        }])],
+        Duration::from_secs(60),
+    );
+    plan.max_out_of_orderness = max_out_of_orderness;
+    plan
+}
+
+#[test]
+fn zero_out_of_orderness_expires_on_future_event() {
+    let mut sm = CepStateMachine::new(
+        "rule22".to_string(),
+        late_tolerant_plan(Duration::ZERO),
+        None,
+    );
+    let base: i64 = 1_700_000_000 * NANOS_PER_SEC;
+    let a = event(vec![("sip", str_val("10.0.0.1"))]);
+    let b = event(vec![("sip", str_val("10.0.0.2"))]);
+
+    sm.advance_at("req", &a, base);
+    sm.advance_at("req", &a, base + 10 * NANOS_PER_SEC);
+    // One event from another key jumps the watermark past A's maxspan,
+    // so A closes before its straggler arrives.
+    sm.advance_at("req", &b, base + 65 * NANOS_PER_SEC);
+    assert_eq!(sm.watermark_nanos(), base + 65 * NANOS_PER_SEC);
+    let expired = sm.scan_expired();
+    assert_eq!(expired.len(), 1);
+    assert!(!expired[0].event_ok);
+}
+
+#[test]
+fn out_of_orderness_holds_back_expiry_for_late_event() {
+    let mut sm = CepStateMachine::new(
+        "rule23".to_string(),
+        late_tolerant_plan(Duration::from_secs(10)),
+        None,
+    );
+    let base: i64 = 1_700_000_000 * NANOS_PER_SEC;
+    let a = event(vec![("sip", str_val("10.0.0.1"))]);
+    let b = event(vec![("sip", str_val("10.0.0.2"))]);
+
+    sm.advance_at("req", &a, base);
+    sm.advance_at("req", &a, base + 10 * NANOS_PER_SEC);
+    sm.advance_at("req", &b, base + 65 * NANOS_PER_SEC);
+    // Watermark trails the newest event by 10s → A is not yet expired.
+    assert_eq!(sm.watermark_nanos(), base + 55 * NANOS_PER_SEC);
+    assert!(sm.scan_expired().is_empty());
+
+    // The earlier event is still folded into A's instance.
+    assert_eq!(
+        sm.advance_at("req", &a, base + 50 * NANOS_PER_SEC),
+        StepResult::Advance
+    );
+
+    // Once the watermark passes A's maxspan it closes with all three events.
+    sm.advance_at("req", &b, base + 75 * NANOS_PER_SEC);
+    let expired = sm.scan_expired();
+    assert_eq!(expired.len(), 1);
+    let out = &expired[0];
+    assert_eq!(out.scope_key, vec![str_val("10.0.0.1")]);
+    assert!(out.event_ok);
+    assert!(out.close_ok);
+    assert_eq!(out.event_step_data[0].measure_value, 3.0);
+}
@@ -321,3 +321,32 @@ test shuffle_order_check for ordered_ab {
         result.failures
     );
 }
+
+#[test]
+fn contract_bind_filter_drops_rejected_rows() {
+    let source = r#"
+rule brute_force {
+    events { e : auth_events && action == "failed" }
+    match<sip:5m> { on event { e | count >= 4; } } -> score(70.0)
+    entity(ip, e.sip)
+    yield security_alerts (sip = e.sip, fail_count = 4)
+}
+
+test filtered for brute_force {
+    input {
+        row(e, sip = "10.0.0.1", action = "failed");
+        row(e, sip = "10.0.0.1", action = "success");
+        row(e, sip = "10.0.0.1", action = "failed");
+        row(e, sip = "10.0.0.1", action = "success");
+        row(e, sip = "10.0.0.1", action = "failed");
+    }
+    expect {
+        hits == 0;
+    }
+}
+"#;
+    // Five rows reach the threshold unfiltered; only three pass the filter.
+    let result = run_contract_from_source(source);
+    assert!(result.passed, "failures: {:?}", result.failures);
+    assert_eq!(result.output_count, 0);
+}
@@ -1,7 +1,10 @@
 use wf_lang::ast::{BinOp, CloseMode, Expr, FieldRef};
+use wf_lang::plan::{CapturePlan, YieldField, YieldPlan};
 
 use crate::rule::RuleExecutor;
-use crate::rule::match_engine::{CloseOutput, CloseReason, MatchedContext, StepData};
+use crate::rule::match_engine::{
+    CepStateMachine, CloseOutput, CloseReason, MatchedContext, StepData, StepResult, Value,
+};
 
 use super::helpers::*;
 
@@ -27,6 +30,7 @@ fn default_matched_context() -> MatchedContext {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     }
 }
 
@@ -46,7 +50,7 @@ fn execute_match_static_score() {
     let exec = RuleExecutor::new(plan);
     let matched = default_matched_context();
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
 
     assert_eq!(alert.rule_name, "r1");
     assert!((alert.score - 70.0).abs() < f64::EPSILON);
@@ -78,7 +82,7 @@ fn execute_match_arithmetic_score() {
     let exec = RuleExecutor::new(plan);
     let matched = default_matched_context();
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     assert!((alert.score - 70.0).abs() < f64::EPSILON);
 }
 
@@ -98,7 +102,7 @@ fn execute_match_entity_simple_key() {
     let exec = RuleExecutor::new(plan);
     let matched = default_matched_context();
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     assert_eq!(alert.entity_id, "10.0.0.1");
 }
 
@@ -128,9 +132,10 @@ fn execute_match_no_keys() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     assert_eq!(alert.entity_id, "all");
     assert!(alert.summary.contains("global"));
 }
@@ -163,9 +168,10 @@ fn execute_match_composite_keys() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     assert_eq!(alert.entity_id, "10.0.0.2");
     // wfx_id should be a 16-hex-char content hash
     assert_eq!(alert.wfx_id.len(), 16);
@@ -203,9 +209,10 @@ fn execute_close_both_ok() {
         close_step_data: vec![],
         watermark_nanos: 0,
         last_event_nanos: 123,
+        captures: vec![],
     };
 
-    let alert = exec.execute_close(&close).unwrap().unwrap();
+    let alert = exec.execute_close(&close).unwrap().remove(0);
     assert_eq!(alert.origin.as_str(), "close:timeout");
     assert!((alert.score - 70.0).abs() < f64::EPSILON);
     assert_eq!(alert.entity_id, "10.0.0.1");
@@ -238,10 +245,11 @@ fn execute_close_close_not_ok() {
         close_step_data: vec![],
         watermark_nanos: 0,
         last_event_nanos: 0,
+        captures: vec![],
     };
 
     let result = exec.execute_close(&close).unwrap();
-    assert!(result.is_none());
+    assert!(result.is_empty());
 }
 
 // =========================================================================
@@ -270,10 +278,11 @@ fn execute_close_event_not_ok() {
         close_step_data: vec![],
         watermark_nanos: 0,
         last_event_nanos: 0,
+        captures: vec![],
     };
 
     let result = exec.execute_close(&close).unwrap();
-    assert!(result.is_none());
+    assert!(result.is_empty());
 }
 
 // =========================================================================
@@ -292,7 +301,7 @@ fn score_clamped_to_range() {
     let exec_high = RuleExecutor::new(plan_high);
     let matched = default_matched_context();
 
-    let alert = exec_high.execute_match(&matched).unwrap();
+    let alert = exec_high.execute_match(&matched).unwrap().remove(0);
     assert!((alert.score - 100.0).abs() < f64::EPSILON);
 
     let plan_low = simple_rule_plan(
@@ -304,10 +313,82 @@ fn score_clamped_to_range() {
     );
     let exec_low = RuleExecutor::new(plan_low);
 
-    let alert = exec_low.execute_match(&matched).unwrap();
+    let alert = exec_low.execute_match(&matched).unwrap().remove(0);
     assert!(alert.score.abs() < f64::EPSILON); // 0.0
 }
 
+#[test]
+fn score_rounded_to_precision() {
+    // score = 200 / 3 = 66.666...
+    let score_expr = Expr::BinOp {
+        op: BinOp::Div,
+        left: Box::new(Expr::Number(200.0)),
+        right: Box::new(Expr::Number(3.0)),
+    };
+    let plan = simple_rule_plan(
+        "r1",
+        default_match_plan(),
+        score_expr,
+        "ip",
+        Expr::Field(FieldRef::Simple("sip".to_string())),
+    );
+    let matched = default_matched_context();
+
+    let exact = RuleExecutor::new(plan.clone())
+        .execute_match(&matched)
+        .unwrap()
+        .remove(0);
+    assert!((exact.score - 200.0 / 3.0).abs() < f64::EPSILON);
+
+    let rounded = RuleExecutor::new(plan.clone())
+        .with_score_precision(Some(2))
+        .execute_match(&matched)
+        .unwrap()
+        .remove(0);
+    assert_eq!(rounded.score, 66.67);
+
+    let whole = RuleExecutor::new(plan)
+        .with_score_precision(Some(0))
+        .execute_match(&matched)
+        .unwrap()
+        .remove(0);
+    assert_eq!(whole.score, 67.0);
+}
+
+#[test]
+fn score_uses_scalar_min_max() {
+    // score = max(min(90, 35 * 3), 10) = 90
+    let call = |name: &str, args: Vec<Expr>| Expr::FuncCall {
+        qualifier: None,
+        name: name.to_string(),
+        args,
+    };
+    let product = Expr::BinOp {
+        op: BinOp::Mul,
+        left: Box::new(Expr::Number(35.0)),
+        right: Box::new(Expr::Number(3.0)),
+    };
+    let score_expr = call(
+        "max",
+        vec![
+            call("min", vec![Expr::Number(90.0), product]),
+            Expr::Number(10.0),
+        ],
+    );
+    let plan = simple_rule_plan(
+        "r1",
+        default_match_plan(),
+        score_expr,
+        "ip",
+        Expr::Field(FieldRef::Simple("sip".to_string())),
+    );
+    let alert = RuleExecutor::new(plan)
+        .execute_match(&default_matched_context())
+        .unwrap()
+        .remove(0);
+    assert_eq!(alert.score, 90.0);
+}
+
 // =========================================================================
 // Test 10: entity eval failure — nonexistent field
 // =========================================================================
@@ -345,8 +426,8 @@ fn wfx_id_deterministic() {
     let exec = RuleExecutor::new(plan);
     let matched = default_matched_context();
 
-    let alert1 = exec.execute_match(&matched).unwrap();
-    let alert2 = exec.execute_match(&matched).unwrap();
+    let alert1 = exec.execute_match(&matched).unwrap().remove(0);
+    let alert2 = exec.execute_match(&matched).unwrap().remove(0);
 
     // Same inputs produce the same content hash
     assert_eq!(alert1.wfx_id, alert2.wfx_id);
@@ -379,9 +460,10 @@ fn summary_format() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     assert!(alert.summary.contains("brute_force"));
     assert!(alert.summary.contains("sip=10.0.0.1"));
     assert!(alert.summary.contains("fail=5.0"));
@@ -423,9 +505,10 @@ fn numeric_key_preserves_type_in_eval_context() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     // score = 443.0 / 100.0 = 4.43, clamped to [0, 100]
     assert!((alert.score - 4.43).abs() < f64::EPSILON);
     assert_eq!(alert.entity_id, "443");
@@ -461,9 +544,10 @@ fn label_cannot_overwrite_key_in_eval_context() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     // Key must win: entity_id should be "10.0.0.1", not "99"
     assert_eq!(alert.entity_id, "10.0.0.1");
 }
@@ -496,9 +580,10 @@ fn wfx_id_hex_format() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     // wfx_id is exactly 16 hex characters, no separators
     assert_eq!(alert.wfx_id.len(), 16);
     assert!(
@@ -510,3 +595,200 @@ fn wfx_id_hex_format() {
     assert!(!alert.wfx_id.contains('|'));
     assert!(!alert.wfx_id.contains('#'));
 }
+
+// =========================================================================
+// Test 16: multiple yield targets — one record per target
+// =========================================================================
+
+fn two_target_executor() -> RuleExecutor {
+    let mut plan = simple_rule_plan(
+        "r1",
+        default_match_plan(),
+        Expr::Number(70.0),
+        "ip",
+        Expr::Field(FieldRef::Simple("sip".to_string())),
+    );
+    plan.yield_plans = vec![
+        YieldPlan {
+            target: "alerts".to_string(),
+            version: None,
+            fields: vec![YieldField {
+                name: "sip".to_string(),
+                value: Expr::Field(FieldRef::Simple("sip".to_string())),
+            }],
+        },
+        YieldPlan {
+            target: "alert_feed".to_string(),
+            version: None,
+            fields: vec![YieldField {
+                name: "n".to_string(),
+                value: Expr::Number(1.0),
+            }],
+        },
+    ];
+    RuleExecutor::new(plan)
+}
+
+#[test]
+fn execute_match_fans_out_to_each_yield_target() {
+    let exec = two_target_executor();
+    let records = exec.execute_match(&default_matched_context()).unwrap();
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].yield_target, "alerts");
+    assert_eq!(
+        records[0].yield_fields,
+        vec![("sip".to_string(), str_val("10.0.0.1"))]
+    );
+    assert_eq!(records[1].yield_target, "alert_feed");
+    assert_eq!(
+        records[1].yield_fields,
+        vec![("n".to_string(), Value::Number(1.0))]
+    );
+    // Everything but the target and its fields is shared.
+    assert_eq!(records[0].wfx_id, records[1].wfx_id);
+    assert_eq!(records[0].entity_id, records[1].entity_id);
+    assert_eq!(records[0].score, records[1].score);
+}
+
+#[test]
+fn execute_close_fans_out_to_each_yield_target() {
+    let exec = two_target_executor();
+    let close = CloseOutput {
+        rule_name: "r1".to_string(),
+        scope_key: vec![str_val("10.0.0.1")],
+        close_reason: CloseReason::Timeout,
+        event_ok: true,
+        close_ok: true,
+        close_mode: CloseMode::And,
+        event_emitted: false,
+        event_step_data: vec![StepData {
+            satisfied_branch_index: 0,
+            label: Some("fail".to_string()),
+            measure_value: 3.0,
+            collected_values: Vec::new(),
+        }],
+        close_step_data: vec![],
+        watermark_nanos: 0,
+        last_event_nanos: 123,
+        captures: vec![],
+    };
+
+    let targets: Vec<_> = exec
+        .execute_close(&close)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.yield_target)
+        .collect();
+    assert_eq!(targets, ["alerts", "alert_feed"]);
+}
+
+// =========================================================================
+// Test 17: first()/last() read the per-instance captured values
+// =========================================================================
+
+fn l3_call(name: &str, alias: &str, field: &str) -> Expr {
+    Expr::FuncCall {
+        qualifier: None,
+        name: name.to_string(),
+        args: vec![Expr::Field(FieldRef::Qualified(
+            alias.to_string(),
+            field.to_string(),
+        ))],
+    }
+}
+
+fn capture_plan(steps: Vec<wf_lang::plan::StepPlan>) -> wf_lang::plan::MatchPlan {
+    let mut plan = simple_plan(vec![simple_key("sip")], steps);
+    plan.captures = vec![CapturePlan {
+        alias: "fail".to_string(),
+        field: "dport".to_string(),
+    }];
+    plan
+}
+
+fn capture_executor(match_plan: wf_lang::plan::MatchPlan) -> RuleExecutor {
+    let mut plan = simple_rule_plan(
+        "r1",
+        match_plan,
+        l3_call("last", "fail", "dport"),
+        "ip",
+        Expr::Field(FieldRef::Simple("sip".to_string())),
+    );
+    plan.yield_plans[0].fields = vec![
+        YieldField {
+            name: "first_port".to_string(),
+            value: l3_call("first", "fail", "dport"),
+        },
+        YieldField {
+            name: "last_port".to_string(),
+            value: l3_call("last", "fail", "dport"),
+        },
+    ];
+    RuleExecutor::new(plan)
+}
+
+fn fail_event(dport: f64) -> crate::rule::match_engine::Event {
+    event(vec![("sip", str_val("10.0.0.1")), ("dport", num(dport))])
+}
+
+#[test]
+fn first_last_captured_into_alert() {
+    let plan = capture_plan(vec![step(vec![branch("fail", count_ge(3.0))])]);
+    let exec = capture_executor(plan.clone());
+    let mut sm = CepStateMachine::new("r1".to_string(), plan, None);
+
+    assert_eq!(
+        sm.advance("fail", &fail_event(22.0)),
+        StepResult::Accumulate
+    );
+    // Events missing the field do not touch the capture.
+    let no_port = event(vec![("sip", str_val("10.0.0.1"))]);
+    assert_eq!(sm.advance("fail", &no_port), StepResult::Accumulate);
+    let StepResult::Matched(ctx) = sm.advance("fail", &fail_event(25.0)) else {
+        panic!("expected match");
+    };
+
+    let alert = exec.execute_match(&ctx).unwrap().remove(0);
+    assert_eq!(
+        alert.yield_fields,
+        vec![
+            ("first_port".to_string(), num(22.0)),
+            ("last_port".to_string(), num(25.0)),
+        ]
+    );
+    assert_eq!(alert.score, 25.0);
+
+    // The instance reset on match, so the next window starts over.
+    sm.advance("fail", &fail_event(80.0));
+    sm.advance("fail", &fail_event(81.0));
+    let StepResult::Matched(ctx) = sm.advance("fail", &fail_event(443.0)) else {
+        panic!("expected second match");
+    };
+    let alert = exec.execute_match(&ctx).unwrap().remove(0);
+    assert_eq!(alert.yield_fields[0], ("first_port".to_string(), num(80.0)));
+}
+
+#[test]
+fn first_last_captured_on_close_path() {
+    let mut plan = capture_plan(vec![step(vec![branch("fail", count_ge(1.0))])]);
+    plan.close_steps = vec![step(vec![branch("fail", count_ge(1.0))])];
+    plan.close_mode = CloseMode::And;
+    let exec = capture_executor(plan.clone());
+    let mut sm = CepStateMachine::new("r1".to_string(), plan, None);
+
+    for port in [22.0, 23.0, 3389.0] {
+        sm.advance("fail", &fail_event(port));
+    }
+    let close = sm
+        .close(&[str_val("10.0.0.1")], CloseReason::Flush)
+        .unwrap();
+    let alert = exec.execute_close(&close).unwrap().remove(0);
+    assert_eq!(
+        alert.yield_fields,
+        vec![
+            ("first_port".to_string(), num(22.0)),
+            ("last_port".to_string(), num(3389.0)),
+        ]
+    );
+}
@@ -46,6 +46,8 @@ pub fn simple_plan(keys: Vec<FieldRef>, steps: Vec<StepPlan>) -> MatchPlan {
         event_steps: steps,
         close_steps: vec![],
         close_mode: CloseMode::Or,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     }
 }
 
@@ -86,6 +88,8 @@ pub fn plan_with_close(
         event_steps,
         close_steps,
         close_mode: CloseMode::And,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     }
 }
 
@@ -97,6 +101,8 @@ pub fn fixed_plan(keys: Vec<FieldRef>, dur: Duration, steps: Vec<StepPlan>) -> M
         event_steps: steps,
         close_steps: vec![],
         close_mode: CloseMode::Or,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     }
 }
 
@@ -113,6 +119,8 @@ pub fn fixed_plan_with_close(
         event_steps,
         close_steps,
         close_mode: CloseMode::And,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     }
 }
 
@@ -151,14 +159,15 @@ pub fn simple_rule_plan(
             entity_type: entity_type.to_string(),
             entity_id_expr,
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: "alerts".to_string(),
             version: None,
             fields: vec![],
-        },
+        }],
         score_plan: ScorePlan { expr: score_expr },
         pattern_origin: None,
         conv_plan: None,
         limits_plan: None,
+        priority: 0,
     }
 }
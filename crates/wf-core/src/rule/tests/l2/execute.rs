@@ -47,10 +47,11 @@ fn execute_match_without_joins_still_works() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
     // Old API still works
-    let alert = exec.execute_match(&matched).unwrap();
+    let alert = exec.execute_match(&matched).unwrap().remove(0);
     assert_eq!(alert.entity_id, "10.0.0.1");
     assert!((alert.score - 50.0).abs() < f64::EPSILON);
 }
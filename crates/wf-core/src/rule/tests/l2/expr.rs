@@ -169,6 +169,41 @@ fn time_diff_absolute_value() {
     assert_eq!(result, Some(Value::Number(5.0)));
 }
 
+#[test]
+fn int_arithmetic_stays_exact_for_nanos() {
+    use crate::rule::match_engine::{Event, eval_expr};
+
+    // 1 ns apart: indistinguishable once both sides go through f64.
+    let t1: i64 = 1_700_000_000_123_456_789;
+    let mut fields = HashMap::new();
+    fields.insert("t1".to_string(), Value::Int(t1));
+    fields.insert("t2".to_string(), Value::Int(t1 + 1));
+    fields.insert("dport".to_string(), Value::Int(22));
+    let event = Event { fields };
+
+    let diff = Expr::BinOp {
+        op: wf_lang::ast::BinOp::Sub,
+        left: Box::new(Expr::Field(FieldRef::Simple("t2".to_string()))),
+        right: Box::new(Expr::Field(FieldRef::Simple("t1".to_string()))),
+    };
+    assert_eq!(eval_expr(&diff, &event), Some(Value::Int(1)));
+
+    let later = Expr::BinOp {
+        op: wf_lang::ast::BinOp::Gt,
+        left: Box::new(Expr::Field(FieldRef::Simple("t2".to_string()))),
+        right: Box::new(Expr::Field(FieldRef::Simple("t1".to_string()))),
+    };
+    assert_eq!(eval_expr(&later, &event), Some(Value::Bool(true)));
+
+    // Int fields still compare equal to float literals.
+    let is_ssh = Expr::BinOp {
+        op: wf_lang::ast::BinOp::Eq,
+        left: Box::new(Expr::Field(FieldRef::Simple("dport".to_string()))),
+        right: Box::new(Expr::Number(22.0)),
+    };
+    assert_eq!(eval_expr(&is_ssh, &event), Some(Value::Bool(true)));
+}
+
 // ===========================================================================
 // time_bucket
 // ===========================================================================
@@ -456,6 +491,42 @@ fn math_functions_work() {
     );
 }
 
+#[test]
+fn scalar_min_max_pick_smaller_and_larger() {
+    use crate::rule::match_engine::{Event, eval_expr};
+
+    let call = |name: &str, a: Expr, b: Expr| Expr::FuncCall {
+        qualifier: None,
+        name: name.to_string(),
+        args: vec![a, b],
+    };
+    let n = || Expr::Field(FieldRef::Simple("n".to_string()));
+    let mut fields = HashMap::new();
+    fields.insert("n".to_string(), Value::Number(-7.5));
+    fields.insert("msg".to_string(), Value::Str("x".to_string()));
+    let event = Event { fields };
+
+    assert_eq!(
+        eval_expr(&call("min", n(), Expr::Number(0.0)), &event),
+        Some(Value::Number(-7.5))
+    );
+    assert_eq!(
+        eval_expr(&call("max", n(), Expr::Number(0.0)), &event),
+        Some(Value::Number(0.0))
+    );
+    // Non-numeric or missing arguments evaluate to nothing.
+    let msg = Expr::Field(FieldRef::Simple("msg".to_string()));
+    assert_eq!(
+        eval_expr(&call("min", msg, Expr::Number(1.0)), &event),
+        None
+    );
+    let missing = Expr::Field(FieldRef::Simple("missing".to_string()));
+    assert_eq!(
+        eval_expr(&call("max", missing, Expr::Number(1.0)), &event),
+        None
+    );
+}
+
 #[test]
 fn strptime_parses_date() {
     use crate::rule::match_engine::{Event, eval_expr};
@@ -471,7 +542,7 @@ fn strptime_parses_date() {
     let event = Event {
         fields: HashMap::new(),
     };
-    assert_eq!(eval_expr(&expr, &event), Some(Value::Number(0.0)));
+    assert_eq!(eval_expr(&expr, &event), Some(Value::Int(0)));
 }
 
 // ===========================================================================
@@ -778,3 +849,248 @@ fn mvdedup_removes_duplicates_keep_order() {
         ]))
     );
 }
+
+// ===========================================================================
+// String concatenation with `+`
+// ===========================================================================
+
+fn add(left: Expr, right: Expr) -> Expr {
+    Expr::BinOp {
+        op: wf_lang::ast::BinOp::Add,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+#[test]
+fn concat_string_literals() {
+    use crate::rule::match_engine::{Event, eval_expr};
+
+    let expr = add(
+        Expr::StringLit("a".to_string()),
+        Expr::StringLit("b".to_string()),
+    );
+    let event = Event {
+        fields: HashMap::new(),
+    };
+    assert_eq!(eval_expr(&expr, &event), Some(Value::Str("ab".to_string())));
+}
+
+#[test]
+fn concat_ip_and_numeric_port() {
+    use crate::rule::match_engine::{Event, eval_expr};
+
+    // sip + ":" + dport → "10.0.0.1:22"
+    let expr = add(
+        add(
+            Expr::Field(FieldRef::Simple("sip".to_string())),
+            Expr::StringLit(":".to_string()),
+        ),
+        Expr::Field(FieldRef::Simple("dport".to_string())),
+    );
+    let mut fields = HashMap::new();
+    fields.insert("sip".to_string(), Value::Str("10.0.0.1".to_string()));
+    fields.insert("dport".to_string(), Value::Number(22.0));
+    let event = Event { fields };
+    assert_eq!(
+        eval_expr(&expr, &event),
+        Some(Value::Str("10.0.0.1:22".to_string()))
+    );
+
+    // numbers on both sides still add
+    let sum = add(Expr::Number(1.0), Expr::Number(2.0));
+    assert_eq!(eval_expr(&sum, &event), Some(Value::Number(3.0)));
+}
+
+// ===========================================================================
+// coalesce on present / missing fields
+// ===========================================================================
+
+#[test]
+fn coalesce_prefers_present_field() {
+    use crate::rule::match_engine::{Event, eval_expr};
+
+    let expr = Expr::FuncCall {
+        qualifier: None,
+        name: "coalesce".to_string(),
+        args: vec![
+            Expr::Field(FieldRef::Simple("user".to_string())),
+            Expr::StringLit("anonymous".to_string()),
+        ],
+    };
+
+    let mut fields = HashMap::new();
+    fields.insert("user".to_string(), Value::Str("admin".to_string()));
+    let present = Event { fields };
+    assert_eq!(
+        eval_expr(&expr, &present),
+        Some(Value::Str("admin".to_string()))
+    );
+
+    let missing = Event {
+        fields: HashMap::new(),
+    };
+    assert_eq!(
+        eval_expr(&expr, &missing),
+        Some(Value::Str("anonymous".to_string()))
+    );
+}
+
+// ===========================================================================
+// Logical `not`
+// ===========================================================================
+
+#[test]
+fn not_negates_conjunction() {
+    use crate::rule::match_engine::{Event, eval_expr};
+
+    // not (action == "failed" && count > 1)
+    let expr = Expr::Not(Box::new(Expr::BinOp {
+        op: wf_lang::ast::BinOp::And,
+        left: Box::new(Expr::BinOp {
+            op: wf_lang::ast::BinOp::Eq,
+            left: Box::new(Expr::Field(FieldRef::Simple("action".to_string()))),
+            right: Box::new(Expr::StringLit("failed".to_string())),
+        }),
+        right: Box::new(Expr::BinOp {
+            op: wf_lang::ast::BinOp::Gt,
+            left: Box::new(Expr::Field(FieldRef::Simple("count".to_string()))),
+            right: Box::new(Expr::Number(1.0)),
+        }),
+    }));
+
+    let mut fields = HashMap::new();
+    fields.insert("action".to_string(), Value::Str("failed".to_string()));
+    fields.insert("count".to_string(), Value::Number(3.0));
+    let both = Event { fields };
+    assert_eq!(eval_expr(&expr, &both), Some(Value::Bool(false)));
+
+    let mut fields = HashMap::new();
+    fields.insert("action".to_string(), Value::Str("failed".to_string()));
+    fields.insert("count".to_string(), Value::Number(1.0));
+    let one = Event { fields };
+    assert_eq!(eval_expr(&expr, &one), Some(Value::Bool(true)));
+}
+
+#[test]
+fn not_on_missing_field_stays_unknown() {
+    use crate::rule::match_engine::{Event, eval_expr};
+
+    let expr = Expr::Not(Box::new(Expr::Field(FieldRef::Simple(
+        "active".to_string(),
+    ))));
+    let missing = Event {
+        fields: HashMap::new(),
+    };
+    assert_eq!(eval_expr(&expr, &missing), None);
+
+    let mut fields = HashMap::new();
+    fields.insert("active".to_string(), Value::Bool(false));
+    let inactive = Event { fields };
+    assert_eq!(eval_expr(&expr, &inactive), Some(Value::Bool(true)));
+}
+
+// ===========================================================================
+// Array `contains`
+// ===========================================================================
+
+#[test]
+fn contains_on_chars_array() {
+    use crate::rule::match_engine::{Event, eval_expr};
+    use wf_lang::ast::ContainsMode;
+
+    let contains = |mode, values: &[&str]| Expr::Contains {
+        expr: Box::new(Expr::Field(FieldRef::Simple("tags".to_string()))),
+        mode,
+        values: values
+            .iter()
+            .map(|v| Expr::StringLit(v.to_string()))
+            .collect(),
+    };
+    let mut fields = HashMap::new();
+    fields.insert(
+        "tags".to_string(),
+        Value::Array(vec![
+            Value::Str("admin".to_string()),
+            Value::Str("ops".to_string()),
+        ]),
+    );
+    let event = Event { fields };
+    let eval = |expr: Expr| eval_expr(&expr, &event);
+
+    assert_eq!(
+        eval(contains(ContainsMode::Single, &["admin"])),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(
+        eval(contains(ContainsMode::Single, &["root"])),
+        Some(Value::Bool(false))
+    );
+    assert_eq!(
+        eval(contains(ContainsMode::Any, &["root", "ops"])),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(
+        eval(contains(ContainsMode::All, &["admin", "ops"])),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(
+        eval(contains(ContainsMode::All, &["admin", "root"])),
+        Some(Value::Bool(false))
+    );
+
+    // A missing or non-array operand is unknown, not false.
+    let missing = Event {
+        fields: HashMap::new(),
+    };
+    assert_eq!(
+        eval_expr(&contains(ContainsMode::Single, &["admin"]), &missing),
+        None
+    );
+    let mut fields = HashMap::new();
+    fields.insert("tags".to_string(), Value::Str("admin".to_string()));
+    let scalar = Event { fields };
+    assert_eq!(
+        eval_expr(&contains(ContainsMode::Single, &["admin"]), &scalar),
+        None
+    );
+}
+
+// ===========================================================================
+// Time difference vs duration literal
+// ===========================================================================
+
+#[test]
+fn time_difference_guard_compares_with_duration() {
+    use crate::rule::match_engine::{Event, eval_expr};
+    use wf_lang::ast::BinOp;
+
+    let field = |name: &str| Box::new(Expr::Field(FieldRef::Simple(name.to_string())));
+    // resp_time - event_time > <secs>s
+    let guard = |secs: u64| Expr::BinOp {
+        op: BinOp::Gt,
+        left: Box::new(Expr::BinOp {
+            op: BinOp::Sub,
+            left: field("resp_time"),
+            right: field("event_time"),
+        }),
+        right: Box::new(Expr::Duration(Duration::from_secs(secs))),
+    };
+
+    // Time fields are epoch nanoseconds; the response came 7s later.
+    let req_ns = 1_700_000_000_000_000_000i64;
+    let mut fields = HashMap::new();
+    fields.insert("event_time".to_string(), Value::Number(req_ns as f64));
+    fields.insert(
+        "resp_time".to_string(),
+        Value::Number((req_ns + 7_000_000_000) as f64),
+    );
+    let event = Event { fields };
+
+    assert_eq!(eval_expr(&guard(5), &event), Some(Value::Bool(true)));
+    assert_eq!(eval_expr(&guard(10), &event), Some(Value::Bool(false)));
+    assert_eq!(
+        eval_expr(&Expr::Duration(Duration::from_secs(5)), &event),
+        Some(Value::Number(5_000_000_000.0))
+    );
+}
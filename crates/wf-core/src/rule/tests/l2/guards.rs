@@ -205,3 +205,111 @@ fn window_has_numeric_field() {
         result
     );
 }
+
+// ---------------------------------------------------------------------------
+// window.count() / window.sum() aggregate lookups
+// ---------------------------------------------------------------------------
+
+fn watchlist_lookup() -> MockWindowLookup {
+    let mut lookup = MockWindowLookup::new();
+    lookup.add_snapshot(
+        "watchlist",
+        vec![
+            row(vec![("ip", str_val("10.0.0.1")), ("score", num(2.5))]),
+            row(vec![("ip", str_val("10.0.0.2")), ("score", num(4.0))]),
+            row(vec![("ip", str_val("10.0.0.3"))]),
+        ],
+    );
+    lookup.add_snapshot("empty", vec![]);
+    lookup
+}
+
+fn window_agg(window: &str, name: &str, args: Vec<Expr>) -> Expr {
+    Expr::FuncCall {
+        qualifier: Some(window.to_string()),
+        name: name.to_string(),
+        args,
+    }
+}
+
+#[test]
+fn window_count_and_sum_lookups() {
+    use crate::rule::match_engine::eval_expr_ext;
+
+    let lookup = watchlist_lookup();
+    assert_eq!(lookup.row_count("watchlist"), Some(3));
+    assert_eq!(lookup.field_sum("watchlist", "score"), Some(6.5));
+    assert_eq!(lookup.field_sum("empty", "score"), Some(0.0));
+    assert_eq!(lookup.row_count("missing"), None);
+
+    let e = event(vec![("sip", str_val("10.0.0.9"))]);
+    let eval = |expr: &Expr| eval_expr_ext(expr, &e, Some(&lookup), &mut HashMap::new());
+    assert_eq!(
+        eval(&window_agg("watchlist", "count", vec![])),
+        Some(num(3.0))
+    );
+    assert_eq!(
+        eval(&window_agg(
+            "watchlist",
+            "sum",
+            vec![Expr::StringLit("score".to_string())]
+        )),
+        Some(num(6.5))
+    );
+    assert_eq!(eval(&window_agg("empty", "count", vec![])), Some(num(0.0)));
+    // Unknown window or no lookup at all: not evaluable.
+    assert_eq!(eval(&window_agg("missing", "count", vec![])), None);
+    assert_eq!(
+        eval_expr_ext(
+            &window_agg("watchlist", "count", vec![]),
+            &e,
+            None,
+            &mut HashMap::new()
+        ),
+        None
+    );
+}
+
+/// `watchlist.count() > 0` gates the step on another window being non-empty.
+#[test]
+fn window_count_gates_guard() {
+    use wf_lang::ast::{BinOp, Measure};
+    use wf_lang::plan::{AggPlan, BranchPlan};
+
+    let plan_for = |window: &str| {
+        let guard = Expr::BinOp {
+            op: BinOp::Gt,
+            left: Box::new(window_agg(window, "count", vec![])),
+            right: Box::new(Expr::Number(0.0)),
+        };
+        simple_plan(
+            vec![simple_key("sip")],
+            vec![step(vec![BranchPlan {
+                label: None,
+                source: "fail".to_string(),
+                field: None,
+                guard: Some(guard),
+                agg: AggPlan {
+                    transforms: vec![],
+                    measure: Measure::Count,
+                    cmp: CmpOp::Ge,
+                    threshold: Expr::Number(1.0),
+                },
+            }])],
+        )
+    };
+    let lookup = watchlist_lookup();
+    let e = event(vec![("sip", str_val("10.0.0.1"))]);
+
+    let mut sm = CepStateMachine::new("count_gate".into(), plan_for("watchlist"), None);
+    assert!(matches!(
+        sm.advance_with("fail", &e, Some(&lookup)),
+        StepResult::Matched(_)
+    ));
+
+    let mut sm = CepStateMachine::new("count_gate_empty".into(), plan_for("empty"), None);
+    assert_eq!(
+        sm.advance_with("fail", &e, Some(&lookup)),
+        StepResult::Accumulate
+    );
+}
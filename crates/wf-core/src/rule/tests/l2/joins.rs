@@ -41,13 +41,97 @@ fn join_snapshot_enriches_context() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match_with_joins(&matched, &wl).unwrap();
+    let alert = exec
+        .execute_match_with_joins(&matched, &wl)
+        .unwrap()
+        .remove(0);
     assert_eq!(alert.rule_name, "r_join");
     assert!((alert.score - 70.0).abs() < f64::EPSILON);
 }
 
+// ===========================================================================
+// Join: equality plus range predicates select a tier row
+// ===========================================================================
+
+#[test]
+fn join_range_bounded_selects_tier() {
+    // join risk_tiers snapshot on sip == ip && fail >= lo && fail < hi
+    let cond = |left: &str, op: CmpOp, right: &str| JoinCondPlan {
+        left: FieldRef::Simple(left.to_string()),
+        op,
+        right: FieldRef::Simple(right.to_string()),
+    };
+    let match_plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![branch("fail", count_ge(1.0))])],
+    );
+    let mut rule_plan = simple_rule_plan(
+        "r_join_range",
+        match_plan,
+        Expr::Field(FieldRef::Simple("risk".to_string())),
+        "ip",
+        Expr::Field(FieldRef::Simple("sip".to_string())),
+    );
+    rule_plan.joins = vec![JoinPlan {
+        right_window: "risk_tiers".to_string(),
+        mode: JoinMode::Snapshot,
+        optional: false,
+        conds: vec![
+            cond("sip", CmpOp::Eq, "ip"),
+            cond("fail", CmpOp::Ge, "lo"),
+            cond("fail", CmpOp::Lt, "hi"),
+        ],
+    }];
+    let exec = RuleExecutor::new(rule_plan);
+
+    let tier = |ip: &str, lo: f64, hi: f64, risk: f64| {
+        row(vec![
+            ("ip", str_val(ip)),
+            ("lo", num(lo)),
+            ("hi", num(hi)),
+            ("risk", num(risk)),
+        ])
+    };
+    let mut wl = MockWindowLookup::new();
+    wl.add_snapshot(
+        "risk_tiers",
+        vec![
+            // Same range on another ip: equality must still filter it out
+            tier("10.0.0.9", 5.0, 10.0, 99.0),
+            tier("10.0.0.1", 0.0, 5.0, 20.0),
+            tier("10.0.0.1", 5.0, 10.0, 60.0),
+            tier("10.0.0.1", 10.0, 1000.0, 90.0),
+        ],
+    );
+
+    let matched_with = |count: f64| MatchedContext {
+        rule_name: "r_join_range".to_string(),
+        scope_key: vec![str_val("10.0.0.1")],
+        step_data: vec![StepData {
+            satisfied_branch_index: 0,
+            label: Some("fail".to_string()),
+            measure_value: count,
+            collected_values: Vec::new(),
+        }],
+        event_time_nanos: 0,
+        captures: vec![],
+    };
+
+    let score = |count: f64| {
+        exec.execute_match_with_joins(&matched_with(count), &wl)
+            .unwrap()
+            .remove(0)
+            .score
+    };
+    assert!((score(7.0) - 60.0).abs() < f64::EPSILON);
+    // Lower bound inclusive, upper bound exclusive
+    assert!((score(10.0) - 90.0).abs() < f64::EPSILON);
+    assert!((score(4.0) - 20.0).abs() < f64::EPSILON);
+}
+
 // ===========================================================================
 // Join: entity from joined field
 // ===========================================================================
@@ -89,9 +173,13 @@ fn join_entity_from_joined_field() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match_with_joins(&matched, &wl).unwrap();
+    let alert = exec
+        .execute_match_with_joins(&matched, &wl)
+        .unwrap()
+        .remove(0);
     assert_eq!(alert.entity_id, "web-server-01");
 }
 
@@ -137,10 +225,14 @@ fn join_no_match_falls_through() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 0,
+        captures: vec![],
     };
 
     // No join match — entity falls back to "sip" from keys
-    let alert = exec.execute_match_with_joins(&matched, &wl).unwrap();
+    let alert = exec
+        .execute_match_with_joins(&matched, &wl)
+        .unwrap()
+        .remove(0);
     assert_eq!(alert.entity_id, "10.0.0.1");
 }
 
@@ -190,9 +282,13 @@ fn join_close_with_joins() {
         close_step_data: vec![],
         watermark_nanos: 0,
         last_event_nanos: 0,
+        captures: vec![],
     };
 
-    let alert = exec.execute_close_with_joins(&close, &wl).unwrap().unwrap();
+    let alert = exec
+        .execute_close_with_joins(&close, &wl)
+        .unwrap()
+        .remove(0);
     assert_eq!(alert.origin.as_str(), "close:timeout");
     assert!((alert.score - 60.0).abs() < f64::EPSILON);
 }
@@ -253,9 +349,13 @@ fn join_asof_picks_latest_before_event_time() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: event_time,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match_with_joins(&matched, &wl).unwrap();
+    let alert = exec
+        .execute_match_with_joins(&matched, &wl)
+        .unwrap()
+        .remove(0);
     // Should pick the row at 800ms with risk=90.0
     assert!((alert.score - 90.0).abs() < f64::EPSILON);
 }
@@ -316,13 +416,190 @@ fn join_asof_within_filters_old_rows() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: event_time,
+        captures: vec![],
     };
 
-    let alert = exec.execute_match_with_joins(&matched, &wl).unwrap();
+    let alert = exec
+        .execute_match_with_joins(&matched, &wl)
+        .unwrap()
+        .remove(0);
     // Should pick the row at 600ms (the only one within the window)
     assert!((alert.score - 75.0).abs() < f64::EPSILON);
 }
 
+// ===========================================================================
+// Join asof direction: forward / backward and exact-timestamp ties
+// ===========================================================================
+
+/// Run a single-join rule whose score is the joined `risk` field and return
+/// that score, or `None` when the join found no row (no output).
+fn asof_risk(join: JoinPlan, rows: TimestampedRows, event_time: i64) -> Option<f64> {
+    let risk = Expr::Field(FieldRef::Simple("risk".to_string()));
+    asof_score(join, risk, rows, event_time)
+}
+
+/// Like [`asof_risk`] with a caller-supplied score expression.
+fn asof_score(join: JoinPlan, score: Expr, rows: TimestampedRows, event_time: i64) -> Option<f64> {
+    let match_plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![branch("fail", count_ge(1.0))])],
+    );
+    let mut rule_plan = simple_rule_plan(
+        "r_asof_dir",
+        match_plan,
+        score,
+        "ip",
+        Expr::Field(FieldRef::Simple("sip".to_string())),
+    );
+    rule_plan.joins = vec![join];
+    let exec = RuleExecutor::new(rule_plan);
+
+    let mut wl = MockWindowLookup::new();
+    wl.add_timestamped_snapshot("resp", rows);
+    let matched = MatchedContext {
+        rule_name: "r_asof_dir".to_string(),
+        scope_key: vec![str_val("10.0.0.1")],
+        step_data: vec![StepData {
+            satisfied_branch_index: 0,
+            label: None,
+            measure_value: 1.0,
+            collected_values: Vec::new(),
+        }],
+        event_time_nanos: event_time,
+        captures: vec![],
+    };
+    exec.execute_match_with_joins(&matched, &wl)
+        .ok()
+        .and_then(|alerts| alerts.first().map(|a| a.score))
+}
+
+fn risk_row(ts: i64, risk: f64) -> (i64, HashMap<String, Value>) {
+    (
+        ts,
+        row(vec![("ip", str_val("10.0.0.1")), ("risk", num(risk))]),
+    )
+}
+
+#[test]
+fn join_asof_forward_picks_earliest_after_event_time() {
+    let rows = vec![
+        risk_row(500_000_000, 10.0),
+        risk_row(3_000_000_000, 30.0),
+        risk_row(1_500_000_000, 20.0),
+    ];
+    let forward = asof_join_directed("resp", "sip", "ip", AsofDirection::Forward, None);
+    assert_eq!(asof_risk(forward, rows.clone(), 1_000_000_000), Some(20.0));
+
+    let backward = asof_join_directed("resp", "sip", "ip", AsofDirection::Backward, None);
+    assert_eq!(asof_risk(backward, rows, 1_000_000_000), Some(10.0));
+}
+
+#[test]
+fn join_asof_forward_within_bounds_lookahead() {
+    let rows = vec![risk_row(5_000_000_000, 50.0)];
+    let near = asof_join_directed(
+        "resp",
+        "sip",
+        "ip",
+        AsofDirection::Forward,
+        Some(Duration::from_secs(2)),
+    );
+    assert_eq!(asof_risk(near, rows.clone(), 1_000_000_000), None);
+
+    let far = asof_join_directed(
+        "resp",
+        "sip",
+        "ip",
+        AsofDirection::Forward,
+        Some(Duration::from_secs(10)),
+    );
+    assert_eq!(asof_risk(far, rows, 1_000_000_000), Some(50.0));
+}
+
+#[test]
+fn join_asof_exact_tie_is_last_wins() {
+    // Two rows at exactly the event time: the one appended later wins,
+    // whichever direction is searched.
+    let rows = vec![
+        risk_row(1_000_000_000, 40.0),
+        risk_row(1_000_000_000, 60.0),
+        risk_row(2_000_000_000, 80.0),
+    ];
+    for direction in [AsofDirection::Backward, AsofDirection::Forward] {
+        let join = asof_join_directed("resp", "sip", "ip", direction, None);
+        assert_eq!(
+            asof_risk(join, rows.clone(), 1_000_000_000),
+            Some(60.0),
+            "{direction:?}"
+        );
+    }
+}
+
+// ===========================================================================
+// Join asof: required by default, `optional` keeps the match with null fields
+// ===========================================================================
+
+fn optional_asof(within: Option<Duration>) -> JoinPlan {
+    JoinPlan {
+        optional: true,
+        ..asof_join_directed("resp", "sip", "ip", AsofDirection::Backward, within)
+    }
+}
+
+/// `coalesce(risk, 5.0)`
+fn risk_or_default() -> Expr {
+    Expr::FuncCall {
+        qualifier: None,
+        name: "coalesce".to_string(),
+        args: vec![
+            Expr::Field(FieldRef::Simple("risk".to_string())),
+            Expr::Number(5.0),
+        ],
+    }
+}
+
+#[test]
+fn join_asof_required_drops_match_without_row() {
+    // Only row is after the event time, so a backward asof finds nothing.
+    let rows = vec![risk_row(2_000_000_000, 80.0)];
+    let join = asof_join_directed("resp", "sip", "ip", AsofDirection::Backward, None);
+    assert_eq!(
+        asof_score(join, risk_or_default(), rows, 1_000_000_000),
+        None
+    );
+}
+
+#[test]
+fn join_asof_optional_keeps_match_with_null_fields() {
+    let rows = vec![risk_row(2_000_000_000, 80.0)];
+    assert_eq!(
+        asof_score(optional_asof(None), risk_or_default(), rows, 1_000_000_000),
+        Some(5.0)
+    );
+
+    // `within` excluding the only earlier row behaves the same way.
+    let rows = vec![risk_row(100_000_000, 80.0)];
+    let within = Some(Duration::from_millis(500));
+    assert_eq!(
+        asof_score(
+            optional_asof(within),
+            risk_or_default(),
+            rows,
+            1_000_000_000
+        ),
+        Some(5.0)
+    );
+}
+
+#[test]
+fn join_asof_optional_uses_row_when_found() {
+    let rows = vec![risk_row(500_000_000, 80.0)];
+    assert_eq!(
+        asof_score(optional_asof(None), risk_or_default(), rows, 1_000_000_000),
+        Some(80.0)
+    );
+}
+
 // ===========================================================================
 // Join asof: no timestamp support → graceful skip (no match)
 // ===========================================================================
@@ -358,10 +635,14 @@ fn join_asof_no_timestamp_support_skips() {
             collected_values: Vec::new(),
         }],
         event_time_nanos: 1_000_000_000,
+        captures: vec![],
     };
 
     // Join produces no match, but alert still works with score=42
-    let alert = exec.execute_match_with_joins(&matched, &wl).unwrap();
+    let alert = exec
+        .execute_match_with_joins(&matched, &wl)
+        .unwrap()
+        .remove(0);
     assert!((alert.score - 42.0).abs() < f64::EPSILON);
 }
 
@@ -429,9 +710,13 @@ fn join_asof_close_uses_last_event_nanos() {
         close_step_data: vec![],
         watermark_nanos: watermark,
         last_event_nanos: last_event,
+        captures: vec![],
     };
 
-    let alert = exec.execute_close_with_joins(&close, &wl).unwrap().unwrap();
+    let alert = exec
+        .execute_close_with_joins(&close, &wl)
+        .unwrap()
+        .remove(0);
     // Should pick the row at 500ms (risk=60), NOT the row at 3s (risk=99)
     assert!(
         (alert.score - 60.0).abs() < f64::EPSILON,
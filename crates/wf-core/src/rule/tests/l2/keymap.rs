@@ -31,6 +31,8 @@ fn key_map_extracts_from_alias_field() {
         ])],
         close_steps: vec![],
         close_mode: CloseMode::Or,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     };
 
     let mut sm = CepStateMachine::new("rule_km".to_string(), plan, None);
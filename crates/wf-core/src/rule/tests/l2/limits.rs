@@ -517,3 +517,94 @@ fn limits_close_all_rate_limit_deterministic() {
         "earliest-created instances should get alerts"
     );
 }
+
+// ===========================================================================
+// Limits: suppression counts
+// ===========================================================================
+
+#[test]
+fn limits_take_suppressed_counts_by_reason() {
+    let plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![branch("fail", count_ge(1.0))])],
+    );
+    let limits = LimitsPlan {
+        max_memory_bytes: None,
+        max_instances: Some(1),
+        max_throttle: Some(RateSpec {
+            count: 1,
+            per: Duration::from_secs(60),
+        }),
+        on_exceed: ExceedAction::FailRule,
+    };
+    let mut sm = CepStateMachine::with_limits("rule_sup".to_string(), plan, None, Some(limits));
+
+    let e1 = event(vec![("sip", str_val("10.0.0.1"))]);
+    assert!(sm.take_suppressed().is_empty());
+
+    // First match passes, second exceeds max_throttle and fails the rule
+    assert!(matches!(
+        sm.advance_at("fail", &e1, 1_000_000_000),
+        StepResult::Matched(_)
+    ));
+    assert_eq!(
+        sm.advance_at("fail", &e1, 2_000_000_000),
+        StepResult::Accumulate
+    );
+    // Further events are rejected by the failed rule
+    sm.advance_at("fail", &e1, 3_000_000_000);
+    sm.advance_at("fail", &e1, 4_000_000_000);
+
+    assert_eq!(
+        sm.take_suppressed(),
+        vec![
+            (SuppressReason::MaxThrottle, 1),
+            (SuppressReason::RuleFailed, 2),
+        ]
+    );
+    // Counts are drained
+    assert!(sm.take_suppressed().is_empty());
+}
+
+#[test]
+fn limits_reset_recovers_failed_rule() {
+    let plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![branch("fail", count_ge(1.0))])],
+    );
+    let limits = LimitsPlan {
+        max_memory_bytes: None,
+        max_instances: Some(1),
+        max_throttle: None,
+        on_exceed: ExceedAction::FailRule,
+    };
+    let mut sm = CepStateMachine::with_limits("rule_reset".to_string(), plan, None, Some(limits));
+
+    let e1 = event(vec![("sip", str_val("10.0.0.1"))]);
+    let e2 = event(vec![("sip", str_val("10.0.0.2"))]);
+
+    // count >= 1 matches immediately but the instance stays; a second key
+    // exceeds max_instances and fails the rule.
+    assert!(matches!(
+        sm.advance_at("fail", &e1, 1_000_000_000),
+        StepResult::Matched(_)
+    ));
+    assert!(!sm.is_failed());
+    let _ = sm.advance_at("fail", &e2, 2_000_000_000);
+    assert!(sm.is_failed());
+    assert_eq!(
+        sm.advance_at("fail", &e1, 3_000_000_000),
+        StepResult::Accumulate
+    );
+
+    sm.reset();
+    assert!(!sm.is_failed());
+    assert_eq!(sm.instance_count(), 0);
+
+    // Matching resumes on a fresh state.
+    assert!(matches!(
+        sm.advance_at("fail", &e2, 4_000_000_000),
+        StepResult::Matched(_)
+    ));
+    assert_eq!(sm.instance_count(), 1);
+}
@@ -12,14 +12,15 @@ mod limits;
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
-use wf_lang::ast::{CloseMode, Expr, FieldRef, JoinMode};
+use wf_lang::ast::{AsofDirection, CloseMode, CmpOp, Expr, FieldRef, JoinMode};
 use wf_lang::plan::{
     ExceedAction, JoinCondPlan, JoinPlan, KeyMapPlan, LimitsPlan, MatchPlan, RateSpec, WindowSpec,
 };
 
 use crate::rule::RuleExecutor;
 use crate::rule::match_engine::{
-    CepStateMachine, CloseReason, MatchedContext, StepData, StepResult, Value, WindowLookup,
+    CepStateMachine, CloseReason, MatchedContext, StepData, StepResult, SuppressReason, Value,
+    WindowLookup,
 };
 
 use super::helpers::*;
@@ -95,8 +96,10 @@ fn snapshot_join(window: &str, left_field: &str, right_field: &str) -> JoinPlan
     JoinPlan {
         right_window: window.to_string(),
         mode: wf_lang::ast::JoinMode::Snapshot,
+        optional: false,
         conds: vec![JoinCondPlan {
             left: FieldRef::Simple(left_field.to_string()),
+            op: CmpOp::Eq,
             right: FieldRef::Simple(right_field.to_string()),
         }],
     }
@@ -106,9 +109,34 @@ fn snapshot_join(window: &str, left_field: &str, right_field: &str) -> JoinPlan
 fn asof_join(window: &str, left_field: &str, right_field: &str) -> JoinPlan {
     JoinPlan {
         right_window: window.to_string(),
-        mode: JoinMode::Asof { within: None },
+        mode: JoinMode::Asof {
+            direction: AsofDirection::Backward,
+            within: None,
+        },
+        optional: false,
+        conds: vec![JoinCondPlan {
+            left: FieldRef::Simple(left_field.to_string()),
+            op: CmpOp::Eq,
+            right: FieldRef::Simple(right_field.to_string()),
+        }],
+    }
+}
+
+/// Build an asof JoinPlan with an explicit direction.
+fn asof_join_directed(
+    window: &str,
+    left_field: &str,
+    right_field: &str,
+    direction: AsofDirection,
+    within: Option<Duration>,
+) -> JoinPlan {
+    JoinPlan {
+        right_window: window.to_string(),
+        mode: JoinMode::Asof { direction, within },
+        optional: false,
         conds: vec![JoinCondPlan {
             left: FieldRef::Simple(left_field.to_string()),
+            op: CmpOp::Eq,
             right: FieldRef::Simple(right_field.to_string()),
         }],
     }
@@ -124,10 +152,13 @@ fn asof_join_within(
     JoinPlan {
         right_window: window.to_string(),
         mode: JoinMode::Asof {
+            direction: AsofDirection::Backward,
             within: Some(within),
         },
+        optional: false,
         conds: vec![JoinCondPlan {
             left: FieldRef::Simple(left_field.to_string()),
+            op: CmpOp::Eq,
             right: FieldRef::Simple(right_field.to_string()),
         }],
     }
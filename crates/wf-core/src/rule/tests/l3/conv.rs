@@ -21,6 +21,7 @@ fn make_close_output(
         close_step_data,
         watermark_nanos: 0,
         last_event_nanos: 0,
+        captures: vec![],
     }
 }
 
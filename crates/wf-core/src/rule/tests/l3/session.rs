@@ -5,10 +5,16 @@ fn secs(n: i64) -> i64 {
 }
 
 fn session_plan(gap_secs: u64) -> wf_lang::plan::MatchPlan {
+    session_plan_with(wf_lang::ast::SessionSpec::new(Duration::from_secs(
+        gap_secs,
+    )))
+}
+
+fn session_plan_with(spec: wf_lang::ast::SessionSpec) -> wf_lang::plan::MatchPlan {
     wf_lang::plan::MatchPlan {
         keys: vec![FieldRef::Simple("k".to_string())],
         key_map: None,
-        window_spec: wf_lang::plan::WindowSpec::Session(Duration::from_secs(gap_secs)),
+        window_spec: wf_lang::plan::WindowSpec::Session(spec),
         event_steps: vec![wf_lang::plan::StepPlan {
             branches: vec![wf_lang::plan::BranchPlan {
                 label: None,
@@ -26,6 +32,8 @@ fn session_plan(gap_secs: u64) -> wf_lang::plan::MatchPlan {
         }],
         close_steps: vec![],
         close_mode: CloseMode::Or,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     }
 }
 
@@ -68,3 +76,87 @@ fn session_scan_expired_sorted_by_last_event_time() {
     assert_eq!(expired[0].watermark_nanos, secs(14)); // 4 + 10
     assert_eq!(expired[1].watermark_nanos, secs(18)); // 8 + 10
 }
+
+/// Session plan whose close path fires on any event (`e | count >= 1`).
+fn session_close_plan(spec: wf_lang::ast::SessionSpec) -> wf_lang::plan::MatchPlan {
+    let mut plan = session_plan_with(spec);
+    let mut close_step = plan.event_steps[0].clone();
+    close_step.branches[0].agg.threshold = Expr::Number(1.0);
+    plan.close_steps = vec![close_step];
+    plan
+}
+
+#[test]
+fn session_max_duration_caps_busy_session() {
+    let spec = wf_lang::ast::SessionSpec {
+        max_duration: Some(Duration::from_secs(30)),
+        ..wf_lang::ast::SessionSpec::new(Duration::from_secs(10))
+    };
+    let mut sm = CepStateMachine::new("r_session_cap".to_string(), session_plan_with(spec), None);
+    let e = crate::rule::tests::helpers::event(vec![("k", Value::Str("a".to_string()))]);
+
+    // An event every 5s never leaves a 10s gap.
+    for t in (0..=25).step_by(5) {
+        let _ = sm.advance_at("e", &e, secs(t));
+        assert!(sm.scan_expired_at(secs(t)).is_empty());
+    }
+
+    // The gap alone would keep it open until 35s; the cap closes it at 30s.
+    let expired = sm.scan_expired_at(secs(30));
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].close_reason, CloseReason::Timeout);
+    assert_eq!(expired[0].watermark_nanos, secs(30)); // created_at(0s) + max_duration(30s)
+    assert_eq!(sm.instance_count(), 0);
+}
+
+#[test]
+fn session_gap_still_applies_before_max_duration() {
+    let spec = wf_lang::ast::SessionSpec {
+        max_duration: Some(Duration::from_secs(60)),
+        ..wf_lang::ast::SessionSpec::new(Duration::from_secs(10))
+    };
+    let mut sm = CepStateMachine::new("r_session_gap".to_string(), session_plan_with(spec), None);
+    let e = crate::rule::tests::helpers::event(vec![("k", Value::Str("a".to_string()))]);
+
+    let _ = sm.advance_at("e", &e, secs(0));
+    let _ = sm.advance_at("e", &e, secs(5));
+
+    let expired = sm.scan_expired_at(secs(15));
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].watermark_nanos, secs(15)); // last_event(5s) + gap(10s)
+}
+
+#[test]
+fn session_below_min_events_does_not_emit() {
+    let spec = wf_lang::ast::SessionSpec {
+        min_events: Some(3),
+        ..wf_lang::ast::SessionSpec::new(Duration::from_secs(10))
+    };
+    let mut sm = CepStateMachine::new("r_session_min".to_string(), session_close_plan(spec), None);
+    let a = crate::rule::tests::helpers::event(vec![("k", Value::Str("a".to_string()))]);
+    let b = crate::rule::tests::helpers::event(vec![("k", Value::Str("b".to_string()))]);
+
+    // a: 2 events (below min_events), b: 3 events.
+    for t in [0, 1] {
+        let _ = sm.advance_at("e", &a, secs(t));
+    }
+    for t in [0, 1, 2] {
+        let _ = sm.advance_at("e", &b, secs(t));
+    }
+
+    let expired = sm.scan_expired_at(secs(30));
+    assert_eq!(expired.len(), 2);
+    let trivial = &expired[0];
+    assert_eq!(trivial.scope_key, vec![Value::Str("a".to_string())]);
+    assert!(!trivial.close_ok, "2-event session must not emit");
+    let kept = &expired[1];
+    assert_eq!(kept.scope_key, vec![Value::Str("b".to_string())]);
+    assert!(kept.close_ok);
+    assert_eq!(kept.close_step_data[0].measure_value, 3.0);
+
+    // close_all applies the same filter.
+    let _ = sm.advance_at("e", &a, secs(40));
+    let flushed = sm.close_all(CloseReason::Eos);
+    assert_eq!(flushed.len(), 1);
+    assert!(!flushed[0].close_ok);
+}
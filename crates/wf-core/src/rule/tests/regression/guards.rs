@@ -1,7 +1,7 @@
 use super::*;
 
 // ---------------------------------------------------------------------------
-// Guards: InList / FuncCall (22–25)
+// Guards: InList / FuncCall (22–26)
 // ---------------------------------------------------------------------------
 
 #[test]
@@ -162,3 +162,51 @@ fn guard_func_lower_in_list() {
     ]);
     assert!(matches!(sm.advance("conn", &tcp), StepResult::Matched(_)));
 }
+
+#[test]
+fn guard_func_lower_eq_case_insensitive() {
+    // Guard: `lower(user) == "admin"` — mixed-case usernames normalize before comparison
+    let guard = Expr::BinOp {
+        op: BinOp::Eq,
+        left: Box::new(Expr::FuncCall {
+            qualifier: None,
+            name: "lower".to_string(),
+            args: vec![Expr::Field(FieldRef::Simple("user".to_string()))],
+        }),
+        right: Box::new(Expr::StringLit("admin".to_string())),
+    };
+
+    let plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![BranchPlan {
+            label: None,
+            source: "auth".to_string(),
+            field: None,
+            guard: Some(guard),
+            agg: count_ge(2.0),
+        }])],
+    );
+    let mut sm = CepStateMachine::new("rule26".to_string(), plan, None);
+
+    // "guest" → lower → "guest" → skipped
+    let guest = event(vec![
+        ("sip", str_val("10.0.0.1")),
+        ("user", str_val("guest")),
+    ]);
+    assert_eq!(sm.advance("auth", &guest), StepResult::Accumulate);
+
+    // "Admin" and "ADMIN" both lower to "admin" → counted
+    let admin = event(vec![
+        ("sip", str_val("10.0.0.1")),
+        ("user", str_val("Admin")),
+    ]);
+    assert_eq!(sm.advance("auth", &admin), StepResult::Accumulate);
+    let admin_upper = event(vec![
+        ("sip", str_val("10.0.0.1")),
+        ("user", str_val("ADMIN")),
+    ]);
+    assert!(matches!(
+        sm.advance("auth", &admin_upper),
+        StepResult::Matched(_)
+    ));
+}
@@ -5,7 +5,7 @@ use std::time::Duration;
 use wf_lang::ast::{BinOp, CmpOp, Expr, FieldRef, FieldSelector, Measure};
 use wf_lang::plan::{AggPlan, BranchPlan};
 
-use crate::rule::match_engine::{CepStateMachine, CloseReason, StepResult};
+use crate::rule::match_engine::{CepStateMachine, CloseReason, StepResult, Value};
 
 use super::helpers::*;
 
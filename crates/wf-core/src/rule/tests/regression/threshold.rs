@@ -92,7 +92,8 @@ fn non_constant_threshold_no_silent_zero() {
     // Threshold is a field ref expression (non-constant).
     // Previously this would silently evaluate to 0.0, causing false positives
     // when count >= 0 (always true after any event).
-    // Now try_eval_expr_to_f64 returns None → check_threshold returns false.
+    // Now it is resolved against the triggering event: a missing field never
+    // satisfies, a present one compares against its real value.
     let agg = AggPlan {
         transforms: vec![],
         measure: Measure::Count,
@@ -112,12 +113,16 @@ fn non_constant_threshold_no_silent_zero() {
     );
     let mut sm = CepStateMachine::new("rule33".to_string(), plan, None);
 
-    // Even after many events, the threshold can't be resolved, so it never matches
-    let e = event(vec![("sip", str_val("10.0.0.1")), ("limit", num(5.0))]);
+    // Without `limit` the threshold can't be resolved, so it never matches
+    let e = event(vec![("sip", str_val("10.0.0.1"))]);
     for _ in 0..10 {
         assert_eq!(sm.advance("fail", &e), StepResult::Accumulate);
     }
-    // Should never fire — threshold is not a static constant
+
+    // With `limit` = 12 the 11th event still does not fire; the 12th does
+    let e = event(vec![("sip", str_val("10.0.0.1")), ("limit", num(12.0))]);
+    assert_eq!(sm.advance("fail", &e), StepResult::Accumulate);
+    assert!(matches!(sm.advance("fail", &e), StepResult::Matched(_)));
 }
 
 #[test]
@@ -125,7 +130,8 @@ fn min_max_non_constant_threshold_no_false_positive() {
     // min(hostname) > field_ref — threshold is a non-constant expression.
     // Previously eval_expr_to_value returned Value::Str(""), which could
     // produce false positives via cross-type ordering (Number < Str < Bool).
-    // Now try_eval_expr_to_value returns None → check_threshold returns false.
+    // Now an unresolvable threshold (field missing from the triggering event
+    // or the synthetic close event) → check_threshold returns false.
 
     // Event-step path
     let agg_min = AggPlan {
@@ -149,13 +155,19 @@ fn min_max_non_constant_threshold_no_false_positive() {
     let e = event(vec![
         ("sip", str_val("10.0.0.1")),
         ("hostname", str_val("zebra")),
-        ("baseline", str_val("alpha")),
     ]);
-    // Even though "zebra" > "alpha" in string ordering, the threshold is a
-    // field ref and cannot be statically resolved → must not fire.
+    // `baseline` is absent, so the threshold cannot be resolved → must not
+    // fire through cross-type ordering.
     for _ in 0..5 {
         assert_eq!(sm.advance("dns", &e), StepResult::Accumulate);
     }
+    // Resolved to a same-typed value it compares normally: "zebra" > "alpha".
+    let e = event(vec![
+        ("sip", str_val("10.0.0.1")),
+        ("hostname", str_val("zebra")),
+        ("baseline", str_val("alpha")),
+    ]);
+    assert!(matches!(sm.advance("dns", &e), StepResult::Matched(_)));
 
     // Close-step path
     let agg_max = AggPlan {
@@ -191,7 +203,7 @@ fn min_max_non_constant_threshold_no_false_positive() {
         .close(&[str_val("10.0.0.1")], CloseReason::Timeout)
         .unwrap();
     assert!(out.event_ok);
-    assert!(!out.close_ok); // threshold is non-constant → must not satisfy
+    assert!(!out.close_ok); // `upper_bound` not in close event → must not satisfy
 }
 
 // ---------------------------------------------------------------------------
@@ -253,3 +265,73 @@ fn min_max_cross_type_threshold_rejected() {
         assert_eq!(sm2.advance("alert", &e2), StepResult::Accumulate);
     }
 }
+
+// ---------------------------------------------------------------------------
+// Conditional threshold
+// ---------------------------------------------------------------------------
+
+#[test]
+fn conditional_threshold_uses_triggering_event() {
+    // fail | count >= if high_risk then 3 else 10
+    let agg = AggPlan {
+        transforms: vec![],
+        measure: Measure::Count,
+        cmp: CmpOp::Ge,
+        threshold: Expr::IfThenElse {
+            cond: Box::new(Expr::Field(FieldRef::Simple("high_risk".to_string()))),
+            then_expr: Box::new(Expr::Number(3.0)),
+            else_expr: Box::new(Expr::Number(10.0)),
+        },
+    };
+    let plan = simple_plan(
+        vec![simple_key("sip")],
+        vec![step(vec![branch("fail", agg)])],
+    );
+    let mut sm = CepStateMachine::new("rule_cond".to_string(), plan, None);
+
+    let mk = |sip: &str, high_risk: bool| {
+        event(vec![
+            ("sip", str_val(sip)),
+            ("high_risk", Value::Bool(high_risk)),
+        ])
+    };
+
+    // High-risk key: fires on the 3rd event.
+    assert_eq!(
+        sm.advance("fail", &mk("10.0.0.1", true)),
+        StepResult::Accumulate
+    );
+    assert_eq!(
+        sm.advance("fail", &mk("10.0.0.1", true)),
+        StepResult::Accumulate
+    );
+    assert!(matches!(
+        sm.advance("fail", &mk("10.0.0.1", true)),
+        StepResult::Matched(_)
+    ));
+
+    // Low-risk key: 3 events are not enough, the 10th fires.
+    for _ in 0..9 {
+        assert_eq!(
+            sm.advance("fail", &mk("10.0.0.2", false)),
+            StepResult::Accumulate
+        );
+    }
+    assert!(matches!(
+        sm.advance("fail", &mk("10.0.0.2", false)),
+        StepResult::Matched(_)
+    ));
+
+    // The threshold follows the event that triggers the check: after 4
+    // low-risk events, one high-risk event (count = 5 >= 3) fires.
+    for _ in 0..4 {
+        assert_eq!(
+            sm.advance("fail", &mk("10.0.0.3", false)),
+            StepResult::Accumulate
+        );
+    }
+    assert!(matches!(
+        sm.advance("fail", &mk("10.0.0.3", true)),
+        StepResult::Matched(_)
+    ));
+}
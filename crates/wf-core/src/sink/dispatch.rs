@@ -1,20 +1,39 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use futures::future::join_all;
+
+use crate::alert::OutputRecord;
+
 use super::runtime::SinkRuntime;
 
+// ---------------------------------------------------------------------------
+// DispatchReport — per-alert fan-out outcome
+// ---------------------------------------------------------------------------
+
+/// Outcome of a single [`SinkDispatcher::dispatch`] call.
+#[derive(Debug, Default)]
+pub struct DispatchReport {
+    /// Whether a pre-bound route matched (vs. falling back to default sinks).
+    pub matched: bool,
+    /// Number of target sinks that accepted the alert.
+    pub delivered: usize,
+    /// `(sink name, error)` for every target sink that failed.
+    pub errors: Vec<(String, String)>,
+}
+
 // ---------------------------------------------------------------------------
 // SinkDispatcher — core routing engine (pre-bound at startup)
 // ---------------------------------------------------------------------------
 
-/// Routes alert JSON to appropriate sinks based on yield-target window name.
+/// Routes alerts to appropriate sinks based on yield-target window name.
 ///
 /// Window→sink bindings are pre-resolved at startup via a `HashMap` lookup,
 /// eliminating runtime wildcard matching on every dispatch call.
 ///
 /// Routing logic:
 /// 1. Look up `window_name` in the pre-bound `routes` map.
-/// 2. If found (and non-empty), send to those sinks.
+/// 2. If found (and non-empty), fan out to all of those sinks concurrently.
 /// 3. Otherwise, send to the `default_sinks` (if configured).
 /// 4. If any send fails, additionally send to `error_sinks` (if configured).
 pub struct SinkDispatcher {
@@ -60,33 +79,49 @@ impl SinkDispatcher {
         }
     }
 
-    /// Route alert JSON to matching sinks by yield-target window name.
+    /// Route an alert to matching sinks by yield-target window name.
     ///
-    /// Returns 1 if a pre-bound route was found, 0 if only default sinks were used.
-    pub async fn dispatch(&self, window_name: &str, alert_json: &str) -> usize {
+    /// JSON sinks receive `alert_json` verbatim; CEF/LEEF sinks render
+    /// `record` themselves. All target sinks are sent to concurrently; a
+    /// failing sink does not stop delivery to the others. Per-sink failures
+    /// are collected in the returned [`DispatchReport`].
+    pub async fn dispatch(
+        &self,
+        window_name: &str,
+        record: &OutputRecord,
+        alert_json: &str,
+    ) -> DispatchReport {
         let (sinks, matched) = match self.routes.get(window_name) {
-            Some(s) if !s.is_empty() => (s.as_slice(), 1),
-            _ => (self.default_sinks.as_slice(), 0),
+            Some(s) if !s.is_empty() => (s.as_slice(), true),
+            _ => (self.default_sinks.as_slice(), false),
         };
 
-        let mut had_error = false;
-        for sink in sinks {
-            if let Err(e) = sink.send_str(alert_json).await {
-                log::warn!("sink dispatch error: {e}");
-                had_error = true;
+        let mut report = DispatchReport {
+            matched,
+            ..Default::default()
+        };
+        for (sink, result) in fan_out(sinks, record, alert_json).await {
+            match result {
+                Ok(()) => report.delivered += 1,
+                Err(e) => report.errors.push((sink.name.clone(), e.to_string())),
             }
         }
 
         // Any error → error sinks
-        if had_error {
-            for sink in &self.error_sinks {
-                if let Err(e) = sink.send_str(alert_json).await {
+        if !report.errors.is_empty() {
+            for (_, result) in fan_out(&self.error_sinks, record, alert_json).await {
+                if let Err(e) = result {
                     log::warn!("error sink error: {e}");
                 }
             }
         }
 
-        matched
+        report
+    }
+
+    /// Names of every sink this dispatcher can deliver to.
+    pub fn sink_names(&self) -> impl Iterator<Item = &str> {
+        self.all_sinks.iter().map(|sink| sink.name.as_str())
     }
 
     /// Gracefully stop all unique sinks.
@@ -98,3 +133,13 @@ impl SinkDispatcher {
         }
     }
 }
+
+/// Send the alert to every sink concurrently, pairing each with its result.
+async fn fan_out<'a>(
+    sinks: &'a [Arc<SinkRuntime>],
+    record: &OutputRecord,
+    alert_json: &str,
+) -> Vec<(&'a Arc<SinkRuntime>, anyhow::Result<()>)> {
+    let results = join_all(sinks.iter().map(|sink| sink.send_alert(record, alert_json))).await;
+    sinks.iter().zip(results).collect()
+}
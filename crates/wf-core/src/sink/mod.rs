@@ -1,5 +1,5 @@
 mod dispatch;
 mod runtime;
 
-pub use dispatch::SinkDispatcher;
+pub use dispatch::{DispatchReport, SinkDispatcher};
 pub use runtime::SinkRuntime;
@@ -1,6 +1,9 @@
 use tokio::sync::Mutex;
+use wf_config::sink::AlertSerializer;
 use wp_connector_api::{SinkHandle, SinkSpec as ResolvedSinkSpec};
 
+use crate::alert::{OutputRecord, render_alert};
+
 /// Runtime state for a single sink instance.
 ///
 /// Wraps a `SinkHandle` (from wp-connector-api) with metadata and provides
@@ -10,6 +13,8 @@ pub struct SinkRuntime {
     pub spec: ResolvedSinkSpec,
     pub handle: Mutex<SinkHandle>,
     pub tags: Vec<String>,
+    /// Wire format this sink receives alerts in.
+    pub serializer: AlertSerializer,
 }
 
 impl SinkRuntime {
@@ -23,6 +28,15 @@ impl SinkRuntime {
             .map_err(|e| anyhow::anyhow!("sink {:?} send error: {e}", self.name))
     }
 
+    /// Send an alert in this sink's configured format. `alert_json` is the
+    /// pre-serialized JSON form, used as-is by JSON sinks.
+    pub async fn send_alert(&self, record: &OutputRecord, alert_json: &str) -> anyhow::Result<()> {
+        match render_alert(record, self.serializer) {
+            Some(line) => self.send_str(&line).await,
+            None => self.send_str(alert_json).await,
+        }
+    }
+
     /// Gracefully stop the sink.
     pub async fn stop(&self) -> anyhow::Result<()> {
         let mut handle = self.handle.lock().await;
@@ -40,6 +54,7 @@ impl std::fmt::Debug for SinkRuntime {
             .field("name", &self.name)
             .field("spec", &self.spec)
             .field("tags", &self.tags)
+            .field("serializer", &self.serializer)
             .finish_non_exhaustive()
     }
 }
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+
+use arrow::compute::concat_batches;
+use arrow::datatypes::SchemaRef;
+
+use super::Window;
+use super::types::TimedBatch;
+
+impl Window {
+    /// Merge every run of adjacent batches smaller than `compact_below` into
+    /// a single batch, returning how many batches were removed.
+    ///
+    /// Time ordering is preserved: each run is replaced in place by one batch
+    /// whose sequence numbers cover all merged appends. The batch remembers
+    /// each constituent's event-time range, so retention still expires rows
+    /// per original append (see [`Self::evict_expired`]). No-op when
+    /// `compact_below` is `0B`.
+    pub fn compact(&mut self) -> usize {
+        let threshold = self.config.compact_below.as_bytes();
+        if threshold == 0 || self.batches.len() < 2 {
+            return 0;
+        }
+
+        let before = self.batches.len();
+        let mut out = VecDeque::with_capacity(before);
+        let mut run: Vec<TimedBatch> = Vec::new();
+        for tb in std::mem::take(&mut self.batches) {
+            if tb.byte_size < threshold {
+                run.push(tb);
+            } else {
+                self.flush_run(&mut run, &mut out);
+                out.push_back(tb);
+            }
+        }
+        self.flush_run(&mut run, &mut out);
+        self.batches = out;
+        self.current_bytes = self.batches.iter().map(|tb| tb.byte_size).sum();
+        before - self.batches.len()
+    }
+
+    /// Opportunistic compaction after an append: once the trailing run of
+    /// small batches adds up to `compact_below`, fold it into one batch.
+    pub(super) fn compact_tail(&mut self) {
+        let threshold = self.config.compact_below.as_bytes();
+        if threshold == 0 {
+            return;
+        }
+
+        let mut start = self.batches.len();
+        let mut run_bytes = 0usize;
+        while start > 0 && self.batches[start - 1].byte_size < threshold {
+            start -= 1;
+            run_bytes += self.batches[start].byte_size;
+        }
+        if self.batches.len() - start < 2 || run_bytes < threshold {
+            return;
+        }
+
+        let mut run: Vec<TimedBatch> = self.batches.drain(start..).collect();
+        let mut out = VecDeque::with_capacity(1);
+        self.flush_run(&mut run, &mut out);
+        let merged_bytes: usize = out.iter().map(|tb| tb.byte_size).sum();
+        self.current_bytes = self.current_bytes - run_bytes + merged_bytes;
+        self.batches.extend(out);
+    }
+
+    /// Move `run` into `out`, merged into a single batch when it holds more
+    /// than one. On concat failure the run is kept as-is.
+    fn flush_run(&self, run: &mut Vec<TimedBatch>, out: &mut VecDeque<TimedBatch>) {
+        if run.len() < 2 {
+            out.extend(run.drain(..));
+            return;
+        }
+        match merge_run(&self.schema, run) {
+            Some(merged) => {
+                run.clear();
+                out.push_back(merged);
+            }
+            None => out.extend(run.drain(..)),
+        }
+    }
+}
+
+fn merge_run(schema: &SchemaRef, run: &[TimedBatch]) -> Option<TimedBatch> {
+    let batch = concat_batches(schema, run.iter().map(|tb| &tb.batch)).ok()?;
+
+    let mut seq_row_starts = Vec::new();
+    let mut seq_time_ranges = Vec::new();
+    let mut offset = 0usize;
+    for tb in run {
+        if tb.seq_row_starts.is_empty() {
+            seq_row_starts.push(offset);
+            seq_time_ranges.push(tb.event_time_range);
+        } else {
+            seq_row_starts.extend(tb.seq_row_starts.iter().map(|s| offset + s));
+            seq_time_ranges.extend_from_slice(&tb.seq_time_ranges);
+        }
+        offset += tb.row_count;
+    }
+
+    let first = run.first()?;
+    let last = run.last()?;
+    Some(TimedBatch {
+        event_time_range: (
+            run.iter().map(|tb| tb.event_time_range.0).min()?,
+            run.iter().map(|tb| tb.event_time_range.1).max()?,
+        ),
+        ingested_at: first.ingested_at,
+        row_count: offset,
+        byte_size: batch.get_array_memory_size(),
+        seq: last.seq,
+        first_seq: first.first_seq,
+        seq_row_starts,
+        seq_time_ranges,
+        batch,
+        decoded: Default::default(),
+    })
+}
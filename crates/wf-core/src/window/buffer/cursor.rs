@@ -1,6 +1,7 @@
 use arrow::record_batch::RecordBatch;
 
 use super::Window;
+use super::shared::SharedBatch;
 
 impl Window {
     /// Read batches appended since the given cursor position.
@@ -9,23 +10,44 @@ impl Window {
     /// `gap_detected = true` means the cursor fell behind eviction and some
     /// data was lost.
     pub fn read_since(&self, cursor: u64) -> (Vec<RecordBatch>, u64, bool) {
-        if self.batches.is_empty() {
+        let Some((start, new_cursor, gap)) = self.read_range(cursor) else {
             return (Vec::new(), cursor, false);
-        }
-        let oldest_seq = self.batches.front().unwrap().seq;
-        let newest_seq = self.batches.back().unwrap().seq;
-        if cursor > newest_seq {
-            return (Vec::new(), cursor, false);
-        }
-        let gap = cursor < oldest_seq;
-        let effective_start = if gap { oldest_seq } else { cursor };
+        };
         let batches: Vec<RecordBatch> = self
             .batches
             .iter()
-            .filter(|tb| tb.seq >= effective_start)
-            .map(|tb| tb.batch.clone()) // Arc clone, zero data copy
+            .filter(|tb| tb.seq >= start)
+            .map(|tb| tb.rows_since(start)) // Arc clone / slice, zero data copy
+            .collect();
+        (batches, new_cursor, gap)
+    }
+
+    /// Same as [`Self::read_since`], returning [`SharedBatch`] handles whose
+    /// decoded events are shared with other readers of this window.
+    pub fn read_shared_since(&self, cursor: u64) -> (Vec<SharedBatch>, u64, bool) {
+        let Some((start, new_cursor, gap)) = self.read_range(cursor) else {
+            return (Vec::new(), cursor, false);
+        };
+        let batches: Vec<SharedBatch> = self
+            .batches
+            .iter()
+            .filter(|tb| tb.seq >= start)
+            .map(|tb| tb.shared_since(start))
             .collect();
-        (batches, newest_seq + 1, gap)
+        (batches, new_cursor, gap)
+    }
+
+    /// `(first_seq_to_read, new_cursor, gap_detected)`, or `None` when there
+    /// is nothing new after `cursor`.
+    fn read_range(&self, cursor: u64) -> Option<(u64, u64, bool)> {
+        let oldest_seq = self.batches.front()?.first_seq;
+        let newest_seq = self.batches.back()?.seq;
+        if cursor > newest_seq {
+            return None;
+        }
+        let gap = cursor < oldest_seq;
+        let start = if gap { oldest_seq } else { cursor };
+        Some((start, newest_seq + 1, gap))
     }
 
     /// Next sequence number that will be assigned to the next appended batch.
@@ -1,10 +1,16 @@
 use std::time::Duration;
 
+use arrow::compute::concat_batches;
+
 use super::Window;
 
 impl Window {
     /// Remove front batches whose max event time is older than `now_nanos - over`.
     ///
+    /// A compacted batch expires per constituent: its leading appends whose
+    /// own max event time is past the cutoff are dropped even while newer
+    /// rows in the same batch are retained.
+    ///
     /// No-op for windows without a time column or with `over == Duration::ZERO`.
     pub fn evict_expired(&mut self, now_nanos: i64) {
         if self.time_col_index.is_none() || self.over == Duration::ZERO {
@@ -19,19 +25,72 @@ impl Window {
                 let evicted = self.batches.pop_front().unwrap();
                 self.current_bytes -= evicted.byte_size;
                 self.total_rows -= evicted.row_count;
+                self.rows_time_evicted += evicted.row_count as u64;
             } else {
+                let expired = front
+                    .seq_time_ranges
+                    .iter()
+                    .take_while(|range| range.1 < cutoff)
+                    .count();
+                if expired > 0 {
+                    self.evict_front_constituents(expired);
+                }
                 break;
             }
         }
     }
 
+    /// Drop the first `count` constituents of the compacted front batch,
+    /// copying the survivors so the expired rows' buffers are released. On
+    /// copy failure the batch is kept whole.
+    fn evict_front_constituents(&mut self, count: usize) {
+        let Some(front) = self.batches.front_mut() else {
+            return;
+        };
+        let offset = front.seq_row_starts[count];
+        let rest = front.batch.slice(offset, front.row_count - offset);
+        let Ok(batch) = concat_batches(&rest.schema(), [&rest]) else {
+            return;
+        };
+
+        let ranges = front.seq_time_ranges.split_off(count);
+        front.event_time_range = (
+            ranges.iter().map(|r| r.0).min().unwrap_or(i64::MAX),
+            ranges.iter().map(|r| r.1).max().unwrap_or(i64::MIN),
+        );
+        let starts: Vec<usize> = front.seq_row_starts[count..]
+            .iter()
+            .map(|s| s - offset)
+            .collect();
+        if ranges.len() > 1 {
+            front.seq_row_starts = starts;
+            front.seq_time_ranges = ranges;
+        } else {
+            front.seq_row_starts.clear();
+            front.seq_time_ranges.clear();
+        }
+        front.first_seq += count as u64;
+        front.row_count -= offset;
+        let old_bytes = front.byte_size;
+        front.byte_size = batch.get_array_memory_size();
+        front.batch = batch;
+        front.decoded = Default::default();
+
+        self.current_bytes = self.current_bytes - old_bytes + front.byte_size;
+        self.total_rows -= offset;
+        self.rows_time_evicted += offset as u64;
+    }
+
     /// Pop the oldest (front) batch, returning its byte size.
     ///
-    /// Returns `None` if the window is empty.
+    /// Used for memory-pressure eviction; the popped rows are counted in
+    /// [`WindowStats::rows_memory_evicted`](super::WindowStats). Returns
+    /// `None` if the window is empty.
     pub fn evict_oldest(&mut self) -> Option<usize> {
         let evicted = self.batches.pop_front()?;
         self.current_bytes -= evicted.byte_size;
         self.total_rows -= evicted.row_count;
+        self.rows_memory_evicted += evicted.row_count as u64;
         Some(evicted.byte_size)
     }
 }
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use arrow::array::{ArrayRef, new_null_array};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+
+use super::Window;
+
+/// A validated schema change with every buffered batch already padded,
+/// produced by [`Window::prepare_evolution`].
+///
+/// Applying it cannot fail, so callers evolving several windows can prepare
+/// all of them first and only commit once every window accepted its change.
+pub struct SchemaEvolution {
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+}
+
+impl Window {
+    /// Widen the window to `schema`, which must keep every current field
+    /// (same name and type) and may only add nullable ones.
+    ///
+    /// Buffered batches are padded with null columns so they stay readable
+    /// under the new schema, and batches still arriving in the old layout
+    /// are padded on append instead of being rejected.
+    pub fn evolve_schema(&mut self, schema: SchemaRef) -> Result<()> {
+        if let Some(evolution) = self.prepare_evolution(schema)? {
+            self.commit_evolution(evolution);
+        }
+        Ok(())
+    }
+
+    /// Validate `schema` as in [`evolve_schema`](Self::evolve_schema) and pad
+    /// the buffered batches without touching the window. Returns `None` when
+    /// the schema is unchanged.
+    ///
+    /// The window must not change between prepare and
+    /// [`commit_evolution`](Self::commit_evolution); hold its write lock.
+    pub fn prepare_evolution(&self, schema: SchemaRef) -> Result<Option<SchemaEvolution>> {
+        if schema == self.schema {
+            return Ok(None);
+        }
+        for field in self.schema.fields() {
+            match schema.field_with_name(field.name()) {
+                Ok(new) if new.data_type() == field.data_type() => {}
+                Ok(new) => bail!(
+                    "window {:?}: field {:?} changes type from {} to {}",
+                    self.name,
+                    field.name(),
+                    field.data_type(),
+                    new.data_type()
+                ),
+                Err(_) => bail!(
+                    "window {:?}: field {:?} is removed",
+                    self.name,
+                    field.name()
+                ),
+            }
+        }
+        // Checked up front: an empty window has no batches to pad, but later
+        // appends in the old layout still need nulls for the added fields.
+        for field in schema.fields() {
+            if self.schema.field_with_name(field.name()).is_err() && !field.is_nullable() {
+                bail!(
+                    "window {:?}: added field {:?} is not nullable",
+                    self.name,
+                    field.name()
+                );
+            }
+        }
+
+        let batches = self
+            .batches
+            .iter()
+            .map(|tb| pad_batch(&tb.batch, &schema))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(SchemaEvolution { schema, batches }))
+    }
+
+    /// Apply a change from [`prepare_evolution`](Self::prepare_evolution).
+    pub fn commit_evolution(&mut self, evolution: SchemaEvolution) {
+        let SchemaEvolution { schema, batches } = evolution;
+        debug_assert_eq!(batches.len(), self.batches.len());
+        for (tb, batch) in self.batches.iter_mut().zip(batches) {
+            let byte_size = batch.get_array_memory_size();
+            self.current_bytes = self.current_bytes - tb.byte_size + byte_size;
+            tb.byte_size = byte_size;
+            tb.batch = batch;
+            tb.decoded = Default::default();
+        }
+
+        self.time_col_index = self
+            .time_col_index
+            .and_then(|idx| schema.index_of(self.schema.field(idx).name()).ok());
+        let old = std::mem::replace(&mut self.schema, schema);
+        self.legacy_schemas.push(old);
+    }
+
+    /// Check `batch` against the window schema, padding batches in a layout
+    /// the window has evolved from.
+    pub(super) fn conform_batch(&self, batch: RecordBatch) -> Result<RecordBatch> {
+        if batch.schema() == self.schema {
+            return Ok(batch);
+        }
+        if self.legacy_schemas.contains(&batch.schema()) {
+            return pad_batch(&batch, &self.schema);
+        }
+        bail!(
+            "schema mismatch: window {:?} expects {:?}, got {:?}",
+            self.name,
+            self.schema,
+            batch.schema()
+        );
+    }
+}
+
+/// Rebuild `batch` under `schema`, filling fields it lacks with nulls.
+fn pad_batch(batch: &RecordBatch, schema: &SchemaRef) -> Result<RecordBatch> {
+    let columns: Vec<ArrayRef> = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(col) => Ok(Arc::clone(col)),
+            None if field.is_nullable() => Ok(new_null_array(field.data_type(), batch.num_rows())),
+            None => bail!("added field {:?} is not nullable", field.name()),
+        })
+        .collect::<Result<_>>()?;
+    Ok(RecordBatch::try_new(Arc::clone(schema), columns)?)
+}
@@ -1,17 +1,22 @@
+mod compaction;
 mod cursor;
 mod eviction;
+mod evolution;
+mod shared;
 mod types;
 mod watermark;
 
 #[cfg(test)]
 mod tests;
 
-pub use types::{AppendOutcome, WindowParams};
+pub use evolution::SchemaEvolution;
+pub use shared::{SharedBatch, SharedEvents};
+pub use types::{AppendOutcome, WindowParams, WindowStats};
 
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
-use anyhow::{Result, bail};
+use anyhow::Result;
 use arrow::array::{Array, TimestampNanosecondArray};
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
@@ -26,6 +31,9 @@ use types::TimedBatch;
 pub struct Window {
     pub(super) name: String,
     pub(super) schema: SchemaRef,
+    /// Schemas this window evolved from; batches in these layouts are padded
+    /// on append.
+    pub(super) legacy_schemas: Vec<SchemaRef>,
     pub(super) time_col_index: Option<usize>,
     pub(super) over: Duration,
     pub(super) config: WindowConfig,
@@ -35,43 +43,62 @@ pub struct Window {
     pub(super) watermark_nanos: i64,
     /// Next sequence number to assign to an appended batch.
     pub(super) next_seq: u64,
+    pub(super) rows_appended: u64,
+    pub(super) rows_time_evicted: u64,
+    pub(super) rows_memory_evicted: u64,
 }
 
 impl Window {
     /// Create a new empty window.
+    ///
+    /// Time retention is `params.over`, clipped to a non-zero
+    /// `config.over_cap`: bootstrap rejects `over > over_cap`, but windows
+    /// built without that check still never retain more than the cap.
     pub fn new(params: WindowParams, config: WindowConfig) -> Self {
+        let cap = config.over_cap.as_duration();
+        let over = if !cap.is_zero() && params.over > cap {
+            log::warn!(
+                "window {:?}: over ({:?}) exceeds over_cap ({:?}); retaining {:?}",
+                params.name,
+                params.over,
+                cap,
+                cap
+            );
+            cap
+        } else {
+            params.over
+        };
         Self {
             name: params.name,
             schema: params.schema,
+            legacy_schemas: Vec::new(),
             time_col_index: params.time_col_index,
-            over: params.over,
+            over,
             config,
             batches: VecDeque::new(),
             current_bytes: 0,
             total_rows: 0,
             watermark_nanos: i64::MIN,
             next_seq: 0,
+            rows_appended: 0,
+            rows_time_evicted: 0,
+            rows_memory_evicted: 0,
         }
     }
 
     /// Append a RecordBatch to this window.
     ///
     /// Empty batches are silently skipped. Returns an error if the batch
-    /// schema does not match the window schema. After appending, memory
+    /// schema does not match the window schema (or one it evolved from, see
+    /// [`Self::evolve_schema`]). After appending, trailing
+    /// small batches may be compacted (see [`Self::compact`]) and memory
     /// eviction runs if `current_bytes > max_window_bytes`.
     pub fn append(&mut self, batch: RecordBatch) -> Result<()> {
         if batch.num_rows() == 0 {
             return Ok(());
         }
 
-        if batch.schema() != self.schema {
-            bail!(
-                "schema mismatch: window {:?} expects {:?}, got {:?}",
-                self.name,
-                self.schema,
-                batch.schema()
-            );
-        }
+        let batch = self.conform_batch(batch)?;
 
         let event_time_range = self.extract_time_range(&batch);
         let row_count = batch.num_rows();
@@ -86,10 +113,17 @@ impl Window {
             row_count,
             byte_size,
             seq,
+            first_seq: seq,
+            seq_row_starts: Vec::new(),
+            seq_time_ranges: Vec::new(),
+            decoded: Default::default(),
         });
 
         self.current_bytes += byte_size;
         self.total_rows += row_count;
+        self.rows_appended += row_count as u64;
+
+        self.compact_tail();
 
         // Memory eviction: pop oldest batches while over budget.
         let max_bytes = self.config.max_window_bytes.as_bytes();
@@ -97,6 +131,7 @@ impl Window {
             if let Some(evicted) = self.batches.pop_front() {
                 self.current_bytes -= evicted.byte_size;
                 self.total_rows -= evicted.row_count;
+                self.rows_memory_evicted += evicted.row_count as u64;
             } else {
                 break;
             }
@@ -118,6 +153,15 @@ impl Window {
         self.current_bytes
     }
 
+    /// Whether appending `incoming_bytes` more would push the window past
+    /// `max_window_bytes` and force memory eviction.
+    ///
+    /// Push-style sources use this to apply backpressure instead of
+    /// silently evicting unread data.
+    pub fn is_saturated(&self, incoming_bytes: usize) -> bool {
+        self.current_bytes.saturating_add(incoming_bytes) > self.config.max_window_bytes.as_bytes()
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -134,6 +178,17 @@ impl Window {
         self.batches.len()
     }
 
+    /// Cumulative append/eviction counters plus current occupancy.
+    pub fn stats(&self) -> WindowStats {
+        WindowStats {
+            rows_appended: self.rows_appended,
+            rows_time_evicted: self.rows_time_evicted,
+            rows_memory_evicted: self.rows_memory_evicted,
+            current_bytes: self.current_bytes,
+            current_rows: self.total_rows,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.batches.is_empty()
     }
@@ -0,0 +1,84 @@
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, Weak};
+
+use arrow::record_batch::RecordBatch;
+
+use crate::rule::{Event, batch_to_events};
+
+/// Decoded events of one stored batch, shared between readers.
+///
+/// Only a weak reference is kept: the events live as long as some reader
+/// holds them and are never charged to the window's memory budget. A reader
+/// arriving after the last holder is done decodes the batch again.
+#[derive(Default)]
+pub(in crate::window) struct DecodeSlot(Mutex<Weak<Vec<Event>>>);
+
+/// A batch returned by [`Window::read_shared_since`](super::Window::read_shared_since).
+///
+/// Rule tasks reading the same window get handles onto the same slot, so the
+/// Arrow → [`Event`] conversion runs once per batch instead of once per rule
+/// while their reads overlap.
+#[derive(Clone)]
+pub struct SharedBatch {
+    /// The whole stored batch; decoding always covers every row so that
+    /// readers resuming at different offsets share the result.
+    batch: RecordBatch,
+    /// First row not yet consumed by this reader.
+    offset: usize,
+    slot: Arc<DecodeSlot>,
+}
+
+impl SharedBatch {
+    pub(super) fn new(batch: RecordBatch, offset: usize, slot: Arc<DecodeSlot>) -> Self {
+        Self {
+            batch,
+            offset,
+            slot,
+        }
+    }
+
+    /// The unread rows as Arrow data (zero-copy slice).
+    pub fn batch(&self) -> RecordBatch {
+        self.batch
+            .slice(self.offset, self.batch.num_rows() - self.offset)
+    }
+
+    /// The unread rows as events, reusing another reader's decode when one
+    /// is still alive.
+    pub fn events(&self) -> SharedEvents {
+        let mut weak = self.slot.0.lock().expect("decode slot poisoned");
+        let events = match weak.upgrade() {
+            Some(events) => events,
+            None => {
+                let events = Arc::new(batch_to_events(&self.batch));
+                *weak = Arc::downgrade(&events);
+                events
+            }
+        };
+        SharedEvents {
+            events,
+            offset: self.offset,
+        }
+    }
+}
+
+/// Events of a [`SharedBatch`]; dereferences to the unread rows.
+pub struct SharedEvents {
+    events: Arc<Vec<Event>>,
+    offset: usize,
+}
+
+impl SharedEvents {
+    /// Whether `self` and `other` point at the same decoded events.
+    pub fn shares_decode_with(&self, other: &SharedEvents) -> bool {
+        Arc::ptr_eq(&self.events, &other.events)
+    }
+}
+
+impl Deref for SharedEvents {
+    type Target = [Event];
+
+    fn deref(&self) -> &[Event] {
+        &self.events[self.offset..]
+    }
+}
@@ -52,6 +52,7 @@ fn test_config(max_bytes: usize) -> WindowConfig {
         watermark: Duration::from_secs(5).into(),
         allowed_lateness: Duration::from_secs(0).into(),
         late_policy: wf_config::LatePolicy::Drop,
+        compact_below: 0.into(),
     }
 }
 
@@ -451,3 +452,418 @@ fn read_since_cursor_ahead() {
     assert_eq!(cursor, 999);
     assert!(!gap);
 }
+
+// -- 18. stats_count_memory_eviction ------------------------------------
+
+#[test]
+fn stats_count_memory_eviction() {
+    let schema = test_schema();
+    let probe = make_batch(&schema, &[1_000_000_000], &[100]);
+    let max_bytes = probe.get_array_memory_size() * 2;
+    let mut win = Window::new(
+        WindowParams {
+            name: "mem_win".into(),
+            schema,
+            time_col_index: Some(0),
+            over: Duration::from_secs(3600),
+        },
+        test_config(max_bytes),
+    );
+
+    win.append(probe).unwrap();
+    win.append(make_batch(win.schema(), &[2_000_000_000], &[200]))
+        .unwrap();
+    assert_eq!(win.stats().rows_memory_evicted, 0);
+
+    // Third batch exceeds budget → oldest row counted as memory-evicted.
+    win.append(make_batch(win.schema(), &[3_000_000_000], &[300]))
+        .unwrap();
+    let stats = win.stats();
+    assert_eq!(stats.rows_appended, 3);
+    assert_eq!(stats.rows_memory_evicted, 1);
+    assert_eq!(stats.rows_time_evicted, 0);
+    assert_eq!(stats.current_rows, 2);
+    assert_eq!(stats.current_bytes, win.memory_usage());
+
+    win.evict_oldest().unwrap();
+    assert_eq!(win.stats().rows_memory_evicted, 2);
+}
+
+// -- 19. stats_count_time_eviction --------------------------------------
+
+#[test]
+fn stats_count_time_eviction() {
+    let mut win = test_window(10, usize::MAX);
+    let schema = win.schema().clone();
+
+    win.append(make_batch(
+        &schema,
+        &[1_000_000_000, 2_000_000_000],
+        &[1, 2],
+    ))
+    .unwrap();
+    win.append(make_batch(&schema, &[20_000_000_000], &[3]))
+        .unwrap();
+
+    // cutoff = 25s - 10s = 15s → first batch (2 rows) expires.
+    win.evict_expired(25_000_000_000);
+    let stats = win.stats();
+    assert_eq!(stats.rows_appended, 3);
+    assert_eq!(stats.rows_time_evicted, 2);
+    assert_eq!(stats.rows_memory_evicted, 0);
+    assert_eq!(stats.current_rows, 1);
+}
+
+// -- 20. compact_merges_small_batches -----------------------------------
+
+fn compacting_window(compact_below: usize) -> Window {
+    let mut config = test_config(usize::MAX);
+    config.compact_below = compact_below.into();
+    Window::new(
+        WindowParams {
+            name: "compact_win".into(),
+            schema: test_schema(),
+            time_col_index: Some(0),
+            over: Duration::from_secs(3600),
+        },
+        config,
+    )
+}
+
+fn concat_all(win: &Window) -> RecordBatch {
+    arrow::compute::concat_batches(win.schema(), &win.snapshot()).unwrap()
+}
+
+#[test]
+fn compact_merges_small_batches() {
+    // Threshold far above any single batch, so the tail never fills up and
+    // only the explicit compaction pass merges.
+    let mut win = compacting_window(usize::MAX);
+    let schema = win.schema().clone();
+    for i in 1..=5i64 {
+        win.append(make_batch(&schema, &[i * 1_000_000_000], &[i * 100]))
+            .unwrap();
+    }
+    assert_eq!(win.batch_count(), 5);
+    let before = concat_all(&win);
+
+    assert_eq!(win.compact(), 4);
+    assert_eq!(win.batch_count(), 1);
+    assert_eq!(win.total_rows(), 5);
+    assert_eq!(concat_all(&win), before);
+    assert_eq!(
+        win.memory_usage(),
+        win.snapshot()[0].get_array_memory_size()
+    );
+
+    // Retention still expires each merged append on its own time: at cutoff
+    // 4s the rows at 1s..3s go while 4s and 5s stay.
+    win.evict_expired(3600 * 1_000_000_000 + 4_000_000_000);
+    assert_eq!(win.batch_count(), 1);
+    assert_eq!(win.total_rows(), 2);
+    assert_eq!(win.stats().rows_time_evicted, 3);
+    assert_eq!(
+        win.memory_usage(),
+        win.snapshot()[0].get_array_memory_size()
+    );
+    win.evict_expired(3600 * 1_000_000_000 + 5_000_000_001);
+    assert!(win.is_empty());
+}
+
+// -- 21. compact_tail_on_append -----------------------------------------
+
+#[test]
+fn compact_tail_on_append() {
+    let schema = test_schema();
+    let one_batch_size = make_batch(&schema, &[1], &[1]).get_array_memory_size();
+    // Tail run is folded once it reaches three batches' worth of bytes.
+    let mut win = compacting_window(one_batch_size * 3);
+
+    win.append(make_batch(&schema, &[1_000_000_000], &[100]))
+        .unwrap();
+    win.append(make_batch(&schema, &[2_000_000_000], &[200]))
+        .unwrap();
+    assert_eq!(win.batch_count(), 2);
+    win.append(make_batch(&schema, &[3_000_000_000], &[300]))
+        .unwrap();
+    assert_eq!(win.batch_count(), 1);
+    assert_eq!(win.total_rows(), 3);
+    assert_eq!(win.next_seq(), 3);
+}
+
+// -- 22. compact_disabled_by_default ------------------------------------
+
+#[test]
+fn compact_disabled_by_default() {
+    let mut win = test_window(3600, usize::MAX);
+    let schema = win.schema().clone();
+    win.append(make_batch(&schema, &[1_000_000_000], &[100]))
+        .unwrap();
+    win.append(make_batch(&schema, &[2_000_000_000], &[200]))
+        .unwrap();
+    assert_eq!(win.compact(), 0);
+    assert_eq!(win.batch_count(), 2);
+}
+
+// -- 23. read_since_inside_compacted_batch ------------------------------
+
+#[test]
+fn read_since_inside_compacted_batch() {
+    let mut win = compacting_window(usize::MAX);
+    let schema = win.schema().clone();
+    win.append(make_batch(&schema, &[1_000_000_000], &[100]))
+        .unwrap(); // seq 0
+    win.append(make_batch(
+        &schema,
+        &[2_000_000_000, 2_500_000_000],
+        &[200, 250],
+    ))
+    .unwrap(); // seq 1
+    win.append(make_batch(&schema, &[3_000_000_000], &[300]))
+        .unwrap(); // seq 2
+    win.compact();
+    assert_eq!(win.batch_count(), 1);
+
+    // Reader already consumed seq 0 → only rows of seq 1 and 2 are returned.
+    let (batches, cursor, gap) = win.read_since(1);
+    assert!(!gap);
+    assert_eq!(cursor, 3);
+    assert_eq!(batches.len(), 1);
+    let values = batches[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(values.values().to_vec(), vec![200, 250, 300]);
+
+    // From the start, everything is returned without a gap.
+    let (batches, _, gap) = win.read_since(0);
+    assert!(!gap);
+    assert_eq!(batches[0].num_rows(), 4);
+}
+
+// -- 24. is_saturated_tracks_max_window_bytes ---------------------------
+
+#[test]
+fn is_saturated_tracks_max_window_bytes() {
+    let schema = test_schema();
+    let batch = make_batch(&schema, &[1_000_000_000], &[1]);
+    let size = batch.get_array_memory_size();
+
+    let mut win = test_window(10, size * 2);
+    assert!(!win.is_saturated(size));
+    win.append(batch).unwrap();
+    assert!(!win.is_saturated(size));
+    assert!(win.is_saturated(size + 1));
+}
+
+// -- 25. read_shared_since_decodes_once ---------------------------------
+
+fn event_values(events: &[crate::rule::Event]) -> Vec<i64> {
+    events
+        .iter()
+        .map(|e| match e.fields.get("value") {
+            Some(crate::rule::Value::Int(n)) => *n,
+            other => panic!("unexpected value {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn read_shared_since_decodes_once() {
+    let mut win = test_window(3600, usize::MAX);
+    let schema = win.schema().clone();
+    win.append(make_batch(
+        &schema,
+        &[1_000_000_000, 2_000_000_000],
+        &[100, 200],
+    ))
+    .unwrap();
+
+    let (a, cursor, gap) = win.read_shared_since(0);
+    assert_eq!((a.len(), cursor, gap), (1, 1, false));
+    let (b, _, _) = win.read_shared_since(0);
+
+    // Two readers holding the batch at once share one decode.
+    let a_events = a[0].events();
+    let b_events = b[0].events();
+    assert!(a_events.shares_decode_with(&b_events));
+    assert_eq!(event_values(&a_events), vec![100, 200]);
+    assert_eq!(a[0].batch().num_rows(), 2);
+
+    // Once every holder is done the events are freed and decoded afresh.
+    drop((a_events, b_events));
+    let again = a[0].events();
+    assert_eq!(event_values(&again), vec![100, 200]);
+}
+
+// -- 26. read_shared_since_inside_compacted_batch -----------------------
+
+#[test]
+fn read_shared_since_inside_compacted_batch() {
+    let mut win = compacting_window(usize::MAX);
+    let schema = win.schema().clone();
+    win.append(make_batch(&schema, &[1_000_000_000], &[100]))
+        .unwrap(); // seq 0
+    win.append(make_batch(&schema, &[2_000_000_000], &[200]))
+        .unwrap(); // seq 1
+    win.compact();
+    assert_eq!(win.batch_count(), 1);
+
+    // A reader that already consumed seq 0 shares the decode with a reader
+    // starting from scratch, but only sees its own unread rows.
+    let (behind, _, _) = win.read_shared_since(0);
+    let (ahead, cursor, gap) = win.read_shared_since(1);
+    assert_eq!((cursor, gap), (2, false));
+    let behind_events = behind[0].events();
+    let ahead_events = ahead[0].events();
+    assert!(ahead_events.shares_decode_with(&behind_events));
+    assert_eq!(event_values(&behind_events), vec![100, 200]);
+    assert_eq!(event_values(&ahead_events), vec![200]);
+    assert_eq!(ahead[0].batch().num_rows(), 1);
+}
+
+// -- 27. over_clipped_to_over_cap ----------------------------------------
+
+#[test]
+fn over_clipped_to_over_cap() {
+    // Rule asks for 10 minutes, the deployment caps retention at 10 s.
+    let mut config = test_config(usize::MAX);
+    config.over_cap = Duration::from_secs(10).into();
+    let mut win = Window::new(
+        WindowParams {
+            name: "test_win".into(),
+            schema: test_schema(),
+            time_col_index: Some(0),
+            over: Duration::from_secs(600),
+        },
+        config,
+    );
+    let schema = win.schema().clone();
+
+    win.append(make_batch(&schema, &[1_000_000_000], &[100]))
+        .unwrap();
+    win.append(make_batch(&schema, &[12_000_000_000], &[200]))
+        .unwrap();
+
+    // cutoff = 12s - 10s (cap, not 600s) = 2s → batch1 (1s) evicted
+    win.evict_expired(12_000_000_000);
+    assert_eq!(win.batch_count(), 1);
+    assert_eq!(win.stats().rows_time_evicted, 1);
+
+    // Within the cap nothing changes: over (10s) < over_cap (3600s).
+    let mut win = test_window(10, usize::MAX);
+    win.append(make_batch(&schema, &[1_000_000_000], &[100]))
+        .unwrap();
+    win.evict_expired(5_000_000_000);
+    assert_eq!(win.batch_count(), 1);
+}
+
+// -- 28. evolve_schema_pads_existing_and_legacy_batches ------------------
+
+#[test]
+fn evolve_schema_pads_existing_and_legacy_batches() {
+    let mut win = test_window(600, usize::MAX);
+    let old = win.schema().clone();
+    win.append(make_batch(&old, &[1_000_000_000], &[100]))
+        .unwrap();
+
+    // Adding a nullable field is compatible; buffered rows read it as null.
+    let evolved = Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        Field::new("value", DataType::Int64, false),
+        Field::new("tag", DataType::Utf8, true),
+    ]));
+    win.evolve_schema(evolved.clone()).unwrap();
+    assert_eq!(win.schema(), &evolved);
+    let snap = win.snapshot();
+    assert_eq!(snap[0].schema(), evolved);
+    assert_eq!(snap[0].column(2).null_count(), 1);
+
+    // In-flight batches in the old layout are padded, not rejected.
+    win.append(make_batch(&old, &[2_000_000_000], &[200]))
+        .unwrap();
+    assert_eq!(win.total_rows(), 2);
+    assert_eq!(win.time_col_index(), Some(0));
+
+    // Retyping an existing field is rejected and leaves the window as is.
+    let retyped = Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        Field::new("value", DataType::Utf8, false),
+        Field::new("tag", DataType::Utf8, true),
+    ]));
+    let err = win.evolve_schema(retyped).unwrap_err();
+    assert!(err.to_string().contains("changes type"), "{err}");
+    assert_eq!(win.schema(), &evolved);
+}
+
+// -- 29. evict_expired_inside_compacted_batch ---------------------------
+
+#[test]
+fn evict_expired_inside_compacted_batch() {
+    let mut config = test_config(usize::MAX);
+    config.compact_below = usize::MAX.into();
+    let mut win = Window::new(
+        WindowParams {
+            name: "compact_win".into(),
+            schema: test_schema(),
+            time_col_index: Some(0),
+            over: Duration::from_secs(10),
+        },
+        config,
+    );
+    let schema = win.schema().clone();
+    win.append(make_batch(&schema, &[1_000_000_000], &[100]))
+        .unwrap(); // seq 0
+    win.append(make_batch(
+        &schema,
+        &[2_000_000_000, 20_000_000_000],
+        &[200, 250],
+    ))
+    .unwrap(); // seq 1
+    win.append(make_batch(&schema, &[3_000_000_000], &[300]))
+        .unwrap(); // seq 2
+    win.compact();
+    assert_eq!(win.batch_count(), 1);
+
+    // cutoff = 15s: seq 0 expires; seq 1 holds a 20s row, so it and
+    // everything behind it stays, exactly as without compaction.
+    win.evict_expired(25_000_000_000);
+    assert_eq!(win.total_rows(), 3);
+    assert_eq!(win.stats().rows_time_evicted, 1);
+
+    // A reader still at seq 0 sees the gap; one at seq 2 only its row.
+    let (batches, cursor, gap) = win.read_since(0);
+    assert!(gap);
+    assert_eq!(cursor, 3);
+    let values = batches[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(values.values().to_vec(), vec![200, 250, 300]);
+    let (batches, _, gap) = win.read_since(2);
+    assert!(!gap);
+    assert_eq!(batches[0].num_rows(), 1);
+}
+
+// -- 30. evolve_empty_window_rejects_non_nullable_field -----------------
+
+#[test]
+fn evolve_empty_window_rejects_non_nullable_field() {
+    let mut win = test_window(600, usize::MAX);
+    let old = win.schema().clone();
+
+    // No batches to pad, yet old-layout appends would have nothing to put
+    // in the new column.
+    let required = Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        Field::new("value", DataType::Int64, false),
+        Field::new("tag", DataType::Utf8, false),
+    ]));
+    let err = win.evolve_schema(required).unwrap_err();
+    assert!(err.to_string().contains("not nullable"), "{err}");
+    assert_eq!(win.schema(), &old);
+    win.append(make_batch(&old, &[1_000_000_000], &[100]))
+        .unwrap();
+}
@@ -1,8 +1,11 @@
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 
+use super::shared::{DecodeSlot, SharedBatch};
+
 /// Result of a watermark-aware append.
 pub enum AppendOutcome {
     Appended,
@@ -19,6 +22,19 @@ pub struct WindowParams {
     pub over: Duration,
 }
 
+/// Cumulative row counters and current occupancy of a [`Window`](super::Window).
+///
+/// Eviction counters are split by cause so operators can tell retention
+/// expiry apart from data lost to memory pressure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowStats {
+    pub rows_appended: u64,
+    pub rows_time_evicted: u64,
+    pub rows_memory_evicted: u64,
+    pub current_bytes: usize,
+    pub current_rows: usize,
+}
+
 pub(in crate::window) struct TimedBatch {
     pub(super) batch: RecordBatch,
     /// (min, max) event time in nanoseconds.
@@ -27,6 +43,48 @@ pub(in crate::window) struct TimedBatch {
     pub(super) ingested_at: Instant,
     pub(super) row_count: usize,
     pub(super) byte_size: usize,
-    /// Monotonically increasing sequence number assigned on append.
+    /// Monotonically increasing sequence number assigned on append. For a
+    /// compacted batch this is the sequence number of its newest constituent.
     pub(super) seq: u64,
+    /// Sequence number of the oldest constituent (equal to `seq` unless the
+    /// batch was produced by compaction).
+    pub(super) first_seq: u64,
+    /// Row offset at which each constituent `first_seq..=seq` starts; empty
+    /// for batches that were never compacted.
+    pub(super) seq_row_starts: Vec<usize>,
+    /// (min, max) event time of each constituent, parallel to
+    /// `seq_row_starts`, so retention can expire a compacted batch piecewise.
+    pub(super) seq_time_ranges: Vec<(i64, i64)>,
+    /// Events decoded from `batch`, shared by concurrent readers.
+    pub(super) decoded: Arc<DecodeSlot>,
+}
+
+impl TimedBatch {
+    /// The rows of this batch appended at or after `seq`.
+    ///
+    /// Lets cursor readers resume in the middle of a compacted batch without
+    /// re-reading rows they have already consumed. Zero-copy slice.
+    pub(super) fn rows_since(&self, seq: u64) -> RecordBatch {
+        match self.row_offset(seq) {
+            0 => self.batch.clone(),
+            offset => self.batch.slice(offset, self.row_count - offset),
+        }
+    }
+
+    /// Like [`Self::rows_since`], as a handle onto the shared decode slot.
+    pub(super) fn shared_since(&self, seq: u64) -> SharedBatch {
+        SharedBatch::new(
+            self.batch.clone(),
+            self.row_offset(seq),
+            Arc::clone(&self.decoded),
+        )
+    }
+
+    /// Index of the first row appended at or after `seq`.
+    fn row_offset(&self, seq: u64) -> usize {
+        if seq <= self.first_seq || self.seq_row_starts.is_empty() {
+            return 0;
+        }
+        self.seq_row_starts[(seq - self.first_seq) as usize]
+    }
 }
@@ -1,4 +1,4 @@
-use anyhow::{Result, bail};
+use anyhow::Result;
 use arrow::record_batch::RecordBatch;
 use wf_config::LatePolicy;
 
@@ -21,14 +21,7 @@ impl Window {
             return Ok(AppendOutcome::Appended);
         }
 
-        if batch.schema() != self.schema {
-            bail!(
-                "schema mismatch: window {:?} expects {:?}, got {:?}",
-                self.name,
-                self.schema,
-                batch.schema()
-            );
-        }
+        let batch = self.conform_batch(batch)?;
 
         let (min_event_time, max_event_time) = self.extract_time_range(&batch);
 
@@ -9,6 +9,8 @@ pub struct EvictReport {
     pub windows_scanned: usize,
     pub batches_time_evicted: usize,
     pub batches_memory_evicted: usize,
+    /// Batches folded away by [`Window::compact`](super::Window::compact).
+    pub batches_compacted: usize,
 }
 
 // ---------------------------------------------------------------------------
@@ -30,7 +32,7 @@ impl Evictor {
     ///
     /// **Phase 1 — time eviction**: calls [`Window::evict_expired`] on every
     /// window, removing batches whose max event time is older than
-    /// `now_nanos - over`.
+    /// `now_nanos - over`, then compacts runs of small batches that survive.
     ///
     /// **Phase 2 — memory eviction**: while the aggregate memory across all
     /// windows exceeds `max_total_bytes`, evicts the oldest batch from the
@@ -40,6 +42,7 @@ impl Evictor {
             windows_scanned: 0,
             batches_time_evicted: 0,
             batches_memory_evicted: 0,
+            batches_compacted: 0,
         };
 
         // Phase 1: time eviction
@@ -52,6 +55,7 @@ impl Evictor {
             let before = win.batch_count();
             win.evict_expired(now_nanos);
             report.batches_time_evicted += before - win.batch_count();
+            report.batches_compacted += win.compact();
         }
 
         // Phase 2: memory eviction
@@ -135,6 +139,7 @@ mod tests {
             watermark: Duration::from_secs(5).into(),
             allowed_lateness: Duration::from_secs(0).into(),
             late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
         }
     }
 
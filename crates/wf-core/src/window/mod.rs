@@ -3,7 +3,9 @@ mod evictor;
 mod registry;
 mod router;
 
-pub use buffer::{AppendOutcome, Window, WindowParams};
+pub use buffer::{
+    AppendOutcome, SchemaEvolution, SharedBatch, SharedEvents, Window, WindowParams, WindowStats,
+};
 pub use evictor::{EvictReport, Evictor};
 pub use registry::{WindowDef, WindowRegistry};
-pub use router::{RouteReport, Router};
+pub use router::{Rejection, RouteReport, Router};
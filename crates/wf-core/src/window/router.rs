@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use arrow::record_batch::RecordBatch;
 use wf_config::DistMode;
 
@@ -10,10 +10,26 @@ use super::registry::WindowRegistry;
 // ---------------------------------------------------------------------------
 
 /// Summary of a single [`Router::route`] call.
+#[derive(Default)]
 pub struct RouteReport {
     pub delivered: usize,
     pub dropped_late: usize,
     pub skipped_non_local: usize,
+    /// Deliveries refused by [`Router::route_to_window_bounded`] because the
+    /// window was at `max_window_bytes`.
+    pub saturated: usize,
+    /// Deliveries that failed, e.g. on a schema mismatch, or the whole batch
+    /// when no window subscribes to its stream. Other subscribers still
+    /// receive the batch.
+    pub rejected: Vec<Rejection>,
+}
+
+/// A batch the router could not deliver, with the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// Window that refused the batch; `None` when no window matched.
+    pub window: Option<String>,
+    pub reason: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -35,46 +51,125 @@ impl Router {
     }
 
     /// Route a batch to all windows subscribed to `stream_name`.
+    ///
+    /// A window refusing the batch is recorded in
+    /// [`RouteReport::rejected`] without affecting the other subscribers.
     pub fn route(&self, stream_name: &str, batch: RecordBatch) -> Result<RouteReport> {
-        let mut report = RouteReport {
-            delivered: 0,
-            dropped_late: 0,
-            skipped_non_local: 0,
-        };
+        let mut report = RouteReport::default();
 
         let subs = self.registry.subscribers_of(stream_name);
+        if subs.is_empty() {
+            report.rejected.push(Rejection {
+                window: None,
+                reason: format!("no window subscribes to stream {stream_name:?}"),
+            });
+        }
 
         for (window_name, mode) in subs {
             if !matches!(mode, DistMode::Local) {
                 report.skipped_non_local += 1;
                 continue;
             }
+            self.deliver(window_name, batch.clone(), false, &mut report);
+        }
 
-            let win_lock = self
-                .registry
-                .get_window(window_name)
-                .expect("subscription references non-existent window");
-            let outcome = {
-                let mut win = win_lock.write().expect("window lock poisoned");
-                win.append_with_watermark(batch.clone())?
-            };
-
-            match outcome {
-                AppendOutcome::Appended => {
-                    report.delivered += 1;
-                    // Notify after releasing the write lock so waiters can
-                    // immediately acquire a read lock.
-                    if let Some(notify) = self.registry.get_notifier(window_name) {
-                        notify.notify_waiters();
-                    }
-                }
-                AppendOutcome::DroppedLate => report.dropped_late += 1,
-            }
+        Ok(report)
+    }
+
+    /// Route a batch directly to the window named `window_name`, bypassing
+    /// stream subscriptions.
+    ///
+    /// Used by sources that map their input straight onto windows (e.g. a
+    /// Kafka topic → window mapping). Returns `Err` if the window does not
+    /// exist; a batch the window refuses is reported in
+    /// [`RouteReport::rejected`].
+    pub fn route_to_window(&self, window_name: &str, batch: RecordBatch) -> Result<RouteReport> {
+        self.route_to_window_inner(window_name, batch, false)
+    }
+
+    /// Like [`route_to_window`](Self::route_to_window), but refuses the batch
+    /// (counted in [`RouteReport::saturated`]) when appending it would push
+    /// the window past `max_window_bytes`.
+    ///
+    /// The check and the append happen under one write lock, so concurrent
+    /// producers cannot overshoot the limit between them.
+    pub fn route_to_window_bounded(
+        &self,
+        window_name: &str,
+        batch: RecordBatch,
+    ) -> Result<RouteReport> {
+        self.route_to_window_inner(window_name, batch, true)
+    }
+
+    fn route_to_window_inner(
+        &self,
+        window_name: &str,
+        batch: RecordBatch,
+        bounded: bool,
+    ) -> Result<RouteReport> {
+        let mut report = RouteReport::default();
+
+        let Some(win_lock) = self.registry.get_window(window_name) else {
+            bail!("unknown window: {window_name:?}");
+        };
+        let is_local = {
+            let win = win_lock.read().expect("window lock poisoned");
+            matches!(win.config.mode, DistMode::Local)
+        };
+        if !is_local {
+            report.skipped_non_local += 1;
+            return Ok(report);
         }
+        self.deliver(window_name, batch, bounded, &mut report);
 
         Ok(report)
     }
 
+    /// Append `batch` to a local window and notify its readers. With
+    /// `bounded`, a saturated window refuses the batch instead.
+    fn deliver(
+        &self,
+        window_name: &str,
+        batch: RecordBatch,
+        bounded: bool,
+        report: &mut RouteReport,
+    ) {
+        let win_lock = self
+            .registry
+            .get_window(window_name)
+            .expect("subscription references non-existent window");
+        let outcome = {
+            let mut win = win_lock.write().expect("window lock poisoned");
+            if bounded && win.is_saturated(batch.get_array_memory_size()) {
+                report.saturated += 1;
+                return;
+            }
+            win.append_with_watermark(batch)
+        };
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                report.rejected.push(Rejection {
+                    window: Some(window_name.to_string()),
+                    reason: e.to_string(),
+                });
+                return;
+            }
+        };
+
+        match outcome {
+            AppendOutcome::Appended => {
+                report.delivered += 1;
+                // Notify after releasing the write lock so waiters can
+                // immediately acquire a read lock.
+                if let Some(notify) = self.registry.get_notifier(window_name) {
+                    notify.notify_waiters();
+                }
+            }
+            AppendOutcome::DroppedLate => report.dropped_late += 1,
+        }
+    }
+
     /// Borrow the inner registry.
     pub fn registry(&self) -> &WindowRegistry {
         &self.registry
@@ -123,6 +218,7 @@ mod tests {
             watermark: Duration::from_secs(5).into(),
             allowed_lateness: Duration::from_secs(0).into(),
             late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
         }
     }
 
@@ -231,5 +327,96 @@ mod tests {
         assert_eq!(report.delivered, 0);
         assert_eq!(report.dropped_late, 0);
         assert_eq!(report.skipped_non_local, 0);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].window, None);
+        assert!(report.rejected[0].reason.contains("\"unknown\""));
+    }
+
+    // -- 5. route_to_window_bypasses_subscriptions ----------------------------
+
+    #[test]
+    fn route_to_window_bypasses_subscriptions() {
+        let reg = WindowRegistry::build(vec![
+            make_def("win_a", vec!["events"], DistMode::Local),
+            make_def("win_b", vec![], DistMode::Replicated),
+        ])
+        .unwrap();
+        let router = Router::new(reg);
+        let schema = test_schema();
+
+        let report = router
+            .route_to_window("win_a", make_batch(&schema, &[10_000_000_000], &[1]))
+            .unwrap();
+        assert_eq!(report.delivered, 1);
+        assert_eq!(router.registry().snapshot("win_a").unwrap().len(), 1);
+
+        let report = router
+            .route_to_window("win_b", make_batch(&schema, &[10_000_000_000], &[1]))
+            .unwrap();
+        assert_eq!(report.skipped_non_local, 1);
+
+        assert!(
+            router
+                .route_to_window("missing", make_batch(&schema, &[10_000_000_000], &[1]))
+                .is_err()
+        );
+    }
+
+    // -- 6. schema_mismatch_rejected_per_window -------------------------------
+
+    #[test]
+    fn schema_mismatch_rejected_per_window() {
+        let other = Arc::new(Schema::new(vec![Field::new(
+            "value",
+            DataType::Int64,
+            false,
+        )]));
+        let mut odd = make_def("win_odd", vec!["events"], DistMode::Local);
+        odd.params.schema = other;
+        odd.params.time_col_index = None;
+        let reg = WindowRegistry::build(vec![
+            make_def("win_a", vec!["events"], DistMode::Local),
+            odd,
+        ])
+        .unwrap();
+        let router = Router::new(reg);
+
+        // The mismatching window rejects the batch; the other still gets it.
+        let report = router
+            .route(
+                "events",
+                make_batch(&test_schema(), &[10_000_000_000], &[1]),
+            )
+            .unwrap();
+        assert_eq!(report.delivered, 1);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].window.as_deref(), Some("win_odd"));
+        assert!(report.rejected[0].reason.contains("schema mismatch"));
+        assert_eq!(router.registry().snapshot("win_a").unwrap().len(), 1);
+        assert!(router.registry().snapshot("win_odd").unwrap().is_empty());
+    }
+
+    // -- 7. route_to_window_bounded_refuses_saturated_window -----------------
+
+    #[test]
+    fn route_to_window_bounded_refuses_saturated_window() {
+        let schema = test_schema();
+        let size = make_batch(&schema, &[10_000_000_000], &[1]).get_array_memory_size();
+        let mut def = make_def("win_a", vec!["events"], DistMode::Local);
+        def.config.max_window_bytes = (size * 2).into();
+        let router = Router::new(WindowRegistry::build(vec![def]).unwrap());
+
+        for value in 0..2 {
+            let report = router
+                .route_to_window_bounded("win_a", make_batch(&schema, &[10_000_000_000], &[value]))
+                .unwrap();
+            assert_eq!(report.delivered, 1);
+        }
+        let report = router
+            .route_to_window_bounded("win_a", make_batch(&schema, &[10_000_000_000], &[2]))
+            .unwrap();
+        assert_eq!(report.delivered, 0);
+        assert_eq!(report.saturated, 1);
+        assert_eq!(router.registry().snapshot("win_a").unwrap().len(), 2);
     }
 }
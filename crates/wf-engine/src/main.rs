@@ -2,10 +2,10 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 
 use wf_config::{FusionConfig, HumanDuration};
-use wf_runtime::lifecycle::{Reactor, wait_for_signal};
+use wf_runtime::lifecycle::{Reactor, reload_on_sighup, wait_for_signal};
 use wf_runtime::tracing_init::init_tracing;
 
 #[derive(Parser)]
@@ -32,6 +32,37 @@ enum Commands {
         #[arg(long)]
         metrics_listen: Option<String>,
     },
+    /// Load config, schemas and rules and run all startup checks without
+    /// starting the engine (exit 1 on any problem)
+    Validate {
+        /// Path to wfusion.toml config file
+        #[arg(short, long)]
+        config: PathBuf,
+    },
+    /// Manage wfusion.toml configuration files
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Target shell: bash, zsh, fish, elvish or powershell
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Write an annotated default wfusion.toml
+    Init {
+        /// Output path
+        #[arg(short, long, default_value = "wfusion.toml")]
+        output: PathBuf,
+        /// Overwrite the output file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[tokio::main]
@@ -81,10 +112,57 @@ async fn main() -> Result<()> {
                 );
             }
 
+            tokio::spawn(reload_on_sighup(
+                reactor.reload_handle(),
+                reactor.cancel_token(),
+            ));
             wait_for_signal(reactor.cancel_token()).await;
             reactor.shutdown();
             reactor.wait().await.map_err(|e| anyhow::anyhow!("{e}"))?;
         }
+
+        Commands::Validate { config } => {
+            let config_path = config
+                .canonicalize()
+                .map_err(|e| anyhow::anyhow!("config path '{}': {e}", config.display()))?;
+            let fusion_config = FusionConfig::load(&config_path)?;
+            let base_dir = config_path
+                .parent()
+                .expect("config path must have a parent directory");
+            let summary = wf_runtime::lifecycle::validate(&fusion_config, base_dir)
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+            println!(
+                "{}: OK ({} schemas, {} rules, {} windows, {} sink groups)",
+                config.display(),
+                summary.schemas,
+                summary.rules,
+                summary.windows,
+                summary.sink_groups
+            );
+        }
+
+        Commands::Config {
+            command: ConfigCommands::Init { output, force },
+        } => {
+            if output.exists() && !force {
+                anyhow::bail!(
+                    "{} already exists (use --force to overwrite)",
+                    output.display()
+                );
+            }
+            std::fs::write(&output, wf_config::template::default_config_toml())
+                .map_err(|e| anyhow::anyhow!("failed to write {}: {e}", output.display()))?;
+            eprintln!("Wrote {}", output.display());
+        }
+
+        Commands::Completions { shell } => {
+            clap_complete::generate(
+                shell,
+                &mut Cli::command(),
+                "wfusion",
+                &mut std::io::stdout(),
+            );
+        }
     }
 
     Ok(())
@@ -0,0 +1,70 @@
+//! `wfusion validate` end to end: a valid deployment passes, a broken one
+//! fails with every problem reported, and neither starts the engine.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn examples() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples")
+}
+
+fn wfusion_validate(config: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wfusion"))
+        .args(["validate", "--config"])
+        .arg(config)
+        .output()
+        .expect("failed to run wfusion")
+}
+
+#[test]
+fn validate_accepts_example_config() {
+    let out = wfusion_validate(&examples().join("wfusion.toml"));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        out.status.success(),
+        "stdout: {stdout}\nstderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(
+        stdout.contains("OK (2 schemas, 1 rules, 2 windows"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn validate_rejects_broken_config() {
+    let dir = tempfile::tempdir().unwrap();
+    // Copy the example deployment, then break it: the auth_events window
+    // is missing from wfusion.toml and the listen address has no scheme.
+    let example = std::fs::read_to_string(examples().join("wfusion.toml")).unwrap();
+    let broken = example
+        .replace("[window.auth_events]", "[window.unused_events]")
+        .replace("tcp://127.0.0.1:9800", "127.0.0.1:0")
+        .replace(
+            "sinks = \"sinks\"",
+            &format!("sinks = {:?}", examples().join("sinks")),
+        )
+        .replace(
+            "count/schemas/*.wfs",
+            &examples().join("count/schemas/*.wfs").display().to_string(),
+        )
+        .replace(
+            "count/rules/*.wfl",
+            &examples().join("count/rules/*.wfl").display().to_string(),
+        );
+    let config = dir.path().join("wfusion.toml");
+    std::fs::write(&config, &broken).unwrap();
+
+    let out = wfusion_validate(&config);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(!out.status.success(), "broken config must fail validation");
+    assert!(stderr.contains("server.listen"), "{stderr}");
+
+    // Fix the config-level problem: the cross-file check now reports the
+    // window missing from wfusion.toml.
+    std::fs::write(&config, broken.replace("127.0.0.1:0", "tcp://127.0.0.1:0")).unwrap();
+    let out = wfusion_validate(&config);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(!out.status.success(), "missing window must fail validation");
+    assert!(stderr.contains("window.auth_events"), "{stderr}");
+}
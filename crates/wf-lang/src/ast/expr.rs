@@ -1,9 +1,11 @@
+use serde::Serialize;
+
 // ---------------------------------------------------------------------------
 // Field references
 // ---------------------------------------------------------------------------
 
 /// Field selector within a step branch: `.ident` or `["string"]`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[non_exhaustive]
 pub enum FieldSelector {
     Dot(String),
@@ -11,7 +13,7 @@ pub enum FieldSelector {
 }
 
 /// Field reference in expressions.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[non_exhaustive]
 pub enum FieldRef {
     /// Bare identifier, e.g. `sip`.
@@ -26,7 +28,7 @@ pub enum FieldRef {
 // Operators
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[non_exhaustive]
 pub enum CmpOp {
     Eq,
@@ -37,7 +39,7 @@ pub enum CmpOp {
     Ge,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[non_exhaustive]
 pub enum BinOp {
     And,
@@ -55,11 +57,22 @@ pub enum BinOp {
     Mod,
 }
 
+/// Quantifier of an [`Expr::Contains`] test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ContainsMode {
+    /// `arr contains v` — exactly one value.
+    Single,
+    /// `arr contains any (v1, v2, ...)` — at least one value is present.
+    Any,
+    /// `arr contains all (v1, v2, ...)` — every value is present.
+    All,
+}
+
 // ---------------------------------------------------------------------------
 // Expressions
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[non_exhaustive]
 pub enum Expr {
     /// Number literal (integer or float).
@@ -68,6 +81,8 @@ pub enum Expr {
     StringLit(String),
     /// Boolean literal.
     Bool(bool),
+    /// Duration literal (`5s`, `10m`); compares with `time` differences.
+    Duration(std::time::Duration),
     /// Field reference.
     Field(FieldRef),
     /// Binary operation.
@@ -78,6 +93,8 @@ pub enum Expr {
     },
     /// Unary negation.
     Neg(Box<Expr>),
+    /// Boolean negation: `not expr` / `!expr`.
+    Not(Box<Expr>),
     /// Function call: `name(args...)` or `qualifier.name(args...)`.
     FuncCall {
         qualifier: Option<String>,
@@ -90,6 +107,13 @@ pub enum Expr {
         list: Vec<Expr>,
         negated: bool,
     },
+    /// Array membership: `arr contains v`, `arr contains any (v1, ...)` or
+    /// `arr contains all (v1, ...)`.
+    Contains {
+        expr: Box<Expr>,
+        mode: ContainsMode,
+        values: Vec<Expr>,
+    },
     /// Conditional expression: `if cond then yes else no`.
     IfThenElse {
         cond: Box<Expr>,
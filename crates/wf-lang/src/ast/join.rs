@@ -1,32 +1,60 @@
 use std::time::Duration;
 
+use serde::Serialize;
+
 use super::*;
 
 // ---------------------------------------------------------------------------
 // Join clause
 // ---------------------------------------------------------------------------
 
-/// `join window snapshot/asof on cond`
+/// `join window snapshot/asof [optional] on cond`
 #[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
 pub struct JoinClause {
     pub target_window: String,
     pub mode: JoinMode,
+    /// `optional`: an asof join that finds no row keeps the match, with the
+    /// joined fields left null, instead of dropping it.
+    pub optional: bool,
     pub conditions: Vec<JoinCondition>,
 }
 
 /// Join time-point semantics.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[non_exhaustive]
 pub enum JoinMode {
     Snapshot,
-    Asof { within: Option<Duration> },
+    Asof {
+        direction: AsofDirection,
+        within: Option<Duration>,
+    },
+}
+
+/// Which side of the event time an asof join searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum AsofDirection {
+    /// Latest right row with `ts <= event_time`.
+    #[default]
+    Backward,
+    /// Earliest right row with `ts >= event_time`.
+    Forward,
+}
+
+impl AsofDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AsofDirection::Backward => "backward",
+            AsofDirection::Forward => "forward",
+        }
+    }
 }
 
-/// `left == right` in a join on-clause.
+/// `left <op> right` in a join on-clause (`==`, `!=`, `<`, `<=`, `>`, `>=`).
 #[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
 pub struct JoinCondition {
     pub left: FieldRef,
+    pub op: CmpOp,
     pub right: FieldRef,
 }
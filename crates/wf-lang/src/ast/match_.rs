@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use serde::Serialize;
+
 use super::*;
 
 // ---------------------------------------------------------------------------
@@ -11,11 +13,33 @@ use super::*;
 pub enum WindowMode {
     Sliding,
     Fixed,
-    Session(std::time::Duration), // gap duration
+    Session(SessionSpec),
+}
+
+/// `session(gap[, max_duration=DUR][, min_events=N])` parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SessionSpec {
+    /// Inactivity gap after the last event that closes the session.
+    pub gap: Duration,
+    /// Hard cap from the session's first event; `None` means unbounded.
+    pub max_duration: Option<Duration>,
+    /// Sessions closing with fewer events emit nothing on the close path.
+    pub min_events: Option<u64>,
+}
+
+impl SessionSpec {
+    /// A plain gap-only session.
+    pub fn new(gap: Duration) -> Self {
+        Self {
+            gap,
+            max_duration: None,
+            min_events: None,
+        }
+    }
 }
 
 /// Close block mode: OR (independent paths) or AND (both required).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum CloseMode {
     /// `on close { ... }` — event path and close path fire independently.
     Or,
@@ -78,13 +102,13 @@ pub struct PipeChain {
     pub threshold: Expr,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[non_exhaustive]
 pub enum Transform {
     Distinct,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[non_exhaustive]
 pub enum Measure {
     Count,
@@ -92,4 +116,6 @@ pub enum Measure {
     Avg,
     Min,
     Max,
+    /// Events per `unit` over the instance's elapsed span (Float).
+    Rate(Duration),
 }
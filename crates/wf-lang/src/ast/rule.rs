@@ -53,7 +53,7 @@ pub struct PipelineStage {
     pub joins: Vec<JoinClause>,
 }
 
-/// `rule name { meta events stage_chain entity yield [conv] [limits] }`
+/// `rule name { meta events stage_chain entity yield+ [conv] [limits] }`
 #[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
 pub struct RuleDecl {
@@ -65,7 +65,8 @@ pub struct RuleDecl {
     pub joins: Vec<JoinClause>,
     pub pipeline_stages: Vec<PipelineStage>,
     pub entity: EntityClause,
-    pub yield_clause: YieldClause,
+    /// One or more `yield` clauses; each target receives its own output.
+    pub yields: Vec<YieldClause>,
     pub pattern_origin: Option<PatternOrigin>,
     pub conv: Option<ConvClause>,
     pub limits: Option<LimitsBlock>,
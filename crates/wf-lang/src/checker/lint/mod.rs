@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::ast::{CmpOp, Expr, MatchStep, WflFile};
+use crate::ast::{BinOp, CmpOp, Expr, FieldRef, MatchStep, StepBranch, WflFile};
 use crate::schema::WindowSchema;
 
 use super::{CheckError, Severity};
@@ -46,6 +46,24 @@ pub fn lint_wfl(file: &WflFile, _schemas: &[WindowSchema]) -> Vec<CheckError> {
 
         // W006: yield field name near-matches a system field
         lint_yield_case_collision(rule, name, &mut warnings);
+
+        // W007: OR branches that can never fire or duplicate an earlier one
+        lint_dead_branches(&rule.match_clause.on_event, name, &mut warnings);
+        if let Some(ref close_block) = rule.match_clause.on_close {
+            lint_dead_branches(&close_block.steps, name, &mut warnings);
+        }
+        for stage in &rule.pipeline_stages {
+            lint_dead_branches(&stage.match_clause.on_event, name, &mut warnings);
+            if let Some(ref close_block) = stage.match_clause.on_close {
+                lint_dead_branches(&close_block.steps, name, &mut warnings);
+            }
+        }
+
+        // W008: yield carries no user fields
+        lint_empty_yield(rule, name, &mut warnings);
+
+        // W009: two event aliases bind the same window with the same filter
+        lint_duplicate_event_binds(rule, name, &mut warnings);
     }
 
     warnings
@@ -72,7 +90,7 @@ fn lint_unused_alias(rule: &crate::ast::RuleDecl, rule_name: &str, warnings: &mu
     collect_expr_aliases(&rule.entity.id_expr, &declared, &mut used);
 
     // Collect aliases referenced in yield arguments
-    for arg in &rule.yield_clause.args {
+    for arg in rule.yields.iter().flat_map(|y| &y.args) {
         collect_expr_aliases(&arg.value, &declared, &mut used);
     }
 
@@ -114,7 +132,7 @@ fn collect_expr_aliases<'a>(expr: &'a Expr, declared: &HashSet<&str>, used: &mut
             collect_expr_aliases(left, declared, used);
             collect_expr_aliases(right, declared, used);
         }
-        Expr::Neg(inner) => collect_expr_aliases(inner, declared, used),
+        Expr::Neg(inner) | Expr::Not(inner) => collect_expr_aliases(inner, declared, used),
         Expr::FuncCall { args, .. } => {
             for arg in args {
                 collect_expr_aliases(arg, declared, used);
@@ -128,7 +146,17 @@ fn collect_expr_aliases<'a>(expr: &'a Expr, declared: &HashSet<&str>, used: &mut
                 collect_expr_aliases(item, declared, used);
             }
         }
-        Expr::Number(_) | Expr::StringLit(_) | Expr::Bool(_) => {}
+        Expr::Contains {
+            expr: inner,
+            values,
+            ..
+        } => {
+            collect_expr_aliases(inner, declared, used);
+            for value in values {
+                collect_expr_aliases(value, declared, used);
+            }
+        }
+        Expr::Number(_) | Expr::StringLit(_) | Expr::Bool(_) | Expr::Duration(_) => {}
         Expr::IfThenElse {
             cond,
             then_expr,
@@ -231,7 +259,7 @@ fn lint_yield_case_collision(
     rule_name: &str,
     warnings: &mut Vec<CheckError>,
 ) {
-    for arg in &rule.yield_clause.args {
+    for arg in rule.yields.iter().flat_map(|y| &y.args) {
         let lower = arg.name.to_ascii_lowercase();
         if SYSTEM_FIELD_NAMES.contains(&arg.name.as_str()) {
             continue;
@@ -250,6 +278,159 @@ fn lint_yield_case_collision(
     }
 }
 
+// ---------------------------------------------------------------------------
+// W007: unreachable OR branch (constant-false guard or duplicate branch)
+// ---------------------------------------------------------------------------
+
+fn lint_dead_branches(steps: &[MatchStep], rule_name: &str, warnings: &mut Vec<CheckError>) {
+    for (step_idx, step) in steps.iter().enumerate() {
+        for (idx, branch) in step.branches.iter().enumerate() {
+            let reason = if branch.guard.as_ref().is_some_and(is_never_true) {
+                Some("its guard can never be true".to_string())
+            } else {
+                step.branches[..idx]
+                    .iter()
+                    .position(|earlier| same_branch(earlier, branch))
+                    .map(|dup| format!("it duplicates branch {}", dup + 1))
+            };
+            if let Some(reason) = reason {
+                warnings.push(CheckError {
+                    severity: Severity::Warning,
+                    rule: Some(rule_name.to_string()),
+                    test: None,
+                    message: format!(
+                        "[W007] step {} branch {} (`{}`) is unreachable: {}",
+                        step_idx + 1,
+                        idx + 1,
+                        branch.source,
+                        reason
+                    ),
+                });
+            }
+        }
+    }
+}
+
+/// Branches that see exactly the same events; labels and pipes are ignored,
+/// so the branch with the looser threshold always fires first.
+fn same_branch(a: &StepBranch, b: &StepBranch) -> bool {
+    a.source == b.source && a.field == b.field && a.guard == b.guard
+}
+
+/// Conservative check for guards that are false for every event: the
+/// literal `false`, or a conjunction that contains `false` or compares the
+/// same field against contradictory literals (`f == "a" && f == "b"`,
+/// `f == "a" && f != "a"`).
+fn is_never_true(guard: &Expr) -> bool {
+    let mut conjuncts = Vec::new();
+    collect_conjuncts(guard, &mut conjuncts);
+    if conjuncts.iter().any(|c| matches!(c, Expr::Bool(false))) {
+        return true;
+    }
+    let cmps: Vec<(&FieldRef, bool, &Expr)> = conjuncts
+        .iter()
+        .filter_map(|c| field_literal_cmp(c))
+        .collect();
+    cmps.iter().enumerate().any(|(i, (field, eq, lit))| {
+        cmps[i + 1..]
+            .iter()
+            .any(|(other_field, other_eq, other_lit)| {
+                field == other_field
+                    && match (eq, other_eq) {
+                        (true, true) => lit != other_lit,
+                        (true, false) | (false, true) => lit == other_lit,
+                        (false, false) => false,
+                    }
+            })
+    })
+}
+
+fn collect_conjuncts<'a>(expr: &'a Expr, out: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::BinOp {
+            op: BinOp::And,
+            left,
+            right,
+        } => {
+            collect_conjuncts(left, out);
+            collect_conjuncts(right, out);
+        }
+        other => out.push(other),
+    }
+}
+
+/// `field == literal` / `field != literal` (either operand order), as
+/// `(field, is_eq, literal)`.
+fn field_literal_cmp(expr: &Expr) -> Option<(&FieldRef, bool, &Expr)> {
+    let Expr::BinOp { op, left, right } = expr else {
+        return None;
+    };
+    let eq = match op {
+        BinOp::Eq => true,
+        BinOp::Ne => false,
+        _ => return None,
+    };
+    let is_lit = |e: &Expr| matches!(e, Expr::Number(_) | Expr::StringLit(_) | Expr::Bool(_));
+    match (left.as_ref(), right.as_ref()) {
+        (Expr::Field(f), lit) if is_lit(lit) => Some((f, eq, lit)),
+        (lit, Expr::Field(f)) if is_lit(lit) => Some((f, eq, lit)),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// W008: empty yield
+// ---------------------------------------------------------------------------
+
+/// Only the rule's final `yield`s are checked: intermediate pipeline stages
+/// have no yield in the AST, their outputs are synthesized by the compiler.
+fn lint_empty_yield(rule: &crate::ast::RuleDecl, rule_name: &str, warnings: &mut Vec<CheckError>) {
+    for yc in rule.yields.iter().filter(|y| y.args.is_empty()) {
+        warnings.push(CheckError {
+            severity: Severity::Warning,
+            rule: Some(rule_name.to_string()),
+            test: None,
+            message: format!(
+                "[W008] yield to '{}' has no fields; alerts will carry only system fields — consider yielding context such as the entity or matched counts",
+                yc.target
+            ),
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// W009: redundant event bind (same window + identical filter)
+// ---------------------------------------------------------------------------
+
+fn lint_duplicate_event_binds(
+    rule: &crate::ast::RuleDecl,
+    rule_name: &str,
+    warnings: &mut Vec<CheckError>,
+) {
+    let decls = &rule.events.decls;
+    for (idx, decl) in decls.iter().enumerate() {
+        let earlier = decls[..idx]
+            .iter()
+            .find(|e| e.window == decl.window && e.filter == decl.filter);
+        if let Some(earlier) = earlier {
+            let filter = if decl.filter.is_some() {
+                "the same filter"
+            } else {
+                "no filter"
+            };
+            warnings.push(CheckError {
+                severity: Severity::Warning,
+                rule: Some(rule_name.to_string()),
+                test: None,
+                message: format!(
+                    "[W009] event aliases '{}' and '{}' both bind window '{}' with {}; one of them is redundant",
+                    earlier.alias, decl.alias, decl.window, filter
+                ),
+            });
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -258,7 +439,7 @@ fn is_zero(expr: &Expr) -> bool {
     matches!(expr, Expr::Number(n) if *n == 0.0)
 }
 
-fn cmp_symbol(cmp: CmpOp) -> &'static str {
+pub(crate) fn cmp_symbol(cmp: CmpOp) -> &'static str {
     match cmp {
         CmpOp::Eq => "==",
         CmpOp::Ne => "!=",
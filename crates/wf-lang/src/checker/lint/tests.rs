@@ -281,3 +281,152 @@ rule r {
 "#;
     assert_no_warning(input, &[auth_events_window(), out], "W006");
 }
+
+// W007: unreachable OR branch
+#[test]
+fn w007_duplicate_branch() {
+    let input = r#"
+rule r {
+    events { a : auth_events  b : auth_events && action == "ok" }
+    match<sip:5m> {
+        on event {
+            a | count >= 3 || b | count >= 1 || first: a | count >= 3;
+        }
+    } -> score(50.0)
+    entity(ip, a.sip)
+    yield out (x = a.sip)
+}
+"#;
+    let warnings = lint_warnings(input, &[auth_events_window(), output_window()]);
+    let w007: Vec<_> = warnings.iter().filter(|w| w.contains("W007")).collect();
+    assert_eq!(w007.len(), 1, "{warnings:?}");
+    assert!(
+        w007[0].contains("branch 3") && w007[0].contains("duplicates branch 1"),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn w007_false_guard() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> {
+        on event {
+            e | count >= 3 || e && false | count >= 1;
+        }
+    } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_warning(
+        input,
+        &[auth_events_window(), output_window()],
+        "branch 2 (`e`) is unreachable: its guard can never be true",
+    );
+}
+
+#[test]
+fn w007_contradictory_guard() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> {
+        on event {
+            e && action == "failed" && action == "ok" | count >= 1;
+        }
+    } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_warning(input, &[auth_events_window(), output_window()], "W007");
+}
+
+#[test]
+fn w007_distinct_branches_no_warning() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> {
+        on event {
+            e && action == "failed" | count >= 3 || e && action == "ok" | count >= 3 || e | count >= 10;
+        }
+    } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_no_warning(input, &[auth_events_window(), output_window()], "W007");
+}
+
+// W008: empty yield
+#[test]
+fn w008_empty_yield() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 3; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out ()
+}
+"#;
+    assert_has_warning(
+        input,
+        &[auth_events_window(), output_window()],
+        "[W008] yield to 'out' has no fields",
+    );
+}
+
+#[test]
+fn w008_pipeline_with_final_fields_no_warning() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip,dip:5m> { on event { e | count >= 1; } }
+    |> match<sip:10m> { on event { _in | count >= 3; } } -> score(50.0)
+    entity(ip, _in.sip)
+    yield out (x = _in.sip)
+}
+"#;
+    assert_no_warning(input, &[auth_events_window(), output_window()], "W008");
+}
+
+// W009: redundant event bind
+#[test]
+fn w009_identical_binds() {
+    let input = r#"
+rule r {
+    events {
+        a : auth_events && action == "failed"
+        b : auth_events && action == "failed"
+    }
+    match<sip:5m> { on event { a | count >= 3; b | count >= 1; } } -> score(50.0)
+    entity(ip, a.sip)
+    yield out (x = a.sip)
+}
+"#;
+    assert_has_warning(
+        input,
+        &[auth_events_window(), output_window()],
+        "[W009] event aliases 'a' and 'b' both bind window 'auth_events' with the same filter",
+    );
+}
+
+#[test]
+fn w009_distinct_filters_no_warning() {
+    let input = r#"
+rule r {
+    events {
+        a : auth_events && action == "failed"
+        b : auth_events && action == "ok"
+        c : auth_events
+    }
+    match<sip:5m> { on event { a | count >= 3; b | count >= 1; c | count >= 1; } } -> score(50.0)
+    entity(ip, a.sip)
+    yield out (x = a.sip)
+}
+"#;
+    assert_no_warning(input, &[auth_events_window(), output_window()], "W009");
+}
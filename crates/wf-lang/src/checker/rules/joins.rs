@@ -1,7 +1,9 @@
-use crate::ast::{FieldRef, JoinMode};
+use crate::ast::{CmpOp, Expr, FieldRef, JoinClause, JoinMode, RuleDecl};
 use crate::schema::WindowSchema;
 
-use crate::checker::scope::Scope;
+use crate::checker::lint::cmp_symbol;
+use crate::checker::scope::{Scope, field_type_to_val};
+use crate::checker::types::{ValType, is_numeric, is_orderable};
 use crate::checker::{CheckError, Severity};
 
 pub fn check_joins_list(
@@ -76,10 +78,53 @@ pub fn check_joins_list(
                             });
                         }
                     }
+
+                    // Ordering predicates need orderable operands of the same kind
+                    if matches!(cond.op, CmpOp::Lt | CmpOp::Le | CmpOp::Gt | CmpOp::Ge) {
+                        let left_ty = scope.resolve_field_ref(&cond.left).ok().flatten();
+                        let right_ty = match &cond.right {
+                            FieldRef::Qualified(_, field) => target_schema
+                                .fields
+                                .iter()
+                                .find(|f| f.name == *field)
+                                .map(|f| field_type_to_val(&f.field_type)),
+                            _ => None,
+                        };
+                        if let Some(msg) =
+                            ordering_cond_problem(left_ty.as_ref(), right_ty.as_ref())
+                        {
+                            errors.push(CheckError {
+                                severity: Severity::Error,
+                                rule: Some(rule_name.to_string()),
+                                test: None,
+                                message: format!(
+                                    "join condition `{} {} {}`: {}",
+                                    field_ref_text(&cond.left),
+                                    cmp_symbol(cond.op),
+                                    field_ref_text(&cond.right),
+                                    msg
+                                ),
+                            });
+                        }
+                    }
+                }
+
+                // T55: snapshot joins never drop the match, so `optional`
+                // would be a no-op there
+                if join.optional && matches!(join.mode, JoinMode::Snapshot) {
+                    errors.push(CheckError {
+                        severity: Severity::Error,
+                        rule: Some(rule_name.to_string()),
+                        test: None,
+                        message: format!(
+                            "join `{}`: `optional` only applies to asof joins",
+                            join.target_window
+                        ),
+                    });
                 }
 
                 // T49: asof mode requires time field on right table
-                if let JoinMode::Asof { within } = &join.mode {
+                if let JoinMode::Asof { within, .. } = &join.mode {
                     if target_schema.time_field.is_none() {
                         errors.push(CheckError {
                             severity: Severity::Error,
@@ -109,3 +154,115 @@ pub fn check_joins_list(
         }
     }
 }
+
+/// T56: fields of an `optional` join are null when no row was found, so the
+/// score and entity id may only use them through `coalesce()`, `isnull()` or
+/// `isnotnull()`. Yield fields are fine: a null field is simply omitted.
+pub fn check_optional_join_refs(
+    rule: &RuleDecl,
+    joins: &[JoinClause],
+    schemas: &[WindowSchema],
+    scope: &Scope<'_>,
+    errors: &mut Vec<CheckError>,
+) {
+    let optional: Vec<&WindowSchema> = joins
+        .iter()
+        .filter(|j| j.optional && !scope.aliases.contains_key(j.target_window.as_str()))
+        .filter_map(|j| schemas.iter().find(|s| s.name == j.target_window))
+        .collect();
+    if optional.is_empty() {
+        return;
+    }
+    // A bare name is nullable only if no event source provides it.
+    let nullable = |fref: &FieldRef| -> Option<String> {
+        match fref {
+            FieldRef::Qualified(window, _) | FieldRef::Bracketed(window, _) => optional
+                .iter()
+                .any(|ws| ws.name == *window)
+                .then(|| window.clone()),
+            FieldRef::Simple(name) if scope.resolve_field_ref(fref).is_err() => optional
+                .iter()
+                .find(|ws| ws.fields.iter().any(|f| f.name == *name))
+                .map(|ws| ws.name.clone()),
+            _ => None,
+        }
+    };
+
+    for (what, expr) in [
+        ("score", &rule.score.expr),
+        ("entity id", &rule.entity.id_expr),
+    ] {
+        if let Some((fref, window)) = unguarded_ref(expr, &nullable) {
+            errors.push(CheckError {
+                severity: Severity::Error,
+                rule: Some(rule.name.clone()),
+                test: None,
+                message: format!(
+                    "{what} uses `{}` from optional join `{window}`, which is null when no row matches; wrap it in coalesce(...)",
+                    field_ref_text(fref)
+                ),
+            });
+        }
+    }
+}
+
+/// First field reference in `expr` that `nullable` flags and that is not
+/// inside a null-handling call.
+fn unguarded_ref<'e>(
+    expr: &'e Expr,
+    nullable: &dyn Fn(&FieldRef) -> Option<String>,
+) -> Option<(&'e FieldRef, String)> {
+    match expr {
+        Expr::Field(fref) => nullable(fref).map(|w| (fref, w)),
+        Expr::FuncCall { name, .. }
+            if matches!(name.as_str(), "coalesce" | "isnull" | "isnotnull") =>
+        {
+            None
+        }
+        Expr::FuncCall { args, .. } => args.iter().find_map(|a| unguarded_ref(a, nullable)),
+        Expr::BinOp { left, right, .. } => {
+            unguarded_ref(left, nullable).or_else(|| unguarded_ref(right, nullable))
+        }
+        Expr::Neg(inner) | Expr::Not(inner) => unguarded_ref(inner, nullable),
+        Expr::InList { expr, list, .. } => unguarded_ref(expr, nullable)
+            .or_else(|| list.iter().find_map(|e| unguarded_ref(e, nullable))),
+        Expr::Contains { expr, values, .. } => unguarded_ref(expr, nullable)
+            .or_else(|| values.iter().find_map(|e| unguarded_ref(e, nullable))),
+        Expr::IfThenElse {
+            cond,
+            then_expr,
+            else_expr,
+        } => unguarded_ref(cond, nullable)
+            .or_else(|| unguarded_ref(then_expr, nullable))
+            .or_else(|| unguarded_ref(else_expr, nullable)),
+        _ => None,
+    }
+}
+
+/// Why an ordering join predicate (`<`, `<=`, `>`, `>=`) cannot compare the
+/// given operand types, if it can't. Unknown types are left to the runtime.
+fn ordering_cond_problem(left: Option<&ValType>, right: Option<&ValType>) -> Option<String> {
+    for (side, ty) in [("left", left), ("right", right)] {
+        if let Some(t) = ty
+            && !is_orderable(t)
+        {
+            return Some(format!(
+                "ordering requires numeric, time or chars operands, {side} side is {t:?}"
+            ));
+        }
+    }
+    match (left, right) {
+        (Some(l), Some(r)) if !(l == r || is_numeric(l) && is_numeric(r)) => {
+            Some(format!("cannot order {l:?} against {r:?}"))
+        }
+        _ => None,
+    }
+}
+
+fn field_ref_text(f: &FieldRef) -> String {
+    match f {
+        FieldRef::Simple(name) => name.clone(),
+        FieldRef::Qualified(q, name) => format!("{q}.{name}"),
+        FieldRef::Bracketed(q, key) => format!("{q}[\"{key}\"]"),
+    }
+}
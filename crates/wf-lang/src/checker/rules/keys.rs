@@ -9,15 +9,30 @@ pub fn check_session_gap_clause(
     rule_name: &str,
     errors: &mut Vec<CheckError>,
 ) {
-    if let WindowMode::Session(gap) = match_clause.window_mode
-        && gap.is_zero()
-    {
+    let WindowMode::Session(spec) = match_clause.window_mode else {
+        return;
+    };
+    let mut error = |message: String| {
         errors.push(CheckError {
             severity: Severity::Error,
             rule: Some(rule_name.to_string()),
             test: None,
-            message: "session(gap) gap must be > 0".to_string(),
+            message,
         });
+    };
+    if spec.gap.is_zero() {
+        error("session(gap) gap must be > 0".to_string());
+    }
+    if let Some(max) = spec.max_duration
+        && max < spec.gap
+    {
+        error(format!(
+            "session max_duration ({:?}) must be >= gap ({:?})",
+            max, spec.gap
+        ));
+    }
+    if spec.min_events == Some(0) {
+        error("session min_events must be > 0".to_string());
     }
 }
 
@@ -59,14 +59,18 @@ pub fn check_rule(rule: &RuleDecl, schemas: &[WindowSchema], errors: &mut Vec<Ch
             errors,
         );
 
+        // Joined fields are visible from here on.
+        let out_scope = base_scope.with_joined_windows(&rule.joins, schemas);
+        joins::check_optional_join_refs(rule, &rule.joins, schemas, &base_scope, errors);
+
         // Check score expression (T27)
-        score_entity::check_score(rule, &base_scope, errors);
+        score_entity::check_score(rule, &out_scope, errors);
 
         // Check entity clause (T33)
-        score_entity::check_entity(rule, &base_scope, errors);
+        score_entity::check_entity(rule, &out_scope, errors);
 
         // Check yield clause
-        yield_check::check_yield(rule, schemas, &base_scope, errors);
+        yield_check::check_yield(rule, schemas, &out_scope, errors);
     } else {
         let mut stage_outputs: Vec<WindowSchema> = Vec::new();
 
@@ -124,10 +128,13 @@ pub fn check_rule(rule: &RuleDecl, schemas: &[WindowSchema], errors: &mut Vec<Ch
             errors,
         );
 
-        // Final stage outputs (score/entity/yield) resolve against `_in`.
-        score_entity::check_score(rule, &final_scope, errors);
-        score_entity::check_entity(rule, &final_scope, errors);
-        yield_check::check_yield(rule, schemas, &final_scope, errors);
+        // Final stage outputs (score/entity/yield) resolve against `_in`
+        // and the final stage's joins.
+        let out_scope = final_scope.with_joined_windows(&rule.joins, schemas);
+        joins::check_optional_join_refs(rule, &rule.joins, schemas, &final_scope, errors);
+        score_entity::check_score(rule, &out_scope, errors);
+        score_entity::check_entity(rule, &out_scope, errors);
+        yield_check::check_yield(rule, schemas, &out_scope, errors);
     }
 
     // Check limits
@@ -135,6 +142,22 @@ pub fn check_rule(rule: &RuleDecl, schemas: &[WindowSchema], errors: &mut Vec<Ch
 
     // Check conv (L3: requires fixed window)
     conv_check::check_conv(rule, name, errors);
+
+    // T59: meta.priority must be an integer
+    if let Some(priority) = rule.meta.as_ref().and_then(|m| {
+        m.entries
+            .iter()
+            .find(|e| e.key == "priority")
+            .map(|e| e.value.as_str())
+    }) && priority.parse::<i64>().is_err()
+    {
+        errors.push(CheckError {
+            severity: Severity::Error,
+            rule: Some(name.to_string()),
+            test: None,
+            message: format!("meta priority must be an integer, got {:?}", priority),
+        });
+    }
 }
 
 fn check_stage(
@@ -265,7 +288,7 @@ fn build_pipeline_stage_output_schema(
                     .clone()
                     .unwrap_or_else(|| measure_output_name(branch.pipe.measure).to_string());
                 let field_type = match branch.pipe.measure {
-                    Measure::Avg => FieldType::Base(BaseType::Float),
+                    Measure::Avg | Measure::Rate(_) => FieldType::Base(BaseType::Float),
                     _ => FieldType::Base(BaseType::Digit),
                 };
                 push_stage_field(
@@ -329,6 +352,7 @@ fn measure_output_name(measure: Measure) -> &'static str {
         Measure::Avg => "avg",
         Measure::Min => "min",
         Measure::Max => "max",
+        Measure::Rate(_) => "rate",
     }
 }
 
@@ -338,5 +362,7 @@ fn val_type_to_field_type(v: ValType) -> Option<FieldType> {
         ValType::Array(bt) => Some(FieldType::Array(bt)),
         ValType::Bool => Some(FieldType::Base(BaseType::Bool)),
         ValType::Numeric => Some(FieldType::Base(BaseType::Digit)),
+        // Durations evaluate to a nanosecond count.
+        ValType::Duration => Some(FieldType::Base(BaseType::Digit)),
     }
 }
@@ -24,6 +24,26 @@ pub fn check_entity(rule: &RuleDecl, scope: &Scope<'_>, errors: &mut Vec<CheckEr
     let name = &rule.name;
     check_expr_type(&rule.entity.id_expr, scope, name, errors);
 
+    // T57: meta.entity_type_case must be "lower" or "preserve"
+    if let Some(case) = rule.meta.as_ref().and_then(|m| {
+        m.entries
+            .iter()
+            .find(|e| e.key == "entity_type_case")
+            .map(|e| e.value.as_str())
+    }) && case != "lower"
+        && case != "preserve"
+    {
+        errors.push(CheckError {
+            severity: Severity::Error,
+            rule: Some(name.to_string()),
+            test: None,
+            message: format!(
+                "meta entity_type_case must be \"lower\" or \"preserve\", got {:?}",
+                case
+            ),
+        });
+    }
+
     if let Some(t) = infer_type(&rule.entity.id_expr, scope)
         && !is_scalar_identity(&t)
     {
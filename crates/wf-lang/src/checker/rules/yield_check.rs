@@ -1,4 +1,6 @@
-use crate::ast::RuleDecl;
+use std::collections::HashSet;
+
+use crate::ast::{RuleDecl, YieldClause};
 use crate::schema::WindowSchema;
 
 use crate::checker::scope::{self, Scope};
@@ -12,9 +14,34 @@ pub fn check_yield(
     schemas: &[WindowSchema],
     scope: &Scope<'_>,
     errors: &mut Vec<CheckError>,
+) {
+    // Each target window may be yielded to at most once per rule
+    let mut seen = HashSet::new();
+    for yc in &rule.yields {
+        if !seen.insert(yc.target.as_str()) {
+            errors.push(CheckError {
+                severity: Severity::Error,
+                rule: Some(rule.name.clone()),
+                test: None,
+                message: format!("yield target `{}` appears more than once", yc.target),
+            });
+        }
+    }
+
+    for yc in &rule.yields {
+        check_yield_clause(rule, yc, schemas, scope, errors);
+    }
+}
+
+/// Validate one `yield` clause against its target window.
+fn check_yield_clause(
+    rule: &RuleDecl,
+    yc: &YieldClause,
+    schemas: &[WindowSchema],
+    scope: &Scope<'_>,
+    errors: &mut Vec<CheckError>,
 ) {
     let name = &rule.name;
-    let yc = &rule.yield_clause;
 
     // Y1: target window must exist
     let target_schema = schemas.iter().find(|s| s.name == yc.target);
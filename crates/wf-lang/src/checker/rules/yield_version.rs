@@ -14,23 +14,24 @@ type VersionEntry = Vec<(String, BTreeSet<String>)>;
 /// - **Warning** when a higher version *adds* new fields (consumers need to adapt)
 /// - **Warning** when a higher version *removes* fields (consumers may depend on them)
 ///
-/// Rules without an explicit `@vN` version are skipped.
+/// Yields without an explicit `@vN` version are skipped.
 pub fn check_yield_versions(file: &WflFile, errors: &mut Vec<CheckError>) {
     // Group rules by yield target, then by version.
     let mut by_target: BTreeMap<String, BTreeMap<u32, VersionEntry>> = BTreeMap::new();
 
     for rule in &file.rules {
-        let yc = &rule.yield_clause;
-        let Some(version) = yc.version else {
-            continue; // skip rules without explicit version
-        };
-        let field_names: BTreeSet<String> = yc.args.iter().map(|a| a.name.clone()).collect();
-        by_target
-            .entry(yc.target.clone())
-            .or_default()
-            .entry(version)
-            .or_default()
-            .push((rule.name.clone(), field_names));
+        for yc in &rule.yields {
+            let Some(version) = yc.version else {
+                continue; // skip yields without explicit version
+            };
+            let field_names: BTreeSet<String> = yc.args.iter().map(|a| a.name.clone()).collect();
+            by_target
+                .entry(yc.target.clone())
+                .or_default()
+                .entry(version)
+                .or_default()
+                .push((rule.name.clone(), field_names));
+        }
     }
 
     // Compare adjacent versions
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::ast::FieldRef;
+use crate::ast::{FieldRef, JoinClause};
 use crate::schema::{BaseType, FieldType, WindowSchema};
 
 use super::types::ValType;
@@ -75,6 +75,23 @@ impl<'a> Scope<'a> {
             .is_some_and(|s| s.fields.iter().any(|f| f.name == field))
     }
 
+    /// This scope plus each join target window under its own name, so that
+    /// score / entity / yield can reference joined fields as `window.field`.
+    /// Event aliases win over a join window of the same name.
+    pub fn with_joined_windows(
+        &self,
+        joins: &'a [JoinClause],
+        schemas: &'a [WindowSchema],
+    ) -> Self {
+        let mut aliases = self.aliases.clone();
+        for join in joins {
+            if let Some(ws) = schemas.iter().find(|s| s.name == join.target_window) {
+                aliases.entry(join.target_window.as_str()).or_insert(ws);
+            }
+        }
+        Scope { aliases }
+    }
+
     /// Get the field type for a field that exists in a specific alias.
     pub fn get_field_type_for_alias(&self, alias: &str, field: &str) -> Option<ValType> {
         self.aliases.get(alias).and_then(|s| {
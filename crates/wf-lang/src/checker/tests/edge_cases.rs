@@ -90,3 +90,54 @@ rule r {
         system_errors
     );
 }
+
+#[test]
+fn entity_type_case_meta_values() {
+    let schemas = [auth_events_window(), output_window()];
+    let rule = |case: &str| {
+        format!(
+            r#"
+rule r {{
+    meta {{ entity_type_case = "{case}" }}
+    events {{ e : auth_events }}
+    match<:5m> {{ on event {{ e | count >= 1; }} }} -> score(50.0)
+    entity(IP, e.sip)
+    yield out (x = e.sip)
+}}
+"#
+        )
+    };
+    assert_no_errors(&rule("preserve"), &schemas);
+    assert_no_errors(&rule("lower"), &schemas);
+    assert_has_error(
+        &rule("upper"),
+        &schemas,
+        "entity_type_case must be \"lower\" or \"preserve\"",
+    );
+}
+
+#[test]
+fn priority_meta_must_be_integer() {
+    let schemas = [auth_events_window(), output_window()];
+    let rule = |priority: &str| {
+        format!(
+            r#"
+rule r {{
+    meta {{ priority = "{priority}" }}
+    events {{ e : auth_events }}
+    match<:5m> {{ on event {{ e | count >= 1; }} }} -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}}
+"#
+        )
+    };
+    assert_no_errors(&rule("10"), &schemas);
+    assert_no_errors(&rule("-1"), &schemas);
+    assert_has_error(
+        &rule("high"),
+        &schemas,
+        "meta priority must be an integer, got \"high\"",
+    );
+    assert_has_error(&rule("1.5"), &schemas, "meta priority must be an integer");
+}
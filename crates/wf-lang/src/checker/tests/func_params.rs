@@ -324,6 +324,35 @@ rule r {
     );
 }
 
+#[test]
+fn coalesce_result_is_common_type() {
+    // digit field with a float fallback promotes to float, which no longer
+    // fits the digit output field `n`
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip, n = coalesce(e.count, 0.5))
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "yield argument `n` type mismatch: expected Base(Digit), got Base(Float)",
+    );
+
+    let input = r#"
+rule r {
+    events { e : auth_events && coalesce(e.user, "anonymous") == "admin" }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip, y = coalesce(e.user, e.action, "unknown"), n = coalesce(e.count, 0))
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), output_window()]);
+}
+
 #[test]
 fn mvsort_wrong_type() {
     let input = r#"
@@ -409,6 +438,42 @@ rule r {
     );
 }
 
+#[test]
+fn lower_upper_valid_in_guard_and_yield() {
+    let out = make_output_window(
+        "out",
+        vec![("x", bt(BaseType::Ip)), ("user", bt(BaseType::Chars))],
+    );
+    let input = r#"
+rule r {
+    events { e : auth_events && lower(user) == "admin" }
+    match<sip:5m> {
+        on event { e && upper(e.action) == "FAILED" | count >= 1; }
+    } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip, user = lower(e.user))
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), out]);
+}
+
+#[test]
+fn lower_wrong_type() {
+    let input = r#"
+rule r {
+    events { e : auth_events && lower(count) == "1" }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "lower() argument must be chars",
+    );
+}
+
 #[test]
 fn split_valid() {
     use crate::schema::FieldType;
@@ -851,3 +916,68 @@ rule r {
         "must be a string literal",
     );
 }
+
+#[test]
+fn scalar_min_max_valid() {
+    let input = r#"
+rule r {
+    events { e : auth_events && max(count, 0) > 3 }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(min(100.0, e.count * 10))
+    entity(ip, e.sip)
+    yield out (x = e.sip, n = max(e.count, 1))
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), output_window()]);
+}
+
+#[test]
+fn scalar_min_max_result_is_promoted() {
+    // digit vs float promotes to float, which no longer fits digit `n`
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip, n = min(e.count, 0.5))
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "yield argument `n` type mismatch: expected Base(Digit), got Base(Float)",
+    );
+}
+
+#[test]
+fn scalar_min_max_non_numeric_rejected() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(min(e.action, 50))
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "min() argument 1 must be numeric",
+    );
+}
+
+#[test]
+fn min_max_wrong_arity_rejected() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(max(10, 20, 30))
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "max() expects 1 argument (aggregate) or 2 numeric arguments, got 3",
+    );
+}
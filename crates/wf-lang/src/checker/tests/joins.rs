@@ -0,0 +1,156 @@
+use super::*;
+
+fn quota_window() -> WindowSchema {
+    make_window(
+        "quota",
+        vec!["quota_stream"],
+        vec![
+            ("ip", bt(BaseType::Ip)),
+            ("min_count", bt(BaseType::Digit)),
+            ("max_count", bt(BaseType::Float)),
+            ("valid_from", bt(BaseType::Time)),
+        ],
+    )
+}
+
+#[test]
+fn range_bounded_join_valid() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join quota snapshot on sip == quota.ip && count >= quota.min_count && count < quota.max_count && event_time >= quota.valid_from
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_no_errors(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+    );
+}
+
+#[test]
+fn ordering_join_on_unordered_type_rejected() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join quota snapshot on sip < quota.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+        "join condition `sip < quota.ip`: ordering requires numeric, time or chars operands, left side is Base(Ip)",
+    );
+}
+
+#[test]
+fn ordering_join_across_kinds_rejected() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join quota snapshot on sip == quota.ip && event_time <= quota.min_count
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+        "cannot order Base(Time) against Base(Digit)",
+    );
+}
+
+#[test]
+fn not_equal_join_skips_ordering_check() {
+    // `!=` keeps the `==` rules: any comparable pair is accepted.
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join quota snapshot on sip == quota.ip && dip != quota.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_no_errors(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+    );
+}
+
+#[test]
+fn joined_fields_resolve_in_score_and_yield() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(quota.max_count)
+    join quota asof on sip == quota.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip, n = quota.min_count)
+}
+"#;
+    assert_no_errors(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+    );
+}
+
+#[test]
+fn optional_snapshot_join_rejected() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join quota snapshot optional on sip == quota.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+        "join `quota`: `optional` only applies to asof joins",
+    );
+}
+
+#[test]
+fn optional_join_field_in_score_needs_coalesce() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(40.0 + quota.max_count)
+    join quota asof optional on sip == quota.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+        "score uses `quota.max_count` from optional join `quota`",
+    );
+}
+
+#[test]
+fn optional_join_field_guarded_by_coalesce() {
+    // Yield fields may stay unguarded: a null field is simply omitted.
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(40.0 + coalesce(quota.max_count, 0.0))
+    join quota asof optional on sip == quota.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip, n = quota.min_count)
+}
+"#;
+    assert_no_errors(
+        input,
+        &[auth_events_window(), quota_window(), output_window()],
+    );
+}
@@ -75,3 +75,34 @@ rule r {
         "field `nonexistent` not found",
     );
 }
+
+#[test]
+fn session_caps_validated() {
+    let rule = |params: &str| {
+        format!(
+            r#"
+rule r {{
+    events {{ fail : auth_events }}
+    match<sip:session({params})> {{
+        on event {{ fail | count >= 1; }}
+        on close {{ fail | count >= 1; }}
+    }} -> score(50.0)
+    entity(ip, fail.sip)
+    yield out (x = fail.sip)
+}}
+"#
+        )
+    };
+    let schemas = [auth_events_window(), output_window()];
+    assert_no_errors(&rule("5m, max_duration=1h, min_events=2"), &schemas);
+    assert_has_error(
+        &rule("5m, max_duration=1m"),
+        &schemas,
+        "session max_duration",
+    );
+    assert_has_error(
+        &rule("5m, min_events=0"),
+        &schemas,
+        "session min_events must be > 0",
+    );
+}
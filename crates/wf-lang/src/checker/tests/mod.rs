@@ -2,6 +2,7 @@ mod contracts;
 mod conv;
 mod edge_cases;
 mod func_params;
+mod joins;
 mod keys;
 mod labels;
 mod limits;
@@ -286,3 +286,37 @@ rule bad_hostname_check {
         "error should be attributed to the rule"
     );
 }
+
+#[test]
+fn rate_accepts_float_threshold() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> {
+        on event { e | rate(1m) >= 1.5; }
+    } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), output_window()]);
+}
+
+#[test]
+fn rate_rejects_zero_unit() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> {
+        on event { e | rate(0s) >= 1; }
+    } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "rate unit must be greater than zero",
+    );
+}
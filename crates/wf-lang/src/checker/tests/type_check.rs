@@ -128,3 +128,238 @@ rule r {
         "requires bool operands",
     );
 }
+
+#[test]
+fn logical_not_on_bool_expr() {
+    let input = r#"
+rule r {
+    events { e : auth_events && not (action == "failed" && sip == dip) }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), output_window()]);
+}
+
+#[test]
+fn logical_not_on_non_bool() {
+    let input = r#"
+rule r {
+    events { e : auth_events && !action }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "logical `not` requires bool operand",
+    );
+}
+
+#[test]
+fn string_concat_literals() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip, y = "a" + "b")
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), output_window()]);
+}
+
+#[test]
+fn string_concat_ip_and_port() {
+    // (sip + ":") is chars, so `+ dport` renders the port as text
+    let input = r#"
+rule r {
+    events { e : fw_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip + ":" + e.dport)
+    yield out (x = e.sip, y = e.sip + ":" + e.dport)
+}
+"#;
+    assert_no_errors(input, &[fw_events_window(), output_window()]);
+}
+
+#[test]
+fn string_concat_rejects_time_operand() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip, y = "at " + e.event_time)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "string concatenation `+` cannot take a Base(Time) operand on the right side",
+    );
+}
+
+#[test]
+fn add_without_chars_operand_stays_numeric() {
+    // ip + digit is neither arithmetic nor concatenation
+    let input = r#"
+rule r {
+    events { e : fw_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip, n = e.sip + e.dport)
+}
+"#;
+    assert_has_error(
+        input,
+        &[fw_events_window(), output_window()],
+        "arithmetic `+` requires numeric operands, left side is Base(Ip)",
+    );
+}
+
+/// auth_events with an additional `resp_time: time` field.
+fn timed_auth_window() -> WindowSchema {
+    let mut win = auth_events_window();
+    win.fields.push(FieldDef {
+        name: "resp_time".to_string(),
+        field_type: bt(BaseType::Time),
+    });
+    win
+}
+
+#[test]
+fn time_difference_compares_with_duration() {
+    let rule = |filter: &str| {
+        format!(
+            r#"
+rule r {{
+    events {{ e : auth_events && {filter} }}
+    match<:5m> {{ on event {{ e | count >= 1; }} }} -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}}
+"#
+        )
+    };
+    let schemas = [timed_auth_window(), output_window()];
+    assert_no_errors(&rule("resp_time - event_time > 5s"), &schemas);
+    assert_no_errors(&rule("resp_time > event_time + 1m"), &schemas);
+    assert_no_errors(&rule("resp_time - event_time <= 2m - 30s"), &schemas);
+    assert_has_error(
+        &rule("resp_time - event_time > 5"),
+        &schemas,
+        "ordering `>` between incompatible types Duration and Base(Digit)",
+    );
+    assert_has_error(
+        &rule("resp_time + event_time > 5s"),
+        &schemas,
+        "time arithmetic `+` supports time - time, time ± duration and duration ± duration, got Base(Time) and Base(Time)",
+    );
+}
+
+/// auth_events with an additional `tags: array<chars>` field.
+fn tagged_auth_window() -> WindowSchema {
+    let mut win = auth_events_window();
+    win.fields.push(FieldDef {
+        name: "tags".to_string(),
+        field_type: FieldType::Array(BaseType::Chars),
+    });
+    win
+}
+
+#[test]
+fn contains_on_chars_array() {
+    let input = r#"
+rule r {
+    events { e : auth_events && tags contains "admin" && tags contains all ("a", action) }
+    match<:5m> { on event { e && tags contains any ("root", "ops") | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_no_errors(input, &[tagged_auth_window(), output_window()]);
+}
+
+#[test]
+fn contains_rejects_non_array_operand() {
+    let input = r#"
+rule r {
+    events { e : auth_events && action contains "x" }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[tagged_auth_window(), output_window()],
+        "`contains` requires an array operand, got Base(Chars)",
+    );
+}
+
+#[test]
+fn contains_rejects_element_type_mismatch() {
+    let input = r#"
+rule r {
+    events { e : auth_events && tags contains any ("admin", 42) }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[tagged_auth_window(), output_window()],
+        "`contains` value must match the array element type Base(Chars), got Base(Digit)",
+    );
+}
+
+#[test]
+fn window_count_and_sum_in_guard() {
+    let input = r#"
+rule r {
+    events { e : auth_events && watchlist.count() > 0 && watchlist.sum("score") >= 10.5 }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), output_window()]);
+}
+
+#[test]
+fn window_count_and_sum_argument_checks() {
+    let rule = |guard: &str| {
+        format!(
+            r#"
+rule r {{
+    events {{ e : auth_events && {guard} }}
+    match<:5m> {{ on event {{ e | count >= 1; }} }} -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}}
+"#
+        )
+    };
+    let schemas = [auth_events_window(), output_window()];
+    assert_has_error(
+        &rule("watchlist.count(sip) > 0"),
+        &schemas,
+        "window.count() takes no arguments",
+    );
+    assert_has_error(
+        &rule("watchlist.sum(score) > 0"),
+        &schemas,
+        "window.sum() expects one string literal argument",
+    );
+    // The result is numeric, so comparing it with a string is a type error.
+    assert_has_error(
+        &rule("watchlist.count() == \"x\""),
+        &schemas,
+        "comparison between incompatible types Base(Digit) and Base(Chars)",
+    );
+}
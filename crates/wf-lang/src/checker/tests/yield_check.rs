@@ -79,3 +79,56 @@ rule r {
         "type mismatch",
     );
 }
+
+#[test]
+fn yield_multiple_targets_ok() {
+    let feed = make_output_window("feed", vec![("n", bt(BaseType::Digit))]);
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+    yield feed (n = count(e))
+}
+"#;
+    assert_no_errors(input, &[auth_events_window(), output_window(), feed]);
+}
+
+#[test]
+fn yield_each_target_checked_independently() {
+    // `x` exists in `out` but not in `feed`.
+    let feed = make_output_window("feed", vec![("n", bt(BaseType::Digit))]);
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+    yield feed (x = e.sip)
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window(), feed],
+        "yield argument `x` is not a field in target window `feed`",
+    );
+}
+
+#[test]
+fn yield_duplicate_target() {
+    let input = r#"
+rule r {
+    events { e : auth_events }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+    yield out (n = count(e))
+}
+"#;
+    assert_has_error(
+        input,
+        &[auth_events_window(), output_window()],
+        "yield target `out` appears more than once",
+    );
+}
@@ -1,11 +1,14 @@
 use crate::ast::{BinOp, Expr};
 
 use super::infer::infer_type;
-use super::{ValType, compatible, is_numeric, op_symbol};
+use super::{
+    ValType, compatible, is_concat_operand, is_numeric, is_string_concat, is_temporal, op_symbol,
+    time_arith,
+};
 use crate::checker::scope::Scope;
 use crate::checker::{CheckError, Severity};
 
-use super::check_funcs::check_func_call;
+use super::check_funcs::{check_func_call, check_window_agg, is_window_agg};
 
 /// Type-check an expression, emitting errors into `errors`.
 pub fn check_expr_type(
@@ -92,6 +95,27 @@ fn check_expr_type_inner(
                         });
                     }
                 }
+                BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge
+                    if lt.as_ref().is_some_and(is_temporal)
+                        || rt.as_ref().is_some_and(is_temporal) =>
+                {
+                    // T60: times order against times, durations against durations
+                    if let (Some(l), Some(r)) = (&lt, &rt)
+                        && l != r
+                    {
+                        errors.push(CheckError {
+                            severity: Severity::Error,
+                            rule: Some(rule_name.to_string()),
+                            test: None,
+                            message: format!(
+                                "ordering `{}` between incompatible types {:?} and {:?}",
+                                op_symbol(*op),
+                                l,
+                                r
+                            ),
+                        });
+                    }
+                }
                 BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge => {
                     // T8: both sides must be numeric
                     if let Some(ref t) = lt
@@ -123,6 +147,45 @@ fn check_expr_type_inner(
                         });
                     }
                 }
+                BinOp::Add if is_string_concat(lt.as_ref(), rt.as_ref()) => {
+                    // String concatenation: the other side is rendered as text
+                    for (side, t) in [("left", &lt), ("right", &rt)] {
+                        if let Some(t) = t
+                            && !is_concat_operand(t)
+                        {
+                            errors.push(CheckError {
+                                severity: Severity::Error,
+                                rule: Some(rule_name.to_string()),
+                                test: None,
+                                message: format!(
+                                    "string concatenation `+` cannot take a {:?} operand on the {} side",
+                                    t, side
+                                ),
+                            });
+                        }
+                    }
+                }
+                BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod
+                    if lt.as_ref().is_some_and(is_temporal)
+                        || rt.as_ref().is_some_and(is_temporal) =>
+                {
+                    // T60: time - time, time ± duration, duration ± duration
+                    if let (Some(l), Some(r)) = (&lt, &rt)
+                        && time_arith(*op, l, r).is_none()
+                    {
+                        errors.push(CheckError {
+                            severity: Severity::Error,
+                            rule: Some(rule_name.to_string()),
+                            test: None,
+                            message: format!(
+                                "time arithmetic `{}` supports time - time, time ± duration and duration ± duration, got {:?} and {:?}",
+                                op_symbol(*op),
+                                l,
+                                r
+                            ),
+                        });
+                    }
+                }
                 BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod => {
                     if let Some(ref t) = lt
                         && !is_numeric(t)
@@ -168,11 +231,32 @@ fn check_expr_type_inner(
                 });
             }
         }
-        Expr::FuncCall { name, args, .. } => {
+        Expr::Not(inner) => {
+            check_expr_type_inner(inner, scope, rule_name, allow_l3_funcs, errors);
+            if let Some(ref t) = infer_type(inner, scope)
+                && !compatible(t, &ValType::Bool)
+            {
+                errors.push(CheckError {
+                    severity: Severity::Error,
+                    rule: Some(rule_name.to_string()),
+                    test: None,
+                    message: format!("logical `not` requires bool operand, got {:?}", t),
+                });
+            }
+        }
+        Expr::FuncCall {
+            qualifier,
+            name,
+            args,
+        } => {
             for arg in args {
                 check_expr_type_inner(arg, scope, rule_name, allow_l3_funcs, errors);
             }
-            check_func_call(name, args, scope, rule_name, allow_l3_funcs, errors);
+            if qualifier.is_some() && is_window_agg(name) {
+                check_window_agg(name, args, rule_name, errors);
+            } else {
+                check_func_call(name, args, scope, rule_name, allow_l3_funcs, errors);
+            }
         }
         Expr::InList {
             expr: inner, list, ..
@@ -182,6 +266,43 @@ fn check_expr_type_inner(
                 check_expr_type_inner(item, scope, rule_name, allow_l3_funcs, errors);
             }
         }
+        Expr::Contains {
+            expr: inner,
+            values,
+            ..
+        } => {
+            check_expr_type_inner(inner, scope, rule_name, allow_l3_funcs, errors);
+            for value in values {
+                check_expr_type_inner(value, scope, rule_name, allow_l3_funcs, errors);
+            }
+            match infer_type(inner, scope) {
+                Some(ValType::Array(elem)) => {
+                    let expected = ValType::Base(elem);
+                    for value in values {
+                        if let Some(t) = infer_type(value, scope)
+                            && !compatible(&expected, &t)
+                        {
+                            errors.push(CheckError {
+                                severity: Severity::Error,
+                                rule: Some(rule_name.to_string()),
+                                test: None,
+                                message: format!(
+                                    "`contains` value must match the array element type {:?}, got {:?}",
+                                    expected, t
+                                ),
+                            });
+                        }
+                    }
+                }
+                Some(t) => errors.push(CheckError {
+                    severity: Severity::Error,
+                    rule: Some(rule_name.to_string()),
+                    test: None,
+                    message: format!("`contains` requires an array operand, got {:?}", t),
+                }),
+                None => {}
+            }
+        }
         Expr::Field(fref) => {
             // Just verify the field resolves.
             if let Err(msg) = scope.resolve_field_ref(fref) {
@@ -193,7 +314,7 @@ fn check_expr_type_inner(
                 });
             }
         }
-        Expr::Number(_) | Expr::StringLit(_) | Expr::Bool(_) => {}
+        Expr::Number(_) | Expr::StringLit(_) | Expr::Bool(_) | Expr::Duration(_) => {}
         Expr::IfThenElse {
             cond,
             then_expr,
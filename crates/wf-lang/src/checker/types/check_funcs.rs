@@ -6,6 +6,33 @@ use super::{ValType, compatible, is_numeric, is_orderable};
 use crate::checker::scope::Scope;
 use crate::checker::{CheckError, Severity};
 
+/// Aggregate lookups that can be qualified with a window name:
+/// `win.count()` and `win.sum("field")`.
+pub(super) fn is_window_agg(name: &str) -> bool {
+    matches!(name, "count" | "sum")
+}
+
+/// T58: `win.count()` takes no arguments; `win.sum("f")` takes one STRING
+/// literal naming the target window's field.
+pub(super) fn check_window_agg(
+    name: &str,
+    args: &[Expr],
+    rule_name: &str,
+    errors: &mut Vec<CheckError>,
+) {
+    let message = match (name, args) {
+        ("count", []) | ("sum", [Expr::StringLit(_)]) => return,
+        ("count", _) => "window.count() takes no arguments".to_string(),
+        _ => "window.sum() expects one string literal argument (field name)".to_string(),
+    };
+    errors.push(CheckError {
+        severity: Severity::Error,
+        rule: Some(rule_name.to_string()),
+        test: None,
+        message,
+    });
+}
+
 pub fn check_func_call(
     name: &str,
     args: &[Expr],
@@ -61,7 +88,39 @@ pub fn check_func_call(
                 });
             }
         }
+        "min" | "max" if args.len() == 2 => {
+            // Scalar form: min(a, b) / max(a, b) over two numbers.
+            for (i, arg) in args.iter().enumerate() {
+                if let Some(t) = infer_type(arg, scope)
+                    && !is_numeric(&t)
+                {
+                    errors.push(CheckError {
+                        severity: Severity::Error,
+                        rule: Some(rule_name.to_string()),
+                        test: None,
+                        message: format!(
+                            "{}() argument {} must be numeric, got {:?}",
+                            name,
+                            i + 1,
+                            t
+                        ),
+                    });
+                }
+            }
+        }
         "min" | "max" => {
+            if args.len() != 1 {
+                errors.push(CheckError {
+                    severity: Severity::Error,
+                    rule: Some(rule_name.to_string()),
+                    test: None,
+                    message: format!(
+                        "{}() expects 1 argument (aggregate) or 2 numeric arguments, got {}",
+                        name,
+                        args.len()
+                    ),
+                });
+            }
             // T2: field must be orderable
             if let Some(arg) = args.first()
                 && let Some(t) = infer_type(arg, scope)
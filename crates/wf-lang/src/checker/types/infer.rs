@@ -1,7 +1,8 @@
 use crate::ast::{BinOp, Expr};
 use crate::schema::BaseType;
 
-use super::{ValType, is_numeric, numeric_promote};
+use super::check_funcs::is_window_agg;
+use super::{ValType, is_numeric, is_string_concat, numeric_promote, time_arith};
 use crate::checker::scope::Scope;
 
 /// Infer the type of an expression within the given scope.
@@ -17,14 +18,24 @@ pub fn infer_type(expr: &Expr, scope: &Scope<'_>) -> Option<ValType> {
         }
         Expr::StringLit(_) => Some(ValType::Base(BaseType::Chars)),
         Expr::Bool(_) => Some(ValType::Bool),
+        Expr::Duration(_) => Some(ValType::Duration),
         Expr::Field(fref) => scope.resolve_field_ref(fref).ok().flatten(),
         Expr::BinOp { op, left, right } => infer_binop(*op, left, right, scope),
         Expr::Neg(inner) => {
             let t = infer_type(inner, scope)?;
             if is_numeric(&t) { Some(t) } else { None }
         }
+        Expr::Not(_) => Some(ValType::Bool),
+        Expr::FuncCall {
+            qualifier: Some(_),
+            name,
+            ..
+        } if is_window_agg(name) => match name.as_str() {
+            "count" => Some(ValType::Base(BaseType::Digit)),
+            _ => Some(ValType::Base(BaseType::Float)),
+        },
         Expr::FuncCall { name, args, .. } => infer_func_call(name, args, scope),
-        Expr::InList { .. } => Some(ValType::Bool),
+        Expr::InList { .. } | Expr::Contains { .. } => Some(ValType::Bool),
         Expr::IfThenElse { then_expr, .. } => infer_type(then_expr, scope),
     }
 }
@@ -35,16 +46,32 @@ fn infer_binop(op: BinOp, left: &Expr, right: &Expr, scope: &Scope<'_>) -> Optio
         BinOp::Eq | BinOp::Ne => Some(ValType::Bool),
         BinOp::Lt | BinOp::Gt | BinOp::Le | BinOp::Ge => Some(ValType::Bool),
         BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod => {
-            let lt = infer_type(left, scope)?;
-            let rt = infer_type(right, scope)?;
-            numeric_promote(&lt, &rt)
+            let lt = infer_type(left, scope);
+            let rt = infer_type(right, scope);
+            if op == BinOp::Add && is_string_concat(lt.as_ref(), rt.as_ref()) {
+                return Some(ValType::Base(BaseType::Chars));
+            }
+            let (lt, rt) = (lt?, rt?);
+            time_arith(op, &lt, &rt).or_else(|| numeric_promote(&lt, &rt))
         }
     }
 }
 
+/// Common type of the `coalesce` arguments: numeric arguments promote
+/// (`coalesce(digit_field, 0.5)` is float), otherwise the first argument
+/// with a known type wins; mismatches are reported by the checker.
+fn infer_coalesce_type(args: &[Expr], scope: &Scope<'_>) -> Option<ValType> {
+    args.iter()
+        .filter_map(|a| infer_type(a, scope))
+        .reduce(|acc, t| numeric_promote(&acc, &t).unwrap_or(acc))
+}
+
 fn infer_func_call(name: &str, args: &[Expr], scope: &Scope<'_>) -> Option<ValType> {
     match name {
         "count" => Some(ValType::Base(BaseType::Digit)),
+        "min" | "max" if args.len() == 2 => {
+            numeric_promote(&infer_type(&args[0], scope)?, &infer_type(&args[1], scope)?)
+        }
         "sum" | "min" | "max" => {
             // Result type follows the argument type.
             args.first().and_then(|a| infer_type(a, scope))
@@ -85,7 +112,7 @@ fn infer_func_call(name: &str, args: &[Expr], scope: &Scope<'_>) -> Option<ValTy
             Some(ValType::Base(BaseType::Chars))
         }
         "indexof" => Some(ValType::Base(BaseType::Digit)),
-        "coalesce" => args.first().and_then(|a| infer_type(a, scope)),
+        "coalesce" => infer_coalesce_type(args, scope),
         "len" => Some(ValType::Base(BaseType::Digit)),
         "time_bucket" => Some(ValType::Base(BaseType::Time)),
         "mvsort" | "mvreverse" => args.first().and_then(|a| match infer_type(a, scope) {
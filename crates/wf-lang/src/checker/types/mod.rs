@@ -23,6 +23,8 @@ pub enum ValType {
     Numeric,
     /// Boolean value.
     Bool,
+    /// Time span: a duration literal or the difference of two `time` values.
+    Duration,
 }
 
 // ---------------------------------------------------------------------------
@@ -39,6 +41,7 @@ pub fn compatible(expected: &ValType, actual: &ValType) -> bool {
         | (ValType::Numeric, ValType::Base(BaseType::Float)) => true,
         (ValType::Numeric, ValType::Numeric) => true,
         (ValType::Bool, ValType::Bool) => true,
+        (ValType::Duration, ValType::Duration) => true,
         (ValType::Bool, ValType::Base(BaseType::Bool))
         | (ValType::Base(BaseType::Bool), ValType::Bool) => true,
         _ => false,
@@ -75,6 +78,40 @@ pub fn is_scalar_identity(t: &ValType) -> bool {
     )
 }
 
+/// `+` concatenates strings when either operand is `chars`.
+pub fn is_string_concat(a: Option<&ValType>, b: Option<&ValType>) -> bool {
+    let chars = ValType::Base(BaseType::Chars);
+    a == Some(&chars) || b == Some(&chars)
+}
+
+/// Whether a type may appear in a string concatenation. Numbers are
+/// rendered as text; `time`, `bool` and arrays are rejected.
+pub fn is_concat_operand(t: &ValType) -> bool {
+    is_scalar_identity(t) || *t == ValType::Base(BaseType::Float)
+}
+
+/// Whether a type takes part in time arithmetic (`time` or duration).
+pub fn is_temporal(t: &ValType) -> bool {
+    matches!(t, ValType::Base(BaseType::Time) | ValType::Duration)
+}
+
+/// Result of `+` / `-` over time values: `time - time` is a duration,
+/// `time ± duration` a time, and `duration ± duration` a duration.
+pub fn time_arith(op: crate::ast::BinOp, a: &ValType, b: &ValType) -> Option<ValType> {
+    use crate::ast::BinOp;
+    match (op, a, b) {
+        (BinOp::Sub, ValType::Base(BaseType::Time), ValType::Base(BaseType::Time)) => {
+            Some(ValType::Duration)
+        }
+        (BinOp::Add | BinOp::Sub, ValType::Base(BaseType::Time), ValType::Duration)
+        | (BinOp::Add, ValType::Duration, ValType::Base(BaseType::Time)) => {
+            Some(ValType::Base(BaseType::Time))
+        }
+        (BinOp::Add | BinOp::Sub, ValType::Duration, ValType::Duration) => Some(ValType::Duration),
+        _ => None,
+    }
+}
+
 /// Numeric promotion: if both sides are numeric, compute the result type.
 pub fn numeric_promote(a: &ValType, b: &ValType) -> Option<ValType> {
     if !is_numeric(a) || !is_numeric(b) {
@@ -46,19 +46,30 @@ pub fn check_pipe_chain(
 
     // Check measure
     match branch.pipe.measure {
-        Measure::Count => {
+        Measure::Count | Measure::Rate(_) => {
             // T4: count operates on a set level. If there's a field but no distinct, it's an error.
             if has_field && !branch.pipe.transforms.contains(&Transform::Distinct) {
+                let name = measure_name(branch.pipe.measure);
                 errors.push(CheckError {
                     severity: Severity::Error,
                     rule: Some(rule_name.to_string()),
                     test: None,
                     message: format!(
-                        "count operates on sets; use `distinct | count` for column `{}`",
+                        "{name} operates on sets; use `distinct | {name}` for column `{}`",
                         field_selector_name(branch.field.as_ref().unwrap())
                     ),
                 });
             }
+            if let Measure::Rate(unit) = branch.pipe.measure
+                && unit.is_zero()
+            {
+                errors.push(CheckError {
+                    severity: Severity::Error,
+                    rule: Some(rule_name.to_string()),
+                    test: None,
+                    message: "rate unit must be greater than zero".to_string(),
+                });
+            }
         }
         Measure::Sum | Measure::Avg => {
             // T1: field must be numeric
@@ -161,6 +172,7 @@ fn measure_name(m: Measure) -> &'static str {
         Measure::Avg => "avg",
         Measure::Min => "min",
         Measure::Max => "max",
+        Measure::Rate(_) => "rate",
     }
 }
 
@@ -169,7 +181,7 @@ fn measure_result_type(measure: Measure, field_val_type: &Option<ValType>) -> Op
     match measure {
         Measure::Count => Some(ValType::Base(BaseType::Digit)),
         Measure::Sum => field_val_type.clone(),
-        Measure::Avg => Some(ValType::Base(BaseType::Float)),
+        Measure::Avg | Measure::Rate(_) => Some(ValType::Base(BaseType::Float)),
         Measure::Min | Measure::Max => field_val_type.clone(),
     }
 }
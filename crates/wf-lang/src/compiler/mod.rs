@@ -1,14 +1,14 @@
 use std::time::Duration;
 
 use crate::ast::{
-    CloseMode, EntityClause, EntityTypeVal, EventsBlock, FieldRef, MatchClause, Measure, RuleDecl,
+    CloseMode, EntityTypeVal, EventsBlock, Expr, FieldRef, MatchClause, Measure, RuleDecl,
     ScoreExpr, WflFile, WindowMode, YieldClause,
 };
-use crate::checker::check_wfl;
+use crate::checker::{CheckError, Severity, check_wfl};
 use crate::plan::{
-    AggPlan, BindPlan, BranchPlan, ConvChainPlan, ConvOpPlan, ConvPlan, EntityPlan, ExceedAction,
-    JoinCondPlan, JoinPlan, KeyMapPlan, LimitsPlan, MatchPlan, PatternOriginPlan, RateSpec,
-    RulePlan, ScorePlan, SortKeyPlan, StepPlan, WindowSpec, YieldField, YieldPlan,
+    AggPlan, BindPlan, BranchPlan, CapturePlan, ConvChainPlan, ConvOpPlan, ConvPlan, EntityPlan,
+    ExceedAction, JoinCondPlan, JoinPlan, KeyMapPlan, LimitsPlan, MatchPlan, PatternOriginPlan,
+    RateSpec, RulePlan, ScorePlan, SortKeyPlan, StepPlan, WindowSpec, YieldField, YieldPlan,
 };
 use crate::schema::WindowSchema;
 
@@ -23,22 +23,69 @@ mod tests;
 /// call `check_wfl` separately.
 ///
 /// Contracts, use declarations, and meta blocks are stripped — only rule
-/// logic is compiled.
+/// logic is compiled. Warnings are dropped; use
+/// [`compile_wfl_with_diagnostics`] to see them.
 pub fn compile_wfl(file: &WflFile, schemas: &[WindowSchema]) -> anyhow::Result<Vec<RulePlan>> {
-    let errors = check_wfl(file, schemas);
-    let hard_errors: Vec<_> = errors
-        .iter()
-        .filter(|e| e.severity == crate::checker::Severity::Error)
-        .collect();
-    if !hard_errors.is_empty() {
-        let msgs: Vec<String> = hard_errors.iter().map(|e| e.to_string()).collect();
-        anyhow::bail!("semantic errors:\n{}", msgs.join("\n"));
+    compile_wfl_with_diagnostics(file, schemas)?.into_plans()
+}
+
+/// Plans compiled from a WFL file together with every semantic diagnostic,
+/// warnings included.
+#[derive(Debug)]
+pub struct CompileOutput {
+    /// Empty when `diagnostics` contains an error.
+    pub plans: Vec<RulePlan>,
+    pub diagnostics: Vec<CheckError>,
+}
+
+impl CompileOutput {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &CheckError> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+
+    /// The plans, or the error [`compile_wfl`] reports when any diagnostic
+    /// is an error.
+    pub fn into_plans(self) -> anyhow::Result<Vec<RulePlan>> {
+        if self.has_errors() {
+            let msgs: Vec<String> = self
+                .diagnostics
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .map(|d| d.to_string())
+                .collect();
+            anyhow::bail!("semantic errors:\n{}", msgs.join("\n"));
+        }
+        Ok(self.plans)
+    }
+}
+
+/// Like [`compile_wfl`], but semantic errors do not fail the call: they are
+/// returned in [`CompileOutput::diagnostics`] alongside any warnings, and no
+/// plans are compiled.
+pub fn compile_wfl_with_diagnostics(
+    file: &WflFile,
+    schemas: &[WindowSchema],
+) -> anyhow::Result<CompileOutput> {
+    let diagnostics = check_wfl(file, schemas);
+    let mut output = CompileOutput {
+        plans: Vec::new(),
+        diagnostics,
+    };
+    if output.has_errors() {
+        return Ok(output);
     }
-    let mut plans = Vec::new();
     for rule in &file.rules {
-        plans.extend(compile_rule(rule)?);
+        output.plans.extend(compile_rule(rule)?);
     }
-    Ok(plans)
+    Ok(output)
 }
 
 fn compile_rule(rule: &RuleDecl) -> anyhow::Result<Vec<RulePlan>> {
@@ -49,13 +96,15 @@ fn compile_rule(rule: &RuleDecl) -> anyhow::Result<Vec<RulePlan>> {
 }
 
 fn compile_regular_rule(rule: &RuleDecl) -> RulePlan {
+    let mut match_plan = compile_match(&rule.match_clause, false);
+    match_plan.captures = compile_captures(rule);
     RulePlan {
         name: rule.name.clone(),
         binds: compile_binds(&rule.events),
-        match_plan: compile_match(&rule.match_clause, false),
+        match_plan,
         joins: compile_joins(&rule.joins),
-        entity_plan: compile_entity(&rule.entity),
-        yield_plan: compile_yield(&rule.yield_clause),
+        entity_plan: compile_entity(rule),
+        yield_plans: compile_yields(&rule.yields),
         score_plan: compile_score(&rule.score),
         pattern_origin: rule.pattern_origin.as_ref().map(|po| PatternOriginPlan {
             pattern_name: po.pattern_name.clone(),
@@ -63,6 +112,7 @@ fn compile_regular_rule(rule: &RuleDecl) -> RulePlan {
         }),
         conv_plan: compile_conv(&rule.conv),
         limits_plan: compile_limits(&rule.limits),
+        priority: compile_priority(rule),
     }
 }
 
@@ -97,16 +147,22 @@ fn compile_pipeline_rule(rule: &RuleDecl) -> Vec<RulePlan> {
             }]
         };
 
-        let match_plan = compile_match(match_clause, !is_final);
+        let mut match_plan = compile_match(match_clause, !is_final);
+        if is_final {
+            match_plan.captures = compile_captures(rule);
+        }
         let entity_plan = if is_final {
-            compile_entity(&rule.entity)
+            compile_entity(rule)
         } else {
             compile_pipeline_entity(&match_plan.keys)
         };
-        let yield_plan = if is_final {
-            compile_yield(&rule.yield_clause)
+        let yield_plans = if is_final {
+            compile_yields(&rule.yields)
         } else {
-            compile_pipeline_stage_yield(match_clause, pipeline_window_name(&rule.name, idx + 1))
+            vec![compile_pipeline_stage_yield(
+                match_clause,
+                pipeline_window_name(&rule.name, idx + 1),
+            )]
         };
         let score_plan = if is_final {
             compile_score(&rule.score)
@@ -122,7 +178,7 @@ fn compile_pipeline_rule(rule: &RuleDecl) -> Vec<RulePlan> {
             match_plan,
             joins: compile_joins(joins),
             entity_plan,
-            yield_plan,
+            yield_plans,
             score_plan,
             pattern_origin: if is_final {
                 rule.pattern_origin.as_ref().map(|po| PatternOriginPlan {
@@ -142,6 +198,7 @@ fn compile_pipeline_rule(rule: &RuleDecl) -> Vec<RulePlan> {
             } else {
                 None
             },
+            priority: if is_final { compile_priority(rule) } else { 0 },
         });
     }
 
@@ -220,7 +277,7 @@ fn compile_match(mc: &MatchClause, inject_implicit_stage_labels: bool) -> MatchP
         window_spec: match mc.window_mode {
             WindowMode::Sliding => WindowSpec::Sliding(mc.duration),
             WindowMode::Fixed => WindowSpec::Fixed(mc.duration),
-            WindowMode::Session(gap) => WindowSpec::Session(gap),
+            WindowMode::Session(spec) => WindowSpec::Session(spec),
         },
         event_steps: mc
             .on_event
@@ -242,6 +299,78 @@ fn compile_match(mc: &MatchClause, inject_implicit_stage_labels: bool) -> MatchP
             .as_ref()
             .map(|cb| cb.mode)
             .unwrap_or(CloseMode::Or),
+        max_out_of_orderness: Duration::ZERO,
+        captures: Vec::new(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Captures
+// ---------------------------------------------------------------------------
+
+/// Register every `first(alias.field)` / `last(alias.field)` used by the
+/// rule's entity, score and yield expressions, so instances can record the
+/// values as events arrive.
+fn compile_captures(rule: &RuleDecl) -> Vec<CapturePlan> {
+    let mut out = Vec::new();
+    collect_captures(&rule.entity.id_expr, &mut out);
+    collect_captures(&rule.score.expr, &mut out);
+    for y in &rule.yields {
+        for arg in &y.args {
+            collect_captures(&arg.value, &mut out);
+        }
+    }
+    out
+}
+
+fn collect_captures(expr: &Expr, out: &mut Vec<CapturePlan>) {
+    match expr {
+        Expr::FuncCall {
+            qualifier: None,
+            name,
+            args,
+        } if name == "first" || name == "last" => {
+            if let [
+                Expr::Field(FieldRef::Qualified(alias, field) | FieldRef::Bracketed(alias, field)),
+            ] = args.as_slice()
+            {
+                let cap = CapturePlan {
+                    alias: alias.clone(),
+                    field: field.clone(),
+                };
+                if !out.contains(&cap) {
+                    out.push(cap);
+                }
+            }
+        }
+        Expr::FuncCall { args, .. } => args.iter().for_each(|a| collect_captures(a, out)),
+        Expr::BinOp { left, right, .. } => {
+            collect_captures(left, out);
+            collect_captures(right, out);
+        }
+        Expr::Neg(inner) | Expr::Not(inner) => collect_captures(inner, out),
+        Expr::InList { expr, list, .. } => {
+            collect_captures(expr, out);
+            list.iter().for_each(|e| collect_captures(e, out));
+        }
+        Expr::Contains { expr, values, .. } => {
+            collect_captures(expr, out);
+            values.iter().for_each(|e| collect_captures(e, out));
+        }
+        Expr::IfThenElse {
+            cond,
+            then_expr,
+            else_expr,
+        } => {
+            collect_captures(cond, out);
+            collect_captures(then_expr, out);
+            collect_captures(else_expr, out);
+        }
+        Expr::Number(_)
+        | Expr::StringLit(_)
+        | Expr::Bool(_)
+        | Expr::Duration(_)
+        | Expr::Field(_) => {}
     }
 }
 
@@ -283,16 +412,41 @@ fn compile_branch(
 // Entity
 // ---------------------------------------------------------------------------
 
-fn compile_entity(entity: &EntityClause) -> EntityPlan {
+/// Entity types are lowercased unless the rule opts out with
+/// `meta { entity_type_case = "preserve" }`.
+fn compile_entity(rule: &RuleDecl) -> EntityPlan {
+    let entity = &rule.entity;
     let raw = match &entity.entity_type {
         EntityTypeVal::Ident(s) | EntityTypeVal::StringLit(s) => s.clone(),
     };
+    let preserve = rule.meta.as_ref().is_some_and(|m| {
+        m.entries
+            .iter()
+            .any(|e| e.key == "entity_type_case" && e.value == "preserve")
+    });
     EntityPlan {
-        entity_type: raw.to_ascii_lowercase(),
+        entity_type: if preserve {
+            raw
+        } else {
+            raw.to_ascii_lowercase()
+        },
         entity_id_expr: entity.id_expr.clone(),
     }
 }
 
+// ---------------------------------------------------------------------------
+// Priority
+// ---------------------------------------------------------------------------
+
+/// `meta { priority = "N" }`; the checker (T59) rejects non-integers.
+fn compile_priority(rule: &RuleDecl) -> i64 {
+    rule.meta
+        .as_ref()
+        .and_then(|m| m.entries.iter().find(|e| e.key == "priority"))
+        .and_then(|e| e.value.parse().ok())
+        .unwrap_or(0)
+}
+
 // ---------------------------------------------------------------------------
 // Score
 // ---------------------------------------------------------------------------
@@ -307,6 +461,10 @@ fn compile_score(score: &ScoreExpr) -> ScorePlan {
 // Yield
 // ---------------------------------------------------------------------------
 
+fn compile_yields(yields: &[YieldClause]) -> Vec<YieldPlan> {
+    yields.iter().map(compile_yield).collect()
+}
+
 fn compile_yield(yield_clause: &YieldClause) -> YieldPlan {
     YieldPlan {
         target: yield_clause.target.clone(),
@@ -392,13 +550,14 @@ fn compile_pipeline_entity(match_keys: &[FieldRef]) -> EntityPlan {
     }
 }
 
-fn measure_output_name(measure: Measure) -> &'static str {
+pub(crate) fn measure_output_name(measure: Measure) -> &'static str {
     match measure {
         Measure::Count => "count",
         Measure::Sum => "sum",
         Measure::Avg => "avg",
         Measure::Min => "min",
         Measure::Max => "max",
+        Measure::Rate(_) => "rate",
     }
 }
 
@@ -419,11 +578,13 @@ fn compile_joins(joins: &[crate::ast::JoinClause]) -> Vec<JoinPlan> {
         .map(|j| JoinPlan {
             right_window: j.target_window.clone(),
             mode: j.mode.clone(),
+            optional: j.optional,
             conds: j
                 .conditions
                 .iter()
                 .map(|c| JoinCondPlan {
                     left: c.left.clone(),
+                    op: c.op,
                     right: c.right.clone(),
                 })
                 .collect(),
@@ -68,11 +68,11 @@ rule brute_force {
     assert_eq!(p.score_plan.expr, Expr::Number(70.0));
 
     // yield: 3 fields
-    assert_eq!(p.yield_plan.target, "security_alerts");
-    assert_eq!(p.yield_plan.fields.len(), 3);
-    assert_eq!(p.yield_plan.fields[0].name, "sip");
-    assert_eq!(p.yield_plan.fields[1].name, "fail_count");
-    assert_eq!(p.yield_plan.fields[2].name, "message");
+    assert_eq!(p.yield_plans[0].target, "security_alerts");
+    assert_eq!(p.yield_plans[0].fields.len(), 3);
+    assert_eq!(p.yield_plans[0].fields[0].name, "sip");
+    assert_eq!(p.yield_plans[0].fields[1].name, "fail_count");
+    assert_eq!(p.yield_plans[0].fields[2].name, "message");
 
     // L1 empties
     assert!(p.joins.is_empty());
@@ -115,6 +115,12 @@ rule multi {
     // 2 event steps
     assert_eq!(p.match_plan.event_steps.len(), 2);
 
+    // step[0]: plain source count, no field, no transforms
+    let step0 = &p.match_plan.event_steps[0].branches[0];
+    assert_eq!(step0.field, None);
+    assert!(step0.agg.transforms.is_empty());
+    assert_eq!(step0.agg.measure, Measure::Count);
+
     // step[1]: field = Dot("dport"), transforms = [Distinct]
     let step1 = &p.match_plan.event_steps[1].branches[0];
     assert_eq!(step1.field, Some(FieldSelector::Dot("dport".into())));
@@ -227,3 +233,45 @@ rule or_rule {
     assert_eq!(step.branches[0].source, "a");
     assert_eq!(step.branches[1].source, "b");
 }
+
+// =========================================================================
+// 5. compile_conditional_threshold
+// =========================================================================
+
+#[test]
+fn compile_conditional_threshold() {
+    let schemas = [generic_window(), generic_window2(), output_window()];
+    let src = r#"
+rule cond_threshold {
+    events { a : win  b : win2 }
+    match<sip:5m> {
+        on event {
+            a | count >= if active then 3 else 10 || b | count >= 5;
+        }
+    } -> score(60.0)
+    entity(ip, a.sip)
+    yield out (x = a.sip)
+}
+"#;
+    let file = parse_wfl(src).expect("parse should succeed");
+    let errors: Vec<_> = crate::check_wfl(&file, &schemas)
+        .into_iter()
+        .filter(|e| e.severity == crate::checker::Severity::Error)
+        .collect();
+    assert!(errors.is_empty(), "unexpected check errors: {errors:?}");
+
+    let plans = compile_with(src, &schemas);
+    let step = &plans[0].match_plan.event_steps[0];
+    // The conditional's else branch stops before the `||` separator.
+    assert_eq!(step.branches.len(), 2);
+    assert_eq!(
+        step.branches[0].agg.threshold,
+        Expr::IfThenElse {
+            cond: Box::new(Expr::Field(FieldRef::Simple("active".into()))),
+            then_expr: Box::new(Expr::Number(3.0)),
+            else_expr: Box::new(Expr::Number(10.0)),
+        }
+    );
+    assert_eq!(step.branches[1].source, "b");
+    assert_eq!(step.branches[1].agg.threshold, Expr::Number(5.0));
+}
@@ -34,6 +34,49 @@ rule r {
     );
 }
 
+#[test]
+fn compile_with_diagnostics_returns_warnings_on_success() {
+    let schemas = [auth_events_window(), output_window()];
+    // No `limits` block: a warning, not an error.
+    let file = parse_wfl(
+        r#"
+rule r {
+    events { fail : auth_events }
+    match<sip:5m> { on event { fail | count >= 3; } } -> score(70.0)
+    entity(ip, fail.sip)
+    yield out (x = fail.sip)
+}
+"#,
+    )
+    .unwrap();
+    let output = compile_wfl_with_diagnostics(&file, &schemas).unwrap();
+    assert!(!output.has_errors());
+    assert_eq!(output.plans.len(), 1);
+    let warnings: Vec<_> = output.warnings().collect();
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(warnings[0].message.contains("limits"));
+}
+
+#[test]
+fn compile_with_diagnostics_keeps_errors_without_plans() {
+    let file = parse_wfl(
+        r#"
+rule r {
+    events { e : nonexistent_window }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#,
+    )
+    .unwrap();
+    let output = compile_wfl_with_diagnostics(&file, &[output_window()]).unwrap();
+    assert!(output.has_errors());
+    assert!(output.plans.is_empty());
+    let err = output.into_plans().unwrap_err();
+    assert!(err.to_string().starts_with("semantic errors:"), "{err}");
+}
+
 // =========================================================================
 // 16. compile_yield_version
 // =========================================================================
@@ -53,7 +96,7 @@ rule r {
 "#,
         &schemas,
     );
-    assert_eq!(plans[0].yield_plan.version, Some(2));
+    assert_eq!(plans[0].yield_plans[0].version, Some(2));
 }
 
 #[test]
@@ -70,7 +113,7 @@ rule r {
 "#,
         &schemas,
     );
-    assert_eq!(plans[0].yield_plan.version, None);
+    assert_eq!(plans[0].yield_plans[0].version, None);
 }
 
 // =========================================================================
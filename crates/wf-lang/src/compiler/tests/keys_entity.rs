@@ -123,3 +123,59 @@ rule r {
     );
     assert_eq!(plans2[0].entity_plan.entity_type, "ip");
 }
+
+/// `meta { entity_type_case = "preserve" }` keeps the authored casing.
+#[test]
+fn compile_entity_type_case_preserved() {
+    let schemas = [generic_window(), output_window()];
+
+    let plans = compile_with(
+        r#"
+rule r {
+    meta { entity_type_case = "preserve" }
+    events { e : win }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity("SrcIP", e.sip)
+    yield out (x = e.sip)
+}
+"#,
+        &schemas,
+    );
+    assert_eq!(plans[0].entity_plan.entity_type, "SrcIP");
+
+    let plans2 = compile_with(
+        r#"
+rule r {
+    meta { entity_type_case = "lower" }
+    events { e : win }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(IP, e.sip)
+    yield out (x = e.sip)
+}
+"#,
+        &schemas,
+    );
+    assert_eq!(plans2[0].entity_plan.entity_type, "ip");
+}
+
+#[test]
+fn compile_meta_priority() {
+    let schemas = [generic_window(), output_window()];
+    let rule = |meta: &str| {
+        format!(
+            r#"
+rule r {{
+    {meta}
+    events {{ e : win }}
+    match<:5m> {{ on event {{ e | count >= 1; }} }} -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}}
+"#
+        )
+    };
+    let plans = compile_with(&rule(r#"meta { priority = "10" }"#), &schemas);
+    assert_eq!(plans[0].priority, 10);
+    let plans = compile_with(&rule(""), &schemas);
+    assert_eq!(plans[0].priority, 0);
+}
@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use crate::ast::*;
-use crate::compiler::compile_wfl;
+use crate::compiler::{compile_wfl, compile_wfl_with_diagnostics};
 use crate::plan::*;
 use crate::schema::{BaseType, FieldDef, FieldType, WindowSchema};
 use crate::wfl_parser::parse_wfl;
@@ -28,7 +28,7 @@ rule pipe {
     assert_eq!(stage1.name, "__wf_pipe_pipe_s1");
     assert_eq!(stage1.binds.len(), 1);
     assert_eq!(stage1.binds[0].alias, "d");
-    assert_eq!(stage1.yield_plan.target, "__wf_pipe_pipe_w1");
+    assert_eq!(stage1.yield_plans[0].target, "__wf_pipe_pipe_w1");
     assert_eq!(stage1.entity_plan.entity_type, "pipeline");
     assert_eq!(stage1.score_plan.expr, Expr::Number(0.0));
 
@@ -37,7 +37,7 @@ rule pipe {
     assert_eq!(final_stage.binds.len(), 1);
     assert_eq!(final_stage.binds[0].alias, "_in");
     assert_eq!(final_stage.binds[0].window, "__wf_pipe_pipe_w1");
-    assert_eq!(final_stage.yield_plan.target, "out");
+    assert_eq!(final_stage.yield_plans[0].target, "out");
 }
 
 #[test]
@@ -68,10 +68,10 @@ rule pipe3 {
 
     assert_eq!(plans.len(), 3);
     assert_eq!(plans[0].name, "__wf_pipe_pipe3_s1");
-    assert_eq!(plans[0].yield_plan.target, "__wf_pipe_pipe3_w1");
+    assert_eq!(plans[0].yield_plans[0].target, "__wf_pipe_pipe3_w1");
     assert_eq!(plans[1].name, "__wf_pipe_pipe3_s2");
     assert_eq!(plans[1].binds[0].window, "__wf_pipe_pipe3_w1");
-    assert_eq!(plans[1].yield_plan.target, "__wf_pipe_pipe3_w2");
+    assert_eq!(plans[1].yield_plans[0].target, "__wf_pipe_pipe3_w2");
     assert_eq!(plans[2].name, "pipe3");
     assert_eq!(plans[2].binds[0].window, "__wf_pipe_pipe3_w2");
 }
@@ -104,8 +104,7 @@ rule keymap_pipe {
     );
 
     let stage1 = &plans[0];
-    let user_id_fields: Vec<_> = stage1
-        .yield_plan
+    let user_id_fields: Vec<_> = stage1.yield_plans[0]
         .fields
         .iter()
         .filter(|f| f.name == "user_id")
@@ -51,7 +51,7 @@ rule r {
 "#,
         &schemas,
     );
-    let yp = &plans[0].yield_plan;
+    let yp = &plans[0].yield_plans[0];
     assert_eq!(yp.target, "out");
     assert_eq!(yp.fields.len(), 2);
 
@@ -68,6 +68,33 @@ rule r {
     ));
 }
 
+#[test]
+fn compile_multiple_yields() {
+    let feed = make_output_window("feed", vec![("n", bt(BaseType::Digit))]);
+    let schemas = [auth_events_window(), output_window(), feed];
+    let plans = compile_with(
+        r#"
+rule r {
+    events { fail : auth_events }
+    match<sip:5m> { on event { fail | count >= 3; } } -> score(70.0)
+    entity(ip, fail.sip)
+    yield out (x = fail.sip)
+    yield feed (n = count(fail))
+}
+"#,
+        &schemas,
+    );
+    assert_eq!(plans.len(), 1);
+    let targets: Vec<_> = plans[0]
+        .yield_plans
+        .iter()
+        .map(|yp| yp.target.as_str())
+        .collect();
+    assert_eq!(targets, ["out", "feed"]);
+    assert_eq!(plans[0].yield_plans[0].fields[0].name, "x");
+    assert_eq!(plans[0].yield_plans[1].fields[0].name, "n");
+}
+
 // =========================================================================
 // 10. compile_score_arithmetic
 // =========================================================================
@@ -173,3 +200,40 @@ rule r {
     let branch = &plans[0].match_plan.event_steps[0].branches[0];
     assert_eq!(branch.label, Some("lbl".into()));
 }
+
+// =========================================================================
+// 14. compile_first_last_captures
+// =========================================================================
+
+#[test]
+fn compile_first_last_captures() {
+    let schemas = [auth_events_window(), output_window()];
+    let plans = compile_with(
+        r#"
+rule r {
+    events { fail : auth_events }
+    match<sip:5m> { on event { fail | count >= 3; } } -> score(if last(fail.count) > 10 then 80.0 else 40.0)
+    entity(ip, fail.sip)
+    yield out (
+        x = first(fail.dip),
+        y = last(fail.user),
+        n = first(fail.count)
+    )
+}
+"#,
+        &schemas,
+    );
+    let capture = |alias: &str, field: &str| CapturePlan {
+        alias: alias.into(),
+        field: field.into(),
+    };
+    // Score is registered first; the repeated fail.count is deduplicated.
+    assert_eq!(
+        plans[0].match_plan.captures,
+        vec![
+            capture("fail", "count"),
+            capture("fail", "dip"),
+            capture("fail", "user"),
+        ]
+    );
+}
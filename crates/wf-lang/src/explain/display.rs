@@ -1,6 +1,12 @@
 use std::fmt;
 
-use super::RuleExplanation;
+use super::{JoinExpl, RuleExplanation};
+
+impl fmt::Display for JoinExpl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
 
 impl fmt::Display for RuleExplanation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -60,15 +66,17 @@ impl fmt::Display for RuleExplanation {
         writeln!(f, "  Entity: {} = {}", self.entity_type, self.entity_id)?;
 
         // Yield
-        writeln!(f, "  Yield -> {}:", self.yield_target)?;
-        for (name, value) in &self.yield_fields {
-            writeln!(
-                f,
-                "    {:width$} = {}",
-                name,
-                value,
-                width = max_field_width(&self.yield_fields)
-            )?;
+        for y in &self.yields {
+            writeln!(f, "  Yield -> {}:", y.target)?;
+            for (name, value) in &y.fields {
+                writeln!(
+                    f,
+                    "    {:width$} = {}",
+                    name,
+                    value,
+                    width = max_field_width(&y.fields)
+                )?;
+            }
         }
 
         // Conv
@@ -93,6 +101,20 @@ impl fmt::Display for RuleExplanation {
             }
         }
 
+        // Stage output
+        if !self.stage_outputs.is_empty() {
+            writeln!(f, "  Stage Output:")?;
+            for (name, origin) in &self.stage_outputs {
+                writeln!(
+                    f,
+                    "    {:width$} <- {}",
+                    name,
+                    origin,
+                    width = max_field_width(&self.stage_outputs)
+                )?;
+            }
+        }
+
         // Limits
         if let Some(ref limits) = self.limits {
             writeln!(f, "  Limits: {}", limits)?;
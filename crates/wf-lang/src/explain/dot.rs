@@ -0,0 +1,180 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use super::RuleExplanation;
+
+/// Prefix of the synthetic windows that connect pipeline stages.
+const PIPE_WINDOW_PREFIX: &str = "__wf_pipe_";
+
+/// Render rule explanations as a Graphviz `digraph`.
+///
+/// Each rule becomes a `cluster_*` subgraph holding its bindings, match
+/// steps, joins, score and entity. Windows are shared nodes outside the
+/// clusters, so pipeline stages are connected through their synthetic
+/// `__wf_pipe_*` windows (drawn dashed).
+pub fn to_dot(explanations: &[RuleExplanation]) -> String {
+    let mut out = String::new();
+    out.push_str("digraph wfl {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+
+    // Window nodes (inputs, join targets, yield targets).
+    let mut windows = BTreeSet::new();
+    for e in explanations {
+        windows.extend(e.bindings.iter().map(|b| b.window.as_str()));
+        windows.extend(e.joins.iter().map(|j| j.window.as_str()));
+        windows.extend(yield_windows(e));
+    }
+    for w in &windows {
+        let style = if w.starts_with(PIPE_WINDOW_PREFIX) {
+            ", style=dashed"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "  {} [shape=cylinder, label={}{}];",
+            window_id(w),
+            quote(w),
+            style
+        );
+    }
+
+    for (idx, e) in explanations.iter().enumerate() {
+        write_rule(&mut out, idx, e);
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn write_rule(out: &mut String, idx: usize, e: &RuleExplanation) {
+    let node = |name: &str| quote(&format!("r{idx}_{name}"));
+
+    let _ = writeln!(out, "  subgraph {} {{", quote(&format!("cluster_{idx}")));
+    let _ = writeln!(out, "    label={};", quote(&format!("rule {}", e.name)));
+
+    for b in &e.bindings {
+        let label = match &b.filter {
+            Some(filter) => format!("{}\n[filter: {}]", b.alias, filter),
+            None => b.alias.clone(),
+        };
+        let _ = writeln!(
+            out,
+            "    {} [shape=ellipse, label={}];",
+            node(&format!("bind_{}", b.alias)),
+            quote(&label)
+        );
+    }
+
+    let match_label = format!("match <{}> {}", e.match_expl.keys, e.match_expl.window_spec);
+    let _ = writeln!(
+        out,
+        "    {} [label={}];",
+        node("match"),
+        quote(&match_label)
+    );
+
+    // The main chain: match -> steps -> joins -> score -> entity.
+    let mut chain = vec![node("match")];
+    let close_label = match e.match_expl.close_mode {
+        Some(crate::ast::CloseMode::And) => "and close",
+        _ => "on close",
+    };
+    let steps = e
+        .match_expl
+        .event_steps
+        .iter()
+        .enumerate()
+        .map(|(i, s)| ("event", "on event", i, s))
+        .chain(
+            e.match_expl
+                .close_steps
+                .iter()
+                .enumerate()
+                .map(|(i, s)| ("close", close_label, i, s)),
+        );
+    for (kind, label, i, step) in steps {
+        let id = node(&format!("{kind}_step_{}", i + 1));
+        let _ = writeln!(
+            out,
+            "    {} [label={}];",
+            id,
+            quote(&format!("{label} step {}: {step}", i + 1))
+        );
+        chain.push(id);
+    }
+    for (i, j) in e.joins.iter().enumerate() {
+        let id = node(&format!("join_{}", i + 1));
+        let _ = writeln!(out, "    {} [shape=diamond, label={}];", id, quote(&j.text));
+        chain.push(id);
+    }
+    let _ = writeln!(
+        out,
+        "    {} [label={}];",
+        node("score"),
+        quote(&format!("score: {}", e.score))
+    );
+    chain.push(node("score"));
+    let _ = writeln!(
+        out,
+        "    {} [label={}];",
+        node("entity"),
+        quote(&format!("entity: {} = {}", e.entity_type, e.entity_id))
+    );
+    chain.push(node("entity"));
+    out.push_str("  }\n");
+
+    for b in &e.bindings {
+        let bind = node(&format!("bind_{}", b.alias));
+        let _ = writeln!(out, "  {} -> {};", window_id(&b.window), bind);
+        let _ = writeln!(out, "  {} -> {};", bind, node("match"));
+    }
+    for pair in chain.windows(2) {
+        let _ = writeln!(out, "  {} -> {};", pair[0], pair[1]);
+    }
+    for (i, j) in e.joins.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "  {} -> {};",
+            window_id(&j.window),
+            node(&format!("join_{}", i + 1))
+        );
+    }
+    for window in yield_windows(e) {
+        let _ = writeln!(
+            out,
+            "  {} -> {} [label=\"yield\"];",
+            node("entity"),
+            window_id(window)
+        );
+    }
+}
+
+/// Yield targets without the `@vN` version suffix.
+fn yield_windows(e: &RuleExplanation) -> impl Iterator<Item = &str> {
+    e.yields.iter().map(|y| {
+        y.target
+            .split_once('@')
+            .map_or(y.target.as_str(), |(w, _)| w)
+    })
+}
+
+fn window_id(name: &str) -> String {
+    quote(&format!("win_{name}"))
+}
+
+fn quote(s: &str) -> String {
+    let mut q = String::with_capacity(s.len() + 2);
+    q.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => q.push_str("\\\""),
+            '\\' => q.push_str("\\\\"),
+            '\n' => q.push_str("\\n"),
+            _ => q.push(c),
+        }
+    }
+    q.push('"');
+    q
+}
@@ -1,4 +1,4 @@
-use crate::ast::{BinOp, CmpOp, Expr, FieldRef, FieldSelector, Measure, Transform};
+use crate::ast::{BinOp, CmpOp, ContainsMode, Expr, FieldRef, FieldSelector, Measure, Transform};
 
 // ---------------------------------------------------------------------------
 // Expression formatting
@@ -15,6 +15,7 @@ pub fn format_expr(expr: &Expr) -> String {
         }
         Expr::StringLit(s) => format!("\"{}\"", s),
         Expr::Bool(b) => format!("{}", b),
+        Expr::Duration(d) => format_duration(d),
         Expr::Field(fref) => format_field_ref(fref),
         Expr::BinOp { op, left, right } => {
             format!(
@@ -25,6 +26,12 @@ pub fn format_expr(expr: &Expr) -> String {
             )
         }
         Expr::Neg(inner) => format!("-{}", format_expr(inner)),
+        Expr::Not(inner) => match inner.as_ref() {
+            Expr::BinOp { .. } | Expr::InList { .. } | Expr::Contains { .. } => {
+                format!("not ({})", format_expr(inner))
+            }
+            _ => format!("not {}", format_expr(inner)),
+        },
         Expr::FuncCall {
             qualifier,
             name,
@@ -45,6 +52,22 @@ pub fn format_expr(expr: &Expr) -> String {
             let kw = if *negated { "not in" } else { "in" };
             format!("{} {} ({})", format_expr(inner), kw, items)
         }
+        Expr::Contains {
+            expr: inner,
+            mode,
+            values,
+        } => {
+            let items = values
+                .iter()
+                .map(format_expr)
+                .collect::<Vec<_>>()
+                .join(", ");
+            match mode {
+                ContainsMode::Single => format!("{} contains {}", format_expr(inner), items),
+                ContainsMode::Any => format!("{} contains any ({})", format_expr(inner), items),
+                ContainsMode::All => format!("{} contains all ({})", format_expr(inner), items),
+            }
+        }
         Expr::IfThenElse {
             cond,
             then_expr,
@@ -104,13 +127,14 @@ pub fn format_cmp(cmp: CmpOp) -> &'static str {
     }
 }
 
-pub fn format_measure(m: Measure) -> &'static str {
+pub fn format_measure(m: Measure) -> String {
     match m {
-        Measure::Count => "count",
-        Measure::Sum => "sum",
-        Measure::Avg => "avg",
-        Measure::Min => "min",
-        Measure::Max => "max",
+        Measure::Count => "count".to_string(),
+        Measure::Sum => "sum".to_string(),
+        Measure::Avg => "avg".to_string(),
+        Measure::Min => "min".to_string(),
+        Measure::Max => "max".to_string(),
+        Measure::Rate(unit) => format!("rate({})", format_duration(&unit)),
     }
 }
 
@@ -1,5 +1,7 @@
 mod display;
+mod dot;
 mod format;
+mod pipeline;
 mod sections;
 #[cfg(test)]
 mod tests;
@@ -8,11 +10,13 @@ use crate::plan::RulePlan;
 use crate::schema::WindowSchema;
 
 use crate::ast::CloseMode;
+pub use dot::to_dot;
 pub use format::{format_cmp, format_expr, format_field_ref, format_measure};
+pub use pipeline::{PipelineExplanation, authored_rule_name, explain_pipelines};
 
 use sections::{
     compute_lineage, explain_binds, explain_conv, explain_joins, explain_limits, explain_match,
-    explain_yield,
+    explain_stage_outputs, explain_yield,
 };
 
 /// Human-readable explanation of a compiled rule.
@@ -23,14 +27,16 @@ pub struct RuleExplanation {
     pub bindings: Vec<BindingExpl>,
     pub match_expl: MatchExpl,
     pub score: String,
-    pub joins: Vec<String>,
+    pub joins: Vec<JoinExpl>,
     pub entity_type: String,
     pub entity_id: String,
-    pub yield_target: String,
-    pub yield_fields: Vec<(String, String)>,
+    pub yields: Vec<YieldExpl>,
     pub conv: Option<Vec<String>>,
     pub limits: Option<String>,
     pub lineage: Vec<(String, String)>,
+    /// Intermediate pipeline stages only: each field passed to the next
+    /// stage and where it comes from.
+    pub stage_outputs: Vec<(String, String)>,
 }
 
 #[derive(Debug)]
@@ -40,6 +46,20 @@ pub struct BindingExpl {
     pub filter: Option<String>,
 }
 
+#[derive(Debug)]
+pub struct YieldExpl {
+    /// Target window, with `@vN` when versioned.
+    pub target: String,
+    pub fields: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+pub struct JoinExpl {
+    /// Right-hand window being joined.
+    pub window: String,
+    pub text: String,
+}
+
 #[derive(Debug)]
 pub struct MatchExpl {
     pub keys: String,
@@ -61,14 +81,18 @@ fn explain_rule(plan: &RulePlan, schemas: &[WindowSchema]) -> RuleExplanation {
     let joins = explain_joins(&plan.joins);
     let entity_type = plan.entity_plan.entity_type.clone();
     let entity_id = format_expr(&plan.entity_plan.entity_id_expr);
-    let yield_target = match plan.yield_plan.version {
-        Some(v) => format!("{}@v{}", plan.yield_plan.target, v),
-        None => plan.yield_plan.target.clone(),
-    };
-    let yield_fields = explain_yield(&plan.yield_plan);
+    let yields = plan.yield_plans.iter().map(explain_yield).collect();
     let conv = plan.conv_plan.as_ref().map(explain_conv);
     let limits = plan.limits_plan.as_ref().map(explain_limits);
-    let lineage = compute_lineage(&plan.binds, &plan.yield_plan, schemas);
+    let lineage = compute_lineage(&plan.binds, &plan.yield_plans, schemas);
+    let stage_outputs = if authored_rule_name(&plan.name) != plan.name {
+        plan.yield_plans
+            .first()
+            .map(|yp| explain_stage_outputs(&plan.match_plan, yp))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
     let pattern_origin = plan
         .pattern_origin
         .as_ref()
@@ -83,10 +107,10 @@ fn explain_rule(plan: &RulePlan, schemas: &[WindowSchema]) -> RuleExplanation {
         joins,
         entity_type,
         entity_id,
-        yield_target,
-        yield_fields,
+        yields,
         conv,
         limits,
         lineage,
+        stage_outputs,
     }
 }
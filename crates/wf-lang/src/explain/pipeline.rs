@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::plan::RulePlan;
+use crate::schema::WindowSchema;
+
+use super::{RuleExplanation, explain_rules};
+
+/// A rule as authored: a single explanation for regular rules, or the
+/// ordered stages (final stage last) of a `|>` pipeline rule.
+#[derive(Debug)]
+pub struct PipelineExplanation {
+    pub name: String,
+    pub stages: Vec<RuleExplanation>,
+}
+
+/// Build explanations grouped back into authored rules.
+///
+/// The compiler splits pipeline rules into `__wf_pipe_<rule>_s<N>` plans
+/// connected by `__wf_pipe_<rule>_w<N>` windows. This regroups them under
+/// the authored rule name and relabels the internal windows as
+/// `stage N output`.
+pub fn explain_pipelines(plans: &[RulePlan], schemas: &[WindowSchema]) -> Vec<PipelineExplanation> {
+    let mut groups = Vec::new();
+    let mut pending: Vec<RuleExplanation> = Vec::new();
+
+    for expl in explain_rules(plans, schemas) {
+        if stage_rule_name(&expl.name).is_some() {
+            pending.push(expl);
+            continue;
+        }
+        let name = expl.name.clone();
+        let (mut stages, others): (Vec<_>, Vec<_>) = std::mem::take(&mut pending)
+            .into_iter()
+            .partition(|s| stage_rule_name(&s.name).is_some_and(|(rule, _)| rule == name));
+        // Stages of a different rule never interleave in compiler output,
+        // but keep anything unexpected visible rather than dropping it.
+        groups.extend(others.into_iter().map(|s| PipelineExplanation {
+            name: s.name.clone(),
+            stages: vec![s],
+        }));
+        stages.push(expl);
+        relabel_stages(&name, &mut stages);
+        groups.push(PipelineExplanation { name, stages });
+    }
+    groups.extend(pending.into_iter().map(|s| PipelineExplanation {
+        name: s.name.clone(),
+        stages: vec![s],
+    }));
+    groups
+}
+
+impl PipelineExplanation {
+    pub fn is_pipeline(&self) -> bool {
+        self.stages.len() > 1
+    }
+
+    /// Authored stage order, e.g. `stage 1 -> stage 2 -> final`.
+    pub fn stage_flow(&self) -> String {
+        let n = self.stages.len();
+        (1..=n)
+            .map(|i| {
+                if i == n {
+                    "final".to_string()
+                } else {
+                    format!("stage {i}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+impl fmt::Display for PipelineExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_pipeline() {
+            writeln!(f, "Pipeline: {}", self.name)?;
+            writeln!(f, "  Stages: {}", self.stage_flow())?;
+        }
+        for stage in &self.stages {
+            write!(f, "{stage}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Split `__wf_pipe_<rule>_s<N>` into `(<rule>, N)`.
+/// Name of the authored rule a compiled plan belongs to: intermediate
+/// pipeline stages (`__wf_pipe_<rule>_s<N>`) map to `<rule>`, every other
+/// plan to its own name.
+pub fn authored_rule_name(plan_name: &str) -> &str {
+    stage_rule_name(plan_name).map_or(plan_name, |(rule, _)| rule)
+}
+
+fn stage_rule_name(name: &str) -> Option<(&str, usize)> {
+    let (rule, idx) = name.strip_prefix("__wf_pipe_")?.rsplit_once("_s")?;
+    Some((rule, idx.parse().ok()?))
+}
+
+fn relabel_stages(rule: &str, stages: &mut [RuleExplanation]) {
+    let count = stages.len();
+    if count < 2 {
+        return;
+    }
+    let relabel = |s: &str| -> String {
+        let mut out = s.to_string();
+        // Highest index first so `_w1` never matches a prefix of `_w10`.
+        for n in (1..count).rev() {
+            out = out.replace(
+                &format!("__wf_pipe_{rule}_w{n}"),
+                &format!("stage {n} output"),
+            );
+        }
+        out
+    };
+    for (i, stage) in stages.iter_mut().enumerate() {
+        stage.name = if i + 1 == count {
+            format!("{rule} (final stage)")
+        } else {
+            format!("{rule} (stage {} of {count})", i + 1)
+        };
+        for b in &mut stage.bindings {
+            b.window = relabel(&b.window);
+        }
+        for y in &mut stage.yields {
+            y.target = relabel(&y.target);
+        }
+        for (_, origin) in &mut stage.lineage {
+            *origin = relabel(origin);
+        }
+    }
+}
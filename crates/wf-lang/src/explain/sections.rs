@@ -1,4 +1,5 @@
 use crate::ast::{Expr, FieldRef};
+use crate::compiler::measure_output_name;
 use crate::plan::{
     AggPlan, BindPlan, BranchPlan, ConvOpPlan, ConvPlan, JoinPlan, LimitsPlan, MatchPlan, StepPlan,
     WindowSpec, YieldPlan,
@@ -9,7 +10,7 @@ use super::format::{
     format_cmp, format_duration, format_expr, format_field_ref, format_field_selector,
     format_measure, format_transform,
 };
-use super::{BindingExpl, MatchExpl};
+use super::{BindingExpl, JoinExpl, MatchExpl, YieldExpl};
 
 // ---------------------------------------------------------------------------
 // Bindings
@@ -44,7 +45,17 @@ pub(super) fn explain_match(mp: &MatchPlan) -> MatchExpl {
     let window_spec = match &mp.window_spec {
         WindowSpec::Sliding(d) => format!("sliding {}", format_duration(d)),
         WindowSpec::Fixed(d) => format!("fixed {}", format_duration(d)),
-        WindowSpec::Session(gap) => format!("session(gap={})", format_duration(gap)),
+        WindowSpec::Session(spec) => {
+            let mut s = format!("session(gap={}", format_duration(&spec.gap));
+            if let Some(max) = &spec.max_duration {
+                s.push_str(&format!(", max_duration={}", format_duration(max)));
+            }
+            if let Some(n) = spec.min_events {
+                s.push_str(&format!(", min_events={n}"));
+            }
+            s.push(')');
+            s
+        }
     };
 
     let event_steps = mp.event_steps.iter().map(format_step).collect();
@@ -112,15 +123,24 @@ fn format_agg(agg: &AggPlan) -> String {
 // Joins
 // ---------------------------------------------------------------------------
 
-pub(super) fn explain_joins(joins: &[JoinPlan]) -> Vec<String> {
+pub(super) fn explain_joins(joins: &[JoinPlan]) -> Vec<JoinExpl> {
     joins
         .iter()
         .map(|j| {
             let mode = match &j.mode {
                 crate::ast::JoinMode::Snapshot => "snapshot".to_string(),
-                crate::ast::JoinMode::Asof { within: None } => "asof".to_string(),
-                crate::ast::JoinMode::Asof { within: Some(d) } => {
-                    format!("asof within {}", format_duration(d))
+                crate::ast::JoinMode::Asof { direction, within } => {
+                    let mut mode = "asof".to_string();
+                    if *direction == crate::ast::AsofDirection::Forward {
+                        mode.push_str(" forward");
+                    }
+                    if let Some(d) = within {
+                        mode.push_str(&format!(" within {}", format_duration(d)));
+                    }
+                    if j.optional {
+                        mode.push_str(" optional");
+                    }
+                    mode
                 }
             };
             let conds: Vec<String> = j
@@ -128,13 +148,17 @@ pub(super) fn explain_joins(joins: &[JoinPlan]) -> Vec<String> {
                 .iter()
                 .map(|c| {
                     format!(
-                        "{} == {}",
+                        "{} {} {}",
                         format_field_ref(&c.left),
+                        format_cmp(c.op),
                         format_field_ref(&c.right)
                     )
                 })
                 .collect();
-            format!("join {} {} on {}", j.right_window, mode, conds.join(" && "))
+            JoinExpl {
+                window: j.right_window.clone(),
+                text: format!("join {} {} on {}", j.right_window, mode, conds.join(" && ")),
+            }
         })
         .collect()
 }
@@ -166,21 +190,31 @@ pub(super) fn explain_limits(lp: &LimitsPlan) -> String {
 // Yield + lineage
 // ---------------------------------------------------------------------------
 
-pub(super) fn explain_yield(yp: &YieldPlan) -> Vec<(String, String)> {
-    yp.fields
+pub(super) fn explain_yield(yp: &YieldPlan) -> YieldExpl {
+    let target = match yp.version {
+        Some(v) => format!("{}@v{}", yp.target, v),
+        None => yp.target.clone(),
+    };
+    let fields = yp
+        .fields
         .iter()
         .map(|f| (f.name.clone(), format_expr(&f.value)))
-        .collect()
+        .collect();
+    YieldExpl { target, fields }
 }
 
+/// Origin of every yielded field; a name yielded to several targets is
+/// listed once, traced from its first occurrence.
 pub(super) fn compute_lineage(
     binds: &[BindPlan],
-    yield_plan: &YieldPlan,
+    yield_plans: &[YieldPlan],
     _schemas: &[WindowSchema],
 ) -> Vec<(String, String)> {
-    yield_plan
-        .fields
+    let mut seen = std::collections::HashSet::new();
+    yield_plans
         .iter()
+        .flat_map(|yp| &yp.fields)
+        .filter(|f| seen.insert(f.name.as_str()))
         .map(|f| {
             let origin = trace_field_origin(&f.value, binds);
             (f.name.clone(), origin)
@@ -217,6 +251,55 @@ fn trace_field_origin(expr: &Expr, binds: &[BindPlan]) -> String {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Pipeline stage output
+// ---------------------------------------------------------------------------
+
+/// Fields an intermediate pipeline stage hands to the next stage (read there
+/// as `_in.<field>`): its match keys, then one field per branch named by the
+/// branch label. Unlabeled branches get the measure name injected by the
+/// compiler, shown as `(implicit label)`.
+pub(super) fn explain_stage_outputs(mp: &MatchPlan, yp: &YieldPlan) -> Vec<(String, String)> {
+    let branches: Vec<&BranchPlan> = mp
+        .event_steps
+        .iter()
+        .chain(&mp.close_steps)
+        .flat_map(|s| &s.branches)
+        .collect();
+    yp.fields
+        .iter()
+        .map(|f| {
+            let name = f.name.as_str();
+            let origin = if let Some(key) = mp.keys.iter().find(|k| key_name(k) == name) {
+                format!("match key {}", format_field_ref(key))
+            } else if let Some(b) = branches.iter().find(|b| b.label.as_deref() == Some(name)) {
+                let mut origin = b.source.clone();
+                if let Some(ref field) = b.field {
+                    origin.push_str(&format_field_selector(field));
+                }
+                for t in &b.agg.transforms {
+                    origin.push_str(&format!(" | {}", format_transform(t)));
+                }
+                origin.push_str(&format!(" | {}", format_measure(b.agg.measure)));
+                if name == measure_output_name(b.agg.measure) {
+                    origin.push_str(" (implicit label)");
+                }
+                origin
+            } else {
+                format_expr(&f.value)
+            };
+            (f.name.clone(), origin)
+        })
+        .collect()
+}
+
+fn key_name(key: &FieldRef) -> &str {
+    match key {
+        FieldRef::Simple(name) => name,
+        FieldRef::Qualified(_, field) | FieldRef::Bracketed(_, field) => field,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Conv
 // ---------------------------------------------------------------------------
@@ -1,12 +1,14 @@
 use std::time::Duration;
 
-use crate::ast::{Expr, FieldRef};
+use crate::ast::{AsofDirection, BinOp, CmpOp, Expr, FieldRef, JoinMode};
 use crate::compile_wfl;
+use crate::plan::{JoinCondPlan, JoinPlan};
 use crate::schema::{BaseType, FieldDef, FieldType, WindowSchema};
 use crate::wfl_parser::parse_wfl;
 
-use super::explain_rules;
 use super::format::format_expr;
+use super::sections::explain_joins;
+use super::{explain_pipelines, explain_rules, to_dot};
 
 fn bt(b: BaseType) -> FieldType {
     FieldType::Base(b)
@@ -58,6 +60,25 @@ fn security_alerts_window() -> WindowSchema {
     }
 }
 
+fn threat_intel_window() -> WindowSchema {
+    WindowSchema {
+        name: "threat_intel".to_string(),
+        streams: vec!["intel_stream".to_string()],
+        time_field: None,
+        over: Duration::from_secs(3600),
+        fields: vec![
+            FieldDef {
+                name: "ip".to_string(),
+                field_type: bt(BaseType::Ip),
+            },
+            FieldDef {
+                name: "message".to_string(),
+                field_type: bt(BaseType::Chars),
+            },
+        ],
+    }
+}
+
 #[test]
 fn explain_brute_force_rule() {
     let input = r#"
@@ -99,8 +120,8 @@ rule brute_force_then_scan {
     assert_eq!(expl.score, "70.0");
     assert_eq!(expl.entity_type, "ip");
     assert_eq!(expl.entity_id, "fail.sip");
-    assert_eq!(expl.yield_target, "security_alerts");
-    assert_eq!(expl.yield_fields.len(), 3);
+    assert_eq!(expl.yields[0].target, "security_alerts");
+    assert_eq!(expl.yields[0].fields.len(), 3);
 
     // Verify Display output
     let output = format!("{}", expl);
@@ -131,6 +152,36 @@ fn format_expr_variants() {
         }),
         "count(fail)"
     );
+    assert_eq!(
+        format_expr(&Expr::BinOp {
+            op: BinOp::Add,
+            left: Box::new(Expr::Field(FieldRef::Simple("sip".into()))),
+            right: Box::new(Expr::StringLit(":".into())),
+        }),
+        "sip + \":\""
+    );
+    assert_eq!(
+        format_expr(&Expr::FuncCall {
+            qualifier: None,
+            name: "lower".into(),
+            args: vec![Expr::Field(FieldRef::Qualified("e".into(), "user".into()))]
+        }),
+        "lower(e.user)"
+    );
+    assert_eq!(
+        format_expr(&Expr::Not(Box::new(Expr::BinOp {
+            op: BinOp::And,
+            left: Box::new(Expr::Field(FieldRef::Simple("a".into()))),
+            right: Box::new(Expr::Field(FieldRef::Simple("b".into()))),
+        }))),
+        "not (a && b)"
+    );
+    assert_eq!(
+        format_expr(&Expr::Not(Box::new(Expr::Field(FieldRef::Simple(
+            "ok".into()
+        ))))),
+        "not ok"
+    );
 }
 
 #[test]
@@ -217,3 +268,274 @@ rule brute_force_then_scan {
         "standard rule should not show Pattern line"
     );
 }
+
+#[test]
+fn dot_export_for_join_rule() {
+    let input = r#"
+rule intel_hit {
+    events {
+        fail : auth_events && action == "failed"
+    }
+    match<sip:5m> {
+        on event {
+            fail | count >= 3;
+        }
+    } -> score(80.0)
+    join threat_intel snapshot on sip == threat_intel.ip
+    entity(ip, fail.sip)
+    yield security_alerts (
+        sip = fail.sip,
+        fail_count = count(fail),
+        message = fmt("{} on threat intel", fail.sip)
+    )
+}
+"#;
+    let schemas = &[
+        auth_events_window(),
+        security_alerts_window(),
+        threat_intel_window(),
+    ];
+    let file = parse_wfl(input).unwrap();
+    let plans = compile_wfl(&file, schemas).unwrap();
+    let dot = to_dot(&explain_rules(&plans, schemas));
+
+    assert!(dot.starts_with("digraph wfl {"), "{dot}");
+    assert!(dot.contains(r#"subgraph "cluster_0" {"#), "{dot}");
+    assert!(dot.contains(r#"label="rule intel_hit";"#), "{dot}");
+    assert!(dot.contains(r#""win_auth_events" [shape=cylinder, label="auth_events"];"#));
+    assert!(
+        dot.contains(r#"label="fail\n[filter: action == \"failed\"]""#),
+        "{dot}"
+    );
+    assert!(dot.contains(r#"label="join threat_intel snapshot on sip == threat_intel.ip""#));
+    assert!(dot.contains(r#"label="score: 80.0""#), "{dot}");
+    assert!(dot.contains(r#"label="entity: ip = fail.sip""#), "{dot}");
+
+    // bindings -> match -> steps -> join -> score -> entity -> yield target
+    assert!(
+        dot.contains(r#""win_auth_events" -> "r0_bind_fail";"#),
+        "{dot}"
+    );
+    assert!(dot.contains(r#""r0_bind_fail" -> "r0_match";"#), "{dot}");
+    assert!(dot.contains(r#""r0_match" -> "r0_event_step_1";"#), "{dot}");
+    assert!(
+        dot.contains(r#""r0_event_step_1" -> "r0_join_1";"#),
+        "{dot}"
+    );
+    assert!(dot.contains(r#""r0_join_1" -> "r0_score";"#), "{dot}");
+    assert!(
+        dot.contains(r#""win_threat_intel" -> "r0_join_1";"#),
+        "{dot}"
+    );
+    assert!(dot.contains(r#""r0_entity" -> "win_security_alerts" [label="yield"];"#));
+}
+
+#[test]
+fn dot_export_connects_pipeline_stages_through_pipe_windows() {
+    let input = r#"
+rule staged {
+    events {
+        fail : auth_events && action == "failed"
+    }
+    match<sip:5m> {
+        on event {
+            fail | count >= 3;
+        }
+    }
+    |> match<sip:10m> {
+        on event {
+            _in | count >= 2;
+        }
+    } -> score(60.0)
+    entity(ip, _in.sip)
+    yield security_alerts (
+        sip = _in.sip
+    )
+}
+"#;
+    let schemas = &[auth_events_window(), security_alerts_window()];
+    let file = parse_wfl(input).unwrap();
+    let plans = compile_wfl(&file, schemas).unwrap();
+    let dot = to_dot(&explain_rules(&plans, schemas));
+
+    assert!(dot.contains(r#"subgraph "cluster_0""#), "{dot}");
+    assert!(dot.contains(r#"subgraph "cluster_1""#), "{dot}");
+    assert!(
+        dot.contains(r#""win___wf_pipe_staged_w1" [shape=cylinder, label="__wf_pipe_staged_w1", style=dashed];"#),
+        "{dot}"
+    );
+    assert!(dot.contains(r#""r0_entity" -> "win___wf_pipe_staged_w1" [label="yield"];"#));
+    assert!(
+        dot.contains(r#""win___wf_pipe_staged_w1" -> "r1_bind__in";"#),
+        "{dot}"
+    );
+}
+
+#[test]
+fn explain_pipelines_presents_stages_as_authored() {
+    let input = r#"
+rule staged {
+    events {
+        fail : auth_events && action == "failed"
+    }
+    match<sip:5m> {
+        on event {
+            fail | count >= 3;
+        }
+    }
+    |> match<sip:10m> {
+        on event {
+            _in | count >= 2;
+        }
+    } -> score(60.0)
+    entity(ip, _in.sip)
+    yield security_alerts (
+        sip = _in.sip
+    )
+}
+"#;
+    let schemas = &[auth_events_window(), security_alerts_window()];
+    let file = parse_wfl(input).unwrap();
+    let plans = compile_wfl(&file, schemas).unwrap();
+    let groups = explain_pipelines(&plans, schemas);
+
+    assert_eq!(groups.len(), 1);
+    let pipe = &groups[0];
+    assert_eq!(pipe.name, "staged");
+    assert!(pipe.is_pipeline());
+    assert_eq!(pipe.stage_flow(), "stage 1 -> final");
+
+    let names: Vec<&str> = pipe.stages.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["staged (stage 1 of 2)", "staged (final stage)"]);
+    assert_eq!(pipe.stages[0].bindings[0].window, "auth_events");
+    assert_eq!(pipe.stages[0].yields[0].target, "stage 1 output");
+    assert_eq!(pipe.stages[1].bindings[0].window, "stage 1 output");
+    assert_eq!(pipe.stages[1].yields[0].target, "security_alerts");
+
+    let output = pipe.to_string();
+    assert!(output.starts_with("Pipeline: staged\n  Stages: stage 1 -> final\n"));
+    assert!(output.contains("_in -> stage 1 output"), "{output}");
+    assert!(!output.contains("__wf_pipe_"), "{output}");
+    let first = output.find("Rule: staged (stage 1 of 2)").unwrap();
+    let last = output.find("Rule: staged (final stage)").unwrap();
+    assert!(first < last);
+}
+
+#[test]
+fn explain_shows_pipeline_stage_output_names() {
+    let input = r#"
+rule staged {
+    events {
+        fail : auth_events && action == "failed"
+    }
+    match<sip:5m> {
+        on event {
+            fail | count >= 3;
+            actions: fail.action | distinct | count >= 2;
+        }
+    }
+    |> match<sip:10m> {
+        on event {
+            _in | count >= 2;
+        }
+    } -> score(60.0)
+    entity(ip, _in.sip)
+    yield security_alerts (
+        sip = _in.sip
+    )
+}
+"#;
+    let schemas = &[auth_events_window(), security_alerts_window()];
+    let file = parse_wfl(input).unwrap();
+    let plans = compile_wfl(&file, schemas).unwrap();
+    let expls = explain_rules(&plans, schemas);
+
+    let outputs: Vec<(&str, &str)> = expls[0]
+        .stage_outputs
+        .iter()
+        .map(|(n, o)| (n.as_str(), o.as_str()))
+        .collect();
+    assert_eq!(
+        outputs,
+        [
+            ("sip", "match key sip"),
+            ("count", "fail | count (implicit label)"),
+            ("actions", "fail.action | distinct | count"),
+        ]
+    );
+    // The implicit label also shows on the step itself.
+    assert_eq!(
+        expls[0].match_expl.event_steps[0],
+        "count: fail | count >= 3.0"
+    );
+
+    let output = expls[0].to_string();
+    assert!(
+        output.contains("  Stage Output:\n    sip     <- match key sip\n"),
+        "{output}"
+    );
+    assert!(
+        output.contains("    count   <- fail | count (implicit label)\n"),
+        "{output}"
+    );
+    assert!(
+        output.contains("    actions <- fail.action | distinct | count\n"),
+        "{output}"
+    );
+
+    // The final stage's yield is authored, so it has no stage output.
+    assert!(expls[1].stage_outputs.is_empty());
+    assert!(!expls[1].to_string().contains("Stage Output:"));
+}
+
+#[test]
+fn explain_pipelines_keeps_regular_rules_unchanged() {
+    let input = r#"
+rule plain {
+    events {
+        fail : auth_events && action == "failed"
+    }
+    match<sip:5m> {
+        on event {
+            fail | count >= 3;
+        }
+    } -> score(70.0)
+    entity(ip, fail.sip)
+    yield security_alerts (
+        sip = fail.sip
+    )
+}
+"#;
+    let schemas = &[auth_events_window(), security_alerts_window()];
+    let file = parse_wfl(input).unwrap();
+    let plans = compile_wfl(&file, schemas).unwrap();
+    let groups = explain_pipelines(&plans, schemas);
+
+    assert_eq!(groups.len(), 1);
+    assert!(!groups[0].is_pipeline());
+    assert_eq!(groups[0].stages[0].name, "plain");
+    assert!(groups[0].to_string().starts_with("Rule: plain\n"));
+}
+
+#[test]
+fn explain_joins_renders_asof_direction() {
+    let join = |direction, within| JoinPlan {
+        right_window: "resp".into(),
+        mode: JoinMode::Asof { direction, within },
+        optional: false,
+        conds: vec![JoinCondPlan {
+            left: FieldRef::Simple("sip".into()),
+            op: CmpOp::Eq,
+            right: FieldRef::Qualified("resp".into(), "ip".into()),
+        }],
+    };
+    let expl = explain_joins(&[
+        join(AsofDirection::Backward, None),
+        join(AsofDirection::Forward, Some(Duration::from_secs(30))),
+    ]);
+    assert_eq!(expl[0].text, "join resp asof on sip == resp.ip");
+    assert_eq!(
+        expl[1].text,
+        "join resp asof forward within 30s on sip == resp.ip"
+    );
+}
@@ -11,8 +11,8 @@ mod wfs_parser;
 
 pub use checker::lint::lint_wfl;
 pub use checker::{CheckError, Severity, check_wfl};
-pub use compiler::compile_wfl;
+pub use compiler::{CompileOutput, compile_wfl, compile_wfl_with_diagnostics};
 pub use preprocess::{preprocess_vars, preprocess_vars_with_env};
 pub use schema::{BaseType, FieldDef, FieldType, WindowSchema};
-pub use wfl_parser::parse_wfl;
+pub use wfl_parser::{WflParseError, parse_wfl};
 pub use wfs_parser::parse_wfs;
@@ -1,6 +1,10 @@
 use std::time::Duration;
 
-use crate::ast::{CloseMode, CmpOp, Expr, FieldRef, FieldSelector, JoinMode, Measure, Transform};
+use serde::Serialize;
+
+use crate::ast::{
+    CloseMode, CmpOp, Expr, FieldRef, FieldSelector, JoinMode, Measure, SessionSpec, Transform,
+};
 
 // ---------------------------------------------------------------------------
 // ExprPlan — L1 alias for ast::Expr
@@ -18,18 +22,21 @@ pub type ExprPlan = Expr;
 // ---------------------------------------------------------------------------
 
 /// Compiled rule — the executable representation consumed by MatchEngine.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RulePlan {
     pub name: String,
     pub binds: Vec<BindPlan>,
     pub match_plan: MatchPlan,
     pub joins: Vec<JoinPlan>,
     pub entity_plan: EntityPlan,
-    pub yield_plan: YieldPlan,
+    /// One plan per `yield` target; the executor emits a record for each.
+    pub yield_plans: Vec<YieldPlan>,
     pub score_plan: ScorePlan,
     pub pattern_origin: Option<PatternOriginPlan>,
     pub conv_plan: Option<ConvPlan>,
     pub limits_plan: Option<LimitsPlan>,
+    /// `meta { priority = "N" }`; higher is more urgent, default 0.
+    pub priority: i64,
 }
 
 // ---------------------------------------------------------------------------
@@ -37,7 +44,7 @@ pub struct RulePlan {
 // ---------------------------------------------------------------------------
 
 /// Tracks the pattern origin for `wf explain` display.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct PatternOriginPlan {
     pub pattern_name: String,
     pub args: Vec<String>,
@@ -48,7 +55,7 @@ pub struct PatternOriginPlan {
 // ---------------------------------------------------------------------------
 
 /// A bound event source: alias + window + optional filter.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct BindPlan {
     pub alias: String,
     pub window: String,
@@ -60,7 +67,7 @@ pub struct BindPlan {
 // ---------------------------------------------------------------------------
 
 /// The match plan: keys, window spec, event steps, close steps, key mapping, and close mode.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct MatchPlan {
     pub keys: Vec<FieldRef>,
     pub key_map: Option<Vec<KeyMapPlan>>,
@@ -68,10 +75,24 @@ pub struct MatchPlan {
     pub event_steps: Vec<StepPlan>,
     pub close_steps: Vec<StepPlan>,
     pub close_mode: CloseMode,
+    /// How far the watermark trails the max seen event time. Events up to
+    /// this much older than the newest one are still folded in before the
+    /// instances they belong to can expire. Zero means no tolerance.
+    pub max_out_of_orderness: Duration,
+    /// Fields whose first/last value each instance records as events
+    /// arrive, for `first(alias.field)` / `last(alias.field)`.
+    pub captures: Vec<CapturePlan>,
+}
+
+/// A per-instance value capture: `alias.field`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CapturePlan {
+    pub alias: String,
+    pub field: String,
 }
 
 /// Explicit key mapping entry: logical name → source alias + field.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct KeyMapPlan {
     pub logical_name: String,
     pub source_alias: String,
@@ -79,24 +100,24 @@ pub struct KeyMapPlan {
 }
 
 /// Window specification for the match clause.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum WindowSpec {
     /// Sliding window with a fixed duration.
     Sliding(Duration),
     /// Fixed window with a fixed duration (non-overlapping buckets).
     Fixed(Duration),
-    /// Session window with gap duration (L3 behavior analysis).
-    Session(Duration),
+    /// Session window closed by an inactivity gap (L3 behavior analysis).
+    Session(SessionSpec),
 }
 
 /// One match step containing one or more OR branches.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct StepPlan {
     pub branches: Vec<BranchPlan>,
 }
 
 /// A single branch within a match step.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct BranchPlan {
     pub label: Option<String>,
     pub source: String,
@@ -106,7 +127,7 @@ pub struct BranchPlan {
 }
 
 /// Aggregation pipeline: transforms → measure → cmp → threshold.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct AggPlan {
     pub transforms: Vec<Transform>,
     pub measure: Measure,
@@ -119,17 +140,21 @@ pub struct AggPlan {
 // ---------------------------------------------------------------------------
 
 /// Cross-source join plan.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct JoinPlan {
     pub right_window: String,
     pub mode: JoinMode,
+    /// Keep the match when an asof join finds no row (fields stay null);
+    /// otherwise the match produces no output.
+    pub optional: bool,
     pub conds: Vec<JoinCondPlan>,
 }
 
-/// A single join condition: left field == right field.
-#[derive(Debug, Clone, PartialEq)]
+/// A single join condition: `left <op> right`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct JoinCondPlan {
     pub left: FieldRef,
+    pub op: CmpOp,
     pub right: FieldRef,
 }
 
@@ -138,7 +163,7 @@ pub struct JoinCondPlan {
 // ---------------------------------------------------------------------------
 
 /// Compiled limits for runtime enforcement.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct LimitsPlan {
     pub max_memory_bytes: Option<usize>,
     pub max_instances: Option<usize>,
@@ -147,7 +172,7 @@ pub struct LimitsPlan {
 }
 
 /// What to do when a limit is exceeded.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ExceedAction {
     Throttle,
     DropOldest,
@@ -155,7 +180,7 @@ pub enum ExceedAction {
 }
 
 /// Emit rate specification: count per duration.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RateSpec {
     pub count: u64,
     pub per: Duration,
@@ -168,7 +193,7 @@ pub struct RateSpec {
 /// Entity identification: lowercase-normalized type string + id expression.
 ///
 /// Both `entity(IP, ...)` and `entity("ip", ...)` compile to `entity_type = "ip"`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct EntityPlan {
     pub entity_type: String,
     pub entity_id_expr: ExprPlan,
@@ -179,7 +204,7 @@ pub struct EntityPlan {
 // ---------------------------------------------------------------------------
 
 /// Score computation expression.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ScorePlan {
     pub expr: ExprPlan,
 }
@@ -189,7 +214,7 @@ pub struct ScorePlan {
 // ---------------------------------------------------------------------------
 
 /// Output yield: target window + optional version + fields.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct YieldPlan {
     pub target: String,
     pub version: Option<u32>,
@@ -197,7 +222,7 @@ pub struct YieldPlan {
 }
 
 /// A single yield field: name = expression.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct YieldField {
     pub name: String,
     pub value: ExprPlan,
@@ -208,19 +233,19 @@ pub struct YieldField {
 // ---------------------------------------------------------------------------
 
 /// Compiled conv plan — post-close result set transformations.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ConvPlan {
     pub chains: Vec<ConvChainPlan>,
 }
 
 /// One semicolon-separated chain of piped operations.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ConvChainPlan {
     pub ops: Vec<ConvOpPlan>,
 }
 
 /// A single conv operation.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ConvOpPlan {
     Sort(Vec<SortKeyPlan>),
     Top(u64),
@@ -229,7 +254,7 @@ pub enum ConvOpPlan {
 }
 
 /// Sort key with direction.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct SortKeyPlan {
     pub expr: ExprPlan,
     pub descending: bool,
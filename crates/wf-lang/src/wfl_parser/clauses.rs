@@ -7,6 +7,7 @@ use crate::ast::*;
 use crate::parse_utils::{duration_value, ident, kw, nonneg_integer, quoted_string, ws_skip};
 
 use super::expr;
+use super::match_p::cmp_op_step;
 
 // ---------------------------------------------------------------------------
 // entity clause
@@ -96,7 +97,8 @@ fn named_arg(input: &mut &str) -> ModalResult<NamedArg> {
 // join clause
 // ---------------------------------------------------------------------------
 
-/// `join WINDOW snapshot/asof [within DUR] on cond [&& cond]`
+/// `join WINDOW snapshot/asof [backward|forward] [within DUR] [optional] on cond [&& cond]`,
+/// where `cond` is `field <op> WINDOW.field`
 pub(super) fn join_clause(input: &mut &str) -> ModalResult<JoinClause> {
     ws_skip.parse_next(input)?;
     kw("join").parse_next(input)?;
@@ -112,6 +114,9 @@ pub(super) fn join_clause(input: &mut &str) -> ModalResult<JoinClause> {
     ws_skip.parse_next(input)?;
     let mode = cut_err(join_mode).parse_next(input)?;
 
+    ws_skip.parse_next(input)?;
+    let optional = opt(kw("optional")).parse_next(input)?.is_some();
+
     ws_skip.parse_next(input)?;
     cut_err(kw("on"))
         .context(StrContext::Expected(StrContextValue::Description(
@@ -137,18 +142,37 @@ pub(super) fn join_clause(input: &mut &str) -> ModalResult<JoinClause> {
     Ok(JoinClause {
         target_window,
         mode,
+        optional,
         conditions,
     })
 }
 
 fn join_mode(input: &mut &str) -> ModalResult<JoinMode> {
     alt((
-        (kw("asof"), ws_skip, opt(asof_within)).map(|(_, _, within)| JoinMode::Asof { within }),
+        (
+            kw("asof"),
+            ws_skip,
+            opt(asof_direction),
+            ws_skip,
+            opt(asof_within),
+        )
+            .map(|(_, _, direction, _, within)| JoinMode::Asof {
+                direction: direction.unwrap_or_default(),
+                within,
+            }),
         kw("snapshot").map(|_| JoinMode::Snapshot),
     ))
     .parse_next(input)
 }
 
+fn asof_direction(input: &mut &str) -> ModalResult<AsofDirection> {
+    alt((
+        kw("backward").map(|_| AsofDirection::Backward),
+        kw("forward").map(|_| AsofDirection::Forward),
+    ))
+    .parse_next(input)
+}
+
 fn asof_within(input: &mut &str) -> ModalResult<std::time::Duration> {
     kw("within").parse_next(input)?;
     ws_skip.parse_next(input)?;
@@ -158,10 +182,14 @@ fn asof_within(input: &mut &str) -> ModalResult<std::time::Duration> {
 fn join_cond(input: &mut &str) -> ModalResult<JoinCondition> {
     let left = join_field_ref.parse_next(input)?;
     ws_skip.parse_next(input)?;
-    cut_err(literal("==")).parse_next(input)?;
+    let op = cut_err(cmp_op_step)
+        .context(StrContext::Expected(StrContextValue::Description(
+            "comparison operator",
+        )))
+        .parse_next(input)?;
     ws_skip.parse_next(input)?;
     let right = cut_err(join_field_ref).parse_next(input)?;
-    Ok(JoinCondition { left, right })
+    Ok(JoinCondition { left, op, right })
 }
 
 /// Parse a field reference for join conditions: `ident.ident` or `ident`
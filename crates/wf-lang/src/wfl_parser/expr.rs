@@ -1,7 +1,7 @@
-use winnow::combinator::{alt, cut_err, opt, separated};
-use winnow::error::{StrContext, StrContextValue};
+use winnow::combinator::{alt, cut_err, not, opt, separated};
+use winnow::error::{ContextError, ErrMode, StrContext, StrContextValue};
 use winnow::prelude::*;
-use winnow::token::literal;
+use winnow::token::{literal, one_of};
 
 use crate::ast::*;
 use crate::parse_utils::{duration_value, ident, kw, number_literal, quoted_string, ws_skip};
@@ -16,12 +16,52 @@ pub(crate) fn parse_expr(input: &mut &str) -> ModalResult<Expr> {
 
 /// Parse an expression that stops before `||` and `&&` — used for pipe chain
 /// thresholds in match steps where `||` is the branch separator.
+///
+/// A threshold may also be `if cond then a else b`; its branches stop at the
+/// same boundary so `count >= if x then 3 else 10 || b | count >= 1` keeps
+/// the trailing branch.
 pub(crate) fn parse_atomic_expr(input: &mut &str) -> ModalResult<Expr> {
+    if let Some(e) = opt(threshold_if_expr).parse_next(input)? {
+        return Ok(e);
+    }
     // Only parse up to additive level (no comparisons or logic)
     // In practice, thresholds are simple values: numbers, field refs, func calls
     unary_expr.parse_next(input)
 }
 
+/// `"if" expr "then" threshold_branch "else" threshold_branch`, where a
+/// branch is a nested conditional or an `add_expr`.
+fn threshold_if_expr(input: &mut &str) -> ModalResult<Expr> {
+    kw("if").parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    let cond = cut_err(parse_expr).parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    cut_err(kw("then"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "'then' after if condition",
+        )))
+        .parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    let then_e = cut_err(threshold_branch).parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    cut_err(kw("else"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "'else' after then branch",
+        )))
+        .parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    let else_e = cut_err(threshold_branch).parse_next(input)?;
+    Ok(Expr::IfThenElse {
+        cond: Box::new(cond),
+        then_expr: Box::new(then_e),
+        else_expr: Box::new(else_e),
+    })
+}
+
+fn threshold_branch(input: &mut &str) -> ModalResult<Expr> {
+    alt((threshold_if_expr, add_expr)).parse_next(input)
+}
+
 // ---------------------------------------------------------------------------
 // Precedence levels (lowest to highest)
 // ---------------------------------------------------------------------------
@@ -46,14 +86,14 @@ fn or_expr(input: &mut &str) -> ModalResult<Expr> {
     Ok(left)
 }
 
-/// `and_expr = cmp_expr { "&&" cmp_expr }`
+/// `and_expr = not_expr { "&&" not_expr }`
 fn and_expr(input: &mut &str) -> ModalResult<Expr> {
-    let mut left = cmp_expr.parse_next(input)?;
+    let mut left = not_expr.parse_next(input)?;
     loop {
         ws_skip.parse_next(input)?;
         if opt(literal("&&")).parse_next(input)?.is_some() {
             ws_skip.parse_next(input)?;
-            let right = cut_err(cmp_expr).parse_next(input)?;
+            let right = cut_err(not_expr).parse_next(input)?;
             left = Expr::BinOp {
                 op: BinOp::And,
                 left: Box::new(left),
@@ -66,7 +106,23 @@ fn and_expr(input: &mut &str) -> ModalResult<Expr> {
     Ok(left)
 }
 
-/// `cmp_expr = add_expr [cmp_op add_expr | "in" "(" list ")" | "not" "in" "(" list ")"]`
+/// `not_expr = ("not" | "!") not_expr | cmp_expr`
+///
+/// Binds looser than comparisons (`not a == b` is `not (a == b)`) and
+/// tighter than `&&` / `||`.
+fn not_expr(input: &mut &str) -> ModalResult<Expr> {
+    let negated =
+        opt(alt((kw("not"), (literal("!"), not(literal("="))).void()))).parse_next(input)?;
+    if negated.is_some() {
+        ws_skip.parse_next(input)?;
+        let inner = cut_err(not_expr).parse_next(input)?;
+        return Ok(Expr::Not(Box::new(inner)));
+    }
+    cmp_expr.parse_next(input)
+}
+
+/// `cmp_expr = add_expr [cmp_op add_expr | "in" "(" list ")" | "not" "in" "(" list ")"
+///            | "contains" add_expr | "contains" ("any" | "all") "(" list ")"]`
 fn cmp_expr(input: &mut &str) -> ModalResult<Expr> {
     let left = add_expr.parse_next(input)?;
     ws_skip.parse_next(input)?;
@@ -96,6 +152,31 @@ fn cmp_expr(input: &mut &str) -> ModalResult<Expr> {
         });
     }
 
+    // Try "contains" [any | all]
+    if opt(kw("contains")).parse_next(input)?.is_some() {
+        ws_skip.parse_next(input)?;
+        let mode = opt(alt((
+            kw("any").value(ContainsMode::Any),
+            kw("all").value(ContainsMode::All),
+        )))
+        .parse_next(input)?;
+        let (mode, values) = match mode {
+            Some(mode) => {
+                ws_skip.parse_next(input)?;
+                (mode, in_list.parse_next(input)?)
+            }
+            None => (
+                ContainsMode::Single,
+                vec![cut_err(add_expr).parse_next(input)?],
+            ),
+        };
+        return Ok(Expr::Contains {
+            expr: Box::new(left),
+            mode,
+            values,
+        });
+    }
+
     // Try cmp_op
     if let Some(op) = opt(cmp_op).parse_next(input)? {
         ws_skip.parse_next(input)?;
@@ -200,6 +281,8 @@ fn unary_expr(input: &mut &str) -> ModalResult<Expr> {
 
 fn primary(input: &mut &str) -> ModalResult<Expr> {
     alt((
+        // Duration literal (before number, since `5s` starts with digit)
+        duration_literal.map(Expr::Duration),
         // Number literal
         number_literal.map(Expr::Number),
         // String literal
@@ -220,6 +303,19 @@ fn primary(input: &mut &str) -> ModalResult<Expr> {
     .parse_next(input)
 }
 
+/// Duration literal in an expression (`5s`, `10m`, `1h`, `2d`). The unit
+/// suffix is required and may not run into an identifier, so `5` stays a
+/// number and `5sec` is rejected.
+fn duration_literal(input: &mut &str) -> ModalResult<std::time::Duration> {
+    let after_digits = input.trim_start_matches(|c: char| c.is_ascii_digit());
+    if after_digits.len() == input.len() || !after_digits.starts_with(['s', 'm', 'h', 'd']) {
+        return Err(ErrMode::Backtrack(ContextError::new()));
+    }
+    let dur = duration_value.parse_next(input)?;
+    not(one_of(|c: char| c.is_ascii_alphanumeric() || c == '_')).parse_next(input)?;
+    Ok(dur)
+}
+
 fn paren_expr(input: &mut &str) -> ModalResult<Expr> {
     literal("(").parse_next(input)?;
     ws_skip.parse_next(input)?;
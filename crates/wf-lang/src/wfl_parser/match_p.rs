@@ -1,10 +1,12 @@
+use std::time::Duration;
+
 use winnow::combinator::{alt, cut_err, opt, separated};
 use winnow::error::{StrContext, StrContextValue};
 use winnow::prelude::*;
 use winnow::token::literal;
 
 use crate::ast::*;
-use crate::parse_utils::{duration_value, ident, kw, quoted_string, ws_skip};
+use crate::parse_utils::{duration_value, ident, kw, nonneg_integer, quoted_string, ws_skip};
 
 use super::expr;
 
@@ -75,6 +77,7 @@ pub(super) fn match_clause_only(input: &mut &str) -> ModalResult<MatchClause> {
 ///   `[key, key, ...] : duration`               (sliding window)
 ///   `[key, key, ...] : duration : fixed`       (fixed window)
 ///   `[key, key, ...] : session(gap)`           (session window, L3)
+///   `[key, key, ...] : session(gap, max_duration=dur, min_events=n)`
 fn match_params(input: &mut &str) -> ModalResult<(Vec<FieldRef>, std::time::Duration, WindowMode)> {
     ws_skip.parse_next(input)?;
 
@@ -107,13 +110,19 @@ fn match_params(input: &mut &str) -> ModalResult<(Vec<FieldRef>, std::time::Dura
             )))
             .parse_next(input)?;
         ws_skip.parse_next(input)?;
+        let mut spec = SessionSpec::new(gap);
+        while opt(literal(",")).parse_next(input)?.is_some() {
+            ws_skip.parse_next(input)?;
+            session_option(input, &mut spec)?;
+            ws_skip.parse_next(input)?;
+        }
         cut_err(literal(")"))
             .context(StrContext::Expected(StrContextValue::Description(
                 "')' after session gap",
             )))
             .parse_next(input)?;
         ws_skip.parse_next(input)?;
-        return Ok((keys, gap, WindowMode::Session(gap)));
+        return Ok((keys, gap, WindowMode::Session(spec)));
     }
 
     // Parse duration for sliding/fixed window
@@ -141,6 +150,37 @@ fn match_params(input: &mut &str) -> ModalResult<(Vec<FieldRef>, std::time::Dura
     Ok((keys, dur, window_mode))
 }
 
+/// One `max_duration=dur` or `min_events=n` option inside `session(...)`.
+fn session_option(input: &mut &str, spec: &mut SessionSpec) -> ModalResult<()> {
+    if opt(kw("max_duration")).parse_next(input)?.is_some() {
+        ws_skip.parse_next(input)?;
+        cut_err(literal("=")).parse_next(input)?;
+        ws_skip.parse_next(input)?;
+        let max = cut_err(duration_value)
+            .context(StrContext::Expected(StrContextValue::Description(
+                "duration for session max_duration",
+            )))
+            .parse_next(input)?;
+        spec.max_duration = Some(max);
+        return Ok(());
+    }
+    cut_err(kw("min_events"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "session option 'max_duration' or 'min_events'",
+        )))
+        .parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    cut_err(literal("=")).parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    let n = cut_err(nonneg_integer)
+        .context(StrContext::Expected(StrContextValue::Description(
+            "integer for session min_events",
+        )))
+        .parse_next(input)?;
+    spec.min_events = Some(n as u64);
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // key mapping block
 // ---------------------------------------------------------------------------
@@ -360,7 +400,7 @@ fn pipe_chain(input: &mut &str) -> ModalResult<PipeChain> {
             // Must be a measure
             let measure = cut_err(measure)
                 .context(StrContext::Expected(StrContextValue::Description(
-                    "measure (count|sum|avg|min|max)",
+                    "measure (count|sum|avg|min|max|rate)",
                 )))
                 .parse_next(input)?;
 
@@ -392,11 +432,30 @@ fn measure(input: &mut &str) -> ModalResult<Measure> {
         kw("avg").map(|_| Measure::Avg),
         kw("min").map(|_| Measure::Min),
         kw("max").map(|_| Measure::Max),
+        rate_measure,
     ))
     .parse_next(input)
 }
 
-fn cmp_op_step(input: &mut &str) -> ModalResult<CmpOp> {
+/// `rate` or `rate(<duration>)`; the unit defaults to one second.
+fn rate_measure(input: &mut &str) -> ModalResult<Measure> {
+    kw("rate").parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    if opt(literal("(")).parse_next(input)?.is_none() {
+        return Ok(Measure::Rate(Duration::from_secs(1)));
+    }
+    ws_skip.parse_next(input)?;
+    let unit = cut_err(duration_value)
+        .context(StrContext::Expected(StrContextValue::Description(
+            "rate unit duration",
+        )))
+        .parse_next(input)?;
+    ws_skip.parse_next(input)?;
+    cut_err(literal(")")).parse_next(input)?;
+    Ok(Measure::Rate(unit))
+}
+
+pub(super) fn cmp_op_step(input: &mut &str) -> ModalResult<CmpOp> {
     alt((
         literal("==").value(CmpOp::Eq),
         literal("!=").value(CmpOp::Ne),
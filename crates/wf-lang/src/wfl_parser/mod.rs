@@ -22,10 +22,50 @@ mod tests;
 // ---------------------------------------------------------------------------
 
 /// Parse a `.wfl` file containing `use` declarations and `rule` definitions.
+///
+/// On a syntax error the returned `anyhow::Error` wraps a [`WflParseError`],
+/// which callers can downcast to get the error position.
 pub fn parse_wfl(input: &str) -> anyhow::Result<WflFile> {
-    wfl_file
-        .parse(input)
-        .map_err(|e| anyhow::anyhow!("parse error: {e}"))
+    wfl_file.parse(input).map_err(|e| {
+        let (line, column) = line_col(input, e.offset());
+        let message = e.inner().to_string();
+        anyhow::Error::new(WflParseError {
+            line,
+            column,
+            message: if message.is_empty() {
+                "invalid syntax".to_string()
+            } else {
+                message
+            },
+            rendered: e.to_string(),
+        })
+    })
+}
+
+/// A `.wfl` syntax error and the 1-based position where parsing stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WflParseError {
+    pub line: usize,
+    /// Column in characters.
+    pub column: usize,
+    /// What the parser expected at that position.
+    pub message: String,
+    rendered: String,
+}
+
+impl std::fmt::Display for WflParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parse error: {}", self.rendered)
+    }
+}
+
+impl std::error::Error for WflParseError {}
+
+fn line_col(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset.min(input.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
 }
 
 // ---------------------------------------------------------------------------
@@ -1,4 +1,4 @@
-use winnow::combinator::{cut_err, opt, repeat, separated};
+use winnow::combinator::{cut_err, opt, preceded, repeat, separated};
 use winnow::error::{StrContext, StrContextValue};
 use winnow::prelude::*;
 use winnow::token::literal;
@@ -134,13 +134,18 @@ pub(super) fn rule_decl_with_patterns(
         )))
         .parse_next(input)?;
 
-    // Required yield clause
+    // Required yield clause, optionally followed by more (fan-out)
     ws_skip.parse_next(input)?;
-    let yield_clause = cut_err(clauses::yield_clause)
+    let first_yield = cut_err(clauses::yield_clause)
         .context(StrContext::Expected(StrContextValue::Description(
             "yield clause",
         )))
         .parse_next(input)?;
+    let more_yields: Vec<YieldClause> =
+        repeat(0.., preceded(ws_skip, clauses::yield_clause)).parse_next(input)?;
+    let mut yields = Vec::with_capacity(1 + more_yields.len());
+    yields.push(first_yield);
+    yields.extend(more_yields);
 
     // Optional conv block (L3, fixed window only — checker enforces constraint)
     ws_skip.parse_next(input)?;
@@ -166,7 +171,7 @@ pub(super) fn rule_decl_with_patterns(
         joins,
         pipeline_stages,
         entity,
-        yield_clause,
+        yields,
         pattern_origin,
         conv,
         limits,
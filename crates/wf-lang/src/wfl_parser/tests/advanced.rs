@@ -178,6 +178,7 @@ rule r {
     assert_eq!(
         rule.joins[0].mode,
         JoinMode::Asof {
+            direction: AsofDirection::Backward,
             within: Some(Duration::from_secs(600))
         }
     );
@@ -195,7 +196,68 @@ rule r {
 }
 "#;
     let file = parse_wfl(input).unwrap();
-    assert_eq!(file.rules[0].joins[0].mode, JoinMode::Asof { within: None });
+    assert_eq!(
+        file.rules[0].joins[0].mode,
+        JoinMode::Asof {
+            direction: AsofDirection::Backward,
+            within: None
+        }
+    );
+}
+
+#[test]
+fn parse_join_asof_direction() {
+    let input = r#"
+rule r {
+    events { e : win }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join resp_log asof forward within 30s on sip == resp_log.src_ip
+    join geo_log asof backward on sip == geo_log.src_ip
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let joins = &file.rules[0].joins;
+    assert_eq!(
+        joins[0].mode,
+        JoinMode::Asof {
+            direction: AsofDirection::Forward,
+            within: Some(Duration::from_secs(30))
+        }
+    );
+    assert_eq!(
+        joins[1].mode,
+        JoinMode::Asof {
+            direction: AsofDirection::Backward,
+            within: None
+        }
+    );
+}
+
+#[test]
+fn parse_join_asof_optional() {
+    let input = r#"
+rule r {
+    events { e : win }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join geo_log asof within 10m optional on sip == geo_log.src_ip
+    join rep_db snapshot on sip == rep_db.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let joins = &file.rules[0].joins;
+    assert!(joins[0].optional);
+    assert_eq!(
+        joins[0].mode,
+        JoinMode::Asof {
+            direction: AsofDirection::Backward,
+            within: Some(Duration::from_secs(600))
+        }
+    );
+    assert!(!joins[1].optional);
 }
 
 #[test]
@@ -229,6 +291,40 @@ rule r {
     assert_eq!(file.rules[0].joins[0].conditions.len(), 2);
 }
 
+#[test]
+fn parse_join_range_conditions() {
+    let input = r#"
+rule r {
+    events { e : win }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join t snapshot on sip == t.ip && amount>=t.low && amount < t.high && code != t.code
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let ops: Vec<CmpOp> = file.rules[0].joins[0]
+        .conditions
+        .iter()
+        .map(|c| c.op)
+        .collect();
+    assert_eq!(ops, vec![CmpOp::Eq, CmpOp::Ge, CmpOp::Lt, CmpOp::Ne]);
+}
+
+#[test]
+fn parse_join_cond_requires_operator() {
+    let input = r#"
+rule r {
+    events { e : win }
+    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)
+    join t snapshot on sip t.ip
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    assert!(parse_wfl(input).is_err());
+}
+
 // -----------------------------------------------------------------------
 // L2: baseline() with duration argument
 // -----------------------------------------------------------------------
@@ -376,7 +472,7 @@ rule r {
 }
 "#;
     let file = parse_wfl(input).unwrap();
-    let y = &file.rules[0].yield_clause;
+    let y = &file.rules[0].yields[0];
     assert!(matches!(y.args[1].value, Expr::IfThenElse { .. }));
 }
 
@@ -395,7 +491,7 @@ rule r {
 }
 "#;
     let file = parse_wfl(input).unwrap();
-    let y = &file.rules[0].yield_clause;
+    let y = &file.rules[0].yields[0];
     assert_eq!(y.target, "out");
     assert_eq!(y.version, Some(2));
     assert_eq!(y.args.len(), 1);
@@ -412,5 +508,5 @@ rule r {
 }
 "#;
     let file = parse_wfl(input).unwrap();
-    assert_eq!(file.rules[0].yield_clause.version, None);
+    assert_eq!(file.rules[0].yields[0].version, None);
 }
@@ -82,10 +82,36 @@ rule r {
 }
 "#;
     let file = parse_wfl(input).unwrap();
-    let y = &file.rules[0].yield_clause;
+    let y = &file.rules[0].yields[0];
     assert_eq!(y.target, "security_alerts");
     assert_eq!(y.args.len(), 3);
     assert_eq!(y.args[0].name, "sip");
     assert_eq!(y.args[1].name, "fail_count");
     assert_eq!(y.args[2].name, "message");
 }
+
+#[test]
+fn parse_multiple_yield_clauses() {
+    let input = r#"
+rule r {
+    events { fail : auth_events }
+    match<sip:5m> { on event { fail | count >= 3; } } -> score(70.0)
+    entity(ip, fail.sip)
+    yield security_alerts (sip = fail.sip)
+    yield alert_feed@v2 (
+        sip = fail.sip,
+        fail_count = count(fail)
+    )
+    limits { max_instances = 10; on_exceed = throttle; }
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let rule = &file.rules[0];
+    assert_eq!(rule.yields.len(), 2);
+    assert_eq!(rule.yields[0].target, "security_alerts");
+    assert_eq!(rule.yields[0].args.len(), 1);
+    assert_eq!(rule.yields[1].target, "alert_feed");
+    assert_eq!(rule.yields[1].version, Some(2));
+    assert_eq!(rule.yields[1].args.len(), 2);
+    assert!(rule.limits.is_some());
+}
@@ -94,3 +94,16 @@ contract ct for r {
 "#;
     assert!(parse_wfl(input).is_err());
 }
+
+#[test]
+fn parse_error_reports_position() {
+    let input =
+        "rule r {\n    events { e : win }\n    match<:5m> { on event { e | count >= 1; } }\n}\n";
+    let err = parse_wfl(input).unwrap_err();
+    let pe = err
+        .downcast_ref::<crate::WflParseError>()
+        .expect("syntax errors carry a WflParseError");
+    // Missing `-> score(...)`: parsing stops at the closing brace.
+    assert_eq!((pe.line, pe.column), (4, 1), "{pe:?}");
+    assert!(err.to_string().starts_with("parse error: "));
+}
@@ -55,8 +55,8 @@ rule brute_force_then_scan {
     assert!(rule.match_clause.on_close.is_none());
     assert_eq!(rule.score.expr, Expr::Number(80.0));
     assert_eq!(rule.entity.entity_type, EntityTypeVal::Ident("ip".into()));
-    assert_eq!(rule.yield_clause.target, "security_alerts");
-    assert_eq!(rule.yield_clause.args.len(), 4);
+    assert_eq!(rule.yields[0].target, "security_alerts");
+    assert_eq!(rule.yields[0].args.len(), 4);
 }
 
 #[test]
@@ -64,7 +64,7 @@ rule r {
 }
 "#;
     let file = parse_wfl(input).unwrap();
-    let y = &file.rules[0].yield_clause;
+    let y = &file.rules[0].yields[0];
 
     // count(fail)
     match &y.args[0].value {
@@ -105,7 +105,7 @@ rule r {
 }
 "#;
     let file = parse_wfl(input).unwrap();
-    let y = &file.rules[0].yield_clause;
+    let y = &file.rules[0].yields[0];
     assert_eq!(y.args[0].value, Expr::Field(FieldRef::Simple("sip".into())));
     assert_eq!(
         y.args[1].value,
@@ -199,6 +199,123 @@ rule r {
     }
 }
 
+#[test]
+fn parse_expr_contains() {
+    let input = r#"
+rule r {
+    events {
+        a : win && tags contains "admin"
+        b : win && tags contains any ("root", "admin")
+        c : win && not tags contains all ("ops", "oncall") && active
+    }
+    match<:5m> { on event { a | count >= 1; } } -> score(50.0)
+    entity(ip, a.sip)
+    yield out (x = a.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let decls = &file.rules[0].events.decls;
+    match decls[0].filter.as_ref().unwrap() {
+        Expr::Contains { expr, mode, values } => {
+            assert_eq!(**expr, Expr::Field(FieldRef::Simple("tags".into())));
+            assert_eq!(*mode, ContainsMode::Single);
+            assert_eq!(values, &[Expr::StringLit("admin".into())]);
+        }
+        other => panic!("expected Contains, got {other:?}"),
+    }
+    match decls[1].filter.as_ref().unwrap() {
+        Expr::Contains { mode, values, .. } => {
+            assert_eq!(*mode, ContainsMode::Any);
+            assert_eq!(values.len(), 2);
+        }
+        other => panic!("expected Contains, got {other:?}"),
+    }
+    // `not` binds looser than `contains`, tighter than `&&`.
+    let Expr::BinOp {
+        op: BinOp::And,
+        left,
+        ..
+    } = decls[2].filter.as_ref().unwrap()
+    else {
+        panic!("expected And");
+    };
+    let Expr::Not(inner) = left.as_ref() else {
+        panic!("expected Not, got {left:?}");
+    };
+    assert!(matches!(
+        inner.as_ref(),
+        Expr::Contains {
+            mode: ContainsMode::All,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn parse_expr_logical_not() {
+    let input = r#"
+rule r {
+    events { e : win && not (action == "a" && count > 1) || !active }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let filter = file.rules[0].events.decls[0].filter.as_ref().unwrap();
+    // (not (a && b)) || (!active)
+    match filter {
+        Expr::BinOp {
+            op: BinOp::Or,
+            left,
+            right,
+        } => {
+            match left.as_ref() {
+                Expr::Not(inner) => {
+                    assert!(matches!(inner.as_ref(), Expr::BinOp { op: BinOp::And, .. }))
+                }
+                other => panic!("expected Not, got {other:?}"),
+            }
+            match right.as_ref() {
+                Expr::Not(inner) => assert!(matches!(inner.as_ref(), Expr::Field(_))),
+                other => panic!("expected Not, got {other:?}"),
+            }
+        }
+        other => panic!("expected Or, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_expr_not_binds_looser_than_comparison() {
+    let input = r#"
+rule r {
+    events { e : win && not action == "a" && ok != true }
+    match<:5m> { on event { e | count >= 1; } } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let filter = file.rules[0].events.decls[0].filter.as_ref().unwrap();
+    // (not (action == "a")) && (ok != true) — `!=` is not a negation prefix
+    match filter {
+        Expr::BinOp {
+            op: BinOp::And,
+            left,
+            right,
+        } => {
+            match left.as_ref() {
+                Expr::Not(inner) => {
+                    assert!(matches!(inner.as_ref(), Expr::BinOp { op: BinOp::Eq, .. }))
+                }
+                other => panic!("expected Not, got {other:?}"),
+            }
+            assert!(matches!(right.as_ref(), Expr::BinOp { op: BinOp::Ne, .. }));
+        }
+        other => panic!("expected And, got {other:?}"),
+    }
+}
+
 #[test]
 fn parse_expr_parenthesized() {
     let input = r#"
@@ -25,8 +25,10 @@ rule session_test {
     let match_clause = &file.rules[0].match_clause;
     assert_eq!(match_clause.keys.len(), 1);
     match match_clause.window_mode {
-        WindowMode::Session(gap) => {
-            assert_eq!(gap.as_secs(), 30 * 60);
+        WindowMode::Session(spec) => {
+            assert_eq!(spec.gap.as_secs(), 30 * 60);
+            assert_eq!(spec.max_duration, None);
+            assert_eq!(spec.min_events, None);
         }
         _ => panic!("expected Session window mode"),
     }
@@ -49,13 +51,40 @@ rule session_test {
     let match_clause = &file.rules[0].match_clause;
     assert!(match_clause.keys.is_empty());
     match match_clause.window_mode {
-        WindowMode::Session(gap) => {
-            assert_eq!(gap.as_secs(), 5 * 60);
+        WindowMode::Session(spec) => {
+            assert_eq!(spec.gap.as_secs(), 5 * 60);
         }
         _ => panic!("expected Session window mode"),
     }
 }
 
+#[test]
+fn parse_match_session_window_caps() {
+    let input = r#"
+rule session_test {
+    events { e : win }
+    match<uid:session(5m, max_duration=1h, min_events=3)> {
+        on event { e | count >= 1; }
+        on close { e | count >= 1; }
+    } -> score(50.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    match file.rules[0].match_clause.window_mode {
+        WindowMode::Session(spec) => {
+            assert_eq!(spec.gap, Duration::from_secs(5 * 60));
+            assert_eq!(spec.max_duration, Some(Duration::from_secs(3600)));
+            assert_eq!(spec.min_events, Some(3));
+        }
+        _ => panic!("expected Session window mode"),
+    }
+
+    let bad = input.replace("min_events=3", "max_events=3");
+    assert!(parse_wfl(&bad).is_err());
+}
+
 // -----------------------------------------------------------------------
 // Match clause - Sliding/Fixed window
 // -----------------------------------------------------------------------
@@ -174,6 +203,32 @@ rule r {
     assert_eq!(step.branches[1].source, "b");
 }
 
+#[test]
+fn parse_signed_and_fractional_thresholds() {
+    let input = r#"
+rule r {
+    events { e : win }
+    match<sip:5m> {
+        on event {
+            e.delta | sum >= -5;
+            e.delta | avg >= 0.5;
+        }
+    } -> score(60.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let steps = &file.rules[0].match_clause.on_event;
+    assert_eq!(steps[0].branches[0].pipe.measure, Measure::Sum);
+    assert_eq!(
+        steps[0].branches[0].pipe.threshold,
+        Expr::Neg(Box::new(Expr::Number(5.0)))
+    );
+    assert_eq!(steps[1].branches[0].pipe.measure, Measure::Avg);
+    assert_eq!(steps[1].branches[0].pipe.threshold, Expr::Number(0.5));
+}
+
 // -----------------------------------------------------------------------
 // on close block
 // -----------------------------------------------------------------------
@@ -297,3 +352,30 @@ rule r {
     assert_eq!(mc.duration, Duration::from_secs(10));
     assert_eq!(mc.window_mode, WindowMode::Fixed);
 }
+
+#[test]
+fn parse_rate_measure() {
+    let input = r#"
+rule r {
+    events { e : fw_events }
+    match<sip:5m> {
+        on event {
+            e | rate > 100;
+            e | rate(1m) >= 1.5;
+        }
+    } -> score(70.0)
+    entity(ip, e.sip)
+    yield out (x = e.sip)
+}
+"#;
+    let file = parse_wfl(input).unwrap();
+    let steps = &file.rules[0].match_clause.on_event;
+    assert_eq!(
+        steps[0].branches[0].pipe.measure,
+        Measure::Rate(Duration::from_secs(1))
+    );
+    assert_eq!(
+        steps[1].branches[0].pipe.measure,
+        Measure::Rate(Duration::from_secs(60))
+    );
+}
@@ -1,45 +1,269 @@
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use tokio::sync::mpsc;
 
+use wf_config::PriorityRoute;
 use wf_core::alert::OutputRecord;
 use wf_core::sink::SinkDispatcher;
 
 use crate::metrics::RuntimeMetrics;
 
-/// Bounded channel capacity for the alert pipeline.
-pub const ALERT_CHANNEL_CAPACITY: usize = 64;
+/// Collapses repeats of the same `(rule_name, entity_type, entity_id)` within
+/// a TTL, tracked per yield target so a rule that fans out to several
+/// targets still reaches each of them. The window starts at the last alert
+/// that was let through, so a steady stream of duplicates still produces one
+/// alert per TTL.
+pub struct AlertSuppressor {
+    ttl: Duration,
+    last_passed: HashMap<(String, String, String, String), Instant>,
+    last_prune: Instant,
+}
+
+impl AlertSuppressor {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            last_passed: HashMap::new(),
+            last_prune: Instant::now(),
+        }
+    }
+
+    /// Returns `true` if `record` should be dispatched, `false` if it repeats
+    /// an alert that passed less than `ttl` before `now`.
+    pub fn admit(&mut self, record: &OutputRecord, now: Instant) -> bool {
+        if now.duration_since(self.last_prune) >= self.ttl {
+            let ttl = self.ttl;
+            self.last_passed
+                .retain(|_, passed| now.duration_since(*passed) < ttl);
+            self.last_prune = now;
+        }
+        let key = (
+            record.rule_name.clone(),
+            record.entity_type.clone(),
+            record.entity_id.clone(),
+            record.yield_target.clone(),
+        );
+        match self.last_passed.get(&key) {
+            Some(passed) if now.duration_since(*passed) < self.ttl => false,
+            _ => {
+                self.last_passed.insert(key, now);
+                true
+            }
+        }
+    }
+}
+
+/// Most records drained from the channel in one go and ordered by priority.
+const PRIORITY_BATCH: usize = 256;
+
+/// Stable-sort records so higher rule priority comes first; records of equal
+/// priority keep their arrival order.
+pub fn order_by_priority(records: &mut [OutputRecord]) {
+    records.sort_by_key(|r| std::cmp::Reverse(r.priority));
+}
+
+/// Targets `record` is dispatched to: its yield target, plus the priority
+/// route's target when the record's priority reaches `route.min`.
+pub fn dispatch_targets<'a>(
+    record: &'a OutputRecord,
+    route: Option<&'a PriorityRoute>,
+) -> Vec<&'a str> {
+    let mut targets = vec![record.yield_target.as_str()];
+    if let Some(route) = route
+        && record.priority >= route.min
+    {
+        targets.push(route.target.as_str());
+    }
+    targets
+}
 
 /// Consume alert records from the channel and route them via the connector-based
 /// `SinkDispatcher`.
 ///
+/// Records that are already queued together (concurrent matches) are
+/// dispatched highest priority first. With `priority_route` set, records at
+/// or above its `min` are also dispatched to its `target`.
+///
 /// Shutdown is driven by channel close: when the scheduler finishes
 /// its drain + flush and drops its `Sender<OutputRecord>`, `rx.recv()` returns
 /// `None` and this task exits. After all records are consumed, all sinks in
 /// the dispatcher are gracefully stopped.
+///
+/// With `suppress_ttl` set, duplicates are dropped by an [`AlertSuppressor`]
+/// before serialization and counted in `wf_alert_suppressed_total`.
+///
+/// Every sink that fails an alert is logged and counted in
+/// `wf_alert_sink_failed_total{sink}`; `wf_alert_dispatch_total` only counts
+/// alerts that every target sink accepted.
 pub async fn run_alert_dispatcher(
     mut rx: mpsc::Receiver<OutputRecord>,
     dispatcher: Arc<SinkDispatcher>,
     metrics: Option<Arc<RuntimeMetrics>>,
+    suppress_ttl: Option<Duration>,
+    priority_route: Option<PriorityRoute>,
 ) {
-    while let Some(record) = rx.recv().await {
-        let json = match serde_json::to_string(&record) {
-            Ok(j) => j,
-            Err(e) => {
+    let mut suppressor = suppress_ttl.map(AlertSuppressor::new);
+    let mut pending = Vec::with_capacity(PRIORITY_BATCH);
+    while rx.recv_many(&mut pending, PRIORITY_BATCH).await > 0 {
+        order_by_priority(&mut pending);
+        for record in pending.drain(..) {
+            if let Some(suppressor) = &mut suppressor
+                && !suppressor.admit(&record, Instant::now())
+            {
                 if let Some(metrics) = &metrics {
-                    metrics.inc_alert_serialize_failed();
+                    metrics.inc_alert_suppressed();
                 }
-                log::warn!("alert serialize error: {e}");
+                log::debug!(
+                    "alert suppressed: rule={} entity={}:{}",
+                    record.rule_name,
+                    record.entity_type,
+                    record.entity_id
+                );
                 continue;
             }
-        };
-        let dispatch_started = Instant::now();
-        dispatcher.dispatch(&record.yield_target, &json).await;
-        if let Some(metrics) = &metrics {
-            metrics.inc_alert_dispatch();
-            metrics.observe_alert_dispatch(dispatch_started.elapsed());
+            let json = match serde_json::to_string(&record) {
+                Ok(j) => j,
+                Err(e) => {
+                    if let Some(metrics) = &metrics {
+                        metrics.inc_alert_serialize_failed();
+                    }
+                    log::warn!("alert serialize error: {e}");
+                    continue;
+                }
+            };
+            let dispatch_started = Instant::now();
+            let mut failed = false;
+            for target in dispatch_targets(&record, priority_route.as_ref()) {
+                let report = dispatcher.dispatch(target, &record, &json).await;
+                for (sink, error) in &report.errors {
+                    failed = true;
+                    if let Some(metrics) = &metrics {
+                        metrics.inc_alert_sink_failed(sink);
+                    }
+                    log::warn!(
+                        "alert dispatch failed: rule={} target={target} sink={sink}: {error}",
+                        record.rule_name
+                    );
+                }
+            }
+            if let Some(metrics) = &metrics {
+                if !failed {
+                    metrics.inc_alert_dispatch();
+                }
+                metrics.observe_alert_dispatch(dispatch_started.elapsed());
+            }
         }
     }
     dispatcher.stop_all().await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wf_core::alert::AlertOrigin;
+
+    fn alert(rule: &str, entity_id: &str) -> OutputRecord {
+        OutputRecord {
+            wfx_id: String::new(),
+            rule_name: rule.to_string(),
+            score: 50.0,
+            entity_type: "ip".to_string(),
+            entity_id: entity_id.to_string(),
+            origin: AlertOrigin::Event,
+            fired_at: String::new(),
+            matched_rows: vec![],
+            summary: String::new(),
+            priority: 0,
+            yield_target: "security_alerts".to_string(),
+            yield_fields: vec![],
+            event_time_nanos: 0,
+        }
+    }
+
+    #[test]
+    fn suppresses_duplicates_within_ttl() {
+        let mut suppressor = AlertSuppressor::new(Duration::from_secs(60));
+        let t0 = Instant::now();
+        let first = alert("brute_force", "10.0.0.1");
+
+        assert!(suppressor.admit(&first, t0));
+        assert!(!suppressor.admit(&first, t0 + Duration::from_secs(1)));
+        assert!(!suppressor.admit(&first, t0 + Duration::from_secs(59)));
+
+        // Different entity or rule is a different key.
+        assert!(suppressor.admit(&alert("brute_force", "10.0.0.2"), t0));
+        assert!(suppressor.admit(&alert("port_scan", "10.0.0.1"), t0));
+
+        // So is the same alert fanned out to another yield target.
+        let mut fanned = first.clone();
+        fanned.yield_target = "alert_feed".to_string();
+        assert!(suppressor.admit(&fanned, t0 + Duration::from_secs(1)));
+
+        // Once the TTL has elapsed the alert passes again and restarts the window.
+        assert!(suppressor.admit(&first, t0 + Duration::from_secs(60)));
+        assert!(!suppressor.admit(&first, t0 + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn prunes_expired_keys() {
+        let mut suppressor = AlertSuppressor::new(Duration::from_secs(10));
+        let t0 = Instant::now();
+        for i in 0..100 {
+            assert!(suppressor.admit(&alert("r", &format!("10.0.0.{i}")), t0));
+        }
+        assert_eq!(suppressor.last_passed.len(), 100);
+
+        assert!(suppressor.admit(&alert("r", "10.0.1.1"), t0 + Duration::from_secs(30)));
+        assert_eq!(suppressor.last_passed.len(), 1);
+    }
+
+    fn prioritized(rule: &str, priority: i64) -> OutputRecord {
+        OutputRecord {
+            priority,
+            ..alert(rule, "10.0.0.1")
+        }
+    }
+
+    #[test]
+    fn concurrent_matches_dispatch_highest_priority_first() {
+        // Four rules fired for the same entity and were queued together.
+        let mut batch = vec![
+            prioritized("low", 0),
+            prioritized("high_a", 10),
+            prioritized("mid", 5),
+            prioritized("high_b", 10),
+        ];
+        order_by_priority(&mut batch);
+        let order: Vec<&str> = batch.iter().map(|r| r.rule_name.as_str()).collect();
+        // Equal priorities keep their arrival order.
+        assert_eq!(order, ["high_a", "high_b", "mid", "low"]);
+
+        // The priority tags the alert JSON; the default 0 is left out.
+        let json: serde_json::Value = serde_json::to_value(&batch[0]).unwrap();
+        assert_eq!(json["priority"], 10);
+        let json: serde_json::Value = serde_json::to_value(&batch[3]).unwrap();
+        assert!(json.get("priority").is_none());
+    }
+
+    #[test]
+    fn priority_route_adds_dedicated_target() {
+        let route = PriorityRoute {
+            min: 10,
+            target: "critical_alerts".to_string(),
+        };
+        assert_eq!(
+            dispatch_targets(&prioritized("r", 10), Some(&route)),
+            ["security_alerts", "critical_alerts"]
+        );
+        assert_eq!(
+            dispatch_targets(&prioritized("r", 9), Some(&route)),
+            ["security_alerts"]
+        );
+        assert_eq!(
+            dispatch_targets(&prioritized("r", 99), None),
+            ["security_alerts"]
+        );
+    }
+}
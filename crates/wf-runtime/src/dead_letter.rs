@@ -0,0 +1,172 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use arrow::json::ArrayWriter;
+use arrow::record_batch::RecordBatch;
+use wf_core::window::{Rejection, RouteReport};
+
+/// Append-only NDJSON file collecting batches the router could not deliver.
+///
+/// Each line is one rejected delivery:
+///
+/// ```json
+/// {"received_at_ms":1700000000000,"source":"tcp","stream":"syslog",
+///  "window":"auth_events","reason":"schema mismatch: ...","rows":2,
+///  "events":[{"sip":"10.0.0.1"},{"sip":"10.0.0.2"}]}
+/// ```
+///
+/// `window` is `null` when no window subscribes to the stream.
+pub struct DeadLetterSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl DeadLetterSink {
+    /// Open (or create) `path` for appending, creating parent directories.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("opening dead-letter file {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one rejected delivery of `batch`.
+    pub fn write(
+        &self,
+        source: &str,
+        stream: &str,
+        rejection: &Rejection,
+        batch: &RecordBatch,
+    ) -> anyhow::Result<()> {
+        let mut writer = ArrayWriter::new(Vec::new());
+        writer.write(batch)?;
+        writer.finish()?;
+        let events: serde_json::Value = match writer.into_inner() {
+            buf if buf.is_empty() => serde_json::Value::Array(Vec::new()),
+            buf => serde_json::from_slice(&buf)?,
+        };
+        let received_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let mut line = serde_json::to_vec(&serde_json::json!({
+            "received_at_ms": received_at_ms,
+            "source": source,
+            "stream": stream,
+            "window": rejection.window,
+            "reason": rejection.reason,
+            "rows": batch.num_rows(),
+            "events": events,
+        }))?;
+        line.push(b'\n');
+        let mut file = self.file.lock().expect("dead-letter lock poisoned");
+        file.write_all(&line)?;
+        Ok(())
+    }
+}
+
+/// Log every rejection in `report` and, when configured, append it to the
+/// dead-letter file. `stream` is the stream tag or the target window.
+pub(crate) fn handle_rejections(
+    report: &RouteReport,
+    source: &str,
+    stream: &str,
+    batch: &RecordBatch,
+    dead_letter: Option<&DeadLetterSink>,
+) {
+    for rejection in &report.rejected {
+        wf_warn!(
+            pipe,
+            source = source,
+            stream = stream,
+            window = rejection.window.as_deref().unwrap_or("-"),
+            rows = batch.num_rows(),
+            reason = &*rejection.reason,
+            "batch rejected by router"
+        );
+        if let Some(dlq) = dead_letter
+            && let Err(e) = dlq.write(source, stream, rejection, batch)
+        {
+            wf_warn!(pipe,
+                path = %dlq.path().display(),
+                error = %e,
+                "dead-letter write failed"
+            );
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    #[test]
+    fn appends_one_json_line_per_rejection() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/dlq.ndjson");
+        let dlq = DeadLetterSink::open(&path).unwrap();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("sip", DataType::Utf8, true),
+            Field::new("n", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec![Some("10.0.0.1"), None])),
+                Arc::new(Int64Array::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+        let rejection = Rejection {
+            window: Some("auth_events".into()),
+            reason: "schema mismatch".into(),
+        };
+        dlq.write("tcp", "syslog", &rejection, &batch).unwrap();
+        let unrouted = Rejection {
+            window: None,
+            reason: "no window subscribes to stream \"x\"".into(),
+        };
+        dlq.write("tcp", "x", &unrouted, &batch).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["stream"], "syslog");
+        assert_eq!(lines[0]["window"], "auth_events");
+        assert_eq!(lines[0]["reason"], "schema mismatch");
+        assert_eq!(lines[0]["rows"], 2);
+        assert_eq!(lines[0]["events"][0]["sip"], "10.0.0.1");
+        assert_eq!(lines[0]["events"][1]["n"], 2);
+        assert!(lines[1]["window"].is_null());
+    }
+}
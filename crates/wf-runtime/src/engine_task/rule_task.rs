@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use arrow::array::{
     ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, TimestampNanosecondArray,
@@ -10,11 +10,14 @@ use arrow::array::{
 use arrow::datatypes::DataType;
 use arrow::record_batch::RecordBatch;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+use wf_config::AlertOverflowPolicy;
 
 use wf_core::alert::OutputRecord;
-use wf_core::rule::{CepStateMachine, CloseReason, RuleExecutor, StepResult, batch_to_events};
+use wf_core::rule::{CepStateMachine, CloseReason, RuleExecutor, StepResult, bind_filter_passes};
 use wf_core::window::{AppendOutcome, Router};
-use wf_lang::plan::ConvPlan;
+use wf_lang::plan::{ConvPlan, ExprPlan, WindowSpec};
 
 use crate::metrics::RuntimeMetrics;
 
@@ -25,6 +28,22 @@ use super::window_lookup::RegistryLookup;
 const PIPE_WINDOW_PREFIX: &str = "__wf_pipe_";
 const PIPE_EVENT_TIME_FIELD: &str = "__wf_pipe_ts";
 
+/// Bounds for the timeout scan interval derived from a rule's window.
+const MIN_TIMEOUT_SCAN_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_TIMEOUT_SCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default timeout scan interval for a rule: a tenth of its window (the
+/// inactivity gap for session windows), clamped to
+/// [`MIN_TIMEOUT_SCAN_INTERVAL`, `MAX_TIMEOUT_SCAN_INTERVAL`]. Short windows
+/// close promptly; long windows are not scanned needlessly often.
+pub(super) fn derive_timeout_scan_interval(spec: &WindowSpec) -> Duration {
+    let span = match spec {
+        WindowSpec::Sliding(d) | WindowSpec::Fixed(d) => *d,
+        WindowSpec::Session(s) => s.gap,
+    };
+    (span / 10).clamp(MIN_TIMEOUT_SCAN_INTERVAL, MAX_TIMEOUT_SCAN_INTERVAL)
+}
+
 // ---------------------------------------------------------------------------
 // RuleTask -- runtime state for a single rule
 // ---------------------------------------------------------------------------
@@ -40,7 +59,10 @@ pub(super) struct RuleTask {
     pub(super) sources: Vec<WindowSource>,
     /// window_name -> Vec<alias>: pre-computed from stream_aliases + window sources.
     aliases: HashMap<String, Vec<String>>,
+    /// alias -> bind filter, for binds declared as `alias : window && expr`.
+    filters: HashMap<String, ExprPlan>,
     alert_tx: mpsc::Sender<OutputRecord>,
+    alert_overflow: AlertOverflowPolicy,
     /// window_name -> cursor: tracks read position per window.
     pub(super) cursors: HashMap<String, u64>,
     /// Shared router for WindowLookup (joins + has()).
@@ -51,17 +73,14 @@ pub(super) struct RuleTask {
 impl RuleTask {
     pub(super) fn new(
         config: RuleTaskConfig,
-    ) -> (
-        Self,
-        tokio_util::sync::CancellationToken,
-        std::time::Duration,
-    ) {
+    ) -> (Self, tokio_util::sync::CancellationToken, Duration) {
         let RuleTaskConfig {
             machine,
             executor,
             window_sources,
             stream_aliases,
             alert_tx,
+            alert_overflow,
             cancel,
             timeout_scan_interval,
             router,
@@ -97,6 +116,15 @@ impl RuleTask {
         let seq = TASK_SEQ.fetch_add(1, Ordering::Relaxed);
         let task_id = format!("{}#{}", machine.rule_name(), seq);
         let conv_plan = executor.plan().conv_plan.clone();
+        let timeout_scan_interval = timeout_scan_interval.unwrap_or_else(|| {
+            derive_timeout_scan_interval(&executor.plan().match_plan.window_spec)
+        });
+        let filters: HashMap<String, ExprPlan> = executor
+            .plan()
+            .binds
+            .iter()
+            .filter_map(|b| Some((b.alias.clone(), b.filter.clone()?)))
+            .collect();
 
         let task = Self {
             task_id,
@@ -105,7 +133,9 @@ impl RuleTask {
             conv_plan,
             sources: window_sources,
             aliases,
+            filters,
             alert_tx,
+            alert_overflow,
             cursors,
             router,
             metrics,
@@ -117,12 +147,16 @@ impl RuleTask {
 
     /// Read new batches from all windows, convert to events, and advance
     /// the state machine.
+    ///
+    /// Batches are read as [`SharedBatch`](wf_core::window::SharedBatch)
+    /// handles, so rules reading the same window at the same time decode each
+    /// batch once. Every rule still advances its own machine over the events.
     pub(super) async fn pull_and_advance(&mut self) {
         for source in &self.sources {
             let cursor = self.cursors.get(&source.window_name).copied().unwrap_or(0);
             let (batches, new_cursor, gap) = {
                 let win = source.window.read().expect("lock poisoned");
-                let result = win.read_since(cursor);
+                let result = win.read_shared_since(cursor);
                 wf_debug!(pipe,
                     task_id = %self.task_id,
                     window = %source.window_name,
@@ -152,21 +186,33 @@ impl RuleTask {
             };
 
             for batch in &batches {
-                let events = batch_to_events(batch);
+                let events = batch.events();
                 if let Some(metrics) = &self.metrics {
                     metrics.add_rule_events(self.machine.rule_name(), events.len());
                 }
                 let lookup = RegistryLookup(&self.router);
-                for event in &events {
+                for event in events.iter() {
                     for alias in aliases {
+                        // Drop events rejected by the bind filter before they
+                        // can create or touch an instance.
+                        if let Some(filter) = self.filters.get(alias)
+                            && !bind_filter_passes(filter, event)
+                        {
+                            continue;
+                        }
                         if let StepResult::Matched(ctx) =
                             self.machine.advance_with(alias, event, Some(&lookup))
                         {
                             if let Some(metrics) = &self.metrics {
                                 metrics.inc_rule_match(self.machine.rule_name());
+                                metrics.sample_match(self.machine.rule_name(), &ctx);
                             }
                             match self.executor.execute_match_with_joins(&ctx, &lookup) {
-                                Ok(record) => self.emit(record).await,
+                                Ok(records) => {
+                                    for record in records {
+                                        self.emit(record).await;
+                                    }
+                                }
                                 Err(e) => {
                                     wf_warn!(pipe, task_id = %self.task_id, error = %e, "execute_match error")
                                 }
@@ -176,11 +222,22 @@ impl RuleTask {
                 }
             }
         }
+        self.record_suppressed();
         if let Some(metrics) = &self.metrics {
             metrics.set_rule_instances(self.machine.rule_name(), self.machine.instance_count());
         }
     }
 
+    /// Drain the machine's limit suppression counts into the metrics.
+    fn record_suppressed(&mut self) {
+        let suppressed = self.machine.take_suppressed();
+        if let Some(metrics) = &self.metrics {
+            for (reason, count) in suppressed {
+                metrics.add_rule_suppressed(self.machine.rule_name(), reason, count);
+            }
+        }
+    }
+
     // -- Timeout & shutdown -------------------------------------------------
 
     /// Scan for expired state machine instances and emit alerts.
@@ -192,13 +249,21 @@ impl RuleTask {
             .scan_expired_at_with_conv(self.machine.watermark_nanos(), self.conv_plan.as_ref())
         {
             match self.executor.execute_close_with_joins(close, &lookup) {
-                Ok(Some(record)) => self.emit(record).await,
-                Ok(None) => {}
+                Ok(records) if records.is_empty() => {}
+                Ok(records) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.inc_rule_close(self.machine.rule_name());
+                    }
+                    for record in records {
+                        self.emit(record).await;
+                    }
+                }
                 Err(e) => {
                     wf_warn!(pipe, task_id = %self.task_id, error = %e, "execute_close error")
                 }
             }
         }
+        self.record_suppressed();
         if let Some(metrics) = &self.metrics {
             metrics.observe_rule_scan_timeout(self.machine.rule_name(), started.elapsed());
             metrics.set_rule_instances(self.machine.rule_name(), self.machine.instance_count());
@@ -215,11 +280,16 @@ impl RuleTask {
             .close_all_with_conv(CloseReason::Flush, self.conv_plan.as_ref())
         {
             match self.executor.execute_close_with_joins(close, &lookup) {
-                Ok(Some(record)) => {
-                    self.emit(record).await;
-                    emitted += 1;
+                Ok(records) if records.is_empty() => {}
+                Ok(records) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.inc_rule_close(self.machine.rule_name());
+                    }
+                    emitted += records.len();
+                    for record in records {
+                        self.emit(record).await;
+                    }
                 }
-                Ok(None) => {}
                 Err(e) => {
                     wf_warn!(pipe, task_id = %self.task_id, error = %e, "execute_close flush error")
                 }
@@ -228,6 +298,7 @@ impl RuleTask {
         if emitted > 0 {
             wf_debug!(pipe, task_id = %self.task_id, alerts = emitted, "flush complete");
         }
+        self.record_suppressed();
         if let Some(metrics) = &self.metrics {
             metrics.observe_rule_flush(self.machine.rule_name(), started.elapsed());
             metrics.set_rule_instances(self.machine.rule_name(), self.machine.instance_count());
@@ -244,11 +315,25 @@ impl RuleTask {
         if let Some(metrics) = &self.metrics {
             metrics.inc_alert_emitted(&record.rule_name);
         }
-        if let Err(e) = self.alert_tx.send(record).await {
+        let closed = match self.alert_overflow {
+            AlertOverflowPolicy::Block => self.alert_tx.send(record).await.is_err(),
+            AlertOverflowPolicy::Drop => match self.alert_tx.try_send(record) {
+                Ok(()) => false,
+                Err(TrySendError::Full(_)) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.inc_alert_channel_dropped();
+                    }
+                    wf_debug!(pipe, task_id = %self.task_id, "alert channel full, alert dropped");
+                    false
+                }
+                Err(TrySendError::Closed(_)) => true,
+            },
+        };
+        if closed {
             if let Some(metrics) = &self.metrics {
                 metrics.inc_alert_channel_send_failed();
             }
-            wf_warn!(pipe, error = %e, "alert channel closed");
+            wf_warn!(pipe, "alert channel closed");
         }
     }
 
@@ -350,12 +435,18 @@ fn value_to_single_row_array(
     value: Option<&wf_core::rule::Value>,
 ) -> ArrayRef {
     match (data_type, value) {
+        (DataType::Int64, Some(wf_core::rule::Value::Int(i))) => {
+            Arc::new(Int64Array::from(vec![Some(*i)]))
+        }
         (DataType::Int64, Some(wf_core::rule::Value::Number(n))) => {
             Arc::new(Int64Array::from(vec![Some(*n as i64)]))
         }
         (DataType::Float64, Some(wf_core::rule::Value::Number(n))) => {
             Arc::new(Float64Array::from(vec![Some(*n)]))
         }
+        (DataType::Float64, Some(wf_core::rule::Value::Int(i))) => {
+            Arc::new(Float64Array::from(vec![Some(*i as f64)]))
+        }
         (DataType::Boolean, Some(wf_core::rule::Value::Bool(b))) => {
             Arc::new(BooleanArray::from(vec![Some(*b)]))
         }
@@ -365,9 +456,15 @@ fn value_to_single_row_array(
         (DataType::Utf8, Some(wf_core::rule::Value::Number(n))) => {
             Arc::new(StringArray::from(vec![Some(n.to_string())]))
         }
+        (DataType::Utf8, Some(wf_core::rule::Value::Int(i))) => {
+            Arc::new(StringArray::from(vec![Some(i.to_string())]))
+        }
         (DataType::Utf8, Some(wf_core::rule::Value::Bool(b))) => {
             Arc::new(StringArray::from(vec![Some(b.to_string())]))
         }
+        (DataType::Timestamp(_, _), Some(wf_core::rule::Value::Int(i))) => {
+            Arc::new(TimestampNanosecondArray::from(vec![Some(*i)]))
+        }
         (DataType::Timestamp(_, _), Some(wf_core::rule::Value::Number(n))) => {
             Arc::new(TimestampNanosecondArray::from(vec![Some(*n as i64)]))
         }
@@ -5,6 +5,8 @@ use std::time::Duration;
 use tokio::sync::{Notify, mpsc};
 use tokio_util::sync::CancellationToken;
 
+use wf_config::AlertOverflowPolicy;
+
 use wf_core::alert::OutputRecord;
 use wf_core::rule::{CepStateMachine, RuleExecutor};
 use wf_core::window::{Router, Window};
@@ -34,8 +36,11 @@ pub(crate) struct RuleTaskConfig {
     /// stream_name -> Vec<alias>: which CEP aliases receive events from each stream.
     pub stream_aliases: HashMap<String, Vec<String>>,
     pub alert_tx: mpsc::Sender<OutputRecord>,
+    /// Whether `alert_tx` sends wait for capacity or drop when full.
+    pub alert_overflow: AlertOverflowPolicy,
     pub cancel: CancellationToken,
-    pub timeout_scan_interval: Duration,
+    /// Fixed timeout scan interval; `None` derives it from the rule's window.
+    pub timeout_scan_interval: Option<Duration>,
     /// Shared router for WindowLookup (joins + has()).
     pub router: Arc<Router>,
     pub metrics: Option<Arc<RuntimeMetrics>>,
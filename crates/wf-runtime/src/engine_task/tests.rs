@@ -14,15 +14,17 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, Layer, fmt};
 
-use wf_config::{DistMode, EvictPolicy, LatePolicy, WindowConfig};
+use wf_config::{AlertOverflowPolicy, DistMode, EvictPolicy, LatePolicy, WindowConfig};
 use wf_core::rule::{CepStateMachine, RuleExecutor, batch_to_events};
 use wf_core::window::{Router, Window, WindowDef, WindowParams, WindowRegistry};
-use wf_lang::ast::{CloseMode, CmpOp, Expr, FieldRef, Measure};
+use wf_lang::ast::{BinOp, CloseMode, CmpOp, Expr, FieldRef, Measure, SessionSpec};
 use wf_lang::plan::{
-    AggPlan, BindPlan, BranchPlan, EntityPlan, MatchPlan, RulePlan, ScorePlan, StepPlan,
-    WindowSpec, YieldField, YieldPlan,
+    AggPlan, BindPlan, BranchPlan, ConvChainPlan, ConvOpPlan, ConvPlan, EntityPlan, ExceedAction,
+    LimitsPlan, MatchPlan, RateSpec, RulePlan, ScorePlan, SortKeyPlan, StepPlan, WindowSpec,
+    YieldField, YieldPlan,
 };
 
+use crate::metrics::RuntimeMetrics;
 use crate::tracing_init::DomainFormat;
 
 // -- helpers ------------------------------------------------------------
@@ -78,6 +80,7 @@ fn test_window_config(max_bytes: usize) -> WindowConfig {
         watermark: Duration::from_secs(0).into(),
         allowed_lateness: Duration::from_secs(3600).into(),
         late_policy: LatePolicy::Drop,
+        compact_below: 0.into(),
     }
 }
 
@@ -167,6 +170,40 @@ fn make_task_with_window_bytes(
     mpsc::Receiver<wf_core::alert::OutputRecord>,
     Arc<RwLock<Window>>,
     Arc<Notify>,
+) {
+    make_task_with_limits(max_bytes, None, None, None)
+}
+
+/// Same rule as [`make_task_with_window_bytes`], with optional `limits`,
+/// runtime metrics and a bind filter (`fail : auth_events && <filter>`).
+fn make_task_with_limits(
+    max_bytes: usize,
+    limits: Option<LimitsPlan>,
+    metrics: Option<Arc<RuntimeMetrics>>,
+    filter: Option<Expr>,
+) -> (
+    rule_task::RuleTask,
+    mpsc::Receiver<wf_core::alert::OutputRecord>,
+    Arc<RwLock<Window>>,
+    Arc<Notify>,
+) {
+    let (config, alert_rx, win_arc, notify_arc) =
+        make_task_config(max_bytes, limits, metrics, filter);
+    let (task, _cancel, _interval) = rule_task::RuleTask::new(config);
+    (task, alert_rx, win_arc, notify_arc)
+}
+
+/// Build the [`task_types::RuleTaskConfig`] behind [`make_task_with_limits`].
+fn make_task_config(
+    max_bytes: usize,
+    limits: Option<LimitsPlan>,
+    metrics: Option<Arc<RuntimeMetrics>>,
+    filter: Option<Expr>,
+) -> (
+    task_types::RuleTaskConfig,
+    mpsc::Receiver<wf_core::alert::OutputRecord>,
+    Arc<RwLock<Window>>,
+    Arc<Notify>,
 ) {
     let schema = test_schema();
     let (win_arc, notify_arc) = make_window("auth_events", &schema, max_bytes);
@@ -191,6 +228,8 @@ fn make_task_with_window_bytes(
         }],
         close_steps: vec![],
         close_mode: CloseMode::Or,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     };
 
     let rule_plan = RulePlan {
@@ -198,7 +237,7 @@ fn make_task_with_window_bytes(
         binds: vec![BindPlan {
             alias: "fail".into(),
             window: "auth_events".into(),
-            filter: None,
+            filter,
         }],
         match_plan: match_plan.clone(),
         joins: vec![],
@@ -206,20 +245,21 @@ fn make_task_with_window_bytes(
             entity_type: "ip".into(),
             entity_id_expr: Expr::Field(FieldRef::Qualified("fail".into(), "sip".into())),
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: "alerts".into(),
             version: None,
             fields: vec![],
-        },
+        }],
         score_plan: ScorePlan {
             expr: Expr::Number(70.0),
         },
         pattern_origin: None,
         conv_plan: None,
-        limits_plan: None,
+        limits_plan: limits.clone(),
+        priority: 0,
     };
 
-    let machine = CepStateMachine::new("test_rule".into(), match_plan, None);
+    let machine = CepStateMachine::with_limits("test_rule".into(), match_plan, None, limits);
     let executor = RuleExecutor::new(rule_plan);
 
     let (alert_tx, alert_rx) = mpsc::channel(64);
@@ -239,14 +279,14 @@ fn make_task_with_window_bytes(
         }],
         stream_aliases: HashMap::from([("syslog".into(), vec!["fail".into()])]),
         alert_tx,
+        alert_overflow: AlertOverflowPolicy::Block,
         cancel: tokio_util::sync::CancellationToken::new(),
-        timeout_scan_interval: Duration::from_secs(60),
+        timeout_scan_interval: Some(Duration::from_secs(60)),
         router,
-        metrics: None,
+        metrics,
     };
 
-    let (task, _cancel, _interval) = rule_task::RuleTask::new(config);
-    (task, alert_rx, win_arc, notify_arc)
+    (config, alert_rx, win_arc, notify_arc)
 }
 
 fn make_pipeline_stage_task() -> (
@@ -288,6 +328,8 @@ fn make_pipeline_stage_task() -> (
         }],
         close_steps: vec![],
         close_mode: CloseMode::Or,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
     };
     let rule_plan = RulePlan {
         name: "__wf_pipe_pipe_s1".into(),
@@ -302,7 +344,7 @@ fn make_pipeline_stage_task() -> (
             entity_type: "pipeline".into(),
             entity_id_expr: Expr::Field(FieldRef::Simple("sip".into())),
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: target_name.into(),
             version: None,
             fields: vec![
@@ -315,13 +357,14 @@ fn make_pipeline_stage_task() -> (
                     value: Expr::Field(FieldRef::Simple("ev_count".into())),
                 },
             ],
-        },
+        }],
         score_plan: ScorePlan {
             expr: Expr::Number(0.0),
         },
         pattern_origin: None,
         conv_plan: None,
         limits_plan: None,
+        priority: 0,
     };
 
     let machine = CepStateMachine::new(
@@ -342,8 +385,9 @@ fn make_pipeline_stage_task() -> (
         }],
         stream_aliases: HashMap::from([("syslog".into(), vec!["fail".into()])]),
         alert_tx,
+        alert_overflow: AlertOverflowPolicy::Block,
         cancel: tokio_util::sync::CancellationToken::new(),
-        timeout_scan_interval: Duration::from_secs(60),
+        timeout_scan_interval: Some(Duration::from_secs(60)),
         router: Arc::clone(&router),
         metrics: None,
     };
@@ -351,6 +395,119 @@ fn make_pipeline_stage_task() -> (
     (task, alert_rx, router)
 }
 
+/// Build a RuleTask for a close-time rule with a `top` conv:
+///
+/// ```wfl
+/// rule top_rule {
+///   events { fail : auth_events }
+///   match<sip:1h:fixed> {
+///     on event { fail | count >= 1; }
+///     on close { hits: fail | count >= 1; }
+///   } -> score(60.0)
+///   conv { sort(-hits) | top(<top>); }
+///   entity(ip, sip)
+///   yield alerts ()
+/// }
+/// ```
+fn make_conv_top_task(
+    top: u64,
+) -> (
+    rule_task::RuleTask,
+    mpsc::Receiver<wf_core::alert::OutputRecord>,
+    Arc<RwLock<Window>>,
+) {
+    let schema = test_schema();
+    let (win_arc, notify_arc) = make_window("auth_events", &schema, usize::MAX);
+
+    let count_branch = |label: &str| BranchPlan {
+        label: Some(label.into()),
+        source: "fail".into(),
+        field: None,
+        guard: None,
+        agg: AggPlan {
+            transforms: vec![],
+            measure: Measure::Count,
+            cmp: CmpOp::Ge,
+            threshold: Expr::Number(1.0),
+        },
+    };
+    let match_plan = MatchPlan {
+        keys: vec![FieldRef::Simple("sip".into())],
+        key_map: None,
+        window_spec: WindowSpec::Fixed(Duration::from_secs(3600)),
+        event_steps: vec![StepPlan {
+            branches: vec![count_branch("fail")],
+        }],
+        close_steps: vec![StepPlan {
+            branches: vec![count_branch("hits")],
+        }],
+        close_mode: CloseMode::And,
+        max_out_of_orderness: Duration::ZERO,
+        captures: vec![],
+    };
+    let conv_plan = ConvPlan {
+        chains: vec![ConvChainPlan {
+            ops: vec![
+                ConvOpPlan::Sort(vec![SortKeyPlan {
+                    expr: Expr::Field(FieldRef::Simple("hits".into())),
+                    descending: true,
+                }]),
+                ConvOpPlan::Top(top),
+            ],
+        }],
+    };
+    let rule_plan = RulePlan {
+        name: "top_rule".into(),
+        binds: vec![BindPlan {
+            alias: "fail".into(),
+            window: "auth_events".into(),
+            filter: None,
+        }],
+        match_plan: match_plan.clone(),
+        joins: vec![],
+        entity_plan: EntityPlan {
+            entity_type: "ip".into(),
+            entity_id_expr: Expr::Field(FieldRef::Simple("sip".into())),
+        },
+        yield_plans: vec![YieldPlan {
+            target: "alerts".into(),
+            version: None,
+            fields: vec![],
+        }],
+        score_plan: ScorePlan {
+            expr: Expr::Number(60.0),
+        },
+        pattern_origin: None,
+        conv_plan: Some(conv_plan),
+        limits_plan: None,
+        priority: 0,
+    };
+
+    let machine = CepStateMachine::new("top_rule".into(), match_plan, None);
+    let executor = RuleExecutor::new(rule_plan);
+    let (alert_tx, alert_rx) = mpsc::channel(64);
+    let registry = WindowRegistry::build(vec![]).unwrap();
+    let config = task_types::RuleTaskConfig {
+        machine,
+        executor,
+        window_sources: vec![task_types::WindowSource {
+            window_name: "auth_events".into(),
+            window: Arc::clone(&win_arc),
+            notify: notify_arc,
+            stream_names: vec!["syslog".into()],
+        }],
+        stream_aliases: HashMap::from([("syslog".into(), vec!["fail".into()])]),
+        alert_tx,
+        alert_overflow: AlertOverflowPolicy::Block,
+        cancel: tokio_util::sync::CancellationToken::new(),
+        timeout_scan_interval: Some(Duration::from_secs(60)),
+        router: Arc::new(Router::new(registry)),
+        metrics: None,
+    };
+    let (task, _cancel, _interval) = rule_task::RuleTask::new(config);
+    (task, alert_rx, win_arc)
+}
+
 // -- test cases ---------------------------------------------------------
 
 #[tokio::test]
@@ -405,6 +562,280 @@ async fn pull_triggers_alert() {
     assert!((alert.score - 70.0).abs() < f64::EPSILON);
 }
 
+#[tokio::test]
+async fn rule_metrics_count_matches_and_suppressions() {
+    init_tracing();
+    let schema = test_schema();
+    let metrics = Arc::new(RuntimeMetrics::new(
+        &["test_rule".to_string()],
+        &["auth_events".to_string()],
+    ));
+    let limits = LimitsPlan {
+        max_memory_bytes: None,
+        max_instances: None,
+        max_throttle: Some(RateSpec {
+            count: 1,
+            per: Duration::from_secs(3600),
+        }),
+        on_exceed: ExceedAction::Throttle,
+    };
+    let (mut task, mut alert_rx, win, _notify) =
+        make_task_with_limits(usize::MAX, Some(limits), Some(Arc::clone(&metrics)), None);
+
+    // Six events for one key: the first match emits, the second is throttled.
+    let ts = 1_700_000_000_000_000_000i64;
+    let batch = make_batch(&schema, &["10.0.0.1"; 6], ts);
+    win.write().unwrap().append(batch).unwrap();
+    task.pull_and_advance().await;
+
+    assert!(alert_rx.try_recv().is_ok(), "first match should emit");
+    assert!(alert_rx.try_recv().is_err(), "second match is throttled");
+
+    let text = metrics.render_prometheus();
+    assert!(text.contains("wf_rule_matches_total{rule=\"test_rule\"} 1"));
+    assert!(text.contains("wf_rule_closes_total{rule=\"test_rule\"} 0"));
+    assert!(
+        text.contains("wf_rule_suppressed_total{rule=\"test_rule\",reason=\"max_throttle\"} 1")
+    );
+    assert!(
+        text.contains("wf_rule_suppressed_total{rule=\"test_rule\",reason=\"max_instances\"} 0")
+    );
+}
+
+#[tokio::test]
+async fn bind_filter_drops_events_before_advance() {
+    init_tracing();
+    let schema = test_schema();
+    let metrics = Arc::new(RuntimeMetrics::new(
+        &["test_rule".to_string()],
+        &["auth_events".to_string()],
+    ));
+    // fail : auth_events && sip == "10.0.0.1"
+    let filter = Expr::BinOp {
+        op: BinOp::Eq,
+        left: Box::new(Expr::Field(FieldRef::Simple("sip".into()))),
+        right: Box::new(Expr::StringLit("10.0.0.1".into())),
+    };
+    let (mut task, mut alert_rx, win, _notify) =
+        make_task_with_limits(usize::MAX, None, Some(Arc::clone(&metrics)), Some(filter));
+
+    let ts = 1_700_000_000_000_000_000i64;
+    let batch = make_batch(
+        &schema,
+        &["10.0.0.2", "10.0.0.2", "10.0.0.2", "10.0.0.3", "10.0.0.1"],
+        ts,
+    );
+    win.write().unwrap().append(batch).unwrap();
+    task.pull_and_advance().await;
+
+    // Filtered keys never reach the state machine: only 10.0.0.1 has an
+    // instance, and 10.0.0.2 does not fire despite count=3.
+    assert!(alert_rx.try_recv().is_err());
+    let text = metrics.render_prometheus();
+    assert!(text.contains("wf_rule_instances{rule=\"test_rule\"} 1"));
+
+    let batch = make_batch(&schema, &["10.0.0.1", "10.0.0.1"], ts + 1_000_000_000);
+    win.write().unwrap().append(batch).unwrap();
+    task.pull_and_advance().await;
+    let alert = alert_rx.try_recv().expect("10.0.0.1 should trigger");
+    assert_eq!(alert.entity_id, "10.0.0.1");
+}
+
+/// Alerts a rule produces when it decodes every batch itself, the path rule
+/// tasks took before decoded batches were shared.
+fn per_rule_decode_alerts(
+    config: &task_types::RuleTaskConfig,
+    batches: &[RecordBatch],
+) -> Vec<String> {
+    let plan = config.executor.plan().clone();
+    let filter = plan.binds[0].filter.clone();
+    let mut machine =
+        CepStateMachine::with_limits(plan.name.clone(), plan.match_plan.clone(), None, None);
+    let executor = RuleExecutor::new(plan);
+    let mut out = Vec::new();
+    for batch in batches {
+        for event in &batch_to_events(batch) {
+            if let Some(filter) = &filter
+                && !wf_core::rule::bind_filter_passes(filter, event)
+            {
+                continue;
+            }
+            if let wf_core::rule::StepResult::Matched(ctx) = machine.advance("fail", event) {
+                for record in executor.execute_match(&ctx).unwrap() {
+                    out.push(record.entity_id);
+                }
+            }
+        }
+    }
+    out
+}
+
+#[tokio::test]
+async fn rules_sharing_a_window_match_per_rule_decode() {
+    init_tracing();
+    let schema = test_schema();
+    // Two rules over one window: the second only counts 10.0.0.2.
+    let (config_a, mut rx_a, win, _notify) = make_task_config(usize::MAX, None, None, None);
+    let filter = Expr::BinOp {
+        op: BinOp::Eq,
+        left: Box::new(Expr::Field(FieldRef::Simple("sip".into()))),
+        right: Box::new(Expr::StringLit("10.0.0.2".into())),
+    };
+    let (mut config_b, mut rx_b, _, _) = make_task_config(usize::MAX, None, None, Some(filter));
+    config_b.window_sources[0].window = Arc::clone(&win);
+
+    let ts = 1_700_000_000_000_000_000i64;
+    let batches = vec![
+        make_batch(&schema, &["10.0.0.1", "10.0.0.2", "10.0.0.1"], ts),
+        make_batch(&schema, &["10.0.0.2", "10.0.0.1", "10.0.0.3"], ts + 1),
+        make_batch(&schema, &["10.0.0.2", "10.0.0.3", "10.0.0.3"], ts + 2),
+    ];
+    let expected_a = per_rule_decode_alerts(&config_a, &batches);
+    let expected_b = per_rule_decode_alerts(&config_b, &batches);
+    assert_eq!(expected_a, vec!["10.0.0.1", "10.0.0.2", "10.0.0.3"]);
+    assert_eq!(expected_b, vec!["10.0.0.2"]);
+
+    let (mut task_a, _, _) = rule_task::RuleTask::new(config_a);
+    let (mut task_b, _, _) = rule_task::RuleTask::new(config_b);
+    for batch in batches {
+        win.write().unwrap().append(batch).unwrap();
+        task_a.pull_and_advance().await;
+        task_b.pull_and_advance().await;
+    }
+
+    let drain = |rx: &mut mpsc::Receiver<wf_core::alert::OutputRecord>| {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|r| r.entity_id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(drain(&mut rx_a), expected_a);
+    assert_eq!(drain(&mut rx_b), expected_b);
+}
+
+/// Build a task whose alert channel holds one record and is never drained,
+/// standing in for a stalled sink.
+fn make_task_with_stalled_sink(
+    policy: AlertOverflowPolicy,
+    metrics: Option<Arc<RuntimeMetrics>>,
+) -> (
+    rule_task::RuleTask,
+    mpsc::Receiver<wf_core::alert::OutputRecord>,
+    Arc<RwLock<Window>>,
+) {
+    let (config, _rx, win, _notify) = make_task_config(usize::MAX, None, metrics, None);
+    let (alert_tx, alert_rx) = mpsc::channel(1);
+    let config = task_types::RuleTaskConfig {
+        alert_tx,
+        alert_overflow: policy,
+        ..config
+    };
+    let (task, _cancel, _interval) = rule_task::RuleTask::new(config);
+    (task, alert_rx, win)
+}
+
+/// Three keys with three events each: one alert per key.
+fn three_alert_batch(schema: &SchemaRef) -> RecordBatch {
+    let sips = ["10.0.0.1", "10.0.0.2", "10.0.0.3"].repeat(3);
+    make_batch(schema, &sips, 1_700_000_000_000_000_000)
+}
+
+#[tokio::test]
+async fn stalled_sink_drop_policy_counts_dropped_alerts() {
+    init_tracing();
+    let schema = test_schema();
+    let metrics = Arc::new(RuntimeMetrics::new(
+        &["test_rule".to_string()],
+        &["auth_events".to_string()],
+    ));
+    let (mut task, mut alert_rx, win) =
+        make_task_with_stalled_sink(AlertOverflowPolicy::Drop, Some(Arc::clone(&metrics)));
+
+    win.write()
+        .unwrap()
+        .append(three_alert_batch(&schema))
+        .unwrap();
+    // Must not stall even though nobody drains the channel.
+    tokio::time::timeout(Duration::from_secs(1), task.pull_and_advance())
+        .await
+        .expect("drop policy must not block on a full channel");
+
+    assert!(alert_rx.try_recv().is_ok());
+    assert!(alert_rx.try_recv().is_err(), "channel stays bounded at 1");
+    let text = metrics.render_prometheus();
+    assert!(text.contains("wf_alert_channel_dropped_total 2"), "{text}");
+    assert!(
+        text.contains("wf_alert_channel_send_failed_total 0"),
+        "{text}"
+    );
+}
+
+#[tokio::test]
+async fn stalled_sink_block_policy_waits_for_capacity() {
+    init_tracing();
+    let schema = test_schema();
+    let (mut task, mut alert_rx, win) =
+        make_task_with_stalled_sink(AlertOverflowPolicy::Block, None);
+
+    win.write()
+        .unwrap()
+        .append(three_alert_batch(&schema))
+        .unwrap();
+    let pull = tokio::spawn(async move {
+        task.pull_and_advance().await;
+        task
+    });
+
+    // The task parks on the full channel instead of buffering more alerts.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!pull.is_finished(), "block policy must wait for the sink");
+
+    // Draining the sink lets every alert through; none are lost.
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        let alert = tokio::time::timeout(Duration::from_secs(1), alert_rx.recv())
+            .await
+            .expect("alert after drain")
+            .expect("channel open");
+        received.push(alert.entity_id);
+    }
+    pull.await.unwrap();
+    received.sort();
+    assert_eq!(received, ["10.0.0.1", "10.0.0.2", "10.0.0.3"]);
+}
+
+#[test]
+fn timeout_scan_interval_derived_from_window() {
+    // The test rule uses a 5m sliding window: 5m / 10 is capped at 5s.
+    let (config, ..) = make_task_config(usize::MAX, None, None, None);
+    let config = task_types::RuleTaskConfig {
+        timeout_scan_interval: None,
+        ..config
+    };
+    let (_task, _cancel, interval) = rule_task::RuleTask::new(config);
+    assert_eq!(interval, Duration::from_secs(5));
+
+    // An explicit interval overrides the derived one.
+    let (config, ..) = make_task_config(usize::MAX, None, None, None);
+    let (_task, _cancel, interval) = rule_task::RuleTask::new(config);
+    assert_eq!(interval, Duration::from_secs(60));
+
+    let derive = rule_task::derive_timeout_scan_interval;
+    assert_eq!(
+        derive(&WindowSpec::Sliding(Duration::from_secs(10))),
+        Duration::from_secs(1)
+    );
+    assert_eq!(
+        derive(&WindowSpec::Fixed(Duration::from_millis(200))),
+        Duration::from_millis(100)
+    );
+    assert_eq!(
+        derive(&WindowSpec::Session(SessionSpec::new(Duration::from_secs(
+            30
+        )))),
+        Duration::from_secs(3)
+    );
+}
+
 #[tokio::test]
 async fn pull_multiple_keys_isolated() {
     init_tracing();
@@ -498,6 +929,37 @@ async fn flush_closes_active_instances() {
     );
 }
 
+#[tokio::test]
+async fn flush_applies_conv_top_to_close_alerts() {
+    init_tracing();
+    let schema = test_schema();
+    let (mut task, mut alert_rx, win) = make_conv_top_task(1);
+
+    let ts = 1_700_000_000_000_000_000i64;
+    let batch = make_batch(
+        &schema,
+        &[
+            "10.0.0.1", "10.0.0.2", "10.0.0.2", "10.0.0.2", "10.0.0.3", "10.0.0.3",
+        ],
+        ts,
+    );
+    win.write().unwrap().append(batch).unwrap();
+    task.pull_and_advance().await;
+    assert!(
+        alert_rx.try_recv().is_err(),
+        "close-time rule must not alert before flush"
+    );
+
+    task.flush().await;
+
+    let alert = alert_rx.try_recv().expect("top(1) should keep one alert");
+    assert_eq!(alert.entity_id, "10.0.0.2", "highest hit count wins");
+    assert!(
+        alert_rx.try_recv().is_err(),
+        "conv top(1) should drop the other instances at shutdown"
+    );
+}
+
 #[tokio::test]
 async fn pipeline_stage_output_writes_internal_window_instead_of_alert_channel() {
     init_tracing();
@@ -528,10 +990,10 @@ async fn pipeline_stage_output_writes_internal_window_instead_of_alert_channel()
     );
     assert_eq!(
         rows[0].fields.get("ev_count"),
-        Some(&wf_core::rule::Value::Number(1.0))
+        Some(&wf_core::rule::Value::Int(1))
     );
     assert_eq!(
         rows[0].fields.get("__wf_pipe_ts"),
-        Some(&wf_core::rule::Value::Number(ts as f64))
+        Some(&wf_core::rule::Value::Int(ts))
     );
 }
@@ -8,7 +8,8 @@ use wf_core::window::Router;
 // ---------------------------------------------------------------------------
 
 /// Implements [`WindowLookup`] by snapshotting windows from the shared
-/// [`Router`]'s registry. Used for `window.has()` guards and join evaluation.
+/// [`Router`]'s registry. Used for `window.has()` / `window.count()` /
+/// `window.sum()` guards and join evaluation.
 pub(super) struct RegistryLookup<'a>(pub(super) &'a Router);
 
 impl WindowLookup for RegistryLookup<'_> {
@@ -26,6 +27,9 @@ impl WindowLookup for RegistryLookup<'_> {
                         Value::Number(n) => {
                             values.insert(n.to_string());
                         }
+                        Value::Int(i) => {
+                            values.insert(i.to_string());
+                        }
                         Value::Bool(b) => {
                             values.insert(b.to_string());
                         }
@@ -50,6 +54,11 @@ impl WindowLookup for RegistryLookup<'_> {
         Some(rows)
     }
 
+    fn row_count(&self, window: &str) -> Option<usize> {
+        let batches = self.0.registry().snapshot(window)?;
+        Some(batches.iter().map(|b| b.num_rows()).sum())
+    }
+
     fn snapshot_with_timestamps(&self, window: &str) -> Option<Vec<(i64, HashMap<String, Value>)>> {
         let win_lock = self.0.registry().get_window(window)?;
         let win = win_lock.read().expect("window lock poisoned");
@@ -99,6 +108,7 @@ mod tests {
             watermark: Duration::from_secs(0).into(),
             allowed_lateness: Duration::from_secs(3600).into(),
             late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
         }
     }
 
@@ -145,14 +155,14 @@ mod tests {
         // Row 0: ts=1s
         assert_eq!(rows[0].0, ts1);
         assert_eq!(rows[0].1["ip"], Value::Str("10.0.0.1".into()));
-        assert_eq!(rows[0].1["score"], Value::Number(80.0));
+        assert_eq!(rows[0].1["score"], Value::Int(80));
         // Time column should also be present as a field
-        assert_eq!(rows[0].1["ts"], Value::Number(ts1 as f64));
+        assert_eq!(rows[0].1["ts"], Value::Int(ts1));
 
         // Row 1: ts=2s
         assert_eq!(rows[1].0, ts2);
         assert_eq!(rows[1].1["ip"], Value::Str("10.0.0.2".into()));
-        assert_eq!(rows[1].1["score"], Value::Number(95.0));
+        assert_eq!(rows[1].1["score"], Value::Int(95));
     }
 
     #[test]
@@ -29,11 +29,15 @@ pub async fn run_evictor(
                 if let Some(metrics) = &metrics {
                     metrics.add_evict_report(&report);
                 }
-                if report.batches_time_evicted > 0 || report.batches_memory_evicted > 0 {
+                if report.batches_time_evicted > 0
+                    || report.batches_memory_evicted > 0
+                    || report.batches_compacted > 0
+                {
                     wf_debug!(res,
                         scanned = report.windows_scanned,
                         time_evicted = report.batches_time_evicted,
                         memory_evicted = report.batches_memory_evicted,
+                        compacted = report.batches_compacted,
                         "evictor sweep"
                     );
                 }
@@ -0,0 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Readiness flag behind the `/readyz` probe.
+///
+/// Starts not-ready; the reactor marks it ready once bootstrap has finished
+/// (windows built, receivers bound, all task groups spawned) and flips it
+/// back when shutdown begins. `/healthz` only reports that the process is up.
+#[derive(Debug, Default)]
+pub struct HealthState {
+    ready: AtomicBool,
+}
+
+impl HealthState {
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::Release);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,438 @@
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+use wf_core::window::Router;
+
+use crate::json_decode::json_to_batch;
+use crate::metrics::RuntimeMetrics;
+
+/// Largest accepted request body.
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+/// Largest accepted request line + headers.
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// HTTP receiver for lightweight push integrations.
+///
+/// Accepts `POST /ingest/{window}` with a JSON array or NDJSON body, decodes
+/// it against the window schema and routes the batch straight to that
+/// window. Responds `429` instead of appending when the window is saturated.
+pub struct HttpReceiver {
+    listener: TcpListener,
+    router: Arc<Router>,
+    metrics: Option<Arc<RuntimeMetrics>>,
+    cancel: CancellationToken,
+}
+
+impl HttpReceiver {
+    /// Bind a TCP listener on `host:port`.
+    pub async fn bind(
+        listen: &str,
+        router: Arc<Router>,
+        metrics: Option<Arc<RuntimeMetrics>>,
+    ) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(listen).await?;
+        Ok(Self {
+            listener,
+            router,
+            metrics,
+            cancel: CancellationToken::new(),
+        })
+    }
+
+    /// Returns the local address the listener is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Returns a clone of the cancellation token for external shutdown signaling.
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// Start the accept loop. Blocks until the cancellation token is
+    /// triggered and every in-flight request has been routed, so accepted
+    /// events are in their windows before the rule tasks drain.
+    #[tracing::instrument(name = "http_receiver", skip_all)]
+    pub async fn run(self) -> anyhow::Result<()> {
+        let mut connections = JoinSet::new();
+        loop {
+            tokio::select! {
+                result = self.listener.accept() => {
+                    let (stream, peer) = result?;
+                    if let Some(metrics) = &self.metrics {
+                        metrics.inc_receiver_connection();
+                    }
+                    let router = Arc::clone(&self.router);
+                    let metrics = self.metrics.clone();
+                    connections.spawn(async move {
+                        if let Err(e) = serve_ingest_connection(stream, router, metrics).await {
+                            wf_debug!(conn, peer = %peer, error = %e, "http ingest connection failed");
+                        }
+                    });
+                }
+                // Reap finished connections so the set stays small.
+                Some(_) = connections.join_next(), if !connections.is_empty() => {}
+                _ = self.cancel.cancelled() => break,
+            }
+        }
+        // Every connection is bounded by `IO_TIMEOUT`, so this terminates.
+        while connections.join_next().await.is_some() {}
+        Ok(())
+    }
+}
+
+/// Parsed request line + the headers we care about.
+struct RequestHead {
+    method: String,
+    path: String,
+    content_length: Option<usize>,
+}
+
+/// Serve a single request, then close the connection.
+async fn serve_ingest_connection(
+    mut stream: TcpStream,
+    router: Arc<Router>,
+    metrics: Option<Arc<RuntimeMetrics>>,
+) -> anyhow::Result<()> {
+    let (status, body) = match timeout(IO_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(Ok((head, payload)))) => handle_request(&head, &payload, &router, &metrics),
+        Ok(Ok(Err(rejection))) => rejection,
+        Ok(Err(e)) => {
+            if let Some(metrics) = &metrics {
+                metrics.inc_receiver_read_error();
+            }
+            return Err(e.into());
+        }
+        Err(_) => return Ok(()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    timeout(IO_TIMEOUT, stream.write_all(response.as_bytes())).await??;
+    let _ = timeout(Duration::from_secs(1), stream.shutdown()).await;
+    Ok(())
+}
+
+type Response = (&'static str, String);
+
+/// Read the request head and body. The inner `Err` is an HTTP error
+/// response for malformed or oversized requests.
+async fn read_request(
+    stream: &mut TcpStream,
+) -> io::Result<Result<(RequestHead, Vec<u8>), Response>> {
+    let mut buf = Vec::with_capacity(1024);
+    let head_end = loop {
+        if let Some(pos) = find_head_end(&buf) {
+            break pos;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Ok(Err(error_response(
+                "431 Request Header Fields Too Large",
+                "headers too large",
+            )));
+        }
+        let mut chunk = [0u8; 1024];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(Err(error_response("400 Bad Request", "incomplete request")));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let Some(head) = parse_head(&buf[..head_end]) else {
+        return Ok(Err(error_response(
+            "400 Bad Request",
+            "malformed request head",
+        )));
+    };
+    if head.method != "POST" {
+        return Ok(Ok((head, Vec::new())));
+    }
+    let Some(len) = head.content_length else {
+        return Ok(Err(error_response(
+            "411 Length Required",
+            "content-length required",
+        )));
+    };
+    if len > MAX_BODY_BYTES {
+        return Ok(Err(error_response(
+            "413 Payload Too Large",
+            "body too large",
+        )));
+    }
+
+    let mut body = buf.split_off(head_end + 4);
+    if body.len() < len {
+        let already = body.len();
+        body.resize(len, 0);
+        stream.read_exact(&mut body[already..]).await?;
+    }
+    body.truncate(len);
+    Ok(Ok((head, body)))
+}
+
+fn find_head_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+fn parse_head(raw: &[u8]) -> Option<RequestHead> {
+    let text = std::str::from_utf8(raw).ok()?;
+    let mut lines = text.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let mut content_length = None;
+    for line in lines {
+        let (name, value) = line.split_once(':')?;
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            content_length = Some(value.trim().parse().ok()?);
+        }
+    }
+    Some(RequestHead {
+        method,
+        path,
+        content_length,
+    })
+}
+
+fn handle_request(
+    head: &RequestHead,
+    payload: &[u8],
+    router: &Router,
+    metrics: &Option<Arc<RuntimeMetrics>>,
+) -> Response {
+    let Some(window) = head.path.strip_prefix("/ingest/").filter(|w| !w.is_empty()) else {
+        return error_response("404 Not Found", "unknown path");
+    };
+    if head.method != "POST" {
+        return error_response("405 Method Not Allowed", "use POST");
+    }
+    let Some(win_lock) = router.registry().get_window(window) else {
+        return error_response("404 Not Found", &format!("unknown window {window:?}"));
+    };
+    let schema = win_lock
+        .read()
+        .expect("window lock poisoned")
+        .schema()
+        .clone();
+
+    let decode_started = Instant::now();
+    let decoded = json_to_batch(&schema, payload);
+    if let Some(metrics) = metrics {
+        metrics.observe_receiver_decode(decode_started.elapsed());
+    }
+    let batch = match decoded {
+        Ok(batch) => batch,
+        Err(e) => {
+            if let Some(metrics) = metrics {
+                metrics.inc_receiver_decode_error();
+            }
+            wf_warn!(conn, window = window, error = %e, "http ingest decode error");
+            return error_response("400 Bad Request", &e.to_string());
+        }
+    };
+
+    let rows = batch.num_rows();
+    if let Some(metrics) = metrics {
+        metrics.inc_router_route_call();
+    }
+    match router.route_to_window_bounded(window, batch) {
+        Ok(report) => {
+            if let Some(metrics) = metrics {
+                metrics.add_route_report(&report);
+            }
+            if report.saturated > 0 {
+                wf_debug!(
+                    pipe,
+                    window = window,
+                    "http ingest rejected: window saturated"
+                );
+                return error_response("429 Too Many Requests", "window saturated");
+            }
+            if let Some(metrics) = metrics {
+                metrics.add_receiver_frame(rows);
+            }
+            // The body was decoded against the window's own schema, so a
+            // rejection here is unexpected; report it to the caller.
+            if let Some(rejection) = report.rejected.first() {
+                wf_warn!(
+                    pipe,
+                    window = window,
+                    reason = &*rejection.reason,
+                    "http ingest rejected by router"
+                );
+                return error_response("422 Unprocessable Entity", &rejection.reason);
+            }
+            (
+                "200 OK",
+                serde_json::json!({
+                    "rows": rows,
+                    "delivered": report.delivered,
+                    "dropped_late": report.dropped_late,
+                })
+                .to_string(),
+            )
+        }
+        Err(e) => {
+            if let Some(metrics) = metrics {
+                metrics.inc_route_error();
+            }
+            wf_warn!(pipe, window = window, error = %e, "route error");
+            error_response("500 Internal Server Error", &e.to_string())
+        }
+    }
+}
+
+fn error_response(status: &'static str, message: &str) -> Response {
+    (status, serde_json::json!({ "error": message }).to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_router(max_window_bytes: usize) -> Arc<Router> {
+        crate::test_support::make_router("tcp_stream", max_window_bytes)
+    }
+
+    async fn start(router: &Arc<Router>) -> (SocketAddr, CancellationToken) {
+        let receiver = HttpReceiver::bind("127.0.0.1:0", Arc::clone(router), None)
+            .await
+            .unwrap();
+        let addr = receiver.local_addr().unwrap();
+        let cancel = receiver.cancel_token();
+        tokio::spawn(receiver.run());
+        (addr, cancel)
+    }
+
+    /// Send a raw request and return `(status_code, body)`.
+    async fn send(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: test\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, b)| b.to_string())
+            .unwrap_or_default();
+        (status, body)
+    }
+
+    fn snapshot_row_count(router: &Router) -> usize {
+        router
+            .registry()
+            .snapshot("test_win")
+            .unwrap_or_default()
+            .iter()
+            .map(|b| b.num_rows())
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn post_json_array_and_ndjson_reach_window() {
+        let router = make_router(usize::MAX);
+        let (addr, cancel) = start(&router).await;
+
+        let (status, body) = send(
+            addr,
+            "POST",
+            "/ingest/test_win",
+            r#"[{"ts":1000000000,"value":1},{"ts":2000000000,"value":2}]"#,
+        )
+        .await;
+        assert_eq!(status, 200, "{body}");
+        assert!(body.contains("\"rows\":2"));
+
+        let (status, _) = send(
+            addr,
+            "POST",
+            "/ingest/test_win",
+            "{\"ts\":3000000000,\"value\":3}\n{\"ts\":\"1970-01-01T00:00:04Z\",\"value\":4}\n",
+        )
+        .await;
+        assert_eq!(status, 200);
+
+        assert_eq!(snapshot_row_count(&router), 4);
+        cancel.cancel();
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_requests() {
+        let router = make_router(usize::MAX);
+        let (addr, cancel) = start(&router).await;
+
+        assert_eq!(send(addr, "POST", "/ingest/missing", "[]").await.0, 404);
+        assert_eq!(send(addr, "POST", "/other", "[]").await.0, 404);
+        assert_eq!(send(addr, "GET", "/ingest/test_win", "").await.0, 405);
+        assert_eq!(
+            send(addr, "POST", "/ingest/test_win", "{\"value\":")
+                .await
+                .0,
+            400
+        );
+        assert_eq!(snapshot_row_count(&router), 0);
+        cancel.cancel();
+    }
+
+    #[tokio::test]
+    async fn saturated_window_returns_429() {
+        let router = make_router(64);
+        let (addr, cancel) = start(&router).await;
+
+        let (status, _) = send(
+            addr,
+            "POST",
+            "/ingest/test_win",
+            r#"[{"ts":1000000000,"value":1}]"#,
+        )
+        .await;
+        assert_eq!(status, 429);
+        assert_eq!(snapshot_row_count(&router), 0);
+        cancel.cancel();
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_in_flight_requests() {
+        let router = make_router(usize::MAX);
+        let receiver = HttpReceiver::bind("127.0.0.1:0", Arc::clone(&router), None)
+            .await
+            .unwrap();
+        let addr = receiver.local_addr().unwrap();
+        let cancel = receiver.cancel_token();
+        let server = tokio::spawn(receiver.run());
+
+        let body = r#"[{"ts":1000000000,"value":1}]"#;
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let head = format!(
+            "POST /ingest/test_win HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).await.unwrap();
+        // Let the receiver accept the connection, then shut down while the
+        // body is still in flight.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancel.cancel();
+        stream.write_all(body.as_bytes()).await.unwrap();
+
+        server.await.unwrap().unwrap();
+        assert_eq!(snapshot_row_count(&router), 1);
+    }
+}
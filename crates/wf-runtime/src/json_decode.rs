@@ -0,0 +1,98 @@
+use arrow::datatypes::SchemaRef;
+use arrow::json::ReaderBuilder;
+use arrow::record_batch::RecordBatch;
+
+/// Decode JSON events into a [`RecordBatch`] matching `schema`.
+///
+/// `payload` is either a JSON array of objects or newline-delimited objects.
+/// Fields are matched by name; missing fields become nulls and unknown
+/// fields are ignored. Timestamp columns accept RFC 3339 strings or epoch
+/// integers in the column's unit.
+pub(crate) fn json_to_batch(schema: &SchemaRef, payload: &[u8]) -> anyhow::Result<RecordBatch> {
+    // Every object is at least two bytes (`{}`), so this batch size always
+    // fits the whole payload into a single flush.
+    let batch_size = payload.len() / 2 + 1;
+    let mut decoder = ReaderBuilder::new(schema.clone())
+        .with_batch_size(batch_size)
+        .build_decoder()?;
+    if payload.trim_ascii_start().starts_with(b"[") {
+        let rows: Vec<serde_json::Value> = serde_json::from_slice(payload)?;
+        decoder.serialize(&rows)?;
+        return Ok(decoder
+            .flush()?
+            .unwrap_or_else(|| RecordBatch::new_empty(schema.clone())));
+    }
+    let consumed = decoder.decode(payload)?;
+    if consumed < payload.len() {
+        anyhow::bail!(
+            "trailing bytes after JSON payload ({consumed}/{})",
+            payload.len()
+        );
+    }
+    Ok(decoder
+        .flush()?
+        .unwrap_or_else(|| RecordBatch::new_empty(schema.clone())))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Array, Int64Array, StringArray, TimestampNanosecondArray};
+    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use std::sync::Arc;
+
+    fn test_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+            Field::new("sip", DataType::Utf8, true),
+            Field::new("count", DataType::Int64, true),
+        ]))
+    }
+
+    #[test]
+    fn decodes_ndjson_by_schema() {
+        let payload = br#"{"ts":"2024-01-01T00:00:01Z","sip":"10.0.0.1","count":3,"extra":true}
+{"ts":2000000000,"sip":"10.0.0.2"}"#;
+        let batch = json_to_batch(&test_schema(), payload).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let ts = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert_eq!(ts.value(0), 1_704_067_201_000_000_000);
+        assert_eq!(ts.value(1), 2_000_000_000);
+        let sip = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(sip.value(1), "10.0.0.2");
+        let count = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(count.value(0), 3);
+        assert!(count.is_null(1));
+    }
+
+    #[test]
+    fn decodes_json_array() {
+        let payload = br#" [{"sip":"10.0.0.1","count":1},{"sip":"10.0.0.2","count":2}]"#;
+        let batch = json_to_batch(&test_schema(), payload).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(json_to_batch(&test_schema(), b"[]").unwrap().num_rows(), 0);
+    }
+
+    #[test]
+    fn rejects_malformed_json() {
+        assert!(json_to_batch(&test_schema(), b"{\"sip\": ").is_err());
+        assert!(json_to_batch(&test_schema(), b"{\"count\": \"x\"}").is_err());
+    }
+}
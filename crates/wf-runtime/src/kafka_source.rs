@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use tokio_util::sync::CancellationToken;
+use wf_config::{KafkaSourceConfig, SourceFormat};
+use wf_core::window::Router;
+
+use crate::dead_letter::{DeadLetterSink, handle_rejections};
+use crate::json_decode::json_to_batch;
+use crate::metrics::RuntimeMetrics;
+
+/// Delay before retrying after the first consume error.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+/// Upper bound for the consume-error retry delay.
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// A single message pulled from a [`SourceConsumer`].
+#[derive(Debug, Clone)]
+pub struct SourceMessage {
+    pub topic: String,
+    pub payload: Vec<u8>,
+}
+
+/// Abstraction over a topic-based message consumer.
+///
+/// The runtime ships an rdkafka-backed implementation behind the `kafka`
+/// feature; tests drive [`KafkaReceiver`] with an in-memory consumer.
+#[async_trait]
+pub trait SourceConsumer: Send + 'static {
+    /// Wait for the next message. `Ok(None)` means the consumer is exhausted
+    /// and the receiver should stop.
+    async fn recv(&mut self) -> anyhow::Result<Option<SourceMessage>>;
+}
+
+/// Kafka receiver: consumes messages from the configured topics, decodes
+/// them (JSON or Arrow IPC) against the target window schema, and routes
+/// the resulting batches to the [`Router`].
+pub struct KafkaReceiver<C> {
+    consumer: C,
+    /// Topic → window name.
+    topics: HashMap<String, String>,
+    format: SourceFormat,
+    router: Arc<Router>,
+    metrics: Option<Arc<RuntimeMetrics>>,
+    cancel: CancellationToken,
+    dead_letter: Option<Arc<DeadLetterSink>>,
+}
+
+impl<C: SourceConsumer> KafkaReceiver<C> {
+    /// Build a receiver for `config`. Fails if a topic maps to a window that
+    /// is not registered in the router.
+    pub fn new(
+        consumer: C,
+        config: &KafkaSourceConfig,
+        router: Arc<Router>,
+        metrics: Option<Arc<RuntimeMetrics>>,
+    ) -> anyhow::Result<Self> {
+        for (topic, window) in &config.topics {
+            if !router.registry().contains(window) {
+                anyhow::bail!("kafka topic {topic:?} maps to unknown window {window:?}");
+            }
+        }
+        Ok(Self {
+            consumer,
+            topics: config
+                .topics
+                .iter()
+                .map(|(t, w)| (t.clone(), w.clone()))
+                .collect(),
+            format: config.format,
+            router,
+            metrics,
+            cancel: CancellationToken::new(),
+            dead_letter: None,
+        })
+    }
+
+    /// Append batches the router rejects to `sink`.
+    pub fn with_dead_letter(mut self, sink: Arc<DeadLetterSink>) -> Self {
+        self.dead_letter = Some(sink);
+        self
+    }
+
+    /// Returns a clone of the cancellation token for external shutdown signaling.
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// Consume until cancelled or the consumer is exhausted.
+    ///
+    /// Each message is routed before the next one is requested, so once this
+    /// returns every consumed message has reached its window. Consume errors
+    /// are retried with exponential backoff, reset by the next message.
+    #[tracing::instrument(name = "kafka_receiver", skip_all)]
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            tokio::select! {
+                result = self.consumer.recv() => {
+                    match result {
+                        Ok(Some(msg)) => {
+                            backoff = INITIAL_BACKOFF;
+                            self.handle_message(msg);
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            if let Some(metrics) = &self.metrics {
+                                metrics.inc_receiver_read_error();
+                            }
+                            wf_warn!(
+                                conn,
+                                error = %e,
+                                retry_in_ms = backoff.as_millis() as u64,
+                                "kafka consume error"
+                            );
+                            tokio::select! {
+                                _ = tokio::time::sleep(backoff) => {}
+                                _ = self.cancel.cancelled() => break,
+                            }
+                            backoff = (backoff * 2).min(MAX_BACKOFF);
+                        }
+                    }
+                }
+                _ = self.cancel.cancelled() => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_message(&self, msg: SourceMessage) {
+        let Some(window) = self.topics.get(&msg.topic) else {
+            wf_debug!(
+                conn,
+                topic = &*msg.topic,
+                "message from unmapped topic ignored"
+            );
+            return;
+        };
+
+        let decode_started = Instant::now();
+        let decoded = self.decode(window, &msg.payload);
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_receiver_decode(decode_started.elapsed());
+        }
+        let batch = match decoded {
+            Ok(batch) => batch,
+            Err(e) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.inc_receiver_decode_error();
+                }
+                wf_warn!(conn, topic = &*msg.topic, error = %e, "kafka payload decode error");
+                return;
+            }
+        };
+        if let Some(metrics) = &self.metrics {
+            metrics.add_receiver_frame(batch.num_rows());
+            metrics.inc_router_route_call();
+        }
+        wf_debug!(
+            pipe,
+            topic = &*msg.topic,
+            window = &**window,
+            rows = batch.num_rows(),
+            "kafka message decoded"
+        );
+
+        match self.router.route_to_window(window, batch.clone()) {
+            Ok(report) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.add_route_report(&report);
+                }
+                handle_rejections(
+                    &report,
+                    "kafka",
+                    window,
+                    &batch,
+                    self.dead_letter.as_deref(),
+                );
+            }
+            Err(e) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.inc_route_error();
+                }
+                wf_warn!(pipe, window = &**window, error = %e, "route error")
+            }
+        }
+    }
+
+    fn decode(&self, window: &str, payload: &[u8]) -> anyhow::Result<RecordBatch> {
+        match self.format {
+            SourceFormat::Arrow => Ok(wp_arrow::ipc::decode_ipc(payload)?.batch),
+            SourceFormat::Json => {
+                let schema = {
+                    let win_lock = self
+                        .router
+                        .registry()
+                        .get_window(window)
+                        .expect("topic mapping validated at construction");
+                    let win = win_lock.read().expect("window lock poisoned");
+                    win.schema().clone()
+                };
+                json_to_batch(&schema, payload)
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// rdkafka-backed consumer
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "kafka")]
+pub use rd::RdKafkaConsumer;
+
+#[cfg(feature = "kafka")]
+mod rd {
+    use async_trait::async_trait;
+    use rdkafka::ClientConfig;
+    use rdkafka::Message;
+    use rdkafka::consumer::{Consumer, StreamConsumer};
+    use wf_config::KafkaSourceConfig;
+
+    use super::{SourceConsumer, SourceMessage};
+
+    /// [`SourceConsumer`] backed by an rdkafka `StreamConsumer`.
+    pub struct RdKafkaConsumer {
+        inner: StreamConsumer,
+    }
+
+    impl RdKafkaConsumer {
+        /// Create a consumer in `config.group_id` and subscribe to all
+        /// configured topics.
+        pub fn connect(config: &KafkaSourceConfig) -> anyhow::Result<Self> {
+            let inner: StreamConsumer = ClientConfig::new()
+                .set("bootstrap.servers", config.brokers.join(","))
+                .set("group.id", &config.group_id)
+                .set("enable.partition.eof", "false")
+                .set("auto.offset.reset", "earliest")
+                .create()?;
+            let topics: Vec<&str> = config.topics.keys().map(String::as_str).collect();
+            inner.subscribe(&topics)?;
+            Ok(Self { inner })
+        }
+    }
+
+    #[async_trait]
+    impl SourceConsumer for RdKafkaConsumer {
+        async fn recv(&mut self) -> anyhow::Result<Option<SourceMessage>> {
+            let msg = self.inner.recv().await?;
+            Ok(Some(SourceMessage {
+                topic: msg.topic().to_string(),
+                payload: msg.payload().unwrap_or_default().to_vec(),
+            }))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, TimestampNanosecondArray};
+    use std::collections::{BTreeMap, VecDeque};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::test_support::test_schema;
+
+    /// In-memory consumer: yields queued messages, then either ends or
+    /// blocks forever (to exercise cancellation).
+    struct MemoryConsumer {
+        queue: VecDeque<SourceMessage>,
+        block_when_empty: bool,
+    }
+
+    #[async_trait]
+    impl SourceConsumer for MemoryConsumer {
+        async fn recv(&mut self) -> anyhow::Result<Option<SourceMessage>> {
+            match self.queue.pop_front() {
+                Some(msg) => Ok(Some(msg)),
+                None if self.block_when_empty => std::future::pending().await,
+                None => Ok(None),
+            }
+        }
+    }
+
+    /// Consumer whose every `recv` fails, counting the attempts.
+    struct FailingConsumer {
+        attempts: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SourceConsumer for FailingConsumer {
+        async fn recv(&mut self) -> anyhow::Result<Option<SourceMessage>> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            anyhow::bail!("broker unavailable")
+        }
+    }
+
+    fn make_router() -> Arc<Router> {
+        crate::test_support::make_router("tcp_stream", usize::MAX)
+    }
+
+    fn kafka_config(format: SourceFormat, window: &str) -> KafkaSourceConfig {
+        KafkaSourceConfig {
+            brokers: vec!["127.0.0.1:9092".into()],
+            group_id: "wf-test".into(),
+            format,
+            topics: BTreeMap::from([("events".to_string(), window.to_string())]),
+        }
+    }
+
+    fn message(topic: &str, payload: impl Into<Vec<u8>>) -> SourceMessage {
+        SourceMessage {
+            topic: topic.into(),
+            payload: payload.into(),
+        }
+    }
+
+    fn snapshot_row_count(router: &Router) -> usize {
+        router
+            .registry()
+            .snapshot("test_win")
+            .unwrap_or_default()
+            .iter()
+            .map(|b| b.num_rows())
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn json_messages_routed_to_mapped_window() {
+        let router = make_router();
+        let consumer = MemoryConsumer {
+            queue: VecDeque::from([
+                message("events", r#"{"ts":1000000000,"value":1}"#),
+                message(
+                    "events",
+                    "{\"ts\":2000000000,\"value\":2}\n{\"ts\":3000000000,\"value\":3}",
+                ),
+                message("other", r#"{"ts":4000000000,"value":4}"#),
+                message("events", "not json"),
+            ]),
+            block_when_empty: false,
+        };
+        let receiver = KafkaReceiver::new(
+            consumer,
+            &kafka_config(SourceFormat::Json, "test_win"),
+            Arc::clone(&router),
+            None,
+        )
+        .unwrap();
+        receiver.run().await.unwrap();
+
+        // Unmapped topic and undecodable payload are both skipped.
+        assert_eq!(snapshot_row_count(&router), 3);
+    }
+
+    #[tokio::test]
+    async fn arrow_messages_routed_to_mapped_window() {
+        let router = make_router();
+        let batch = RecordBatch::try_new(
+            test_schema(),
+            vec![
+                Arc::new(TimestampNanosecondArray::from(vec![
+                    1_000_000_000,
+                    2_000_000_000,
+                ])),
+                Arc::new(Int64Array::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+        let payload = wp_arrow::ipc::encode_ipc("ignored", &batch).unwrap();
+        let consumer = MemoryConsumer {
+            queue: VecDeque::from([message("events", payload)]),
+            block_when_empty: false,
+        };
+        let receiver = KafkaReceiver::new(
+            consumer,
+            &kafka_config(SourceFormat::Arrow, "test_win"),
+            Arc::clone(&router),
+            None,
+        )
+        .unwrap();
+        receiver.run().await.unwrap();
+
+        assert_eq!(snapshot_row_count(&router), 2);
+    }
+
+    #[tokio::test]
+    async fn cancel_stops_blocked_consumer() {
+        let router = make_router();
+        let consumer = MemoryConsumer {
+            queue: VecDeque::from([message("events", r#"{"ts":1000000000,"value":1}"#)]),
+            block_when_empty: true,
+        };
+        let receiver = KafkaReceiver::new(
+            consumer,
+            &kafka_config(SourceFormat::Json, "test_win"),
+            Arc::clone(&router),
+            None,
+        )
+        .unwrap();
+        let cancel = receiver.cancel_token();
+        let handle = tokio::spawn(receiver.run());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("receiver should stop on cancel")
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot_row_count(&router), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn consume_errors_back_off() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let receiver = KafkaReceiver::new(
+            FailingConsumer {
+                attempts: Arc::clone(&attempts),
+            },
+            &kafka_config(SourceFormat::Json, "test_win"),
+            make_router(),
+            None,
+        )
+        .unwrap();
+        let cancel = receiver.cancel_token();
+        let handle = tokio::spawn(receiver.run());
+
+        // Retries at 0, 200ms, 600ms, 1.4s, 3s: doubling delays, not a spin.
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+
+        // Cancellation interrupts the pending backoff sleep.
+        cancel.cancel();
+        handle.await.unwrap().unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn unknown_window_rejected() {
+        let consumer = MemoryConsumer {
+            queue: VecDeque::new(),
+            block_when_empty: false,
+        };
+        let result = KafkaReceiver::new(
+            consumer,
+            &kafka_config(SourceFormat::Json, "missing"),
+            make_router(),
+            None,
+        );
+        assert!(result.is_err());
+    }
+}
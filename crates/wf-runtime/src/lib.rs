@@ -2,13 +2,21 @@
 mod log_macros;
 
 pub(crate) mod alert_task;
+pub mod dead_letter;
 pub(crate) mod engine_task;
 pub mod error;
 mod evictor_task;
+pub mod health;
+pub mod http_receiver;
+mod json_decode;
+pub mod kafka_source;
 pub mod lifecycle;
+mod match_sample;
 pub(crate) mod metrics;
 pub mod receiver;
 mod schema_bridge;
 pub mod sink_build;
 pub mod sink_factory;
+#[cfg(test)]
+mod test_support;
 pub mod tracing_init;
@@ -1,7 +1,5 @@
-use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
 
 use orion_error::prelude::*;
 
@@ -11,12 +9,14 @@ use wf_core::window::{Router, WindowRegistry};
 use crate::error::{RuntimeReason, RuntimeResult};
 use crate::schema_bridge::schemas_to_window_defs;
 use crate::sink_build::{SinkFactoryRegistry, build_sink_dispatcher};
+use crate::sink_factory::console::ConsoleSinkFactory;
 use crate::sink_factory::file::FileSinkFactory;
+use crate::sink_factory::webhook::WebhookSinkFactory;
 
 use super::compile::{
     build_pipeline_internal_windows, build_run_rules, compile_rules, load_schemas,
 };
-use super::types::BootstrapData;
+use super::types::{BootstrapData, CompiledRules, ValidationSummary};
 
 // ---------------------------------------------------------------------------
 // Phase 1: load_and_compile — pure data transforms + async sink build
@@ -27,49 +27,31 @@ pub(super) async fn load_and_compile(
     config: &FusionConfig,
     base_dir: &Path,
 ) -> RuntimeResult<BootstrapData> {
-    // 1. Load .wfs files → Vec<WindowSchema>
-    let all_schemas = load_schemas(&config.runtime.schemas, base_dir)?;
-
-    // 2. Preprocess .wfl with config.vars → parse → compile → Vec<RulePlan>
-    let all_rule_plans =
-        compile_rules(&config.runtime.rules, base_dir, &config.vars, &all_schemas)?;
-    let (pipeline_schemas, pipeline_window_configs) =
-        build_pipeline_internal_windows(&all_rule_plans, &all_schemas, &config.window_defaults);
-    let mut runtime_schemas = all_schemas.clone();
-    runtime_schemas.extend(pipeline_schemas);
-    let mut runtime_window_configs = config.windows.clone();
-    runtime_window_configs.extend(pipeline_window_configs);
-
-    // 3. Cross-validate over vs over_cap
-    let window_overs: HashMap<String, Duration> = runtime_schemas
-        .iter()
-        .map(|ws| (ws.name.clone(), ws.over))
-        .collect();
-    wf_config::validate_over_vs_over_cap(&runtime_window_configs, &window_overs).owe_conf()?;
-    wf_debug!(
-        conf,
-        windows = config.windows.len(),
-        "over vs over_cap validation passed"
-    );
+    // 1–2. Load schemas, compile rules, derive pipeline internal windows,
+    //      cross-validate them against the config
+    let CompiledRules {
+        rules,
+        schemas: runtime_schemas,
+        window_configs: runtime_window_configs,
+    } = compile_rule_set(config, base_dir)?;
 
-    // 4. Schema bridge: WindowSchema × WindowConfig → Vec<WindowDef>
+    // 3. Schema bridge: WindowSchema × WindowConfig → Vec<WindowDef>
     let window_defs = schemas_to_window_defs(&runtime_schemas, &runtime_window_configs)
         .owe(RuntimeReason::Bootstrap)?;
 
-    // 5. WindowRegistry::build → registry
+    // 4. WindowRegistry::build → registry
     let registry = WindowRegistry::build(window_defs).err_conv()?;
 
-    // 6. Router::new(registry)
+    // 5. Router::new(registry)
     let router = Arc::new(Router::new(registry));
 
-    // 7. Build RunRules (precompute stream_name → alias routing)
-    let rules = build_run_rules(&all_rule_plans, &runtime_schemas);
-
-    // 8. Build connector-based sink dispatcher
+    // 6. Build connector-based sink dispatcher
     let sinks_dir = base_dir.join(&config.sinks);
     let bundle = wf_config::sink::load_sink_config(&sinks_dir).owe_conf()?;
     let mut factory_registry = SinkFactoryRegistry::new();
     factory_registry.register(Arc::new(FileSinkFactory));
+    factory_registry.register(Arc::new(WebhookSinkFactory));
+    factory_registry.register(Arc::new(ConsoleSinkFactory));
     let work_root = config
         .work_root
         .as_ref()
@@ -91,3 +73,87 @@ pub(super) async fn load_and_compile(
         schemas: runtime_schemas,
     })
 }
+
+/// Dry-run the config-loading phase: compile rules, cross-validate them
+/// against the config, build the window registry and check sink routing.
+/// Binds no sockets and spawns no tasks.
+pub(super) fn validate_deployment(
+    config: &FusionConfig,
+    base_dir: &Path,
+) -> RuntimeResult<ValidationSummary> {
+    let CompiledRules {
+        rules,
+        schemas,
+        window_configs,
+    } = compile_rule_set(config, base_dir)?;
+
+    let window_defs =
+        schemas_to_window_defs(&schemas, &window_configs).owe(RuntimeReason::Bootstrap)?;
+    let windows = window_defs.len();
+    WindowRegistry::build(window_defs).err_conv()?;
+
+    let sinks_dir = base_dir.join(&config.sinks);
+    let bundle = wf_config::sink::load_sink_config(&sinks_dir).owe_conf()?;
+    let mut yield_targets: Vec<String> = rules
+        .iter()
+        .flat_map(|r| r.executor.plan().yield_plans.iter())
+        .map(|yp| yp.target.clone())
+        .filter(|t| !t.starts_with("__wf_pipe_"))
+        .collect();
+    yield_targets.sort();
+    yield_targets.dedup();
+    wf_config::sink::validate_sink_coverage(&yield_targets, &bundle).owe_conf()?;
+
+    Ok(ValidationSummary {
+        schemas: schemas.len(),
+        rules: rules.len(),
+        windows,
+        sink_groups: bundle.business.len()
+            + usize::from(bundle.infra_default.is_some())
+            + usize::from(bundle.infra_error.is_some()),
+    })
+}
+
+/// Load schemas and compile rules into `RunRule`s, together with the
+/// runtime window schemas/configs they need (including pipeline internal
+/// windows). Shared by bootstrap and rule hot reload.
+pub(super) fn compile_rule_set(
+    config: &FusionConfig,
+    base_dir: &Path,
+) -> RuntimeResult<CompiledRules> {
+    // 1. Load .wfs files → Vec<WindowSchema>
+    let all_schemas = load_schemas(&config.runtime.schemas, base_dir)?;
+
+    // 2. Preprocess .wfl with config.vars → parse → compile → Vec<RulePlan>
+    let all_rule_plans =
+        compile_rules(&config.runtime.rules, base_dir, &config.vars, &all_schemas)?;
+    let (pipeline_schemas, pipeline_window_configs) =
+        build_pipeline_internal_windows(&all_rule_plans, &all_schemas, &config.window_defaults);
+    let mut schemas = all_schemas;
+    schemas.extend(pipeline_schemas);
+    let mut window_configs = config.windows.clone();
+    window_configs.extend(pipeline_window_configs);
+
+    // Report every cross-file problem (over vs over_cap, duplicate windows,
+    // static windows bound as event sources) at once.
+    wf_config::validate::ensure_no_problems(wf_config::check_deployment(
+        &window_configs,
+        &schemas,
+        &all_rule_plans,
+    ))
+    .owe_conf()?;
+
+    // Build RunRules (precompute stream_name → alias routing)
+    let rules = build_run_rules(
+        &all_rule_plans,
+        &schemas,
+        config.runtime.max_out_of_orderness.as_duration(),
+        config.alert.score_precision,
+    );
+
+    Ok(CompiledRules {
+        rules,
+        schemas,
+        window_configs,
+    })
+}
@@ -82,8 +82,11 @@ pub(super) fn build_pipeline_internal_windows(
         .collect();
 
     let mut derived = Vec::new();
-    for plan in plans {
-        let target = &plan.yield_plan.target;
+    for (plan, yield_plan) in plans
+        .iter()
+        .flat_map(|p| p.yield_plans.iter().map(move |yp| (p, yp)))
+    {
+        let target = &yield_plan.target;
         if !is_pipeline_window_name(target) || known_schemas.contains_key(target) {
             continue;
         }
@@ -101,7 +104,11 @@ pub(super) fn build_pipeline_internal_windows(
             name: PIPE_EVENT_TIME_FIELD.to_string(),
             field_type: FieldType::Base(BaseType::Time),
         }];
-        fields.extend(infer_pipeline_output_fields(plan, &known_schemas));
+        fields.extend(infer_pipeline_output_fields(
+            plan,
+            yield_plan,
+            &known_schemas,
+        ));
 
         let ws = WindowSchema {
             name: target.clone(),
@@ -127,6 +134,7 @@ pub(super) fn build_pipeline_internal_windows(
             watermark: defaults.watermark,
             allowed_lateness: defaults.allowed_lateness,
             late_policy: defaults.late_policy,
+            compact_below: defaults.compact_below,
         })
         .collect();
 
@@ -135,22 +143,26 @@ pub(super) fn build_pipeline_internal_windows(
 
 /// Build [`RunRule`] instances from compiled plans, pre-computing stream
 /// alias routing and constructing the CEP state machines.
+///
+/// `max_out_of_orderness` (from `runtime.max_out_of_orderness`) is applied
+/// to every rule's match plan; `score_precision` (from
+/// `alert.score_precision`) to every rule's executor.
 pub(super) fn build_run_rules(
     plans: &[wf_lang::plan::RulePlan],
     schemas: &[wf_lang::WindowSchema],
+    max_out_of_orderness: Duration,
+    score_precision: Option<u32>,
 ) -> Vec<RunRule> {
     let mut rules = Vec::with_capacity(plans.len());
     for plan in plans {
         let stream_aliases = build_stream_aliases(&plan.binds, schemas);
         let time_field = resolve_time_field(&plan.binds, schemas);
         let limits = plan.limits_plan.clone();
-        let machine = CepStateMachine::with_limits(
-            plan.name.clone(),
-            plan.match_plan.clone(),
-            time_field,
-            limits,
-        );
-        let executor = RuleExecutor::new(plan.clone());
+        let mut match_plan = plan.match_plan.clone();
+        match_plan.max_out_of_orderness = max_out_of_orderness;
+        let machine =
+            CepStateMachine::with_limits(plan.name.clone(), match_plan, time_field, limits);
+        let executor = RuleExecutor::new(plan.clone()).with_score_precision(score_precision);
         rules.push(RunRule {
             machine,
             executor,
@@ -201,20 +213,20 @@ fn find_pipeline_window_over(plans: &[wf_lang::plan::RulePlan], window: &str) ->
         .iter()
         .find(|p| p.binds.iter().any(|b| b.window == window))
         .map(|p| match p.match_plan.window_spec {
-            wf_lang::plan::WindowSpec::Sliding(d)
-            | wf_lang::plan::WindowSpec::Fixed(d)
-            | wf_lang::plan::WindowSpec::Session(d) => d,
+            wf_lang::plan::WindowSpec::Sliding(d) | wf_lang::plan::WindowSpec::Fixed(d) => d,
+            wf_lang::plan::WindowSpec::Session(spec) => spec.gap,
         })
 }
 
 fn infer_pipeline_output_fields(
     plan: &wf_lang::plan::RulePlan,
+    yield_plan: &wf_lang::plan::YieldPlan,
     schemas: &HashMap<String, WindowSchema>,
 ) -> Vec<FieldDef> {
     let key_types = infer_key_field_types(plan, schemas);
     let branch_types = infer_branch_output_types(plan);
 
-    plan.yield_plan
+    yield_plan
         .fields
         .iter()
         .filter_map(|f| {
@@ -330,7 +342,7 @@ fn infer_branch_output_types(plan: &wf_lang::plan::RulePlan) -> HashMap<String,
                 .unwrap_or_else(|| measure_output_name(branch.agg.measure).to_string());
             let field_type = match branch.agg.measure {
                 Measure::Count => FieldType::Base(BaseType::Digit),
-                Measure::Sum | Measure::Avg | Measure::Min | Measure::Max => {
+                Measure::Sum | Measure::Avg | Measure::Min | Measure::Max | Measure::Rate(_) => {
                     FieldType::Base(BaseType::Float)
                 }
                 _ => FieldType::Base(BaseType::Float),
@@ -356,6 +368,7 @@ fn measure_output_name(measure: Measure) -> &'static str {
         Measure::Avg => "avg",
         Measure::Min => "min",
         Measure::Max => "max",
+        Measure::Rate(_) => "rate",
         _ => "measure",
     }
 }
@@ -377,6 +390,7 @@ mod tests {
             watermark: HumanDuration::from(Duration::from_secs(0)),
             allowed_lateness: HumanDuration::from(Duration::from_secs(60)),
             late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
         }
     }
 
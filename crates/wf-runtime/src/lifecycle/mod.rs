@@ -1,10 +1,14 @@
 mod bootstrap;
 mod compile;
+mod reload;
 mod signal;
 mod spawn;
 mod types;
 
 use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
 use orion_error::op_context;
 use orion_error::prelude::*;
@@ -13,17 +17,28 @@ use tokio_util::sync::CancellationToken;
 use wf_config::FusionConfig;
 
 use crate::error::RuntimeResult;
+use crate::health::HealthState;
 
 // Re-export public API
-pub use signal::wait_for_signal;
+pub use reload::{ReloadHandle, ReloadSummary};
+pub use signal::{reload_on_sighup, wait_for_signal};
+pub use types::ValidationSummary;
 
 use crate::metrics::maybe_build_metrics;
-use bootstrap::load_and_compile;
+use bootstrap::{load_and_compile, validate_deployment};
 use spawn::{
-    spawn_alert_task, spawn_evictor_task, spawn_metrics_task, spawn_receiver_task, spawn_rule_tasks,
+    spawn_alert_task, spawn_evictor_task, spawn_http_receiver_task, spawn_metrics_task,
+    spawn_receiver_task, spawn_rule_tasks,
 };
 use types::TaskGroup;
 
+/// Load schemas and rules for `config` and run every startup check —
+/// compilation, cross-file config validation, window registry and sink
+/// routing — without binding sockets or spawning tasks.
+pub fn validate(config: &FusionConfig, base_dir: &Path) -> RuntimeResult<ValidationSummary> {
+    validate_deployment(config, base_dir)
+}
+
 // ---------------------------------------------------------------------------
 // Reactor — the top-level lifecycle handle
 // ---------------------------------------------------------------------------
@@ -34,14 +49,25 @@ use types::TaskGroup;
 /// Task groups are stored in start order and joined in reverse (LIFO)
 /// during [`wait`](Self::wait), ensuring correct drain sequencing:
 /// receiver stops first, then rule tasks drain and flush, then alert
-/// sink flushes to disk, and finally background tasks stop.
+/// sink flushes to disk, and finally background tasks stop. The metrics
+/// group (which also serves the health probes) is kept aside and stopped
+/// last.
 pub struct Reactor {
     cancel: CancellationToken,
     /// Separate cancel token for rule tasks — triggered only after the
     /// receiver has fully stopped, ensuring all in-flight data is drained.
     rule_cancel: CancellationToken,
+    /// Cancel token for the metrics group — triggered after every other
+    /// group has finished, so `/readyz` answers not-ready during the drain.
+    metrics_cancel: CancellationToken,
     groups: Vec<TaskGroup>,
+    metrics_group: TaskGroup,
     listen_addr: SocketAddr,
+    metrics_addr: Option<SocketAddr>,
+    health: Arc<HealthState>,
+    reload: ReloadHandle,
+    /// Upper bound for [`wait`](Self::wait) (`runtime.drain_timeout`).
+    drain_timeout: Duration,
 }
 
 impl Reactor {
@@ -76,12 +102,14 @@ impl Reactor {
             .window_names()
             .map(str::to_string)
             .collect();
-        let metrics = maybe_build_metrics(&config.metrics, &rule_names, &window_names);
+        let sink_names: Vec<String> = data.dispatcher.sink_names().map(str::to_string).collect();
+        let metrics = maybe_build_metrics(&config.metrics, &rule_names, &window_names, &sink_names);
 
-        // Phase 2: Spawn task groups (start order: alert → evictor → rules → receiver → metrics)
+        // Phase 2: Spawn task groups
+        // (start order: alert → evictor → rules → receiver → http_receiver → metrics)
         let mut groups: Vec<TaskGroup> = Vec::with_capacity(5);
 
-        let (alert_tx, alert_group) = spawn_alert_task(data.dispatcher, metrics.clone());
+        let (alert_tx, alert_group) = spawn_alert_task(&config, data.dispatcher, metrics.clone());
         groups.push(alert_group);
 
         groups.push(spawn_evictor_task(
@@ -91,7 +119,7 @@ impl Reactor {
             metrics.clone(),
         ));
 
-        let rule_group = spawn_rule_tasks(
+        let (rule_group, reload_tx) = spawn_rule_tasks(
             data.rules,
             &data.router,
             &data.schemas,
@@ -104,21 +132,52 @@ impl Reactor {
 
         let (listen_addr, receiver_group) = spawn_receiver_task(
             &config,
+            base_dir,
             data.router.clone(),
             cancel.clone(),
             metrics.clone(),
         )
         .await?;
         groups.push(receiver_group);
-        groups
-            .push(spawn_metrics_task(&config, &data.router, cancel.child_token(), metrics).await?);
+        groups.push(
+            spawn_http_receiver_task(&config, &data.router, cancel.clone(), metrics.clone())
+                .await?,
+        );
+        let metrics_cancel = CancellationToken::new();
+        let health = Arc::new(HealthState::default());
+        let (metrics_addr, metrics_group) = spawn_metrics_task(
+            &config,
+            &data.router,
+            metrics_cancel.clone(),
+            metrics,
+            Arc::clone(&health),
+        )
+        .await?;
+
+        let drain_timeout = config.runtime.drain_timeout.as_duration();
+        let reload = ReloadHandle::new(
+            Arc::new(config),
+            base_dir.to_path_buf(),
+            Arc::clone(&data.router),
+            data.schemas,
+            reload_tx,
+        );
+
+        // Windows built, receivers bound, all groups running.
+        health.set_ready(true);
 
         op.mark_suc();
         Ok(Self {
             cancel,
             rule_cancel,
+            metrics_cancel,
             groups,
+            metrics_group,
             listen_addr,
+            metrics_addr,
+            health,
+            reload,
+            drain_timeout,
         })
     }
 
@@ -127,34 +186,45 @@ impl Reactor {
         self.listen_addr
     }
 
-    /// Request graceful shutdown of all tasks.
+    /// Returns the bound metrics/health address, if metrics are enabled.
+    pub fn metrics_addr(&self) -> Option<SocketAddr> {
+        self.metrics_addr
+    }
+
+    /// Request graceful shutdown of all tasks. `/readyz` turns not-ready
+    /// immediately.
     pub fn shutdown(&self) {
         wf_info!(sys, "initiating graceful shutdown");
+        self.health.set_ready(false);
         self.cancel.cancel();
     }
 
     /// Wait for all task groups to complete after shutdown.
     ///
     /// Groups are joined in LIFO order (reverse of start order):
-    /// metrics → receiver → rules → alert → evictor.
+    /// http_receiver → receiver → rules → alert → evictor, then metrics.
     ///
     /// Two-phase shutdown: the receiver is joined first, ensuring all
     /// in-flight data has been routed to windows. Only then are the rule
     /// tasks cancelled so they can do a final drain + flush.
-    pub async fn wait(mut self) -> RuntimeResult<()> {
-        while let Some(group) = self.groups.pop() {
-            let name = group.name;
-            wf_debug!(sys, task_group = name, "waiting for task group to finish");
-            group.wait().await?;
-            wf_debug!(sys, task_group = name, "task group finished");
-
-            if name == "receiver" {
-                // Receiver fully stopped — all data is in windows.
-                // Now signal engine tasks to do their final drain + flush.
-                self.rule_cancel.cancel();
-            }
-        }
-        Ok(())
+    ///
+    /// The whole drain is bounded by `runtime.drain_timeout`: groups still
+    /// running at the deadline are aborted (in the same order) with a
+    /// warning, so shutdown always terminates.
+    pub async fn wait(self) -> RuntimeResult<()> {
+        self.health.set_ready(false);
+        let deadline = tokio::time::Instant::now() + self.drain_timeout;
+        let result = drain_groups(self.groups, &self.rule_cancel, deadline).await;
+
+        // Metrics stop last so health probes and the final summary cover
+        // the whole drain.
+        self.metrics_cancel.cancel();
+        result.and(drain_groups(vec![self.metrics_group], &self.rule_cancel, deadline).await)
+    }
+
+    /// Returns a handle for hot-reloading rules (see [`reload_on_sighup`]).
+    pub fn reload_handle(&self) -> ReloadHandle {
+        self.reload.clone()
     }
 
     /// Returns a clone of the root cancellation token (for signal integration).
@@ -162,3 +232,129 @@ impl Reactor {
         self.cancel.clone()
     }
 }
+
+/// Join `groups` in LIFO order, aborting whatever is still running at
+/// `deadline`. Cancels `rule_cancel` once the receiver group has stopped.
+///
+/// A failing group does not stop the drain: every group is still joined or
+/// aborted, and the first error is returned at the end.
+async fn drain_groups(
+    mut groups: Vec<TaskGroup>,
+    rule_cancel: &CancellationToken,
+    deadline: tokio::time::Instant,
+) -> RuntimeResult<()> {
+    let mut first_err = None;
+    while let Some(group) = groups.pop() {
+        let name = group.name;
+        wf_debug!(sys, task_group = name, "waiting for task group to finish");
+        match group.wait_until(deadline).await {
+            Ok(true) => wf_debug!(sys, task_group = name, "task group finished"),
+            Ok(false) => wf_warn!(
+                sys,
+                task_group = name,
+                "drain timeout exceeded, task group aborted"
+            ),
+            Err(e) => {
+                wf_warn!(sys, task_group = name, error = %e, "task group failed");
+                first_err.get_or_insert(e);
+            }
+        }
+
+        if name == "receiver" {
+            // Receiver stopped (or failed) — all routed data is in windows.
+            // Now signal engine tasks to do their final drain + flush.
+            rule_cancel.cancel();
+        }
+    }
+    first_err.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn drain_aborts_slow_group_at_deadline() {
+        let rule_cancel = CancellationToken::new();
+
+        // Start order: alert → rules (stuck) → receiver.
+        let mut alert = TaskGroup::new("alert");
+        alert.push(tokio::spawn(async { Ok(()) }));
+
+        let (stuck_tx, stuck_rx) = tokio::sync::oneshot::channel::<()>();
+        let mut rules = TaskGroup::new("rules");
+        rules.push(tokio::spawn(async move {
+            let _keep = stuck_tx;
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(())
+        }));
+
+        let mut receiver = TaskGroup::new("receiver");
+        receiver.push(tokio::spawn(async { Ok(()) }));
+
+        let start = tokio::time::Instant::now();
+        let deadline = start + Duration::from_millis(200);
+        drain_groups(vec![alert, rules, receiver], &rule_cancel, deadline)
+            .await
+            .unwrap();
+
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(200), "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(5), "{elapsed:?}");
+        assert!(rule_cancel.is_cancelled());
+        // The stuck task was aborted, dropping its sender.
+        assert!(stuck_rx.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn drain_continues_past_failed_group() {
+        let rule_cancel = CancellationToken::new();
+
+        // Start order: alert (stuck) → rules → receiver (fails).
+        let (stuck_tx, stuck_rx) = tokio::sync::oneshot::channel::<()>();
+        let mut alert = TaskGroup::new("alert");
+        alert.push(tokio::spawn(async move {
+            let _keep = stuck_tx;
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(())
+        }));
+
+        let mut rules = TaskGroup::new("rules");
+        let cancel = rule_cancel.clone();
+        rules.push(tokio::spawn(async move {
+            cancel.cancelled().await;
+            Ok(())
+        }));
+
+        let mut receiver = TaskGroup::new("receiver");
+        receiver.push(tokio::spawn(async { anyhow::bail!("listener failed") }));
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(200);
+        let result = drain_groups(vec![alert, rules, receiver], &rule_cancel, deadline).await;
+
+        assert!(result.is_err());
+        // The rules were still told to drain, and the drain went on to
+        // abort the stuck alert group at the deadline.
+        assert!(rule_cancel.is_cancelled());
+        assert!(stuck_rx.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_task_aborts_rest_of_group() {
+        let (stuck_tx, stuck_rx) = tokio::sync::oneshot::channel::<()>();
+        let mut group = TaskGroup::new("rules");
+        group.push(tokio::spawn(async { anyhow::bail!("rule failed") }));
+        group.push(tokio::spawn(async move {
+            let _keep = stuck_tx;
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(())
+        }));
+
+        assert!(group.wait().await.is_err());
+        // The stuck sibling was aborted, dropping its sender.
+        tokio::time::timeout(Duration::from_secs(1), stuck_rx)
+            .await
+            .expect("sibling task should be aborted")
+            .unwrap_err();
+    }
+}
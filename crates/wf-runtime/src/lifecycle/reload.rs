@@ -0,0 +1,632 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use orion_error::prelude::*;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use wf_config::{AlertOverflowPolicy, FusionConfig};
+use wf_core::alert::OutputRecord;
+use wf_core::window::Router;
+use wf_lang::WindowSchema;
+use wf_lang::plan::RulePlan;
+
+use crate::engine_task::{RuleTaskConfig, run_rule_task};
+use crate::error::{RuntimeReason, RuntimeResult};
+use crate::metrics::RuntimeMetrics;
+use crate::schema_bridge::window_arrow_schema;
+
+use super::bootstrap::compile_rule_set;
+use super::spawn::resolve_window_sources;
+use super::types::RunRule;
+
+// ---------------------------------------------------------------------------
+// ReloadHandle — public entry point for rule hot reload
+// ---------------------------------------------------------------------------
+
+/// Rule names affected by a successful reload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadSummary {
+    /// Rules that did not exist before; started fresh.
+    pub added: Vec<String>,
+    /// Rules whose compiled plan changed; old task drained, new one started fresh.
+    pub replaced: Vec<String>,
+    /// Rules no longer present; drained and stopped.
+    pub removed: Vec<String>,
+    /// Rules with an identical plan; kept running with their state.
+    pub unchanged: Vec<String>,
+}
+
+/// Cloneable handle that recompiles the configured `.wfs` / `.wfl` files and
+/// swaps the running rule tasks, leaving receivers untouched. Windows keep
+/// their data; compatible schema changes (added fields) widen them in place.
+#[derive(Clone)]
+pub struct ReloadHandle {
+    config: Arc<FusionConfig>,
+    base_dir: PathBuf,
+    router: Arc<Router>,
+    /// Window schemas currently applied. The lock also serializes reloads.
+    schemas: Arc<tokio::sync::Mutex<Vec<WindowSchema>>>,
+    tx: mpsc::Sender<ReloadRequest>,
+}
+
+impl ReloadHandle {
+    pub(super) fn new(
+        config: Arc<FusionConfig>,
+        base_dir: PathBuf,
+        router: Arc<Router>,
+        schemas: Vec<WindowSchema>,
+        tx: mpsc::Sender<ReloadRequest>,
+    ) -> Self {
+        Self {
+            config,
+            base_dir,
+            router,
+            schemas: Arc::new(tokio::sync::Mutex::new(schemas)),
+            tx,
+        }
+    }
+
+    /// Recompile rules through the bootstrap path and apply them.
+    ///
+    /// Window schemas may only evolve compatibly (see
+    /// [`check_schema_evolution`]); evolved windows are widened all or
+    /// nothing before the new rules start. On any error (compile failure,
+    /// incompatible window schemas, engine shutting down) the running rules
+    /// and windows are left as they are.
+    pub async fn reload(&self) -> RuntimeResult<ReloadSummary> {
+        let mut schemas = self.schemas.lock().await;
+        let compiled = compile_rule_set(&self.config, &self.base_dir)?;
+        let evolved = check_schema_evolution(&schemas, &compiled.schemas).map_err(|problems| {
+            StructError::from(RuntimeReason::Bootstrap).with_detail(format!(
+                "incompatible window schema change (restart wfusion to apply): {}",
+                problems.join("; ")
+            ))
+        })?;
+
+        // Reserve the supervisor's queue slot before touching any window, so
+        // a stopped supervisor leaves windows and schemas on the old version.
+        let Ok(permit) = self.tx.reserve().await else {
+            return shutting_down();
+        };
+        evolve_windows(&self.router, &evolved)?;
+        *schemas = compiled.schemas.clone();
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        permit.send(ReloadRequest {
+            rules: compiled.rules,
+            schemas: compiled.schemas,
+            reply: reply_tx,
+        });
+        match reply_rx.await {
+            Ok(summary) => Ok(summary),
+            Err(_) => shutting_down(),
+        }
+    }
+}
+
+/// Widen every window in `evolved` to its new schema, all or nothing.
+///
+/// Each window is write-locked and validated (with its batches padded)
+/// before any of them is changed, so a window rejecting its schema leaves
+/// every window — and the rules reading them — on the old version.
+fn evolve_windows(router: &Router, evolved: &[&WindowSchema]) -> RuntimeResult<()> {
+    let mut prepared = Vec::with_capacity(evolved.len());
+    for ws in evolved {
+        let prepare = || -> anyhow::Result<_> {
+            let schema = window_arrow_schema(ws)?;
+            let window = router
+                .registry()
+                .get_window(&ws.name)
+                .ok_or_else(|| anyhow::anyhow!("window {:?} not found", ws.name))?;
+            let window = window.write().expect("window lock poisoned");
+            let evolution = window.prepare_evolution(Arc::new(schema))?;
+            Ok((window, evolution))
+        };
+        prepared.push(prepare().map_err(|e| {
+            StructError::from(RuntimeReason::Bootstrap)
+                .with_detail(format!("evolving window {:?}: {e}", ws.name))
+        })?);
+    }
+
+    for (ws, (mut window, evolution)) in evolved.iter().zip(prepared) {
+        if let Some(evolution) = evolution {
+            window.commit_evolution(evolution);
+            wf_info!(conf, window = %ws.name, "window schema evolved");
+        }
+    }
+    Ok(())
+}
+
+fn shutting_down<T>() -> RuntimeResult<T> {
+    StructError::from(RuntimeReason::Shutdown)
+        .with_detail("rule supervisor stopped; reload rejected")
+        .err()
+}
+
+/// Check that `new` window schemas (matched to `old` by name, independent of
+/// load order) are compatible supersets: the same windows, streams, time
+/// field and retention, with every existing field kept at the same type.
+/// Only added fields are allowed.
+///
+/// Returns the schemas that gained fields, or every incompatibility found.
+pub(super) fn check_schema_evolution<'a>(
+    old: &[WindowSchema],
+    new: &'a [WindowSchema],
+) -> Result<Vec<&'a WindowSchema>, Vec<String>> {
+    let mut problems = Vec::new();
+    let mut evolved = Vec::new();
+
+    for ws in old {
+        if !new.iter().any(|n| n.name == ws.name) {
+            problems.push(format!("window {:?} removed", ws.name));
+        }
+    }
+    for ws in new {
+        let Some(prev) = old.iter().find(|o| o.name == ws.name) else {
+            problems.push(format!("window {:?} added", ws.name));
+            continue;
+        };
+        if prev.streams != ws.streams {
+            problems.push(format!("window {:?}: streams changed", ws.name));
+        }
+        if prev.time_field != ws.time_field {
+            problems.push(format!("window {:?}: time field changed", ws.name));
+        }
+        if prev.over != ws.over {
+            problems.push(format!("window {:?}: over changed", ws.name));
+        }
+        for field in &prev.fields {
+            match ws.fields.iter().find(|f| f.name == field.name) {
+                Some(f) if f.field_type == field.field_type => {}
+                Some(f) => problems.push(format!(
+                    "window {:?}: field {:?} retyped from {:?} to {:?}",
+                    ws.name, field.name, field.field_type, f.field_type
+                )),
+                None => problems.push(format!(
+                    "window {:?}: field {:?} removed",
+                    ws.name, field.name
+                )),
+            }
+        }
+        if ws.fields != prev.fields {
+            evolved.push(ws);
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(evolved)
+    } else {
+        Err(problems)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rule supervisor — owns one task per rule, applies reload requests
+// ---------------------------------------------------------------------------
+
+/// A compiled rule set sent to the supervisor, answered with a summary.
+pub(super) struct ReloadRequest {
+    rules: Vec<RunRule>,
+    schemas: Vec<WindowSchema>,
+    reply: oneshot::Sender<ReloadSummary>,
+}
+
+/// Everything needed to spawn a rule task, shared across reloads.
+pub(super) struct RuleSpawner {
+    pub router: Arc<Router>,
+    pub schemas: Vec<WindowSchema>,
+    pub alert_tx: mpsc::Sender<OutputRecord>,
+    pub cancel: CancellationToken,
+    /// Override for every rule; `None` derives it from each rule's window.
+    pub timeout_scan_interval: Option<Duration>,
+    pub alert_overflow: AlertOverflowPolicy,
+    pub metrics: Option<Arc<RuntimeMetrics>>,
+}
+
+/// One running rule task. Dropping the slot aborts the task, so rule tasks
+/// never outlive the supervisor (e.g. when a drain timeout aborts it).
+struct RuleSlot {
+    plan: RulePlan,
+    stream_aliases: HashMap<String, Vec<String>>,
+    cancel: CancellationToken,
+    handle: JoinHandle<anyhow::Result<()>>,
+}
+
+impl RuleSpawner {
+    fn spawn(&self, rule: RunRule) -> RuleSlot {
+        let cancel = self.cancel.child_token();
+        let window_sources =
+            resolve_window_sources(&rule.stream_aliases, &self.schemas, self.router.registry());
+        let plan = rule.executor.plan().clone();
+        let stream_aliases = rule.stream_aliases.clone();
+
+        let task_config = RuleTaskConfig {
+            machine: rule.machine,
+            executor: rule.executor,
+            window_sources,
+            stream_aliases: rule.stream_aliases,
+            alert_tx: self.alert_tx.clone(),
+            alert_overflow: self.alert_overflow,
+            cancel: cancel.clone(),
+            timeout_scan_interval: self.timeout_scan_interval,
+            router: Arc::clone(&self.router),
+            metrics: self.metrics.clone(),
+        };
+
+        RuleSlot {
+            plan,
+            stream_aliases,
+            cancel,
+            handle: tokio::spawn(async move { run_rule_task(task_config).await }),
+        }
+    }
+}
+
+impl RuleSlot {
+    fn runs(&self, rule: &RunRule) -> bool {
+        self.plan == *rule.executor.plan() && self.stream_aliases == rule.stream_aliases
+    }
+
+    /// Cancel the task and wait for its final drain + flush.
+    async fn stop(mut self, name: &str) {
+        self.cancel.cancel();
+        match (&mut self.handle).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => wf_warn!(conf, rule = name, error = %e, "rule task failed on stop"),
+            Err(e) => wf_warn!(conf, rule = name, error = %e, "rule task join error"),
+        }
+    }
+}
+
+impl Drop for RuleSlot {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Run every rule as its own task until `cancel` fires, applying reload
+/// requests in between. Returns after all rule tasks have drained.
+///
+/// The supervisor owns the last `alert_tx` sender, so the alert channel
+/// closes once it and all rule tasks have finished. Aborting the supervisor
+/// aborts its rule tasks too.
+pub(super) async fn run_rule_supervisor(
+    rules: Vec<RunRule>,
+    mut spawner: RuleSpawner,
+    mut reload_rx: mpsc::Receiver<ReloadRequest>,
+    cancel: CancellationToken,
+) -> anyhow::Result<()> {
+    let mut slots: BTreeMap<String, RuleSlot> = BTreeMap::new();
+    for rule in rules {
+        let name = rule.machine.rule_name().to_string();
+        slots.insert(name, spawner.spawn(rule));
+    }
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => break,
+            Some(request) = reload_rx.recv() => {
+                spawner.schemas = request.schemas;
+                let summary = apply_reload(&mut slots, &spawner, request.rules).await;
+                wf_info!(
+                    conf,
+                    added = summary.added.len(),
+                    replaced = summary.replaced.len(),
+                    removed = summary.removed.len(),
+                    unchanged = summary.unchanged.len(),
+                    "rules reloaded"
+                );
+                if let Some(metrics) = &spawner.metrics {
+                    for name in summary.added.iter().filter(|name| !metrics.tracks_rule(name)) {
+                        wf_warn!(
+                            conf,
+                            rule = %name,
+                            "added rule has no wf_rule_* metrics or /debug/matches samples until restart"
+                        );
+                    }
+                }
+                let _ = request.reply.send(summary);
+            }
+        }
+    }
+
+    // Rule tokens are children of `cancel`, so every task is draining now.
+    // Join all of them before reporting the first failure.
+    let mut first_err = None;
+    for (name, mut slot) in slots {
+        let err = match (&mut slot.handle).await {
+            Ok(Ok(())) => continue,
+            Ok(Err(e)) => {
+                wf_warn!(conf, rule = %name, error = %e, "rule task failed");
+                e
+            }
+            Err(e) => {
+                wf_warn!(conf, rule = %name, error = %e, "rule task join error");
+                anyhow::Error::from(e).context(format!("rule task {name:?}"))
+            }
+        };
+        first_err.get_or_insert(err);
+    }
+    first_err.map_or(Ok(()), Err)
+}
+
+/// Diff `rules` against the running set by rule name and swap tasks.
+async fn apply_reload(
+    slots: &mut BTreeMap<String, RuleSlot>,
+    spawner: &RuleSpawner,
+    rules: Vec<RunRule>,
+) -> ReloadSummary {
+    let mut summary = ReloadSummary::default();
+    let incoming: BTreeMap<String, RunRule> = rules
+        .into_iter()
+        .map(|rule| (rule.machine.rule_name().to_string(), rule))
+        .collect();
+
+    let removed: Vec<String> = slots
+        .keys()
+        .filter(|name| !incoming.contains_key(*name))
+        .cloned()
+        .collect();
+    for name in removed {
+        if let Some(slot) = slots.remove(&name) {
+            slot.stop(&name).await;
+        }
+        summary.removed.push(name);
+    }
+
+    for (name, rule) in incoming {
+        match slots.remove(&name) {
+            Some(slot) if slot.runs(&rule) => {
+                slots.insert(name.clone(), slot);
+                summary.unchanged.push(name);
+            }
+            Some(slot) => {
+                slot.stop(&name).await;
+                slots.insert(name.clone(), spawner.spawn(rule));
+                summary.replaced.push(name);
+            }
+            None => {
+                slots.insert(name.clone(), spawner.spawn(rule));
+                summary.added.push(name);
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{StringArray, TimestampNanosecondArray};
+    use arrow::record_batch::RecordBatch;
+    use wf_config::{DistMode, EvictPolicy, LatePolicy, WindowConfig};
+    use wf_core::window::WindowRegistry;
+    use wf_lang::{BaseType, FieldDef, FieldType};
+
+    use crate::schema_bridge::schemas_to_window_defs;
+
+    use super::super::compile::build_run_rules;
+    use super::super::types::TaskGroup;
+
+    fn auth_events(fields: &[(&str, BaseType)]) -> WindowSchema {
+        WindowSchema {
+            name: "auth_events".into(),
+            streams: vec!["syslog".into()],
+            time_field: Some("event_time".into()),
+            over: Duration::from_secs(300),
+            fields: fields
+                .iter()
+                .map(|(name, ty)| FieldDef {
+                    name: (*name).into(),
+                    field_type: FieldType::Base(ty.clone()),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn schema_evolution_accepts_added_field() {
+        let old = vec![auth_events(&[
+            ("sip", BaseType::Ip),
+            ("event_time", BaseType::Time),
+        ])];
+        let new = vec![auth_events(&[
+            ("sip", BaseType::Ip),
+            ("event_time", BaseType::Time),
+            ("country", BaseType::Chars),
+        ])];
+
+        let evolved = check_schema_evolution(&old, &new).unwrap();
+        assert_eq!(evolved.len(), 1);
+        assert_eq!(evolved[0].fields.len(), 3);
+        assert!(check_schema_evolution(&old, &old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn schema_evolution_rejects_retyped_and_removed_fields() {
+        let old = vec![auth_events(&[
+            ("sip", BaseType::Ip),
+            ("fail_count", BaseType::Digit),
+            ("event_time", BaseType::Time),
+        ])];
+        let new = vec![auth_events(&[
+            ("sip", BaseType::Chars),
+            ("event_time", BaseType::Time),
+        ])];
+
+        let problems = check_schema_evolution(&old, &new).unwrap_err();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(
+            problems[0].contains("field \"sip\" retyped"),
+            "{problems:?}"
+        );
+        assert!(
+            problems[1].contains("field \"fail_count\" removed"),
+            "{problems:?}"
+        );
+
+        let mut moved = new.clone();
+        moved[0].over = Duration::from_secs(60);
+        moved[0].fields = old[0].fields.clone();
+        let problems = check_schema_evolution(&old, &moved).unwrap_err();
+        assert_eq!(problems, vec!["window \"auth_events\": over changed"]);
+    }
+
+    #[test]
+    fn rejected_window_evolution_leaves_other_windows_unchanged() {
+        let auth_old = auth_events(&[("sip", BaseType::Ip), ("event_time", BaseType::Time)]);
+        let mut feed_old = auth_events(&[("ioc", BaseType::Chars), ("event_time", BaseType::Time)]);
+        feed_old.name = "threat_feed".into();
+        feed_old.streams = vec!["feed".into()];
+
+        let defs = schemas_to_window_defs(
+            &[auth_old.clone(), feed_old.clone()],
+            &[window_config("auth_events"), window_config("threat_feed")],
+        )
+        .unwrap();
+        let router = Router::new(WindowRegistry::build(defs).unwrap());
+        let old_schema = Arc::new(window_arrow_schema(&auth_old).unwrap());
+        let ts = 1_700_000_000_000_000_000;
+        router
+            .route(
+                "syslog",
+                RecordBatch::try_new(
+                    Arc::clone(&old_schema),
+                    vec![
+                        Arc::new(StringArray::from(vec!["10.0.0.1"])),
+                        Arc::new(TimestampNanosecondArray::from(vec![ts])),
+                    ],
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        // auth_events gains a field; threat_feed retypes one, which the
+        // window itself rejects.
+        let auth_new = auth_events(&[
+            ("sip", BaseType::Ip),
+            ("event_time", BaseType::Time),
+            ("country", BaseType::Chars),
+        ]);
+        let mut feed_bad = auth_events(&[("ioc", BaseType::Digit), ("event_time", BaseType::Time)]);
+        feed_bad.name = "threat_feed".into();
+
+        assert!(evolve_windows(&router, &[&auth_new, &feed_bad]).is_err());
+
+        let window = router.registry().get_window("auth_events").unwrap();
+        let window = window.read().unwrap();
+        assert_eq!(window.schema(), &old_schema);
+        assert_eq!(window.snapshot()[0].schema(), old_schema);
+    }
+
+    const SUPERVISOR_WFS: &str = r#"
+window auth_events {
+    stream = "syslog"
+    time = event_time
+    over = 5m
+    fields {
+        sip: ip
+        event_time: time
+    }
+}
+
+window alerts {
+    over = 0
+    fields {
+        sip: ip
+    }
+}
+"#;
+
+    const SUPERVISOR_WFL: &str = r#"
+rule every_login {
+  events { e : auth_events }
+  match<sip:5m> {
+    on event { e | count >= 1; }
+  } -> score(50.0)
+  entity(ip, e.sip)
+  yield alerts (sip = e.sip)
+}
+"#;
+
+    fn window_config(name: &str) -> WindowConfig {
+        WindowConfig {
+            name: name.into(),
+            mode: DistMode::Local,
+            max_window_bytes: usize::MAX.into(),
+            over_cap: Duration::from_secs(3600).into(),
+            evict_policy: EvictPolicy::TimeFirst,
+            watermark: Duration::from_secs(0).into(),
+            allowed_lateness: Duration::from_secs(3600).into(),
+            late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn drain_timeout_aborts_supervised_rule_tasks() {
+        let schemas = wf_lang::parse_wfs(SUPERVISOR_WFS).unwrap();
+        let wfl = wf_lang::parse_wfl(SUPERVISOR_WFL).unwrap();
+        let plans = wf_lang::compile_wfl(&wfl, &schemas).unwrap();
+        let rules = build_run_rules(&plans, &schemas, Duration::ZERO, None);
+
+        let defs = schemas_to_window_defs(&schemas[..1], &[window_config("auth_events")]).unwrap();
+        let router = Arc::new(Router::new(WindowRegistry::build(defs).unwrap()));
+
+        // A stalled sink: one slot, never drained, so the rule task blocks
+        // on its second alert during the final drain.
+        let (alert_tx, alert_rx) = mpsc::channel(1);
+        let cancel = CancellationToken::new();
+        let spawner = RuleSpawner {
+            router: Arc::clone(&router),
+            schemas: schemas.clone(),
+            alert_tx,
+            cancel: cancel.clone(),
+            timeout_scan_interval: Some(Duration::from_secs(60)),
+            alert_overflow: AlertOverflowPolicy::Block,
+            metrics: None,
+        };
+        let (_reload_tx, reload_rx) = mpsc::channel(1);
+        let mut group = TaskGroup::new("rules");
+        group.push(tokio::spawn(run_rule_supervisor(
+            rules,
+            spawner,
+            reload_rx,
+            cancel.clone(),
+        )));
+
+        let ts = 1_700_000_000_000_000_000;
+        let batch = RecordBatch::try_new(
+            Arc::new(window_arrow_schema(&schemas[0]).unwrap()),
+            vec![
+                Arc::new(StringArray::from(vec!["10.0.0.1", "10.0.0.2", "10.0.0.3"])),
+                Arc::new(TimestampNanosecondArray::from(vec![ts; 3])),
+            ],
+        )
+        .unwrap();
+        router.route("syslog", batch).unwrap();
+        cancel.cancel();
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(200);
+        assert!(
+            !group.wait_until(deadline).await.unwrap(),
+            "drain must time out"
+        );
+
+        // Aborting the supervisor drops every rule task and its `alert_tx`,
+        // so the alert channel closes and the alert group can finish.
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while !alert_rx.is_closed() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("rule tasks outlived the aborted supervisor");
+    }
+}
@@ -1,5 +1,7 @@
 use tokio_util::sync::CancellationToken;
 
+use super::reload::ReloadHandle;
+
 /// Register Ctrl-C (SIGINT) and SIGTERM handling; cancel the engine on first
 /// signal received.
 pub async fn wait_for_signal(cancel: CancellationToken) {
@@ -28,3 +30,42 @@ pub async fn wait_for_signal(cancel: CancellationToken) {
     }
     cancel.cancel();
 }
+
+/// Reload rules on every SIGHUP until `cancel` fires.
+///
+/// A failed reload (e.g. a compile error) keeps the running rules and logs
+/// the error. No-op on non-unix platforms.
+pub async fn reload_on_sighup(handle: ReloadHandle, cancel: CancellationToken) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut sighup = signal(SignalKind::hangup()).expect("failed to listen for SIGHUP");
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = sighup.recv() => {
+                    wf_info!(sys, signal = "SIGHUP", "received signal, reloading rules");
+                    match handle.reload().await {
+                        Ok(summary) => wf_info!(
+                            conf,
+                            added = ?summary.added,
+                            replaced = ?summary.replaced,
+                            removed = ?summary.removed,
+                            "rule reload applied"
+                        ),
+                        Err(e) => wf_warn!(
+                            conf,
+                            error = %e,
+                            "rule reload failed, keeping current rules"
+                        ),
+                    }
+                }
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = handle;
+        cancel.cancelled().await;
+    }
+}
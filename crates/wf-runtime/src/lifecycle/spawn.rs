@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
 
 use orion_error::prelude::*;
 use tokio::net::TcpListener;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
 use wf_config::FusionConfig;
@@ -14,12 +15,18 @@ use wf_core::sink::SinkDispatcher;
 use wf_core::window::{Evictor, Router, WindowRegistry};
 
 use crate::alert_task;
-use crate::engine_task::{RuleTaskConfig, WindowSource, run_rule_task};
+use crate::dead_letter::DeadLetterSink;
+use crate::engine_task::WindowSource;
 use crate::error::RuntimeResult;
 use crate::evictor_task;
+use crate::health::HealthState;
+use crate::http_receiver::HttpReceiver;
+#[cfg(feature = "kafka")]
+use crate::kafka_source::KafkaReceiver;
 use crate::metrics::{RuntimeMetrics, run_metrics_task};
-use crate::receiver::Receiver;
+use crate::receiver::{Receiver, load_tls_acceptor};
 
+use super::reload::{ReloadRequest, RuleSpawner, run_rule_supervisor};
 use super::types::{RunRule, TaskGroup};
 
 // ---------------------------------------------------------------------------
@@ -29,13 +36,23 @@ use super::types::{RunRule, TaskGroup};
 /// Spawn the alert pipeline: build channel, spawn consumer task.
 /// Returns (alert_tx, task_group).
 pub(super) fn spawn_alert_task(
+    config: &FusionConfig,
     dispatcher: Arc<SinkDispatcher>,
     metrics: Option<Arc<RuntimeMetrics>>,
 ) -> (mpsc::Sender<OutputRecord>, TaskGroup) {
-    let (alert_tx, alert_rx) = mpsc::channel(alert_task::ALERT_CHANNEL_CAPACITY);
+    let suppress_ttl = config.alert.suppress_ttl.map(|ttl| ttl.as_duration());
+    let priority_route = config.alert.priority_route.clone();
+    let (alert_tx, alert_rx) = mpsc::channel(config.alert.channel_capacity);
     let mut group = TaskGroup::new("alert");
     group.push(tokio::spawn(async move {
-        alert_task::run_alert_dispatcher(alert_rx, dispatcher, metrics).await;
+        alert_task::run_alert_dispatcher(
+            alert_rx,
+            dispatcher,
+            metrics,
+            suppress_ttl,
+            priority_route,
+        )
+        .await;
         Ok(())
     }));
     (alert_tx, group)
@@ -59,49 +76,43 @@ pub(super) fn spawn_evictor_task(
     group
 }
 
-/// Spawn one independent task per compiled rule.
+/// Spawn the rule supervisor, which runs one independent task per compiled
+/// rule and swaps them on reload requests.
 ///
 /// Each rule task owns its `CepStateMachine` exclusively (no `Arc<Mutex>`).
 /// It subscribes to window notifications and uses cursor-based `read_since()`
-/// to pull new batches.
+/// to pull new batches. Returns the task group and the reload request sender.
 pub(super) fn spawn_rule_tasks(
     rules: Vec<RunRule>,
     router: &Arc<Router>,
     schemas: &[wf_lang::WindowSchema],
     alert_tx: mpsc::Sender<OutputRecord>,
-    _config: &FusionConfig,
+    config: &FusionConfig,
     cancel: CancellationToken,
     metrics: Option<Arc<RuntimeMetrics>>,
-) -> TaskGroup {
-    let mut group = TaskGroup::new("rules");
-    let timeout_scan_interval = Duration::from_secs(1);
-
-    for rule in rules {
-        let window_sources =
-            resolve_window_sources(&rule.stream_aliases, schemas, router.registry());
-
-        let task_config = RuleTaskConfig {
-            machine: rule.machine,
-            executor: rule.executor,
-            window_sources,
-            stream_aliases: rule.stream_aliases,
-            alert_tx: alert_tx.clone(),
-            cancel: cancel.child_token(),
-            timeout_scan_interval,
-            router: Arc::clone(router),
-            metrics: metrics.clone(),
-        };
-
-        group.push(tokio::spawn(
-            async move { run_rule_task(task_config).await },
-        ));
-    }
-
-    // Drop our copy of alert_tx so the alert channel closes when all rule
-    // tasks finish.
-    drop(alert_tx);
+) -> (TaskGroup, mpsc::Sender<ReloadRequest>) {
+    let (reload_tx, reload_rx) = mpsc::channel(1);
+    // The supervisor takes our alert_tx, so the alert channel closes when it
+    // and all rule tasks finish.
+    let spawner = RuleSpawner {
+        router: Arc::clone(router),
+        schemas: schemas.to_vec(),
+        alert_tx,
+        cancel: cancel.clone(),
+        timeout_scan_interval: config
+            .runtime
+            .timeout_scan_interval
+            .as_ref()
+            .map(|d| d.as_duration()),
+        alert_overflow: config.alert.on_full,
+        metrics,
+    };
 
-    group
+    let mut group = TaskGroup::new("rules");
+    group.push(tokio::spawn(run_rule_supervisor(
+        rules, spawner, reload_rx, cancel,
+    )));
+    (group, reload_tx)
 }
 
 /// Resolve which windows a rule needs to subscribe to, based on its
@@ -149,49 +160,150 @@ pub(super) fn resolve_window_sources(
     sources
 }
 
-/// Bind the receiver and spawn its task.
-/// Returns (listen_addr, task_group).
+/// Bind the receiver (TLS-terminated when `[server.tls]` is set) and spawn
+/// its task. Returns (listen_addr, task_group).
 pub(super) async fn spawn_receiver_task(
     config: &FusionConfig,
+    base_dir: &Path,
     router: Arc<Router>,
     cancel: CancellationToken,
     metrics: Option<Arc<RuntimeMetrics>>,
 ) -> RuntimeResult<(SocketAddr, TaskGroup)> {
-    let receiver = Receiver::bind(&config.server.listen, router, metrics)
+    let mut receiver = Receiver::bind(&config.server.listen, Arc::clone(&router), metrics.clone())
         .await
         .owe_sys()?;
+    if let Some(tls) = &config.server.tls {
+        let acceptor =
+            load_tls_acceptor(&base_dir.join(&tls.cert), &base_dir.join(&tls.key)).owe_conf()?;
+        receiver = receiver.with_tls(acceptor);
+        wf_info!(conn, cert = %tls.cert, "tcp receiver TLS enabled");
+    }
+    let dead_letter = match &config.server.dead_letter {
+        Some(dlq) => {
+            let sink = DeadLetterSink::open(&base_dir.join(&dlq.path)).owe_conf()?;
+            wf_info!(conn, path = %dlq.path, "dead-letter file enabled");
+            Some(Arc::new(sink))
+        }
+        None => None,
+    };
+    if let Some(sink) = &dead_letter {
+        receiver = receiver.with_dead_letter(Arc::clone(sink));
+    }
     let listen_addr = receiver.local_addr().owe_sys()?;
     let receiver_cancel = receiver.cancel_token();
+    let tcp_cancel = cancel.clone();
     tokio::spawn(async move {
-        cancel.cancelled().await;
+        tcp_cancel.cancelled().await;
         receiver_cancel.cancel();
     });
     let mut group = TaskGroup::new("receiver");
     group.push(tokio::spawn(async move { receiver.run().await }));
+
+    if let Some(kafka) = &config.server.kafka {
+        group.push(spawn_kafka_receiver(
+            kafka,
+            router,
+            cancel,
+            metrics,
+            dead_letter,
+        )?);
+    }
+
     Ok((listen_addr, group))
 }
 
-pub(super) async fn spawn_metrics_task(
+/// Connect the Kafka consumer and spawn its receiver. The task joins the
+/// "receiver" group so rule tasks only drain after it has stopped.
+#[cfg(feature = "kafka")]
+fn spawn_kafka_receiver(
+    config: &wf_config::KafkaSourceConfig,
+    router: Arc<Router>,
+    cancel: CancellationToken,
+    metrics: Option<Arc<RuntimeMetrics>>,
+    dead_letter: Option<Arc<DeadLetterSink>>,
+) -> RuntimeResult<JoinHandle<anyhow::Result<()>>> {
+    let consumer = crate::kafka_source::RdKafkaConsumer::connect(config).owe_sys()?;
+    let mut receiver = KafkaReceiver::new(consumer, config, router, metrics).owe_conf()?;
+    if let Some(sink) = dead_letter {
+        receiver = receiver.with_dead_letter(sink);
+    }
+    let receiver_cancel = receiver.cancel_token();
+    tokio::spawn(async move {
+        cancel.cancelled().await;
+        receiver_cancel.cancel();
+    });
+    wf_info!(conn, topics = config.topics.len(), "kafka source started");
+    Ok(tokio::spawn(async move { receiver.run().await }))
+}
+
+#[cfg(not(feature = "kafka"))]
+fn spawn_kafka_receiver(
+    _config: &wf_config::KafkaSourceConfig,
+    _router: Arc<Router>,
+    _cancel: CancellationToken,
+    _metrics: Option<Arc<RuntimeMetrics>>,
+    _dead_letter: Option<Arc<DeadLetterSink>>,
+) -> RuntimeResult<JoinHandle<anyhow::Result<()>>> {
+    StructError::from(crate::error::RuntimeReason::Bootstrap)
+        .with_detail(
+            "[server.kafka] is configured but wfusion was built without the `kafka` feature",
+        )
+        .err()
+}
+
+/// Bind the optional HTTP ingest listener and spawn its task.
+///
+/// Started after the receiver group so it is joined (and stopped) before
+/// it, keeping the receiver → rules drain ordering intact.
+pub(super) async fn spawn_http_receiver_task(
     config: &FusionConfig,
     router: &Arc<Router>,
     cancel: CancellationToken,
     metrics: Option<Arc<RuntimeMetrics>>,
 ) -> RuntimeResult<TaskGroup> {
+    let mut group = TaskGroup::new("http_receiver");
+    let Some(http_listen) = &config.server.http_listen else {
+        return Ok(group);
+    };
+    let receiver = HttpReceiver::bind(http_listen, Arc::clone(router), metrics)
+        .await
+        .owe_sys()?;
+    let receiver_cancel = receiver.cancel_token();
+    tokio::spawn(async move {
+        cancel.cancelled().await;
+        receiver_cancel.cancel();
+    });
+    wf_info!(conn, listen = %http_listen, "http ingest listener started");
+    group.push(tokio::spawn(async move { receiver.run().await }));
+    Ok(group)
+}
+
+/// Bind the metrics listener (which also serves `/healthz` and `/readyz`)
+/// and spawn the exporter task. Returns (bound address, task_group); both
+/// are empty when metrics are disabled.
+pub(super) async fn spawn_metrics_task(
+    config: &FusionConfig,
+    router: &Arc<Router>,
+    cancel: CancellationToken,
+    metrics: Option<Arc<RuntimeMetrics>>,
+    health: Arc<HealthState>,
+) -> RuntimeResult<(Option<SocketAddr>, TaskGroup)> {
     let mut group = TaskGroup::new("metrics");
     if !config.metrics.enabled {
-        return Ok(group);
+        return Ok((None, group));
     }
     let Some(metrics) = metrics else {
-        return Ok(group);
+        return Ok((None, group));
     };
     let listener = TcpListener::bind(&config.metrics.prometheus_listen)
         .await
         .owe_sys()?;
+    let metrics_addr = listener.local_addr().owe_sys()?;
     let router = Arc::clone(router);
     let metrics_config = config.metrics.clone();
     group.push(tokio::spawn(async move {
-        run_metrics_task(metrics, metrics_config, listener, router, cancel).await?;
+        run_metrics_task(metrics, metrics_config, listener, router, health, cancel).await?;
         Ok(())
     }));
-    Ok(group)
+    Ok((Some(metrics_addr), group))
 }
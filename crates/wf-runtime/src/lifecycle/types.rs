@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use tokio::task::JoinHandle;
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio::time::Instant;
 
 use orion_error::prelude::*;
 use wf_core::rule::{CepStateMachine, RuleExecutor};
@@ -16,8 +17,8 @@ use crate::error::{RuntimeReason, RuntimeResult};
 /// Groups are assembled in *start order* and joined in *reverse order*
 /// (LIFO) during shutdown, mirroring the dependency graph:
 ///
-///   start:  alert → evictor → rules → receiver (→ metrics)
-///   join:   (metrics →) receiver → rules → alert → evictor
+///   start:  alert → evictor → rules → receiver (→ http_receiver → metrics)
+///   join:   (http_receiver →) receiver → rules → alert → evictor (→ metrics)
 ///
 /// This ensures upstream producers exit before downstream consumers,
 /// and consumers can drain all in-flight work before the reactor stops.
@@ -39,18 +40,44 @@ impl TaskGroup {
     }
 
     /// Join all tasks in this group, returning the first error.
+    ///
+    /// A failed task aborts the rest of the group rather than leaving them
+    /// running unjoined.
     pub(super) async fn wait(self) -> RuntimeResult<()> {
-        for handle in self.handles {
-            handle
-                .await
-                .map_err(|e| {
-                    StructError::from(RuntimeReason::Shutdown)
-                        .with_detail(format!("task join error: {e}"))
-                })?
-                .owe(RuntimeReason::Shutdown)?;
+        let mut handles = self.handles.into_iter();
+        while let Some(handle) = handles.next() {
+            let result = match handle.await {
+                Ok(result) => result.owe(RuntimeReason::Shutdown),
+                Err(e) => Err(StructError::from(RuntimeReason::Shutdown)
+                    .with_detail(format!("task join error: {e}"))),
+            };
+            if let Err(e) = result {
+                for rest in handles {
+                    rest.abort();
+                }
+                return Err(e);
+            }
         }
         Ok(())
     }
+
+    /// Like [`wait`](Self::wait), but abort every task still running at
+    /// `deadline`. Returns `Ok(false)` if the group had to be aborted.
+    ///
+    /// A group that has already finished still joins cleanly when the
+    /// deadline is in the past.
+    pub(super) async fn wait_until(self, deadline: Instant) -> RuntimeResult<bool> {
+        let aborts: Vec<AbortHandle> = self.handles.iter().map(|h| h.abort_handle()).collect();
+        match tokio::time::timeout_at(deadline, self.wait()).await {
+            Ok(result) => result.map(|()| true),
+            Err(_) => {
+                for abort in aborts {
+                    abort.abort();
+                }
+                Ok(false)
+            }
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -67,6 +94,33 @@ pub(super) struct RunRule {
     pub stream_aliases: HashMap<String, Vec<String>>,
 }
 
+// ---------------------------------------------------------------------------
+// CompiledRules — output of rule compilation (bootstrap and reload)
+// ---------------------------------------------------------------------------
+
+/// Compiled rules plus the runtime window schemas/configs they require.
+pub(super) struct CompiledRules {
+    pub rules: Vec<RunRule>,
+    pub schemas: Vec<wf_lang::WindowSchema>,
+    pub window_configs: Vec<wf_config::WindowConfig>,
+}
+
+// ---------------------------------------------------------------------------
+// ValidationSummary — result of `lifecycle::validate`
+// ---------------------------------------------------------------------------
+
+/// What a successful [`validate`](super::validate) checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationSummary {
+    /// Window schemas, including pipeline internal windows.
+    pub schemas: usize,
+    /// Compiled rules, counting each pipeline stage.
+    pub rules: usize,
+    pub windows: usize,
+    /// Business groups plus the infra default/error groups.
+    pub sink_groups: usize,
+}
+
 // ---------------------------------------------------------------------------
 // BootstrapData — compiled artifacts from config-loading phase
 // ---------------------------------------------------------------------------
@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use serde_json::json;
+use wf_core::rule::{MatchedContext, Value};
+
+/// Last-N matched contexts per rule, served as JSON on `/debug/matches`.
+///
+/// Each rule keeps a ring buffer of at most `capacity` contexts; older
+/// contexts are dropped as new matches arrive. Rules are fixed at build time,
+/// so recording never allocates a new buffer.
+pub struct MatchSampler {
+    capacity: usize,
+    rules: BTreeMap<String, Mutex<VecDeque<MatchedContext>>>,
+}
+
+impl MatchSampler {
+    /// Build a sampler for `rule_names`; `None` when `capacity` is 0.
+    pub fn new(rule_names: &[String], capacity: usize) -> Option<Self> {
+        if capacity == 0 {
+            return None;
+        }
+        let rules = rule_names
+            .iter()
+            .map(|name| (name.clone(), Mutex::new(VecDeque::with_capacity(capacity))))
+            .collect();
+        Some(Self { capacity, rules })
+    }
+
+    pub fn record(&self, rule: &str, ctx: &MatchedContext) {
+        let Some(buf) = self.rules.get(rule) else {
+            return;
+        };
+        let mut buf = buf.lock().expect("match sampler lock poisoned");
+        if buf.len() == self.capacity {
+            buf.pop_front();
+        }
+        buf.push_back(ctx.clone());
+    }
+
+    /// Sampled contexts of `rule`, oldest first.
+    pub fn snapshot(&self, rule: &str) -> Vec<MatchedContext> {
+        self.rules
+            .get(rule)
+            .map(|buf| {
+                let buf = buf.lock().expect("match sampler lock poisoned");
+                buf.iter().cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// `{ "<rule>": [<context>, ...] }`, oldest context first.
+    pub fn render_json(&self) -> String {
+        let body: serde_json::Map<String, serde_json::Value> = self
+            .rules
+            .keys()
+            .map(|rule| {
+                let contexts = self.snapshot(rule).iter().map(context_json).collect();
+                (rule.clone(), serde_json::Value::Array(contexts))
+            })
+            .collect();
+        let mut text = serde_json::Value::Object(body).to_string();
+        text.push('\n');
+        text
+    }
+}
+
+fn context_json(ctx: &MatchedContext) -> serde_json::Value {
+    let steps: Vec<_> = ctx
+        .step_data
+        .iter()
+        .map(|step| {
+            json!({
+                "branch": step.satisfied_branch_index,
+                "label": step.label,
+                "measure": step.measure_value,
+            })
+        })
+        .collect();
+    let captures: Vec<_> = ctx
+        .captures
+        .iter()
+        .map(|cap| match cap {
+            Some(cap) => json!({ "first": value_json(&cap.first), "last": value_json(&cap.last) }),
+            None => serde_json::Value::Null,
+        })
+        .collect();
+    json!({
+        "scope_key": ctx.scope_key.iter().map(value_json).collect::<Vec<_>>(),
+        "event_time_nanos": ctx.event_time_nanos,
+        "steps": steps,
+        "captures": captures,
+    })
+}
+
+fn value_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Number(n) => json!(n),
+        Value::Int(i) => json!(i),
+        Value::Str(s) => json!(s),
+        Value::Bool(b) => json!(b),
+        Value::Array(items) => serde_json::Value::Array(items.iter().map(value_json).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(n: i64) -> MatchedContext {
+        MatchedContext {
+            rule_name: "r1".into(),
+            scope_key: vec![Value::Str(format!("10.0.0.{n}"))],
+            step_data: vec![],
+            event_time_nanos: n,
+            captures: vec![],
+        }
+    }
+
+    #[test]
+    fn ring_buffer_keeps_most_recent_n() {
+        let sampler = MatchSampler::new(&["r1".to_string()], 3).unwrap();
+        for n in 1..=5 {
+            sampler.record("r1", &ctx(n));
+        }
+        let times: Vec<i64> = sampler
+            .snapshot("r1")
+            .iter()
+            .map(|c| c.event_time_nanos)
+            .collect();
+        assert_eq!(times, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn disabled_and_unknown_rules() {
+        assert!(MatchSampler::new(&["r1".to_string()], 0).is_none());
+        let sampler = MatchSampler::new(&["r1".to_string()], 2).unwrap();
+        sampler.record("other", &ctx(1));
+        assert!(sampler.snapshot("other").is_empty());
+        assert_eq!(
+            sampler.render_json(),
+            "{\"r1\":[]}\n",
+            "rules without matches still appear"
+        );
+    }
+
+    #[test]
+    fn renders_context_fields() {
+        let sampler = MatchSampler::new(&["r1".to_string()], 2).unwrap();
+        sampler.record("r1", &ctx(7));
+        let body: serde_json::Value = serde_json::from_str(&sampler.render_json()).unwrap();
+        assert_eq!(body["r1"][0]["scope_key"], json!(["10.0.0.7"]));
+        assert_eq!(body["r1"][0]["event_time_nanos"], json!(7));
+    }
+}
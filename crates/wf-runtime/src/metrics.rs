@@ -10,8 +10,12 @@ use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
 
 use wf_config::MetricsConfig;
+use wf_core::rule::{MatchedContext, SuppressReason};
 use wf_core::window::{EvictReport, RouteReport, Router};
 
+use crate::health::HealthState;
+use crate::match_sample::MatchSampler;
+
 const DEFAULT_HISTOGRAM_BUCKETS_SECONDS: &[f64] = &[
     0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0,
 ];
@@ -159,7 +163,8 @@ impl RunSummary {
 /// Shared runtime metrics store.
 ///
 /// Counters are lock-free atomics. Label sets (`rule`, `window`) are fixed at
-/// startup to keep hot-path updates allocation-free.
+/// startup to keep hot-path updates allocation-free, so rules added by a
+/// reload are not tracked (see [`RuntimeMetrics::tracks_rule`]) until restart.
 pub struct RuntimeMetrics {
     receiver_connections_total: AtomicU64,
     receiver_frames_total: AtomicU64,
@@ -171,30 +176,42 @@ pub struct RuntimeMetrics {
     router_delivered_total: AtomicU64,
     router_dropped_late_total: AtomicU64,
     router_skipped_non_local_total: AtomicU64,
+    router_rejected_total: AtomicU64,
     router_route_errors_total: AtomicU64,
 
     rule_events_total: BTreeMap<String, AtomicU64>,
     rule_matches_total: BTreeMap<String, AtomicU64>,
+    rule_closes_total: BTreeMap<String, AtomicU64>,
+    rule_suppressed_total: BTreeMap<String, BTreeMap<&'static str, AtomicU64>>,
     rule_instances: BTreeMap<String, AtomicU64>,
     rule_cursor_gap_total: BTreeMap<String, BTreeMap<String, AtomicU64>>,
 
     alert_emitted_total: BTreeMap<String, AtomicU64>,
     alert_channel_send_failed_total: AtomicU64,
+    alert_channel_dropped_total: AtomicU64,
     alert_serialize_failed_total: AtomicU64,
+    alert_suppressed_total: AtomicU64,
     alert_dispatch_total: AtomicU64,
+    alert_sink_failed_total: BTreeMap<String, AtomicU64>,
 
     evictor_sweeps_total: AtomicU64,
     evictor_time_evicted_total: AtomicU64,
     evictor_memory_evicted_total: AtomicU64,
+    evictor_compacted_total: AtomicU64,
 
     window_memory_bytes: BTreeMap<String, AtomicU64>,
     window_rows: BTreeMap<String, AtomicU64>,
     window_batches: BTreeMap<String, AtomicU64>,
+    window_rows_appended_total: BTreeMap<String, AtomicU64>,
+    window_rows_time_evicted_total: BTreeMap<String, AtomicU64>,
+    window_rows_memory_evicted_total: BTreeMap<String, AtomicU64>,
 
     receiver_decode_seconds: Histogram,
     alert_dispatch_seconds: Histogram,
     rule_scan_timeout_seconds: BTreeMap<String, Histogram>,
     rule_flush_seconds: BTreeMap<String, Histogram>,
+
+    match_sampler: Option<MatchSampler>,
 }
 
 impl RuntimeMetrics {
@@ -303,6 +320,16 @@ impl RuntimeMetrics {
             }
             gap_map.insert(rule.clone(), by_window);
         }
+        let suppressed_map = rule_names
+            .iter()
+            .map(|rule| {
+                let by_reason = SuppressReason::ALL
+                    .iter()
+                    .map(|reason| (reason.as_str(), AtomicU64::new(0)))
+                    .collect::<BTreeMap<_, _>>();
+                (rule.clone(), by_reason)
+            })
+            .collect::<BTreeMap<_, _>>();
 
         Self {
             receiver_connections_total: AtomicU64::new(0),
@@ -314,21 +341,31 @@ impl RuntimeMetrics {
             router_delivered_total: AtomicU64::new(0),
             router_dropped_late_total: AtomicU64::new(0),
             router_skipped_non_local_total: AtomicU64::new(0),
+            router_rejected_total: AtomicU64::new(0),
             router_route_errors_total: AtomicU64::new(0),
             rule_events_total: make_rule_map(),
             rule_matches_total: make_rule_map(),
+            rule_closes_total: make_rule_map(),
+            rule_suppressed_total: suppressed_map,
             rule_instances: make_rule_map(),
             rule_cursor_gap_total: gap_map,
             alert_emitted_total: make_rule_map(),
             alert_channel_send_failed_total: AtomicU64::new(0),
+            alert_channel_dropped_total: AtomicU64::new(0),
             alert_serialize_failed_total: AtomicU64::new(0),
+            alert_suppressed_total: AtomicU64::new(0),
             alert_dispatch_total: AtomicU64::new(0),
+            alert_sink_failed_total: BTreeMap::new(),
             evictor_sweeps_total: AtomicU64::new(0),
             evictor_time_evicted_total: AtomicU64::new(0),
             evictor_memory_evicted_total: AtomicU64::new(0),
+            evictor_compacted_total: AtomicU64::new(0),
             window_memory_bytes: make_window_map(),
             window_rows: make_window_map(),
             window_batches: make_window_map(),
+            window_rows_appended_total: make_window_map(),
+            window_rows_time_evicted_total: make_window_map(),
+            window_rows_memory_evicted_total: make_window_map(),
             receiver_decode_seconds: Histogram::from_seconds_bounds(
                 DEFAULT_HISTOGRAM_BUCKETS_SECONDS,
             ),
@@ -337,6 +374,7 @@ impl RuntimeMetrics {
             ),
             rule_scan_timeout_seconds: make_rule_hist_map(),
             rule_flush_seconds: make_rule_hist_map(),
+            match_sampler: None,
         }
     }
 
@@ -377,6 +415,8 @@ impl RuntimeMetrics {
             .fetch_add(report.dropped_late as u64, Ordering::Relaxed);
         self.router_skipped_non_local_total
             .fetch_add(report.skipped_non_local as u64, Ordering::Relaxed);
+        self.router_rejected_total
+            .fetch_add(report.rejected.len() as u64, Ordering::Relaxed);
     }
 
     pub fn inc_route_error(&self) {
@@ -396,6 +436,49 @@ impl RuntimeMetrics {
         }
     }
 
+    /// Whether `rule` has `wf_rule_*` series and a `/debug/matches` slot.
+    /// Only rules known at startup do; updates for any other rule are ignored.
+    pub fn tracks_rule(&self, rule: &str) -> bool {
+        self.rule_matches_total.contains_key(rule)
+    }
+
+    /// Keep the last `capacity` matched contexts per rule for
+    /// `/debug/matches`; `0` leaves sampling off.
+    pub fn with_match_sampling(mut self, capacity: usize) -> Self {
+        let rule_names: Vec<String> = self.rule_matches_total.keys().cloned().collect();
+        self.match_sampler = MatchSampler::new(&rule_names, capacity);
+        self
+    }
+
+    /// Label `wf_alert_sink_failed_total` with every sink in `sink_names`.
+    pub fn with_sinks(mut self, sink_names: &[String]) -> Self {
+        self.alert_sink_failed_total = sink_names
+            .iter()
+            .map(|name| (name.clone(), AtomicU64::new(0)))
+            .collect();
+        self
+    }
+
+    pub fn sample_match(&self, rule: &str, ctx: &MatchedContext) {
+        if let Some(sampler) = &self.match_sampler {
+            sampler.record(rule, ctx);
+        }
+    }
+
+    pub fn inc_rule_close(&self, rule: &str) {
+        if let Some(v) = self.rule_closes_total.get(rule) {
+            v.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn add_rule_suppressed(&self, rule: &str, reason: SuppressReason, count: u64) {
+        if let Some(by_reason) = self.rule_suppressed_total.get(rule)
+            && let Some(v) = by_reason.get(reason.as_str())
+        {
+            v.fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
     pub fn set_rule_instances(&self, rule: &str, count: usize) {
         if let Some(v) = self.rule_instances.get(rule) {
             v.store(count as u64, Ordering::Relaxed);
@@ -421,15 +504,30 @@ impl RuntimeMetrics {
             .fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn inc_alert_channel_dropped(&self) {
+        self.alert_channel_dropped_total
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
     pub fn inc_alert_serialize_failed(&self) {
         self.alert_serialize_failed_total
             .fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn inc_alert_suppressed(&self) {
+        self.alert_suppressed_total.fetch_add(1, Ordering::Relaxed);
+    }
+
     pub fn inc_alert_dispatch(&self) {
         self.alert_dispatch_total.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn inc_alert_sink_failed(&self, sink: &str) {
+        if let Some(v) = self.alert_sink_failed_total.get(sink) {
+            v.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
     pub fn observe_alert_dispatch(&self, elapsed: Duration) {
         self.alert_dispatch_seconds.observe_duration(elapsed);
     }
@@ -452,6 +550,8 @@ impl RuntimeMetrics {
             .fetch_add(report.batches_time_evicted as u64, Ordering::Relaxed);
         self.evictor_memory_evicted_total
             .fetch_add(report.batches_memory_evicted as u64, Ordering::Relaxed);
+        self.evictor_compacted_total
+            .fetch_add(report.batches_compacted as u64, Ordering::Relaxed);
     }
 
     /// Periodically sample expensive window gauges to keep scrape path light.
@@ -459,20 +559,30 @@ impl RuntimeMetrics {
         for window_name in router.registry().window_names() {
             if let Some(win_lock) = router.registry().get_window(window_name) {
                 let win = win_lock.read().expect("window lock poisoned");
+                let stats = win.stats();
                 if let Some(v) = self.window_memory_bytes.get(window_name) {
-                    v.store(win.memory_usage() as u64, Ordering::Relaxed);
+                    v.store(stats.current_bytes as u64, Ordering::Relaxed);
                 }
                 if let Some(v) = self.window_rows.get(window_name) {
-                    v.store(win.total_rows() as u64, Ordering::Relaxed);
+                    v.store(stats.current_rows as u64, Ordering::Relaxed);
                 }
                 if let Some(v) = self.window_batches.get(window_name) {
                     v.store(win.batch_count() as u64, Ordering::Relaxed);
                 }
+                if let Some(v) = self.window_rows_appended_total.get(window_name) {
+                    v.store(stats.rows_appended, Ordering::Relaxed);
+                }
+                if let Some(v) = self.window_rows_time_evicted_total.get(window_name) {
+                    v.store(stats.rows_time_evicted, Ordering::Relaxed);
+                }
+                if let Some(v) = self.window_rows_memory_evicted_total.get(window_name) {
+                    v.store(stats.rows_memory_evicted, Ordering::Relaxed);
+                }
             }
         }
     }
 
-    fn render_prometheus(&self) -> String {
+    pub(crate) fn render_prometheus(&self) -> String {
         let mut out = String::with_capacity(16 * 1024);
         let mut rendered_types = BTreeSet::new();
 
@@ -537,6 +647,12 @@ impl RuntimeMetrics {
             "wf_router_skipped_non_local_total",
             self.router_skipped_non_local_total.load(Ordering::Relaxed),
         );
+        self.render_counter(
+            &mut out,
+            &mut rendered_types,
+            "wf_router_rejected_total",
+            self.router_rejected_total.load(Ordering::Relaxed),
+        );
         self.render_counter(
             &mut out,
             &mut rendered_types,
@@ -562,6 +678,26 @@ impl RuntimeMetrics {
                 value.load(Ordering::Relaxed),
             );
         }
+        for (rule, value) in &self.rule_closes_total {
+            self.render_counter_labeled(
+                &mut out,
+                &mut rendered_types,
+                "wf_rule_closes_total",
+                &[("rule", rule)],
+                value.load(Ordering::Relaxed),
+            );
+        }
+        for (rule, by_reason) in &self.rule_suppressed_total {
+            for (reason, value) in by_reason {
+                self.render_counter_labeled(
+                    &mut out,
+                    &mut rendered_types,
+                    "wf_rule_suppressed_total",
+                    &[("rule", rule), ("reason", reason)],
+                    value.load(Ordering::Relaxed),
+                );
+            }
+        }
         for (rule, value) in &self.rule_instances {
             self.render_gauge_labeled(
                 &mut out,
@@ -598,18 +734,39 @@ impl RuntimeMetrics {
             "wf_alert_channel_send_failed_total",
             self.alert_channel_send_failed_total.load(Ordering::Relaxed),
         );
+        self.render_counter(
+            &mut out,
+            &mut rendered_types,
+            "wf_alert_channel_dropped_total",
+            self.alert_channel_dropped_total.load(Ordering::Relaxed),
+        );
         self.render_counter(
             &mut out,
             &mut rendered_types,
             "wf_alert_serialize_failed_total",
             self.alert_serialize_failed_total.load(Ordering::Relaxed),
         );
+        self.render_counter(
+            &mut out,
+            &mut rendered_types,
+            "wf_alert_suppressed_total",
+            self.alert_suppressed_total.load(Ordering::Relaxed),
+        );
         self.render_counter(
             &mut out,
             &mut rendered_types,
             "wf_alert_dispatch_total",
             self.alert_dispatch_total.load(Ordering::Relaxed),
         );
+        for (sink, value) in &self.alert_sink_failed_total {
+            self.render_counter_labeled(
+                &mut out,
+                &mut rendered_types,
+                "wf_alert_sink_failed_total",
+                &[("sink", sink)],
+                value.load(Ordering::Relaxed),
+            );
+        }
         self.render_histogram(
             &mut out,
             &mut rendered_types,
@@ -635,6 +792,12 @@ impl RuntimeMetrics {
             "wf_evictor_memory_evicted_total",
             self.evictor_memory_evicted_total.load(Ordering::Relaxed),
         );
+        self.render_counter(
+            &mut out,
+            &mut rendered_types,
+            "wf_evictor_compacted_total",
+            self.evictor_compacted_total.load(Ordering::Relaxed),
+        );
 
         for (rule, histogram) in &self.rule_scan_timeout_seconds {
             self.render_histogram_labeled(
@@ -682,6 +845,33 @@ impl RuntimeMetrics {
                 value.load(Ordering::Relaxed),
             );
         }
+        for (window, value) in &self.window_rows_appended_total {
+            self.render_counter_labeled(
+                &mut out,
+                &mut rendered_types,
+                "wf_window_rows_appended_total",
+                &[("window", window)],
+                value.load(Ordering::Relaxed),
+            );
+        }
+        for (window, value) in &self.window_rows_time_evicted_total {
+            self.render_counter_labeled(
+                &mut out,
+                &mut rendered_types,
+                "wf_window_rows_time_evicted_total",
+                &[("window", window)],
+                value.load(Ordering::Relaxed),
+            );
+        }
+        for (window, value) in &self.window_rows_memory_evicted_total {
+            self.render_counter_labeled(
+                &mut out,
+                &mut rendered_types,
+                "wf_window_rows_memory_evicted_total",
+                &[("window", window)],
+                value.load(Ordering::Relaxed),
+            );
+        }
 
         out
     }
@@ -849,6 +1039,7 @@ pub async fn run_metrics_task(
     config: MetricsConfig,
     listener: TcpListener,
     router: Arc<Router>,
+    health: Arc<HealthState>,
     cancel: CancellationToken,
 ) -> anyhow::Result<()> {
     wf_info!(
@@ -880,8 +1071,9 @@ pub async fn run_metrics_task(
             result = listener.accept() => {
                 let (stream, _) = result?;
                 let metrics = Arc::clone(&metrics);
+                let health = Arc::clone(&health);
                 tokio::spawn(async move {
-                    if let Err(e) = serve_metrics_connection(stream, metrics).await {
+                    if let Err(e) = serve_metrics_connection(stream, metrics, health).await {
                         wf_debug!(sys, error = %e, "metrics connection handling failed");
                     }
                 });
@@ -908,9 +1100,12 @@ pub async fn run_metrics_task(
     Ok(())
 }
 
+/// Serve one request on the metrics listener: `/metrics`, `/healthz`,
+/// `/readyz` or `/debug/matches` (when sampling is on); anything else is 404.
 async fn serve_metrics_connection(
     mut stream: TcpStream,
     metrics: Arc<RuntimeMetrics>,
+    health: Arc<HealthState>,
 ) -> anyhow::Result<()> {
     let mut req_buf = [0u8; 512];
     let req_n = match timeout(Duration::from_secs(2), stream.read(&mut req_buf)).await {
@@ -918,28 +1113,37 @@ async fn serve_metrics_connection(
         Ok(Err(e)) => return Err(e.into()),
         Err(_) => return Ok(()),
     };
-    let is_metrics = req_n > 0
-        && std::str::from_utf8(&req_buf[..req_n])
-            .unwrap_or("")
-            .starts_with("GET /metrics");
-
-    if is_metrics {
-        let body = metrics.render_prometheus();
-        let header = format!(
-            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
-            body.len()
-        );
-        timeout(Duration::from_secs(2), stream.write_all(header.as_bytes())).await??;
-        timeout(Duration::from_secs(2), stream.write_all(body.as_bytes())).await??;
-    } else {
-        timeout(
-            Duration::from_secs(2),
-            stream.write_all(
-                b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
-            ),
-        )
-        .await??;
-    }
+    let request = std::str::from_utf8(&req_buf[..req_n]).unwrap_or("");
+    let path = request
+        .strip_prefix("GET ")
+        .and_then(|rest| rest.split_whitespace().next())
+        .unwrap_or("");
+
+    let (status, content_type, body) = match path {
+        "/metrics" => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            metrics.render_prometheus(),
+        ),
+        "/healthz" => ("200 OK", "text/plain", "ok\n".to_string()),
+        "/readyz" if health.is_ready() => ("200 OK", "text/plain", "ready\n".to_string()),
+        "/readyz" => (
+            "503 Service Unavailable",
+            "text/plain",
+            "not ready\n".to_string(),
+        ),
+        "/debug/matches" => match &metrics.match_sampler {
+            Some(sampler) => ("200 OK", "application/json", sampler.render_json()),
+            None => ("404 Not Found", "text/plain", String::new()),
+        },
+        _ => ("404 Not Found", "text/plain", String::new()),
+    };
+    let header = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    timeout(Duration::from_secs(2), stream.write_all(header.as_bytes())).await??;
+    timeout(Duration::from_secs(2), stream.write_all(body.as_bytes())).await??;
     let _ = timeout(Duration::from_secs(1), stream.shutdown()).await;
     Ok(())
 }
@@ -948,11 +1152,16 @@ pub fn maybe_build_metrics(
     config: &MetricsConfig,
     rule_names: &[String],
     window_names: &[String],
+    sink_names: &[String],
 ) -> Option<Arc<RuntimeMetrics>> {
     if !config.enabled {
         return None;
     }
-    Some(Arc::new(RuntimeMetrics::new(rule_names, window_names)))
+    Some(Arc::new(
+        RuntimeMetrics::new(rule_names, window_names)
+            .with_sinks(sink_names)
+            .with_match_sampling(config.sample_matches),
+    ))
 }
 
 #[cfg(test)]
@@ -981,6 +1190,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn window_eviction_counters_sampled_with_window_label() {
+        use arrow::array::{Int64Array, TimestampNanosecondArray};
+        use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+        use arrow::record_batch::RecordBatch;
+        use wf_config::{DistMode, EvictPolicy, LatePolicy, WindowConfig};
+        use wf_core::window::{WindowDef, WindowParams, WindowRegistry};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+            Field::new("value", DataType::Int64, false),
+        ]));
+        let make_batch = |t: i64| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(TimestampNanosecondArray::from(vec![t])),
+                    Arc::new(Int64Array::from(vec![t])),
+                ],
+            )
+            .unwrap()
+        };
+        let one_batch_size = make_batch(1).get_array_memory_size();
+        let reg = WindowRegistry::build(vec![WindowDef {
+            params: WindowParams {
+                name: "w1".into(),
+                schema: schema.clone(),
+                time_col_index: Some(0),
+                over: Duration::from_secs(3600),
+            },
+            streams: vec!["events".into()],
+            config: WindowConfig {
+                name: "w1".into(),
+                mode: DistMode::Local,
+                max_window_bytes: one_batch_size.into(),
+                over_cap: Duration::from_secs(3600).into(),
+                evict_policy: EvictPolicy::TimeFirst,
+                watermark: Duration::from_secs(0).into(),
+                allowed_lateness: Duration::from_secs(3600).into(),
+                late_policy: LatePolicy::Drop,
+                compact_below: 0.into(),
+            },
+        }])
+        .unwrap();
+        let router = Router::new(reg);
+        router.route("events", make_batch(1_000_000_000)).unwrap();
+        router.route("events", make_batch(2_000_000_000)).unwrap();
+
+        let metrics = RuntimeMetrics::new(&[], &["w1".to_string()]);
+        metrics.sample_windows(&router);
+        let text = metrics.render_prometheus();
+        assert!(text.contains("wf_window_rows_appended_total{window=\"w1\"} 2"));
+        assert!(text.contains("wf_window_rows_memory_evicted_total{window=\"w1\"} 1"));
+        assert!(text.contains("wf_window_rows_time_evicted_total{window=\"w1\"} 0"));
+    }
+
+    #[test]
+    fn rules_added_after_startup_are_ignored() {
+        let metrics =
+            RuntimeMetrics::new(&["r1".to_string()], &["w1".to_string()]).with_match_sampling(2);
+        assert!(metrics.tracks_rule("r1"));
+        assert!(!metrics.tracks_rule("r2"));
+
+        let ctx = MatchedContext {
+            rule_name: "r2".into(),
+            scope_key: vec![],
+            step_data: vec![],
+            event_time_nanos: 1,
+            captures: vec![],
+        };
+        metrics.inc_rule_match("r2");
+        metrics.inc_alert_emitted("r2");
+        metrics.sample_match("r2", &ctx);
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("wf_rule_matches_total{rule=\"r1\"} 0"));
+        assert!(!text.contains("rule=\"r2\""));
+        let sampled = metrics.match_sampler.as_ref().unwrap().render_json();
+        assert_eq!(sampled, "{\"r1\":[]}\n");
+    }
+
     #[test]
     fn histogram_count_matches_inf_bucket() {
         let metrics = RuntimeMetrics::new(&["r1".to_string()], &["w1".to_string()]);
@@ -1,22 +1,39 @@
 use std::io;
 use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Instant;
-
-use tokio::io::{AsyncReadExt, BufReader};
-use tokio::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use tokio_util::sync::CancellationToken;
 use wf_core::window::Router;
 
+use crate::dead_letter::{DeadLetterSink, handle_rejections};
 use crate::metrics::RuntimeMetrics;
 
+/// Upper bound on a TLS handshake before the connection is dropped.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// TCP receiver that accepts connections, reads length-prefixed Arrow IPC
 /// frames, decodes them, and routes batches to the [`Router`].
+///
+/// With [`with_tls`](Self::with_tls) each connection is TLS-terminated
+/// before frame decoding; the frame format is unchanged. Batches the router
+/// rejects are logged and, with [`with_dead_letter`](Self::with_dead_letter),
+/// kept in a dead-letter file; the connection stays open either way.
 pub struct Receiver {
     listener: TcpListener,
     router: Arc<Router>,
     metrics: Option<Arc<RuntimeMetrics>>,
     cancel: CancellationToken,
+    tls: Option<TlsAcceptor>,
+    dead_letter: Option<Arc<DeadLetterSink>>,
 }
 
 impl Receiver {
@@ -33,9 +50,23 @@ impl Receiver {
             router,
             metrics,
             cancel: CancellationToken::new(),
+            tls: None,
+            dead_letter: None,
         })
     }
 
+    /// Terminate TLS on every accepted connection.
+    pub fn with_tls(mut self, acceptor: TlsAcceptor) -> Self {
+        self.tls = Some(acceptor);
+        self
+    }
+
+    /// Append batches the router rejects to `sink`.
+    pub fn with_dead_letter(mut self, sink: Arc<DeadLetterSink>) -> Self {
+        self.dead_letter = Some(sink);
+        self
+    }
+
     /// Returns the local address the listener is bound to.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.listener.local_addr()
@@ -59,8 +90,44 @@ impl Receiver {
                     }
                     let router = Arc::clone(&self.router);
                     let metrics = self.metrics.clone();
+                    let dead_letter = self.dead_letter.clone();
                     let cancel = self.cancel.child_token();
-                    tokio::spawn(handle_connection(stream, router, metrics, cancel, peer));
+                    match &self.tls {
+                        None => {
+                            tokio::spawn(handle_connection(stream, router, metrics, dead_letter, cancel, peer));
+                        }
+                        Some(acceptor) => {
+                            // Handshake off the accept loop so a slow client
+                            // cannot stall other connections.
+                            let acceptor = acceptor.clone();
+                            tokio::spawn(async move {
+                                let handshake = tokio::time::timeout(
+                                    TLS_HANDSHAKE_TIMEOUT,
+                                    acceptor.accept(stream),
+                                );
+                                match handshake.await {
+                                    Ok(Ok(tls_stream)) => {
+                                        handle_connection(
+                                            tls_stream, router, metrics, dead_letter, cancel, peer,
+                                        )
+                                        .await;
+                                    }
+                                    Ok(Err(e)) => {
+                                        if let Some(metrics) = &metrics {
+                                            metrics.inc_receiver_read_error();
+                                        }
+                                        wf_warn!(conn, peer = %peer, error = %e, "TLS handshake failed");
+                                    }
+                                    Err(_) => {
+                                        if let Some(metrics) = &metrics {
+                                            metrics.inc_receiver_read_error();
+                                        }
+                                        wf_warn!(conn, peer = %peer, "TLS handshake timed out");
+                                    }
+                                }
+                            });
+                        }
+                    }
                 }
                 _ = self.cancel.cancelled() => break,
             }
@@ -69,16 +136,39 @@ impl Receiver {
     }
 }
 
+/// Build a TLS acceptor from PEM certificate chain and private key files.
+pub fn load_tls_acceptor(cert_path: &Path, key_path: &Path) -> anyhow::Result<TlsAcceptor> {
+    let cert_pem = std::fs::read(cert_path)
+        .with_context(|| format!("reading TLS certificate {}", cert_path.display()))?;
+    let certs = CertificateDer::pem_slice_iter(&cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("parsing TLS certificate {}", cert_path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificate found in {}", cert_path.display());
+    }
+    let key_pem = std::fs::read(key_path)
+        .with_context(|| format!("reading TLS key {}", key_path.display()))?;
+    let key = PrivateKeyDer::from_pem_slice(&key_pem)
+        .with_context(|| format!("parsing TLS key {}", key_path.display()))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
 #[tracing::instrument(skip_all, fields(peer = %peer))]
 async fn handle_connection(
-    stream: TcpStream,
+    stream: impl AsyncRead + Unpin,
     router: Arc<Router>,
     metrics: Option<Arc<RuntimeMetrics>>,
+    dead_letter: Option<Arc<DeadLetterSink>>,
     cancel: CancellationToken,
     peer: SocketAddr,
 ) {
-    let (reader, _writer) = stream.into_split();
-    let mut reader = BufReader::new(reader);
+    let mut reader = BufReader::new(stream);
     loop {
         tokio::select! {
             result = read_frame(&mut reader) => {
@@ -98,11 +188,18 @@ async fn handle_connection(
                                 if let Some(metrics) = &metrics {
                                     metrics.inc_router_route_call();
                                 }
-                                match router.route(&frame.tag, frame.batch) {
+                                match router.route(&frame.tag, frame.batch.clone()) {
                                     Ok(report) => {
                                         if let Some(metrics) = &metrics {
                                             metrics.add_route_report(&report);
                                         }
+                                        handle_rejections(
+                                            &report,
+                                            "tcp",
+                                            &frame.tag,
+                                            &frame.batch,
+                                            dead_letter.as_deref(),
+                                        );
                                         wf_debug!(pipe,
                                             delivered = report.delivered,
                                             dropped_late = report.dropped_late,
@@ -168,18 +265,15 @@ async fn read_frame(reader: &mut (impl AsyncReadExt + Unpin)) -> io::Result<Opti
 mod tests {
     use super::*;
     use arrow::array::{Int64Array, TimestampNanosecondArray};
-    use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
     use std::time::Duration;
     use tokio::io::AsyncWriteExt;
     use tokio::net::TcpStream;
-    use wf_config::{DistMode, EvictPolicy, LatePolicy, WindowConfig};
-    use wf_core::window::{WindowDef, WindowParams, WindowRegistry};
-
-    fn test_schema() -> SchemaRef {
-        Arc::new(Schema::new(vec![
-            Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
-            Field::new("value", DataType::Int64, false),
-        ]))
+
+    use crate::test_support::test_schema;
+
+    fn make_router(stream_name: &str) -> Arc<Router> {
+        crate::test_support::make_router(stream_name, usize::MAX)
     }
 
     fn make_batch(
@@ -197,34 +291,6 @@ mod tests {
         .unwrap()
     }
 
-    fn test_config() -> WindowConfig {
-        WindowConfig {
-            name: "default".into(),
-            mode: DistMode::Local,
-            max_window_bytes: usize::MAX.into(),
-            over_cap: Duration::from_secs(3600).into(),
-            evict_policy: EvictPolicy::TimeFirst,
-            watermark: Duration::from_secs(0).into(),
-            allowed_lateness: Duration::from_secs(3600).into(),
-            late_policy: LatePolicy::Drop,
-        }
-    }
-
-    fn make_router(stream_name: &str) -> Arc<Router> {
-        let reg = WindowRegistry::build(vec![WindowDef {
-            params: WindowParams {
-                name: "test_win".into(),
-                schema: test_schema(),
-                time_col_index: Some(0),
-                over: Duration::from_secs(3600),
-            },
-            streams: vec![stream_name.to_string()],
-            config: test_config(),
-        }])
-        .unwrap();
-        Arc::new(Router::new(reg))
-    }
-
     /// Encode a RecordBatch and wrap it in a length-prefixed outer frame.
     fn make_frame(stream_name: &str, batch: &arrow::record_batch::RecordBatch) -> Vec<u8> {
         let payload = wp_arrow::ipc::encode_ipc(stream_name, batch).unwrap();
@@ -362,4 +428,118 @@ mod tests {
         cancel.cancel();
         server.await.unwrap().unwrap();
     }
+
+    // -- Test 4: tls_connection_end_to_end -------------------------------------
+
+    #[tokio::test]
+    async fn tls_connection_end_to_end() {
+        use tokio_rustls::TlsConnector;
+        use tokio_rustls::rustls::pki_types::ServerName;
+
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("server.pem");
+        let key_path = dir.path().join("server.key");
+        std::fs::write(&cert_path, certified.cert.pem()).unwrap();
+        std::fs::write(&key_path, certified.key_pair.serialize_pem()).unwrap();
+
+        let router = make_router("secure");
+        let receiver = Receiver::bind("tcp://127.0.0.1:0", Arc::clone(&router), None)
+            .await
+            .unwrap()
+            .with_tls(load_tls_acceptor(&cert_path, &key_path).unwrap());
+        let addr = receiver.local_addr().unwrap();
+        let cancel = receiver.cancel_token();
+
+        let server = tokio::spawn(async move { receiver.run().await });
+
+        let schema = test_schema();
+        let frame = make_frame("secure", &make_batch(&schema, &[10_000_000_000], &[1]));
+
+        // Plaintext frames are rejected by the handshake.
+        let mut plain = TcpStream::connect(addr).await.unwrap();
+        send_frame(&mut plain, &frame).await;
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(certified.cert.der().clone()).unwrap();
+        let client_config = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+        let connector = TlsConnector::from(Arc::new(client_config));
+        let tcp = TcpStream::connect(addr).await.unwrap();
+        let mut conn = connector
+            .connect(ServerName::try_from("localhost").unwrap(), tcp)
+            .await
+            .unwrap();
+        conn.write_all(&frame).await.unwrap();
+        conn.flush().await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(snapshot_row_count(&router), 1);
+
+        cancel.cancel();
+        server.await.unwrap().unwrap();
+    }
+
+    // -- Test 5: mismatched_batch_goes_to_dead_letter --------------------------
+
+    #[tokio::test]
+    async fn mismatched_batch_goes_to_dead_letter() {
+        use arrow::array::StringArray;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dlq_path = dir.path().join("dead_letter.ndjson");
+        let router = make_router("events");
+        let receiver = Receiver::bind("tcp://127.0.0.1:0", Arc::clone(&router), None)
+            .await
+            .unwrap()
+            .with_dead_letter(Arc::new(DeadLetterSink::open(&dlq_path).unwrap()));
+        let addr = receiver.local_addr().unwrap();
+        let cancel = receiver.cancel_token();
+
+        let server = tokio::spawn(async move { receiver.run().await });
+
+        let wrong_schema = Arc::new(Schema::new(vec![Field::new("sip", DataType::Utf8, false)]));
+        let wrong = arrow::record_batch::RecordBatch::try_new(
+            wrong_schema,
+            vec![Arc::new(StringArray::from(vec!["10.0.0.1", "10.0.0.2"]))],
+        )
+        .unwrap();
+
+        // Mismatched batch, then a valid one on the same connection.
+        let mut conn = TcpStream::connect(addr).await.unwrap();
+        send_frame(&mut conn, &make_frame("events", &wrong)).await;
+        let good = make_batch(&test_schema(), &[10_000_000_000], &[1]);
+        send_frame(&mut conn, &make_frame("events", &good)).await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Ingestion keeps going: the valid batch still reaches the window.
+        assert_eq!(snapshot_row_count(&router), 1);
+
+        let text = std::fs::read_to_string(&dlq_path).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["source"], "tcp");
+        assert_eq!(lines[0]["stream"], "events");
+        assert_eq!(lines[0]["window"], "test_win");
+        assert!(
+            lines[0]["reason"]
+                .as_str()
+                .unwrap()
+                .contains("schema mismatch")
+        );
+        assert_eq!(lines[0]["events"][1]["sip"], "10.0.0.2");
+
+        cancel.cancel();
+        server.await.unwrap().unwrap();
+    }
 }
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::Result;
+use arrow::datatypes::Schema;
 
 use wf_config::WindowConfig;
 use wf_core::window::{WindowDef, WindowParams};
@@ -11,16 +12,8 @@ use wp_arrow::schema::{FieldDef as WpFieldDef, WpDataType, to_arrow_schema};
 /// [`WindowConfig`] (resolved from `wfusion.toml`) into a [`WindowDef`]
 /// that can be fed to [`WindowRegistry::build`].
 pub fn schema_to_window_def(ws: &WindowSchema, config: &WindowConfig) -> Result<WindowDef> {
-    // 1. Convert wf-lang FieldDef → wp-arrow FieldDef
-    let wp_fields: Vec<WpFieldDef> = ws
-        .fields
-        .iter()
-        .map(|f| WpFieldDef::new(&f.name, base_type_to_wp(&f.field_type)))
-        .collect();
-
-    // 2. Build Arrow Schema
-    let schema = to_arrow_schema(&wp_fields)
-        .map_err(|e| anyhow::anyhow!("schema conversion failed for {:?}: {e}", ws.name))?;
+    // 1–2. Convert wf-lang FieldDef → wp-arrow FieldDef → Arrow Schema
+    let schema = window_arrow_schema(ws)?;
 
     // 3. Find time column index
     let time_col_index = ws.time_field.as_ref().map(|tf| {
@@ -46,6 +39,17 @@ pub fn schema_to_window_def(ws: &WindowSchema, config: &WindowConfig) -> Result<
     })
 }
 
+/// Build the Arrow schema a window stores its batches in.
+pub fn window_arrow_schema(ws: &WindowSchema) -> Result<Schema> {
+    let wp_fields: Vec<WpFieldDef> = ws
+        .fields
+        .iter()
+        .map(|f| WpFieldDef::new(&f.name, base_type_to_wp(&f.field_type)))
+        .collect();
+    to_arrow_schema(&wp_fields)
+        .map_err(|e| anyhow::anyhow!("schema conversion failed for {:?}: {e}", ws.name))
+}
+
 fn base_type_to_wp(ft: &FieldType) -> WpDataType {
     match ft {
         FieldType::Base(bt) => match bt {
@@ -107,6 +111,7 @@ mod tests {
             watermark: Duration::from_secs(5).into(),
             allowed_lateness: Duration::from_secs(0).into(),
             late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
         }
     }
 
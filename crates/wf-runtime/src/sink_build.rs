@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use wp_connector_api::{SinkBuildCtx, SinkFactory, SinkSpec as ResolvedSinkSpec};
 
-use wf_config::sink::{SinkConfigBundle, WildArray};
+use wf_config::sink::{AlertSerializer, SinkConfigBundle, WildArray};
 use wf_core::sink::{SinkDispatcher, SinkRuntime};
 
 // ---------------------------------------------------------------------------
@@ -63,21 +63,22 @@ pub async fn build_sink_dispatcher(
     // Build business groups (name, compiled windows, sinks)
     let mut business: Vec<(String, WildArray, Vec<Arc<SinkRuntime>>)> = Vec::new();
     for flex in &bundle.business {
-        let sinks = build_sink_runtimes(&flex.sinks, &flex.tags, registry, &ctx).await?;
+        let sinks =
+            build_sink_runtimes(&flex.sinks, &flex.serializers, &flex.tags, registry, &ctx).await?;
         let windows = WildArray::new(flex.windows.raw_patterns());
         business.push((flex.name.clone(), windows, sinks));
     }
 
     // Build infra default sinks
     let default_sinks = if let Some(ref fixed) = bundle.infra_default {
-        build_sink_runtimes(&fixed.sinks, &[], registry, &ctx).await?
+        build_sink_runtimes(&fixed.sinks, &fixed.serializers, &[], registry, &ctx).await?
     } else {
         Vec::new()
     };
 
     // Build infra error sinks
     let error_sinks = if let Some(ref fixed) = bundle.infra_error {
-        build_sink_runtimes(&fixed.sinks, &[], registry, &ctx).await?
+        build_sink_runtimes(&fixed.sinks, &fixed.serializers, &[], registry, &ctx).await?
     } else {
         Vec::new()
     };
@@ -97,16 +98,17 @@ pub async fn build_sink_dispatcher(
     Ok(SinkDispatcher::new(routes, default_sinks, error_sinks))
 }
 
-/// Build `SinkRuntime` instances from resolved specs.
+/// Build `SinkRuntime` instances from resolved specs and their serializers.
 async fn build_sink_runtimes(
     specs: &[ResolvedSinkSpec],
+    serializers: &[AlertSerializer],
     tags: &[String],
     registry: &SinkFactoryRegistry,
     ctx: &SinkBuildCtx,
 ) -> anyhow::Result<Vec<Arc<SinkRuntime>>> {
     let mut runtimes = Vec::with_capacity(specs.len());
 
-    for spec in specs {
+    for (i, spec) in specs.iter().enumerate() {
         let factory = registry.get(&spec.kind).ok_or_else(|| {
             anyhow::anyhow!(
                 "no factory registered for sink kind {:?} (connector={:?})",
@@ -129,8 +131,295 @@ async fn build_sink_runtimes(
             spec: spec.clone(),
             handle: tokio::sync::Mutex::new(handle),
             tags: tags.to_vec(),
+            serializer: serializers.get(i).copied().unwrap_or_default(),
         }));
     }
 
     Ok(runtimes)
 }
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use wf_core::alert::{AlertOrigin, OutputRecord};
+    use wp_connector_api::*;
+    use wp_model_core::model::DataRecord;
+
+    type Received = Arc<Mutex<HashMap<String, Vec<String>>>>;
+
+    /// In-memory sink kind: records every line per sink name; a sink with
+    /// `fail = true` rejects every send.
+    struct MemorySinkFactory {
+        received: Received,
+    }
+
+    #[async_trait]
+    impl SinkFactory for MemorySinkFactory {
+        fn kind(&self) -> &'static str {
+            "memory"
+        }
+
+        fn validate_spec(&self, _spec: &ResolvedSinkSpec) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn build(
+            &self,
+            spec: &ResolvedSinkSpec,
+            _ctx: &SinkBuildCtx,
+        ) -> SinkResult<SinkHandle> {
+            Ok(SinkHandle::new(Box::new(MemorySink {
+                name: spec.name.clone(),
+                fail: spec.params.get("fail").and_then(|v| v.as_bool()) == Some(true),
+                received: Arc::clone(&self.received),
+            })))
+        }
+    }
+
+    struct MemorySink {
+        name: String,
+        fail: bool,
+        received: Received,
+    }
+
+    #[async_trait]
+    impl AsyncCtrl for MemorySink {
+        async fn stop(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn reconnect(&mut self) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRawDataSink for MemorySink {
+        async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+            if self.fail {
+                return Err(SinkError::from(SinkReason::Sink("boom".into())));
+            }
+            self.received
+                .lock()
+                .unwrap()
+                .entry(self.name.clone())
+                .or_default()
+                .push(data.to_string());
+            Ok(())
+        }
+
+        async fn sink_bytes(&mut self, _data: &[u8]) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_str_batch(&mut self, _data: Vec<&str>) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_bytes_batch(&mut self, _data: Vec<&[u8]>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl AsyncRecordSink for MemorySink {
+        async fn sink_record(&mut self, _record: &DataRecord) -> SinkResult<()> {
+            Ok(())
+        }
+
+        async fn sink_records(&mut self, _records: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Write a `sinks/` tree whose business group fans out to the given
+    /// `[[sink_group.sinks]]` entries.
+    fn write_sink_dir(root: &Path, sinks_toml: &str) {
+        std::fs::create_dir_all(root.join("sink.d")).unwrap();
+        std::fs::create_dir_all(root.join("business.d")).unwrap();
+        std::fs::write(
+            root.join("sink.d/memory.toml"),
+            r#"
+[[connectors]]
+id = "mem"
+type = "memory"
+allow_override = ["fail"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("business.d/fanout.toml"),
+            format!("[sink_group]\nname = \"fanout\"\nwindows = [\"*\"]\n{sinks_toml}"),
+        )
+        .unwrap();
+    }
+
+    async fn build_memory_dispatcher(sinks_toml: &str) -> (SinkDispatcher, Received) {
+        let dir = tempfile::tempdir().unwrap();
+        write_sink_dir(dir.path(), sinks_toml);
+        let bundle = wf_config::sink::load_sink_config(dir.path()).unwrap();
+
+        let received: Received = Arc::default();
+        let mut registry = SinkFactoryRegistry::new();
+        registry.register(Arc::new(MemorySinkFactory {
+            received: Arc::clone(&received),
+        }));
+        let dispatcher =
+            build_sink_dispatcher(&bundle, &registry, dir.path(), &["alerts".to_string()])
+                .await
+                .unwrap();
+        (dispatcher, received)
+    }
+
+    fn alert() -> OutputRecord {
+        OutputRecord {
+            wfx_id: "0000000000000001".into(),
+            rule_name: "brute_force".into(),
+            score: 70.0,
+            entity_type: "ip".into(),
+            entity_id: "10.0.0.1".into(),
+            origin: AlertOrigin::Event,
+            fired_at: "2024-01-01T00:00:00.000Z".into(),
+            matched_rows: vec![],
+            summary: "5 failed logins".into(),
+            priority: 0,
+            yield_target: "alerts".into(),
+            yield_fields: vec![],
+            event_time_nanos: 1_704_067_200_000_000_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn every_configured_sink_receives_every_alert() {
+        let (dispatcher, received) = build_memory_dispatcher(
+            r#"
+[[sink_group.sinks]]
+connect = "mem"
+name = "a"
+
+[[sink_group.sinks]]
+connect = "mem"
+name = "b"
+"#,
+        )
+        .await;
+
+        for i in 0..3 {
+            let report = dispatcher
+                .dispatch("alerts", &alert(), &format!("{{\"n\":{i}}}"))
+                .await;
+            assert!(report.matched);
+            assert_eq!(report.delivered, 2);
+        }
+
+        let received = received.lock().unwrap();
+        let expected = vec!["{\"n\":0}", "{\"n\":1}", "{\"n\":2}"];
+        assert_eq!(received["a"], expected);
+        assert_eq!(received["b"], expected);
+    }
+
+    #[tokio::test]
+    async fn failing_sink_does_not_block_others() {
+        let (dispatcher, received) = build_memory_dispatcher(
+            r#"
+[[sink_group.sinks]]
+connect = "mem"
+name = "broken"
+
+[sink_group.sinks.params]
+fail = true
+
+[[sink_group.sinks]]
+connect = "mem"
+name = "ok"
+"#,
+        )
+        .await;
+
+        let report = dispatcher.dispatch("alerts", &alert(), "{}").await;
+        assert_eq!(report.delivered, 1);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, "broken");
+        assert_eq!(received.lock().unwrap()["ok"], vec!["{}"]);
+    }
+
+    #[tokio::test]
+    async fn alert_task_counts_failing_sink() {
+        let (dispatcher, received) = build_memory_dispatcher(
+            r#"
+[[sink_group.sinks]]
+connect = "mem"
+name = "broken"
+
+[sink_group.sinks.params]
+fail = true
+
+[[sink_group.sinks]]
+connect = "mem"
+name = "ok"
+"#,
+        )
+        .await;
+        let sink_names: Vec<String> = dispatcher.sink_names().map(str::to_string).collect();
+        let metrics = Arc::new(
+            crate::metrics::RuntimeMetrics::new(&["brute_force".to_string()], &[])
+                .with_sinks(&sink_names),
+        );
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tx.send(alert()).await.unwrap();
+        tx.send(alert()).await.unwrap();
+        drop(tx);
+        crate::alert_task::run_alert_dispatcher(
+            rx,
+            Arc::new(dispatcher),
+            Some(Arc::clone(&metrics)),
+            None,
+            None,
+        )
+        .await;
+
+        assert_eq!(received.lock().unwrap()["ok"].len(), 2);
+        let text = metrics.render_prometheus();
+        assert!(
+            text.contains("wf_alert_sink_failed_total{sink=\"broken\"} 2"),
+            "{text}"
+        );
+        assert!(
+            text.contains("wf_alert_sink_failed_total{sink=\"ok\"} 0"),
+            "{text}"
+        );
+        assert!(text.contains("wf_alert_dispatch_total 0"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn cef_sink_receives_cef_lines() {
+        let (dispatcher, received) = build_memory_dispatcher(
+            r#"
+[[sink_group.sinks]]
+connect = "mem"
+name = "siem"
+serializer = "cef"
+
+[[sink_group.sinks]]
+connect = "mem"
+name = "archive"
+"#,
+        )
+        .await;
+
+        let report = dispatcher.dispatch("alerts", &alert(), "{}").await;
+        assert_eq!(report.delivered, 2);
+
+        let received = received.lock().unwrap();
+        assert_eq!(received["archive"], vec!["{}"]);
+        assert_eq!(received["siem"], vec![wf_core::alert::to_cef(&alert())]);
+        assert!(received["siem"][0].starts_with("CEF:0|WarpFusion|wp-reactor|"));
+    }
+}
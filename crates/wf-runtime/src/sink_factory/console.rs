@@ -0,0 +1,235 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use wp_connector_api::*;
+use wp_model_core::model::DataRecord;
+
+// ---------------------------------------------------------------------------
+// ConsoleSinkFactory — built-in stdout sink
+// ---------------------------------------------------------------------------
+
+/// Factory for the built-in `console` sink type.
+///
+/// Prints each alert to stdout, one line per alert. The `format` parameter
+/// selects `"json"` (compact JSON, the default) or `"human"` (one readable
+/// summary line).
+pub struct ConsoleSinkFactory;
+
+impl SinkDefProvider for ConsoleSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "builtin_console".into(),
+            kind: "console".into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec!["format".into()],
+            default_params: ParamMap::new(),
+            origin: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SinkFactory for ConsoleSinkFactory {
+    fn kind(&self) -> &'static str {
+        "console"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        ConsoleFormat::from_params(&spec.params).map(|_| ())
+    }
+
+    async fn build(&self, spec: &SinkSpec, _ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let format = ConsoleFormat::from_params(&spec.params)?;
+        Ok(SinkHandle::new(Box::new(ConsoleSink::new(
+            tokio::io::stdout(),
+            format,
+        ))))
+    }
+}
+
+/// Line format of the console sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConsoleFormat {
+    Json,
+    Human,
+}
+
+impl ConsoleFormat {
+    fn from_params(params: &ParamMap) -> SinkResult<Self> {
+        match params.get("format").map(|v| v.as_str()) {
+            None | Some(Some("json")) => Ok(Self::Json),
+            Some(Some("human")) => Ok(Self::Human),
+            Some(other) => Err(SinkError::from(SinkReason::Sink(format!(
+                "console 'format' must be \"json\" or \"human\", got {other:?}"
+            )))),
+        }
+    }
+}
+
+/// Render one alert JSON as a human-readable line. Falls back to the raw
+/// input when it is not an alert object.
+fn human_line(alert_json: &str) -> String {
+    let Ok(serde_json::Value::Object(alert)) = serde_json::from_str(alert_json) else {
+        return alert_json.to_string();
+    };
+    let field = |name: &str| match alert.get(name) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => "-".to_string(),
+    };
+    format!(
+        "[{}] {} score={} {}={} ({}) {}",
+        field("fired_at"),
+        field("rule_name"),
+        field("score"),
+        field("entity_type"),
+        field("entity_id"),
+        field("origin"),
+        field("summary"),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// ConsoleSink — line writer implementing wp-connector-api traits
+// ---------------------------------------------------------------------------
+
+/// Writes one line per alert to `writer`, flushing after every line so
+/// output interleaves sanely with logs.
+pub(crate) struct ConsoleSink<W> {
+    writer: W,
+    format: ConsoleFormat,
+}
+
+impl<W: AsyncWrite + Unpin + Send + Sync> ConsoleSink<W> {
+    pub(crate) fn new(writer: W, format: ConsoleFormat) -> Self {
+        Self { writer, format }
+    }
+
+    async fn write_line(&mut self, data: &str) -> SinkResult<()> {
+        let line = match self.format {
+            ConsoleFormat::Json => data.trim_end().to_string(),
+            ConsoleFormat::Human => human_line(data),
+        };
+        self.writer
+            .write_all(line.as_bytes())
+            .await
+            .owe_sink("write line")?;
+        self.writer
+            .write_all(b"\n")
+            .await
+            .owe_sink("write newline")?;
+        self.writer.flush().await.owe_sink("flush")?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<W: AsyncWrite + Unpin + Send + Sync> AsyncCtrl for ConsoleSink<W> {
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.writer.flush().await.owe_sink("flush on stop")?;
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<W: AsyncWrite + Unpin + Send + Sync> AsyncRawDataSink for ConsoleSink<W> {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.write_line(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        self.write_line(&String::from_utf8_lossy(data)).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        for s in data {
+            self.write_line(s).await?;
+        }
+        Ok(())
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        for b in data {
+            self.sink_bytes(b).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<W: AsyncWrite + Unpin + Send + Sync> AsyncRecordSink for ConsoleSink<W> {
+    // wp-reactor doesn't use DataRecord; provide no-op implementations.
+    async fn sink_record(&mut self, _record: &DataRecord) -> SinkResult<()> {
+        Ok(())
+    }
+
+    async fn sink_records(&mut self, _records: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALERT: &str = r#"{"wfx_id":"abc","rule_name":"brute_force","score":70.0,"entity_type":"ip","entity_id":"10.0.0.1","origin":"event","fired_at":"2024-01-01T00:00:00Z","summary":"3 failed logins"}"#;
+
+    async fn written_lines(format: ConsoleFormat, alerts: &[&str]) -> Vec<String> {
+        let mut sink = ConsoleSink::new(Vec::new(), format);
+        for alert in alerts {
+            sink.sink_str(alert).await.unwrap();
+        }
+        sink.stop().await.unwrap();
+        String::from_utf8(sink.writer)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn json_format_writes_one_line_per_alert() {
+        let lines = written_lines(ConsoleFormat::Json, &[ALERT, "{\"n\":2}\n"]).await;
+        assert_eq!(lines, vec![ALERT.to_string(), "{\"n\":2}".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn human_format_summarises_alert() {
+        let lines = written_lines(ConsoleFormat::Human, &[ALERT, "not json"]).await;
+        assert_eq!(
+            lines,
+            vec![
+                "[2024-01-01T00:00:00Z] brute_force score=70.0 ip=10.0.0.1 (event) 3 failed logins"
+                    .to_string(),
+                "not json".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn format_param_validated() {
+        let params = |v: serde_json::Value| {
+            let mut params = ParamMap::new();
+            params.insert("format".to_string(), v);
+            params
+        };
+        assert_eq!(
+            ConsoleFormat::from_params(&ParamMap::new()).unwrap(),
+            ConsoleFormat::Json
+        );
+        assert_eq!(
+            ConsoleFormat::from_params(&params("human".into())).unwrap(),
+            ConsoleFormat::Human
+        );
+        assert!(ConsoleFormat::from_params(&params("yaml".into())).is_err());
+    }
+}
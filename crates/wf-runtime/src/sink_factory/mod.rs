@@ -1 +1,3 @@
+pub mod console;
 pub mod file;
+pub mod webhook;
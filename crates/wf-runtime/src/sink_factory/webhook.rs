@@ -0,0 +1,667 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{Mutex, mpsc};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use wf_config::HumanDuration;
+use wp_connector_api::*;
+use wp_model_core::model::DataRecord;
+
+use super::file::FileSinkFactory;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_QUEUE_CAPACITY: usize = 1024;
+const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+// ---------------------------------------------------------------------------
+// WebhookSinkFactory — built-in HTTP webhook sink
+// ---------------------------------------------------------------------------
+
+/// Factory for the built-in `webhook` sink type.
+///
+/// POSTs each alert JSON to `url`. Alerts are queued in a bounded channel
+/// and delivered by a background worker with exponential-backoff retries,
+/// so a slow or failing endpoint never blocks the alert task. Only transport
+/// errors, 5xx and 429 responses are retried. Alerts that cannot be queued or
+/// delivered, or are still queued when `stop_timeout` expires, are appended
+/// to the optional `dead_letter` file through a built-in `file` sink (so the
+/// path resolves relative to `SinkBuildCtx::work_root`); without one they
+/// are dropped with a warning.
+///
+/// Parameters: `url` (required), `timeout` (default `"5s"`), `max_retries`
+/// (default 3), `headers` (table), `queue_capacity` (default 1024),
+/// `stop_timeout` (default `"10s"`), `dead_letter` (path).
+pub struct WebhookSinkFactory;
+
+impl SinkDefProvider for WebhookSinkFactory {
+    fn sink_def(&self) -> ConnectorDef {
+        ConnectorDef {
+            id: "builtin_webhook".into(),
+            kind: "webhook".into(),
+            scope: ConnectorScope::Sink,
+            allow_override: vec![
+                "url".into(),
+                "timeout".into(),
+                "max_retries".into(),
+                "headers".into(),
+                "queue_capacity".into(),
+                "stop_timeout".into(),
+                "dead_letter".into(),
+            ],
+            default_params: ParamMap::new(),
+            origin: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SinkFactory for WebhookSinkFactory {
+    fn kind(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn validate_spec(&self, spec: &SinkSpec) -> SinkResult<()> {
+        WebhookParams::from_params(&spec.params).map(|_| ())
+    }
+
+    async fn build(&self, spec: &SinkSpec, ctx: &SinkBuildCtx) -> SinkResult<SinkHandle> {
+        let params = WebhookParams::from_params(&spec.params)?;
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &params.headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .owe_sink(format!("invalid header name {name:?}"))?;
+            let value = reqwest::header::HeaderValue::from_str(value)
+                .owe_sink(format!("invalid header value for {name}"))?;
+            headers.insert(name, value);
+        }
+        let client = reqwest::Client::builder()
+            .timeout(params.timeout)
+            .default_headers(headers)
+            .build()
+            .owe_sink("build http client")?;
+
+        let dead_letter = match &params.dead_letter {
+            Some(path) => Some(Arc::new(DeadLetter::open(spec, path, ctx).await?)),
+            None => None,
+        };
+
+        let (tx, rx) = mpsc::channel(params.queue_capacity);
+        let cancel = CancellationToken::new();
+        let worker = tokio::spawn(run_webhook_worker(
+            rx,
+            client,
+            params.url.clone(),
+            params.max_retries,
+            dead_letter.clone(),
+            cancel.clone(),
+        ));
+
+        Ok(SinkHandle::new(Box::new(AsyncWebhookSink {
+            url: params.url,
+            tx: Some(tx),
+            worker: Some(worker),
+            stop_timeout: params.stop_timeout,
+            cancel,
+            dead_letter,
+        })))
+    }
+}
+
+/// Parsed and validated webhook sink parameters.
+struct WebhookParams {
+    url: String,
+    timeout: Duration,
+    max_retries: u32,
+    headers: Vec<(String, String)>,
+    queue_capacity: usize,
+    stop_timeout: Duration,
+    dead_letter: Option<String>,
+}
+
+impl WebhookParams {
+    fn from_params(params: &ParamMap) -> SinkResult<Self> {
+        let url = params
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| sink_err("webhook sink requires string 'url' parameter"))?;
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(sink_err(format!(
+                "webhook 'url' must start with http:// or https://, got {url:?}"
+            )));
+        }
+
+        let timeout = duration_param(params, "timeout", DEFAULT_TIMEOUT)?;
+        let stop_timeout = duration_param(params, "stop_timeout", DEFAULT_STOP_TIMEOUT)?;
+
+        let max_retries = match params.get("max_retries") {
+            None => DEFAULT_MAX_RETRIES,
+            Some(v) => v
+                .as_u64()
+                .and_then(|n| u32::try_from(n).ok())
+                .ok_or_else(|| sink_err("'max_retries' must be a non-negative integer"))?,
+        };
+
+        let queue_capacity = match params.get("queue_capacity") {
+            None => DEFAULT_QUEUE_CAPACITY,
+            Some(v) => v
+                .as_u64()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| sink_err("'queue_capacity' must be a positive integer"))?
+                as usize,
+        };
+
+        let headers = match params.get("headers") {
+            None => Vec::new(),
+            Some(v) => v
+                .as_object()
+                .ok_or_else(|| sink_err("'headers' must be a table"))?
+                .iter()
+                .map(|(k, v)| {
+                    v.as_str()
+                        .map(|s| (k.clone(), s.to_string()))
+                        .ok_or_else(|| sink_err(format!("header {k:?} must be a string")))
+                })
+                .collect::<SinkResult<_>>()?,
+        };
+
+        let dead_letter = match params.get("dead_letter") {
+            None => None,
+            Some(v) => Some(
+                v.as_str()
+                    .ok_or_else(|| sink_err("'dead_letter' must be a string path"))?
+                    .to_string(),
+            ),
+        };
+
+        Ok(Self {
+            url: url.to_string(),
+            timeout,
+            max_retries,
+            headers,
+            queue_capacity,
+            stop_timeout,
+            dead_letter,
+        })
+    }
+}
+
+fn duration_param(params: &ParamMap, key: &str, default: Duration) -> SinkResult<Duration> {
+    match params.get(key) {
+        None => Ok(default),
+        Some(v) => Ok(v
+            .as_str()
+            .ok_or_else(|| sink_err(format!("'{key}' must be a duration string")))?
+            .parse::<HumanDuration>()
+            .owe_sink(format!("invalid '{key}'"))?
+            .as_duration()),
+    }
+}
+
+fn sink_err(msg: impl Into<String>) -> SinkError {
+    SinkError::from(SinkReason::Sink(msg.into()))
+}
+
+// ---------------------------------------------------------------------------
+// Delivery worker
+// ---------------------------------------------------------------------------
+
+async fn run_webhook_worker(
+    mut rx: mpsc::Receiver<String>,
+    client: reqwest::Client,
+    url: String,
+    max_retries: u32,
+    dead_letter: Option<Arc<DeadLetter>>,
+    cancel: CancellationToken,
+) {
+    while let Some(body) = rx.recv().await {
+        tokio::select! {
+            biased;
+            _ = cancel.cancelled() => {
+                abandon_queue(body, &mut rx, &url, dead_letter.as_deref()).await;
+                return;
+            }
+            result = post_with_retry(&client, &url, &body, max_retries) => {
+                if let Err(e) = result {
+                    wf_warn!(res, url = &*url, error = %e, "webhook delivery failed");
+                    if let Some(dead_letter) = &dead_letter {
+                        dead_letter.write(&body).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Stop deadline passed: give up on the in-flight alert and everything still
+/// queued behind it, dead-lettering them when a dead-letter file is set.
+async fn abandon_queue(
+    body: String,
+    rx: &mut mpsc::Receiver<String>,
+    url: &str,
+    dead_letter: Option<&DeadLetter>,
+) {
+    rx.close();
+    let mut pending = vec![body];
+    while let Ok(body) = rx.try_recv() {
+        pending.push(body);
+    }
+    let Some(dead_letter) = dead_letter else {
+        wf_warn!(
+            res,
+            url = url,
+            pending = pending.len(),
+            "webhook stop timed out, undelivered alerts dropped"
+        );
+        return;
+    };
+    wf_warn!(
+        res,
+        url = url,
+        pending = pending.len(),
+        "webhook stop timed out, undelivered alerts dead-lettered"
+    );
+    for body in &pending {
+        dead_letter.write(body).await;
+    }
+}
+
+/// POST `body`, retrying transport errors, 5xx and 429 responses with
+/// exponential backoff. Other non-2xx responses (a rejected payload, bad
+/// credentials) fail at once; otherwise the last error is returned once
+/// retries are exhausted.
+async fn post_with_retry(
+    client: &reqwest::Client,
+    url: &str,
+    body: &str,
+    max_retries: u32,
+) -> anyhow::Result<()> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        let result = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status());
+        match result {
+            Ok(_) => return Ok(()),
+            Err(e) if attempt >= max_retries || !is_retryable(&e) => return Err(e.into()),
+            Err(e) => {
+                wf_debug!(res, url = url, attempt, error = %e, "webhook post failed, retrying");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Transport errors carry no status and are always worth another attempt.
+fn is_retryable(e: &reqwest::Error) -> bool {
+    match e.status() {
+        Some(status) => {
+            status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }
+        None => true,
+    }
+}
+
+/// JSON-lines fallback for undeliverable alerts, written through a built-in
+/// `file` sink shared by the enqueue side and the delivery worker.
+struct DeadLetter {
+    sink: Mutex<SinkHandle>,
+}
+
+impl DeadLetter {
+    async fn open(spec: &SinkSpec, path: &str, ctx: &SinkBuildCtx) -> SinkResult<Self> {
+        let file_spec = SinkSpec {
+            group: spec.group.clone(),
+            name: format!("{}.dead_letter", spec.name),
+            kind: "file".into(),
+            connector_id: "builtin_file".into(),
+            params: [("path".to_string(), path.into())].into_iter().collect(),
+            filter: None,
+        };
+        Ok(Self {
+            sink: Mutex::new(FileSinkFactory.build(&file_spec, ctx).await?),
+        })
+    }
+
+    async fn write(&self, line: &str) {
+        if let Err(e) = self.sink.lock().await.sink.sink_str(line).await {
+            wf_warn!(res, error = %e, "webhook dead-letter write failed");
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AsyncWebhookSink — enqueue side implementing wp-connector-api traits
+// ---------------------------------------------------------------------------
+
+struct AsyncWebhookSink {
+    url: String,
+    /// `None` once stopped.
+    tx: Option<mpsc::Sender<String>>,
+    worker: Option<JoinHandle<()>>,
+    /// How long `stop` waits for the worker before abandoning the queue.
+    stop_timeout: Duration,
+    /// Tells the worker the stop deadline has passed.
+    cancel: CancellationToken,
+    dead_letter: Option<Arc<DeadLetter>>,
+}
+
+impl AsyncWebhookSink {
+    /// Queue one alert without waiting. When the queue is full the alert is
+    /// dead-lettered, or reported as an error if no dead-letter file is set.
+    async fn enqueue(&mut self, data: &str) -> SinkResult<()> {
+        let Some(tx) = &self.tx else {
+            return Err(sink_err("webhook sink already stopped"));
+        };
+        match tx.try_send(data.to_string()) {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Full(body)) => match &self.dead_letter {
+                Some(dead_letter) => {
+                    wf_warn!(
+                        res,
+                        url = &*self.url,
+                        "webhook queue full, alert dead-lettered"
+                    );
+                    dead_letter.write(&body).await;
+                    Ok(())
+                }
+                None => Err(sink_err(format!("webhook queue full ({})", self.url))),
+            },
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                Err(sink_err(format!("webhook worker gone ({})", self.url)))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncCtrl for AsyncWebhookSink {
+    /// Close the queue and give the worker `stop_timeout` to deliver what is
+    /// left; alerts still undelivered after that are dead-lettered or dropped.
+    async fn stop(&mut self) -> SinkResult<()> {
+        self.tx.take();
+        if let Some(mut worker) = self.worker.take() {
+            let joined = match tokio::time::timeout(self.stop_timeout, &mut worker).await {
+                Ok(joined) => joined,
+                Err(_) => {
+                    self.cancel.cancel();
+                    worker.await
+                }
+            };
+            joined.owe_sink("webhook worker join")?;
+        }
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRawDataSink for AsyncWebhookSink {
+    async fn sink_str(&mut self, data: &str) -> SinkResult<()> {
+        self.enqueue(data).await
+    }
+
+    async fn sink_bytes(&mut self, data: &[u8]) -> SinkResult<()> {
+        let data = std::str::from_utf8(data).owe_sink("webhook payload must be UTF-8")?;
+        self.enqueue(data).await
+    }
+
+    async fn sink_str_batch(&mut self, data: Vec<&str>) -> SinkResult<()> {
+        for s in data {
+            self.enqueue(s).await?;
+        }
+        Ok(())
+    }
+
+    async fn sink_bytes_batch(&mut self, data: Vec<&[u8]>) -> SinkResult<()> {
+        for b in data {
+            self.sink_bytes(b).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncRecordSink for AsyncWebhookSink {
+    // wp-reactor doesn't use DataRecord; provide no-op implementations.
+    async fn sink_record(&mut self, _record: &DataRecord) -> SinkResult<()> {
+        Ok(())
+    }
+
+    async fn sink_records(&mut self, _records: Vec<Arc<DataRecord>>) -> SinkResult<()> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn spec(params: serde_json::Value) -> SinkSpec {
+        SinkSpec {
+            group: "test".into(),
+            name: "hook".into(),
+            kind: "webhook".into(),
+            connector_id: "builtin_webhook".into(),
+            params: params
+                .as_object()
+                .unwrap()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            filter: None,
+        }
+    }
+
+    /// Minimal HTTP server: answers each request with `statuses[i]` (then
+    /// 200) and forwards `(headers, body)` of every request.
+    async fn mock_server(
+        statuses: Vec<u16>,
+    ) -> (String, mpsc::UnboundedReceiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut statuses = statuses.into_iter();
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = Vec::new();
+                let (head, body) = loop {
+                    let mut chunk = [0u8; 4096];
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..n]);
+                    let text = String::from_utf8_lossy(&buf).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let len: usize = head
+                            .lines()
+                            .find_map(|l| {
+                                l.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= len {
+                            break (head.to_string(), body.to_string());
+                        }
+                    }
+                    if n == 0 {
+                        break (text, String::new());
+                    }
+                };
+                let status = statuses.next().unwrap_or(200);
+                let _ = tx.send((head, body));
+                let resp = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                let _ = stream.write_all(resp.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        (format!("http://{addr}/alerts"), rx)
+    }
+
+    #[tokio::test]
+    async fn posts_alert_json_with_headers_and_retries() {
+        let (url, mut requests) = mock_server(vec![503]).await;
+        let ctx = SinkBuildCtx::new(std::env::temp_dir());
+        let mut handle = WebhookSinkFactory
+            .build(
+                &spec(serde_json::json!({
+                    "url": url,
+                    "max_retries": 2,
+                    "headers": { "X-Token": "secret" },
+                })),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        let alert = r#"{"rule_name":"brute_force","score":70.0}"#;
+        handle.sink.sink_str(alert).await.unwrap();
+        handle.sink.stop().await.unwrap();
+
+        // First attempt gets 503, the retry succeeds with the same payload.
+        let (_, first) = requests.recv().await.unwrap();
+        let (head, second) = requests.recv().await.unwrap();
+        assert_eq!(first, alert);
+        assert_eq!(second, alert);
+        assert!(head.to_ascii_lowercase().contains("x-token: secret"));
+        assert!(head.contains("application/json"));
+    }
+
+    #[tokio::test]
+    async fn undeliverable_alert_is_dead_lettered() {
+        let (url, _requests) = mock_server(vec![500, 500]).await;
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = SinkBuildCtx::new(dir.path().to_path_buf());
+        let mut handle = WebhookSinkFactory
+            .build(
+                &spec(serde_json::json!({
+                    "url": url,
+                    "max_retries": 1,
+                    "dead_letter": "alerts/webhook_dead.jsonl",
+                })),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        handle.sink.sink_str(r#"{"id":1}"#).await.unwrap();
+        handle.sink.stop().await.unwrap();
+
+        let dead = std::fs::read_to_string(dir.path().join("alerts/webhook_dead.jsonl")).unwrap();
+        assert_eq!(dead, "{\"id\":1}\n");
+    }
+
+    #[tokio::test]
+    async fn client_error_is_not_retried() {
+        let (url, mut requests) = mock_server(vec![400]).await;
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = SinkBuildCtx::new(dir.path().to_path_buf());
+        let mut handle = WebhookSinkFactory
+            .build(
+                &spec(serde_json::json!({
+                    "url": url,
+                    "max_retries": 3,
+                    "dead_letter": "dead.jsonl",
+                })),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        handle.sink.sink_str(r#"{"id":1}"#).await.unwrap();
+        handle.sink.stop().await.unwrap();
+
+        requests.recv().await.unwrap();
+        assert!(requests.try_recv().is_err(), "400 must not be retried");
+        let dead = std::fs::read_to_string(dir.path().join("dead.jsonl")).unwrap();
+        assert_eq!(dead, "{\"id\":1}\n");
+    }
+
+    #[tokio::test]
+    async fn stop_deadline_dead_letters_queued_alerts() {
+        // Accepted by the kernel backlog but never answered, so the first
+        // post hangs until the (long) request timeout.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = SinkBuildCtx::new(dir.path().to_path_buf());
+        let mut handle = WebhookSinkFactory
+            .build(
+                &spec(serde_json::json!({
+                    "url": url,
+                    "timeout": "30s",
+                    "stop_timeout": "100ms",
+                    "dead_letter": "dead.jsonl",
+                })),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        handle.sink.sink_str(r#"{"id":1}"#).await.unwrap();
+        handle.sink.sink_str(r#"{"id":2}"#).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), handle.sink.stop())
+            .await
+            .expect("stop must honour stop_timeout")
+            .unwrap();
+
+        let dead = std::fs::read_to_string(dir.path().join("dead.jsonl")).unwrap();
+        assert_eq!(dead, "{\"id\":1}\n{\"id\":2}\n");
+        drop(listener);
+    }
+
+    #[test]
+    fn validate_rejects_bad_params() {
+        let factory = WebhookSinkFactory;
+        assert!(factory.validate_spec(&spec(serde_json::json!({}))).is_err());
+        assert!(
+            factory
+                .validate_spec(&spec(serde_json::json!({ "url": "ftp://x" })))
+                .is_err()
+        );
+        assert!(
+            factory
+                .validate_spec(&spec(
+                    serde_json::json!({ "url": "http://x", "timeout": "soon" })
+                ))
+                .is_err()
+        );
+        assert!(
+            factory
+                .validate_spec(&spec(
+                    serde_json::json!({ "url": "http://x", "timeout": "2s" })
+                ))
+                .is_ok()
+        );
+        assert!(
+            factory
+                .validate_spec(&spec(
+                    serde_json::json!({ "url": "http://x", "stop_timeout": 5 })
+                ))
+                .is_err()
+        );
+    }
+}
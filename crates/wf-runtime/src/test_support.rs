@@ -0,0 +1,44 @@
+//! Shared fixtures for the ingestion tests (TCP receiver, HTTP receiver and
+//! Kafka source).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use wf_config::{DistMode, EvictPolicy, LatePolicy, WindowConfig};
+use wf_core::window::{Router, WindowDef, WindowParams, WindowRegistry};
+
+/// `ts: timestamp(ns), value: int64` — the layout of the `test_win` window.
+pub(crate) fn test_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+        Field::new("value", DataType::Int64, true),
+    ]))
+}
+
+/// Router with a single `test_win` window fed by `stream_name`, holding at
+/// most `max_window_bytes`.
+pub(crate) fn make_router(stream_name: &str, max_window_bytes: usize) -> Arc<Router> {
+    let reg = WindowRegistry::build(vec![WindowDef {
+        params: WindowParams {
+            name: "test_win".into(),
+            schema: test_schema(),
+            time_col_index: Some(0),
+            over: Duration::from_secs(3600),
+        },
+        streams: vec![stream_name.to_string()],
+        config: WindowConfig {
+            name: "test_win".into(),
+            mode: DistMode::Local,
+            max_window_bytes: max_window_bytes.into(),
+            over_cap: Duration::from_secs(3600).into(),
+            evict_policy: EvictPolicy::TimeFirst,
+            watermark: Duration::from_secs(0).into(),
+            allowed_lateness: Duration::from_secs(3600).into(),
+            late_policy: LatePolicy::Drop,
+            compact_below: 0.into(),
+        },
+    }])
+    .unwrap();
+    Arc::new(Router::new(reg))
+}
@@ -1,14 +1,17 @@
+use std::collections::HashMap;
 use std::fmt::{self as stdfmt, Write as _};
 use std::path::Path;
 
 use anyhow::Result;
 use tracing::field::{Field, Visit};
-use tracing::{Event, Level, Subscriber};
+use tracing::level_filters::LevelFilter;
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber, span};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::fmt::format::DefaultFields;
+use tracing_subscriber::fmt::format::{DefaultFields, Format, Json, JsonFields};
 use tracing_subscriber::fmt::time::{FormatTime, SystemTime};
-use tracing_subscriber::fmt::{self, FmtContext, FormatEvent, FormattedFields};
-use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::fmt::{self, FmtContext, FormatEvent, FormattedFields, MakeWriter};
+use tracing_subscriber::layer::{self, Context, SubscriberExt};
 use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, Layer};
@@ -31,6 +34,46 @@ impl<'writer> fmt::FormatFields<'writer> for FileFields {
     }
 }
 
+/// JSON counterpart of [`FileFields`]: span fields must be cached as JSON
+/// for the JSON formatter to embed them as objects.
+#[derive(Default)]
+pub struct FileJsonFields(JsonFields);
+
+impl<'writer> fmt::FormatFields<'writer> for FileJsonFields {
+    fn format_fields<R: tracing_subscriber::field::RecordFields>(
+        &self,
+        writer: fmt::format::Writer<'writer>,
+        fields: R,
+    ) -> stdfmt::Result {
+        self.0.format_fields(writer, fields)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// json_layer — one JSON object per event
+// ---------------------------------------------------------------------------
+
+/// Build a JSON fmt layer. Event fields (`domain`, `task_id`, `message`, …)
+/// become top-level keys; the current span and its fields (e.g. `listen`)
+/// are nested under `span`.
+///
+/// ```text
+/// {"timestamp":"…","level":"INFO","message":"engine bootstrap complete","domain":"sys","rules":3,"span":{"listen":"0.0.0.0:9800","name":"engine.start"},…}
+/// ```
+fn json_layer<S, N, W>(fields: N, writer: W) -> fmt::Layer<S, N, Format<Json>, W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'writer> fmt::FormatFields<'writer> + 'static,
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_target(false)
+        .fmt_fields(fields)
+        .with_writer(writer)
+}
+
 // ---------------------------------------------------------------------------
 // DomainFormat — promotes `domain` field to a `[domain]` prefix
 // ---------------------------------------------------------------------------
@@ -233,6 +276,123 @@ impl Visit for DomainExtractor {
     }
 }
 
+// ---------------------------------------------------------------------------
+// DomainFilter — per-domain level overrides on top of an EnvFilter
+// ---------------------------------------------------------------------------
+
+/// Per-layer filter applying `[logging.domains]` overrides.
+///
+/// `EnvFilter` field directives only match span fields, while `domain` is an
+/// event field, so domain-tagged events are decided here by the level
+/// configured for their domain. Everything else — including events of
+/// domains without an override — falls through to the inner `EnvFilter`.
+pub struct DomainFilter {
+    base: EnvFilter,
+    domains: HashMap<String, LevelFilter>,
+    /// Most verbose domain level; callsites above it skip the domain lookup.
+    domain_max: LevelFilter,
+}
+
+impl DomainFilter {
+    pub fn new(base: EnvFilter, domains: HashMap<String, LevelFilter>) -> Self {
+        let domain_max = domains.values().copied().max().unwrap_or(LevelFilter::OFF);
+        Self {
+            base,
+            domains,
+            domain_max,
+        }
+    }
+
+    /// Whether `meta` is an event that may be decided by a domain override.
+    fn is_domain_callsite(&self, meta: &Metadata<'_>) -> bool {
+        !self.domains.is_empty() && meta.is_event() && meta.fields().field("domain").is_some()
+    }
+}
+
+impl<S: Subscriber> layer::Filter<S> for DomainFilter {
+    fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        // Domain callsites are decided per event in `event_enabled`.
+        self.is_domain_callsite(meta) || layer::Filter::<S>::enabled(&self.base, meta, cx)
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        if self.is_domain_callsite(meta) {
+            Interest::sometimes()
+        } else {
+            layer::Filter::<S>::callsite_enabled(&self.base, meta)
+        }
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, cx: &Context<'_, S>) -> bool {
+        let meta = event.metadata();
+        if !self.is_domain_callsite(meta) {
+            return true;
+        }
+        let mut visitor = DomainExtractor::default();
+        event.record(&mut visitor);
+        match visitor.domain.and_then(|d| self.domains.get(&d)) {
+            Some(level) => meta.level() <= level,
+            None => layer::Filter::<S>::enabled(&self.base, meta, cx),
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        layer::Filter::<S>::max_level_hint(&self.base).map(|hint| hint.max(self.domain_max))
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        layer::Filter::<S>::on_new_span(&self.base, attrs, id, ctx)
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        layer::Filter::<S>::on_record(&self.base, id, values, ctx)
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        layer::Filter::<S>::on_enter(&self.base, id, ctx)
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        layer::Filter::<S>::on_exit(&self.base, id, ctx)
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        layer::Filter::<S>::on_close(&self.base, id, ctx)
+    }
+}
+
+/// Build the stderr filter from [`LoggingConfig`]: `level` + `modules` as an
+/// [`EnvFilter`], plus `domains` overrides.
+///
+/// `RUST_LOG` overrides all config-driven directives, domains included.
+pub fn build_filter(config: &LoggingConfig) -> Result<DomainFilter> {
+    if std::env::var("RUST_LOG").is_ok() {
+        return Ok(DomainFilter::new(
+            EnvFilter::from_default_env(),
+            HashMap::new(),
+        ));
+    }
+
+    let mut directives = config.level.clone();
+    for (module, level) in &config.modules {
+        directives.push(',');
+        directives.push_str(module);
+        directives.push('=');
+        directives.push_str(level);
+    }
+    let base = EnvFilter::try_new(&directives)
+        .map_err(|e| anyhow::anyhow!("invalid log filter '{directives}': {e}"))?;
+
+    let mut domains = HashMap::with_capacity(config.domains.len());
+    for (domain, level) in &config.domains {
+        let level: LevelFilter = level.parse().map_err(|e| {
+            anyhow::anyhow!("invalid level '{level}' for log domain '{domain}': {e}")
+        })?;
+        domains.insert(domain.clone(), level);
+    }
+    Ok(DomainFilter::new(base, domains))
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -247,20 +407,8 @@ impl Visit for DomainExtractor {
 /// The `log` → `tracing` bridge is set up automatically by
 /// `tracing-subscriber`'s default `tracing-log` feature.
 pub fn init_tracing(config: &LoggingConfig, base_dir: &Path) -> Result<Option<WorkerGuard>> {
-    // 1. Build EnvFilter ------------------------------------------------
-    let filter = if std::env::var("RUST_LOG").is_ok() {
-        EnvFilter::from_default_env()
-    } else {
-        let mut directives = config.level.clone();
-        for (module, level) in &config.modules {
-            directives.push(',');
-            directives.push_str(module);
-            directives.push('=');
-            directives.push_str(level);
-        }
-        EnvFilter::try_new(&directives)
-            .map_err(|e| anyhow::anyhow!("invalid log filter '{directives}': {e}"))?
-    };
+    // 1. Build filter (EnvFilter + domain overrides) ---------------------
+    let filter = build_filter(config)?;
 
     // 2. stderr + optional file layer -----------------------------------
     let mut guard: Option<WorkerGuard> = None;
@@ -289,17 +437,9 @@ pub fn init_tracing(config: &LoggingConfig, base_dir: &Path) -> Result<Option<Wo
 
         if is_json {
             // JSON: keep domain as a regular field — consumers query by key
-            let stderr_layer = fmt::layer()
-                .json()
-                .with_target(false)
-                .with_writer(std::io::stderr)
-                .with_filter(filter);
-            let file_layer = fmt::layer()
-                .json()
-                .fmt_fields(FileFields::default())
-                .with_target(false)
-                .with_ansi(false)
-                .with_writer(non_blocking);
+            let stderr_layer =
+                json_layer(JsonFields::default(), std::io::stderr).with_filter(filter);
+            let file_layer = json_layer(FileJsonFields::default(), non_blocking).with_ansi(false);
             tracing_subscriber::registry()
                 .with(stderr_layer)
                 .with(file_layer)
@@ -324,13 +464,7 @@ pub fn init_tracing(config: &LoggingConfig, base_dir: &Path) -> Result<Option<Wo
         // stderr only
         if is_json {
             tracing_subscriber::registry()
-                .with(
-                    fmt::layer()
-                        .json()
-                        .with_target(false)
-                        .with_writer(std::io::stderr)
-                        .with_filter(filter),
-                )
+                .with(json_layer(JsonFields::default(), std::io::stderr).with_filter(filter))
                 .init();
         } else {
             tracing_subscriber::registry()
@@ -346,3 +480,88 @@ pub fn init_tracing(config: &LoggingConfig, base_dir: &Path) -> Result<Option<Wo
 
     Ok(guard)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Captured {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn json_layer_emits_structured_fields() {
+        let out = Captured::default();
+        let subscriber =
+            tracing_subscriber::registry().with(json_layer(JsonFields::default(), out.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("engine.start", listen = "127.0.0.1:9800");
+            let _enter = span.enter();
+            wf_info!(sys, rules = 3, "engine bootstrap complete");
+        });
+
+        let bytes = out.0.lock().unwrap().clone();
+        let line = String::from_utf8(bytes).unwrap();
+        let json: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(json["level"], "INFO");
+        assert_eq!(json["message"], "engine bootstrap complete");
+        assert_eq!(json["domain"], "sys");
+        assert_eq!(json["rules"], 3);
+        assert_eq!(json["span"]["name"], "engine.start");
+        assert_eq!(json["span"]["listen"], "127.0.0.1:9800");
+    }
+
+    #[test]
+    fn domain_filter_raises_level_for_one_domain() {
+        let out = Captured::default();
+        let filter = DomainFilter::new(
+            EnvFilter::try_new("info").unwrap(),
+            HashMap::from([("pipe".to_string(), LevelFilter::DEBUG)]),
+        );
+        let subscriber = tracing_subscriber::registry().with(
+            fmt::layer()
+                .event_format(DomainFormat::new())
+                .with_ansi(false)
+                .with_writer(out.clone())
+                .with_filter(filter),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            wf_debug!(pipe, rows = 3, "batch routed");
+            wf_debug!(
+                sys,
+                task_group = "rules",
+                "waiting for task group to finish"
+            );
+            wf_info!(sys, "engine started");
+            wf_trace!(pipe, "frame decoded");
+        });
+
+        let text = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+        assert!(text.contains("[pipe] batch routed"), "{text}");
+        assert!(text.contains("[sys] engine started"), "{text}");
+        assert!(!text.contains("waiting for task group"), "{text}");
+        assert!(!text.contains("frame decoded"), "{text}");
+    }
+}
@@ -0,0 +1,96 @@
+//! End-to-end health probe test.
+//!
+//! `/healthz` and `/readyz` are served on the metrics listener; readiness
+//! is set once `Reactor::start` returns and cleared by `shutdown()` while
+//! the listener keeps answering through the drain.
+
+use std::net::SocketAddr;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use wf_config::FusionConfig;
+use wf_runtime::lifecycle::Reactor;
+
+/// Send `GET path` and return the status code.
+async fn get_status(addr: SocketAddr, path: &str) -> u16 {
+    let mut stream = TcpStream::connect(addr).await.expect("connect failed");
+    stream
+        .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+        .await
+        .expect("write failed");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("read failed");
+    response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .unwrap_or_else(|| panic!("malformed response: {response:?}"))
+}
+
+#[tokio::test]
+async fn readyz_flips_on_shutdown() {
+    let work_root = tempfile::tempdir().unwrap();
+    let toml_str = format!(
+        r#"
+sinks = "sinks"
+work_root = "{}"
+
+[server]
+listen = "tcp://127.0.0.1:0"
+
+[runtime]
+executor_parallelism = 2
+rule_exec_timeout = "30s"
+schemas = "count/schemas/*.wfs"
+rules   = "count/rules/*.wfl"
+
+[window_defaults]
+evict_interval = "30s"
+max_window_bytes = "256MB"
+max_total_bytes = "2GB"
+evict_policy = "time_first"
+watermark = "5s"
+allowed_lateness = "0s"
+late_policy = "drop"
+
+[window.auth_events]
+mode = "local"
+max_window_bytes = "256MB"
+over_cap = "30m"
+
+[window.security_alerts]
+mode = "local"
+max_window_bytes = "64MB"
+over_cap = "1h"
+
+[metrics]
+enabled = true
+report_interval = "60s"
+prometheus_listen = "127.0.0.1:0"
+"#,
+        work_root.path().display()
+    );
+    let config: FusionConfig = toml_str.parse().expect("failed to parse config TOML");
+    let base_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples");
+
+    let reactor = Reactor::start(config, &base_dir)
+        .await
+        .expect("Reactor::start failed");
+    let addr = reactor.metrics_addr().expect("metrics listener bound");
+
+    assert_eq!(get_status(addr, "/healthz").await, 200);
+    assert_eq!(get_status(addr, "/readyz").await, 200);
+    assert_eq!(get_status(addr, "/metrics").await, 200);
+    assert_eq!(get_status(addr, "/nope").await, 404);
+
+    reactor.shutdown();
+    assert_eq!(get_status(addr, "/healthz").await, 200);
+    assert_eq!(get_status(addr, "/readyz").await, 503);
+
+    reactor.wait().await.expect("reactor.wait failed");
+    assert!(TcpStream::connect(addr).await.is_err(), "listener closed");
+}
@@ -0,0 +1,176 @@
+//! End-to-end rule hot reload test.
+//!
+//! Starts a reactor with one rule, rewrites the `.wfl` file, reloads, and
+//! proves the newly added rule matches data sent after the reload while
+//! windows and the receiver keep running.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::array::{StringArray, TimestampNanosecondArray};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+use wf_config::FusionConfig;
+use wf_runtime::lifecycle::Reactor;
+
+const QUIET_RULE: &str = r#"
+rule quiet {
+  events { fail : auth_events && action == "failed" }
+  match<sip:5m> {
+    on event { fail | count >= 100; }
+  } -> score(10.0)
+  entity(ip, fail.sip)
+  yield security_alerts (sip = fail.sip, fail_count = count(fail), message = "quiet")
+}
+"#;
+
+const LOUD_RULE: &str = r#"
+rule loud {
+  events { fail : auth_events && action == "failed" }
+  match<sip:5m> {
+    on event { fail | count >= 3; }
+  } -> score(90.0)
+  entity(ip, fail.sip)
+  yield security_alerts (sip = fail.sip, fail_count = count(fail), message = "loud")
+}
+"#;
+
+/// Build a length-prefixed TCP frame from an Arrow IPC payload.
+fn make_tcp_frame(ipc_payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(4 + ipc_payload.len());
+    frame.extend_from_slice(&(ipc_payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(ipc_payload);
+    frame
+}
+
+fn failed_logins(sip: &str, base_ts: i64) -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("sip", DataType::Utf8, true),
+        Field::new("username", DataType::Utf8, true),
+        Field::new("action", DataType::Utf8, true),
+        Field::new(
+            "event_time",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        ),
+    ]));
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(StringArray::from(vec![sip; 3])),
+            Arc::new(StringArray::from(vec!["admin"; 3])),
+            Arc::new(StringArray::from(vec!["failed"; 3])),
+            Arc::new(TimestampNanosecondArray::from(vec![
+                base_ts,
+                base_ts + 1_000_000_000,
+                base_ts + 2_000_000_000,
+            ])),
+        ],
+    )
+    .expect("failed to build RecordBatch")
+}
+
+#[tokio::test]
+async fn reload_starts_new_rule_and_keeps_old_on_compile_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let examples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples");
+    std::fs::create_dir_all(dir.path().join("schemas")).unwrap();
+    std::fs::create_dir_all(dir.path().join("rules")).unwrap();
+    std::fs::copy(
+        examples.join("count/schemas/security.wfs"),
+        dir.path().join("schemas/security.wfs"),
+    )
+    .unwrap();
+    let rule_file = dir.path().join("rules/active.wfl");
+    std::fs::write(&rule_file, format!("use \"security.wfs\"\n{QUIET_RULE}")).unwrap();
+
+    let toml_str = format!(
+        r#"
+sinks = "{sinks}"
+work_root = "{work_root}"
+
+[server]
+listen = "tcp://127.0.0.1:0"
+
+[runtime]
+executor_parallelism = 2
+rule_exec_timeout = "30s"
+schemas = "schemas/*.wfs"
+rules   = "rules/*.wfl"
+
+[window_defaults]
+evict_interval = "30s"
+max_window_bytes = "256MB"
+max_total_bytes = "2GB"
+evict_policy = "time_first"
+watermark = "5s"
+allowed_lateness = "0s"
+late_policy = "drop"
+
+[window.auth_events]
+mode = "local"
+max_window_bytes = "256MB"
+over_cap = "30m"
+
+[window.security_alerts]
+mode = "local"
+max_window_bytes = "64MB"
+over_cap = "1h"
+"#,
+        sinks = examples.join("sinks").display(),
+        work_root = dir.path().display(),
+    );
+    let config: FusionConfig = toml_str.parse().expect("failed to parse config TOML");
+
+    let reactor = Reactor::start(config, dir.path())
+        .await
+        .expect("Reactor::start failed");
+    let reload = reactor.reload_handle();
+
+    // A broken rule file is rejected; the running rules stay in place.
+    std::fs::write(&rule_file, "use \"security.wfs\"\nrule broken {").unwrap();
+    assert!(reload.reload().await.is_err());
+
+    std::fs::write(
+        &rule_file,
+        format!("use \"security.wfs\"\n{QUIET_RULE}\n{LOUD_RULE}"),
+    )
+    .unwrap();
+    let summary = reload.reload().await.expect("reload failed");
+    assert_eq!(summary.added, vec!["loud".to_string()]);
+    assert_eq!(summary.unchanged, vec!["quiet".to_string()]);
+    assert!(summary.replaced.is_empty());
+    assert!(summary.removed.is_empty());
+
+    // Data sent after the reload reaches the new rule.
+    let batch = failed_logins("10.0.0.7", 1_700_000_000_000_000_000);
+    let ipc_payload = wp_arrow::ipc::encode_ipc("syslog", &batch).expect("encode_ipc failed");
+    let mut stream = TcpStream::connect(reactor.listen_addr())
+        .await
+        .expect("TCP connect failed");
+    stream
+        .write_all(&make_tcp_frame(&ipc_payload))
+        .await
+        .expect("TCP write failed");
+    stream.flush().await.expect("TCP flush failed");
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    reactor.shutdown();
+    drop(stream);
+    reactor.wait().await.expect("reactor.wait failed");
+
+    let alert_path = dir.path().join("alerts/all.jsonl");
+    let alert_content = std::fs::read_to_string(&alert_path)
+        .unwrap_or_else(|e| panic!("failed to read alert file {}: {e}", alert_path.display()));
+    let rules: Vec<String> = alert_content
+        .lines()
+        .map(|line| {
+            let alert: serde_json::Value = serde_json::from_str(line).unwrap();
+            alert["rule_name"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(rules, vec!["loud".to_string()], "alerts:\n{alert_content}");
+}
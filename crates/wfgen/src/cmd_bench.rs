@@ -1,44 +1,36 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use anyhow::Context;
-
 use wfgen::datagen::generate;
-use wfgen::loader::load_from_uses;
-use wfgen::wfg_parser::parse_wfg;
+use wfgen::loader::{CompiledProject, compile_project};
 
-use crate::cmd_helpers::{load_wfl_files, load_ws_files};
-use crate::tcp_send::send_events;
+use crate::cmd_helpers::parse_duration_arg;
+use crate::tcp_send::{SendOptions, send_events};
 
 pub(crate) fn run(
     scenario: PathBuf,
-    ws: Vec<PathBuf>,
+    schema: Vec<String>,
     wfl: Vec<PathBuf>,
     bench_duration: Option<String>,
     send: bool,
     addr: String,
+    seed: Option<u64>,
 ) -> anyhow::Result<()> {
-    let wfg_content = std::fs::read_to_string(&scenario).context("reading .wfg file")?;
-    let wfg = parse_wfg(&wfg_content).context("parsing .wfg file")?;
-
-    let (mut schemas, mut wfl_files) = load_from_uses(&wfg, &scenario, &HashMap::new())?;
-    schemas.extend(load_ws_files(&ws)?);
-    wfl_files.extend(load_wfl_files(&wfl)?);
-
-    // Compile WFL rules
-    let mut rule_plans = Vec::new();
-    for wfl_file in &wfl_files {
-        match wf_lang::compile_wfl(wfl_file, &schemas) {
-            Ok(plans) => rule_plans.extend(plans),
-            Err(e) => {
-                eprintln!("Warning: WFL compilation failed: {}", e);
-            }
-        }
+    let project = compile_project(&scenario, &schema, &wfl, &HashMap::new())?;
+    for e in &project.compile_errors {
+        eprintln!("Warning: WFL compilation failed: {}", e);
+    }
+    let CompiledProject {
+        mut wfg,
+        schemas,
+        rule_plans,
+        ..
+    } = project;
+    if let Some(seed) = seed {
+        wfg.scenario.seed = seed;
     }
 
-    let sustained = bench_duration
-        .map(|s| parse_bench_duration(&s))
-        .transpose()?;
+    let sustained = bench_duration.map(|s| parse_duration_arg(&s)).transpose()?;
 
     match sustained {
         Some(target_dur) => {
@@ -51,7 +43,9 @@ pub(crate) fn run(
             while wall_start.elapsed() < target_dur {
                 let result = generate(&wfg, &schemas, &rule_plans)?;
                 if send {
-                    total_frames += send_events(&result.events, &schemas, &addr)? as u64;
+                    total_frames +=
+                        send_events(&result.events, &schemas, &addr, SendOptions::default())?
+                            as u64;
                 }
                 total_events += result.events.len() as u64;
                 iterations += 1;
@@ -78,7 +72,12 @@ pub(crate) fn run(
             let start = std::time::Instant::now();
             let result = generate(&wfg, &schemas, &rule_plans)?;
             let sent_frames = if send {
-                Some(send_events(&result.events, &schemas, &addr)?)
+                Some(send_events(
+                    &result.events,
+                    &schemas,
+                    &addr,
+                    SendOptions::default(),
+                )?)
             } else {
                 None
             };
@@ -103,39 +102,3 @@ pub(crate) fn run(
 
     Ok(())
 }
-
-/// Parse a human-friendly duration string (e.g. "30s", "2m", "1h") into `std::time::Duration`.
-fn parse_bench_duration(s: &str) -> anyhow::Result<std::time::Duration> {
-    let s = s.trim();
-    if s.is_empty() {
-        anyhow::bail!("empty duration string");
-    }
-
-    let (num_str, suffix) = if let Some(stripped) = s.strip_suffix('s') {
-        (stripped, "s")
-    } else if let Some(stripped) = s.strip_suffix('m') {
-        (stripped, "m")
-    } else if let Some(stripped) = s.strip_suffix('h') {
-        (stripped, "h")
-    } else {
-        // Assume seconds if no suffix
-        (s, "s")
-    };
-
-    let value: f64 = num_str
-        .parse()
-        .map_err(|_| anyhow::anyhow!("invalid duration number: '{}'", num_str))?;
-
-    let secs = match suffix {
-        "s" => value,
-        "m" => value * 60.0,
-        "h" => value * 3600.0,
-        _ => unreachable!(),
-    };
-
-    if secs <= 0.0 {
-        anyhow::bail!("bench duration must be positive, got '{}'", s);
-    }
-
-    Ok(std::time::Duration::from_secs_f64(secs))
-}
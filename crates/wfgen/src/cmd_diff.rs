@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use wfgen::oracle::OracleTolerances;
+use wfgen::output::jsonl::read_oracle_jsonl;
+use wfgen::verify::diff_oracles;
+
+pub(crate) fn run(
+    old: PathBuf,
+    new: PathBuf,
+    score_tolerance: Option<f64>,
+    time_tolerance: Option<f64>,
+    format: String,
+) -> anyhow::Result<()> {
+    let defaults = OracleTolerances::default();
+    let score_tolerance = score_tolerance.unwrap_or(defaults.score_tolerance);
+    let time_tolerance = time_tolerance.unwrap_or(defaults.time_tolerance_secs);
+
+    let old_alerts =
+        read_oracle_jsonl(&old).with_context(|| format!("reading old: {}", old.display()))?;
+    let new_alerts =
+        read_oracle_jsonl(&new).with_context(|| format!("reading new: {}", new.display()))?;
+
+    let report = diff_oracles(&old_alerts, &new_alerts, score_tolerance, time_tolerance);
+
+    match format.as_str() {
+        "markdown" | "md" => {
+            println!("{}", report.to_markdown());
+        }
+        "json" => {
+            let json = serde_json::to_string_pretty(&report)?;
+            println!("{}", json);
+        }
+        other => anyhow::bail!("unknown --format '{other}': expected json or markdown"),
+    }
+
+    Ok(())
+}
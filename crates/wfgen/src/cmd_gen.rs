@@ -1,51 +1,70 @@
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
-use anyhow::Context;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 
 use wfgen::datagen::fault_gen::apply_faults;
-use wfgen::datagen::generate;
-use wfgen::loader::load_from_uses;
-use wfgen::oracle::{extract_oracle_tolerances, run_oracle};
+use wfgen::datagen::{DryRunReport, dry_run, generate, generate_streaming, parse_timestamp};
+use wfgen::loader::{CompiledProject, compile_project};
+use wfgen::oracle::{OracleAlert, extract_oracle_tolerances, run_oracle};
 use wfgen::output::arrow_ipc::write_arrow_ipc;
-use wfgen::output::jsonl::{write_jsonl, write_oracle_jsonl};
+use wfgen::output::csv::{schema_columns, write_csv, write_csv_stream};
+use wfgen::output::jsonl::{write_jsonl, write_jsonl_stream, write_oracle_jsonl};
+use wfgen::output::parquet::{parse_compression, write_parquet};
 use wfgen::validate::validate_wfg;
-use wfgen::wfg_parser::parse_wfg;
+use wfgen::verify::truncate_oracle;
 
-use crate::cmd_helpers::{load_wfl_files, load_ws_files};
-use crate::tcp_send::send_events;
+use crate::tcp_send::{SendOptions, send_events};
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
     scenario: PathBuf,
     format: String,
+    compression: String,
     out: PathBuf,
-    ws: Vec<PathBuf>,
+    schema: Vec<String>,
     wfl: Vec<PathBuf>,
     no_oracle: bool,
     send: bool,
     addr: String,
+    send_opts: SendOptions,
+    stream: bool,
+    plan_only: bool,
+    seed: Option<u64>,
+    limit: Option<usize>,
 ) -> anyhow::Result<()> {
     let normalized_format = match format.as_str() {
         "jsonl" => "jsonl",
         "arrow" | "arrow-ipc" | "ipc" => "arrow",
+        "parquet" => "parquet",
+        "csv" => "csv",
         _ => "",
     };
     if normalized_format.is_empty() {
         anyhow::bail!(
-            "unsupported format: '{}'. Supported: 'jsonl', 'arrow' ('arrow-ipc' alias).",
+            "unsupported format: '{}'. Supported: 'jsonl', 'arrow' ('arrow-ipc' alias), 'parquet', 'csv'.",
             format
         );
     }
+    let compression = parse_compression(&compression)?;
 
-    let wfg_content = std::fs::read_to_string(&scenario).context("reading .wfg file")?;
-    let wfg = parse_wfg(&wfg_content).context("parsing .wfg file")?;
-
-    let (mut schemas, mut wfl_files) = load_from_uses(&wfg, &scenario, &HashMap::new())?;
-    schemas.extend(load_ws_files(&ws)?);
-    wfl_files.extend(load_wfl_files(&wfl)?);
+    let CompiledProject {
+        mut wfg,
+        schemas,
+        wfl_files,
+        rule_plans,
+        compile_errors,
+        compile_warnings,
+    } = compile_project(&scenario, &schema, &wfl, &HashMap::new())?;
+    for w in &compile_warnings {
+        eprintln!("{w}");
+    }
+    // `--seed` replaces the scenario seed before anything derives from it
+    // (event generation and the fault RNG at `seed + 1`).
+    if let Some(seed) = seed {
+        wfg.scenario.seed = seed;
+    }
 
     let errors = validate_wfg(&wfg, &schemas, &wfl_files);
     if !errors.is_empty() {
@@ -56,18 +75,6 @@ pub(crate) fn run(
         anyhow::bail!("{} validation error(s) found", errors.len());
     }
 
-    // Compile WFL rules
-    let mut rule_plans = Vec::new();
-    let mut compile_errors = Vec::new();
-    for wfl_file in &wfl_files {
-        match wf_lang::compile_wfl(wfl_file, &schemas) {
-            Ok(plans) => rule_plans.extend(plans),
-            Err(e) => {
-                compile_errors.push(e);
-            }
-        }
-    }
-
     // Expected output is requested by either:
     // - legacy oracle block, or
     // - new syntax expect block.
@@ -94,20 +101,51 @@ pub(crate) fn run(
         }
     }
 
-    // Generate clean events
-    let result = generate(&wfg, &schemas, &rule_plans)?;
+    if plan_only {
+        let report = dry_run(&wfg, &rule_plans)?;
+        print_dry_run(&wfg.scenario.name, &report);
+        return Ok(());
+    }
 
     // Expected alert generation (on CLEAN events, before faults).
     let expected_enabled = expected_requested && !rule_plans.is_empty();
+
+    if stream {
+        if wfg.scenario.faults.is_some() {
+            anyhow::bail!("--stream does not support faults (they reorder the whole event set)");
+        }
+        if expected_enabled {
+            anyhow::bail!("--stream does not support expected output; use --no-oracle");
+        }
+        if send {
+            anyhow::bail!("--stream does not support --send; use `wfgen send` on the output");
+        }
+        let events = generate_streaming(&wfg, &schemas, &rule_plans)?;
+        let (count, output_file) = match normalized_format {
+            "jsonl" => {
+                let output_file = out.join(format!("{}.jsonl", wfg.scenario.name));
+                (write_jsonl_stream(events, &output_file)?, output_file)
+            }
+            "csv" => {
+                let output_file = out.join(format!("{}.csv", wfg.scenario.name));
+                let columns = schema_columns(events.windows(), &schemas);
+                (
+                    write_csv_stream(events, &columns, &output_file)?,
+                    output_file,
+                )
+            }
+            other => anyhow::bail!("--stream supports 'jsonl' and 'csv' formats, not '{other}'"),
+        };
+        println!("Generated {} events -> {}", count, output_file.display());
+        return Ok(());
+    }
+
+    // Generate clean events
+    let result = generate(&wfg, &schemas, &rule_plans)?;
     let mut expected_alert_count = 0;
     if expected_enabled {
-        let start = wfg.scenario.time_clause.start.parse().map_err(|e| {
-            anyhow::anyhow!(
-                "invalid start time '{}': {}",
-                wfg.scenario.time_clause.start,
-                e
-            )
-        })?;
+        let start = parse_timestamp(&wfg.scenario.time_clause.start)
+            .map_err(|e| anyhow::anyhow!("invalid start time: {e}"))?;
         let duration = wfg.scenario.time_clause.duration;
 
         // SC7: only evaluate rules that have inject coverage
@@ -118,7 +156,7 @@ pub(crate) fn run(
             .map(|i| i.rule.clone())
             .collect();
 
-        let expected_result = run_oracle(
+        let mut expected_result = run_oracle(
             &result.events,
             &rule_plans,
             &start,
@@ -126,12 +164,14 @@ pub(crate) fn run(
             Some(&injected_rules),
         )?;
         expected_alert_count = expected_result.alerts.len();
+        let note = limit_oracle(&mut expected_result.alerts, limit);
 
         let expected_file = out.join(format!("{}.except.jsonl", wfg.scenario.name));
         write_oracle_jsonl(&expected_result.alerts, &expected_file)?;
         println!(
-            "Expected: {} alerts -> {}",
+            "Expected: {} alerts{} -> {}",
             expected_result.alerts.len(),
+            note,
             expected_file.display()
         );
 
@@ -153,7 +193,7 @@ pub(crate) fn run(
     let has_faults = wfg.scenario.faults.is_some();
     let output_events = if let Some(faults) = &wfg.scenario.faults {
         let mut fault_rng = StdRng::seed_from_u64(wfg.scenario.seed.wrapping_add(1));
-        let fault_result = apply_faults(result.events, faults, &mut fault_rng);
+        let fault_result = apply_faults(result.events, faults, &schemas, &mut fault_rng);
         eprintln!("Faults applied: {}", fault_result.stats);
         fault_result.events
     } else {
@@ -163,13 +203,8 @@ pub(crate) fn run(
     // Post-fault expected generation (M33 P2): run oracle again on faulted events
     // so verify can compare clean vs faulted outcomes.
     if expected_enabled && has_faults {
-        let start = wfg.scenario.time_clause.start.parse().map_err(|e| {
-            anyhow::anyhow!(
-                "invalid start time '{}': {}",
-                wfg.scenario.time_clause.start,
-                e
-            )
-        })?;
+        let start = parse_timestamp(&wfg.scenario.time_clause.start)
+            .map_err(|e| anyhow::anyhow!("invalid start time: {e}"))?;
         let duration = wfg.scenario.time_clause.duration;
 
         let injected_rules: HashSet<String> = wfg
@@ -179,19 +214,21 @@ pub(crate) fn run(
             .map(|i| i.rule.clone())
             .collect();
 
-        let faulted_expected = run_oracle(
+        let mut faulted_expected = run_oracle(
             &output_events,
             &rule_plans,
             &start,
             &duration,
             Some(&injected_rules),
         )?;
+        let note = limit_oracle(&mut faulted_expected.alerts, limit);
 
         let faulted_expected_file = out.join(format!("{}.faulted-except.jsonl", wfg.scenario.name));
         write_oracle_jsonl(&faulted_expected.alerts, &faulted_expected_file)?;
         println!(
-            "Faulted expected: {} alerts -> {}",
+            "Faulted expected: {} alerts{} -> {}",
             faulted_expected.alerts.len(),
+            note,
             faulted_expected_file.display()
         );
     }
@@ -216,11 +253,29 @@ pub(crate) fn run(
                 output_file.display()
             );
         }
+        "parquet" => {
+            let output_file = out.join(format!("{}.parquet", wfg.scenario.name));
+            write_parquet(&output_events, &output_file, compression)?;
+            println!(
+                "Generated {} events -> {}",
+                output_events.len(),
+                output_file.display()
+            );
+        }
+        "csv" => {
+            let output_file = out.join(format!("{}.csv", wfg.scenario.name));
+            write_csv(&output_events, &schemas, &output_file)?;
+            println!(
+                "Generated {} events -> {}",
+                output_events.len(),
+                output_file.display()
+            );
+        }
         _ => unreachable!(),
     }
 
     if send {
-        let sent_frames = send_events(&output_events, &schemas, &addr)?;
+        let sent_frames = send_events(&output_events, &schemas, &addr, send_opts)?;
         println!(
             "Sent {} events as {} frame(s) -> {}",
             output_events.len(),
@@ -231,3 +286,63 @@ pub(crate) fn run(
 
     Ok(())
 }
+
+fn print_dry_run(scenario: &str, report: &DryRunReport) {
+    println!(
+        "Dry run: scenario '{}', total {} events (nothing generated)",
+        scenario, report.total
+    );
+    println!();
+    println!(
+        "{:<24} {:<24} {:>10} {:>10} {:>10}",
+        "STREAM", "WINDOW", "BUDGET", "INJECT", "BACKGROUND"
+    );
+    for s in &report.streams {
+        println!(
+            "{:<24} {:<24} {:>10} {:>10} {:>10}",
+            s.alias, s.window, s.budget, s.inject, s.background
+        );
+    }
+
+    if !report.injects.is_empty() {
+        println!();
+        println!(
+            "{:<24} {:<10} {:>8} {:>10} {:>10}  STREAMS",
+            "RULE", "MODE", "PERCENT", "CLUSTERS", "EVENTS"
+        );
+        for line in &report.injects {
+            let clusters = line
+                .clusters
+                .map_or_else(|| "-".to_string(), |c| c.to_string());
+            let streams: Vec<String> = line
+                .stream_events
+                .iter()
+                .map(|(alias, n)| format!("{alias}={n}"))
+                .collect();
+            println!(
+                "{:<24} {:<10} {:>7}% {:>10} {:>10}  {}",
+                line.rule,
+                line.mode.to_string(),
+                line.percent,
+                clusters,
+                line.total_events(),
+                streams.join(", ")
+            );
+        }
+    }
+
+    println!();
+    println!("Events: {}", report.event_count());
+    for w in &report.warnings {
+        eprintln!("Warning: {}", w);
+    }
+}
+
+/// Apply `--limit` to oracle output; returns a note for the summary line.
+fn limit_oracle(alerts: &mut Vec<OracleAlert>, limit: Option<usize>) -> String {
+    let total = alerts.len();
+    match limit {
+        Some(n) if truncate_oracle(alerts, n) => format!(" (truncated from {total} by --limit)"),
+        _ => String::new(),
+    }
+}
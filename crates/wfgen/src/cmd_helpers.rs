@@ -1,27 +1,76 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use anyhow::Context;
 
-pub(crate) fn load_ws_files(paths: &[PathBuf]) -> anyhow::Result<Vec<wf_lang::WindowSchema>> {
-    let mut schemas = Vec::new();
-    for path in paths {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("reading .wfs file: {}", path.display()))?;
-        let parsed = wf_lang::parse_wfs(&content)
-            .with_context(|| format!("parsing .wfs file: {}", path.display()))?;
-        schemas.extend(parsed);
+use crate::tcp_send::{RetryPolicy, SendOptions, TlsSettings};
+
+/// Parse a human-friendly duration string (e.g. "200ms", "30s", "2m", "1h")
+/// into `std::time::Duration`. A bare number means seconds.
+pub(crate) fn parse_duration_arg(s: &str) -> anyhow::Result<std::time::Duration> {
+    let s = s.trim();
+    if s.is_empty() {
+        anyhow::bail!("empty duration string");
+    }
+
+    let (num_str, scale) = if let Some(stripped) = s.strip_suffix("ms") {
+        (stripped, 0.001)
+    } else if let Some(stripped) = s.strip_suffix('s') {
+        (stripped, 1.0)
+    } else if let Some(stripped) = s.strip_suffix('m') {
+        (stripped, 60.0)
+    } else if let Some(stripped) = s.strip_suffix('h') {
+        (stripped, 3600.0)
+    } else {
+        // Assume seconds if no suffix
+        (s, 1.0)
+    };
+
+    let value: f64 = num_str
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid duration number: '{}'", num_str))?;
+
+    let secs = value * scale;
+    if !(secs.is_finite() && secs > 0.0) {
+        anyhow::bail!("duration must be positive, got '{}'", s);
+    }
+
+    Ok(std::time::Duration::from_secs_f64(secs))
+}
+
+/// Build sender options from `--connect-retries`, `--retry-backoff`,
+/// `--batch-size` and `--timeout`.
+pub(crate) fn send_options(
+    retries: u32,
+    backoff: &str,
+    batch_size: Option<usize>,
+    timeout: Option<&str>,
+) -> anyhow::Result<SendOptions> {
+    let backoff = parse_duration_arg(backoff).context("parsing --retry-backoff")?;
+    if batch_size == Some(0) {
+        anyhow::bail!("--batch-size must be a positive number of rows");
     }
-    Ok(schemas)
+    let stall_timeout = timeout
+        .map(|t| parse_duration_arg(t).context("parsing --timeout"))
+        .transpose()?;
+    Ok(SendOptions {
+        retry: RetryPolicy { retries, backoff },
+        batch_size,
+        stall_timeout,
+        tls: None,
+    })
 }
 
-pub(crate) fn load_wfl_files(paths: &[PathBuf]) -> anyhow::Result<Vec<wf_lang::ast::WflFile>> {
-    let mut files = Vec::new();
-    for path in paths {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("reading .wfl file: {}", path.display()))?;
-        let parsed = wf_lang::parse_wfl(&content)
-            .with_context(|| format!("parsing .wfl file: {}", path.display()))?;
-        files.push(parsed);
+/// Build client TLS settings from `--tls`, `--tls-ca` and
+/// `--tls-server-name`; `None` when `--tls` is off.
+pub(crate) fn tls_settings(
+    tls: bool,
+    ca: Option<&Path>,
+    server_name: Option<String>,
+) -> anyhow::Result<Option<TlsSettings>> {
+    if !tls {
+        return Ok(None);
     }
-    Ok(files)
+    TlsSettings::load(ca, server_name)
+        .context("loading --tls settings")
+        .map(Some)
 }
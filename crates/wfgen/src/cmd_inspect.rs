@@ -0,0 +1,12 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use wfgen::output::arrow_ipc::inspect_arrow_ipc;
+
+pub(crate) fn run(file: PathBuf, rows: usize) -> anyhow::Result<()> {
+    let inspection =
+        inspect_arrow_ipc(&file, rows).with_context(|| format!("reading {}", file.display()))?;
+    print!("{}", inspection.render());
+    Ok(())
+}
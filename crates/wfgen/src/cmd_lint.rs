@@ -1,24 +1,29 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use anyhow::Context;
-
-use wfgen::loader::load_from_uses;
+use wfgen::loader::compile_project;
 use wfgen::validate::validate_wfg;
-use wfgen::wfg_parser::parse_wfg;
-
-use crate::cmd_helpers::{load_wfl_files, load_ws_files};
-
-pub(crate) fn run(scenario: PathBuf, ws: Vec<PathBuf>, wfl: Vec<PathBuf>) -> anyhow::Result<()> {
-    let wfg_content = std::fs::read_to_string(&scenario).context("reading .wfg file")?;
-    let wfg = parse_wfg(&wfg_content).context("parsing .wfg file")?;
 
-    let (mut schemas, mut wfl_files) = load_from_uses(&wfg, &scenario, &HashMap::new())?;
-    schemas.extend(load_ws_files(&ws)?);
-    wfl_files.extend(load_wfl_files(&wfl)?);
+pub(crate) fn run(
+    scenario: PathBuf,
+    schema: Vec<String>,
+    wfl: Vec<PathBuf>,
+    fail_on_warning: bool,
+) -> anyhow::Result<()> {
+    let project = compile_project(&scenario, &schema, &wfl, &HashMap::new())?;
+    for w in &project.compile_warnings {
+        eprintln!("{w}");
+    }
 
-    let errors = validate_wfg(&wfg, &schemas, &wfl_files);
+    let errors = validate_wfg(&project.wfg, &project.schemas, &project.wfl_files);
     if errors.is_empty() {
+        if fail_on_warning && !project.compile_warnings.is_empty() {
+            eprintln!(
+                "{} warning(s) with --fail-on-warning",
+                project.compile_warnings.len()
+            );
+            std::process::exit(1);
+        }
         println!("OK");
     } else {
         for e in &errors {
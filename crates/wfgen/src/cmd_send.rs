@@ -1,30 +1,58 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::Context;
 
-use wfgen::loader::load_from_uses;
+use wfgen::loader::{load_from_uses, load_schema_files, merge_schemas};
 use wfgen::output::jsonl::read_events_jsonl;
 use wfgen::wfg_parser::parse_wfg;
 
-use crate::cmd_helpers::load_ws_files;
-use crate::tcp_send::send_events;
+use crate::tcp_send::{SendOptions, send_events, send_events_paced};
 
 pub(crate) fn run(
     scenario: PathBuf,
     input: PathBuf,
     addr: String,
-    ws: Vec<PathBuf>,
+    schema: Vec<String>,
+    speed: Option<f64>,
+    send_opts: SendOptions,
 ) -> anyhow::Result<()> {
     let wfg_content = std::fs::read_to_string(&scenario).context("reading .wfg file")?;
     let wfg = parse_wfg(&wfg_content).context("parsing .wfg file")?;
 
     let (mut schemas, _) = load_from_uses(&wfg, &scenario, &HashMap::new())?;
-    schemas.extend(load_ws_files(&ws)?);
+    schemas.extend(load_schema_files(&schema)?);
+    let schemas = merge_schemas(schemas)?;
 
     let events = read_events_jsonl(&input)
         .with_context(|| format!("reading events: {}", input.display()))?;
-    let sent_frames = send_events(&events, &schemas, &addr)?;
+
+    if let Some(speed) = speed {
+        let cancel = Arc::new(AtomicBool::new(false));
+        cancel_on_ctrl_c(Arc::clone(&cancel))?;
+        let sent = send_events_paced(&events, &schemas, &addr, speed, send_opts, &cancel)?;
+        println!(
+            "Sent {}/{} events as {} frame(s) at {}x -> {}{}",
+            sent.events,
+            events.len(),
+            sent.frames,
+            speed,
+            addr,
+            if sent.cancelled { " (cancelled)" } else { "" }
+        );
+        if sent.cancelled {
+            anyhow::bail!(
+                "replay cancelled after {}/{} events",
+                sent.events,
+                events.len()
+            );
+        }
+        return Ok(());
+    }
+
+    let sent_frames = send_events(&events, &schemas, &addr, send_opts)?;
 
     println!(
         "Sent {} events as {} frame(s) -> {}",
@@ -34,3 +62,9 @@ pub(crate) fn run(
     );
     Ok(())
 }
+
+/// Set `cancel` on Ctrl-C so a paced replay stops between sends.
+fn cancel_on_ctrl_c(cancel: Arc<AtomicBool>) -> anyhow::Result<()> {
+    ctrlc::set_handler(move || cancel.store(true, Ordering::Relaxed))
+        .context("installing Ctrl-C handler")
+}
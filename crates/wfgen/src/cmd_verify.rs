@@ -1,18 +1,24 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 
-use wfgen::oracle::OracleTolerances;
+use wfgen::oracle::{OracleAlert, OracleTolerances};
+use wfgen::output::arrow_ipc::read_alerts_arrow;
 use wfgen::output::jsonl::{read_alerts_jsonl, read_oracle_jsonl};
-use wfgen::verify::verify;
+use wfgen::verify::{ActualAlert, truncate_actual, truncate_oracle, verify, verify_strict};
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
-    expected: PathBuf,
+    expected: Option<PathBuf>,
+    expected_from_actual: Option<PathBuf>,
     actual: PathBuf,
     score_tolerance: Option<f64>,
     time_tolerance: Option<f64>,
     meta: Option<PathBuf>,
     format: String,
+    strict_time: bool,
+    actual_format: Option<String>,
+    limit: Option<usize>,
 ) -> anyhow::Result<()> {
     // Load tolerances: CLI flags > meta file > defaults
     let base_tolerances = if let Some(meta_path) = &meta {
@@ -27,22 +33,49 @@ pub(crate) fn run(
     let effective_score_tol = score_tolerance.unwrap_or(base_tolerances.score_tolerance);
     let effective_time_tol = time_tolerance.unwrap_or(base_tolerances.time_tolerance_secs);
 
-    let oracle_alerts = read_oracle_jsonl(&expected)
-        .with_context(|| format!("reading expected: {}", expected.display()))?;
-    let actual_alerts = read_alerts_jsonl(&actual)
+    let mut oracle_alerts = match (&expected, &expected_from_actual) {
+        (_, Some(baseline)) => read_actual_alerts(baseline, None)
+            .with_context(|| format!("reading expected baseline: {}", baseline.display()))?
+            .into_iter()
+            .map(OracleAlert::from)
+            .collect(),
+        (Some(expected), None) => read_oracle_jsonl(expected)
+            .with_context(|| format!("reading expected: {}", expected.display()))?,
+        (None, None) => anyhow::bail!("either --expected or --expected-from-actual is required"),
+    };
+    let mut actual_alerts = read_actual_alerts(&actual, actual_format.as_deref())
         .with_context(|| format!("reading actual: {}", actual.display()))?;
+    let truncated = limit.is_some_and(|n| {
+        // Both sides must be cut; `|` keeps the second call from short-circuiting.
+        truncate_oracle(&mut oracle_alerts, n) | truncate_actual(&mut actual_alerts, n)
+    });
 
-    let report = verify(
-        &oracle_alerts,
-        &actual_alerts,
-        effective_score_tol,
-        effective_time_tol,
-    );
+    let mut report = if strict_time {
+        verify_strict(
+            &oracle_alerts,
+            &actual_alerts,
+            effective_score_tol,
+            effective_time_tol,
+        )?
+    } else {
+        verify(
+            &oracle_alerts,
+            &actual_alerts,
+            effective_score_tol,
+            effective_time_tol,
+        )
+    };
+    if truncated && let Some(n) = limit {
+        report.mark_truncated(n);
+    }
 
     match format.as_str() {
         "markdown" | "md" => {
             println!("{}", report.to_markdown());
         }
+        "junit" => {
+            print!("{}", report.to_junit());
+        }
         _ => {
             let json = serde_json::to_string_pretty(&report)?;
             println!("{}", json);
@@ -55,3 +88,23 @@ pub(crate) fn run(
         std::process::exit(1);
     }
 }
+
+/// Read actual alerts as JSONL or Arrow IPC. Without an explicit format,
+/// `.arrow` / `.ipc` files are read as Arrow and everything else as JSONL.
+fn read_actual_alerts(path: &Path, format: Option<&str>) -> anyhow::Result<Vec<ActualAlert>> {
+    let format = match format {
+        Some(f) => f,
+        None => match path.extension().and_then(|e| e.to_str()) {
+            Some("arrow" | "ipc") => "arrow",
+            _ => "jsonl",
+        },
+    };
+    match format {
+        "jsonl" => read_alerts_jsonl(path),
+        "arrow" | "arrow-ipc" | "ipc" => read_alerts_arrow(path),
+        other => anyhow::bail!(
+            "unsupported actual format: '{}'. Supported: 'jsonl', 'arrow' ('arrow-ipc' alias).",
+            other
+        ),
+    }
+}
@@ -1,5 +1,7 @@
 use rand::Rng;
 use rand::rngs::StdRng;
+use serde_json::Value;
+use wf_lang::{BaseType, FieldType, WindowSchema};
 
 use crate::datagen::stream_gen::GenEvent;
 use crate::wfg_ast::{FaultType, FaultsBlock};
@@ -18,6 +20,8 @@ pub struct FaultStats {
     pub late: usize,
     pub duplicate: usize,
     pub dropped: usize,
+    pub reordered: usize,
+    pub corrupted: usize,
     pub clean: usize,
 }
 
@@ -25,12 +29,21 @@ impl std::fmt::Display for FaultStats {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{} out_of_order, {} late, {} duplicate, {} dropped, {} clean",
-            self.out_of_order, self.late, self.duplicate, self.dropped, self.clean
+            "{} out_of_order, {} late, {} duplicate, {} dropped, {} reordered, {} corrupted, {} clean",
+            self.out_of_order,
+            self.late,
+            self.duplicate,
+            self.dropped,
+            self.reordered,
+            self.corrupted,
+            self.clean
         )
     }
 }
 
+/// Maximum distance (in input positions) a `reorder` fault moves an event.
+pub const REORDER_WINDOW: usize = 4;
+
 /// Fault assignment for a single event.
 #[derive(Clone, Copy, PartialEq)]
 enum Assignment {
@@ -48,7 +61,15 @@ enum Assignment {
 ///
 /// The output represents **arrival order** — it is NOT necessarily sorted by
 /// timestamp.
-pub fn apply_faults(events: Vec<GenEvent>, faults: &FaultsBlock, rng: &mut StdRng) -> FaultResult {
+///
+/// `schemas` drives the type-aware `corrupt` fault; events whose window has
+/// no schema are emitted clean instead of corrupted.
+pub fn apply_faults(
+    events: Vec<GenEvent>,
+    faults: &FaultsBlock,
+    schemas: &[WindowSchema],
+    rng: &mut StdRng,
+) -> FaultResult {
     if events.is_empty() || faults.faults.is_empty() {
         let clean = events.len();
         return FaultResult {
@@ -64,7 +85,7 @@ pub fn apply_faults(events: Vec<GenEvent>, faults: &FaultsBlock, rng: &mut StdRn
     let assignments = assign_faults(events.len(), faults, rng);
 
     // Phase 2: Transform based on assignments
-    two_pass_transform(events, &assignments, rng)
+    two_pass_transform(events, &assignments, schemas, rng)
 }
 
 /// Phase 1: For each event, roll [0, 1) and assign a fault type based on
@@ -93,12 +114,14 @@ fn assign_faults(count: usize, faults: &FaultsBlock, rng: &mut StdRng) -> Vec<As
 
 /// Phase 2: Two-pass transform.
 ///
-/// Pass 1: Build output list handling Clean, Drop, Duplicate, OutOfOrder.
+/// Pass 1: Build output list handling Clean, Drop, Duplicate, OutOfOrder,
+///         Reorder, Corrupt.
 ///         Collect Late events into a deferred list with target offsets.
 /// Pass 2: Insert deferred (late) events at their target positions.
 fn two_pass_transform(
     events: Vec<GenEvent>,
     assignments: &[Assignment],
+    schemas: &[WindowSchema],
     rng: &mut StdRng,
 ) -> FaultResult {
     let n = events.len();
@@ -109,10 +132,15 @@ fn two_pass_transform(
 
     // Track which events have been consumed by an OutOfOrder swap partner
     let mut consumed = vec![false; n];
+    // Reorder: event parked at its partner's position, emitted when reached
+    let mut parked: Vec<Option<GenEvent>> = vec![None; n];
 
     let mut i = 0;
     while i < n {
         if consumed[i] {
+            if let Some(event) = parked[i].take() {
+                result.push(event);
+            }
             i += 1;
             continue;
         }
@@ -145,6 +173,41 @@ fn two_pass_transform(
                     stats.clean += 1;
                 }
             }
+            Assignment::Fault(FaultType::Reorder) => {
+                // Swap with a random partner within the reorder window
+                let max_offset = (n - i - 1).min(REORDER_WINDOW);
+                let partner = if max_offset >= 1 {
+                    Some(i + rng.random_range(1..=max_offset))
+                } else {
+                    None
+                };
+                match partner {
+                    Some(j) if !consumed[j] => {
+                        result.push(events[j].clone());
+                        parked[j] = Some(events[i].clone());
+                        consumed[j] = true;
+                        stats.reordered += 1;
+                        // The partner is moved but not independently faulted
+                        stats.clean += 1;
+                    }
+                    _ => {
+                        // No partner in range or already consumed: degrade to clean
+                        result.push(events[i].clone());
+                        stats.clean += 1;
+                    }
+                }
+            }
+            Assignment::Fault(FaultType::Corrupt) => {
+                let mut event = events[i].clone();
+                let schema = schemas.iter().find(|s| s.name == event.window_name);
+                if schema.is_some_and(|s| corrupt_event(&mut event, s, rng)) {
+                    stats.corrupted += 1;
+                } else {
+                    // No schema or no corruptible field: emit as clean
+                    stats.clean += 1;
+                }
+                result.push(event);
+            }
             Assignment::Fault(FaultType::Late) => {
                 // Defer: insert at a random later position in the output
                 // We record the current output length + a random offset as the
@@ -184,3 +247,59 @@ fn two_pass_transform(
         stats,
     }
 }
+
+/// Mutate one randomly chosen field of `event` according to its schema type.
+///
+/// The time field is left intact so arrival-order faults stay meaningful.
+/// Returns `false` when the schema has no corruptible field.
+fn corrupt_event(event: &mut GenEvent, schema: &WindowSchema, rng: &mut StdRng) -> bool {
+    let candidates: Vec<_> = schema
+        .fields
+        .iter()
+        .filter(|f| schema.time_field.as_deref() != Some(f.name.as_str()))
+        .filter(|f| event.fields.contains_key(&f.name))
+        .collect();
+    if candidates.is_empty() {
+        return false;
+    }
+    let field = candidates[rng.random_range(0..candidates.len())];
+    let Some(value) = event.fields.get_mut(&field.name) else {
+        return false;
+    };
+
+    // One in four corruptions nulls the field regardless of type
+    if rng.random_range(0..4) == 0 {
+        *value = Value::Null;
+        return true;
+    }
+    *value = match &field.field_type {
+        FieldType::Base(BaseType::Bool) => match value {
+            Value::Bool(b) => Value::Bool(!*b),
+            _ => Value::Null,
+        },
+        FieldType::Base(BaseType::Chars | BaseType::Ip | BaseType::Hex) => match value {
+            Value::String(s) if !s.is_empty() => {
+                let keep = rng.random_range(0..s.chars().count());
+                Value::String(s.chars().take(keep).collect())
+            }
+            _ => Value::Null,
+        },
+        FieldType::Base(BaseType::Digit) => {
+            if rng.random() {
+                Value::from(i64::MAX)
+            } else {
+                Value::from(i64::MIN)
+            }
+        }
+        FieldType::Base(BaseType::Float) => {
+            if rng.random() {
+                Value::from(f64::MAX)
+            } else {
+                Value::from(f64::MIN)
+            }
+        }
+        FieldType::Base(BaseType::Time) => Value::String("not-a-time".to_string()),
+        FieldType::Array(_) => Value::Null,
+    };
+    true
+}
@@ -1,6 +1,11 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
 use rand::Rng;
 use rand::rngs::StdRng;
-use wf_lang::FieldType;
+use serde_json::Value;
+use wf_lang::{FieldType, WindowSchema};
 
 use crate::wfg_ast::{GenArg, GenExpr};
 
@@ -11,11 +16,116 @@ pub fn generate_field_value(
     rng: &mut StdRng,
 ) -> serde_json::Value {
     match override_expr {
-        Some(expr) => generate_from_expr(expr, rng),
+        Some(expr) => generate_override(field_type, expr, &serde_json::Map::new(), rng),
         None => generate_default(field_type, rng),
     }
 }
 
+fn generate_override(
+    field_type: &FieldType,
+    expr: &GenExpr,
+    fields: &serde_json::Map<String, Value>,
+    rng: &mut StdRng,
+) -> Value {
+    match expr {
+        GenExpr::GenFunc { name, args } if name == "pool" => generate_pool(field_type, args, rng),
+        _ => generate_from_expr(expr, fields, rng),
+    }
+}
+
+/// Names of the fields an expression reads, in order of appearance.
+pub fn expr_field_refs(expr: &GenExpr) -> Vec<&str> {
+    let mut refs = Vec::new();
+    collect_field_refs(expr, &mut refs);
+    refs
+}
+
+fn collect_field_refs<'a>(expr: &'a GenExpr, refs: &mut Vec<&'a str>) {
+    match expr {
+        GenExpr::FieldRef(name) => refs.push(name),
+        GenExpr::GenFunc { args, .. } => {
+            for arg in args {
+                collect_field_refs(&arg.value, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Order the derived overrides (those reading other fields) so that every
+/// field comes after the derived fields it depends on.
+///
+/// Fields on a reference cycle are left out; `validate_wfg` reports them.
+pub fn derived_order<'a>(overrides: &HashMap<&'a str, &'a GenExpr>) -> Vec<&'a str> {
+    let mut names: Vec<&str> = overrides
+        .iter()
+        .filter(|(_, expr)| !expr_field_refs(expr).is_empty())
+        .map(|(name, _)| *name)
+        .collect();
+    names.sort_unstable();
+
+    // 0 = unvisited, 1 = on the DFS stack, 2 = done, 3 = on or behind a cycle
+    let mut state: HashMap<&str, u8> = HashMap::new();
+    let mut order = Vec::new();
+    for name in &names {
+        visit_derived(name, overrides, &mut state, &mut order);
+    }
+    order
+}
+
+fn visit_derived<'a>(
+    name: &'a str,
+    overrides: &HashMap<&'a str, &'a GenExpr>,
+    state: &mut HashMap<&'a str, u8>,
+    order: &mut Vec<&'a str>,
+) -> bool {
+    match state.get(name).copied().unwrap_or(0) {
+        1 | 3 => {
+            state.insert(name, 3);
+            return false;
+        }
+        2 => return true,
+        _ => {}
+    }
+    let Some(expr) = overrides.get(name) else {
+        // Plain schema field: generated before any derived field.
+        return true;
+    };
+    state.insert(name, 1);
+    let mut ok = true;
+    for dep in expr_field_refs(expr) {
+        ok &= visit_derived(dep, overrides, state, order);
+    }
+    if ok && state.get(name) == Some(&1) {
+        state.insert(name, 2);
+        order.push(name);
+        true
+    } else {
+        state.insert(name, 3);
+        false
+    }
+}
+
+/// Evaluate derived fields in `order` against the already generated `fields`.
+pub fn fill_derived_fields(
+    schema: &WindowSchema,
+    overrides: &HashMap<&str, &GenExpr>,
+    order: &[&str],
+    fields: &mut serde_json::Map<String, Value>,
+    rng: &mut StdRng,
+) {
+    for name in order {
+        let (Some(field_def), Some(expr)) = (
+            schema.fields.iter().find(|f| f.name == *name),
+            overrides.get(name),
+        ) else {
+            continue;
+        };
+        let value = generate_override(&field_def.field_type, expr, fields, rng);
+        fields.insert(field_def.name.clone(), value);
+    }
+}
+
 /// Generate a default random value for a field type.
 fn generate_default(field_type: &FieldType, rng: &mut StdRng) -> serde_json::Value {
     let base = match field_type {
@@ -88,13 +198,122 @@ fn generate_default_base(base: &wf_lang::BaseType, rng: &mut StdRng) -> serde_js
     }
 }
 
-/// Generate a value from a GenExpr.
-fn generate_from_expr(expr: &GenExpr, rng: &mut StdRng) -> serde_json::Value {
+/// Generate a value from a GenExpr; field references read from `fields`.
+fn generate_from_expr(
+    expr: &GenExpr,
+    fields: &serde_json::Map<String, Value>,
+    rng: &mut StdRng,
+) -> serde_json::Value {
     match expr {
         GenExpr::StringLit(s) => serde_json::Value::String(s.clone()),
         GenExpr::NumberLit(n) => serde_json::json!(n),
         GenExpr::BoolLit(b) => serde_json::Value::Bool(*b),
-        GenExpr::GenFunc { name, args } => dispatch_gen_func(name, args, rng),
+        GenExpr::FieldRef(name) => fields.get(name).cloned().unwrap_or(Value::Null),
+        GenExpr::GenFunc { name, args } => dispatch_gen_func(name, args, fields, rng),
+    }
+}
+
+/// Render a value as it appears inside a concatenated string.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// 64-bit FNV-1a, stable across platforms and releases.
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// `pool(n[, skew: s][, prefix: "p"])`: draw from a bounded set of `n`
+/// entities. With `skew > 0` member `k` is picked with weight `1/(k+1)^skew`
+/// (Zipf-like), so a few entities dominate; otherwise the choice is uniform.
+///
+/// Members are rendered from their index according to the field type, so
+/// the same index always yields the same value and the set is seed-stable.
+fn generate_pool(field_type: &FieldType, args: &[GenArg], rng: &mut StdRng) -> serde_json::Value {
+    let size = match resolve_arg(args, "size", 0) {
+        Some(GenExpr::NumberLit(n)) if *n >= 1.0 => *n as u64,
+        _ => return serde_json::Value::Null,
+    };
+    let skew = match resolve_arg(args, "skew", 1) {
+        Some(GenExpr::NumberLit(s)) if *s > 0.0 => *s,
+        _ => 0.0,
+    };
+    let prefix = match resolve_arg(args, "prefix", 2) {
+        Some(GenExpr::StringLit(p)) => p.as_str(),
+        _ => "id",
+    };
+
+    let idx = if skew > 0.0 {
+        weighted_index(size, skew, rng)
+    } else {
+        rng.random_range(0..size)
+    };
+
+    match field_type {
+        FieldType::Base(b) => pool_member(b, idx, prefix),
+        FieldType::Array(b) => serde_json::Value::Array(vec![pool_member(b, idx, prefix)]),
+    }
+}
+
+thread_local! {
+    /// Cumulative pool weights by `(size, skew bits)`, built once per pool
+    /// spec rather than on every draw.
+    static POOL_WEIGHTS: RefCell<HashMap<(u64, u64), Rc<[f64]>>> = RefCell::default();
+}
+
+/// Draw a pool index with weight `1/(k+1)^skew` by binary search over the
+/// cached cumulative weights.
+fn weighted_index(size: u64, skew: f64, rng: &mut StdRng) -> u64 {
+    let cumulative = POOL_WEIGHTS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let table = cache
+            .entry((size, skew.to_bits()))
+            .or_insert_with(|| cumulative_weights(size, skew));
+        Rc::clone(table)
+    });
+    let total = cumulative[cumulative.len() - 1];
+    let target = rng.random_range(0.0..total);
+    let idx = cumulative.partition_point(|&sum| sum <= target);
+    (idx as u64).min(size - 1)
+}
+
+fn cumulative_weights(size: u64, skew: f64) -> Rc<[f64]> {
+    (0..size)
+        .scan(0.0, |sum, k| {
+            *sum += 1.0 / ((k + 1) as f64).powf(skew);
+            Some(*sum)
+        })
+        .collect()
+}
+
+fn pool_member(base: &wf_lang::BaseType, idx: u64, prefix: &str) -> serde_json::Value {
+    use wf_lang::BaseType;
+    match base {
+        BaseType::Chars => serde_json::Value::String(format!("{prefix}_{idx}")),
+        BaseType::Digit => serde_json::Value::Number(serde_json::Number::from(idx)),
+        BaseType::Float => serde_json::json!(idx as f64),
+        BaseType::Bool => serde_json::Value::Bool(idx.is_multiple_of(2)),
+        BaseType::Ip => {
+            let a = (idx >> 16) & 0xFF;
+            let b = (idx >> 8) & 0xFF;
+            let c = idx & 0xFF;
+            serde_json::Value::String(format!("10.{a}.{b}.{c}"))
+        }
+        BaseType::Hex => {
+            // Spread consecutive indices across the hex space (splitmix64).
+            let mut z = idx.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            serde_json::Value::String(format!("{z:016x}"))
+        }
+        BaseType::Time => serde_json::Value::String("1970-01-01T00:00:00Z".to_string()),
     }
 }
 
@@ -111,8 +330,27 @@ fn resolve_arg<'a>(args: &'a [GenArg], name: &str, index: usize) -> Option<&'a G
 }
 
 /// Dispatch a gen function call.
-fn dispatch_gen_func(name: &str, args: &[GenArg], rng: &mut StdRng) -> serde_json::Value {
+fn dispatch_gen_func(
+    name: &str,
+    args: &[GenArg],
+    fields: &serde_json::Map<String, Value>,
+    rng: &mut StdRng,
+) -> serde_json::Value {
     match name {
+        "concat" => {
+            let text: String = args
+                .iter()
+                .map(|a| value_text(&generate_from_expr(&a.value, fields, rng)))
+                .collect();
+            serde_json::Value::String(text)
+        }
+        "hash" => {
+            let source = match resolve_arg(args, "of", 0) {
+                Some(expr) => value_text(&generate_from_expr(expr, fields, rng)),
+                None => String::new(),
+            };
+            serde_json::Value::String(format!("{:016x}", fnv1a64(source.as_bytes())))
+        }
         "ipv4" => {
             let pool = match resolve_arg(args, "pool", 0) {
                 Some(GenExpr::NumberLit(n)) => *n as u32,
@@ -150,7 +388,7 @@ fn dispatch_gen_func(name: &str, args: &[GenArg], rng: &mut StdRng) -> serde_jso
                 return serde_json::Value::Null;
             }
             let idx = rng.random_range(0..args.len());
-            generate_from_expr(&args[idx].value, rng)
+            generate_from_expr(&args[idx].value, fields, rng)
         }
         "range" => {
             let min = match resolve_arg(args, "min", 0) {
@@ -7,10 +7,11 @@ use wf_lang::WindowSchema;
 use wf_lang::plan::RulePlan;
 
 use super::extract::extract_inject_overrides;
-use super::hit::generate_hit_clusters;
-use super::near_miss::generate_near_miss_clusters;
-use super::non_hit::generate_non_hit_events;
-use super::structures::{AliasMap, RuleStructure};
+use super::hit::{effective_steps, generate_hit_clusters, plan_hit_counts};
+use super::near_miss::{generate_near_miss_clusters, plan_near_miss_counts};
+use super::non_hit::{generate_non_hit_events, plan_non_hit_counts};
+use super::replay::{generate_replay_clusters, plan_replay_counts};
+use super::structures::{AliasMap, LineCounts, RuleStructure};
 use crate::datagen::stream_gen::GenEvent;
 use crate::wfg_ast::{InjectLine, InjectMode, StreamBlock};
 
@@ -150,5 +151,41 @@ pub(super) fn generate_for_line(
             rng,
             inject_counts,
         ),
+        InjectMode::Replay => generate_replay_clusters(
+            inject_line.percent,
+            rule_struct,
+            stream_totals,
+            schemas,
+            scenario_streams,
+            start,
+            duration,
+            rng,
+            inject_counts,
+            &overrides,
+        ),
+    }
+}
+
+/// Event counts `generate_for_line` would produce, without generating.
+pub(super) fn plan_for_line(
+    inject_line: &InjectLine,
+    rule_struct: &RuleStructure,
+    stream_totals: &HashMap<String, u64>,
+) -> LineCounts {
+    let overrides = extract_inject_overrides(inject_line);
+
+    match inject_line.mode {
+        InjectMode::Hit => plan_hit_counts(
+            inject_line.percent,
+            &effective_steps(rule_struct, &overrides),
+            stream_totals,
+        ),
+        InjectMode::NearMiss => {
+            plan_near_miss_counts(inject_line.percent, rule_struct, stream_totals, &overrides)
+        }
+        InjectMode::NonHit => plan_non_hit_counts(inject_line.percent, rule_struct, stream_totals),
+        InjectMode::Replay => {
+            plan_replay_counts(inject_line.percent, rule_struct, stream_totals, &overrides)
+        }
     }
 }
@@ -12,7 +12,8 @@ pub(super) fn extract_rule_structure(
     alias_map: &AliasMap,
 ) -> anyhow::Result<RuleStructure> {
     let window_dur = match rule_plan.match_plan.window_spec {
-        WindowSpec::Sliding(d) | WindowSpec::Fixed(d) | WindowSpec::Session(d) => d,
+        WindowSpec::Sliding(d) | WindowSpec::Fixed(d) => d,
+        WindowSpec::Session(spec) => spec.gap,
     };
 
     let keys: Vec<String> = rule_plan
@@ -102,6 +103,7 @@ pub(super) fn extract_inject_overrides(inject_line: &InjectLine) -> InjectOverri
         count_per_entity: None,
         steps_completed: None,
         within: None,
+        copies: None,
     };
 
     for param in &inject_line.params {
@@ -116,6 +118,11 @@ pub(super) fn extract_inject_overrides(inject_line: &InjectLine) -> InjectOverri
                     overrides.steps_completed = Some(*n as usize);
                 }
             }
+            "copies" => {
+                if let ParamValue::Number(n) = &param.value {
+                    overrides.copies = Some(*n as u64);
+                }
+            }
             "within" => {
                 if let ParamValue::Duration(d) = &param.value {
                     overrides.within = Some(*d);
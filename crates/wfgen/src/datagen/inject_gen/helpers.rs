@@ -6,7 +6,7 @@ use rand::rngs::StdRng;
 use wf_lang::{BaseType, FieldType, WindowSchema};
 
 use super::structures::{InjectOverrides, StepInfo};
-use crate::datagen::field_gen::generate_field_value;
+use crate::datagen::field_gen::{derived_order, fill_derived_fields, generate_field_value};
 use crate::datagen::stream_gen::GenEvent;
 use crate::wfg_ast::StreamBlock;
 
@@ -165,6 +165,11 @@ pub(super) fn build_event_fields(
     rng: &mut StdRng,
 ) -> serde_json::Map<String, serde_json::Value> {
     let mut fields = serde_json::Map::new();
+    // Key and filter overrides win over derived stream overrides.
+    let derived: Vec<&str> = derived_order(overrides_map)
+        .into_iter()
+        .filter(|n| !key_overrides.contains_key(*n) && !filter_overrides.contains_key(*n))
+        .collect();
 
     for field_def in &schema.fields {
         // 1. Key field override (highest priority)
@@ -193,11 +198,18 @@ pub(super) fn build_event_fields(
             }
         }
 
-        // 4. Normal field with possible stream override
+        // 4. Derived field: filled in once the fields it reads exist
+        if derived.contains(&field_def.name.as_str()) {
+            fields.insert(field_def.name.clone(), serde_json::Value::Null);
+            continue;
+        }
+
+        // 5. Normal field with possible stream override
         let override_expr = overrides_map.get(field_def.name.as_str()).copied();
         let value = generate_field_value(&field_def.field_type, override_expr, rng);
         fields.insert(field_def.name.clone(), value);
     }
+    fill_derived_fields(schema, overrides_map, &derived, &mut fields, rng);
 
     fields
 }
@@ -9,7 +9,7 @@ use wf_lang::WindowSchema;
 use super::helpers::{
     compute_cluster_count, compute_window_bounds, generate_cluster_events, generate_key_values,
 };
-use super::structures::{InjectOverrides, RuleStructure, StepInfo};
+use super::structures::{InjectOverrides, LineCounts, RuleStructure, StepInfo};
 use crate::datagen::stream_gen::GenEvent;
 use crate::wfg_ast::StreamBlock;
 
@@ -26,35 +26,15 @@ pub(super) fn generate_hit_clusters(
     inject_counts: &mut HashMap<String, u64>,
     overrides: &InjectOverrides,
 ) -> anyhow::Result<Vec<GenEvent>> {
-    // Apply count_per_entity override: use overridden threshold for cluster sizing
-    let effective_steps: Vec<StepInfo> = if let Some(cpe) = overrides.count_per_entity {
-        rule_struct
-            .steps
-            .iter()
-            .map(|s| StepInfo {
-                bind_alias: s.bind_alias.clone(),
-                scenario_alias: s.scenario_alias.clone(),
-                window_name: s.window_name.clone(),
-                measure: s.measure,
-                threshold: cpe, // override
-                filter_overrides: s.filter_overrides.clone(),
-            })
-            .collect()
-    } else {
-        rule_struct.steps.clone()
-    };
-
-    let num_clusters = compute_cluster_count(percent, &effective_steps, stream_totals);
+    let effective_steps = effective_steps(rule_struct, overrides);
+    let counts = plan_hit_counts(percent, &effective_steps, stream_totals);
+    let num_clusters = counts.clusters.unwrap_or(0);
     if num_clusters == 0 {
         return Ok(Vec::new());
     }
 
     // Update inject counts
-    for step in &effective_steps {
-        *inject_counts
-            .entry(step.scenario_alias.clone())
-            .or_insert(0) += step.threshold * num_clusters;
-    }
+    counts.add_to(inject_counts);
 
     let dur_secs = duration.as_secs_f64();
     let window_dur = overrides.within.unwrap_or(rule_struct.window_dur);
@@ -93,3 +73,37 @@ pub(super) fn generate_hit_clusters(
 
     Ok(events)
 }
+
+/// Rule steps with the `count_per_entity` override applied to thresholds.
+pub(super) fn effective_steps(
+    rule_struct: &RuleStructure,
+    overrides: &InjectOverrides,
+) -> Vec<StepInfo> {
+    match overrides.count_per_entity {
+        Some(cpe) => rule_struct
+            .steps
+            .iter()
+            .map(|s| StepInfo {
+                threshold: cpe,
+                ..s.clone()
+            })
+            .collect(),
+        None => rule_struct.steps.clone(),
+    }
+}
+
+/// Cluster count and per-step events for a hit line.
+pub(super) fn plan_hit_counts(
+    percent: f64,
+    effective_steps: &[StepInfo],
+    stream_totals: &HashMap<String, u64>,
+) -> LineCounts {
+    let clusters = compute_cluster_count(percent, effective_steps, stream_totals);
+    LineCounts {
+        clusters: Some(clusters),
+        per_step: effective_steps
+            .iter()
+            .map(|step| (step.scenario_alias.clone(), step.threshold * clusters))
+            .collect(),
+    }
+}
@@ -4,6 +4,7 @@ mod helpers;
 mod hit;
 mod near_miss;
 mod non_hit;
+mod replay;
 mod structures;
 
 use std::collections::HashMap;
@@ -18,12 +19,12 @@ use crate::wfg_ast::WfgFile;
 
 use dispatch::{build_alias_map, compute_stream_totals};
 use extract::extract_rule_structure;
-pub use structures::InjectGenResult;
+pub use structures::{InjectGenResult, InjectLinePlan};
 
 /// Generate inject events driven by rule plans.
 ///
 /// For each inject block in the scenario, generates hit / near-miss / non-hit
-/// event clusters according to the rule's structure and thresholds.
+/// / replay event clusters according to the rule's structure and thresholds.
 pub fn generate_inject_events(
     wfg: &WfgFile,
     rule_plans: &[RulePlan],
@@ -66,6 +67,37 @@ pub fn generate_inject_events(
     })
 }
 
+/// Plan inject sizes without generating events: one entry per inject line,
+/// in scenario order, using the same budget math as
+/// [`generate_inject_events`].
+pub fn plan_inject_lines(
+    wfg: &WfgFile,
+    rule_plans: &[RulePlan],
+) -> anyhow::Result<Vec<InjectLinePlan>> {
+    let scenario = &wfg.scenario;
+    let stream_totals = compute_stream_totals(scenario);
+
+    let mut plans = Vec::new();
+    for inject_block in &scenario.injects {
+        let rule_plan = resolve_rule_plan(&inject_block.rule, rule_plans)?;
+
+        let alias_map = build_alias_map(&inject_block.streams, &scenario.streams, rule_plan)?;
+        let rule_struct = extract_rule_structure(rule_plan, &alias_map)?;
+
+        for inject_line in &inject_block.lines {
+            let counts = dispatch::plan_for_line(inject_line, &rule_struct, &stream_totals);
+            plans.push(InjectLinePlan {
+                rule: rule_plan.name.clone(),
+                mode: inject_line.mode,
+                percent: inject_line.percent,
+                clusters: counts.clusters,
+                stream_events: counts.per_step,
+            });
+        }
+    }
+    Ok(plans)
+}
+
 fn resolve_rule_plan<'a>(
     inject_rule: &str,
     rule_plans: &'a [RulePlan],
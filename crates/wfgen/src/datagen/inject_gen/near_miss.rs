@@ -9,7 +9,7 @@ use wf_lang::WindowSchema;
 use super::helpers::{
     compute_near_miss_counts, compute_window_bounds, generate_cluster_events, generate_key_values,
 };
-use super::structures::{InjectOverrides, RuleStructure};
+use super::structures::{InjectOverrides, LineCounts, RuleStructure};
 use crate::datagen::stream_gen::GenEvent;
 use crate::wfg_ast::StreamBlock;
 
@@ -27,41 +27,15 @@ pub(super) fn generate_near_miss_clusters(
     overrides: &InjectOverrides,
 ) -> anyhow::Result<Vec<GenEvent>> {
     let steps = &rule_struct.steps;
-    if steps.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let near_miss_counts = compute_near_miss_counts(steps, overrides);
-
-    // Total events per cluster
-    let events_per_cluster: u64 = near_miss_counts.iter().sum();
-    if events_per_cluster == 0 {
-        return Ok(Vec::new());
-    }
-
-    // Compute number of clusters from the near-miss step's budget
-    let nm_step_idx = overrides
-        .steps_completed
-        .unwrap_or(steps.len() - 1)
-        .min(steps.len() - 1);
-    let primary_step = &steps[nm_step_idx];
-    let stream_total = *stream_totals
-        .get(&primary_step.scenario_alias)
-        .unwrap_or(&0);
-    let budget = (stream_total as f64 * percent / 100.0).round() as u64;
-    let nm_count = near_miss_counts[nm_step_idx];
-    let num_clusters = if nm_count > 0 { budget / nm_count } else { 0 };
-
+    let counts = plan_near_miss_counts(percent, rule_struct, stream_totals, overrides);
+    let num_clusters = counts.clusters.unwrap_or(0);
     if num_clusters == 0 {
         return Ok(Vec::new());
     }
+    let near_miss_counts = compute_near_miss_counts(steps, overrides);
 
     // Update inject counts
-    for (i, step) in steps.iter().enumerate() {
-        *inject_counts
-            .entry(step.scenario_alias.clone())
-            .or_insert(0) += near_miss_counts[i] * num_clusters;
-    }
+    counts.add_to(inject_counts);
 
     let dur_secs = duration.as_secs_f64();
     let window_dur = overrides.within.unwrap_or(rule_struct.window_dur);
@@ -101,3 +75,53 @@ pub(super) fn generate_near_miss_clusters(
 
     Ok(events)
 }
+
+/// Cluster count and per-step events for a near-miss line.
+pub(super) fn plan_near_miss_counts(
+    percent: f64,
+    rule_struct: &RuleStructure,
+    stream_totals: &HashMap<String, u64>,
+    overrides: &InjectOverrides,
+) -> LineCounts {
+    let steps = &rule_struct.steps;
+    let empty = LineCounts {
+        clusters: Some(0),
+        per_step: steps
+            .iter()
+            .map(|s| (s.scenario_alias.clone(), 0))
+            .collect(),
+    };
+    if steps.is_empty() {
+        return empty;
+    }
+
+    let near_miss_counts = compute_near_miss_counts(steps, overrides);
+
+    // Total events per cluster
+    let events_per_cluster: u64 = near_miss_counts.iter().sum();
+    if events_per_cluster == 0 {
+        return empty;
+    }
+
+    // Compute number of clusters from the near-miss step's budget
+    let nm_step_idx = overrides
+        .steps_completed
+        .unwrap_or(steps.len() - 1)
+        .min(steps.len() - 1);
+    let primary_step = &steps[nm_step_idx];
+    let stream_total = *stream_totals
+        .get(&primary_step.scenario_alias)
+        .unwrap_or(&0);
+    let budget = (stream_total as f64 * percent / 100.0).round() as u64;
+    let nm_count = near_miss_counts[nm_step_idx];
+    let num_clusters = if nm_count > 0 { budget / nm_count } else { 0 };
+
+    LineCounts {
+        clusters: Some(num_clusters),
+        per_step: steps
+            .iter()
+            .zip(&near_miss_counts)
+            .map(|(step, n)| (step.scenario_alias.clone(), n * num_clusters))
+            .collect(),
+    }
+}
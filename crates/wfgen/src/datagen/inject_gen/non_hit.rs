@@ -6,7 +6,7 @@ use rand::rngs::StdRng;
 use wf_lang::WindowSchema;
 
 use super::helpers::{build_event_fields, generate_key_values};
-use super::structures::RuleStructure;
+use super::structures::{LineCounts, RuleStructure};
 use crate::datagen::stream_gen::GenEvent;
 use crate::wfg_ast::StreamBlock;
 
@@ -29,18 +29,15 @@ pub(super) fn generate_non_hit_events(
     // Generate events on each participating stream.
     let mut entity_counter: u64 = 1_000_000; // offset to avoid collision with hit/nm
 
-    for step in &rule_struct.steps {
-        let stream_total = *stream_totals.get(&step.scenario_alias).unwrap_or(&0);
-        let event_count = (stream_total as f64 * percent / 100.0).round() as u64;
+    let counts = plan_non_hit_counts(percent, rule_struct, stream_totals);
+    counts.add_to(inject_counts);
 
+    for (step, (_, event_count)) in rule_struct.steps.iter().zip(&counts.per_step) {
+        let event_count = *event_count;
         if event_count == 0 {
             continue;
         }
 
-        *inject_counts
-            .entry(step.scenario_alias.clone())
-            .or_insert(0) += event_count;
-
         let schema = schemas
             .iter()
             .find(|s| s.name == step.window_name)
@@ -102,3 +99,23 @@ pub(super) fn generate_non_hit_events(
 
     Ok(events)
 }
+
+/// Per-step events for a non-hit line.
+pub(super) fn plan_non_hit_counts(
+    percent: f64,
+    rule_struct: &RuleStructure,
+    stream_totals: &HashMap<String, u64>,
+) -> LineCounts {
+    LineCounts {
+        clusters: None,
+        per_step: rule_struct
+            .steps
+            .iter()
+            .map(|step| {
+                let stream_total = *stream_totals.get(&step.scenario_alias).unwrap_or(&0);
+                let event_count = (stream_total as f64 * percent / 100.0).round() as u64;
+                (step.scenario_alias.clone(), event_count)
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use rand::rngs::StdRng;
+use wf_lang::WindowSchema;
+
+use super::helpers::{compute_window_bounds, generate_cluster_events, generate_key_values};
+use super::hit::effective_steps;
+use super::structures::{InjectOverrides, LineCounts, RuleStructure, StepInfo};
+use crate::datagen::stream_gen::GenEvent;
+use crate::wfg_ast::StreamBlock;
+
+/// Copies per event when a replay line does not set `copies=`.
+const DEFAULT_REPLAY_COPIES: u64 = 2;
+
+/// Generate hit-shaped clusters whose events are each emitted `copies` times
+/// with identical fields and timestamps, simulating at-least-once delivery.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn generate_replay_clusters(
+    percent: f64,
+    rule_struct: &RuleStructure,
+    stream_totals: &HashMap<String, u64>,
+    schemas: &[WindowSchema],
+    scenario_streams: &[StreamBlock],
+    start: &DateTime<Utc>,
+    duration: &Duration,
+    rng: &mut StdRng,
+    inject_counts: &mut HashMap<String, u64>,
+    overrides: &InjectOverrides,
+) -> anyhow::Result<Vec<GenEvent>> {
+    let steps = effective_steps(rule_struct, overrides);
+    let copies = replay_copies(overrides);
+    let counts = plan_replay_counts(percent, rule_struct, stream_totals, overrides);
+    let num_clusters = counts.clusters.unwrap_or(0);
+    if num_clusters == 0 {
+        return Ok(Vec::new());
+    }
+
+    counts.add_to(inject_counts);
+
+    let dur_secs = duration.as_secs_f64();
+    let window_dur = overrides.within.unwrap_or(rule_struct.window_dur);
+    let (window_secs, max_start_offset) = compute_window_bounds(dur_secs, window_dur);
+
+    let mut events = Vec::new();
+    let mut originals = Vec::new();
+
+    for (entity_counter, _cluster_idx) in (0_u64..).zip(0..num_clusters) {
+        let key_overrides =
+            generate_key_values(&rule_struct.keys, entity_counter, "replay", schemas, &steps);
+
+        let cluster_start_secs = if max_start_offset > 0.0 {
+            rng.random_range(0.0..max_start_offset)
+        } else {
+            0.0
+        };
+
+        originals.clear();
+        generate_cluster_events(
+            &steps,
+            |_idx, step| step.threshold,
+            &key_overrides,
+            cluster_start_secs,
+            window_secs,
+            schemas,
+            scenario_streams,
+            start,
+            rng,
+            &mut originals,
+        )?;
+
+        for event in &originals {
+            events.extend(std::iter::repeat_n(event, copies as usize).cloned());
+        }
+    }
+
+    Ok(events)
+}
+
+/// Cluster count and per-step events for a replay line.
+///
+/// Each cluster carries `threshold × copies` events per step, so the budget
+/// covers the duplicates as well as the originals.
+pub(super) fn plan_replay_counts(
+    percent: f64,
+    rule_struct: &RuleStructure,
+    stream_totals: &HashMap<String, u64>,
+    overrides: &InjectOverrides,
+) -> LineCounts {
+    let copies = replay_copies(overrides);
+    let steps = effective_steps(rule_struct, overrides);
+    let clusters = replay_cluster_count(percent, &steps, copies, stream_totals);
+    LineCounts {
+        clusters: Some(clusters),
+        per_step: steps
+            .iter()
+            .map(|step| {
+                (
+                    step.scenario_alias.clone(),
+                    step.threshold * copies * clusters,
+                )
+            })
+            .collect(),
+    }
+}
+
+fn replay_copies(overrides: &InjectOverrides) -> u64 {
+    overrides.copies.unwrap_or(DEFAULT_REPLAY_COPIES).max(1)
+}
+
+fn replay_cluster_count(
+    percent: f64,
+    steps: &[StepInfo],
+    copies: u64,
+    stream_totals: &HashMap<String, u64>,
+) -> u64 {
+    steps
+        .iter()
+        .filter(|step| step.threshold > 0)
+        .map(|step| {
+            let stream_total = *stream_totals.get(&step.scenario_alias).unwrap_or(&0);
+            let budget = (stream_total as f64 * percent / 100.0).round() as u64;
+            budget / (step.threshold * copies)
+        })
+        .min()
+        .unwrap_or(0)
+}
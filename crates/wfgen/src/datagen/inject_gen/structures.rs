@@ -4,6 +4,7 @@ use std::time::Duration;
 use wf_lang::ast::Measure;
 
 use crate::datagen::stream_gen::GenEvent;
+use crate::wfg_ast::InjectMode;
 
 /// Result of inject event generation.
 pub struct InjectGenResult {
@@ -12,6 +13,42 @@ pub struct InjectGenResult {
     pub inject_counts: HashMap<String, u64>,
 }
 
+/// Planned size of one inject line (see [`super::plan_inject_lines`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InjectLinePlan {
+    /// Resolved rule name.
+    pub rule: String,
+    pub mode: InjectMode,
+    pub percent: f64,
+    /// Entity clusters (hit / near-miss / replay); `None` for non-hit lines.
+    pub clusters: Option<u64>,
+    /// `(scenario_alias, events)` per rule step, in step order.
+    pub stream_events: Vec<(String, u64)>,
+}
+
+impl InjectLinePlan {
+    pub fn total_events(&self) -> u64 {
+        self.stream_events.iter().map(|(_, n)| n).sum()
+    }
+}
+
+/// Per-step event counts for one inject line, computed before any event is
+/// generated.
+pub(super) struct LineCounts {
+    /// Entity clusters (hit / near-miss / replay); `None` for non-hit lines.
+    pub(super) clusters: Option<u64>,
+    /// `(scenario_alias, events)` per rule step, in step order.
+    pub(super) per_step: Vec<(String, u64)>,
+}
+
+impl LineCounts {
+    pub(super) fn add_to(&self, inject_counts: &mut HashMap<String, u64>) {
+        for (alias, n) in &self.per_step {
+            *inject_counts.entry(alias.clone()).or_insert(0) += n;
+        }
+    }
+}
+
 /// Extracted rule structure for inject generation.
 #[allow(dead_code)]
 pub(super) struct RuleStructure {
@@ -49,4 +86,6 @@ pub(super) struct InjectOverrides {
     pub(super) steps_completed: Option<usize>,
     /// Override the window duration for cluster time distribution.
     pub(super) within: Option<Duration>,
+    /// For replay: how many times each cluster event is emitted.
+    pub(super) copies: Option<u64>,
 }
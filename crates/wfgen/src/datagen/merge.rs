@@ -0,0 +1,91 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+
+use super::stream_gen::{GenEvent, StreamEventGen};
+
+/// One timestamp-sorted input of the merge.
+pub(super) enum EventSource<'a> {
+    /// Pre-generated events (inject clusters), already sorted.
+    Buffered(std::vec::IntoIter<GenEvent>),
+    /// A background stream generated one event at a time.
+    Lazy(Box<LazySource<'a>>),
+}
+
+/// Generator state of one background stream.
+pub(super) struct LazySource<'a> {
+    pub generator: StreamEventGen<'a>,
+    pub next: u64,
+    pub count: u64,
+    /// RNG positioned where the buffered path starts this stream.
+    pub rng: StdRng,
+}
+
+impl EventSource<'_> {
+    fn next_event(&mut self) -> Option<GenEvent> {
+        match self {
+            EventSource::Buffered(iter) => iter.next(),
+            EventSource::Lazy(lazy) => {
+                if lazy.next >= lazy.count {
+                    return None;
+                }
+                let event = lazy.generator.event(lazy.next, &mut lazy.rng);
+                lazy.next += 1;
+                Some(event)
+            }
+        }
+    }
+}
+
+/// Scenario events in timestamp order, produced by a k-way merge over the
+/// inject events and every background stream.
+///
+/// Equal timestamps are yielded in source order, matching the stable sort
+/// used by [`generate`](super::generate).
+pub struct EventStream<'a> {
+    sources: Vec<EventSource<'a>>,
+    heads: Vec<Option<GenEvent>>,
+    heap: BinaryHeap<Reverse<(DateTime<Utc>, usize)>>,
+    windows: Vec<String>,
+}
+
+impl<'a> EventStream<'a> {
+    pub(super) fn new(mut sources: Vec<EventSource<'a>>, windows: Vec<String>) -> Self {
+        let mut heads = Vec::with_capacity(sources.len());
+        let mut heap = BinaryHeap::with_capacity(sources.len());
+        for (idx, source) in sources.iter_mut().enumerate() {
+            let head = source.next_event();
+            if let Some(event) = &head {
+                heap.push(Reverse((event.timestamp, idx)));
+            }
+            heads.push(head);
+        }
+        Self {
+            sources,
+            heads,
+            heap,
+            windows,
+        }
+    }
+
+    /// Windows that receive at least one event, in first-declared order.
+    pub fn windows(&self) -> &[String] {
+        &self.windows
+    }
+}
+
+impl Iterator for EventStream<'_> {
+    type Item = GenEvent;
+
+    fn next(&mut self) -> Option<GenEvent> {
+        let Reverse((_, idx)) = self.heap.pop()?;
+        let event = self.heads[idx].take()?;
+        if let Some(next) = self.sources[idx].next_event() {
+            self.heap.push(Reverse((next.timestamp, idx)));
+            self.heads[idx] = Some(next);
+        }
+        Some(event)
+    }
+}
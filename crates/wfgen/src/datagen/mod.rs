@@ -1,27 +1,163 @@
 pub mod fault_gen;
 pub mod field_gen;
 pub mod inject_gen;
+mod merge;
 pub mod stream_gen;
 #[cfg(test)]
 mod tests;
 
 use std::collections::HashMap;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 use wf_lang::WindowSchema;
 use wf_lang::plan::RulePlan;
 
-use crate::wfg_ast::WfgFile;
-use inject_gen::generate_inject_events;
-use stream_gen::{GenEvent, generate_stream_events};
+use crate::wfg_ast::{ScenarioDecl, WfgFile};
+use inject_gen::{InjectLinePlan, generate_inject_events, plan_inject_lines};
+use stream_gen::{GenEvent, StreamEventGen};
+
+pub use merge::EventStream;
+use merge::{EventSource, LazySource};
 
 /// Result of data generation.
 pub struct GenResult {
     pub events: Vec<GenEvent>,
 }
 
+/// Planned event counts for one scenario stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamPlan {
+    pub alias: String,
+    pub window: String,
+    /// Share of the scenario `total` assigned by rate proportion.
+    pub budget: u64,
+    /// Inject events placed on this stream (subtracted from the budget).
+    pub inject: u64,
+    /// Background events left after inject subtraction.
+    pub background: u64,
+}
+
+/// Event counts [`generate`] would produce, computed without generating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunReport {
+    pub total: u64,
+    pub streams: Vec<StreamPlan>,
+    pub injects: Vec<InjectLinePlan>,
+    pub warnings: Vec<String>,
+}
+
+impl DryRunReport {
+    /// Events the generation would emit (inject + background).
+    ///
+    /// Inject events are summed per inject line: streams sharing an alias
+    /// all report the same `inject` figure.
+    pub fn event_count(&self) -> u64 {
+        let background: u64 = self.streams.iter().map(|s| s.background).sum();
+        let inject: u64 = self.injects.iter().map(|l| l.total_events()).sum();
+        background + inject
+    }
+}
+
+/// Run the distribution math of [`generate`] — rate proportions and inject
+/// budget subtraction — without generating any events.
+pub fn dry_run(wfg: &WfgFile, rule_plans: &[RulePlan]) -> anyhow::Result<DryRunReport> {
+    let scenario = &wfg.scenario;
+    let budgets = stream_budgets(scenario)?;
+    let mut warnings = Vec::new();
+
+    let injects = if scenario.injects.is_empty() {
+        Vec::new()
+    } else if rule_plans.is_empty() {
+        warnings.push(
+            "scenario has inject blocks but no compiled rules; injects will be skipped".to_string(),
+        );
+        Vec::new()
+    } else {
+        plan_inject_lines(wfg, rule_plans)?
+    };
+
+    let mut inject_counts: HashMap<&str, u64> = HashMap::new();
+    for line in &injects {
+        for (alias, n) in &line.stream_events {
+            *inject_counts.entry(alias).or_insert(0) += n;
+        }
+        if line.total_events() == 0 {
+            warnings.push(format!(
+                "{} {}% for rule '{}' produces no events; the stream budget is too small \
+                 for the rule threshold",
+                line.mode, line.percent, line.rule
+            ));
+        }
+    }
+
+    let streams: Vec<StreamPlan> = scenario
+        .streams
+        .iter()
+        .zip(budgets)
+        .map(|(stream, budget)| {
+            let inject = inject_counts
+                .get(stream.alias.as_str())
+                .copied()
+                .unwrap_or(0);
+            StreamPlan {
+                alias: stream.alias.clone(),
+                window: stream.window.clone(),
+                budget,
+                inject,
+                background: budget.saturating_sub(inject),
+            }
+        })
+        .collect();
+    for s in &streams {
+        if s.inject > s.budget {
+            warnings.push(format!(
+                "stream '{}': inject events ({}) exceed its budget ({}); output will exceed total",
+                s.alias, s.inject, s.budget
+            ));
+        }
+    }
+
+    Ok(DryRunReport {
+        total: scenario.total,
+        streams,
+        injects,
+        warnings,
+    })
+}
+
+/// Split the scenario `total` across streams by rate proportion; the last
+/// stream absorbs rounding.
+fn stream_budgets(scenario: &ScenarioDecl) -> anyhow::Result<Vec<u64>> {
+    let total = scenario.total;
+    let total_rate: f64 = scenario
+        .streams
+        .iter()
+        .map(|s| s.rate.events_per_second())
+        .sum();
+
+    if total_rate == 0.0 {
+        return Err(anyhow::anyhow!("total rate across all streams is 0"));
+    }
+
+    let mut remaining = total;
+    let mut budgets = Vec::with_capacity(scenario.streams.len());
+    for (i, stream) in scenario.streams.iter().enumerate() {
+        let proportion = stream.rate.events_per_second() / total_rate;
+        let stream_total = if i == scenario.streams.len() - 1 {
+            remaining
+        } else {
+            let count = (total as f64 * proportion).round() as u64;
+            let count = count.min(remaining);
+            remaining -= count;
+            count
+        };
+        budgets.push(stream_total);
+    }
+    Ok(budgets)
+}
+
 /// Generate events from a parsed and validated `.wfg` scenario.
 ///
 /// When `rule_plans` is non-empty and the scenario contains inject blocks,
@@ -33,55 +169,123 @@ pub fn generate(
     schemas: &[WindowSchema],
     rule_plans: &[RulePlan],
 ) -> anyhow::Result<GenResult> {
+    let mut plan = plan_generation(wfg, schemas, rule_plans)?;
+
+    let mut all_events = std::mem::take(&mut plan.inject_events);
+    for (generator, count) in &plan.backgrounds {
+        all_events.extend((0..*count).map(|i| generator.event(i, &mut plan.rng)));
+    }
+
+    // Sort all events by timestamp
+    all_events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    Ok(GenResult { events: all_events })
+}
+
+/// Generate events lazily in timestamp order without materializing the
+/// background streams.
+///
+/// Inject events are still generated up front; each background stream is
+/// produced one event at a time and k-way merged by timestamp. The output is
+/// identical to [`generate`] for the same seed: every stream starts from the
+/// RNG state the buffered path would reach, found by a discard pass that
+/// generates (and drops) the preceding streams' events.
+pub fn generate_streaming<'a>(
+    wfg: &'a WfgFile,
+    schemas: &'a [WindowSchema],
+    rule_plans: &[RulePlan],
+) -> anyhow::Result<EventStream<'a>> {
+    let mut plan = plan_generation(wfg, schemas, rule_plans)?;
+
+    let mut windows: Vec<String> = Vec::new();
+    let mut add_window = |name: &str| {
+        if !windows.iter().any(|w| w == name) {
+            windows.push(name.to_string());
+        }
+    };
+
+    // Stable sort + stable merge (ties go to the lower source index) keeps
+    // the buffered path's ordering of equal timestamps.
+    let mut inject_events = std::mem::take(&mut plan.inject_events);
+    inject_events.sort_by_key(|e| e.timestamp);
+    for event in &inject_events {
+        add_window(&event.window_name);
+    }
+    let mut sources = vec![EventSource::Buffered(inject_events.into_iter())];
+
+    for (generator, count) in plan.backgrounds {
+        add_window(generator.window_name());
+        let rng = plan.rng.clone();
+        for i in 0..count {
+            generator.event(i, &mut plan.rng);
+        }
+        sources.push(EventSource::Lazy(Box::new(LazySource {
+            generator,
+            next: 0,
+            count,
+            rng,
+        })));
+    }
+
+    Ok(EventStream::new(sources, windows))
+}
+
+/// Inject events plus per-stream background budgets, with the RNG positioned
+/// right after inject generation.
+struct GenerationPlan<'a> {
+    inject_events: Vec<GenEvent>,
+    backgrounds: Vec<(StreamEventGen<'a>, u64)>,
+    rng: StdRng,
+}
+
+/// Parse an RFC 3339 timestamp with any UTC offset and normalize it to UTC.
+///
+/// `2024-01-01T09:00:00+09:00` and `2024-01-01T00:00:00Z` yield the same
+/// instant. Timestamps without an offset are rejected instead of being
+/// silently read as UTC.
+pub fn parse_timestamp(s: &str) -> anyhow::Result<DateTime<Utc>> {
+    match s.parse::<DateTime<FixedOffset>>() {
+        Ok(dt) => Ok(dt.with_timezone(&Utc)),
+        Err(_) if s.parse::<NaiveDateTime>().is_ok() => anyhow::bail!(
+            "timestamp '{s}' has no UTC offset; append `Z` or an offset such as `+08:00`"
+        ),
+        Err(e) => anyhow::bail!("invalid timestamp '{s}': {e}"),
+    }
+}
+
+fn plan_generation<'a>(
+    wfg: &'a WfgFile,
+    schemas: &'a [WindowSchema],
+    rule_plans: &[RulePlan],
+) -> anyhow::Result<GenerationPlan<'a>> {
     let scenario = &wfg.scenario;
 
     // Parse start time
-    let start: DateTime<Utc> = scenario.time_clause.start.parse().map_err(|e| {
-        anyhow::anyhow!("invalid start time '{}': {}", scenario.time_clause.start, e)
-    })?;
+    let start = parse_timestamp(&scenario.time_clause.start)
+        .map_err(|e| anyhow::anyhow!("invalid start time: {e}"))?;
 
     let duration = scenario.time_clause.duration;
-    let total = scenario.total;
 
     // Create deterministic RNG
     let mut rng = StdRng::seed_from_u64(scenario.seed);
 
     // --- Inject generation (if applicable) ---
     let mut inject_counts: HashMap<String, u64> = HashMap::new();
-    let mut all_events = Vec::new();
+    let mut inject_events = Vec::new();
 
     let has_inject = !scenario.injects.is_empty() && !rule_plans.is_empty();
     if has_inject {
         let inject_result =
             generate_inject_events(wfg, rule_plans, schemas, &start, &duration, &mut rng)?;
         inject_counts = inject_result.inject_counts;
-        all_events.extend(inject_result.events);
+        inject_events = inject_result.events;
     }
 
-    // --- Background event generation ---
-    let total_rate: f64 = scenario
-        .streams
-        .iter()
-        .map(|s| s.rate.events_per_second())
-        .sum();
-
-    if total_rate == 0.0 {
-        return Err(anyhow::anyhow!("total rate across all streams is 0"));
-    }
-
-    let mut remaining = total;
-
-    for (i, stream) in scenario.streams.iter().enumerate() {
-        let proportion = stream.rate.events_per_second() / total_rate;
-        let stream_total = if i == scenario.streams.len() - 1 {
-            remaining
-        } else {
-            let count = (total as f64 * proportion).round() as u64;
-            let count = count.min(remaining);
-            remaining -= count;
-            count
-        };
+    // --- Background event budgets ---
+    let budgets = stream_budgets(scenario)?;
+    let mut backgrounds = Vec::new();
 
+    for (stream, stream_total) in scenario.streams.iter().zip(budgets) {
         // Subtract inject events from this stream's budget
         let inject_used = inject_counts.get(&stream.alias).copied().unwrap_or(0);
         let bg_count = stream_total.saturating_sub(inject_used);
@@ -95,12 +299,15 @@ pub fn generate(
             .find(|s| s.name == stream.window)
             .ok_or_else(|| anyhow::anyhow!("schema not found for window '{}'", stream.window))?;
 
-        let events = generate_stream_events(stream, schema, bg_count, &start, &duration, &mut rng);
-        all_events.extend(events);
+        backgrounds.push((
+            StreamEventGen::new(stream, schema, bg_count, &start, &duration),
+            bg_count,
+        ));
     }
 
-    // Sort all events by timestamp
-    all_events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
-
-    Ok(GenResult { events: all_events })
+    Ok(GenerationPlan {
+        inject_events,
+        backgrounds,
+        rng,
+    })
 }
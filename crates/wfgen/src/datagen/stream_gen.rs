@@ -7,7 +7,7 @@ use wf_lang::{BaseType, FieldType, WindowSchema};
 
 use crate::wfg_ast::{GenExpr, StreamBlock};
 
-use super::field_gen::generate_field_value;
+use super::field_gen::{derived_order, fill_derived_fields, generate_field_value};
 
 /// A single generated event.
 #[derive(Debug, Clone)]
@@ -29,36 +29,77 @@ pub fn generate_stream_events(
     duration: &std::time::Duration,
     rng: &mut StdRng,
 ) -> Vec<GenEvent> {
-    let mut events = Vec::with_capacity(event_count as usize);
-
-    // Get the actual stream name from schema (e.g., "syslog")
-    let stream_name = schema
-        .streams
-        .first()
-        .cloned()
-        .unwrap_or_else(|| schema.name.clone());
-
-    // Build field override lookup
-    let overrides: HashMap<&str, &GenExpr> = stream
-        .overrides
-        .iter()
-        .map(|o| (o.field_name.as_str(), &o.gen_expr))
-        .collect();
-
-    let duration_nanos = duration.as_nanos() as i64;
-    let interval = if event_count > 1 {
-        duration_nanos / (event_count as i64)
-    } else {
-        0
-    };
-
-    for i in 0..event_count {
-        let ts = *start + ChronoDuration::nanoseconds(interval * i as i64);
+    let generator = StreamEventGen::new(stream, schema, event_count, start, duration);
+    (0..event_count).map(|i| generator.event(i, rng)).collect()
+}
+
+/// Per-stream event builder: produces the `i`-th event of a stream on demand,
+/// so events can be generated one at a time without buffering the stream.
+pub(crate) struct StreamEventGen<'a> {
+    schema: &'a WindowSchema,
+    /// The actual stream name from schema (e.g., "syslog")
+    stream_name: String,
+    window_name: String,
+    overrides: HashMap<&'a str, &'a GenExpr>,
+    /// Overrides that read other fields, in evaluation order.
+    derived: Vec<&'a str>,
+    start: DateTime<Utc>,
+    interval: i64,
+}
+
+impl<'a> StreamEventGen<'a> {
+    pub(crate) fn new(
+        stream: &'a StreamBlock,
+        schema: &'a WindowSchema,
+        event_count: u64,
+        start: &DateTime<Utc>,
+        duration: &std::time::Duration,
+    ) -> Self {
+        // Get the actual stream name from schema (e.g., "syslog")
+        let stream_name = schema
+            .streams
+            .first()
+            .cloned()
+            .unwrap_or_else(|| schema.name.clone());
+
+        // Build field override lookup
+        let overrides: HashMap<&str, &GenExpr> = stream
+            .overrides
+            .iter()
+            .map(|o| (o.field_name.as_str(), &o.gen_expr))
+            .collect();
+        let derived = derived_order(&overrides);
+
+        let duration_nanos = duration.as_nanos() as i64;
+        let interval = if event_count > 1 {
+            duration_nanos / (event_count as i64)
+        } else {
+            0
+        };
+
+        Self {
+            schema,
+            stream_name,
+            window_name: stream.window.clone(),
+            overrides,
+            derived,
+            start: *start,
+            interval,
+        }
+    }
+
+    pub(crate) fn window_name(&self) -> &str {
+        &self.window_name
+    }
+
+    /// Build event `i`, drawing field values from `rng`.
+    pub(crate) fn event(&self, i: u64, rng: &mut StdRng) -> GenEvent {
+        let ts = self.start + ChronoDuration::nanoseconds(self.interval * i as i64);
 
         let mut fields = serde_json::Map::new();
 
-        for field_def in &schema.fields {
-            let override_expr = overrides.get(field_def.name.as_str()).copied();
+        for field_def in &self.schema.fields {
+            let override_expr = self.overrides.get(field_def.name.as_str()).copied();
 
             // For Time fields, set the timestamp
             if matches!(&field_def.field_type, FieldType::Base(BaseType::Time))
@@ -72,17 +113,28 @@ pub fn generate_stream_events(
                 continue;
             }
 
+            // Derived fields are filled in below; reserve their slot now.
+            if self.derived.contains(&field_def.name.as_str()) {
+                fields.insert(field_def.name.clone(), Value::Null);
+                continue;
+            }
+
             let value = generate_field_value(&field_def.field_type, override_expr, rng);
             fields.insert(field_def.name.clone(), value);
         }
+        fill_derived_fields(
+            self.schema,
+            &self.overrides,
+            &self.derived,
+            &mut fields,
+            rng,
+        );
 
-        events.push(GenEvent {
-            stream_name: stream_name.clone(),
-            window_name: stream.window.clone(),
+        GenEvent {
+            stream_name: self.stream_name.clone(),
+            window_name: self.window_name.clone(),
             timestamp: ts,
             fields,
-        });
+        }
     }
-
-    events
 }
@@ -205,3 +205,156 @@ scenario enum_values<seed=7> {
         assert!(!user.is_empty());
     }
 }
+
+#[test]
+fn test_pool_override_bounded_and_deterministic() {
+    let input = r#"
+#[duration=20s]
+scenario pooled<seed=11> {
+    traffic {
+        stream LoginWindow gen 20/s {
+            src_ip = pool(20)
+            username = pool(5, skew: 1.2, prefix: "user")
+            request_id = pool(8)
+        }
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_login_schema()];
+
+    let result = generate(&wfg, &schemas, &[]).unwrap();
+    assert!(result.events.len() >= 100);
+
+    let distinct = |field: &str| -> std::collections::HashSet<String> {
+        result
+            .events
+            .iter()
+            .map(|e| e.fields[field].as_str().unwrap().to_string())
+            .collect()
+    };
+    let ips = distinct("src_ip");
+    assert!(ips.len() <= 20 && ips.len() > 1, "ips: {}", ips.len());
+    assert!(ips.iter().all(|ip| ip.starts_with("10.")));
+    let users = distinct("username");
+    assert!(users.len() <= 5);
+    assert!(users.iter().all(|u| u.starts_with("user_")));
+    assert!(distinct("request_id").len() <= 8);
+
+    // Skewed pools favour the head of the set.
+    let head = result
+        .events
+        .iter()
+        .filter(|e| e.fields["username"] == "user_0")
+        .count();
+    let tail = result
+        .events
+        .iter()
+        .filter(|e| e.fields["username"] == "user_4")
+        .count();
+    assert!(head > tail, "head={head} tail={tail}");
+
+    let again = generate(&wfg, &schemas, &[]).unwrap();
+    for (a, b) in result.events.iter().zip(again.events.iter()) {
+        assert_eq!(a.fields, b.fields);
+    }
+}
+
+#[test]
+fn test_derived_fields_follow_source() {
+    // `request_id` depends on `username`, which itself is derived: the
+    // declaration order is deliberately reversed.
+    let input = r#"
+#[duration=5s]
+scenario derived<seed=3> {
+    traffic {
+        stream LoginWindow gen 20/s {
+            request_id = hash(username)
+            username = "user_" + src_ip
+            src_ip = pool(10)
+        }
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_login_schema()];
+
+    let result = generate(&wfg, &schemas, &[]).unwrap();
+    assert!(!result.events.is_empty());
+    let mut by_user = std::collections::HashMap::new();
+    for event in &result.events {
+        let ip = event.fields["src_ip"].as_str().unwrap();
+        let user = event.fields["username"].as_str().unwrap();
+        let rid = event.fields["request_id"].as_str().unwrap();
+        assert_eq!(user, format!("user_{ip}"));
+        assert_eq!(rid.len(), 16);
+        assert_eq!(
+            by_user.entry(user.to_string()).or_insert(rid.to_string()),
+            rid
+        );
+    }
+    assert!(by_user.len() > 1);
+}
+
+#[test]
+fn test_offset_start_normalized_to_utc() {
+    let input = r#"
+#[duration=5s]
+scenario tz<seed=7> {
+    traffic {
+        stream LoginWindow gen 10/s
+    }
+}
+"#;
+    let schemas = vec![make_login_schema()];
+
+    let mut utc = parse_wfg(input).unwrap();
+    utc.scenario.time_clause.start = "2024-01-01T00:00:00Z".to_string();
+    let mut tokyo = parse_wfg(input).unwrap();
+    tokyo.scenario.time_clause.start = "2024-01-01T09:00:00+09:00".to_string();
+
+    let a = generate(&utc, &schemas, &[]).unwrap();
+    let b = generate(&tokyo, &schemas, &[]).unwrap();
+
+    let start = "2024-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+    assert_eq!(a.events.len(), b.events.len());
+    assert!(b.events.iter().all(|e| e.timestamp >= start));
+    for (x, y) in a.events.iter().zip(b.events.iter()) {
+        assert_eq!(x.timestamp, y.timestamp);
+        assert_eq!(x.fields, y.fields);
+    }
+}
+
+#[test]
+fn test_naive_start_rejected() {
+    let input = r#"
+#[duration=5s]
+scenario naive<seed=7> {
+    traffic {
+        stream LoginWindow gen 10/s
+    }
+}
+"#;
+    let mut wfg = parse_wfg(input).unwrap();
+    wfg.scenario.time_clause.start = "2024-01-01T00:00:00".to_string();
+
+    let err = match generate(&wfg, &[make_login_schema()], &[]) {
+        Ok(_) => panic!("naive start time should be rejected"),
+        Err(e) => e.to_string(),
+    };
+    assert!(err.contains("invalid start time"), "got: {err}");
+    assert!(err.contains("no UTC offset"), "got: {err}");
+}
+
+#[test]
+fn test_parse_timestamp_offsets() {
+    let expected = "2024-03-10T16:30:00Z".parse::<DateTime<Utc>>().unwrap();
+    for s in [
+        "2024-03-10T16:30:00Z",
+        "2024-03-11T01:30:00+09:00",
+        "2024-03-10T11:30:00-05:00",
+    ] {
+        assert_eq!(parse_timestamp(s).unwrap(), expected, "{s}");
+    }
+    assert!(parse_timestamp("not a time").is_err());
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::datagen::fault_gen::apply_faults;
+use crate::datagen::fault_gen::{REORDER_WINDOW, apply_faults};
 use crate::wfg_ast::{FaultLine, FaultType, FaultsBlock};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
@@ -39,7 +39,7 @@ fn test_fault_drop_removes_events() {
     let events = make_clean_events(100);
     let faults = faults_block(vec![(FaultType::Drop, 10.0)]);
     let mut rng = StdRng::seed_from_u64(42);
-    let result = apply_faults(events, &faults, &mut rng);
+    let result = apply_faults(events, &faults, &[], &mut rng);
 
     assert!(
         result.events.len() < 100,
@@ -59,7 +59,7 @@ fn test_fault_duplicate_adds_events() {
     let events = make_clean_events(100);
     let faults = faults_block(vec![(FaultType::Duplicate, 10.0)]);
     let mut rng = StdRng::seed_from_u64(42);
-    let result = apply_faults(events, &faults, &mut rng);
+    let result = apply_faults(events, &faults, &[], &mut rng);
 
     assert!(
         result.events.len() > 100,
@@ -76,7 +76,7 @@ fn test_fault_out_of_order_preserves_count() {
     let events = make_clean_events(100);
     let faults = faults_block(vec![(FaultType::OutOfOrder, 20.0)]);
     let mut rng = StdRng::seed_from_u64(42);
-    let result = apply_faults(events, &faults, &mut rng);
+    let result = apply_faults(events, &faults, &[], &mut rng);
 
     // OutOfOrder swaps pairs but doesn't change count
     assert_eq!(result.events.len(), 100);
@@ -101,13 +101,158 @@ fn test_fault_late_preserves_count() {
     let events = make_clean_events(100);
     let faults = faults_block(vec![(FaultType::Late, 10.0)]);
     let mut rng = StdRng::seed_from_u64(42);
-    let result = apply_faults(events, &faults, &mut rng);
+    let result = apply_faults(events, &faults, &[], &mut rng);
 
     // Late moves events later in the output but doesn't change count
     assert_eq!(result.events.len(), 100);
     assert!(result.stats.late > 0);
 }
 
+/// Tag each clean event with its input position so displacement can be measured.
+fn make_indexed_events(count: usize) -> Vec<super::super::stream_gen::GenEvent> {
+    let mut events = make_clean_events(count);
+    for (idx, event) in events.iter_mut().enumerate() {
+        event
+            .fields
+            .insert("_idx".to_string(), serde_json::json!(idx));
+    }
+    events
+}
+
+#[test]
+fn test_fault_reorder_bounded() {
+    let events = make_indexed_events(200);
+    let faults = faults_block(vec![(FaultType::Reorder, 20.0)]);
+    let mut rng = StdRng::seed_from_u64(42);
+    let result = apply_faults(events, &faults, &[], &mut rng);
+    let s = &result.stats;
+
+    assert_eq!(result.events.len(), 200);
+    assert!(s.reordered > 0);
+    assert_eq!(
+        s.reordered + s.clean,
+        200,
+        "reordered + clean (including partners) should equal original count"
+    );
+
+    let mut moved = 0;
+    for (pos, event) in result.events.iter().enumerate() {
+        let idx = event.fields["_idx"].as_u64().unwrap() as usize;
+        assert!(
+            idx.abs_diff(pos) <= REORDER_WINDOW,
+            "event {idx} arrived at {pos}, beyond reorder window {REORDER_WINDOW}"
+        );
+        if idx != pos {
+            moved += 1;
+        }
+    }
+    // Each reorder moves the faulted event and its partner
+    assert_eq!(moved, 2 * s.reordered);
+}
+
+#[test]
+fn test_fault_reorder_deterministic() {
+    let faults = faults_block(vec![(FaultType::Reorder, 15.0), (FaultType::Drop, 5.0)]);
+    let run = |seed: u64| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let result = apply_faults(make_indexed_events(100), &faults, &[], &mut rng);
+        let order: Vec<u64> = result
+            .events
+            .iter()
+            .map(|e| e.fields["_idx"].as_u64().unwrap())
+            .collect();
+        (order, result.stats.reordered)
+    };
+
+    let (order1, reordered1) = run(7);
+    let (order2, reordered2) = run(7);
+    assert_eq!(order1, order2);
+    assert_eq!(reordered1, reordered2);
+    assert!(reordered1 > 0);
+}
+
+#[test]
+fn test_fault_corrupt_type_aware() {
+    let clean = make_clean_events(200);
+    let faults = faults_block(vec![(FaultType::Corrupt, 25.0)]);
+    let schemas = vec![make_login_schema()];
+    let mut rng = StdRng::seed_from_u64(42);
+    let result = apply_faults(clean.clone(), &faults, &schemas, &mut rng);
+    let s = &result.stats;
+
+    assert_eq!(result.events.len(), 200);
+    assert!(s.corrupted > 0);
+    assert_eq!(s.corrupted + s.clean, 200);
+
+    let mut changed = 0;
+    for (before, after) in clean.iter().zip(result.events.iter()) {
+        // Time field and timestamp are never corrupted
+        assert_eq!(before.timestamp, after.timestamp);
+        assert_eq!(before.fields["timestamp"], after.fields["timestamp"]);
+        if before.fields == after.fields {
+            continue;
+        }
+        changed += 1;
+        for (name, value) in &after.fields {
+            if *value == before.fields[name] || value.is_null() {
+                continue;
+            }
+            match name.as_str() {
+                "success" => assert_eq!(value.as_bool(), before.fields[name].as_bool().map(|b| !b)),
+                "attempts" => assert!(matches!(value.as_i64(), Some(i64::MAX | i64::MIN))),
+                "score" => {
+                    assert!(matches!(value.as_f64(), Some(f) if f == f64::MAX || f == f64::MIN))
+                }
+                "src_ip" | "username" | "request_id" => {
+                    let (b, a) = (
+                        before.fields[name].as_str().unwrap(),
+                        value.as_str().unwrap(),
+                    );
+                    assert!(
+                        a.len() < b.len() && b.starts_with(a),
+                        "{a:?} not a truncation of {b:?}"
+                    );
+                }
+                other => panic!("unexpected corruption of field '{other}'"),
+            }
+        }
+    }
+    assert_eq!(changed, s.corrupted);
+}
+
+#[test]
+fn test_fault_corrupt_deterministic() {
+    let faults = faults_block(vec![(FaultType::Corrupt, 30.0), (FaultType::Reorder, 10.0)]);
+    let schemas = vec![make_login_schema()];
+    let run = |seed: u64| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        apply_faults(make_clean_events(100), &faults, &schemas, &mut rng)
+    };
+
+    let result1 = run(11);
+    let result2 = run(11);
+    assert_eq!(result1.stats.corrupted, result2.stats.corrupted);
+    assert!(result1.stats.corrupted > 0);
+    for (e1, e2) in result1.events.iter().zip(result2.events.iter()) {
+        assert_eq!(e1.timestamp, e2.timestamp);
+        assert_eq!(e1.fields, e2.fields);
+    }
+}
+
+#[test]
+fn test_fault_corrupt_without_schema_is_clean() {
+    let faults = faults_block(vec![(FaultType::Corrupt, 50.0)]);
+    let mut rng = StdRng::seed_from_u64(3);
+    let clean = make_clean_events(50);
+    let result = apply_faults(clean.clone(), &faults, &[], &mut rng);
+
+    assert_eq!(result.stats.corrupted, 0);
+    assert_eq!(result.stats.clean, 50);
+    for (before, after) in clean.iter().zip(result.events.iter()) {
+        assert_eq!(before.fields, after.fields);
+    }
+}
+
 #[test]
 fn test_fault_deterministic() {
     let events1 = make_clean_events(100);
@@ -121,8 +266,8 @@ fn test_fault_deterministic() {
     let mut rng1 = StdRng::seed_from_u64(99);
     let mut rng2 = StdRng::seed_from_u64(99);
 
-    let result1 = apply_faults(events1, &faults, &mut rng1);
-    let result2 = apply_faults(events2, &faults, &mut rng2);
+    let result1 = apply_faults(events1, &faults, &[], &mut rng1);
+    let result2 = apply_faults(events2, &faults, &[], &mut rng2);
 
     assert_eq!(result1.events.len(), result2.events.len());
     for (e1, e2) in result1.events.iter().zip(result2.events.iter()) {
@@ -141,7 +286,7 @@ fn test_fault_combined_stats() {
         (FaultType::Drop, 2.0),
     ]);
     let mut rng = StdRng::seed_from_u64(42);
-    let result = apply_faults(events, &faults, &mut rng);
+    let result = apply_faults(events, &faults, &[], &mut rng);
     let s = &result.stats;
 
     // Every input event is accounted for exactly once
@@ -167,7 +312,7 @@ fn test_empty_faults_passthrough() {
     let events = make_clean_events(50);
     let faults = FaultsBlock { faults: vec![] };
     let mut rng = StdRng::seed_from_u64(1);
-    let result = apply_faults(events, &faults, &mut rng);
+    let result = apply_faults(events, &faults, &[], &mut rng);
 
     assert_eq!(result.events.len(), 50);
     assert_eq!(result.stats.clean, 50);
@@ -175,4 +320,6 @@ fn test_empty_faults_passthrough() {
     assert_eq!(result.stats.duplicate, 0);
     assert_eq!(result.stats.out_of_order, 0);
     assert_eq!(result.stats.late, 0);
+    assert_eq!(result.stats.reordered, 0);
+    assert_eq!(result.stats.corrupted, 0);
 }
@@ -266,6 +266,108 @@ scenario inject_budget<seed=42> {
     assert_eq!(result.events.len(), 1000);
 }
 
+#[test]
+fn test_dry_run_counts_sum_to_total() {
+    let input = r#"
+#[duration=10s]
+scenario dry<seed=42> {
+    traffic {
+        stream LoginWindow gen 100/s
+    }
+    injection {
+        hit<30%> LoginWindow {
+            src_ip seq {
+                use(action="failed") with(5,2m)
+            }
+        }
+        near_miss<10%> LoginWindow {
+            src_ip seq {
+                use(action="failed") with(5,2m)
+            }
+        }
+        miss<20%> LoginWindow {
+            src_ip seq {
+                use(action="success") with(1,30s)
+            }
+        }
+    }
+    expect {
+        hit(brute_force) >= 0%
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_login_schema()];
+    let plans = vec![make_brute_force_plan()];
+
+    let report = dry_run(&wfg, &plans).unwrap();
+    assert_eq!(report.total, 1000);
+    let budgets: u64 = report.streams.iter().map(|s| s.budget).sum();
+    assert_eq!(budgets, report.total);
+    assert_eq!(report.event_count(), report.total);
+    assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+    // 1000 events: hit 300/5 = 60 clusters, near-miss 100/4 = 25, miss 200.
+    let injects: Vec<_> = report
+        .injects
+        .iter()
+        .map(|l| (l.mode, l.clusters, l.total_events()))
+        .collect();
+    assert_eq!(
+        injects,
+        vec![
+            (InjectMode::Hit, Some(60), 300),
+            (InjectMode::NearMiss, Some(25), 100),
+            (InjectMode::NonHit, None, 200),
+        ]
+    );
+    let stream = &report.streams[0];
+    assert_eq!((stream.inject, stream.background), (600, 400));
+
+    // The plan matches what generation actually produces.
+    let result = generate(&wfg, &schemas, &plans).unwrap();
+    assert_eq!(result.events.len() as u64, report.event_count());
+}
+
+#[test]
+fn test_dry_run_warns_on_inject_overflow() {
+    let input = r#"
+#[duration=10s]
+scenario overflow<seed=1> {
+    traffic {
+        stream LoginWindow gen 10/s
+    }
+    injection {
+        hit<80%> LoginWindow {
+            src_ip seq {
+                use(action="failed") with(5,2m)
+            }
+        }
+        miss<50%> LoginWindow {
+            src_ip seq {
+                use(action="success") with(1,30s)
+            }
+        }
+    }
+    expect {
+        hit(brute_force) >= 0%
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let report = dry_run(&wfg, &[make_brute_force_plan()]).unwrap();
+    assert_eq!(report.streams[0].inject, 130);
+    assert_eq!(report.streams[0].background, 0);
+    assert!(
+        report
+            .warnings
+            .iter()
+            .any(|w| w.contains("LoginWindow") && w.contains("exceed")),
+        "{:?}",
+        report.warnings
+    );
+}
+
 #[test]
 fn test_inject_deterministic() {
     let input = r#"
@@ -299,3 +401,124 @@ scenario inject_det<seed=42> {
         assert_eq!(e1.fields, e2.fields);
     }
 }
+
+#[test]
+fn test_streaming_matches_buffered() {
+    let input = r#"
+#[duration=5s]
+scenario inject_stream<seed=42> {
+    traffic {
+        stream LoginWindow gen 100/s
+    }
+    injection {
+        hit<30%> LoginWindow {
+            src_ip seq {
+                use(action="failed") with(5,2m)
+            }
+        }
+    }
+    expect {
+        hit(brute_force) >= 0%
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_login_schema()];
+    let plans = vec![make_brute_force_plan()];
+
+    let buffered = generate(&wfg, &schemas, &plans).unwrap().events;
+    let stream = generate_streaming(&wfg, &schemas, &plans).unwrap();
+    assert_eq!(stream.windows(), ["LoginWindow".to_string()]);
+    let streamed: Vec<_> = stream.collect();
+
+    assert_eq!(buffered.len(), streamed.len());
+    for (b, s) in buffered.iter().zip(streamed.iter()) {
+        assert_eq!(b.window_name, s.window_name);
+        assert_eq!(b.timestamp, s.timestamp);
+        assert_eq!(b.fields, s.fields);
+    }
+}
+
+const REPLAY_SCENARIO: &str = r#"
+#[duration=10s]
+scenario inject_replay<seed=42> {
+    traffic {
+        stream LoginWindow gen 100/s
+    }
+    injection {
+        replay<60%, copies=3> LoginWindow {
+            src_ip seq {
+                use(action="failed") with(5,2m)
+            }
+        }
+    }
+    expect {
+        hit(brute_force) >= 0%
+    }
+}
+"#;
+
+#[test]
+fn test_inject_replay_duplicates_events() {
+    let wfg = parse_wfg(REPLAY_SCENARIO).unwrap();
+    let schemas = vec![make_login_schema()];
+    let plans = vec![make_brute_force_plan()];
+
+    let result = generate(&wfg, &schemas, &plans).unwrap();
+    assert_eq!(result.events.len(), 1000);
+
+    // 1000 * 60% = 600 events / (5 × 3) per cluster = 40 clusters
+    let lines = crate::datagen::inject_gen::plan_inject_lines(&wfg, &plans).unwrap();
+    assert_eq!(lines[0].mode, InjectMode::Replay);
+    assert_eq!(lines[0].clusters, Some(40));
+    assert_eq!(lines[0].total_events(), 600);
+
+    let replayed: Vec<_> = result
+        .events
+        .iter()
+        .filter(|e| {
+            e.fields
+                .get("src_ip")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| s.starts_with("10."))
+        })
+        .collect();
+    assert_eq!(replayed.len(), 600);
+
+    // Every replayed event appears exactly three times, fields and timestamp intact.
+    let mut copies: std::collections::HashMap<String, usize> = Default::default();
+    for e in &replayed {
+        let key = format!("{}|{:?}", e.timestamp, e.fields);
+        *copies.entry(key).or_insert(0) += 1;
+    }
+    assert_eq!(copies.len(), 200);
+    assert!(copies.values().all(|&n| n == 3));
+}
+
+#[test]
+fn test_inject_replay_oracle_distinct_vs_count() {
+    use wf_lang::ast::{FieldSelector, Transform};
+
+    let wfg = parse_wfg(REPLAY_SCENARIO).unwrap();
+    let schemas = vec![make_login_schema()];
+
+    // distinct(request_id) >= 5: duplicates add no new values, one alert per entity.
+    let mut distinct_plan = make_brute_force_plan();
+    let branch = &mut distinct_plan.match_plan.event_steps[0].branches[0];
+    branch.field = Some(FieldSelector::Dot("request_id".to_string()));
+    branch.agg.transforms = vec![Transform::Distinct];
+    let plans = vec![distinct_plan];
+
+    let result = generate(&wfg, &schemas, &plans).unwrap();
+    let start = "2024-01-01T00:00:00Z".parse().unwrap();
+    let duration = Duration::from_secs(3600);
+    let oracle = run_oracle(&result.events, &plans, &start, &duration, None).unwrap();
+    assert_eq!(oracle.alerts.len(), 40);
+
+    // count >= 5 over the same stream: each entity sees 15 events and fires
+    // once per 5 of them.
+    let plans = vec![make_brute_force_plan()];
+    let result = generate(&wfg, &schemas, &plans).unwrap();
+    let oracle = run_oracle(&result.events, &plans, &start, &duration, None).unwrap();
+    assert_eq!(oracle.alerts.len(), 120);
+}
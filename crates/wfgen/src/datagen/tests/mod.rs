@@ -5,6 +5,7 @@ mod inject;
 
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use wf_lang::ast::{CloseMode, CmpOp, Expr, FieldRef, Measure};
 use wf_lang::plan::{
     AggPlan, BindPlan, BranchPlan, EntityPlan, MatchPlan, RulePlan, ScorePlan, StepPlan,
@@ -12,7 +13,8 @@ use wf_lang::plan::{
 };
 use wf_lang::{BaseType, FieldDef, FieldType, WindowSchema};
 
-use super::generate;
+use super::{dry_run, generate, generate_streaming, parse_timestamp};
+use crate::wfg_ast::InjectMode;
 use crate::wfg_parser::parse_wfg;
 
 fn make_login_schema() -> WindowSchema {
@@ -82,23 +84,26 @@ fn make_brute_force_plan() -> RulePlan {
             }],
             close_steps: vec![],
             close_mode: CloseMode::Or,
+            max_out_of_orderness: Duration::ZERO,
+            captures: vec![],
         },
         joins: vec![],
         entity_plan: EntityPlan {
             entity_type: "ip".to_string(),
             entity_id_expr: Expr::Field(FieldRef::Simple("src_ip".to_string())),
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: "alerts".to_string(),
             version: None,
             fields: vec![],
-        },
+        }],
         score_plan: ScorePlan {
             expr: Expr::Number(85.0),
         },
         pattern_origin: None,
         conv_plan: None,
         limits_plan: None,
+        priority: 0,
     }
 }
 
@@ -135,22 +140,25 @@ fn make_auth_fail_plan() -> RulePlan {
             }],
             close_steps: vec![],
             close_mode: CloseMode::Or,
+            max_out_of_orderness: Duration::ZERO,
+            captures: vec![],
         },
         joins: vec![],
         entity_plan: EntityPlan {
             entity_type: "ip".to_string(),
             entity_id_expr: Expr::Field(FieldRef::Simple("src_ip".to_string())),
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: "alerts".to_string(),
             version: None,
             fields: vec![],
-        },
+        }],
         score_plan: ScorePlan {
             expr: Expr::Number(90.0),
         },
         pattern_origin: None,
         conv_plan: None,
         limits_plan: None,
+        priority: 0,
     }
 }
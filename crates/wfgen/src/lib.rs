@@ -2,6 +2,8 @@ pub mod datagen;
 pub mod loader;
 pub mod oracle;
 pub mod output;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 pub mod validate;
 pub mod verify;
 pub mod wfg_ast;
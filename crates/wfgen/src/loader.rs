@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 
@@ -17,49 +17,176 @@ pub struct LoadedScenario {
 /// Load a `.wfg` scenario file, resolve `use` declarations, and compile rules.
 ///
 /// `.wfl` files are preprocessed with `vars` before parsing, falling back to
-/// environment variables for any undefined references.
+/// environment variables for any undefined references. Unlike
+/// [`compile_project`], the first compile error fails the load.
 pub fn load_scenario(
     wfg_path: &Path,
     vars: &HashMap<String, String>,
 ) -> anyhow::Result<LoadedScenario> {
-    let wfg_content = std::fs::read_to_string(wfg_path)
-        .with_context(|| format!("reading .wfg file: {}", wfg_path.display()))?;
+    let project = compile_project(wfg_path, &[], &[], vars)?;
+    if let Some(err) = project.compile_errors.into_iter().next() {
+        return Err(err.context("compiling .wfl rules"));
+    }
+
+    Ok(LoadedScenario {
+        wfg: project.wfg,
+        schemas: project.schemas,
+        wfl_files: project.wfl_files,
+        rule_plans: project.rule_plans,
+    })
+}
+
+/// A `.wfg` scenario plus every schema and rule it pulls in, compiled.
+pub struct CompiledProject {
+    pub wfg: WfgFile,
+    pub schemas: Vec<wf_lang::WindowSchema>,
+    pub wfl_files: Vec<wf_lang::ast::WflFile>,
+    /// Plans from every `.wfl` file that compiled.
+    pub rule_plans: Vec<wf_lang::plan::RulePlan>,
+    /// One error per `.wfl` file that failed to compile; its rules are
+    /// missing from `rule_plans`.
+    pub compile_errors: Vec<anyhow::Error>,
+    /// Warning-level semantic diagnostics from every `.wfl` file.
+    pub compile_warnings: Vec<wf_lang::CheckError>,
+}
+
+/// Read the `.wfg` at `entry`, load the schemas and rules from its `use`
+/// declarations plus `extra_schemas` / `extra_wfl`, and compile every rule file.
+///
+/// `extra_schemas` are `--schema` paths or globs (see [`load_schema_files`]);
+/// schemas are merged with [`merge_schemas`].
+///
+/// I/O and parse failures abort; compile failures are collected in
+/// [`CompiledProject::compile_errors`] so callers decide whether they are
+/// fatal. `.wfl` sources (including extras) are preprocessed with `vars`.
+///
+/// `entry` may be `-` to read the scenario from standard input; its `use`
+/// paths are then resolved against the current directory.
+pub fn compile_project(
+    entry: &Path,
+    extra_schemas: &[String],
+    extra_wfl: &[PathBuf],
+    vars: &HashMap<String, String>,
+) -> anyhow::Result<CompiledProject> {
+    let wfg_content = wf_config::read_source(entry).context("reading .wfg file")?;
     let wfg = parse_wfg(&wfg_content)
-        .with_context(|| format!("parsing .wfg file: {}", wfg_path.display()))?;
+        .with_context(|| format!("parsing .wfg file: {}", entry.display()))?;
 
-    let (schemas, wfl_files) = load_from_uses(&wfg, wfg_path, vars)?;
+    let (mut schemas, mut wfl_files) = load_from_uses(&wfg, entry, vars)?;
+    schemas.extend(load_schema_files(extra_schemas)?);
+    let schemas = merge_schemas(schemas)?;
+    wfl_files.extend(load_wfl_files(extra_wfl, vars)?);
 
     let mut rule_plans = Vec::new();
+    let mut compile_errors = Vec::new();
+    let mut compile_warnings = Vec::new();
     for wfl_file in &wfl_files {
-        let plans = wf_lang::compile_wfl(wfl_file, &schemas).context("compiling .wfl rules")?;
-        rule_plans.extend(plans);
+        let compiled =
+            wf_lang::compile_wfl_with_diagnostics(wfl_file, &schemas).and_then(|output| {
+                compile_warnings.extend(output.warnings().cloned());
+                output.into_plans()
+            });
+        match compiled {
+            Ok(plans) => rule_plans.extend(plans),
+            Err(e) => compile_errors.push(e),
+        }
     }
 
-    Ok(LoadedScenario {
+    Ok(CompiledProject {
         wfg,
         schemas,
         wfl_files,
         rule_plans,
+        compile_errors,
+        compile_warnings,
     })
 }
 
+/// Load `.wfs` files given explicitly via `--schema` (alias `--ws`).
+///
+/// Each entry is a path or glob resolved against the current directory; a
+/// glob that matches nothing is an error, and a file matched by several
+/// entries is loaded once.
+pub fn load_schema_files<S: AsRef<str>>(
+    patterns: &[S],
+) -> anyhow::Result<Vec<wf_lang::WindowSchema>> {
+    let paths =
+        wf_config::resolve_patterns(patterns, Path::new(".")).context("resolving --schema")?;
+    let mut schemas = Vec::new();
+    for path in paths {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading .wfs file: {}", path.display()))?;
+        let parsed = wf_lang::parse_wfs(&content)
+            .with_context(|| format!("parsing .wfs file: {}", path.display()))?;
+        schemas.extend(parsed);
+    }
+    Ok(schemas)
+}
+
+/// Drop repeated window definitions, keeping the first.
+///
+/// The same window commonly arrives twice, e.g. from a `use` declaration and
+/// a `--schema` glob covering the same file. Two *different* definitions of
+/// one window name are an error.
+pub fn merge_schemas(
+    schemas: Vec<wf_lang::WindowSchema>,
+) -> anyhow::Result<Vec<wf_lang::WindowSchema>> {
+    let mut merged: Vec<wf_lang::WindowSchema> = Vec::with_capacity(schemas.len());
+    for schema in schemas {
+        match merged.iter().find(|s| s.name == schema.name) {
+            Some(existing) if *existing == schema => {}
+            Some(_) => anyhow::bail!("conflicting definitions for window '{}'", schema.name),
+            None => merged.push(schema),
+        }
+    }
+    Ok(merged)
+}
+
+/// Load `.wfl` files given explicitly (e.g. via `--wfl`), preprocessing
+/// each with `vars`.
+pub fn load_wfl_files(
+    paths: &[PathBuf],
+    vars: &HashMap<String, String>,
+) -> anyhow::Result<Vec<wf_lang::ast::WflFile>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading .wfl file: {}", path.display()))?;
+        let source = wf_lang::preprocess_vars_with_env(&raw, vars)
+            .with_context(|| format!("preprocessing .wfl file: {}", path.display()))?;
+        let parsed = wf_lang::parse_wfl(&source)
+            .with_context(|| format!("parsing .wfl file: {}", path.display()))?;
+        files.push(parsed);
+    }
+    Ok(files)
+}
+
 /// Load `.wfs` schemas and `.wfl` rule files referenced by `use` declarations.
 ///
-/// Paths in `use` declarations are resolved relative to `wfg_path`'s directory.
-/// `.wfl` sources are preprocessed with `vars` (and environment variable
+/// Paths in `use` declarations are resolved relative to `wfg_path`'s directory
+/// and may be glob patterns (`use "../schemas/*.wfs"`); a pattern that
+/// matches nothing is an error, and a file matched by several declarations
+/// is loaded once. `.wfl` sources are preprocessed with `vars` (and environment variable
 /// fallback) via [`wf_lang::preprocess_vars_with_env`] before parsing.
 pub fn load_from_uses(
     wfg: &WfgFile,
     wfg_path: &Path,
     vars: &HashMap<String, String>,
 ) -> anyhow::Result<(Vec<wf_lang::WindowSchema>, Vec<wf_lang::ast::WflFile>)> {
-    let base_dir = wfg_path.parent().unwrap_or_else(|| Path::new("."));
+    // A scenario read from stdin (`-`) resolves its uses against the cwd.
+    let base_dir = match wfg_path.parent() {
+        Some(dir) if !wf_config::is_stdin(wfg_path) => dir,
+        _ => Path::new("."),
+    };
 
     let mut schemas = Vec::new();
     let mut wfl_files = Vec::new();
 
-    for use_decl in &wfg.uses {
-        let resolved = base_dir.join(&use_decl.path);
+    let patterns: Vec<&str> = wfg.uses.iter().map(|u| u.path.as_str()).collect();
+    let resolved_uses =
+        wf_config::resolve_patterns(&patterns, base_dir).context("resolving use declarations")?;
+
+    for resolved in resolved_uses {
         let ext = resolved.extension().and_then(|e| e.to_str()).unwrap_or("");
 
         match ext {
@@ -85,7 +212,7 @@ pub fn load_from_uses(
                 anyhow::bail!(
                     "unsupported file extension '{}' in use declaration: {}",
                     other,
-                    use_decl.path
+                    resolved.display()
                 );
             }
         }
@@ -93,3 +220,163 @@ pub fn load_from_uses(
 
     Ok((schemas, wfl_files))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_schema(dir: &Path, file: &str, window: &str) {
+        let source = format!(
+            "window {window} {{\n    stream = \"{window}\"\n    time = event_time\n    over = 5m\n    fields {{\n        sip: ip\n        event_time: time\n    }}\n}}\n"
+        );
+        std::fs::write(dir.join(file), source).unwrap();
+    }
+
+    fn scenario(dir: &Path, uses: &[&str]) -> (WfgFile, std::path::PathBuf) {
+        let mut source: String = uses.iter().map(|u| format!("use \"{u}\"\n")).collect();
+        source
+            .push_str("#[duration=1m]\nscenario s<seed=1> {\n  traffic { stream a gen 1/s }\n}\n");
+        let path = dir.join("scenarios/s.wfg");
+        std::fs::write(&path, &source).unwrap();
+        (parse_wfg(&source).unwrap(), path)
+    }
+
+    #[test]
+    fn use_glob_loads_every_matching_schema_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("schemas")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("scenarios")).unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        write_schema(&schema_dir, "a.wfs", "a");
+        write_schema(&schema_dir, "b.wfs", "b");
+        write_schema(&schema_dir, "c.wfs", "c");
+
+        let (wfg, path) = scenario(tmp.path(), &["../schemas/*.wfs", "../schemas/b.wfs"]);
+        let (schemas, wfl_files) = load_from_uses(&wfg, &path, &HashMap::new()).unwrap();
+
+        let names: Vec<_> = schemas.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert!(wfl_files.is_empty());
+    }
+
+    #[test]
+    fn use_glob_matching_nothing_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("scenarios")).unwrap();
+
+        let (wfg, path) = scenario(tmp.path(), &["../schemas/*.wfs"]);
+        let err = load_from_uses(&wfg, &path, &HashMap::new()).unwrap_err();
+        assert!(format!("{err:#}").contains("matched no files"), "{err:#}");
+    }
+
+    #[test]
+    fn schema_glob_dedups_against_uses() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("schemas")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("scenarios")).unwrap();
+        let schema_dir = tmp.path().join("schemas");
+        write_schema(&schema_dir, "a.wfs", "a");
+        write_schema(&schema_dir, "b.wfs", "b");
+
+        let (_, path) = scenario(tmp.path(), &["../schemas/a.wfs"]);
+        let glob = schema_dir.join("*.wfs").to_string_lossy().into_owned();
+        let project = compile_project(&path, &[glob], &[], &HashMap::new()).unwrap();
+
+        let names: Vec<_> = project.schemas.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    #[test]
+    fn conflicting_schema_definitions_are_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("schemas")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("other")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("scenarios")).unwrap();
+        write_schema(&tmp.path().join("schemas"), "a.wfs", "a");
+        std::fs::write(
+            tmp.path().join("other/a.wfs"),
+            "window a {\n    over = 0\n    fields {\n        sip: ip\n    }\n}\n",
+        )
+        .unwrap();
+
+        let (_, path) = scenario(tmp.path(), &["../schemas/a.wfs"]);
+        let extra = tmp
+            .path()
+            .join("other/a.wfs")
+            .to_string_lossy()
+            .into_owned();
+        let err = compile_project(&path, &[extra], &[], &HashMap::new())
+            .err()
+            .unwrap();
+        assert!(
+            format!("{err:#}").contains("conflicting definitions for window 'a'"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn compile_project_collects_plans_and_compile_errors() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("schemas")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("rules")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("scenarios")).unwrap();
+        write_schema(&tmp.path().join("schemas"), "a.wfs", "a");
+        std::fs::write(
+            tmp.path().join("schemas/alerts.wfs"),
+            "window alerts {\n    over = 0\n    fields {\n        sip: ip\n    }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("rules/burst.wfl"),
+            "rule burst {\n    events { e : a }\n    match<sip:5m> { on event { e | count >= ${MIN:3}; } } -> score(50.0)\n    entity(ip, e.sip)\n    yield alerts (sip = e.sip)\n}\n",
+        )
+        .unwrap();
+        // References a window no schema defines → compile error, not a load error.
+        let broken = tmp.path().join("rules/broken.wfl");
+        std::fs::write(
+            &broken,
+            "rule broken {\n    events { e : missing }\n    match<sip:5m> { on event { e | count >= 1; } } -> score(50.0)\n    entity(ip, e.sip)\n    yield alerts (sip = e.sip)\n}\n",
+        )
+        .unwrap();
+
+        let (_, path) = scenario(tmp.path(), &["../schemas/a.wfs", "../rules/burst.wfl"]);
+        // The yield target is supplied only as an extra schema
+        let extra_ws = vec![
+            tmp.path()
+                .join("schemas/alerts.wfs")
+                .to_string_lossy()
+                .into_owned(),
+        ];
+        let vars = HashMap::from([("MIN".to_string(), "5".to_string())]);
+        let project = compile_project(&path, &extra_ws, &[broken], &vars).unwrap();
+
+        let names: Vec<_> = project.schemas.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a", "alerts"]);
+        assert_eq!(project.wfl_files.len(), 2);
+        assert_eq!(project.rule_plans.len(), 1);
+        assert_eq!(project.rule_plans[0].name, "burst");
+        assert_eq!(
+            project.rule_plans[0].match_plan.event_steps[0].branches[0]
+                .agg
+                .threshold,
+            wf_lang::ast::Expr::Number(5.0)
+        );
+        assert_eq!(project.compile_errors.len(), 1);
+        // Warnings (here: no `limits` block) are kept for every file,
+        // including the one that failed.
+        let warned: Vec<_> = project
+            .compile_warnings
+            .iter()
+            .filter_map(|w| w.rule.as_deref())
+            .collect();
+        assert_eq!(warned, ["burst", "broken"]);
+
+        // load_scenario treats the same compile error as fatal
+        let (_, path) = scenario(tmp.path(), &["../schemas/*.wfs", "../rules/*.wfl"]);
+        let err = load_scenario(&path, &vars).err().unwrap();
+        assert!(
+            format!("{err:#}").contains("compiling .wfl rules"),
+            "{err:#}"
+        );
+    }
+}
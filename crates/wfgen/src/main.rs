@@ -1,15 +1,20 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 
 mod cmd_bench;
+mod cmd_diff;
 mod cmd_gen;
 mod cmd_helpers;
+mod cmd_inspect;
 mod cmd_lint;
 mod cmd_send;
 mod cmd_verify;
 mod tcp_send;
 
+use cmd_helpers::{send_options, tls_settings};
+use tcp_send::SendOptions;
+
 #[derive(Parser)]
 #[command(name = "wfgen", about = "WarpFusion test data generator")]
 struct Cli {
@@ -25,17 +30,22 @@ enum Commands {
         #[arg(long)]
         scenario: PathBuf,
 
-        /// Output format: "jsonl" or "arrow" ("arrow-ipc"/"ipc" aliases)
+        /// Output format: "jsonl", "arrow" ("arrow-ipc"/"ipc" aliases), "parquet" or "csv"
         #[arg(long, default_value = "jsonl")]
         format: String,
 
+        /// Parquet compression: "snappy", "zstd", "gzip" or "none"
+        #[arg(long, default_value = "snappy")]
+        compression: String,
+
         /// Output directory
         #[arg(long)]
         out: PathBuf,
 
-        /// Additional .wfs schema files (beyond those in `use` declarations)
-        #[arg(long)]
-        ws: Vec<PathBuf>,
+        /// Additional .wfs schema file or glob, repeatable (alias `--ws`);
+        /// windows already loaded by `use` are deduplicated
+        #[arg(long, alias = "ws", value_name = "PATH_OR_GLOB")]
+        schema: Vec<String>,
 
         /// Additional .wfl rule files (beyond those in `use` declarations)
         #[arg(long)]
@@ -52,27 +62,74 @@ enum Commands {
         /// Runtime TCP address used with --send, e.g. 127.0.0.1:9800
         #[arg(long, default_value = "127.0.0.1:9800")]
         addr: String,
+
+        /// Retry a failed connect or send this many times before giving up
+        #[arg(long, default_value_t = 0)]
+        connect_retries: u32,
+
+        /// Initial delay between retries, doubled per attempt (e.g. "500ms", "2s")
+        #[arg(long, default_value = "500ms")]
+        retry_backoff: String,
+
+        /// Maximum rows per Arrow IPC frame (default: one frame per window).
+        /// Smaller batches lower peak sender memory per frame.
+        #[arg(long)]
+        batch_size: Option<usize>,
+
+        /// Abort when the runtime accepts no bytes for this long (e.g. "30s");
+        /// omit to wait indefinitely
+        #[arg(long)]
+        timeout: Option<String>,
+
+        /// Generate and write events incrementally in constant memory
+        /// (jsonl/csv only; no faults, expected output or --send)
+        #[arg(long)]
+        stream: bool,
+
+        /// Print per-stream and per-inject event counts without generating
+        /// or writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Override the scenario's `seed` (fault injection derives from it too)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Keep only the first N expected (oracle) alerts, by emit time
+        #[arg(long)]
+        limit: Option<usize>,
     },
     /// Lint (validate) a .wfg scenario file
     Lint {
-        /// Path to the .wfg scenario file
+        /// Path to the .wfg scenario file (`-` reads standard input; `use`
+        /// paths then resolve against the current directory)
         scenario: PathBuf,
 
-        /// Additional .wfs schema files (beyond those in `use` declarations)
-        #[arg(long)]
-        ws: Vec<PathBuf>,
+        /// Additional .wfs schema file or glob, repeatable (alias `--ws`);
+        /// windows already loaded by `use` are deduplicated
+        #[arg(long, alias = "ws", value_name = "PATH_OR_GLOB")]
+        schema: Vec<String>,
 
         /// Additional .wfl rule files (beyond those in `use` declarations)
         #[arg(long)]
         wfl: Vec<PathBuf>,
+
+        /// Exit non-zero when any rule warning is reported, not just errors
+        #[arg(long)]
+        fail_on_warning: bool,
     },
     /// Verify actual alerts against oracle expectations
     Verify {
         /// Path to the oracle (expected) JSONL file
-        #[arg(long)]
-        expected: PathBuf,
+        #[arg(long, required_unless_present = "expected_from_actual")]
+        expected: Option<PathBuf>,
 
-        /// Path to the actual alerts JSONL file
+        /// Use a trusted prior run's actual alerts (JSONL, or Arrow IPC for
+        /// .arrow / .ipc) as the expected baseline instead of an oracle
+        #[arg(long, conflicts_with = "expected")]
+        expected_from_actual: Option<PathBuf>,
+
+        /// Path to the actual alerts file (JSONL, or Arrow IPC for .arrow / .ipc)
         #[arg(long)]
         actual: PathBuf,
 
@@ -88,11 +145,55 @@ enum Commands {
         #[arg(long)]
         meta: Option<PathBuf>,
 
+        /// Output format: "json", "markdown" or "junit" (default: json)
+        #[arg(long, default_value = "json")]
+        format: String,
+
+        /// Fail on unparseable emit_time / fired_at instead of treating them as epoch 0
+        #[arg(long)]
+        strict_time: bool,
+
+        /// Format of --actual: "jsonl" or "arrow" (default: by file extension)
+        #[arg(long)]
+        actual_format: Option<String>,
+
+        /// Compare only the first N alerts (by time) on each side; the report
+        /// is then marked truncated and never passes
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Compare two oracle JSONL files (e.g. before and after a rule change)
+    Diff {
+        /// Path to the baseline oracle JSONL file
+        #[arg(long)]
+        old: PathBuf,
+
+        /// Path to the updated oracle JSONL file
+        #[arg(long)]
+        new: PathBuf,
+
+        /// Score tolerance below which a paired alert counts as unchanged
+        #[arg(long)]
+        score_tolerance: Option<f64>,
+
+        /// Time tolerance in seconds below which a paired alert counts as unchanged
+        #[arg(long)]
+        time_tolerance: Option<f64>,
+
         /// Output format: "json" or "markdown" (default: json)
         #[arg(long, default_value = "json")]
         format: String,
     },
-    /// Send generated JSONL events to wfusion over TCP + Arrow IPC
+    /// Print the schema and first rows of an Arrow IPC file as JSON
+    Inspect {
+        /// Path to the Arrow IPC file (e.g. written by `gen --format arrow`)
+        file: PathBuf,
+
+        /// Number of rows to print
+        #[arg(long, default_value_t = 10)]
+        rows: usize,
+    },
+    /// Send generated JSONL events to wfusion over TCP (optionally TLS) + Arrow IPC
     Send {
         /// Path to the .wfg scenario file (used to load schemas)
         #[arg(long)]
@@ -106,9 +207,46 @@ enum Commands {
         #[arg(long, default_value = "127.0.0.1:9800")]
         addr: String,
 
-        /// Additional .wfs schema files (beyond those in `use` declarations)
+        /// Additional .wfs schema file or glob, repeatable (alias `--ws`);
+        /// windows already loaded by `use` are deduplicated
+        #[arg(long, alias = "ws", value_name = "PATH_OR_GLOB")]
+        schema: Vec<String>,
+
+        /// Replay with the original inter-event timing scaled by this factor
+        /// (1 = real time, 10 = ten times faster); omit to send at full speed
         #[arg(long)]
-        ws: Vec<PathBuf>,
+        speed: Option<f64>,
+
+        /// Retry a failed connect or send this many times before giving up
+        #[arg(long, default_value_t = 0)]
+        connect_retries: u32,
+
+        /// Initial delay between retries, doubled per attempt (e.g. "500ms", "2s")
+        #[arg(long, default_value = "500ms")]
+        retry_backoff: String,
+
+        /// Maximum rows per Arrow IPC frame (default: one frame per window).
+        /// Smaller batches lower peak sender memory per frame.
+        #[arg(long)]
+        batch_size: Option<usize>,
+
+        /// Abort when the runtime accepts no bytes for this long (e.g. "30s");
+        /// omit to wait indefinitely
+        #[arg(long)]
+        timeout: Option<String>,
+
+        /// Connect over TLS (runtime configured with `[server.tls]`)
+        #[arg(long)]
+        tls: bool,
+
+        /// PEM file with the CA certificate(s) to trust for --tls
+        /// (default: the Mozilla root set)
+        #[arg(long, requires = "tls")]
+        tls_ca: Option<PathBuf>,
+
+        /// Name to verify in the server certificate (default: host of --addr)
+        #[arg(long, requires = "tls")]
+        tls_server_name: Option<String>,
     },
     /// Measure generation throughput (optional TCP send to wfusion)
     Bench {
@@ -116,9 +254,10 @@ enum Commands {
         #[arg(long)]
         scenario: PathBuf,
 
-        /// Additional .wfs schema files (beyond those in `use` declarations)
-        #[arg(long)]
-        ws: Vec<PathBuf>,
+        /// Additional .wfs schema file or glob, repeatable (alias `--ws`);
+        /// windows already loaded by `use` are deduplicated
+        #[arg(long, alias = "ws", value_name = "PATH_OR_GLOB")]
+        schema: Vec<String>,
 
         /// Additional .wfl rule files (beyond those in `use` declarations)
         #[arg(long)]
@@ -135,6 +274,16 @@ enum Commands {
         /// Runtime TCP address used with --send, e.g. 127.0.0.1:9800
         #[arg(long, default_value = "127.0.0.1:9800")]
         addr: String,
+
+        /// Override the scenario's `seed`
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Target shell: bash, zsh, fish, elvish or powershell
+        shell: clap_complete::Shell,
     },
 }
 
@@ -145,42 +294,120 @@ fn main() -> anyhow::Result<()> {
         Commands::Gen {
             scenario,
             format,
+            compression,
+            out,
+            schema,
+            wfl,
+            no_oracle,
+            send,
+            addr,
+            connect_retries,
+            retry_backoff,
+            batch_size,
+            timeout,
+            stream,
+            dry_run,
+            seed,
+            limit,
+        } => cmd_gen::run(
+            scenario,
+            format,
+            compression,
             out,
-            ws,
+            schema,
             wfl,
             no_oracle,
             send,
             addr,
-        } => cmd_gen::run(scenario, format, out, ws, wfl, no_oracle, send, addr),
-        Commands::Lint { scenario, ws, wfl } => cmd_lint::run(scenario, ws, wfl),
+            send_options(
+                connect_retries,
+                &retry_backoff,
+                batch_size,
+                timeout.as_deref(),
+            )?,
+            stream,
+            dry_run,
+            seed,
+            limit,
+        ),
+        Commands::Lint {
+            scenario,
+            schema,
+            wfl,
+            fail_on_warning,
+        } => cmd_lint::run(scenario, schema, wfl, fail_on_warning),
         Commands::Verify {
             expected,
+            expected_from_actual,
             actual,
             score_tolerance,
             time_tolerance,
             meta,
             format,
+            strict_time,
+            actual_format,
+            limit,
         } => cmd_verify::run(
             expected,
+            expected_from_actual,
             actual,
             score_tolerance,
             time_tolerance,
             meta,
             format,
+            strict_time,
+            actual_format,
+            limit,
         ),
+        Commands::Diff {
+            old,
+            new,
+            score_tolerance,
+            time_tolerance,
+            format,
+        } => cmd_diff::run(old, new, score_tolerance, time_tolerance, format),
+        Commands::Inspect { file, rows } => cmd_inspect::run(file, rows),
         Commands::Send {
             scenario,
             input,
             addr,
-            ws,
-        } => cmd_send::run(scenario, input, addr, ws),
+            schema,
+            speed,
+            connect_retries,
+            retry_backoff,
+            batch_size,
+            timeout,
+            tls,
+            tls_ca,
+            tls_server_name,
+        } => cmd_send::run(
+            scenario,
+            input,
+            addr,
+            schema,
+            speed,
+            SendOptions {
+                tls: tls_settings(tls, tls_ca.as_deref(), tls_server_name)?,
+                ..send_options(
+                    connect_retries,
+                    &retry_backoff,
+                    batch_size,
+                    timeout.as_deref(),
+                )?
+            },
+        ),
         Commands::Bench {
             scenario,
-            ws,
+            schema,
             wfl,
             duration,
             send,
             addr,
-        } => cmd_bench::run(scenario, ws, wfl, duration, send, addr),
+            seed,
+        } => cmd_bench::run(scenario, schema, wfl, duration, send, addr, seed),
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "wfgen", &mut std::io::stdout());
+            Ok(())
+        }
     }
 }
@@ -5,8 +5,9 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use wf_core::rule::{CepStateMachine, Event, RuleExecutor, StepResult, Value};
-use wf_lang::plan::{ConvPlan, RulePlan};
+use wf_core::alert::OutputRecord;
+use wf_core::rule::{CepStateMachine, Event, RuleExecutor, StepResult, Value, bind_filter_passes};
+use wf_lang::plan::{ConvPlan, ExprPlan, RulePlan};
 
 use crate::datagen::stream_gen::GenEvent;
 
@@ -22,17 +23,38 @@ pub struct OracleAlert {
     pub emit_time: String,
 }
 
+impl From<OutputRecord> for OracleAlert {
+    fn from(record: OutputRecord) -> Self {
+        Self {
+            rule_name: record.rule_name,
+            score: record.score,
+            entity_type: record.entity_type,
+            entity_id: record.entity_id,
+            origin: record.origin.as_str().to_string(),
+            emit_time: record.fired_at,
+        }
+    }
+}
+
 /// Result of oracle evaluation.
 pub struct OracleResult {
     pub alerts: Vec<OracleAlert>,
 }
 
+/// Minimum `events × rules` before [`run_oracle`] fans rules out across
+/// threads; smaller scenarios stay sequential to avoid spawn overhead.
+const PARALLEL_MIN_WORK: usize = 100_000;
+
 /// Run the reference evaluator on generated events.
 ///
 /// Creates a `CepStateMachine` + `RuleExecutor` per rule, feeds events in
 /// timestamp order, and collects oracle alerts. Uses event-time nanoseconds
 /// for deterministic window expiry.
 ///
+/// Rule engines are independent, so large scenarios evaluate them on
+/// parallel threads. Alerts are returned sorted by rule name, then emit
+/// time, which is identical whether or not the rules ran in parallel.
+///
 /// SC7: when `injected_rules` is `Some`, only the rules whose names appear
 /// in the set are evaluated. Rules without `inject` coverage are skipped so
 /// the oracle doesn't generate spurious expected hits from baseline traffic.
@@ -42,6 +64,25 @@ pub fn run_oracle(
     scenario_start: &DateTime<Utc>,
     scenario_duration: &Duration,
     injected_rules: Option<&std::collections::HashSet<String>>,
+) -> anyhow::Result<OracleResult> {
+    let parallel = events.len().saturating_mul(rule_plans.len()) >= PARALLEL_MIN_WORK;
+    run_oracle_with(
+        events,
+        rule_plans,
+        scenario_start,
+        scenario_duration,
+        injected_rules,
+        parallel,
+    )
+}
+
+fn run_oracle_with(
+    events: &[GenEvent],
+    rule_plans: &[RulePlan],
+    scenario_start: &DateTime<Utc>,
+    scenario_duration: &Duration,
+    injected_rules: Option<&std::collections::HashSet<String>>,
+    parallel: bool,
 ) -> anyhow::Result<OracleResult> {
     if rule_plans.is_empty() {
         return Ok(OracleResult { alerts: vec![] });
@@ -57,104 +98,137 @@ pub fn run_oracle(
         })
         .map(|plan| {
             let alias_map = build_window_alias_map(plan);
+            let filters = plan
+                .binds
+                .iter()
+                .filter_map(|b| Some((b.alias.clone(), b.filter.clone()?)))
+                .collect();
             RuleEngine {
                 sm: CepStateMachine::new(plan.name.clone(), plan.match_plan.clone(), None),
                 executor: RuleExecutor::new(plan.clone()),
                 conv_plan: plan.conv_plan.clone(),
                 alias_map,
+                filters,
             }
         })
         .collect();
 
-    let mut alerts = Vec::new();
+    // Convert once; every engine reads the same events (caller should have
+    // sorted them by timestamp).
+    let core_events: Vec<(&GenEvent, Event, i64)> = events
+        .iter()
+        .map(|e| {
+            let nanos = e.timestamp.timestamp_nanos_opt().unwrap_or(0);
+            (e, gen_event_to_core(e), nanos)
+        })
+        .collect();
+
+    let eos_time =
+        *scenario_start + chrono::Duration::from_std(*scenario_duration).unwrap_or_default();
+    let eos_nanos = eos_time.timestamp_nanos_opt().unwrap_or(i64::MAX);
+
+    let per_engine: Vec<Vec<OracleAlert>> = if parallel && engines.len() > 1 {
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let chunk_size = engines.len().div_ceil(threads);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = engines
+                .chunks_mut(chunk_size)
+                .map(|chunk| {
+                    let core_events = &core_events;
+                    scope.spawn(move || {
+                        chunk
+                            .iter_mut()
+                            .map(|engine| engine.run(core_events, eos_nanos))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("oracle rule thread panicked"))
+                .collect()
+        })
+    } else {
+        engines
+            .iter_mut()
+            .map(|engine| engine.run(&core_events, eos_nanos))
+            .collect()
+    };
+
+    // Stable sort: alerts of one rule at the same emit time keep the order
+    // that rule's engine produced them in.
+    let mut alerts: Vec<OracleAlert> = per_engine.into_iter().flatten().collect();
+    alerts.sort_by(|a, b| {
+        a.rule_name
+            .cmp(&b.rule_name)
+            .then_with(|| a.emit_time.cmp(&b.emit_time))
+    });
 
-    // Process events in order (caller should have sorted by timestamp)
-    for event in events {
-        let event_nanos = event.timestamp.timestamp_nanos_opt().unwrap_or(0);
+    Ok(OracleResult { alerts })
+}
 
-        let core_event = gen_event_to_core(event);
+// ---------------------------------------------------------------------------
+// Internal types and helpers
+// ---------------------------------------------------------------------------
 
-        for engine in &mut engines {
+struct RuleEngine {
+    sm: CepStateMachine,
+    executor: RuleExecutor,
+    conv_plan: Option<ConvPlan>,
+    /// window_name → Vec<bind_alias> for routing events to all matching aliases
+    alias_map: HashMap<String, Vec<String>>,
+    /// bind_alias → filter, mirroring the runtime's pre-advance filtering
+    filters: HashMap<String, ExprPlan>,
+}
+
+impl RuleEngine {
+    /// Feed every event through this rule, then flush at end-of-scenario.
+    fn run(&mut self, events: &[(&GenEvent, Event, i64)], eos_nanos: i64) -> Vec<OracleAlert> {
+        let mut alerts = Vec::new();
+
+        for (event, core_event, event_nanos) in events {
             // Scan for expired instances first (with conv)
-            let expired = engine
-                .sm
-                .scan_expired_at_with_conv(event_nanos, engine.conv_plan.as_ref());
-            for close_out in expired {
-                if let Ok(Some(alert_record)) = engine.executor.execute_close(&close_out) {
-                    alerts.push(OracleAlert {
-                        rule_name: alert_record.rule_name,
-                        score: alert_record.score,
-                        entity_type: alert_record.entity_type,
-                        entity_id: alert_record.entity_id,
-                        origin: alert_record.origin.as_str().to_string(),
-                        emit_time: alert_record.fired_at.clone(),
-                    });
-                }
-            }
+            self.scan_expired(*event_nanos, &mut alerts);
 
             // Find bind aliases for this event's window
-            let bind_aliases = match engine.alias_map.get(&event.window_name) {
-                Some(aliases) => aliases,
-                None => continue, // this rule doesn't use this window
+            let Some(bind_aliases) = self.alias_map.get(&event.window_name) else {
+                continue; // this rule doesn't use this window
             };
 
             // Advance the state machine for each alias bound to this window
             for bind_alias in bind_aliases {
-                let result = engine.sm.advance_at(bind_alias, &core_event, event_nanos);
+                if let Some(filter) = self.filters.get(bind_alias)
+                    && !bind_filter_passes(filter, core_event)
+                {
+                    continue;
+                }
+                let result = self.sm.advance_at(bind_alias, core_event, *event_nanos);
 
                 if let StepResult::Matched(ctx) = result
-                    && let Ok(alert_record) = engine.executor.execute_match(&ctx)
+                    && let Ok(records) = self.executor.execute_match(&ctx)
                 {
-                    alerts.push(OracleAlert {
-                        rule_name: alert_record.rule_name,
-                        score: alert_record.score,
-                        entity_type: alert_record.entity_type,
-                        entity_id: alert_record.entity_id,
-                        origin: alert_record.origin.as_str().to_string(),
-                        emit_time: alert_record.fired_at.clone(),
-                    });
+                    alerts.extend(records.into_iter().map(OracleAlert::from));
                 }
             }
         }
-    }
 
-    // End-of-scenario sweep: flush remaining instances
-    let eos_time =
-        *scenario_start + chrono::Duration::from_std(*scenario_duration).unwrap_or_default();
-    let eos_nanos = eos_time.timestamp_nanos_opt().unwrap_or(i64::MAX);
+        // End-of-scenario sweep: flush remaining instances
+        self.scan_expired(eos_nanos, &mut alerts);
+        alerts
+    }
 
-    for engine in &mut engines {
-        let expired = engine
+    fn scan_expired(&mut self, watermark_nanos: i64, alerts: &mut Vec<OracleAlert>) {
+        let expired = self
             .sm
-            .scan_expired_at_with_conv(eos_nanos, engine.conv_plan.as_ref());
-
+            .scan_expired_at_with_conv(watermark_nanos, self.conv_plan.as_ref());
         for close_out in expired {
-            if let Ok(Some(alert_record)) = engine.executor.execute_close(&close_out) {
-                alerts.push(OracleAlert {
-                    rule_name: alert_record.rule_name,
-                    score: alert_record.score,
-                    entity_type: alert_record.entity_type,
-                    entity_id: alert_record.entity_id,
-                    origin: alert_record.origin.as_str().to_string(),
-                    emit_time: alert_record.fired_at.clone(),
-                });
+            if let Ok(records) = self.executor.execute_close(&close_out) {
+                alerts.extend(records.into_iter().map(OracleAlert::from));
             }
         }
     }
-
-    Ok(OracleResult { alerts })
-}
-
-// ---------------------------------------------------------------------------
-// Internal types and helpers
-// ---------------------------------------------------------------------------
-
-struct RuleEngine {
-    sm: CepStateMachine,
-    executor: RuleExecutor,
-    conv_plan: Option<ConvPlan>,
-    /// window_name → Vec<bind_alias> for routing events to all matching aliases
-    alias_map: HashMap<String, Vec<String>>,
 }
 
 /// Build a mapping from window name to ALL bind aliases for a rule.
@@ -182,7 +256,10 @@ fn gen_event_to_core(event: &GenEvent) -> Event {
 fn json_to_core_value(v: &serde_json::Value) -> Option<Value> {
     match v {
         serde_json::Value::String(s) => Some(Value::Str(s.clone())),
-        serde_json::Value::Number(n) => n.as_f64().map(Value::Number),
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .map(Value::Int)
+            .or_else(|| n.as_f64().map(Value::Number)),
         serde_json::Value::Bool(b) => Some(Value::Bool(*b)),
         _ => None,
     }
@@ -1,6 +1,8 @@
 use std::time::Duration;
 
 use chrono::Utc;
+use serde_json::json;
+use wf_core::rule::Value;
 use wf_lang::ast::{CloseMode, CmpOp, Expr, FieldRef, FieldSelector, Measure, Transform};
 use wf_lang::plan::{
     AggPlan, BindPlan, BranchPlan, ConvChainPlan, ConvOpPlan, ConvPlan, EntityPlan, MatchPlan,
@@ -8,7 +10,7 @@ use wf_lang::plan::{
 };
 
 use crate::datagen::stream_gen::GenEvent;
-use crate::oracle::run_oracle;
+use crate::oracle::{json_to_core_value, run_oracle, run_oracle_with};
 
 fn make_simple_rule_plan() -> RulePlan {
     RulePlan {
@@ -38,23 +40,26 @@ fn make_simple_rule_plan() -> RulePlan {
             }],
             close_steps: vec![],
             close_mode: CloseMode::Or,
+            max_out_of_orderness: Duration::ZERO,
+            captures: vec![],
         },
         joins: vec![],
         entity_plan: EntityPlan {
             entity_type: "ip".to_string(),
             entity_id_expr: Expr::Field(FieldRef::Simple("sip".to_string())),
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: "alerts".to_string(),
             version: None,
             fields: vec![],
-        },
+        }],
         score_plan: ScorePlan {
             expr: Expr::Number(85.0),
         },
         pattern_origin: None,
         conv_plan: None,
         limits_plan: None,
+        priority: 0,
     }
 }
 
@@ -197,23 +202,26 @@ fn multi_alias_same_window_both_receive_events() {
             ],
             close_steps: vec![],
             close_mode: CloseMode::Or,
+            max_out_of_orderness: Duration::ZERO,
+            captures: vec![],
         },
         joins: vec![],
         entity_plan: EntityPlan {
             entity_type: "ip".to_string(),
             entity_id_expr: Expr::Field(FieldRef::Simple("sip".to_string())),
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: "alerts".to_string(),
             version: None,
             fields: vec![],
-        },
+        }],
         score_plan: ScorePlan {
             expr: Expr::Number(90.0),
         },
         pattern_origin: None,
         conv_plan: None,
         limits_plan: None,
+        priority: 0,
     };
 
     let start: chrono::DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
@@ -305,15 +313,11 @@ fn make_scan_event(alias: &str, window: &str, sip: &str, dport: u16, ts: &str) -
     }
 }
 
-/// Conv with mixed qualifying/non-qualifying outputs in the oracle path.
-///
-/// 4 IPs in one fixed window: 3 qualify via `on close (distinct >= 3)`, 1
-/// does not (only 2 distinct ports). Conv `sort(-scan) | top(2)` must
-/// operate only on qualifying outputs, producing 2 alerts.
-#[test]
-fn conv_top_filters_non_qualifying() {
-    let plan = RulePlan {
-        name: "conv_mixed".to_string(),
+/// Port-scan rule: per-`sip` fixed 1h window, `on close (distinct(dport) >= 3)`,
+/// conv `sort(-scan) | top(top)`.
+fn conv_scan_plan(name: &str, top: u64) -> RulePlan {
+    RulePlan {
+        name: name.to_string(),
         binds: vec![BindPlan {
             alias: "c".to_string(),
             window: "ConnWindow".to_string(),
@@ -352,17 +356,19 @@ fn conv_top_filters_non_qualifying() {
                 }],
             }],
             close_mode: CloseMode::And,
+            max_out_of_orderness: Duration::ZERO,
+            captures: vec![],
         },
         joins: vec![],
         entity_plan: EntityPlan {
             entity_type: "ip".to_string(),
             entity_id_expr: Expr::Field(FieldRef::Simple("sip".to_string())),
         },
-        yield_plan: YieldPlan {
+        yield_plans: vec![YieldPlan {
             target: "alerts".to_string(),
             version: None,
             fields: vec![],
-        },
+        }],
         score_plan: ScorePlan {
             expr: Expr::Number(80.0),
         },
@@ -374,12 +380,23 @@ fn conv_top_filters_non_qualifying() {
                         expr: Expr::Field(FieldRef::Simple("scan".into())),
                         descending: true,
                     }]),
-                    ConvOpPlan::Top(2),
+                    ConvOpPlan::Top(top),
                 ],
             }],
         }),
         limits_plan: None,
-    };
+        priority: 0,
+    }
+}
+
+/// Conv with mixed qualifying/non-qualifying outputs in the oracle path.
+///
+/// 4 IPs in one fixed window: 3 qualify via `on close (distinct >= 3)`, 1
+/// does not (only 2 distinct ports). Conv `sort(-scan) | top(2)` must
+/// operate only on qualifying outputs, producing 2 alerts.
+#[test]
+fn conv_top_filters_non_qualifying() {
+    let plan = conv_scan_plan("conv_mixed", 2);
 
     let start: chrono::DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
     let duration = Duration::from_secs(7200); // 2h > 1h window → expires
@@ -448,3 +465,105 @@ fn conv_top_filters_non_qualifying() {
     ids.sort();
     assert_eq!(ids, vec!["10.0.0.1", "10.0.0.2"]);
 }
+
+/// Conv `top(3)` over five qualifying entities keeps exactly the three
+/// highest-scoring ones.
+#[test]
+fn conv_top3_emits_three_alerts() {
+    let plan = conv_scan_plan("conv_top3", 3);
+    let start: chrono::DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+    let duration = Duration::from_secs(7200);
+
+    let mut events = Vec::new();
+    let mut sec = 0;
+    // 10.0.0.1 scans 7 ports, 10.0.0.2 scans 6, ... 10.0.0.5 scans 3 — all qualify
+    for (ip_idx, port_count) in [7, 6, 5, 4, 3].into_iter().enumerate() {
+        for port in 0..port_count {
+            sec += 1;
+            events.push(make_scan_event(
+                "s1",
+                "ConnWindow",
+                &format!("10.0.0.{}", ip_idx + 1),
+                1000 + port,
+                &format!("2024-01-01T00:{:02}:{:02}Z", sec / 60, sec % 60),
+            ));
+        }
+    }
+
+    let result = run_oracle(&events, &[plan], &start, &duration, None).unwrap();
+
+    assert_eq!(
+        result.alerts.len(),
+        3,
+        "expected 3 alerts after conv top(3)"
+    );
+    let mut ids: Vec<&str> = result.alerts.iter().map(|a| a.entity_id.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["10.0.0.1", "10.0.0.2", "10.0.0.3"]);
+}
+
+/// Fanning rules out across threads must not change the alert list.
+#[test]
+fn parallel_matches_sequential_for_multi_rule_scenario() {
+    let mut brute_force_b = make_simple_rule_plan();
+    brute_force_b.name = "brute_force_b".to_string();
+    let plans = vec![
+        conv_scan_plan("scan_top2", 2),
+        make_simple_rule_plan(),
+        brute_force_b,
+        conv_scan_plan("scan_all", 10),
+    ];
+    let start: chrono::DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+    let duration = Duration::from_secs(7200);
+
+    let mut events = Vec::new();
+    for sec in 0..600u32 {
+        let ts = format!("2024-01-01T00:{:02}:{:02}Z", sec / 60, sec % 60);
+        let sip = format!("10.0.0.{}", sec % 7);
+        events.push(make_event("s1", "LoginWindow", &sip, &ts));
+        events.push(make_scan_event(
+            "s2",
+            "ConnWindow",
+            &sip,
+            1000 + (sec % 11) as u16,
+            &ts,
+        ));
+    }
+
+    let sequential = run_oracle_with(&events, &plans, &start, &duration, None, false).unwrap();
+    let parallel = run_oracle_with(&events, &plans, &start, &duration, None, true).unwrap();
+
+    let key = |a: &crate::oracle::OracleAlert| {
+        (
+            a.rule_name.clone(),
+            a.emit_time.clone(),
+            a.entity_id.clone(),
+            a.origin.clone(),
+            a.score.to_bits(),
+        )
+    };
+    // every rule fires, so the comparison covers cross-rule ordering
+    let fired: std::collections::HashSet<&str> = sequential
+        .alerts
+        .iter()
+        .map(|a| a.rule_name.as_str())
+        .collect();
+    assert_eq!(fired.len(), plans.len());
+    assert_eq!(
+        sequential.alerts.iter().map(key).collect::<Vec<_>>(),
+        parallel.alerts.iter().map(key).collect::<Vec<_>>()
+    );
+    assert!(
+        parallel
+            .alerts
+            .windows(2)
+            .all(|w| (&w[0].rule_name, &w[0].emit_time) <= (&w[1].rule_name, &w[1].emit_time))
+    );
+}
+
+#[test]
+fn json_integers_stay_exact() {
+    let nanos: i64 = 1_700_000_000_123_456_789;
+    assert_eq!(json_to_core_value(&json!(nanos)), Some(Value::Int(nanos)));
+    assert_eq!(json_to_core_value(&json!(2.5)), Some(Value::Number(2.5)));
+}
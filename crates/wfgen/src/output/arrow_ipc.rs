@@ -4,16 +4,30 @@ use std::path::Path;
 use std::sync::Arc;
 
 use arrow::array::{
-    ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray,
-    TimestampNanosecondArray,
+    Array, ArrayRef, BooleanArray, Float64Array, Int64Array, LargeStringArray, RecordBatch,
+    StringArray, TimestampNanosecondArray,
 };
 use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::ipc::reader::FileReader;
 use arrow::ipc::writer::FileWriter;
-use chrono::{DateTime, SecondsFormat, Utc};
+use arrow::util::display::array_value_to_string;
+use chrono::SecondsFormat;
 
 use wf_lang::{BaseType, FieldType, WindowSchema};
 
+use crate::datagen::parse_timestamp;
 use crate::datagen::stream_gen::GenEvent;
+use crate::verify::ActualAlert;
+
+/// Column order for alert files; names match the [`ActualAlert`] JSONL keys.
+const ALERT_COLUMNS: [&str; 6] = [
+    "rule_name",
+    "score",
+    "entity_type",
+    "entity_id",
+    "origin",
+    "fired_at",
+];
 
 /// Write events as Arrow IPC file.
 ///
@@ -29,6 +43,19 @@ pub fn write_arrow_ipc(events: &[GenEvent], output_path: &Path) -> anyhow::Resul
         std::fs::create_dir_all(parent)?;
     }
 
+    let batch = events_to_string_batch(events)?;
+
+    let file = File::create(output_path)?;
+    let mut writer = FileWriter::try_new(file, &batch.schema())?;
+    writer.write(&batch)?;
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Build a single all-Utf8 RecordBatch covering every field seen in `events`,
+/// preceded by the `_stream`, `_window`, `_timestamp` metadata columns.
+pub(crate) fn events_to_string_batch(events: &[GenEvent]) -> anyhow::Result<RecordBatch> {
     // Collect all field names from events (preserving order from first event)
     let mut field_names: Vec<String> = Vec::new();
     // Always include metadata columns first
@@ -62,10 +89,7 @@ pub fn write_arrow_ipc(events: &[GenEvent], output_path: &Path) -> anyhow::Resul
                 "_stream" => Some(event.stream_name.clone()),
                 "_window" => Some(event.window_name.clone()),
                 "_timestamp" => Some(event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
-                name => event.fields.get(name).map(|v| match v {
-                    serde_json::Value::String(s) => s.clone(),
-                    other => other.to_string(),
-                }),
+                name => event.fields.get(name).map(json_cell),
             })
             .collect();
 
@@ -73,6 +97,49 @@ pub fn write_arrow_ipc(events: &[GenEvent], output_path: &Path) -> anyhow::Resul
         columns.push(Arc::new(array) as ArrayRef);
     }
 
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+/// Render a JSON value as a Utf8 cell: strings as-is, anything else JSON-encoded.
+fn json_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Write actual alerts as an Arrow IPC file.
+///
+/// Follows the [`write_arrow_ipc`] layout: one Utf8 column per alert field,
+/// with the score JSON-encoded.
+pub fn write_alerts_arrow(alerts: &[ActualAlert], output_path: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let arrow_fields: Vec<Field> = ALERT_COLUMNS
+        .iter()
+        .map(|name| Field::new(*name, DataType::Utf8, true))
+        .collect();
+    let schema = Arc::new(Schema::new(arrow_fields));
+
+    let columns: Vec<ArrayRef> = ALERT_COLUMNS
+        .iter()
+        .map(|name| {
+            let values: Vec<String> = alerts
+                .iter()
+                .map(|a| match *name {
+                    "rule_name" => a.rule_name.clone(),
+                    "score" => json_cell(&serde_json::json!(a.score)),
+                    "entity_type" => a.entity_type.clone(),
+                    "entity_id" => a.entity_id.clone(),
+                    "origin" => a.origin.clone(),
+                    _ => a.fired_at.clone(),
+                })
+                .collect();
+            Arc::new(StringArray::from(values)) as ArrayRef
+        })
+        .collect();
     let batch = RecordBatch::try_new(schema.clone(), columns)?;
 
     let file = File::create(output_path)?;
@@ -83,6 +150,172 @@ pub fn write_arrow_ipc(events: &[GenEvent], output_path: &Path) -> anyhow::Resul
     Ok(())
 }
 
+/// Read actual alerts from an Arrow IPC file.
+///
+/// Expects the columns written by [`write_alerts_arrow`]. Text columns may be
+/// `Utf8` or `LargeUtf8`; `score` may also be a native `Float64` / `Int64`
+/// column. Extra columns are ignored.
+pub fn read_alerts_arrow(path: &Path) -> anyhow::Result<Vec<ActualAlert>> {
+    let file = File::open(path)?;
+    let reader = FileReader::try_new(file, None)?;
+    let mut alerts = Vec::new();
+
+    for batch in reader {
+        let batch = batch?;
+        let column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .ok_or_else(|| anyhow::anyhow!("missing column '{name}'"))
+        };
+        let rule_name = column("rule_name")?;
+        let score = column("score")?;
+        let entity_type = column("entity_type")?;
+        let entity_id = column("entity_id")?;
+        let origin = column("origin")?;
+        let fired_at = column("fired_at")?;
+
+        for row in 0..batch.num_rows() {
+            alerts.push(ActualAlert {
+                rule_name: string_cell(rule_name, "rule_name", row)?,
+                score: f64_cell(score, row)?,
+                entity_type: string_cell(entity_type, "entity_type", row)?,
+                entity_id: string_cell(entity_id, "entity_id", row)?,
+                origin: string_cell(origin, "origin", row)?,
+                fired_at: string_cell(fired_at, "fired_at", row)?,
+            });
+        }
+    }
+
+    Ok(alerts)
+}
+
+fn string_cell(array: &ArrayRef, name: &str, row: usize) -> anyhow::Result<String> {
+    if array.is_null(row) {
+        anyhow::bail!("row {row}: column '{name}' is null");
+    }
+    if let Some(a) = array.as_any().downcast_ref::<StringArray>() {
+        return Ok(a.value(row).to_string());
+    }
+    if let Some(a) = array.as_any().downcast_ref::<LargeStringArray>() {
+        return Ok(a.value(row).to_string());
+    }
+    anyhow::bail!(
+        "column '{name}' has type {}, expected Utf8",
+        array.data_type()
+    )
+}
+
+fn f64_cell(array: &ArrayRef, row: usize) -> anyhow::Result<f64> {
+    if array.is_null(row) {
+        anyhow::bail!("row {row}: column 'score' is null");
+    }
+    if let Some(a) = array.as_any().downcast_ref::<Float64Array>() {
+        return Ok(a.value(row));
+    }
+    if let Some(a) = array.as_any().downcast_ref::<Int64Array>() {
+        return Ok(a.value(row) as f64);
+    }
+    let text = string_cell(array, "score", row)?;
+    text.parse()
+        .map_err(|_| anyhow::anyhow!("row {row}: score '{text}' is not a number"))
+}
+
+/// Schema and leading rows of an Arrow IPC file, as shown by `wfgen inspect`.
+#[derive(Debug)]
+pub struct ArrowInspection {
+    /// `(name, data type, nullable)` per column.
+    pub fields: Vec<(String, DataType, bool)>,
+    /// Rows across all batches in the file.
+    pub total_rows: usize,
+    /// The first rows as JSON objects keyed by column name.
+    pub rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ArrowInspection {
+    /// Render as text: one line per field, the row count, then one JSON
+    /// object per shown row.
+    pub fn render(&self) -> String {
+        let mut out = format!("schema ({} fields):\n", self.fields.len());
+        for (name, data_type, nullable) in &self.fields {
+            let null = if *nullable { "" } else { " not null" };
+            out.push_str(&format!("  {name}: {data_type}{null}\n"));
+        }
+        out.push_str(&format!(
+            "rows: {} (showing {})\n",
+            self.total_rows,
+            self.rows.len()
+        ));
+        for row in &self.rows {
+            out.push_str(&serde_json::Value::Object(row.clone()).to_string());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Read an Arrow IPC file's schema, row count and first `limit` rows.
+pub fn inspect_arrow_ipc(path: &Path, limit: usize) -> anyhow::Result<ArrowInspection> {
+    let file = File::open(path)?;
+    let reader = FileReader::try_new(file, None)?;
+    let fields = reader
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| (f.name().clone(), f.data_type().clone(), f.is_nullable()))
+        .collect();
+
+    let mut total_rows = 0;
+    let mut rows = Vec::new();
+    for batch in reader {
+        let batch = batch?;
+        total_rows += batch.num_rows();
+        let schema = batch.schema();
+        for row in 0..batch.num_rows() {
+            if rows.len() == limit {
+                break;
+            }
+            let mut obj = serde_json::Map::new();
+            for (field, column) in schema.fields().iter().zip(batch.columns()) {
+                obj.insert(field.name().clone(), json_value(column, row)?);
+            }
+            rows.push(obj);
+        }
+    }
+
+    Ok(ArrowInspection {
+        fields,
+        total_rows,
+        rows,
+    })
+}
+
+/// One Arrow cell as JSON: strings, numbers and booleans natively, any other
+/// type via Arrow's display formatting.
+fn json_value(array: &ArrayRef, row: usize) -> anyhow::Result<serde_json::Value> {
+    use serde_json::Value;
+
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+    let any = array.as_any();
+    if let Some(a) = any.downcast_ref::<StringArray>() {
+        return Ok(Value::from(a.value(row)));
+    }
+    if let Some(a) = any.downcast_ref::<LargeStringArray>() {
+        return Ok(Value::from(a.value(row)));
+    }
+    if let Some(a) = any.downcast_ref::<Int64Array>() {
+        return Ok(Value::from(a.value(row)));
+    }
+    if let Some(a) = any.downcast_ref::<Float64Array>() {
+        return Ok(Value::from(a.value(row)));
+    }
+    if let Some(a) = any.downcast_ref::<BooleanArray>() {
+        return Ok(Value::from(a.value(row)));
+    }
+    Ok(Value::from(array_value_to_string(array, row)?))
+}
+
 /// Group GenEvents by window, build typed Arrow RecordBatches keyed by stream name.
 ///
 /// Each window group produces one `(stream_name, RecordBatch)` pair. Column types
@@ -196,7 +429,7 @@ fn build_typed_column(field_def: &wf_lang::FieldDef, events: &[&GenEvent]) -> Ar
                 .map(|e| {
                     if let Some(v) = e.fields.get(name) {
                         if let Some(s) = v.as_str()
-                            && let Ok(dt) = s.parse::<DateTime<Utc>>()
+                            && let Ok(dt) = parse_timestamp(s)
                         {
                             return dt.timestamp_nanos_opt();
                         }
@@ -211,3 +444,103 @@ fn build_typed_column(field_def: &wf_lang::FieldDef, events: &[&GenEvent]) -> Ar
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::OracleAlert;
+    use crate::verify::verify;
+
+    fn alert(i: usize) -> ActualAlert {
+        ActualAlert {
+            rule_name: "brute_force".to_string(),
+            score: 85.0 + i as f64 / 2.0,
+            entity_type: "ip".to_string(),
+            entity_id: format!("10.0.0.{i}"),
+            origin: "event".to_string(),
+            fired_at: format!("2024-01-01T00:0{i}:00Z"),
+        }
+    }
+
+    #[test]
+    fn alerts_round_trip_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("alerts.arrow");
+        let alerts: Vec<ActualAlert> = (0..5).map(alert).collect();
+        write_alerts_arrow(&alerts, &path).unwrap();
+
+        let read = read_alerts_arrow(&path).unwrap();
+        assert_eq!(read.len(), 5);
+        for (a, b) in alerts.iter().zip(&read) {
+            assert_eq!(a.entity_id, b.entity_id);
+            assert_eq!(a.fired_at, b.fired_at);
+            assert_eq!(a.score, b.score);
+        }
+
+        let expected: Vec<OracleAlert> = alerts
+            .iter()
+            .map(|a| OracleAlert {
+                rule_name: a.rule_name.clone(),
+                score: a.score,
+                entity_type: a.entity_type.clone(),
+                entity_id: a.entity_id.clone(),
+                origin: a.origin.clone(),
+                emit_time: a.fired_at.clone(),
+            })
+            .collect();
+        let report = verify(&expected, &read, 0.01, 1.0);
+        assert_eq!(report.status, "pass");
+        assert_eq!(report.summary.matched, 5);
+    }
+
+    #[test]
+    fn alerts_accept_native_score_column() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("alerts.arrow");
+        let mut fields: Vec<Field> = ALERT_COLUMNS
+            .iter()
+            .map(|name| Field::new(*name, DataType::Utf8, true))
+            .collect();
+        fields[1] = Field::new("score", DataType::Float64, true);
+        let schema = Arc::new(Schema::new(fields));
+        let text = |v: &str| Arc::new(StringArray::from(vec![v])) as ArrayRef;
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                text("r1"),
+                Arc::new(Float64Array::from(vec![70.5])),
+                text("ip"),
+                text("10.0.0.1"),
+                text("close:timeout"),
+                text("2024-01-01T00:05:00Z"),
+            ],
+        )
+        .unwrap();
+        let mut writer = FileWriter::try_new(File::create(&path).unwrap(), &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let read = read_alerts_arrow(&path).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].score, 70.5);
+        assert_eq!(read[0].origin, "close:timeout");
+    }
+
+    #[test]
+    fn alerts_missing_column_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.arrow");
+        let mut fields = serde_json::Map::new();
+        fields.insert("sip".to_string(), serde_json::json!("10.0.0.1"));
+        let event = GenEvent {
+            stream_name: "syslog".to_string(),
+            window_name: "auth_events".to_string(),
+            timestamp: "2024-01-01T00:00:00Z".parse().unwrap(),
+            fields,
+        };
+        write_arrow_ipc(&[event], &path).unwrap();
+
+        let err = read_alerts_arrow(&path).unwrap_err();
+        assert!(err.to_string().contains("missing column 'rule_name'"));
+    }
+}
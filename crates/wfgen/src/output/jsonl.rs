@@ -4,6 +4,7 @@ use std::path::Path;
 
 use chrono::{DateTime, SecondsFormat, Utc};
 
+use crate::datagen::parse_timestamp;
 use crate::datagen::stream_gen::GenEvent;
 use crate::oracle::OracleAlert;
 use crate::verify::ActualAlert;
@@ -19,30 +20,59 @@ pub fn write_jsonl(events: &[GenEvent], output_path: &Path) -> anyhow::Result<()
     let mut writer = BufWriter::new(file);
 
     for event in events {
-        let mut obj = serde_json::Map::new();
-        obj.insert(
-            "_stream".to_string(),
-            serde_json::Value::String(event.stream_name.clone()),
-        );
-        obj.insert(
-            "_window".to_string(),
-            serde_json::Value::String(event.window_name.clone()),
-        );
-        obj.insert(
-            "_timestamp".to_string(),
-            serde_json::Value::String(event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
-        );
-
-        // Merge event fields
-        for (k, v) in &event.fields {
-            obj.insert(k.clone(), v.clone());
-        }
+        write_event_line(&mut writer, event)?;
+    }
 
-        let line = serde_json::to_string(&obj)?;
-        writeln!(writer, "{}", line)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write events as JSONL as they are produced, without buffering them.
+///
+/// Returns the number of events written.
+pub fn write_jsonl_stream(
+    events: impl IntoIterator<Item = GenEvent>,
+    output_path: &Path,
+) -> anyhow::Result<u64> {
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = File::create(output_path)?;
+    let mut writer = BufWriter::new(file);
+
+    let mut written = 0;
+    for event in events {
+        write_event_line(&mut writer, &event)?;
+        written += 1;
     }
 
     writer.flush()?;
+    Ok(written)
+}
+
+fn write_event_line(writer: &mut impl Write, event: &GenEvent) -> anyhow::Result<()> {
+    let mut obj = serde_json::Map::new();
+    obj.insert(
+        "_stream".to_string(),
+        serde_json::Value::String(event.stream_name.clone()),
+    );
+    obj.insert(
+        "_window".to_string(),
+        serde_json::Value::String(event.window_name.clone()),
+    );
+    obj.insert(
+        "_timestamp".to_string(),
+        serde_json::Value::String(event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
+    );
+
+    // Merge event fields
+    for (k, v) in &event.fields {
+        obj.insert(k.clone(), v.clone());
+    }
+
+    let line = serde_json::to_string(&obj)?;
+    writeln!(writer, "{}", line)?;
     Ok(())
 }
 
@@ -73,7 +103,7 @@ pub fn read_events_jsonl(path: &Path) -> anyhow::Result<Vec<GenEvent>> {
     let reader = BufReader::new(file);
     let mut events = Vec::new();
 
-    for line in reader.lines() {
+    for (lineno, line) in reader.lines().enumerate() {
         let line = line?;
         if line.trim().is_empty() {
             continue;
@@ -91,12 +121,11 @@ pub fn read_events_jsonl(path: &Path) -> anyhow::Result<Vec<GenEvent>> {
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string();
-        let timestamp: DateTime<Utc> = obj
-            .get("_timestamp")
-            .and_then(|v| v.as_str())
-            .unwrap_or("1970-01-01T00:00:00Z")
-            .parse()
-            .unwrap_or_default();
+        let timestamp = match obj.get("_timestamp").and_then(|v| v.as_str()) {
+            Some(s) => parse_timestamp(s)
+                .map_err(|e| anyhow::anyhow!("{}:{}: {e}", path.display(), lineno + 1))?,
+            None => DateTime::<Utc>::default(),
+        };
 
         // Remaining fields (exclude metadata)
         let mut fields = serde_json::Map::new();
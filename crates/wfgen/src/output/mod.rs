@@ -1,2 +1,4 @@
 pub mod arrow_ipc;
+pub mod csv;
 pub mod jsonl;
+pub mod parquet;
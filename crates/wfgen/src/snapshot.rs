@@ -0,0 +1,164 @@
+//! Golden-file snapshots of generated events (feature `snapshot`).
+//!
+//! [`snapshot_scenario`] runs a `.wfg` scenario the way `wfgen gen` does and
+//! renders the events in a canonical text form: one event per line, sorted,
+//! with field keys in lexical order and floats at a fixed precision. The
+//! output only changes when the generated data does, so it can be committed
+//! and compared with [`assert_golden`].
+//!
+//! ```text
+//! 2024-01-01T00:00:00.000Z auth_events syslog action="failed" score=0.500000 sip="10.0.0.1"
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use chrono::SecondsFormat;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde_json::Value;
+
+use crate::datagen::fault_gen::apply_faults;
+use crate::datagen::generate;
+use crate::datagen::stream_gen::GenEvent;
+use crate::loader::compile_project;
+
+/// Set to `1` to (re)write golden files instead of comparing against them.
+pub const UPDATE_ENV: &str = "WFGEN_UPDATE_GOLDEN";
+
+/// Digits after the decimal point for float field values.
+const FLOAT_PRECISION: usize = 6;
+
+/// Generate the events of the scenario at `path` and render them with
+/// [`snapshot_events`].
+///
+/// Faults are applied as in `wfgen gen` (fault RNG seeded at `seed + 1`).
+/// `seed` overrides the scenario's seed, like `wfgen gen --seed`.
+pub fn snapshot_scenario(path: &Path, seed: Option<u64>) -> anyhow::Result<String> {
+    let mut project = compile_project(path, &[], &[], &HashMap::new())?;
+    if let Some(seed) = seed {
+        project.wfg.scenario.seed = seed;
+    }
+    let wfg = &project.wfg;
+    let result = generate(wfg, &project.schemas, &project.rule_plans)?;
+    let events = match &wfg.scenario.faults {
+        Some(faults) => {
+            let mut rng = StdRng::seed_from_u64(wfg.scenario.seed.wrapping_add(1));
+            apply_faults(result.events, faults, &project.schemas, &mut rng).events
+        }
+        None => result.events,
+    };
+    Ok(snapshot_events(&events))
+}
+
+/// Render `events` canonically: sorted by timestamp, window, stream and
+/// fields; one line per event.
+pub fn snapshot_events(events: &[GenEvent]) -> String {
+    let mut lines: Vec<String> = events.iter().map(event_line).collect();
+    lines.sort();
+    let mut out = String::with_capacity(lines.iter().map(|l| l.len() + 1).sum());
+    for line in lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Compare `actual` with the golden file at `golden`, panicking with the
+/// first differing line on mismatch.
+///
+/// With `WFGEN_UPDATE_GOLDEN=1` the golden file is written instead.
+pub fn assert_golden(actual: &str, golden: &Path) {
+    if std::env::var(UPDATE_ENV).is_ok_and(|v| v == "1") {
+        if let Some(parent) = golden.parent() {
+            std::fs::create_dir_all(parent).expect("create golden directory");
+        }
+        std::fs::write(golden, actual).expect("write golden file");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(golden).unwrap_or_else(|e| {
+        panic!(
+            "cannot read golden file {}: {e} (run with {UPDATE_ENV}=1 to create it)",
+            golden.display()
+        )
+    });
+    if expected == actual {
+        return;
+    }
+
+    let mismatch = expected
+        .lines()
+        .zip(actual.lines())
+        .enumerate()
+        .find(|(_, (e, a))| e != a);
+    match mismatch {
+        Some((idx, (e, a))) => panic!(
+            "snapshot differs from {} at line {}:\n  expected: {e}\n  actual:   {a}\n\
+             (run with {UPDATE_ENV}=1 to accept)",
+            golden.display(),
+            idx + 1
+        ),
+        None => panic!(
+            "snapshot differs from {}: expected {} lines, got {} (run with {UPDATE_ENV}=1 to accept)",
+            golden.display(),
+            expected.lines().count(),
+            actual.lines().count()
+        ),
+    }
+}
+
+fn event_line(event: &GenEvent) -> String {
+    let mut line = format!(
+        "{} {} {}",
+        event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+        event.window_name,
+        event.stream_name
+    );
+    let mut keys: Vec<&String> = event.fields.keys().collect();
+    keys.sort();
+    for key in keys {
+        let _ = write!(line, " {key}=");
+        write_value(&mut line, &event.fields[key]);
+    }
+    line
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            let _ = write!(
+                out,
+                "{:.*}",
+                FLOAT_PRECISION,
+                n.as_f64().unwrap_or_default()
+            );
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                let _ = write!(out, "{key}:");
+                write_value(out, &map[key]);
+            }
+            out.push('}');
+        }
+        // Strings are JSON-quoted so spaces and escapes stay unambiguous.
+        other => out.push_str(&other.to_string()),
+    }
+}
@@ -1,12 +1,22 @@
-use std::io::Write;
+use std::io::{ErrorKind, Write};
 use std::net::TcpStream;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 
+use arrow::record_batch::RecordBatch;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName};
 use wf_lang::WindowSchema;
 use wfgen::datagen::stream_gen::GenEvent;
 use wfgen::output::arrow_ipc::events_to_typed_batches;
 
+/// Longest single sleep while pacing, so cancellation is noticed promptly.
+const PACE_SLEEP_SLICE: Duration = Duration::from_millis(50);
+
 fn make_tcp_frame(ipc_payload: &[u8]) -> Vec<u8> {
     let mut frame = Vec::with_capacity(4 + ipc_payload.len());
     frame.extend_from_slice(&(ipc_payload.len() as u32).to_be_bytes());
@@ -14,36 +24,835 @@ fn make_tcp_frame(ipc_payload: &[u8]) -> Vec<u8> {
     frame
 }
 
+/// Reconnect policy for senders: how many times a failed connect or write
+/// is retried, and the initial backoff (doubled per consecutive failure).
+/// The default fails on the first error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct RetryPolicy {
+    pub retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (1-based).
+    fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff.saturating_mul(factor).min(MAX_RETRY_BACKOFF)
+    }
+}
+
+/// Client-side TLS for senders talking to a runtime with `[server.tls]`.
+#[derive(Debug, Clone)]
+pub(crate) struct TlsSettings {
+    pub config: Arc<rustls::ClientConfig>,
+    /// Name checked against the server certificate; `None` uses the host
+    /// part of the target address.
+    pub server_name: Option<String>,
+}
+
+impl TlsSettings {
+    /// Trust the PEM certificates in `ca`, or the Mozilla root set when
+    /// `ca` is `None`.
+    pub(crate) fn load(ca: Option<&Path>, server_name: Option<String>) -> anyhow::Result<Self> {
+        let mut roots = rustls::RootCertStore::empty();
+        match ca {
+            Some(path) => {
+                let pem = std::fs::read(path)
+                    .with_context(|| format!("reading TLS CA {}", path.display()))?;
+                for cert in CertificateDer::pem_slice_iter(&pem) {
+                    let cert =
+                        cert.with_context(|| format!("parsing TLS CA {}", path.display()))?;
+                    roots
+                        .add(cert)
+                        .with_context(|| format!("adding TLS CA {}", path.display()))?;
+                }
+                if roots.is_empty() {
+                    anyhow::bail!("no certificate found in {}", path.display());
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(Self {
+            config: Arc::new(config),
+            server_name,
+        })
+    }
+
+    /// The name to verify when connecting to `addr` (`host:port`).
+    fn server_name(&self, addr: &str) -> anyhow::Result<ServerName<'static>> {
+        let name = match &self.server_name {
+            Some(name) => name.as_str(),
+            None => addr
+                .rsplit_once(':')
+                .map_or(addr, |(host, _)| host)
+                .trim_start_matches('[')
+                .trim_end_matches(']'),
+        };
+        ServerName::try_from(name.to_string())
+            .with_context(|| format!("invalid TLS server name: {name:?}"))
+    }
+}
+
+/// Sender settings shared by `send`, `gen --send` and `bench --send`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SendOptions {
+    pub retry: RetryPolicy,
+    /// Maximum rows per Arrow IPC frame; `None` sends each window as a
+    /// single frame.
+    pub batch_size: Option<usize>,
+    /// Abort when the runtime accepts no bytes for this long; `None` waits
+    /// indefinitely.
+    pub stall_timeout: Option<Duration>,
+    /// Connect over TLS; `None` sends plaintext.
+    pub tls: Option<TlsSettings>,
+}
+
+/// The runtime stopped consuming: a write made no progress within the
+/// stall timeout. Not retried, since reconnecting would only resend into
+/// the same stuck consumer.
+#[derive(Debug)]
+pub(crate) struct Stalled(pub Duration);
+
+impl std::fmt::Display for Stalled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "runtime accepted no data for {:?}; aborting stalled transfer",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for Stalled {}
+
+/// Upper bound for a single backoff sleep.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Opens connections to the runtime; abstracted so tests can inject
+/// connections that fail.
+pub(crate) trait Connector {
+    type Conn: Write;
+
+    fn connect(&mut self) -> anyhow::Result<Self::Conn>;
+
+    /// Write timeout configured on opened connections, if any.
+    fn stall_timeout(&self) -> Option<Duration> {
+        None
+    }
+}
+
+pub(crate) struct TcpConnector<'a> {
+    pub addr: &'a str,
+    pub stall_timeout: Option<Duration>,
+    pub tls: Option<TlsSettings>,
+}
+
+/// A sender connection, plaintext or wrapped in TLS.
+pub(crate) enum SenderConn {
+    Plain(TcpStream),
+    Tls(Box<rustls::StreamOwned<rustls::ClientConnection, TcpStream>>),
+}
+
+impl Write for SenderConn {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            SenderConn::Plain(stream) => stream.write(buf),
+            SenderConn::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            SenderConn::Plain(stream) => stream.flush(),
+            SenderConn::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+impl Connector for TcpConnector<'_> {
+    type Conn = SenderConn;
+
+    fn connect(&mut self) -> anyhow::Result<SenderConn> {
+        let stream = TcpStream::connect(self.addr)
+            .with_context(|| format!("connecting to runtime: {}", self.addr))?;
+        stream
+            .set_nodelay(true)
+            .context("setting TCP_NODELAY on sender socket")?;
+        stream
+            .set_write_timeout(self.stall_timeout)
+            .context("setting sender write timeout")?;
+        let Some(tls) = &self.tls else {
+            return Ok(SenderConn::Plain(stream));
+        };
+        // The handshake runs on the first write, under the same retry policy.
+        let conn =
+            rustls::ClientConnection::new(Arc::clone(&tls.config), tls.server_name(self.addr)?)
+                .context("starting TLS session")?;
+        Ok(SenderConn::Tls(Box::new(rustls::StreamOwned::new(
+            conn, stream,
+        ))))
+    }
+
+    fn stall_timeout(&self) -> Option<Duration> {
+        self.stall_timeout
+    }
+}
+
+/// Writes frames over a connection that is re-established on failure.
+///
+/// A frame whose write fails is sent again in full on the new connection,
+/// so delivery resumes from the last frame that was not fully written.
+/// Frames the kernel accepted before the peer went away may still be lost;
+/// TCP gives no application-level acknowledgement.
+pub(crate) struct FrameSender<C: Connector> {
+    connector: C,
+    conn: Option<C::Conn>,
+    policy: RetryPolicy,
+    connects: u32,
+}
+
+impl<C: Connector> FrameSender<C> {
+    pub(crate) fn new(connector: C, policy: RetryPolicy) -> Self {
+        Self {
+            connector,
+            conn: None,
+            policy,
+            connects: 0,
+        }
+    }
+
+    /// Number of times a connection was re-established after a failure.
+    pub(crate) fn reconnects(&self) -> u32 {
+        self.connects.saturating_sub(1)
+    }
+
+    /// Write one complete frame, reconnecting per the retry policy.
+    fn send(&mut self, frame: &[u8]) -> anyhow::Result<()> {
+        let mut failures = 0;
+        loop {
+            let err = match self.try_send(frame) {
+                Ok(()) => return Ok(()),
+                Err(e) if e.is::<Stalled>() => return Err(e),
+                Err(e) => e,
+            };
+            self.conn = None;
+            failures += 1;
+            if failures > self.policy.retries {
+                if self.policy.retries == 0 {
+                    return Err(err);
+                }
+                return Err(err.context(format!("giving up after {} retries", self.policy.retries)));
+            }
+            let delay = self.policy.delay(failures);
+            eprintln!(
+                "wfgen: {err:#}; retrying in {delay:?} ({failures}/{})",
+                self.policy.retries
+            );
+            std::thread::sleep(delay);
+        }
+    }
+
+    fn try_send(&mut self, frame: &[u8]) -> anyhow::Result<()> {
+        let conn = match &mut self.conn {
+            Some(conn) => conn,
+            None => {
+                let conn = self.connector.connect()?;
+                self.connects += 1;
+                self.conn.insert(conn)
+            }
+        };
+        let stall_timeout = self.connector.stall_timeout();
+        let stalled = |e: std::io::Error| match (e.kind(), stall_timeout) {
+            // A socket write timeout surfaces as WouldBlock on Unix and
+            // TimedOut on Windows.
+            (ErrorKind::WouldBlock | ErrorKind::TimedOut, Some(timeout)) => {
+                anyhow::Error::new(Stalled(timeout))
+            }
+            _ => anyhow::Error::new(e),
+        };
+        conn.write_all(frame)
+            .map_err(stalled)
+            .context("sending frame")?;
+        conn.flush()
+            .map_err(stalled)
+            .context("flushing sender socket")?;
+        Ok(())
+    }
+}
+
+/// Split `batch` into zero-copy slices of at most `batch_size` rows.
+fn row_chunks(batch: &RecordBatch, batch_size: Option<usize>) -> Vec<RecordBatch> {
+    let rows = batch.num_rows();
+    let size = match batch_size {
+        Some(size) if size < rows => size,
+        _ => return vec![batch.clone()],
+    };
+    (0..rows)
+        .step_by(size)
+        .map(|offset| batch.slice(offset, size.min(rows - offset)))
+        .collect()
+}
+
+/// Encode `events` as frames of at most `batch_size` rows per window and
+/// write them through `sender`. Returns the number of frames sent.
+fn write_frames<C: Connector>(
+    sender: &mut FrameSender<C>,
+    events: &[GenEvent],
+    schemas: &[WindowSchema],
+    batch_size: Option<usize>,
+) -> anyhow::Result<usize> {
+    let batches = events_to_typed_batches(events, schemas)?;
+    if batches.is_empty() {
+        anyhow::bail!("no arrow batches built from events");
+    }
+
+    let mut frames = 0;
+    for (stream_name, batch) in &batches {
+        for chunk in row_chunks(batch, batch_size) {
+            let ipc_payload = wp_arrow::ipc::encode_ipc(stream_name, &chunk)
+                .with_context(|| format!("encode_ipc failed for stream '{stream_name}'"))?;
+            sender
+                .send(&make_tcp_frame(&ipc_payload))
+                .with_context(|| format!("sending frame for stream '{stream_name}'"))?;
+            frames += 1;
+        }
+    }
+    Ok(frames)
+}
+
 pub(crate) fn send_events(
     events: &[GenEvent],
     schemas: &[WindowSchema],
     addr: &str,
+    opts: SendOptions,
+) -> anyhow::Result<usize> {
+    let connector = TcpConnector {
+        addr,
+        stall_timeout: opts.stall_timeout,
+        tls: opts.tls.clone(),
+    };
+    send_events_via(connector, events, schemas, opts)
+}
+
+fn send_events_via<C: Connector>(
+    connector: C,
+    events: &[GenEvent],
+    schemas: &[WindowSchema],
+    opts: SendOptions,
 ) -> anyhow::Result<usize> {
     if events.is_empty() {
         anyhow::bail!("no events to send");
     }
 
-    let batches = events_to_typed_batches(events, schemas)?;
-    if batches.is_empty() {
-        anyhow::bail!("no arrow batches built from events");
+    let mut sender = FrameSender::new(connector, opts.retry);
+    let sent_frames = write_frames(&mut sender, events, schemas, opts.batch_size)?;
+    if sender.reconnects() > 0 {
+        eprintln!(
+            "wfgen: reconnected {} time(s) while sending",
+            sender.reconnects()
+        );
     }
 
-    let mut stream =
-        TcpStream::connect(addr).with_context(|| format!("connecting to runtime: {addr}"))?;
-    stream
-        .set_nodelay(true)
-        .context("setting TCP_NODELAY on sender socket")?;
+    Ok(sent_frames)
+}
 
-    let mut sent_frames = 0usize;
-    for (stream_name, batch) in &batches {
-        let ipc_payload = wp_arrow::ipc::encode_ipc(stream_name, batch)
-            .with_context(|| format!("encode_ipc failed for stream '{stream_name}'"))?;
-        stream
-            .write_all(&make_tcp_frame(&ipc_payload))
-            .with_context(|| format!("sending frame for stream '{stream_name}'"))?;
-        sent_frames += 1;
+/// Outcome of a paced replay.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct PacedSend {
+    pub events: usize,
+    pub frames: usize,
+    /// `true` when `cancel` stopped the replay before all events were sent.
+    pub cancelled: bool,
+}
+
+/// Replay `events` in file order, reproducing their inter-event timing
+/// divided by `speed` (1.0 = real time).
+///
+/// Each event is due at `start + (timestamp - first_timestamp) / speed`,
+/// measured from a fixed wall-clock origin so sleep overshoot never
+/// accumulates. Events already due (the sender fell behind, or the event's
+/// timestamp is earlier than the first one) are coalesced into one send.
+/// Setting `cancel` stops the replay between sends.
+pub(crate) fn send_events_paced(
+    events: &[GenEvent],
+    schemas: &[WindowSchema],
+    addr: &str,
+    speed: f64,
+    opts: SendOptions,
+    cancel: &AtomicBool,
+) -> anyhow::Result<PacedSend> {
+    if events.is_empty() {
+        anyhow::bail!("no events to send");
+    }
+    if !(speed.is_finite() && speed > 0.0) {
+        anyhow::bail!("replay speed must be a positive number, got {speed}");
     }
-    stream.flush().context("flushing sender socket")?;
 
-    Ok(sent_frames)
+    let connector = TcpConnector {
+        addr,
+        stall_timeout: opts.stall_timeout,
+        tls: opts.tls.clone(),
+    };
+    let mut sender = FrameSender::new(connector, opts.retry);
+    let mut result = PacedSend::default();
+
+    let first_ts = events[0].timestamp;
+    let origin = Instant::now();
+    let due_at = |event: &GenEvent| {
+        let offset = (event.timestamp - first_ts).to_std().unwrap_or_default();
+        origin + offset.div_f64(speed)
+    };
+
+    let mut next = 0;
+    while next < events.len() {
+        if cancel.load(Ordering::Relaxed) {
+            result.cancelled = true;
+            break;
+        }
+
+        let now = Instant::now();
+        let due = events[next..]
+            .iter()
+            .take_while(|event| due_at(event) <= now)
+            .count();
+        if due == 0 {
+            let wait = due_at(&events[next]) - now;
+            std::thread::sleep(wait.min(PACE_SLEEP_SLICE));
+            continue;
+        }
+
+        let batch = &events[next..next + due];
+        result.frames += write_frames(&mut sender, batch, schemas, opts.batch_size)?;
+        result.events += due;
+        next += due;
+    }
+    if sender.reconnects() > 0 {
+        eprintln!(
+            "wfgen: reconnected {} time(s) while sending",
+            sender.reconnects()
+        );
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::time::Duration as StdDuration;
+
+    use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+    use wf_lang::{BaseType, FieldDef, FieldType};
+
+    use super::*;
+
+    fn schema() -> WindowSchema {
+        WindowSchema {
+            name: "auth_events".to_string(),
+            streams: vec!["syslog".to_string()],
+            time_field: None,
+            over: StdDuration::ZERO,
+            fields: vec![FieldDef {
+                name: "sip".to_string(),
+                field_type: FieldType::Base(BaseType::Chars),
+            }],
+        }
+    }
+
+    /// Events spaced `gap_ms` apart in event time.
+    fn events(count: usize, gap_ms: i64) -> Vec<GenEvent> {
+        let base = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        (0..count)
+            .map(|i| {
+                let mut fields = serde_json::Map::new();
+                fields.insert("sip".to_string(), serde_json::json!(format!("10.0.0.{i}")));
+                GenEvent {
+                    stream_name: "syslog".to_string(),
+                    window_name: "auth_events".to_string(),
+                    timestamp: base + ChronoDuration::milliseconds(gap_ms * i as i64),
+                    fields,
+                }
+            })
+            .collect()
+    }
+
+    /// Accept one connection and record the arrival time of each frame.
+    fn frame_recorder() -> (String, std::thread::JoinHandle<Vec<Instant>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut arrivals = Vec::new();
+            let mut len_buf = [0u8; 4];
+            while conn.read_exact(&mut len_buf).is_ok() {
+                let mut payload = vec![0u8; u32::from_be_bytes(len_buf) as usize];
+                conn.read_exact(&mut payload).unwrap();
+                arrivals.push(Instant::now());
+            }
+            arrivals
+        });
+        (addr, handle)
+    }
+
+    #[test]
+    fn paced_send_reproduces_scaled_gaps() {
+        let (addr, recorder) = frame_recorder();
+        // 200ms event-time gaps replayed at 2x => ~100ms wall-clock gaps
+        let events = events(4, 200);
+        let cancel = AtomicBool::new(false);
+
+        let start = Instant::now();
+        let sent = send_events_paced(
+            &events,
+            &[schema()],
+            &addr,
+            2.0,
+            SendOptions::default(),
+            &cancel,
+        )
+        .unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(
+            sent,
+            PacedSend {
+                events: 4,
+                frames: 4,
+                cancelled: false
+            }
+        );
+        assert!(
+            elapsed >= StdDuration::from_millis(290) && elapsed < StdDuration::from_secs(2),
+            "replay took {elapsed:?}, expected ~300ms"
+        );
+
+        let arrivals = recorder.join().unwrap();
+        assert_eq!(arrivals.len(), 4);
+        for pair in arrivals.windows(2) {
+            let gap = pair[1] - pair[0];
+            assert!(
+                gap >= StdDuration::from_millis(60),
+                "frames arrived {gap:?} apart, expected ~100ms"
+            );
+        }
+    }
+
+    #[test]
+    fn paced_send_stops_when_cancelled() {
+        let (addr, recorder) = frame_recorder();
+        // One hour of event time: only the first event is due immediately.
+        let events = events(3, 3_600_000);
+        let cancel = AtomicBool::new(false);
+
+        let sent = std::thread::scope(|scope| {
+            let sender = scope.spawn(|| {
+                send_events_paced(
+                    &events,
+                    &[schema()],
+                    &addr,
+                    1.0,
+                    SendOptions::default(),
+                    &cancel,
+                )
+            });
+            std::thread::sleep(StdDuration::from_millis(100));
+            cancel.store(true, Ordering::Relaxed);
+            sender.join().unwrap().unwrap()
+        });
+
+        assert_eq!(
+            sent,
+            PacedSend {
+                events: 1,
+                frames: 1,
+                cancelled: true
+            }
+        );
+        assert_eq!(recorder.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn paced_send_rejects_non_positive_speed() {
+        let cancel = AtomicBool::new(false);
+        assert!(
+            send_events_paced(
+                &events(1, 0),
+                &[schema()],
+                "127.0.0.1:1",
+                0.0,
+                SendOptions::default(),
+                &cancel
+            )
+            .is_err()
+        );
+    }
+
+    /// Shared log of frames accepted by [`FlakyConnector`] connections.
+    type Received = std::rc::Rc<std::cell::RefCell<Vec<Vec<u8>>>>;
+
+    /// Refuses the first `refuse` connects, then hands out connections; the
+    /// first one breaks when asked to write frame number `break_at`.
+    struct FlakyConnector {
+        refuse: u32,
+        break_at: Option<usize>,
+        attempts: u32,
+        received: Received,
+    }
+
+    struct FlakyConn {
+        break_at: Option<usize>,
+        written: usize,
+        received: Received,
+    }
+
+    impl Write for FlakyConn {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.break_at == Some(self.written) {
+                return Err(std::io::ErrorKind::BrokenPipe.into());
+            }
+            self.written += 1;
+            self.received.borrow_mut().push(buf.to_vec());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Connector for FlakyConnector {
+        type Conn = FlakyConn;
+
+        fn connect(&mut self) -> anyhow::Result<FlakyConn> {
+            self.attempts += 1;
+            if self.attempts <= self.refuse {
+                anyhow::bail!("connection refused");
+            }
+            Ok(FlakyConn {
+                break_at: self.break_at.take(),
+                written: 0,
+                received: Received::clone(&self.received),
+            })
+        }
+    }
+
+    /// Events spread over `windows` distinct windows => one frame each.
+    fn multi_window(windows: usize) -> (Vec<GenEvent>, Vec<WindowSchema>) {
+        let schemas: Vec<WindowSchema> = (0..windows)
+            .map(|w| WindowSchema {
+                name: format!("w{w}"),
+                streams: vec![format!("s{w}")],
+                ..schema()
+            })
+            .collect();
+        let events = (0..windows)
+            .flat_map(|w| {
+                events(2, 10).into_iter().map(move |mut e| {
+                    e.window_name = format!("w{w}");
+                    e.stream_name = format!("s{w}");
+                    e
+                })
+            })
+            .collect();
+        (events, schemas)
+    }
+
+    fn retry(retries: u32) -> SendOptions {
+        SendOptions {
+            retry: RetryPolicy {
+                retries,
+                backoff: StdDuration::from_millis(1),
+            },
+            batch_size: None,
+            stall_timeout: None,
+            tls: None,
+        }
+    }
+
+    #[test]
+    fn send_resumes_after_dropped_connection() {
+        let (events, schemas) = multi_window(3);
+        let received = Received::default();
+        let connector = FlakyConnector {
+            refuse: 2,
+            break_at: Some(1),
+            attempts: 0,
+            received: Received::clone(&received),
+        };
+
+        let frames = send_events_via(connector, &events, &schemas, retry(3)).unwrap();
+        assert_eq!(frames, 3);
+
+        // One frame on the first connection, the other two after the
+        // reconnect: nothing lost, nothing duplicated.
+        let received = received.borrow();
+        assert_eq!(received.len(), 3);
+        let mut streams: Vec<String> = received
+            .iter()
+            .map(|frame| wp_arrow::ipc::decode_ipc(&frame[4..]).unwrap().tag)
+            .collect();
+        streams.sort();
+        assert_eq!(streams, ["s0", "s1", "s2"]);
+    }
+
+    #[test]
+    fn send_gives_up_after_retry_budget() {
+        let (events, schemas) = multi_window(1);
+        let connector = FlakyConnector {
+            refuse: 5,
+            break_at: None,
+            attempts: 0,
+            received: Received::default(),
+        };
+        let err = send_events_via(connector, &events, &schemas, retry(2)).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("giving up after 2 retries"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn send_aborts_when_consumer_stalls() {
+        // Accepts the connection but never reads, so the socket buffers fill
+        // and the sender's write stops making progress.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+        let consumer = std::thread::spawn(move || {
+            let (_conn, _) = listener.accept().unwrap();
+            let _ = done_rx.recv();
+        });
+
+        let connector = TcpConnector {
+            addr: &addr,
+            stall_timeout: Some(StdDuration::from_millis(200)),
+            tls: None,
+        };
+        // Retries are allowed, but a stall aborts without reconnecting.
+        let mut sender = FrameSender::new(connector, retry(3).retry);
+        let start = Instant::now();
+        let err = sender.send(&vec![0u8; 64 << 20]).unwrap_err();
+        let elapsed = start.elapsed();
+        done_tx.send(()).unwrap();
+        consumer.join().unwrap();
+
+        assert!(err.is::<Stalled>(), "{err:#}");
+        assert!(
+            format!("{err:#}").contains("runtime accepted no data for 200ms"),
+            "{err:#}"
+        );
+        assert_eq!(sender.reconnects(), 0);
+        assert!(elapsed < StdDuration::from_secs(10), "took {elapsed:?}");
+    }
+
+    #[test]
+    fn send_over_tls() {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let ca_path = dir.path().join("ca.pem");
+        std::fs::write(&ca_path, certified.cert.pem()).unwrap();
+
+        let server_config = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![certified.cert.der().clone()],
+            rustls::pki_types::PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der()).into(),
+        )
+        .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = std::thread::spawn(move || {
+            let (tcp, _) = listener.accept().unwrap();
+            let conn = rustls::ServerConnection::new(Arc::new(server_config)).unwrap();
+            let mut stream = rustls::StreamOwned::new(conn, tcp);
+            let mut frames = 0;
+            let mut len_buf = [0u8; 4];
+            while stream.read_exact(&mut len_buf).is_ok() {
+                let mut payload = vec![0u8; u32::from_be_bytes(len_buf) as usize];
+                stream.read_exact(&mut payload).unwrap();
+                frames += 1;
+            }
+            frames
+        });
+
+        // The address is an IP, so the certificate name is given explicitly.
+        let opts = SendOptions {
+            tls: Some(TlsSettings::load(Some(&ca_path), Some("localhost".into())).unwrap()),
+            ..SendOptions::default()
+        };
+        let sent = send_events(&events(2, 0), &[schema()], &addr, opts).unwrap();
+        assert_eq!(sent, 1);
+        assert_eq!(server.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn tls_server_name_defaults_to_host() {
+        let tls = TlsSettings::load(None, None).unwrap();
+        assert_eq!(
+            tls.server_name("runtime.example:9800").unwrap(),
+            ServerName::try_from("runtime.example").unwrap()
+        );
+        assert_eq!(
+            tls.server_name("[::1]:9800").unwrap(),
+            ServerName::try_from("::1").unwrap()
+        );
+    }
+
+    #[test]
+    fn retry_backoff_doubles_and_is_capped() {
+        let policy = RetryPolicy {
+            retries: 100,
+            backoff: StdDuration::from_millis(100),
+        };
+        assert_eq!(policy.delay(1), StdDuration::from_millis(100));
+        assert_eq!(policy.delay(3), StdDuration::from_millis(400));
+        assert_eq!(policy.delay(40), MAX_RETRY_BACKOFF);
+    }
+
+    #[test]
+    fn batch_size_splits_frames_per_window() {
+        // Window w0 gets 6 rows, w1 gets 2.
+        let (_, schemas) = multi_window(2);
+        let mut events = events(8, 10);
+        for (i, event) in events.iter_mut().enumerate() {
+            let w = usize::from(i >= 6);
+            event.window_name = format!("w{w}");
+            event.stream_name = format!("s{w}");
+        }
+
+        for (batch_size, expected) in [(None, 2), (Some(1), 8), (Some(4), 3), (Some(6), 2)] {
+            let received = Received::default();
+            let connector = FlakyConnector {
+                refuse: 0,
+                break_at: None,
+                attempts: 0,
+                received: Received::clone(&received),
+            };
+            let opts = SendOptions {
+                batch_size,
+                ..SendOptions::default()
+            };
+            let frames = send_events_via(connector, &events, &schemas, opts).unwrap();
+            // ceil(6 / n) + ceil(2 / n)
+            assert_eq!(frames, expected, "batch_size {batch_size:?}");
+            assert_eq!(received.borrow().len(), expected);
+            let rows: usize = received
+                .borrow()
+                .iter()
+                .map(|f| wp_arrow::ipc::decode_ipc(&f[4..]).unwrap().batch.num_rows())
+                .sum();
+            assert_eq!(rows, 8);
+        }
+    }
 }
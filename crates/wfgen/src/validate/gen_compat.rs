@@ -1,6 +1,11 @@
 use wf_lang::BaseType;
 
-use crate::wfg_ast::GenExpr;
+use std::collections::HashMap;
+
+use wf_lang::WindowSchema;
+
+use crate::datagen::field_gen::{derived_order, expr_field_refs};
+use crate::wfg_ast::{FieldOverride, GenExpr};
 
 /// Check whether a `GenExpr` is type-compatible with a field's `BaseType`.
 ///
@@ -19,6 +24,8 @@ pub(super) fn check_gen_expr_compat(expr: &GenExpr, base: &BaseType) -> Option<S
             BaseType::Bool => None,
             _ => Some(format!("boolean literal not compatible with {:?}", base)),
         },
+        // Copies another field; its own type is checked at the source.
+        GenExpr::FieldRef(_) => None,
         GenExpr::GenFunc { name, .. } => check_gen_func_compat(name, base),
     }
 }
@@ -54,9 +61,92 @@ pub(super) fn check_gen_func_compat(func_name: &str, base: &BaseType) -> Option<
                 base
             )),
         },
+        "pool" => match base {
+            BaseType::Time => {
+                Some("pool() produces entity ids, not compatible with Time".to_string())
+            }
+            _ => None,
+        },
+        "concat" | "hash" => match base {
+            BaseType::Chars | BaseType::Hex | BaseType::Ip => None,
+            _ => Some(format!(
+                "{func_name}() produces strings, not compatible with {:?}",
+                base
+            )),
+        },
         // `enum` is a generic selector -- compatible with any type
         "enum" => None,
         // Unknown gen functions -- skip validation (user-extensible)
         _ => None,
     }
 }
+
+/// Check the arguments of gen functions that have hard requirements.
+///
+/// Returns `None` if the arguments are acceptable, or `Some(reason)` if not.
+pub(super) fn check_gen_expr_args(expr: &GenExpr) -> Option<String> {
+    let GenExpr::GenFunc { name, args } = expr else {
+        return None;
+    };
+    match name.as_str() {
+        "pool" => {
+            let size = args
+                .iter()
+                .find(|a| a.name.as_deref() == Some("size"))
+                .or_else(|| args.first().filter(|a| a.name.is_none()));
+            match size.map(|a| &a.value) {
+                Some(GenExpr::NumberLit(n)) if *n >= 1.0 && n.fract() == 0.0 => {}
+                Some(GenExpr::NumberLit(n)) => {
+                    return Some(format!("pool() size must be a positive integer, got {n}"));
+                }
+                _ => return Some("pool() requires a size argument".to_string()),
+            }
+            let skew = args
+                .iter()
+                .find(|a| a.name.as_deref() == Some("skew"))
+                .map(|a| &a.value);
+            match skew {
+                None => None,
+                Some(GenExpr::NumberLit(s)) if *s >= 0.0 => None,
+                Some(_) => Some("pool() skew must be a non-negative number".to_string()),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Check field references in a stream's overrides against its schema.
+///
+/// Returns `(field, reason)` pairs for references to unknown fields and for
+/// derived fields that sit on (or depend on) a reference cycle.
+pub(super) fn check_field_refs(
+    overrides: &[FieldOverride],
+    schema: &WindowSchema,
+) -> Vec<(String, String)> {
+    let mut issues = Vec::new();
+    for ov in overrides {
+        for name in expr_field_refs(&ov.gen_expr) {
+            if !schema.fields.iter().any(|f| f.name == name) {
+                issues.push((
+                    ov.field_name.clone(),
+                    format!("references unknown field '{name}'"),
+                ));
+            }
+        }
+    }
+
+    let map: HashMap<&str, &GenExpr> = overrides
+        .iter()
+        .map(|o| (o.field_name.as_str(), &o.gen_expr))
+        .collect();
+    let order = derived_order(&map);
+    for ov in overrides {
+        if !expr_field_refs(&ov.gen_expr).is_empty() && !order.contains(&ov.field_name.as_str()) {
+            issues.push((
+                ov.field_name.clone(),
+                "is part of (or depends on) a field reference cycle".to_string(),
+            ));
+        }
+    }
+    issues
+}
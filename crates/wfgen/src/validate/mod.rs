@@ -38,7 +38,9 @@ pub fn validate_wfg(
     let all_rules: Vec<_> = wfl_files.iter().flat_map(|f| f.rules.iter()).collect();
 
     if wfg.syntax.is_some() {
-        return syntax::validate_syntax(wfg, schemas, &all_rules);
+        let mut errors = syntax::validate_syntax(wfg, schemas, &all_rules);
+        errors.extend(stream_schema::validate_gen_args(&wfg.scenario));
+        return errors;
     }
 
     let mut errors = Vec::new();
@@ -48,6 +50,7 @@ pub fn validate_wfg(
     errors.extend(stream_schema::validate_streams_with_schemas(
         scenario, schemas,
     ));
+    errors.extend(stream_schema::validate_gen_args(scenario));
 
     errors.extend(stream_rule::validate_stream_rule_bindings(
         scenario, &all_rules,
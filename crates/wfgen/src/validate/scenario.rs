@@ -1,7 +1,7 @@
 use super::ValidationError;
-use crate::wfg_ast::ScenarioDecl;
+use crate::wfg_ast::{InjectMode, ScenarioDecl};
 
-/// SV2-SV6: basic scenario value checks (total, rates, percents).
+/// SV2-SV6, SV11: basic scenario value checks (total, rates, percents, modes).
 pub(super) fn validate_scenario_basics(scenario: &ScenarioDecl) -> Vec<ValidationError> {
     let mut errors = Vec::new();
 
@@ -70,6 +70,24 @@ pub(super) fn validate_scenario_basics(scenario: &ScenarioDecl) -> Vec<Validatio
         }
     }
 
+    // SV11: each inject mode at most once per block
+    for inject in &scenario.injects {
+        let mut seen: Vec<InjectMode> = Vec::new();
+        for line in &inject.lines {
+            if seen.contains(&line.mode) {
+                errors.push(ValidationError {
+                    code: "SV11",
+                    message: format!(
+                        "inject for '{}': mode '{}' is specified more than once",
+                        inject.rule, line.mode
+                    ),
+                });
+            } else {
+                seen.push(line.mode);
+            }
+        }
+    }
+
     // SV6: fault line percentages sum <= 100%
     if let Some(faults) = &scenario.faults {
         let sum: f64 = faults.faults.iter().map(|f| f.percent).sum();
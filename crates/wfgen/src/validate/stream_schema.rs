@@ -1,10 +1,10 @@
 use wf_lang::{FieldType, WindowSchema};
 
 use super::ValidationError;
-use super::gen_compat::check_gen_expr_compat;
+use super::gen_compat::{check_field_refs, check_gen_expr_args, check_gen_expr_compat};
 use crate::wfg_ast::ScenarioDecl;
 
-/// SC3, SC4, SV7: stream-schema cross-checks (window exists, field names, type compat).
+/// SC3, SC4, SV7, SV10: stream-schema cross-checks (window exists, field names, type compat).
 pub(super) fn validate_streams_with_schemas(
     scenario: &ScenarioDecl,
     schemas: &[WindowSchema],
@@ -64,5 +64,39 @@ pub(super) fn validate_streams_with_schemas(
         }
     }
 
+    // SV10: field references (derived fields) must resolve without cycles
+    for stream in &scenario.streams {
+        if let Some(schema) = schemas.iter().find(|s| s.name == stream.window) {
+            for (field, reason) in check_field_refs(&stream.overrides, schema) {
+                errors.push(ValidationError {
+                    code: "SV10",
+                    message: format!("stream '{}': field '{}' {}", stream.alias, field, reason),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// SV9: gen function arguments (e.g. pool size > 0).
+///
+/// Runs for both syntaxes; stream-first files are desugared into
+/// `scenario.streams` with their overrides.
+pub(super) fn validate_gen_args(scenario: &ScenarioDecl) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for stream in &scenario.streams {
+        for ov in &stream.overrides {
+            if let Some(reason) = check_gen_expr_args(&ov.gen_expr) {
+                errors.push(ValidationError {
+                    code: "SV9",
+                    message: format!(
+                        "stream '{}': field '{}' — {}",
+                        stream.alias, ov.field_name, reason
+                    ),
+                });
+            }
+        }
+    }
     errors
 }
@@ -1,8 +1,9 @@
-use wf_lang::WindowSchema;
 use wf_lang::ast::RuleDecl;
+use wf_lang::{FieldType, WindowSchema};
 
 use super::ValidationError;
-use crate::wfg_ast::{ExpectValue, WfgFile};
+use super::gen_compat::{check_field_refs, check_gen_expr_compat};
+use crate::wfg_ast::{ExpectValue, InjectCaseMode, WfgFile};
 
 pub(super) fn validate_syntax(
     wfg: &WfgFile,
@@ -28,7 +29,8 @@ pub(super) fn validate_syntax(
                 message: format!("stream '{}': rate must be greater than 0", s.stream),
             });
         }
-        if !schemas.iter().any(|ws| ws.name == s.stream) {
+        let schema = schemas.iter().find(|ws| ws.name == s.stream);
+        if schema.is_none() {
             errors.push(ValidationError {
                 code: "VN3",
                 message: format!(
@@ -37,11 +39,59 @@ pub(super) fn validate_syntax(
                 ),
             });
         }
+        for ov in &s.overrides {
+            if let Some(schema) = schema {
+                match schema.fields.iter().find(|f| f.name == ov.field_name) {
+                    None => errors.push(ValidationError {
+                        code: "VN9",
+                        message: format!(
+                            "stream '{}': override field '{}' not found in schema",
+                            s.stream, ov.field_name
+                        ),
+                    }),
+                    Some(field_def) => {
+                        let base = match &field_def.field_type {
+                            FieldType::Base(b) | FieldType::Array(b) => b,
+                        };
+                        if let Some(reason) = check_gen_expr_compat(&ov.gen_expr, base) {
+                            errors.push(ValidationError {
+                                code: "VN10",
+                                message: format!(
+                                    "stream '{}': field '{}' ({:?}) incompatible with override — {}",
+                                    s.stream, ov.field_name, base, reason
+                                ),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(schema) = schema {
+            for (field, reason) in check_field_refs(&s.overrides, schema) {
+                errors.push(ValidationError {
+                    code: "VN12",
+                    message: format!("stream '{}': field '{}' {}", s.stream, field, reason),
+                });
+            }
+        }
     }
 
     if let Some(inj) = &syntax.injection {
         let mut sum = 0.0;
+        let mut seen_modes: Vec<InjectCaseMode> = Vec::new();
         for case in &inj.cases {
+            if seen_modes.contains(&case.mode) {
+                errors.push(ValidationError {
+                    code: "VN14",
+                    message: format!(
+                        "injection case '{}': mode '{}' is specified more than once",
+                        case.stream, case.mode
+                    ),
+                });
+            } else {
+                seen_modes.push(case.mode);
+            }
+
             if case.percent <= 0.0 || case.percent > 100.0 {
                 errors.push(ValidationError {
                     code: "VN4",
@@ -53,6 +103,16 @@ pub(super) fn validate_syntax(
             }
             sum += case.percent;
 
+            if case.mode == InjectCaseMode::Replay && case.copies < 2 {
+                errors.push(ValidationError {
+                    code: "VN13",
+                    message: format!(
+                        "replay case '{}' copies {} must be at least 2",
+                        case.stream, case.copies
+                    ),
+                });
+            }
+
             if case.seq.steps.is_empty() {
                 errors.push(ValidationError {
                     code: "VN5",
@@ -186,3 +186,32 @@ fn test_sv7_valid_combinations() {
         errors
     );
 }
+
+#[test]
+fn test_sv9_pool_size_must_be_positive() {
+    let pool = |size: f64| {
+        minimal_wfg(
+            vec![stream_with_override(
+                "s1",
+                "W",
+                "addr",
+                GenExpr::GenFunc {
+                    name: "pool".into(),
+                    args: vec![GenArg::positional(GenExpr::NumberLit(size))],
+                },
+            )],
+            vec![],
+        )
+    };
+    let schemas = vec![make_schema("W", vec![("addr", BaseType::Ip)])];
+
+    let errors = validate_wfg(&pool(0.0), &schemas, &[]);
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.code == "SV9" && e.message.contains("addr")),
+        "{errors:?}"
+    );
+    let errors = validate_wfg(&pool(20.0), &schemas, &[]);
+    assert!(!errors.iter().any(|e| e.code == "SV9" || e.code == "SV7"));
+}
@@ -1,5 +1,64 @@
 use super::*;
 
+// -----------------------------------------------------------------------
+// SV5 / SV11 tests
+// -----------------------------------------------------------------------
+
+fn inject_lines(lines: Vec<(InjectMode, f64)>) -> InjectBlock {
+    let mut block = inject("my_rule", vec!["s1"]);
+    block.lines = lines
+        .into_iter()
+        .map(|(mode, percent)| InjectLine {
+            mode,
+            percent,
+            params: vec![],
+        })
+        .collect();
+    block
+}
+
+#[test]
+fn test_sv5_inject_percentages_exceed_100() {
+    let wfg = minimal_wfg(
+        vec![stream("s1", "LoginWindow")],
+        vec![inject_lines(vec![
+            (InjectMode::Hit, 60.0),
+            (InjectMode::NearMiss, 30.0),
+            (InjectMode::NonHit, 20.0),
+        ])],
+    );
+    let schemas = vec![make_schema("LoginWindow", vec![])];
+    let wfl = make_wfl("my_rule", vec![("s1", "LoginWindow")]);
+    let errors = validate_wfg(&wfg, &schemas, &[wfl]);
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.code == "SV5" && e.message.contains("110")),
+        "errors: {:?}",
+        errors
+    );
+    assert!(!errors.iter().any(|e| e.code == "SV11"));
+}
+
+#[test]
+fn test_sv11_inject_duplicate_mode() {
+    let wfg = minimal_wfg(
+        vec![stream("s1", "LoginWindow")],
+        vec![inject_lines(vec![
+            (InjectMode::Hit, 20.0),
+            (InjectMode::NearMiss, 10.0),
+            (InjectMode::Hit, 30.0),
+        ])],
+    );
+    let schemas = vec![make_schema("LoginWindow", vec![])];
+    let wfl = make_wfl("my_rule", vec![("s1", "LoginWindow")]);
+    let errors = validate_wfg(&wfg, &schemas, &[wfl]);
+    let dup: Vec<_> = errors.iter().filter(|e| e.code == "SV11").collect();
+    assert_eq!(dup.len(), 1, "errors: {:?}", errors);
+    assert!(dup[0].message.contains("'hit'"));
+    assert!(!errors.iter().any(|e| e.code == "SV5"));
+}
+
 // -----------------------------------------------------------------------
 // SC6 / SC2a tests
 // -----------------------------------------------------------------------
@@ -74,6 +74,49 @@ scenario s<seed=1> {
     );
 }
 
+#[test]
+fn test_syntax_injection_duplicate_mode() {
+    let input = r#"
+#[duration=10m]
+scenario s<seed=1> {
+    traffic { stream auth_events gen 100/s }
+    injection {
+        hit<20%> auth_events { user seq { use(login="failed") with(1,1m) } }
+        miss<30%> auth_events { user seq { use(login="success") with(1,1m) } }
+        hit<10%> auth_events { user seq { use(login="failed") with(2,1m) } }
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_schema("auth_events", vec![])];
+    let errors = validate_wfg(&wfg, &schemas, &[]);
+    let dup: Vec<_> = errors.iter().filter(|e| e.code == "VN14").collect();
+    assert_eq!(dup.len(), 1, "errors: {:?}", errors);
+    assert!(dup[0].message.contains("'hit'"));
+    assert!(!errors.iter().any(|e| e.code == "VN6"));
+}
+
+#[test]
+fn test_syntax_replay_copies_too_small() {
+    let input = r#"
+#[duration=10m]
+scenario s<seed=1> {
+    traffic { stream auth_events gen 100/s }
+    injection {
+        replay<20%, copies=1> auth_events { user seq { use(login="failed") with(1,1m) } }
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_schema("auth_events", vec![])];
+    let errors = validate_wfg(&wfg, &schemas, &[]);
+    assert!(
+        errors.iter().any(|e| e.code == "VN13"),
+        "errors: {:?}",
+        errors
+    );
+}
+
 #[test]
 fn test_syntax_expect_rule_missing() {
     let input = r#"
@@ -112,3 +155,68 @@ scenario s<seed=1> {
         errors
     );
 }
+
+#[test]
+fn test_syntax_override_checks() {
+    let input = r#"
+#[duration=10m]
+scenario pooled<seed=1> {
+    traffic {
+        stream auth_events gen 100/s {
+            sip = pool(0)
+            missing = pool(3)
+            ts = pool(3)
+            user = pool(4, skew: 1.0)
+        }
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_schema(
+        "auth_events",
+        vec![
+            ("sip", BaseType::Ip),
+            ("ts", BaseType::Time),
+            ("user", BaseType::Chars),
+        ],
+    )];
+    let errors = validate_wfg(&wfg, &schemas, &[]);
+    let codes: Vec<_> = errors.iter().map(|e| e.code).collect();
+    assert_eq!(codes, vec!["VN9", "VN10", "SV9"], "{errors:?}");
+    assert!(errors[0].message.contains("missing"));
+    assert!(errors[1].message.contains("ts"));
+    assert!(errors[2].message.contains("sip"));
+}
+
+#[test]
+fn test_syntax_field_ref_checks() {
+    let input = r#"
+#[duration=10m]
+scenario derived<seed=1> {
+    traffic {
+        stream auth_events gen 100/s {
+            user = "u_" + nope
+            a = b
+            b = hash(a)
+            sip = pool(4)
+        }
+    }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let schemas = vec![make_schema(
+        "auth_events",
+        vec![
+            ("sip", BaseType::Ip),
+            ("user", BaseType::Chars),
+            ("a", BaseType::Chars),
+            ("b", BaseType::Chars),
+        ],
+    )];
+    let errors = validate_wfg(&wfg, &schemas, &[]);
+    assert!(errors.iter().all(|e| e.code == "VN12"), "{errors:?}");
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert!(errors[0].message.contains("unknown field 'nope'"));
+    assert!(errors[1].message.contains("'a'") && errors[1].message.contains("cycle"));
+    assert!(errors[2].message.contains("'b'") && errors[2].message.contains("cycle"));
+}
@@ -0,0 +1,156 @@
+use crate::oracle::OracleAlert;
+
+use super::types::{ActualAlert, AlertDetail};
+use super::verify;
+
+/// Summary statistics of an oracle-vs-oracle comparison.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DiffSummary {
+    pub old_total: usize,
+    pub new_total: usize,
+    pub unchanged: usize,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+/// Detail record for an alert present in both oracles whose score or time
+/// moved beyond tolerance.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChangeDetail {
+    pub rule_name: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub old_score: f64,
+    pub new_score: f64,
+    pub old_time: String,
+    pub new_time: String,
+}
+
+/// Full diff report between two oracle files.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OracleDiffReport {
+    /// `"same"` when nothing was added, removed or changed, else `"changed"`.
+    pub status: String,
+    pub summary: DiffSummary,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<AlertDetail>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<AlertDetail>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<ChangeDetail>,
+}
+
+/// Compare two oracle outputs, e.g. before and after a rule edit.
+///
+/// Uses the same grouping and nearest-time pairing as [`verify`], with the
+/// `new` oracle standing in for the actual side: missing alerts are
+/// reported as removed, unexpected ones as added and mismatches as changed.
+pub fn diff_oracles(
+    old: &[OracleAlert],
+    new: &[OracleAlert],
+    score_tolerance: f64,
+    time_tolerance_secs: f64,
+) -> OracleDiffReport {
+    let new_as_actual: Vec<ActualAlert> = new
+        .iter()
+        .map(|a| ActualAlert {
+            rule_name: a.rule_name.clone(),
+            score: a.score,
+            entity_type: a.entity_type.clone(),
+            entity_id: a.entity_id.clone(),
+            origin: a.origin.clone(),
+            fired_at: a.emit_time.clone(),
+        })
+        .collect();
+    let report = verify(old, &new_as_actual, score_tolerance, time_tolerance_secs);
+
+    let changed: Vec<ChangeDetail> = report
+        .mismatch_details
+        .into_iter()
+        .map(|m| ChangeDetail {
+            rule_name: m.rule_name,
+            entity_type: m.entity_type,
+            entity_id: m.entity_id,
+            old_score: m.expected_score,
+            new_score: m.actual_score,
+            old_time: m.expected_time,
+            new_time: m.actual_time,
+        })
+        .collect();
+
+    let status = if report.status == "pass" {
+        "same"
+    } else {
+        "changed"
+    };
+
+    OracleDiffReport {
+        status: status.to_string(),
+        summary: DiffSummary {
+            old_total: old.len(),
+            new_total: new.len(),
+            unchanged: report.summary.matched,
+            added: report.summary.unexpected,
+            removed: report.summary.missing,
+            changed: report.summary.field_mismatch,
+        },
+        added: report.unexpected_details,
+        removed: report.missing_details,
+        changed,
+    }
+}
+
+impl OracleDiffReport {
+    /// Render the diff as a PR-friendly Markdown report.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        md.push_str("## wfgen Oracle Diff\n\n");
+        md.push_str(&format!("**Status**: {}\n\n", self.status.to_uppercase()));
+
+        md.push_str("### Summary\n\n");
+        md.push_str("| Metric | Count |\n");
+        md.push_str("|--------|-------|\n");
+        md.push_str(&format!("| Old total | {} |\n", self.summary.old_total));
+        md.push_str(&format!("| New total | {} |\n", self.summary.new_total));
+        md.push_str(&format!("| Unchanged | {} |\n", self.summary.unchanged));
+        md.push_str(&format!("| Added | {} |\n", self.summary.added));
+        md.push_str(&format!("| Removed | {} |\n", self.summary.removed));
+        md.push_str(&format!("| Changed | {} |\n", self.summary.changed));
+
+        for (title, details) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if details.is_empty() {
+                continue;
+            }
+            md.push_str(&format!("\n### {} ({})\n\n", title, details.len()));
+            md.push_str("| Rule | Entity | Score | Time |\n");
+            md.push_str("|------|--------|-------|------|\n");
+            for d in details {
+                md.push_str(&format!(
+                    "| {} | {}:{} | {:.2} | {} |\n",
+                    d.rule_name, d.entity_type, d.entity_id, d.score, d.time
+                ));
+            }
+        }
+
+        if !self.changed.is_empty() {
+            md.push_str(&format!("\n### Changed ({})\n\n", self.changed.len()));
+            md.push_str("| Rule | Entity | Old | New | Old Time | New Time |\n");
+            md.push_str("|------|--------|-----|-----|----------|----------|\n");
+            for d in &self.changed {
+                md.push_str(&format!(
+                    "| {} | {}:{} | {:.2} | {:.2} | {} | {} |\n",
+                    d.rule_name,
+                    d.entity_type,
+                    d.entity_id,
+                    d.old_score,
+                    d.new_score,
+                    d.old_time,
+                    d.new_time
+                ));
+            }
+        }
+
+        md
+    }
+}
@@ -4,7 +4,8 @@ use super::types::ActualAlert;
 
 /// Result of greedy matching within a single key group.
 pub(super) struct MatchResult {
-    pub(super) matched: usize,
+    /// (expected_idx, actual_idx) pairs within tolerance.
+    pub(super) matched: Vec<(usize, usize)>,
     /// (expected_idx, actual_idx) pairs with score mismatch.
     pub(super) mismatches: Vec<(usize, usize)>,
     /// Indices into the expected slice that were not paired.
@@ -21,7 +22,7 @@ pub(super) fn greedy_match(
     time_tolerance_secs: f64,
 ) -> MatchResult {
     let mut used_actual = vec![false; actual.len()];
-    let mut matched = 0usize;
+    let mut matched = Vec::new();
     let mut mismatches = Vec::new();
     let mut paired_expected = vec![false; expected.len()];
 
@@ -49,7 +50,7 @@ pub(super) fn greedy_match(
             let score_diff = (exp.score - actual[j].score).abs();
             let time_diff = best_dist; // abs time diff in seconds
             if score_diff <= score_tolerance && time_diff <= time_tolerance_secs {
-                matched += 1;
+                matched.push((ei, j));
             } else {
                 mismatches.push((ei, j));
             }
@@ -79,8 +80,16 @@ pub(super) fn greedy_match(
 }
 
 /// Parse an ISO 8601 timestamp to seconds-since-epoch (approximate, for ordering).
+///
+/// Unparseable timestamps collapse to `0.0`; [`super::verify_strict`] rejects
+/// them up front via [`parse_time`].
 pub(super) fn parse_time_approx(s: &str) -> f64 {
-    s.parse::<chrono::DateTime<chrono::Utc>>()
+    parse_time(s).unwrap_or(0.0)
+}
+
+/// Parse an ISO 8601 timestamp to seconds-since-epoch with millisecond precision.
+pub(super) fn parse_time(s: &str) -> Option<f64> {
+    crate::datagen::parse_timestamp(s)
+        .ok()
         .map(|dt| dt.timestamp() as f64 + dt.timestamp_subsec_millis() as f64 / 1000.0)
-        .unwrap_or(0.0)
 }
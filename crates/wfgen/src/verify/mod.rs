@@ -1,3 +1,4 @@
+mod diff;
 mod matching;
 mod types;
 
@@ -9,9 +10,10 @@ use std::collections::HashMap;
 use crate::oracle::OracleAlert;
 
 // Re-export public types so external API is unchanged.
+pub use diff::{ChangeDetail, DiffSummary, OracleDiffReport, diff_oracles};
 pub use types::{ActualAlert, AlertDetail, MismatchDetail, VerifyReport, VerifySummary};
 
-use matching::greedy_match;
+use matching::{greedy_match, parse_time, parse_time_approx};
 
 /// Match key for grouping alerts.
 type MatchKey = (String, String, String, String);
@@ -39,6 +41,7 @@ pub fn verify(
     let mut unexpected = 0usize;
     let mut field_mismatch = 0usize;
 
+    let mut matched_details = Vec::new();
     let mut missing_details = Vec::new();
     let mut unexpected_details = Vec::new();
     let mut mismatch_details = Vec::new();
@@ -61,11 +64,20 @@ pub fn verify(
         match (exp_list, act_list) {
             (Some(exp), Some(act)) => {
                 let result = greedy_match(exp, act, score_tolerance, time_tolerance_secs);
-                matched += result.matched;
+                matched += result.matched.len();
                 field_mismatch += result.mismatches.len();
                 missing += result.missing_indices.len();
                 unexpected += result.unexpected_indices.len();
 
+                for &(exp_idx, act_idx) in &result.matched {
+                    matched_details.push(AlertDetail {
+                        rule_name: exp[exp_idx].rule_name.clone(),
+                        entity_type: exp[exp_idx].entity_type.clone(),
+                        entity_id: exp[exp_idx].entity_id.clone(),
+                        score: act[act_idx].score,
+                        time: act[act_idx].fired_at.clone(),
+                    });
+                }
                 for &idx in &result.missing_indices {
                     missing_details.push(AlertDetail {
                         rule_name: exp[idx].rule_name.clone(),
@@ -140,10 +152,81 @@ pub fn verify(
             unexpected,
             field_mismatch,
         },
+        matched_details,
         missing_details,
         unexpected_details,
         mismatch_details,
+        truncated_at: None,
+    }
+}
+
+/// Keep the `limit` earliest oracle alerts by `emit_time`. Returns whether
+/// any were dropped.
+pub fn truncate_oracle(alerts: &mut Vec<OracleAlert>, limit: usize) -> bool {
+    truncate_earliest(alerts, limit, |a| &a.emit_time)
+}
+
+/// Keep the `limit` earliest actual alerts by `fired_at`. Returns whether
+/// any were dropped.
+pub fn truncate_actual(alerts: &mut Vec<ActualAlert>, limit: usize) -> bool {
+    truncate_earliest(alerts, limit, |a| &a.fired_at)
+}
+
+fn truncate_earliest<T>(alerts: &mut Vec<T>, limit: usize, time: impl Fn(&T) -> &str) -> bool {
+    if alerts.len() <= limit {
+        return false;
+    }
+    alerts.sort_by(|a, b| parse_time_approx(time(a)).total_cmp(&parse_time_approx(time(b))));
+    alerts.truncate(limit);
+    true
+}
+
+/// Like [`verify`], but fails when any expected `emit_time` or actual
+/// `fired_at` is not a valid ISO 8601 timestamp.
+///
+/// [`verify`] matches unparseable times as epoch 0, which can pair alerts
+/// that are really hours apart or hide a broken clock; strict mode surfaces
+/// every bad timestamp instead.
+pub fn verify_strict(
+    expected: &[OracleAlert],
+    actual: &[ActualAlert],
+    score_tolerance: f64,
+    time_tolerance_secs: f64,
+) -> anyhow::Result<VerifyReport> {
+    let mut bad = Vec::new();
+    for (i, a) in expected.iter().enumerate() {
+        if parse_time(&a.emit_time).is_none() {
+            bad.push(format!(
+                "expected #{} (rule {}): emit_time {:?}",
+                i + 1,
+                a.rule_name,
+                a.emit_time
+            ));
+        }
+    }
+    for (i, a) in actual.iter().enumerate() {
+        if parse_time(&a.fired_at).is_none() {
+            bad.push(format!(
+                "actual #{} (rule {}): fired_at {:?}",
+                i + 1,
+                a.rule_name,
+                a.fired_at
+            ));
+        }
+    }
+    if !bad.is_empty() {
+        anyhow::bail!(
+            "{} unparseable alert timestamp(s):\n  {}",
+            bad.len(),
+            bad.join("\n  ")
+        );
     }
+    Ok(verify(
+        expected,
+        actual,
+        score_tolerance,
+        time_tolerance_secs,
+    ))
 }
 
 // ---------------------------------------------------------------------------
@@ -1,5 +1,7 @@
 use crate::oracle::OracleAlert;
-use crate::verify::{ActualAlert, verify};
+use crate::verify::{
+    ActualAlert, diff_oracles, truncate_actual, truncate_oracle, verify, verify_strict,
+};
 
 #[test]
 fn exact_match_passes() {
@@ -263,3 +265,387 @@ fn time_mismatch_beyond_tolerance_fails() {
     assert_eq!(report.status, "pass");
     assert_eq!(report.summary.matched, 1);
 }
+
+#[test]
+fn test_junit_report_structure() {
+    let oracle = |id: &str, score: f64| OracleAlert {
+        rule_name: "brute_force".to_string(),
+        score,
+        entity_type: "ip".to_string(),
+        entity_id: id.to_string(),
+        origin: "event".to_string(),
+        emit_time: "2024-01-01T00:05:00Z".to_string(),
+    };
+    let actual_alert = |id: &str, score: f64| ActualAlert {
+        rule_name: "brute_force".to_string(),
+        score,
+        entity_type: "ip".to_string(),
+        entity_id: id.to_string(),
+        origin: "event".to_string(),
+        fired_at: "2024-01-01T00:05:00Z".to_string(),
+    };
+    // matched, mismatch (score), missing, unexpected
+    let expected = vec![
+        oracle("10.0.0.1", 85.0),
+        oracle("10.0.0.2", 85.0),
+        oracle("10.0.0.3", 85.0),
+    ];
+    let actual = vec![
+        actual_alert("10.0.0.1", 85.0),
+        actual_alert("10.0.0.2", 50.0),
+        actual_alert("10.0.0.<4>", 70.0),
+    ];
+
+    let report = verify(&expected, &actual, 0.01, 1.0);
+    let xml = report.to_junit();
+
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+    assert!(xml.contains("<testsuites name=\"wfgen verify\" tests=\"4\" failures=\"3\">"));
+    assert!(xml.contains("<testsuite name=\"wfgen verify\" tests=\"4\" failures=\"3\""));
+    assert_eq!(xml.matches("<testcase ").count(), 4);
+    assert_eq!(xml.matches("<failure ").count(), 3);
+    assert!(xml.contains(
+        "<testcase classname=\"brute_force\" name=\"matched ip:10.0.0.1 @ 2024-01-01T00:05:00Z\"/>"
+    ));
+    assert!(xml.contains("<failure type=\"missing\""));
+    assert!(xml.contains("<failure type=\"field_mismatch\""));
+    assert!(xml.contains("name=\"unexpected ip:10.0.0.&lt;4&gt; @ 2024-01-01T00:05:00Z\""));
+    assert!(xml.trim_end().ends_with("</testsuites>"));
+    // Balanced open/close tags for non-self-closing cases
+    assert_eq!(xml.matches("</testcase>").count(), 3);
+}
+
+#[test]
+fn test_junit_pass_report_json_unchanged() {
+    let report = verify(&[], &[], 0.01, 1.0);
+    assert!(
+        report
+            .to_junit()
+            .contains("<testsuites name=\"wfgen verify\" tests=\"0\" failures=\"0\">")
+    );
+    // matched details are JUnit-only; JSON output keeps its shape
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json.get("matched_details").is_none());
+}
+
+#[test]
+fn diff_oracle_files_reports_added_and_removed() {
+    use crate::output::jsonl::read_oracle_jsonl;
+
+    let line = |id: &str, score: f64| {
+        format!(
+            r#"{{"rule_name":"brute_force","score":{score},"entity_type":"ip","entity_id":"{id}","origin":"event","emit_time":"2024-01-01T00:05:00Z"}}"#
+        )
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let old_path = dir.path().join("old.oracle.jsonl");
+    let new_path = dir.path().join("new.oracle.jsonl");
+    std::fs::write(
+        &old_path,
+        [line("10.0.0.1", 70.0), line("10.0.0.2", 70.0)].join("\n"),
+    )
+    .unwrap();
+    std::fs::write(
+        &new_path,
+        [line("10.0.0.1", 70.0), line("10.0.0.3", 70.0)].join("\n"),
+    )
+    .unwrap();
+
+    let old = read_oracle_jsonl(&old_path).unwrap();
+    let new = read_oracle_jsonl(&new_path).unwrap();
+    let report = diff_oracles(&old, &new, 0.01, 1.0);
+
+    assert_eq!(report.status, "changed");
+    assert_eq!(report.summary.unchanged, 1);
+    assert_eq!(report.summary.added, 1);
+    assert_eq!(report.summary.removed, 1);
+    assert_eq!(report.summary.changed, 0);
+    assert_eq!(report.added[0].entity_id, "10.0.0.3");
+    assert_eq!(report.removed[0].entity_id, "10.0.0.2");
+
+    let md = report.to_markdown();
+    assert!(md.contains("**Status**: CHANGED"));
+    assert!(md.contains("### Added (1)"));
+    assert!(md.contains("### Removed (1)"));
+    assert!(!md.contains("### Changed"));
+}
+
+#[test]
+fn diff_identical_oracles_is_same() {
+    let alert = OracleAlert {
+        rule_name: "r1".to_string(),
+        score: 85.0,
+        entity_type: "ip".to_string(),
+        entity_id: "10.0.0.1".to_string(),
+        origin: "event".to_string(),
+        emit_time: "2024-01-01T00:05:00Z".to_string(),
+    };
+    let moved = OracleAlert {
+        score: 90.0,
+        ..alert.clone()
+    };
+
+    let same = diff_oracles(
+        std::slice::from_ref(&alert),
+        std::slice::from_ref(&alert),
+        0.01,
+        1.0,
+    );
+    assert_eq!(same.status, "same");
+    let json = serde_json::to_value(&same).unwrap();
+    assert!(json.get("added").is_none());
+
+    let changed = diff_oracles(&[alert], &[moved], 0.01, 1.0);
+    assert_eq!(changed.summary.changed, 1);
+    assert_eq!(changed.changed[0].old_score, 85.0);
+    assert_eq!(changed.changed[0].new_score, 90.0);
+}
+
+#[test]
+fn origin_round_trips_through_oracle_and_engine_output() {
+    use crate::output::jsonl::{read_alerts_jsonl, read_oracle_jsonl, write_oracle_jsonl};
+    use wf_core::alert::{AlertOrigin, OutputRecord};
+    use wf_core::rule::CloseReason;
+
+    let oracle = vec![
+        OracleAlert {
+            rule_name: "r1".to_string(),
+            score: 80.0,
+            entity_type: "ip".to_string(),
+            entity_id: "10.0.0.1".to_string(),
+            origin: AlertOrigin::Event.as_str().to_string(),
+            emit_time: "2024-01-01T00:05:00Z".to_string(),
+        },
+        OracleAlert {
+            rule_name: "r1".to_string(),
+            score: 80.0,
+            entity_type: "ip".to_string(),
+            entity_id: "10.0.0.1".to_string(),
+            origin: AlertOrigin::Close {
+                reason: CloseReason::Timeout,
+            }
+            .as_str()
+            .to_string(),
+            emit_time: "2024-01-01T00:10:00Z".to_string(),
+        },
+    ];
+    let engine_line = |origin: AlertOrigin, fired_at: &str| {
+        serde_json::to_string(&OutputRecord {
+            wfx_id: "0000000000000000".to_string(),
+            rule_name: "r1".to_string(),
+            score: 80.0,
+            entity_type: "ip".to_string(),
+            entity_id: "10.0.0.1".to_string(),
+            origin,
+            fired_at: fired_at.to_string(),
+            matched_rows: vec![],
+            summary: String::new(),
+            priority: 0,
+            yield_target: "out".to_string(),
+            yield_fields: vec![],
+            event_time_nanos: 0,
+        })
+        .unwrap()
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let oracle_path = dir.path().join("expected.oracle.jsonl");
+    let actual_path = dir.path().join("alerts.jsonl");
+    write_oracle_jsonl(&oracle, &oracle_path).unwrap();
+    let expected = read_oracle_jsonl(&oracle_path).unwrap();
+    assert_eq!(expected[1].origin, "close:timeout");
+
+    // Engine output carrying the same origins matches pairwise.
+    std::fs::write(
+        &actual_path,
+        [
+            engine_line(AlertOrigin::Event, "2024-01-01T00:05:00Z"),
+            engine_line(
+                AlertOrigin::Close {
+                    reason: CloseReason::Timeout,
+                },
+                "2024-01-01T00:10:00Z",
+            ),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    let actual = read_alerts_jsonl(&actual_path).unwrap();
+    assert_eq!(actual[1].origin, "close:timeout");
+    let report = verify(&expected, &actual, 0.01, 1.0);
+    assert_eq!(report.status, "pass");
+    assert_eq!(report.summary.matched, 2);
+
+    // A close alert with a different reason is a different match key.
+    std::fs::write(
+        &actual_path,
+        [
+            engine_line(AlertOrigin::Event, "2024-01-01T00:05:00Z"),
+            engine_line(
+                AlertOrigin::Close {
+                    reason: CloseReason::Flush,
+                },
+                "2024-01-01T00:10:00Z",
+            ),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    let actual = read_alerts_jsonl(&actual_path).unwrap();
+    let report = verify(&expected, &actual, 0.01, 1.0);
+    assert_eq!(report.summary.matched, 1);
+    assert_eq!(report.summary.missing, 1);
+    assert_eq!(report.summary.unexpected, 1);
+}
+
+#[test]
+fn strict_time_rejects_malformed_timestamp() {
+    let expected = vec![OracleAlert {
+        rule_name: "r1".to_string(),
+        score: 85.0,
+        entity_type: "ip".to_string(),
+        entity_id: "10.0.0.1".to_string(),
+        origin: "event".to_string(),
+        emit_time: "2024-01-01T00:05:00Z".to_string(),
+    }];
+    let actual = vec![ActualAlert {
+        rule_name: "r1".to_string(),
+        score: 85.0,
+        entity_type: "ip".to_string(),
+        entity_id: "10.0.0.1".to_string(),
+        origin: "event".to_string(),
+        fired_at: "2024-01-01 00:05".to_string(),
+    }];
+
+    // Lenient mode pairs the alert anyway (time collapses to epoch 0).
+    let report = verify(&expected, &actual, 0.01, 1.0);
+    assert_eq!(report.summary.field_mismatch, 1);
+
+    let err = verify_strict(&expected, &actual, 0.01, 1.0).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("1 unparseable alert timestamp"), "{msg}");
+    assert!(msg.contains("actual #1 (rule r1)"), "{msg}");
+    assert!(msg.contains("\"2024-01-01 00:05\""), "{msg}");
+
+    let report = verify_strict(&expected, &expected_as_actual(&expected), 0.01, 1.0).unwrap();
+    assert_eq!(report.status, "pass");
+}
+
+#[test]
+fn actual_file_round_trips_as_expected_baseline() {
+    use crate::output::jsonl::read_alerts_jsonl;
+
+    let baseline_run = vec![
+        oracle_at("10.0.0.1", "2024-01-01T00:01:00Z"),
+        oracle_at("10.0.0.2", "2024-01-01T00:02:00Z"),
+    ];
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("alerts.jsonl");
+    let lines: Vec<String> = expected_as_actual(&baseline_run)
+        .iter()
+        .map(|a| serde_json::to_string(a).unwrap())
+        .collect();
+    std::fs::write(&path, lines.join("\n")).unwrap();
+
+    let actual = read_alerts_jsonl(&path).unwrap();
+    let expected: Vec<OracleAlert> = actual.iter().cloned().map(OracleAlert::from).collect();
+    assert_eq!(expected[1].entity_id, "10.0.0.2");
+    assert_eq!(expected[1].emit_time, "2024-01-01T00:02:00Z");
+
+    // A run compared against itself passes.
+    let report = verify(&expected, &actual, 0.01, 1.0);
+    assert_eq!(report.status, "pass");
+    assert_eq!(report.summary.matched, 2);
+
+    // A regressed second run is reported against the baseline.
+    let report = verify(&expected, &actual[..1], 0.01, 1.0);
+    assert_eq!(report.status, "fail");
+    assert_eq!(report.missing_details[0].entity_id, "10.0.0.2");
+}
+
+fn oracle_at(entity_id: &str, emit_time: &str) -> OracleAlert {
+    OracleAlert {
+        rule_name: "r1".to_string(),
+        score: 70.0,
+        entity_type: "ip".to_string(),
+        entity_id: entity_id.to_string(),
+        origin: "event".to_string(),
+        emit_time: emit_time.to_string(),
+    }
+}
+
+#[test]
+fn limit_truncates_both_sides_and_never_passes() {
+    let mut expected = vec![
+        oracle_at("10.0.0.3", "2024-01-01T00:03:00Z"),
+        oracle_at("10.0.0.1", "2024-01-01T00:01:00Z"),
+        oracle_at("10.0.0.2", "2024-01-01T00:02:00Z"),
+    ];
+    let mut actual = expected_as_actual(&expected);
+    actual.push(ActualAlert {
+        fired_at: "2024-01-01T00:09:00Z".to_string(),
+        ..actual[0].clone()
+    });
+
+    assert!(truncate_oracle(&mut expected, 2));
+    assert!(truncate_actual(&mut actual, 2));
+    // The earliest alerts survive, whatever the input order.
+    assert_eq!(expected[0].entity_id, "10.0.0.1");
+    assert_eq!(expected[1].entity_id, "10.0.0.2");
+
+    let mut report = verify(&expected, &actual, 0.01, 1.0);
+    report.mark_truncated(2);
+    assert_eq!(report.status, "truncated");
+    assert_eq!(report.summary.oracle_total, 2);
+    assert_eq!(report.summary.actual_total, 2);
+    assert_eq!(report.summary.matched, 2);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["status"], "truncated");
+    assert_eq!(json["truncated_at"], 2);
+    assert!(
+        report
+            .to_markdown()
+            .contains("**Truncated**: only the first 2 alerts")
+    );
+    assert!(
+        report
+            .to_junit()
+            .contains("name=\"truncated_at\" value=\"2\"")
+    );
+
+    // Discrepancies inside the kept window still fail.
+    actual[1].score = 10.0;
+    let mut report = verify(&expected, &actual, 0.01, 1.0);
+    report.mark_truncated(2);
+    assert_eq!(report.status, "fail");
+}
+
+#[test]
+fn limit_above_alert_count_is_a_no_op() {
+    let mut expected = vec![oracle_at("10.0.0.1", "2024-01-01T00:01:00Z")];
+    assert!(!truncate_oracle(&mut expected, 1));
+    assert_eq!(expected.len(), 1);
+
+    let report = verify(&expected, &expected_as_actual(&expected), 0.01, 1.0);
+    assert!(report.truncated_at.is_none());
+    assert!(
+        !serde_json::to_string(&report)
+            .unwrap()
+            .contains("truncated")
+    );
+}
+
+fn expected_as_actual(expected: &[OracleAlert]) -> Vec<ActualAlert> {
+    expected
+        .iter()
+        .map(|e| ActualAlert {
+            rule_name: e.rule_name.clone(),
+            score: e.score,
+            entity_type: e.entity_type.clone(),
+            entity_id: e.entity_id.clone(),
+            origin: e.origin.clone(),
+            fired_at: e.emit_time.clone(),
+        })
+        .collect()
+}
@@ -1,3 +1,5 @@
+use crate::oracle::OracleAlert;
+
 /// An actual alert to compare against oracle expectations.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ActualAlert {
@@ -9,6 +11,21 @@ pub struct ActualAlert {
     pub fired_at: String,
 }
 
+/// Treat an alert from a trusted prior run as an expectation, so two engine
+/// runs can be compared with `verify --expected-from-actual`.
+impl From<ActualAlert> for OracleAlert {
+    fn from(alert: ActualAlert) -> Self {
+        Self {
+            rule_name: alert.rule_name,
+            score: alert.score,
+            entity_type: alert.entity_type,
+            entity_id: alert.entity_id,
+            origin: alert.origin,
+            emit_time: alert.fired_at,
+        }
+    }
+}
+
 /// Summary statistics of the verify comparison.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct VerifySummary {
@@ -47,20 +64,41 @@ pub struct MismatchDetail {
 pub struct VerifyReport {
     pub status: String,
     pub summary: VerifySummary,
+    /// Matched pairs (actual side); only rendered as JUnit passing cases.
+    #[serde(skip)]
+    pub matched_details: Vec<AlertDetail>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub missing_details: Vec<AlertDetail>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub unexpected_details: Vec<AlertDetail>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub mismatch_details: Vec<MismatchDetail>,
+    /// Per-side alert cap when `--limit` dropped alerts from the comparison.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_at: Option<usize>,
 }
 
 impl VerifyReport {
+    /// Record that only the first `limit` alerts per side were compared.
+    /// A clean comparison then reports `"truncated"` rather than `"pass"`.
+    pub fn mark_truncated(&mut self, limit: usize) {
+        self.truncated_at = Some(limit);
+        if self.status == "pass" {
+            self.status = "truncated".to_string();
+        }
+    }
+
     /// Render the report as a PR-friendly Markdown table.
     pub fn to_markdown(&self) -> String {
         let mut md = String::new();
         md.push_str("## wfgen Verify Report\n\n");
         md.push_str(&format!("**Status**: {}\n\n", self.status.to_uppercase()));
+        if let Some(limit) = self.truncated_at {
+            md.push_str(&format!(
+                "**Truncated**: only the first {} alerts per side were compared\n\n",
+                limit
+            ));
+        }
 
         // Summary table
         md.push_str("### Summary\n\n");
@@ -138,4 +176,109 @@ impl VerifyReport {
 
         md
     }
+
+    /// Render the report as a JUnit XML suite for CI test reporting.
+    ///
+    /// Every matched alert is a passing test case; every missing, unexpected
+    /// and mismatched alert is a failing one. Test cases are grouped by rule
+    /// name via `classname`.
+    pub fn to_junit(&self) -> String {
+        let tests = self.matched_details.len()
+            + self.missing_details.len()
+            + self.unexpected_details.len()
+            + self.mismatch_details.len();
+        let failures = tests - self.matched_details.len();
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"wfgen verify\" tests=\"{tests}\" failures=\"{failures}\">\n"
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"wfgen verify\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\">\n"
+        ));
+        if let Some(limit) = self.truncated_at {
+            xml.push_str(&format!(
+                "    <properties>\n      <property name=\"truncated_at\" value=\"{limit}\"/>\n    </properties>\n"
+            ));
+        }
+
+        for d in &self.matched_details {
+            xml.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\"/>\n",
+                xml_escape(&d.rule_name),
+                xml_escape(&format!(
+                    "matched {}:{} @ {}",
+                    d.entity_type, d.entity_id, d.time
+                )),
+            ));
+        }
+        for (kind, details) in [
+            ("missing", &self.missing_details),
+            ("unexpected", &self.unexpected_details),
+        ] {
+            for d in details {
+                let message = match kind {
+                    "missing" => format!("expected alert not produced (score {:.2})", d.score),
+                    _ => format!("alert not in oracle (score {:.2})", d.score),
+                };
+                push_failing_case(
+                    &mut xml,
+                    &d.rule_name,
+                    &format!("{kind} {}:{} @ {}", d.entity_type, d.entity_id, d.time),
+                    kind,
+                    &message,
+                );
+            }
+        }
+        for d in &self.mismatch_details {
+            push_failing_case(
+                &mut xml,
+                &d.rule_name,
+                &format!(
+                    "mismatch {}:{} @ {}",
+                    d.entity_type, d.entity_id, d.expected_time
+                ),
+                "field_mismatch",
+                &format!(
+                    "expected score {:.2} at {}, actual score {:.2} at {}",
+                    d.expected_score, d.expected_time, d.actual_score, d.actual_time
+                ),
+            );
+        }
+
+        xml.push_str("  </testsuite>\n");
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+fn push_failing_case(xml: &mut String, rule: &str, name: &str, kind: &str, message: &str) {
+    xml.push_str(&format!(
+        "    <testcase classname=\"{}\" name=\"{}\">\n",
+        xml_escape(rule),
+        xml_escape(name)
+    ));
+    xml.push_str(&format!(
+        "      <failure type=\"{}\" message=\"{}\"/>\n",
+        kind,
+        xml_escape(message)
+    ));
+    xml.push_str("    </testcase>\n");
+}
+
+/// Escape text for use inside an XML attribute value.
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
 }
@@ -92,6 +92,8 @@ pub struct TrafficBlock {
 pub struct SyntaxStreamDecl {
     pub stream: String,
     pub rate: RateExpr,
+    /// Optional `{ field = gen_expr ... }` block after the rate expression.
+    pub overrides: Vec<FieldOverride>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -155,6 +157,8 @@ pub struct SyntaxInjectionBlock {
 pub struct SyntaxInjectCase {
     pub mode: InjectCaseMode,
     pub percent: f64,
+    /// Emissions per generated event; only `replay` cases use more than 1.
+    pub copies: u64,
     pub stream: String,
     pub seq: SeqBlock,
 }
@@ -165,6 +169,19 @@ pub enum InjectCaseMode {
     Hit,
     NearMiss,
     Miss,
+    /// Hit clusters whose events are each emitted `copies` times.
+    Replay,
+}
+
+impl std::fmt::Display for InjectCaseMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InjectCaseMode::Hit => write!(f, "hit"),
+            InjectCaseMode::NearMiss => write!(f, "near_miss"),
+            InjectCaseMode::Miss => write!(f, "miss"),
+            InjectCaseMode::Replay => write!(f, "replay"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -306,7 +323,12 @@ pub enum GenExpr {
     StringLit(String),
     NumberLit(f64),
     BoolLit(bool),
-    GenFunc { name: String, args: Vec<GenArg> },
+    /// Bare field name: the value of another field in the same event.
+    FieldRef(String),
+    GenFunc {
+        name: String,
+        args: Vec<GenArg>,
+    },
 }
 
 /// A gen function argument, optionally named.
@@ -364,6 +386,18 @@ pub enum InjectMode {
     Hit,
     NearMiss,
     NonHit,
+    Replay,
+}
+
+impl std::fmt::Display for InjectMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InjectMode::Hit => write!(f, "hit"),
+            InjectMode::NearMiss => write!(f, "near_miss"),
+            InjectMode::NonHit => write!(f, "miss"),
+            InjectMode::Replay => write!(f, "replay"),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -389,6 +423,10 @@ pub enum FaultType {
     Duplicate,
     /// Remove event from the output stream.
     Drop,
+    /// Swap an event with a random partner at most `REORDER_WINDOW` positions later.
+    Reorder,
+    /// Mutate one field value in a type-aware way (flip, null, truncate, out-of-range).
+    Corrupt,
 }
 
 impl std::fmt::Display for FaultType {
@@ -398,6 +436,8 @@ impl std::fmt::Display for FaultType {
             FaultType::Late => write!(f, "late"),
             FaultType::Duplicate => write!(f, "duplicate"),
             FaultType::Drop => write!(f, "drop"),
+            FaultType::Reorder => write!(f, "reorder"),
+            FaultType::Corrupt => write!(f, "corrupt"),
         }
     }
 }
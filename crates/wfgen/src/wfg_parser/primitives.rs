@@ -1,9 +1,12 @@
 use winnow::ascii::multispace0;
-use winnow::combinator::opt;
+use winnow::combinator::{cut_err, opt};
+use winnow::error::{StrContext, StrContextValue};
 use winnow::prelude::*;
 use winnow::token::{literal, take_while};
 
-use crate::wfg_ast::{Rate, RateUnit};
+use wf_lang::parse_utils::{ident, number_literal, quoted_string};
+
+use crate::wfg_ast::{FieldOverride, GenArg, GenExpr, Rate, RateUnit};
 
 // ---------------------------------------------------------------------------
 // Whitespace & comments (// style for .wfg)
@@ -48,3 +51,128 @@ pub fn percent(input: &mut &str) -> ModalResult<f64> {
     literal("%").parse_next(input)?;
     Ok(num)
 }
+
+// ---------------------------------------------------------------------------
+// Field override: IDENT "=" gen_expr
+// ---------------------------------------------------------------------------
+
+pub fn field_override(input: &mut &str) -> ModalResult<FieldOverride> {
+    let field_name = ident
+        .context(StrContext::Expected(StrContextValue::Description(
+            "field name",
+        )))
+        .parse_next(input)?
+        .to_string();
+    ws_skip(input)?;
+    cut_err(literal("="))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "'=' after field name",
+        )))
+        .parse_next(input)?;
+    ws_skip(input)?;
+    let gen_expr = cut_err(gen_expr)
+        .context(StrContext::Expected(StrContextValue::Description(
+            "generator expression",
+        )))
+        .parse_next(input)?;
+    Ok(FieldOverride {
+        field_name,
+        gen_expr,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Gen expression: gen_term ("+" gen_term)*
+// gen_term: STRING | NUMBER | true | false | IDENT "(" gen_args ")" | IDENT
+// ---------------------------------------------------------------------------
+
+/// Parse a generator expression. `a + b + ...` is sugar for `concat(a, b, ...)`.
+pub fn gen_expr(input: &mut &str) -> ModalResult<GenExpr> {
+    let first = gen_term(input)?;
+    let mut parts = Vec::new();
+    loop {
+        let saved = *input;
+        ws_skip(input)?;
+        if opt(literal("+")).parse_next(input)?.is_none() {
+            *input = saved;
+            break;
+        }
+        ws_skip(input)?;
+        let term = cut_err(gen_term)
+            .context(StrContext::Expected(StrContextValue::Description(
+                "operand after '+'",
+            )))
+            .parse_next(input)?;
+        parts.push(GenArg::positional(term));
+    }
+    if parts.is_empty() {
+        return Ok(first);
+    }
+    parts.insert(0, GenArg::positional(first));
+    Ok(GenExpr::GenFunc {
+        name: "concat".to_string(),
+        args: parts,
+    })
+}
+
+fn gen_term(input: &mut &str) -> ModalResult<GenExpr> {
+    if let Some(s) = opt(quoted_string).parse_next(input)? {
+        return Ok(GenExpr::StringLit(s));
+    }
+    let negative = opt(literal("-")).parse_next(input)?.is_some();
+    if let Some(n) = opt(number_literal).parse_next(input)? {
+        return Ok(GenExpr::NumberLit(if negative { -n } else { n }));
+    }
+    if negative {
+        return Err(winnow::error::ErrMode::Backtrack(
+            winnow::error::ContextError::new(),
+        ));
+    }
+
+    let name = ident(input)?.to_string();
+    match name.as_str() {
+        "true" => return Ok(GenExpr::BoolLit(true)),
+        "false" => return Ok(GenExpr::BoolLit(false)),
+        _ => {}
+    }
+    let saved = *input;
+    ws_skip(input)?;
+    if opt(literal("(")).parse_next(input)?.is_none() {
+        *input = saved;
+        return Ok(GenExpr::FieldRef(name));
+    }
+
+    let mut args = Vec::new();
+    loop {
+        ws_skip(input)?;
+        if opt(literal(")")).parse_next(input)?.is_some() {
+            break;
+        }
+        if !args.is_empty() {
+            cut_err(literal(","))
+                .context(StrContext::Expected(StrContextValue::Description(
+                    "',' or ')' in gen function arguments",
+                )))
+                .parse_next(input)?;
+            ws_skip(input)?;
+        }
+        args.push(cut_err(gen_arg).parse_next(input)?);
+    }
+    Ok(GenExpr::GenFunc { name, args })
+}
+
+/// `IDENT ":" gen_expr` (named) or `gen_expr` (positional).
+fn gen_arg(input: &mut &str) -> ModalResult<GenArg> {
+    let saved = *input;
+    if let Ok(name) = ident.parse_next(input) {
+        let name = name.to_string();
+        ws_skip(input)?;
+        if opt(literal(":")).parse_next(input)?.is_some() {
+            ws_skip(input)?;
+            let value = gen_expr(input)?;
+            return Ok(GenArg::named(name, value));
+        }
+    }
+    *input = saved;
+    Ok(GenArg::positional(gen_expr(input)?))
+}
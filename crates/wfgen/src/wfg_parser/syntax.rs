@@ -9,7 +9,7 @@ use wf_lang::parse_utils::{ident, number_literal};
 
 use crate::wfg_ast::*;
 
-use super::primitives::{percent, rate, ws_skip};
+use super::primitives::{field_override, percent, rate, ws_skip};
 
 pub(super) fn scenario_attrs(input: &mut &str) -> ModalResult<Vec<ScenarioAttr>> {
     ws_skip(input)?;
@@ -238,17 +238,40 @@ fn parse_traffic_block(input: &mut &str) -> ModalResult<TrafficBlock> {
             )))
             .parse_next(input)?;
         ws_skip(input)?;
+        let overrides = if opt(literal("{")).parse_next(input)?.is_some() {
+            parse_override_block(input)?
+        } else {
+            Vec::new()
+        };
+        ws_skip(input)?;
         let _ = opt(literal(";")).parse_next(input)?;
 
         streams.push(SyntaxStreamDecl {
             stream,
             rate: rate_expr,
+            overrides,
         });
     }
 
     Ok(TrafficBlock { streams })
 }
 
+/// Parse `field = gen_expr` entries up to the closing `}`; the opening
+/// brace has already been consumed. Entries may be separated by `;` or `,`.
+fn parse_override_block(input: &mut &str) -> ModalResult<Vec<FieldOverride>> {
+    let mut overrides = Vec::new();
+    loop {
+        ws_skip(input)?;
+        if opt(literal("}")).parse_next(input)?.is_some() {
+            break;
+        }
+        overrides.push(cut_err(field_override).parse_next(input)?);
+        ws_skip(input)?;
+        let _ = opt(alt((literal(";"), literal(",")))).parse_next(input)?;
+    }
+    Ok(overrides)
+}
+
 fn parse_rate_expr(input: &mut &str) -> ModalResult<RateExpr> {
     if opt(wf_lang::parse_utils::kw("wave"))
         .parse_next(input)?
@@ -403,14 +426,21 @@ fn parse_injection_case(input: &mut &str) -> ModalResult<SyntaxInjectCase> {
         wf_lang::parse_utils::kw("hit").value(InjectCaseMode::Hit),
         wf_lang::parse_utils::kw("near_miss").value(InjectCaseMode::NearMiss),
         wf_lang::parse_utils::kw("miss").value(InjectCaseMode::Miss),
+        wf_lang::parse_utils::kw("replay").value(InjectCaseMode::Replay),
     ))
     .context(StrContext::Expected(StrContextValue::Description(
-        "injection mode (hit, near_miss, miss)",
+        "injection mode (hit, near_miss, miss, replay)",
     )))
     .parse_next(input)?;
     ws_skip(input)?;
     cut_err(literal("<")).parse_next(input)?;
     let pct = cut_err(percent).parse_next(input)?;
+    ws_skip(input)?;
+    let copies = if mode == InjectCaseMode::Replay {
+        parse_replay_copies(input)?
+    } else {
+        1
+    };
     cut_err(literal(">")).parse_next(input)?;
     ws_skip(input)?;
     let stream = cut_err(ident)
@@ -428,11 +458,35 @@ fn parse_injection_case(input: &mut &str) -> ModalResult<SyntaxInjectCase> {
     Ok(SyntaxInjectCase {
         mode,
         percent: pct,
+        copies,
         stream,
         seq,
     })
 }
 
+/// Optional `, copies=N` after a replay percentage; defaults to 2.
+fn parse_replay_copies(input: &mut &str) -> ModalResult<u64> {
+    if opt(literal(",")).parse_next(input)?.is_none() {
+        return Ok(2);
+    }
+    ws_skip(input)?;
+    cut_err(wf_lang::parse_utils::kw("copies"))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "'copies' parameter",
+        )))
+        .parse_next(input)?;
+    ws_skip(input)?;
+    cut_err(literal("=")).parse_next(input)?;
+    ws_skip(input)?;
+    let copies = cut_err(wf_lang::parse_utils::nonneg_integer)
+        .context(StrContext::Expected(StrContextValue::Description(
+            "replay copy count",
+        )))
+        .parse_next(input)? as u64;
+    ws_skip(input)?;
+    Ok(copies)
+}
+
 fn parse_seq_block(input: &mut &str) -> ModalResult<SeqBlock> {
     let entity = cut_err(ident)
         .context(StrContext::Expected(StrContextValue::Description(
@@ -666,7 +720,7 @@ fn derive_legacy_streams(traffic: &TrafficBlock) -> Vec<StreamBlock> {
             alias: s.stream.clone(),
             window: s.stream.clone(),
             rate: rate_from_expr(&s.rate),
-            overrides: Vec::new(),
+            overrides: s.overrides.clone(),
         })
         .collect()
 }
@@ -706,11 +760,19 @@ fn derive_legacy_injects(
             });
         }
 
+        if matches!(case.mode, InjectCaseMode::Replay) {
+            params.push(ParamAssign {
+                name: "copies".to_string(),
+                value: ParamValue::Number(case.copies as f64),
+            });
+        }
+
         lines.push(InjectLine {
             mode: match case.mode {
                 InjectCaseMode::Hit => InjectMode::Hit,
                 InjectCaseMode::NearMiss => InjectMode::NearMiss,
                 InjectCaseMode::Miss => InjectMode::NonHit,
+                InjectCaseMode::Replay => InjectMode::Replay,
             },
             percent: case.percent,
             params,
@@ -152,6 +152,185 @@ scenario s<seed=1> {
     assert_eq!(wfg.scenario.name, "s");
 }
 
+#[test]
+fn test_parse_comments_between_clauses() {
+    let input = r#"
+// schemas and rules
+use "../schemas/security.wfs" // auth windows
+// rules under test
+use "../rules/brute_force.wfl"
+
+#[duration=10m] // ten minutes is enough
+// seed pinned for reproducible output
+scenario s<seed=1> { // body starts here
+  // steady background
+  traffic { // one line per stream
+    stream auth_events gen 100/s // end-of-line after a stream
+    // overrides may be annotated too
+    stream other gen 5/s { // per-field overrides
+      // tiny user pool
+      username = pool(10) // inline note
+    }
+    stream ramp gen timeline {
+      0m..2m=20/s // warm-up
+      // peak
+      2m..4m=60/s
+    }
+  }
+
+  // attack traffic
+  injection {
+    // mostly hits
+    hit<30%> auth_events { // brute force
+      user seq { // per user
+        use(login="failed") with(3,2m) // three failures
+        // then a scan
+        then use(action="port_scan") with(1,1m)
+      }
+    }
+  }
+  // acceptance
+  expect {
+    hit(brute_force) >= 95% // recall floor
+    // latency budget
+    latency_p95(brute_force) <= 2s
+  }
+  // trailing comment in the scenario body
+}
+// trailing comment at end of file
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    assert_eq!(wfg.uses.len(), 2);
+    let syntax = wfg.syntax.as_ref().unwrap();
+    let streams = &syntax.traffic.streams;
+    assert_eq!(streams.len(), 3);
+    assert_eq!(streams[0].stream, "auth_events");
+    assert_eq!(streams[1].overrides.len(), 1);
+    assert!(matches!(&streams[2].rate, RateExpr::Timeline(segs) if segs.len() == 2));
+    let inj = syntax.injection.as_ref().unwrap();
+    assert_eq!(inj.cases[0].seq.steps.len(), 2);
+    assert_eq!(syntax.expect.as_ref().unwrap().checks.len(), 2);
+}
+
+#[test]
+fn test_parse_replay_case() {
+    let input = r#"
+#[duration=1m]
+scenario s<seed=1> {
+  traffic {
+    stream auth_events gen 100/s
+  }
+  injection {
+    replay<20%, copies=3> auth_events {
+      user seq { use(login="failed") with(3,2m) }
+    }
+    replay<10%> auth_events {
+      user seq { use(login="failed") with(3,2m) }
+    }
+  }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let inj = wfg.syntax.as_ref().unwrap().injection.as_ref().unwrap();
+    assert_eq!(inj.cases[0].mode, InjectCaseMode::Replay);
+    assert_eq!(inj.cases[0].percent, 20.0);
+    assert_eq!(inj.cases[0].copies, 3);
+    assert_eq!(inj.cases[1].copies, 2);
+
+    let line = &wfg.scenario.injects[0].lines[0];
+    assert_eq!(line.mode, InjectMode::Replay);
+    assert!(
+        line.params
+            .iter()
+            .any(|p| p.name == "copies" && matches!(p.value, ParamValue::Number(n) if n == 3.0))
+    );
+
+    // copies= is only accepted on replay cases
+    let bad = input.replace("replay<20%, copies=3>", "hit<20%, copies=3>");
+    assert!(parse_wfg(&bad).is_err());
+}
+
+#[test]
+fn test_parse_stream_override_block() {
+    let input = r#"
+#[duration=10m]
+scenario s<seed=1> {
+  traffic {
+    stream auth_events gen 100/s {
+      src_ip = pool(20, skew: 1.5);
+      username = pool(size: 50, prefix: "user"),
+      attempts = -3
+      note = "x"
+    }
+    stream other gen 5/s
+  }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let streams = &wfg.syntax.as_ref().unwrap().traffic.streams;
+    assert_eq!(streams[0].overrides.len(), 4);
+    assert!(streams[1].overrides.is_empty());
+    assert_eq!(
+        streams[0].overrides[0].gen_expr,
+        GenExpr::GenFunc {
+            name: "pool".into(),
+            args: vec![
+                GenArg::positional(GenExpr::NumberLit(20.0)),
+                GenArg::named("skew", GenExpr::NumberLit(1.5)),
+            ],
+        }
+    );
+    assert_eq!(
+        streams[0].overrides[1].gen_expr,
+        GenExpr::GenFunc {
+            name: "pool".into(),
+            args: vec![
+                GenArg::named("size", GenExpr::NumberLit(50.0)),
+                GenArg::named("prefix", GenExpr::StringLit("user".into())),
+            ],
+        }
+    );
+    assert_eq!(streams[0].overrides[2].gen_expr, GenExpr::NumberLit(-3.0));
+    // Overrides flow into the derived stream blocks used by the generator.
+    assert_eq!(wfg.scenario.streams[0].overrides, streams[0].overrides);
+}
+
+#[test]
+fn test_parse_derived_field_exprs() {
+    let input = r#"
+#[duration=10m]
+scenario s<seed=1> {
+  traffic {
+    stream auth_events gen 100/s {
+      username = "user_" + src_ip
+      session = hash(username)
+      alias = src_ip
+    }
+  }
+}
+"#;
+    let wfg = parse_wfg(input).unwrap();
+    let ovs = &wfg.syntax.as_ref().unwrap().traffic.streams[0].overrides;
+    assert_eq!(
+        ovs[0].gen_expr,
+        GenExpr::GenFunc {
+            name: "concat".into(),
+            args: vec![
+                GenArg::positional(GenExpr::StringLit("user_".into())),
+                GenArg::positional(GenExpr::FieldRef("src_ip".into())),
+            ],
+        }
+    );
+    assert_eq!(
+        ovs[1].gen_expr,
+        GenExpr::GenFunc {
+            name: "hash".into(),
+            args: vec![GenArg::positional(GenExpr::FieldRef("username".into()))],
+        }
+    );
+    assert_eq!(ovs[2].gen_expr, GenExpr::FieldRef("src_ip".into()));
+}
+
 #[test]
 fn test_legacy_syntax_rejected() {
     let input = r#"
@@ -0,0 +1,61 @@
+//! `wfgen inspect <file>` prints an Arrow IPC file's schema, row count and
+//! first rows as JSON.
+
+use std::process::Command;
+
+use wfgen::output::arrow_ipc::write_alerts_arrow;
+use wfgen::verify::ActualAlert;
+
+fn alert(i: usize) -> ActualAlert {
+    ActualAlert {
+        rule_name: "brute_force".to_string(),
+        score: 85.0,
+        entity_type: "ip".to_string(),
+        entity_id: format!("10.0.0.{i}"),
+        origin: "event".to_string(),
+        fired_at: format!("2024-01-01T00:0{i}:00Z"),
+    }
+}
+
+#[test]
+fn inspect_prints_schema_and_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("alerts.arrow");
+    let alerts: Vec<ActualAlert> = (0..5).map(alert).collect();
+    write_alerts_arrow(&alerts, &path).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_wfgen"))
+        .arg("inspect")
+        .arg(&path)
+        .args(["--rows", "2"])
+        .output()
+        .expect("failed to run wfgen");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines[0], "schema (6 fields):");
+    for (line, name) in lines[1..7].iter().zip([
+        "rule_name",
+        "score",
+        "entity_type",
+        "entity_id",
+        "origin",
+        "fired_at",
+    ]) {
+        assert_eq!(*line, format!("  {name}: Utf8"));
+    }
+    assert_eq!(lines[7], "rows: 5 (showing 2)");
+
+    let rows: Vec<serde_json::Value> = lines[8..]
+        .iter()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["entity_id"], "10.0.0.1");
+    assert_eq!(rows[1]["score"], "85.0");
+}
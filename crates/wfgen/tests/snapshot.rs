@@ -0,0 +1,28 @@
+//! Self-test for `wfgen::snapshot`: the committed golden file pins the
+//! generator's output for a small scenario.
+//!
+//! Regenerate after an intended generator change with
+//! `WFGEN_UPDATE_GOLDEN=1 cargo test -p wfgen --test snapshot`.
+
+use std::path::PathBuf;
+
+use wfgen::snapshot::{assert_golden, snapshot_scenario};
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+#[test]
+fn login_scenario_matches_golden() {
+    let snapshot = snapshot_scenario(&golden_dir().join("login.wfg"), None).unwrap();
+    assert_eq!(snapshot.lines().count(), 20);
+    assert_golden(&snapshot, &golden_dir().join("login.snap"));
+}
+
+#[test]
+fn snapshot_is_stable_and_seed_sensitive() {
+    let path = golden_dir().join("login.wfg");
+    let a = snapshot_scenario(&path, None).unwrap();
+    assert_eq!(a, snapshot_scenario(&path, None).unwrap());
+    assert_ne!(a, snapshot_scenario(&path, Some(8)).unwrap());
+}
@@ -0,0 +1,88 @@
+//! `wfgen lint -` reads the scenario from standard input and resolves its
+//! `use` paths against the current directory.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn lint_stdin(scenario: &str) -> Output {
+    lint_stdin_with(scenario, &[])
+}
+
+fn lint_stdin_with(scenario: &str, extra: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wfgen"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/count"))
+        .args(["lint", "-"])
+        .args(extra)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run wfgen");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(scenario.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn lint_reads_scenario_from_stdin() {
+    let scenario = "use \"schemas/security.wfs\"\nuse \"rules/brute_force.wfl\"\n\n\
+                    #[duration=10s]\nscenario piped<seed=1> {\n  traffic {\n    \
+                    stream auth_events gen 10/s\n  }\n}\n";
+    let out = lint_stdin(scenario);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "OK");
+
+    // `use` paths are relative to the cwd, not to the (nonexistent) file.
+    let out = lint_stdin(&scenario.replace("schemas/", "../schemas/"));
+    assert!(!out.status.success());
+}
+
+#[test]
+fn lint_fail_on_warning_covers_rule_warnings() {
+    // brute_force.wfl compiles with a warning (no `limits` block).
+    let scenario = "use \"schemas/security.wfs\"\nuse \"rules/brute_force.wfl\"\n\n\
+                    #[duration=10s]\nscenario piped<seed=1> {\n  traffic {\n    \
+                    stream auth_events gen 10/s\n  }\n}\n";
+    let out = lint_stdin(scenario);
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("limits"));
+
+    let out = lint_stdin_with(scenario, &["--fail-on-warning"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(out.stdout.is_empty());
+}
+
+#[test]
+fn lint_loads_schemas_from_schema_flag_glob() {
+    // Schemas come only from `--schema`; the rule still resolves its windows.
+    let scenario = "use \"rules/brute_force.wfl\"\n\n\
+                    #[duration=10s]\nscenario piped<seed=1> {\n  traffic {\n    \
+                    stream auth_events gen 10/s\n  }\n}\n";
+    let out = lint_stdin_with(scenario, &["--schema", "schemas/*.wfs"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    // The same file via `use` and the `--ws` alias is loaded once.
+    let scenario = format!("use \"schemas/security.wfs\"\n{scenario}");
+    let out = lint_stdin_with(&scenario, &["--ws", "schemas/security.wfs"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let out = lint_stdin_with(&scenario, &["--schema", "schemas/missing_*.wfs"]);
+    assert!(!out.status.success());
+}
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+
+use wf_config::project::{load_schemas, load_wfl, parse_vars};
+use wf_lang::explain::authored_rule_name;
+use wf_lang::plan::RulePlan;
+
+use crate::rule_filter::{ensure_rules_exist, is_selected};
+
+pub fn run(
+    file: PathBuf,
+    schemas: Vec<String>,
+    vars: Vec<String>,
+    format: String,
+    rules: Vec<String>,
+) -> Result<()> {
+    if format != "json" {
+        bail!("unknown --format '{format}' (expected \"json\")");
+    }
+    let cwd = std::env::current_dir()?;
+    let var_map = parse_vars(&vars)?;
+    let all_schemas = load_schemas(&schemas, &cwd)?;
+    let source = load_wfl(&file, &var_map)?;
+
+    let wfl_file = wf_lang::parse_wfl(&source)?;
+    ensure_rules_exist(&wfl_file, &rules)?;
+
+    let plans: Vec<_> = wf_lang::compile_wfl(&wfl_file, &all_schemas)?
+        .into_iter()
+        .filter(|p| is_selected(authored_rule_name(&p.name), &rules))
+        .collect();
+
+    println!("{}", plans_json(&plans)?);
+    Ok(())
+}
+
+/// The compiled plans as a pretty-printed JSON array, one object per
+/// `RulePlan` with its fields under their Rust names.
+fn plans_json(plans: &[RulePlan]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(plans)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    const SCHEMAS: &str = r#"
+window auth {
+    stream = "syslog"
+    time = event_time
+    over = 5m
+
+    fields {
+        sip: ip
+        action: chars
+        event_time: time
+    }
+}
+
+window alerts {
+    over = 0
+
+    fields {
+        sip: ip
+    }
+}
+"#;
+
+    #[test]
+    fn json_exposes_plan_structure() {
+        let src = r#"
+rule brute {
+    events { fail : auth && action == "failed" }
+    match<sip:5m> { on event { fail | count >= 3; } } -> score(70.0)
+    entity(ip, fail.sip)
+    yield alerts (sip = fail.sip)
+}
+"#;
+        let schemas = wf_lang::parse_wfs(SCHEMAS).unwrap();
+        let file = wf_lang::parse_wfl(src).unwrap();
+        let plans = wf_lang::compile_wfl(&file, &schemas).unwrap();
+
+        let json: Value = serde_json::from_str(&plans_json(&plans).unwrap()).unwrap();
+        let plan = &json[0];
+        assert_eq!(plan["name"], "brute");
+        assert_eq!(plan["binds"][0]["alias"], "fail");
+        assert_eq!(plan["binds"][0]["window"], "auth");
+        assert_eq!(plan["match_plan"]["keys"][0]["Simple"], "sip");
+        assert_eq!(
+            plan["match_plan"]["event_steps"][0]["branches"][0]["agg"]["measure"],
+            "Count"
+        );
+        assert_eq!(plan["yield_plans"][0]["target"], "alerts");
+        assert_eq!(plan["yield_plans"][0]["fields"][0]["name"], "sip");
+        assert_eq!(plan["score_plan"]["expr"]["Number"], 70.0);
+    }
+}
@@ -1,10 +1,12 @@
 use std::io::IsTerminal;
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 
 use wf_config::project::{load_schemas, load_wfl, parse_vars};
-use wf_lang::explain::RuleExplanation;
+use wf_lang::explain::{PipelineExplanation, RuleExplanation, authored_rule_name};
+
+use crate::rule_filter::{ensure_rules_exist, is_selected};
 
 const BOLD: &str = "\x1b[1m";
 const GREEN: &str = "\x1b[1;32m";
@@ -12,7 +14,19 @@ const CYAN: &str = "\x1b[36m";
 const DIM: &str = "\x1b[2m";
 const RESET: &str = "\x1b[0m";
 
-pub fn run(file: PathBuf, schemas: Vec<String>, vars: Vec<String>) -> Result<()> {
+pub fn run(
+    file: PathBuf,
+    schemas: Vec<String>,
+    vars: Vec<String>,
+    format: String,
+    as_authored: bool,
+    rules: Vec<String>,
+) -> Result<()> {
+    let dot = match format.as_str() {
+        "text" => false,
+        "dot" => true,
+        other => bail!("unknown --format '{other}' (expected \"text\" or \"dot\")"),
+    };
     let cwd = std::env::current_dir()?;
     let var_map = parse_vars(&vars)?;
     let color = std::io::stdout().is_terminal();
@@ -24,15 +38,34 @@ pub fn run(file: PathBuf, schemas: Vec<String>, vars: Vec<String>) -> Result<()>
     let source = load_wfl(&file, &var_map)?;
 
     // Parse
-    let wfl_file = wf_lang::parse_wfl(&source).map_err(|e| anyhow::anyhow!("parse error: {e}"))?;
+    let wfl_file = wf_lang::parse_wfl(&source)?;
+    ensure_rules_exist(&wfl_file, &rules)?;
 
-    // Compile (runs check_wfl internally)
-    let plans = wf_lang::compile_wfl(&wfl_file, &all_schemas)?;
+    // Compile (runs check_wfl internally), keeping only the --rule selection
+    let plans: Vec<_> = wf_lang::compile_wfl(&wfl_file, &all_schemas)?
+        .into_iter()
+        .filter(|p| is_selected(authored_rule_name(&p.name), &rules))
+        .collect();
 
     // Explain
+    if as_authored {
+        let groups = wf_lang::explain::explain_pipelines(&plans, &all_schemas);
+        if dot {
+            let stages: Vec<RuleExplanation> = groups.into_iter().flat_map(|g| g.stages).collect();
+            print!("{}", wf_lang::explain::to_dot(&stages));
+        } else {
+            for group in &groups {
+                print_group(group, color);
+            }
+        }
+        return Ok(());
+    }
+
     let explanations = wf_lang::explain::explain_rules(&plans, &all_schemas);
 
-    if color {
+    if dot {
+        print!("{}", wf_lang::explain::to_dot(&explanations));
+    } else if color {
         for expl in &explanations {
             print_colored(expl);
         }
@@ -45,6 +78,20 @@ pub fn run(file: PathBuf, schemas: Vec<String>, vars: Vec<String>) -> Result<()>
     Ok(())
 }
 
+fn print_group(g: &PipelineExplanation, color: bool) {
+    if !color {
+        print!("{g}");
+        return;
+    }
+    if g.is_pipeline() {
+        println!("{BOLD}Pipeline: {GREEN}{}{RESET}", g.name);
+        println!("  {BOLD}Stages:{RESET} {}", g.stage_flow());
+    }
+    for stage in &g.stages {
+        print_colored(stage);
+    }
+}
+
 fn print_colored(e: &RuleExplanation) {
     println!("{BOLD}Rule: {GREEN}{}{RESET}", e.name);
 
@@ -104,18 +151,20 @@ fn print_colored(e: &RuleExplanation) {
     );
 
     // Yield
-    println!(
-        "  {BOLD}Yield{RESET} {DIM}->{RESET} {CYAN}{}{RESET}:",
-        e.yield_target
-    );
-    let yw = max_field_width(&e.yield_fields);
-    for (name, value) in &e.yield_fields {
+    for y in &e.yields {
         println!(
-            "    {CYAN}{:width$}{RESET} {DIM}={RESET} {}",
-            name,
-            value,
-            width = yw
+            "  {BOLD}Yield{RESET} {DIM}->{RESET} {CYAN}{}{RESET}:",
+            y.target
         );
+        let yw = max_field_width(&y.fields);
+        for (name, value) in &y.fields {
+            println!(
+                "    {CYAN}{:width$}{RESET} {DIM}={RESET} {}",
+                name,
+                value,
+                width = yw
+            );
+        }
     }
 
     // Lineage
@@ -132,6 +181,20 @@ fn print_colored(e: &RuleExplanation) {
         }
     }
 
+    // Stage output
+    if !e.stage_outputs.is_empty() {
+        println!("  {BOLD}Stage Output:{RESET}");
+        let sw = max_field_width(&e.stage_outputs);
+        for (name, origin) in &e.stage_outputs {
+            println!(
+                "    {CYAN}{:width$}{RESET} {DIM}<-{RESET} {}",
+                name,
+                origin,
+                width = sw
+            );
+        }
+    }
+
     // Limits
     if let Some(ref limits) = e.limits {
         println!("  {BOLD}Limits:{RESET} {}", limits);
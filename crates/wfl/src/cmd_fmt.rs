@@ -3,29 +3,121 @@ use std::path::PathBuf;
 use std::process;
 
 use anyhow::Result;
+use wf_config::project::{is_stdin, read_source};
+
+use crate::watch;
 
 const GREEN: &str = "\x1b[1;32m";
 const RED: &str = "\x1b[1;31m";
 const YELLOW: &str = "\x1b[1;38;5;208m";
 const RESET: &str = "\x1b[0m";
 
-pub fn run(files: Vec<PathBuf>, write: bool, check: bool) -> Result<()> {
+/// Line ending written by the formatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eol {
+    Lf,
+    Crlf,
+}
+
+impl Eol {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "lf" => Ok(Eol::Lf),
+            "crlf" => Ok(Eol::Crlf),
+            other => anyhow::bail!("unsupported --eol '{other}': expected 'lf' or 'crlf'"),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Eol::Lf => "\n",
+            Eol::Crlf => "\r\n",
+        }
+    }
+}
+
+/// House style applied by [`format_source`]. The default (4 spaces, LF)
+/// matches the formatter's original output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FmtStyle {
+    /// Spaces per nesting level; ignored when `use_tabs` is set.
+    pub indent: usize,
+    pub use_tabs: bool,
+    pub eol: Eol,
+}
+
+impl Default for FmtStyle {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            use_tabs: false,
+            eol: Eol::Lf,
+        }
+    }
+}
+
+impl FmtStyle {
+    fn indent_unit(&self) -> String {
+        if self.use_tabs {
+            "\t".to_string()
+        } else {
+            " ".repeat(self.indent)
+        }
+    }
+}
+
+pub fn run(
+    files: Vec<PathBuf>,
+    write: bool,
+    check: bool,
+    style: FmtStyle,
+    watch: bool,
+) -> Result<()> {
     if files.is_empty() {
         anyhow::bail!("no input files specified");
     }
-
-    let color = std::io::stderr().is_terminal();
+    let stdin_inputs = files.iter().filter(|f| is_stdin(f)).count();
+    if stdin_inputs > 1 {
+        anyhow::bail!("`-` (standard input) can only be given once");
+    }
+    if stdin_inputs > 0 && (write || watch) {
+        anyhow::bail!("`-` (standard input) cannot be used with --write or --watch");
+    }
 
     let mut parser = tree_sitter::Parser::new();
     parser
         .set_language(&tree_sitter_wfl::language())
         .map_err(|e| anyhow::anyhow!("failed to load WFL grammar: {e}"))?;
 
+    if watch {
+        let mut trigger = watch::FsTrigger::new(&files, watch::DEBOUNCE)?;
+        watch::run(&mut trigger, || {
+            fmt_once(&mut parser, &files, write, check, &style).map(|_| ())
+        });
+        return Ok(());
+    }
+
+    if fmt_once(&mut parser, &files, write, check, &style)? && check {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Format every file once. Returns whether any file was unformatted or
+/// had a syntax error.
+fn fmt_once(
+    parser: &mut tree_sitter::Parser,
+    files: &[PathBuf],
+    write: bool,
+    check: bool,
+    style: &FmtStyle,
+) -> Result<bool> {
+    let color = std::io::stderr().is_terminal();
     let mut any_diff = false;
 
-    for file in &files {
-        let source = std::fs::read_to_string(file)
-            .map_err(|e| anyhow::anyhow!("reading {}: {e}", file.display()))?;
+    for file in files {
+        let source = read_source(file)?;
 
         // Parse with tree-sitter to validate syntax
         let tree = parser
@@ -45,7 +137,7 @@ pub fn run(files: Vec<PathBuf>, write: bool, check: bool) -> Result<()> {
             continue;
         }
 
-        let formatted = format_source(&source);
+        let formatted = format_source(&source, style);
 
         if check {
             if source != formatted {
@@ -71,16 +163,13 @@ pub fn run(files: Vec<PathBuf>, write: bool, check: bool) -> Result<()> {
         }
     }
 
-    if check && any_diff {
-        process::exit(1);
-    }
-
-    Ok(())
+    Ok(any_diff)
 }
 
 /// Format WFL source by normalizing indentation based on brace/paren nesting.
-fn format_source(source: &str) -> String {
-    let indent_str = "    ";
+fn format_source(source: &str, style: &FmtStyle) -> String {
+    let indent_str = style.indent_unit();
+    let eol = style.eol.as_str();
     let mut output = String::with_capacity(source.len());
     let mut indent: i32 = 0;
     let mut prev_blank = false;
@@ -91,7 +180,7 @@ fn format_source(source: &str) -> String {
         // Collapse multiple blank lines into one
         if trimmed.is_empty() {
             if !prev_blank && !output.is_empty() {
-                output.push('\n');
+                output.push_str(eol);
             }
             prev_blank = true;
             continue;
@@ -110,21 +199,22 @@ fn format_source(source: &str) -> String {
 
         // Write indented line (strip trailing whitespace)
         for _ in 0..this_indent {
-            output.push_str(indent_str);
+            output.push_str(&indent_str);
         }
         output.push_str(trimmed);
-        output.push('\n');
+        output.push_str(eol);
 
         // Update indent for next line
         indent += opens as i32 - closes as i32;
     }
 
     // Ensure exactly one trailing newline
-    while output.ends_with("\n\n") {
-        output.pop();
+    let double_eol = eol.repeat(2);
+    while output.ends_with(&double_eol) {
+        output.truncate(output.len() - eol.len());
     }
-    if !output.ends_with('\n') {
-        output.push('\n');
+    if !output.ends_with(eol) {
+        output.push_str(eol);
     }
 
     output
@@ -188,7 +278,7 @@ fail | count >= 3;
 entity(ip, fail.sip)
 }
 "#;
-        let formatted = format_source(input);
+        let formatted = format_source(input, &FmtStyle::default());
         // Check indentation of events block
         assert!(formatted.contains("    events {"));
         assert!(formatted.contains("        fail : auth_events"));
@@ -213,7 +303,7 @@ b = e.y
 )
 }
 "#;
-        let formatted = format_source(input);
+        let formatted = format_source(input, &FmtStyle::default());
         assert!(formatted.contains("    yield out ("));
         assert!(formatted.contains("        a = e.x,"));
         assert!(formatted.contains("    )"));
@@ -222,7 +312,7 @@ b = e.y
     #[test]
     fn format_preserves_comments() {
         let input = "# top comment\nrule r {\n# inner comment\nevents { e : w }\n}\n";
-        let formatted = format_source(input);
+        let formatted = format_source(input, &FmtStyle::default());
         assert!(formatted.contains("# top comment"));
         assert!(formatted.contains("    # inner comment"));
     }
@@ -230,7 +320,7 @@ b = e.y
     #[test]
     fn format_braces_in_string_ignored() {
         let input = "rule r {\nevents { e : w && x == \"{test}\" }\n}\n";
-        let formatted = format_source(input);
+        let formatted = format_source(input, &FmtStyle::default());
         // Braces inside string should not affect indentation
         assert!(formatted.contains("    events { e : w && x == \"{test}\" }"));
     }
@@ -238,7 +328,7 @@ b = e.y
     #[test]
     fn format_collapses_blank_lines() {
         let input = "use \"a.wfs\"\n\n\n\nrule r {\n}\n";
-        let formatted = format_source(input);
+        let formatted = format_source(input, &FmtStyle::default());
         assert!(!formatted.contains("\n\n\n"));
     }
 
@@ -258,8 +348,44 @@ rule brute_force {
     entity(ip, fail.sip)
 }
 "#;
-        let formatted = format_source(input);
-        let formatted2 = format_source(&formatted);
+        let formatted = format_source(input, &FmtStyle::default());
+        let formatted2 = format_source(&formatted, &FmtStyle::default());
         assert_eq!(formatted, formatted2, "formatting should be idempotent");
     }
+
+    const GOLDEN_SOURCE: &str = "rule r {\nevents { e : w }\nmatch<k:5m> {\non event {\ne | count >= 1;\n}\n} -> score(1.0)\n}\n";
+
+    #[test]
+    fn golden_two_space_indent() {
+        let style = FmtStyle {
+            indent: 2,
+            ..FmtStyle::default()
+        };
+        assert_eq!(
+            format_source(GOLDEN_SOURCE, &style),
+            "rule r {\n  events { e : w }\n  match<k:5m> {\n    on event {\n      e | count >= 1;\n    }\n  } -> score(1.0)\n}\n"
+        );
+    }
+
+    #[test]
+    fn golden_four_space_indent() {
+        assert_eq!(
+            format_source(GOLDEN_SOURCE, &FmtStyle::default()),
+            "rule r {\n    events { e : w }\n    match<k:5m> {\n        on event {\n            e | count >= 1;\n        }\n    } -> score(1.0)\n}\n"
+        );
+    }
+
+    #[test]
+    fn tabs_and_crlf() {
+        let style = FmtStyle {
+            use_tabs: true,
+            eol: Eol::Crlf,
+            ..FmtStyle::default()
+        };
+        let formatted = format_source("rule r {\n\n\nevents { e : w }\n}\n\n", &style);
+        assert_eq!(formatted, "rule r {\r\n\r\n\tevents { e : w }\r\n}\r\n");
+        // Re-formatting CRLF input with the same style is a no-op, so `-w`
+        // leaves already-styled files untouched.
+        assert_eq!(format_source(&formatted, &style), formatted);
+    }
 }
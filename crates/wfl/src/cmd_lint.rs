@@ -1,12 +1,17 @@
+use std::collections::HashMap;
 use std::io::{IsTerminal, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
+use serde_json::{Value, json};
 
-use wf_lang::{CheckError, Severity};
+use wf_lang::{CheckError, Severity, WflParseError};
 
-use wf_config::project::{load_schemas, load_wfl, parse_vars};
+use wf_config::project::{is_stdin, load_schemas, load_wfl, parse_vars, resolve_patterns};
+
+use crate::rule_filter::{diag_selected, ensure_rules_exist};
+use crate::watch;
 
 fn print_diag(diag: &CheckError, color: bool) {
     let (prefix, code) = match diag.severity {
@@ -28,46 +33,192 @@ fn print_diag(diag: &CheckError, color: bool) {
     }
 }
 
-pub fn run(file: PathBuf, schemas: Vec<String>, vars: Vec<String>) -> Result<()> {
+fn severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+/// JSON form of a semantic / lint diagnostic. These carry no source
+/// position, so `row` and `col` are `null`.
+fn json_diag(file: &Path, diag: &CheckError) -> Value {
+    json!({
+        "file": file.display().to_string(),
+        "row": Value::Null,
+        "col": Value::Null,
+        "severity": severity_str(diag.severity),
+        "message": diag.message,
+        "rule": diag.rule,
+        "test": diag.test,
+    })
+}
+
+/// JSON form of a syntax error, with 1-based `row` / `col`.
+fn json_parse_error(file: &Path, err: &WflParseError) -> Value {
+    json!({
+        "file": file.display().to_string(),
+        "row": err.line,
+        "col": err.column,
+        "severity": "error",
+        "message": err.message,
+        "rule": Value::Null,
+        "test": Value::Null,
+    })
+}
+
+/// Diagnostic counts from one lint pass, by severity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Findings {
+    errors: usize,
+    warnings: usize,
+}
+
+impl Findings {
+    fn of<'a>(diags: impl IntoIterator<Item = &'a CheckError>) -> Self {
+        diags.into_iter().fold(Findings::default(), |mut f, d| {
+            match d.severity {
+                Severity::Error => f.errors += 1,
+                Severity::Warning => f.warnings += 1,
+            }
+            f
+        })
+    }
+
+    /// Whether the pass should exit non-zero. Warnings only count with
+    /// `--fail-on-warning`.
+    fn fails(self, fail_on_warning: bool) -> bool {
+        self.errors > 0 || (fail_on_warning && self.warnings > 0)
+    }
+}
+
+/// Run error-level checks and lint checks, keeping only diagnostics that
+/// belong to the `--rule` selection (all of them when `rules` is empty).
+fn diagnostics(
+    wfl_file: &wf_lang::ast::WflFile,
+    schemas: &[wf_lang::WindowSchema],
+    rules: &[String],
+) -> Result<(Vec<CheckError>, Vec<CheckError>)> {
+    ensure_rules_exist(wfl_file, rules)?;
+    let mut errors = wf_lang::check_wfl(wfl_file, schemas);
+    let mut warnings = wf_lang::lint_wfl(wfl_file, schemas);
+    errors.retain(|d| diag_selected(wfl_file, d, rules));
+    warnings.retain(|d| diag_selected(wfl_file, d, rules));
+    Ok((errors, warnings))
+}
+
+/// Lint `source` and return the diagnostics as JSON values, plus their
+/// counts. Parse failures become a single error entry.
+fn lint_json(
+    file: &Path,
+    source: &str,
+    schemas: &[wf_lang::WindowSchema],
+    rules: &[String],
+) -> Result<(Vec<Value>, Findings)> {
+    let wfl_file = match wf_lang::parse_wfl(source) {
+        Ok(f) => f,
+        Err(e) => match e.downcast_ref::<WflParseError>() {
+            Some(pe) => {
+                let findings = Findings {
+                    errors: 1,
+                    warnings: 0,
+                };
+                return Ok((vec![json_parse_error(file, pe)], findings));
+            }
+            None => return Err(e),
+        },
+    };
+    let (errors, warnings) = diagnostics(&wfl_file, schemas, rules)?;
+    let findings = Findings::of(errors.iter().chain(warnings.iter()));
+    let diags = errors
+        .iter()
+        .chain(warnings.iter())
+        .map(|d| json_diag(file, d))
+        .collect();
+    Ok((diags, findings))
+}
+
+pub fn run(
+    file: PathBuf,
+    schemas: Vec<String>,
+    vars: Vec<String>,
+    format: String,
+    rules: Vec<String>,
+    watch: bool,
+    fail_on_warning: bool,
+) -> Result<()> {
+    let json_output = match format.as_str() {
+        "human" => false,
+        "json" => true,
+        other => bail!("unknown --format '{other}' (expected \"human\" or \"json\")"),
+    };
     let cwd = std::env::current_dir()?;
     let var_map = parse_vars(&vars)?;
+
+    if watch {
+        if is_stdin(&file) {
+            bail!("`-` (standard input) cannot be used with --watch");
+        }
+        // Schemas are re-read on every run, so watch them too.
+        let mut inputs = resolve_patterns(&schemas, &cwd).unwrap_or_default();
+        inputs.push(file.clone());
+        let mut trigger = watch::FsTrigger::new(&inputs, watch::DEBOUNCE)?;
+        watch::run(&mut trigger, || {
+            lint_once(&file, &schemas, &var_map, &cwd, json_output, &rules).map(|_| ())
+        });
+        return Ok(());
+    }
+
+    if lint_once(&file, &schemas, &var_map, &cwd, json_output, &rules)?.fails(fail_on_warning) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Lint `file` once and print the report. Returns the diagnostic counts.
+fn lint_once(
+    file: &Path,
+    schemas: &[String],
+    var_map: &HashMap<String, String>,
+    cwd: &Path,
+    json_output: bool,
+    rules: &[String],
+) -> Result<Findings> {
     let color = std::io::stderr().is_terminal();
 
     // Load schemas
-    let all_schemas = load_schemas(&schemas, &cwd)?;
+    let all_schemas = load_schemas(schemas, cwd)?;
 
     // Load and preprocess the .wfl file
-    let source = load_wfl(&file, &var_map)?;
+    let source = load_wfl(file, var_map)?;
 
-    // Parse
-    let wfl_file = wf_lang::parse_wfl(&source).map_err(|e| anyhow::anyhow!("parse error: {e}"))?;
-
-    // Run error-level checks
-    let errors = wf_lang::check_wfl(&wfl_file, &all_schemas);
+    if json_output {
+        let (diags, findings) = lint_json(file, &source, &all_schemas, rules)?;
+        println!("{}", serde_json::to_string_pretty(&diags)?);
+        return Ok(findings);
+    }
 
-    // Run lint-level checks
-    let warnings = wf_lang::lint_wfl(&wfl_file, &all_schemas);
+    // Parse
+    let wfl_file = wf_lang::parse_wfl(&source)?;
 
-    let total = errors.len() + warnings.len();
-    let mut has_errors = false;
+    // Run error-level and lint-level checks
+    let (errors, warnings) = diagnostics(&wfl_file, &all_schemas, rules)?;
+    let findings = Findings::of(errors.iter().chain(warnings.iter()));
 
     // Print all diagnostics
     for diag in errors.iter().chain(warnings.iter()) {
-        if diag.severity == Severity::Error {
-            has_errors = true;
-        }
         print_diag(diag, color);
     }
 
-    if total == 0 {
+    if findings == Findings::default() {
         if color {
             eprintln!("\x1b[1;32mNo issues found.\x1b[0m");
         } else {
             eprintln!("No issues found.");
         }
     } else {
-        let ec = errors.len();
-        let wc = warnings.len();
+        let ec = findings.errors;
+        let wc = findings.warnings;
         if color {
             let mut buf = String::new();
             buf.push_str("\n\x1b[1m");
@@ -88,9 +239,24 @@ pub fn run(file: PathBuf, schemas: Vec<String>, vars: Vec<String>) -> Result<()>
         }
     }
 
-    if has_errors {
-        process::exit(1);
-    }
+    Ok(findings)
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_reports_parse_error_position() {
+        let src = "rule r {\n    events { e : win }\n    match<:5m> { on event { e | count >= 1; } }\n}\n";
+        let (diags, findings) = lint_json(Path::new("r.wfl"), src, &[], &[]).unwrap();
+        assert!(findings.fails(false));
+        let text = serde_json::to_string(&diags).unwrap();
+        let parsed: Vec<Value> = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0]["file"], "r.wfl");
+        assert_eq!(parsed[0]["row"], 4);
+        assert_eq!(parsed[0]["col"], 1);
+        assert_eq!(parsed[0]["severity"], "error");
+    }
 }
@@ -7,9 +7,10 @@ use anyhow::Result;
 use wf_core::alert::OutputRecord;
 use wf_core::rule::{
     CepStateMachine, CloseReason, Event, RuleExecutor, StepResult, Value, WindowLookup,
+    bind_filter_passes,
 };
 use wf_lang::WindowSchema;
-use wf_lang::plan::RulePlan;
+use wf_lang::plan::{ExprPlan, RulePlan};
 
 const GREEN: &str = "\x1b[1;32m";
 const RED: &str = "\x1b[1;31m";
@@ -94,8 +95,7 @@ pub fn replay_events<R: BufRead>(
     reader: R,
     color: bool,
 ) -> Result<ReplayResult> {
-    let wfl_file =
-        wf_lang::parse_wfl(wfl_source).map_err(|e| anyhow::anyhow!("parse error: {e}"))?;
+    let wfl_file = wf_lang::parse_wfl(wfl_source)?;
     let plans = wf_lang::compile_wfl(&wfl_file, schemas)?;
 
     if plans.is_empty() {
@@ -140,6 +140,7 @@ struct ReplayEngine {
 struct ConsumerRoute {
     engine_idx: usize,
     bind_alias: String,
+    filter: Option<ExprPlan>,
 }
 
 /// Replay events against pre-compiled rule plans.
@@ -264,10 +265,11 @@ fn replay_with_plans<R: BufRead>(
                 engine.executor.execute_close_with_joins(close, &lookup)
             };
             match result {
-                Ok(Some(record)) => {
-                    handle_output_record(record, &mut queue, &mut alerts, &mut match_count);
+                Ok(records) => {
+                    for record in records {
+                        handle_output_record(record, &mut queue, &mut alerts, &mut match_count);
+                    }
                 }
-                Ok(None) => {}
                 Err(e) => {
                     if color {
                         eprintln!("{RED}ERROR{RESET}: execute_close failed: {}", e);
@@ -416,6 +418,7 @@ fn build_all_routes(plans: &[RulePlan]) -> HashMap<String, Vec<ConsumerRoute>> {
             routes.entry(route_key).or_default().push(ConsumerRoute {
                 engine_idx,
                 bind_alias: bind.alias.clone(),
+                filter: bind.filter.clone(),
             });
         }
     }
@@ -443,8 +446,17 @@ fn route_event_once(
     error_count: &mut u64,
     color: bool,
 ) {
-    let consumers = routes.get(route_key).cloned().unwrap_or_default();
+    let Some(consumers) = routes.get(route_key) else {
+        return;
+    };
     for consumer in consumers {
+        // Events rejected by the bind filter never reach the machine, as in
+        // the engine.
+        if let Some(filter) = &consumer.filter
+            && !bind_filter_passes(filter, event)
+        {
+            continue;
+        }
         let step = engines[consumer.engine_idx].machine.advance_with(
             &consumer.bind_alias,
             event,
@@ -455,8 +467,10 @@ fn route_event_once(
                 .executor
                 .execute_match_with_joins(&ctx, lookup)
             {
-                Ok(record) => {
-                    handle_output_record(record, queue, alerts, match_count);
+                Ok(records) => {
+                    for record in records {
+                        handle_output_record(record, queue, alerts, match_count);
+                    }
                 }
                 Err(e) => {
                     if color {
@@ -489,7 +503,7 @@ fn output_record_to_event(record: &OutputRecord) -> Event {
     let mut fields = HashMap::new();
     fields.insert(
         PIPE_EVENT_TIME_FIELD.to_string(),
-        Value::Number(record.event_time_nanos as f64),
+        Value::Int(record.event_time_nanos),
     );
     for (name, value) in &record.yield_fields {
         fields.insert(name.clone(), value.clone());
@@ -504,7 +518,9 @@ pub fn json_to_event(json: &serde_json::Value) -> Event {
         for (key, val) in map {
             let v = match val {
                 serde_json::Value::Number(n) => {
-                    if let Some(f) = n.as_f64() {
+                    if let Some(i) = n.as_i64() {
+                        Value::Int(i)
+                    } else if let Some(f) = n.as_f64() {
                         Value::Number(f)
                     } else {
                         continue;
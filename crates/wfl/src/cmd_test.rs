@@ -36,7 +36,7 @@ pub fn run(
     let source = load_wfl(&file, &var_map)?;
 
     // Parse
-    let wfl_file = wf_lang::parse_wfl(&source).map_err(|e| anyhow::anyhow!("parse error: {e}"))?;
+    let wfl_file = wf_lang::parse_wfl(&source)?;
 
     // Compile rules into plans
     let plans = wf_lang::compile_wfl(&wfl_file, &all_schemas)?;
@@ -1,11 +1,14 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 
+mod cmd_dump_plan;
 mod cmd_explain;
 mod cmd_fmt;
 mod cmd_lint;
+mod rule_filter;
+mod watch;
 
 #[derive(Parser)]
 #[command(name = "wfl", about = "WarpFusion project tools for rule developers")]
@@ -18,7 +21,34 @@ struct Cli {
 enum Commands {
     /// Explain compiled rules in human-readable form
     Explain {
-        /// Path to the .wfl rule file
+        /// Path to the .wfl rule file (`-` reads standard input)
+        file: PathBuf,
+
+        /// Schema file glob patterns (e.g. "schemas/*.wfs")
+        #[arg(short, long, default_value = "schemas/*.wfs")]
+        schemas: Vec<String>,
+
+        /// Variable substitutions in KEY=VALUE format
+        #[arg(long)]
+        var: Vec<String>,
+
+        /// Output format: "text" or "dot" (Graphviz) (default: text)
+        #[arg(long, default_value = "text")]
+        format: String,
+
+        /// Group pipeline stages under the authored rule and label the
+        /// internal stage windows as "stage N output"
+        #[arg(long)]
+        as_authored: bool,
+
+        /// Only explain the named rule (repeatable)
+        #[arg(long = "rule", value_name = "NAME")]
+        rules: Vec<String>,
+    },
+
+    /// Print the compiled rule plans in a machine-readable format
+    DumpPlan {
+        /// Path to the .wfl rule file (`-` reads standard input)
         file: PathBuf,
 
         /// Schema file glob patterns (e.g. "schemas/*.wfs")
@@ -28,11 +58,19 @@ enum Commands {
         /// Variable substitutions in KEY=VALUE format
         #[arg(long)]
         var: Vec<String>,
+
+        /// Output format: "json" (default: json)
+        #[arg(long, default_value = "json")]
+        format: String,
+
+        /// Only dump the named rule (repeatable)
+        #[arg(long = "rule", value_name = "NAME")]
+        rules: Vec<String>,
     },
 
     /// Run lint checks on a .wfl rule file
     Lint {
-        /// Path to the .wfl rule file
+        /// Path to the .wfl rule file (`-` reads standard input)
         file: PathBuf,
 
         /// Schema file glob patterns (e.g. "schemas/*.wfs")
@@ -42,11 +80,27 @@ enum Commands {
         /// Variable substitutions in KEY=VALUE format
         #[arg(long)]
         var: Vec<String>,
+
+        /// Output format: "human" or "json" (default: human)
+        #[arg(long, default_value = "human")]
+        format: String,
+
+        /// Only report diagnostics for the named rule (repeatable)
+        #[arg(long = "rule", value_name = "NAME")]
+        rules: Vec<String>,
+
+        /// Re-run whenever the rule file or a schema file changes
+        #[arg(long)]
+        watch: bool,
+
+        /// Exit non-zero when any warning is reported, not just errors
+        #[arg(long)]
+        fail_on_warning: bool,
     },
 
     /// Format .wfl rule files
     Fmt {
-        /// Input .wfl files to format
+        /// Input .wfl files to format (`-` reads standard input and prints to stdout)
         files: Vec<PathBuf>,
 
         /// Write formatted output back to the files (in-place)
@@ -56,6 +110,22 @@ enum Commands {
         /// Check if files are already formatted (exit 1 if not)
         #[arg(long)]
         check: bool,
+
+        /// Spaces per indentation level
+        #[arg(long, default_value_t = 4)]
+        indent: usize,
+
+        /// Indent with tabs instead of spaces (overrides --indent)
+        #[arg(long)]
+        use_tabs: bool,
+
+        /// Line ending: "lf" or "crlf"
+        #[arg(long, default_value = "lf")]
+        eol: String,
+
+        /// Re-run whenever one of the files changes
+        #[arg(long)]
+        watch: bool,
     },
 
     /// Replay NDJSON data through a compiled rule for offline debugging
@@ -135,26 +205,67 @@ enum Commands {
         #[arg(long)]
         runs: Option<usize>,
     },
+
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Target shell: bash, zsh, fish, elvish or powershell
+        shell: clap_complete::Shell,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Explain { file, schemas, var } => {
-            cmd_explain::run(file, schemas, var)?;
+        Commands::Explain {
+            file,
+            schemas,
+            var,
+            format,
+            as_authored,
+            rules,
+        } => {
+            cmd_explain::run(file, schemas, var, format, as_authored, rules)?;
         }
 
-        Commands::Lint { file, schemas, var } => {
-            cmd_lint::run(file, schemas, var)?;
+        Commands::DumpPlan {
+            file,
+            schemas,
+            var,
+            format,
+            rules,
+        } => {
+            cmd_dump_plan::run(file, schemas, var, format, rules)?;
+        }
+
+        Commands::Lint {
+            file,
+            schemas,
+            var,
+            format,
+            rules,
+            watch,
+            fail_on_warning,
+        } => {
+            cmd_lint::run(file, schemas, var, format, rules, watch, fail_on_warning)?;
         }
 
         Commands::Fmt {
             files,
             write,
             check,
+            indent,
+            use_tabs,
+            eol,
+            watch,
         } => {
-            cmd_fmt::run(files, write, check)?;
+            let style = cmd_fmt::FmtStyle {
+                indent,
+                use_tabs,
+                eol: cmd_fmt::Eol::parse(&eol)?,
+            };
+            cmd_fmt::run(files, write, check, style, watch)?;
         }
 
         Commands::Replay {
@@ -199,6 +310,10 @@ fn main() -> Result<()> {
         } => {
             wfl::cmd_test::run(file, schemas, var, shuffle, runs)?;
         }
+
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "wfl", &mut std::io::stdout());
+        }
     }
 
     Ok(())
@@ -0,0 +1,45 @@
+use anyhow::{Result, bail};
+
+use wf_lang::CheckError;
+use wf_lang::ast::WflFile;
+
+/// Error out if any `--rule` name is not a rule declared in `file`.
+pub(crate) fn ensure_rules_exist(file: &WflFile, selected: &[String]) -> Result<()> {
+    let missing: Vec<&str> = selected
+        .iter()
+        .filter(|name| !file.rules.iter().any(|r| &r.name == *name))
+        .map(String::as_str)
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let available: Vec<&str> = file.rules.iter().map(|r| r.name.as_str()).collect();
+    bail!(
+        "unknown rule(s) passed to --rule: {} (rules in file: {})",
+        missing.join(", "),
+        available.join(", ")
+    );
+}
+
+/// Whether rule `name` passes the `--rule` filter (an empty filter keeps
+/// every rule).
+pub(crate) fn is_selected(name: &str, selected: &[String]) -> bool {
+    selected.is_empty() || selected.iter().any(|s| s == name)
+}
+
+/// Whether a diagnostic belongs to a selected rule, either directly or
+/// through a contract test that targets it. Diagnostics tied to neither
+/// are kept only when no filter is given.
+pub(crate) fn diag_selected(file: &WflFile, diag: &CheckError, selected: &[String]) -> bool {
+    if selected.is_empty() {
+        return true;
+    }
+    match (&diag.rule, &diag.test) {
+        (Some(rule), _) => is_selected(rule, selected),
+        (None, Some(test)) => file
+            .tests
+            .iter()
+            .any(|t| &t.name == test && is_selected(&t.rule_name, selected)),
+        (None, None) => false,
+    }
+}
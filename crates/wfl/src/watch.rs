@@ -0,0 +1,192 @@
+//! `--watch` mode for `wfl lint` / `wfl fmt`: re-run a check whenever one
+//! of its input files changes.
+//!
+//! Each input's parent directory is watched rather than the file itself, so
+//! a deleted file, or one that an editor saves by writing a temporary file
+//! and renaming it, keeps being watched.
+
+use std::collections::HashSet;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Quiet period that must follow the last change before the check re-runs.
+/// Editors often save in several steps (truncate, write, rename).
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Source of change notifications for [`run`].
+pub trait Trigger {
+    /// Block until the watched inputs change. Returns `false` once no more
+    /// changes can arrive and watching should stop.
+    fn wait(&mut self) -> bool;
+}
+
+/// [`Trigger`] backed by the platform filesystem notifier.
+pub struct FsTrigger {
+    // Dropping the watcher stops notifications, so it lives as long as `rx`.
+    _watcher: RecommendedWatcher,
+    rx: Receiver<()>,
+    debounce: Duration,
+}
+
+impl FsTrigger {
+    /// Watch `files` for modification, creation, removal and renames.
+    pub fn new(files: &[PathBuf], debounce: Duration) -> Result<Self> {
+        let mut dirs = HashSet::new();
+        let mut targets = HashSet::new();
+        for file in files {
+            let (dir, name) = split_path(file)?;
+            targets.insert(dir.join(name));
+            dirs.insert(dir);
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else { return };
+            // Our own reads of the inputs must not re-trigger the check.
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+            if event.paths.iter().any(|p| targets.contains(p)) {
+                let _ = tx.send(());
+            }
+        })
+        .context("starting file watcher")?;
+        for dir in &dirs {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("watching {}", dir.display()))?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            rx,
+            debounce,
+        })
+    }
+}
+
+impl Trigger for FsTrigger {
+    fn wait(&mut self) -> bool {
+        if self.rx.recv().is_err() {
+            return false;
+        }
+        // Fold a burst of events into one re-run.
+        loop {
+            match self.rx.recv_timeout(self.debounce) {
+                Ok(()) => {}
+                Err(RecvTimeoutError::Timeout) => return true,
+                Err(RecvTimeoutError::Disconnected) => return false,
+            }
+        }
+    }
+}
+
+/// Canonical parent directory and file name of `file`. The file itself may
+/// not exist yet; its directory must.
+fn split_path(file: &Path) -> Result<(PathBuf, PathBuf)> {
+    let name = file
+        .file_name()
+        .with_context(|| format!("cannot watch {}: not a file path", file.display()))?;
+    let parent = match file.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let dir = std::fs::canonicalize(parent)
+        .with_context(|| format!("cannot watch {}", parent.display()))?;
+    Ok((dir, PathBuf::from(name)))
+}
+
+/// Run `check` now and again after every change reported by `trigger`.
+///
+/// On a terminal the screen is cleared before each run so only the current
+/// diagnostics are visible. Errors from `check` (e.g. a file that was just
+/// deleted) are printed and watching continues.
+pub fn run(trigger: &mut impl Trigger, mut check: impl FnMut() -> Result<()>) {
+    let clear = std::io::stderr().is_terminal();
+    loop {
+        if clear {
+            eprint!("\x1b[2J\x1b[H");
+        }
+        if let Err(e) = check() {
+            eprintln!("error: {e:#}");
+        }
+        eprintln!("\nwatching for changes (Ctrl-C to stop)...");
+        if !trigger.wait() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fires a fixed number of times, then reports the source closed.
+    struct ManualTrigger {
+        remaining: usize,
+    }
+
+    impl Trigger for ManualTrigger {
+        fn wait(&mut self) -> bool {
+            if self.remaining == 0 {
+                return false;
+            }
+            self.remaining -= 1;
+            true
+        }
+    }
+
+    #[test]
+    fn each_change_reruns_the_check() {
+        let mut trigger = ManualTrigger { remaining: 2 };
+        let mut runs = 0;
+        run(&mut trigger, || {
+            runs += 1;
+            Ok(())
+        });
+        // Initial run plus one per change.
+        assert_eq!(runs, 3);
+    }
+
+    #[test]
+    fn check_errors_do_not_stop_watching() {
+        let mut trigger = ManualTrigger { remaining: 1 };
+        let mut runs = 0;
+        run(&mut trigger, || {
+            runs += 1;
+            anyhow::bail!("reading r.wfl: No such file or directory")
+        });
+        assert_eq!(runs, 2);
+    }
+
+    #[test]
+    fn fs_trigger_fires_on_write_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("r.wfl");
+        std::fs::write(&file, "rule r {}\n").unwrap();
+        let other = dir.path().join("unrelated.txt");
+
+        let mut trigger = FsTrigger::new(std::slice::from_ref(&file), DEBOUNCE).unwrap();
+        let (done_tx, done_rx) = mpsc::channel();
+        let waiter = std::thread::spawn(move || {
+            for _ in 0..2 {
+                done_tx.send(trigger.wait()).unwrap();
+            }
+        });
+
+        std::fs::write(&other, "ignored").unwrap();
+        std::fs::write(&file, "rule r { }\n").unwrap();
+        let changed = done_rx.recv_timeout(Duration::from_secs(5));
+        assert_eq!(changed, Ok(true));
+
+        std::fs::remove_file(&file).unwrap();
+        let deleted = done_rx.recv_timeout(Duration::from_secs(5));
+        assert_eq!(deleted, Ok(true));
+        waiter.join().unwrap();
+    }
+}
@@ -0,0 +1,26 @@
+//! `wfl completions <shell>` prints a completion script for every
+//! top-level subcommand.
+
+use std::process::Command;
+
+#[test]
+fn bash_completions_list_subcommands() {
+    let out = Command::new(env!("CARGO_BIN_EXE_wfl"))
+        .args(["completions", "bash"])
+        .output()
+        .expect("failed to run wfl");
+    assert!(out.status.success());
+    let script = String::from_utf8(out.stdout).unwrap();
+    assert!(!script.is_empty());
+    for sub in [
+        "explain",
+        "dump-plan",
+        "lint",
+        "fmt",
+        "replay",
+        "replay-verify",
+        "test",
+    ] {
+        assert!(script.contains(sub), "missing {sub} in:\n{script}");
+    }
+}
@@ -0,0 +1,43 @@
+//! `wfl lint` exits non-zero on errors, and on warnings too with
+//! `--fail-on-warning`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// `examples/count/rules/brute_force.wfl` has no `limits` block: one
+/// warning, no errors.
+fn lint(extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wfl"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/count"))
+        .args([
+            "lint",
+            "rules/brute_force.wfl",
+            "--schemas",
+            "schemas/*.wfs",
+        ])
+        .args(extra)
+        .output()
+        .expect("failed to run wfl")
+}
+
+#[test]
+fn warnings_only_pass_by_default() {
+    let out = lint(&[]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{stderr}");
+    assert!(stderr.contains("0 error(s), 1 warning(s)"), "{stderr}");
+}
+
+#[test]
+fn fail_on_warning_flips_exit_code() {
+    let out = lint(&["--fail-on-warning"]);
+    assert_eq!(out.status.code(), Some(1));
+
+    let out = lint(&["--fail-on-warning", "--format", "json"]);
+    assert_eq!(out.status.code(), Some(1));
+    let diags: Vec<serde_json::Value> = serde_json::from_slice(&out.stdout).unwrap();
+    assert!(
+        diags.iter().all(|d| d["severity"] == "warning"),
+        "{diags:?}"
+    );
+}
@@ -104,6 +104,23 @@ fn replay_below_threshold_no_match() {
     assert!(result.alerts.is_empty());
 }
 
+#[test]
+fn replay_applies_bind_filter() {
+    let schemas = vec![make_auth_events_schema(), make_security_alerts_schema()];
+    let filtered = WFL_RULE.replace(
+        "e : auth_events }",
+        "e : auth_events && action == \"failed\" }",
+    );
+    // Five events would reach the threshold; the filter rejects one.
+    let ndjson = make_ndjson_events(5).replacen("\"failed\"", "\"success\"", 1);
+    let reader = BufReader::new(ndjson.as_bytes());
+
+    let result = replay_events(&filtered, &schemas, reader, false).expect("replay should succeed");
+    assert_eq!(result.event_count, 5);
+    assert_eq!(result.match_count, 0);
+    assert!(result.alerts.is_empty());
+}
+
 // ===========================================================================
 // EOF close_all(Eos) with on_close steps
 // ===========================================================================
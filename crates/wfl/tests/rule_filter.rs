@@ -0,0 +1,97 @@
+//! `--rule NAME` on `wfl explain` / `wfl lint` restricts output to the
+//! selected rules and rejects names that are not in the file.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const RULES: &str = r#"
+rule quiet {
+  events { fail : auth_events && action == "failed" }
+  match<sip:5m> { on event { fail | count >= 3; } } -> score(70.0)
+  entity(ip, fail.sip)
+  yield security_alerts (sip = fail.sip, fail_count = count(fail), message = "quiet")
+}
+
+rule noisy {
+  events { fail : auth_events && action == "failed" }
+  match<sip:5m> { on event { fail | count >= 3; } } -> score(0.0)
+  entity(ip, fail.sip)
+  yield security_alerts (sip = fail.sip, fail_count = count(fail), message = "noisy")
+}
+"#;
+
+fn count_example() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/count")
+}
+
+fn wfl(args: &[&str], file: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wfl"))
+        .current_dir(count_example())
+        .arg(args[0])
+        .arg(file)
+        .args(["--schemas", "schemas/*.wfs"])
+        .args(&args[1..])
+        .output()
+        .expect("failed to run wfl")
+}
+
+fn rules_file(dir: &tempfile::TempDir) -> PathBuf {
+    let path = dir.path().join("two_rules.wfl");
+    std::fs::write(&path, RULES).unwrap();
+    path
+}
+
+#[test]
+fn explain_shows_only_selected_rule() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = rules_file(&dir);
+
+    let out = wfl(&["explain", "--rule", "quiet"], &file);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(stdout.contains("Rule: quiet"), "{stdout}");
+    assert!(!stdout.contains("noisy"), "{stdout}");
+
+    let all = wfl(&["explain"], &file);
+    let stdout = String::from_utf8_lossy(&all.stdout);
+    assert!(stdout.contains("Rule: quiet") && stdout.contains("Rule: noisy"));
+}
+
+#[test]
+fn lint_reports_only_selected_rule() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = rules_file(&dir);
+
+    let out = wfl(&["lint", "--rule", "quiet"], &file);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{stderr}");
+    assert!(stderr.contains("rule `quiet`"), "{stderr}");
+    assert!(!stderr.contains("noisy"), "{stderr}");
+
+    let out = wfl(&["lint", "--rule", "noisy", "--format", "json"], &file);
+    let diags: Vec<serde_json::Value> = serde_json::from_slice(&out.stdout).unwrap();
+    assert!(!diags.is_empty());
+    assert!(diags.iter().all(|d| d["rule"] == "noisy"), "{diags:?}");
+}
+
+#[test]
+fn unknown_rule_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = rules_file(&dir);
+
+    for cmd in ["explain", "lint"] {
+        let out = wfl(&[cmd, "--rule", "quiet", "--rule", "missing"], &file);
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(!out.status.success(), "{cmd}: {stderr}");
+        assert!(
+            stderr.contains(
+                "unknown rule(s) passed to --rule: missing (rules in file: quiet, noisy)"
+            ),
+            "{cmd}: {stderr}"
+        );
+    }
+}
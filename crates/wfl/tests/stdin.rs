@@ -0,0 +1,70 @@
+//! `-` as the rule path reads the source from standard input.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+const RULE: &str = r#"
+rule brute {
+  events { fail : auth_events && action == "failed" }
+  match<sip:5m> { on event { fail | count >= 3; } } -> score(70.0)
+  entity(ip, fail.sip)
+  yield security_alerts (sip = fail.sip, fail_count = count(fail), message = "brute")
+}
+"#;
+
+fn count_example() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/count")
+}
+
+fn wfl_stdin(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wfl"))
+        .current_dir(count_example())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run wfl");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn lint_reads_rule_from_stdin() {
+    let out = wfl_stdin(&["lint", "-", "--schemas", "schemas/*.wfs"], RULE);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{stderr}");
+    assert!(stderr.contains("rule `brute`"), "{stderr}");
+
+    let broken = RULE.replace("fail.sip)\n  yield", "fail.nope)\n  yield");
+    let out = wfl_stdin(&["lint", "-", "--schemas", "schemas/*.wfs"], &broken);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(!out.status.success());
+    assert!(stderr.contains("nope"), "{stderr}");
+}
+
+#[test]
+fn explain_reads_rule_from_stdin() {
+    let out = wfl_stdin(&["explain", "-", "--schemas", "schemas/*.wfs"], RULE);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(stdout.contains("Rule: brute"), "{stdout}");
+}
+
+#[test]
+fn fmt_rejects_writing_back_to_stdin() {
+    let out = wfl_stdin(&["fmt", "-", "--write"], RULE);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(!out.status.success());
+    assert!(stderr.contains("cannot be used with --write"), "{stderr}");
+}
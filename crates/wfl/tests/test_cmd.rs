@@ -0,0 +1,58 @@
+//! `wfl test` end to end: contract blocks in a rule file are executed and
+//! reported per test, with a non-zero exit code when any of them fails.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn count_example() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/count")
+}
+
+fn wfl_test(file: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wfl"))
+        .current_dir(count_example())
+        .arg("test")
+        .arg(file)
+        .args(["--schemas", "schemas/*.wfs"])
+        .output()
+        .expect("failed to run wfl")
+}
+
+#[test]
+fn passing_contracts_exit_zero() {
+    let out = wfl_test(&count_example().join("rules/brute_force.wfl"));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{stderr}");
+    assert!(
+        stderr.contains("PASS  close_hit (brute_force_then_scan)"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("2 tests: 2 passed, 0 failed"), "{stderr}");
+}
+
+#[test]
+fn failing_contract_is_reported_and_exits_non_zero() {
+    let dir = tempfile::tempdir().unwrap();
+    // below_threshold feeds two failures; expecting a hit makes it fail.
+    let source = std::fs::read_to_string(count_example().join("rules/brute_force.wfl")).unwrap();
+    let (head, tail) = source.split_once("test below_threshold").unwrap();
+    let broken = format!(
+        "{head}test below_threshold{}",
+        tail.replace("hits == 0;", "hits == 1;")
+    );
+    let file = dir.path().join("brute_force.wfl");
+    std::fs::write(&file, broken).unwrap();
+
+    let out = wfl_test(&file);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert_eq!(out.status.code(), Some(1), "{stderr}");
+    assert!(
+        stderr.contains("PASS  close_hit (brute_force_then_scan)"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("FAIL  below_threshold (brute_force_then_scan)"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("2 tests: 1 passed, 1 failed"), "{stderr}");
+}